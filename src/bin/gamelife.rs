@@ -0,0 +1,18 @@
+//! gamelife 命令行工具
+
+use std::process;
+
+use gameboy_emulator::i18n::{trf, Msg};
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL_ALLOCATOR: gameboy_emulator::util::alloc::CountingAllocator = gameboy_emulator::util::alloc::CountingAllocator;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if let Err(e) = gameboy_emulator::cli::run(&args) {
+        eprintln!("{}", trf(Msg::ErrorPrefix, &[&e]));
+        process::exit(1);
+    }
+}
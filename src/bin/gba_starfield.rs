@@ -0,0 +1,261 @@
+//! GBA仿射背景演示：旋转的3D星空
+//!
+//! 用 `GbaRomBuilder` 生成一个带调色板、瓦片和图块表的GBA ROM，
+//! 在Mode 2下使用两个仿射背景层：地平线以上是绕屏幕中心旋转的星空(BG3)，
+//! 地平线以下是逐扫描线设置仿射参数得到的透视棋盘地面(BG2，类似Mode 7)。
+//! 逐行修改寄存器本应由HBlank中断完成，这里由宿主程序在每条扫描线前写入。
+
+use gameboy_emulator::gba::rom::{ENTRY_OFFSET, ROM_BASE};
+use gameboy_emulator::gba::{AffineParams, GBASystem, GbaRomBuilder, SCREEN_HEIGHT, SCREEN_WIDTH};
+use std::f64::consts::TAU;
+
+/// ROM中各资源的偏移
+const PALETTE_OFFSET: u32 = 0x1000;
+const FLOOR_TILES_OFFSET: u32 = 0x1200;
+const FLOOR_MAP_OFFSET: u32 = 0x1400;
+const STAR_TILES_OFFSET: u32 = 0x1800;
+const STAR_MAP_OFFSET: u32 = 0x2000;
+
+/// BG2: 128x128地面，字符基址0，屏幕基址16，环绕
+const FLOOR_BGCNT: u16 = 0x2000 | (16 << 8);
+/// BG3: 256x256星空，字符基址1，屏幕基址17，环绕
+const STAR_BGCNT: u16 = 0x4000 | 0x2000 | (17 << 8) | (1 << 2);
+const FLOOR_SIZE: usize = 128;
+const STAR_SIZE: usize = 256;
+const STAR_TILE_COUNT: usize = 6;
+
+/// Mode 2，分别只打开BG2或BG3
+const DISPCNT_FLOOR: u16 = 0x0002 | 0x0400;
+const DISPCNT_SKY: u16 = 0x0002 | 0x0800;
+
+/// 地平线所在扫描线
+pub const HORIZON: usize = 64;
+/// 摄像机高度和焦距（像素）
+const CAMERA_HEIGHT: f64 = 24.0;
+const FOCAL_LENGTH: f64 = 160.0;
+/// 旋转一整圈所需的帧数
+const FRAMES_PER_TURN: u32 = 600;
+
+/// BGR555颜色
+fn rgb(r: u8, g: u8, b: u8) -> u16 {
+    (r as u16 >> 3) | ((g as u16 >> 3) << 5) | ((b as u16 >> 3) << 10)
+}
+
+/// 确定性的xorshift随机数，保证每次生成的ROM相同
+fn xorshift(state: &mut u32) -> u32 {
+    *state ^= *state << 13;
+    *state ^= *state >> 17;
+    *state ^= *state << 5;
+    *state
+}
+
+/// 调色板：0背景黑，1-4星星由暗到亮，16/17地面棋盘格
+fn palette() -> Vec<u8> {
+    let mut colors = [0u16; 256];
+    colors[1] = rgb(0x40, 0x40, 0x70);
+    colors[2] = rgb(0x80, 0x80, 0xB0);
+    colors[3] = rgb(0xC0, 0xC0, 0xF0);
+    colors[4] = rgb(0xFF, 0xFF, 0xFF);
+    colors[16] = rgb(0x20, 0x10, 0x60);
+    colors[17] = rgb(0xE0, 0x40, 0xA0);
+    colors.iter().flat_map(|c| c.to_le_bytes()).collect()
+}
+
+/// 8bpp瓦片：地面两块纯色瓦片
+fn floor_tiles() -> Vec<u8> {
+    [16u8, 17].iter().flat_map(|&color| [color; 64]).collect()
+}
+
+/// 地面图块表：每2x2个瓦片为一格的棋盘
+fn floor_map() -> Vec<u8> {
+    let columns = FLOOR_SIZE / 8;
+    (0..columns * columns)
+        .map(|i| (((i % columns) / 2 + (i / columns) / 2) % 2) as u8)
+        .collect()
+}
+
+/// 星星瓦片：0空白，1-4不同亮度的单点，5亮星十字
+fn star_tiles() -> Vec<u8> {
+    let mut tiles = vec![0u8; STAR_TILE_COUNT * 64];
+    for brightness in 1..=4u8 {
+        let position = (brightness as usize * 19) % 64;
+        tiles[brightness as usize * 64 + position] = brightness;
+    }
+    let cross = 5 * 64;
+    for i in 2..7 {
+        tiles[cross + 4 * 8 + i] = 3;
+        tiles[cross + i * 8 + 4] = 3;
+    }
+    tiles[cross + 4 * 8 + 4] = 4;
+    tiles
+}
+
+/// 星空图块表：约五分之一的格子放一颗星
+fn star_map() -> Vec<u8> {
+    let mut seed = 0x2545_F491;
+    (0..(STAR_SIZE / 8) * (STAR_SIZE / 8))
+        .map(|_| match xorshift(&mut seed) % 20 {
+            0 => 5,
+            value @ 1..=4 => value as u8,
+            _ => 0,
+        })
+        .collect()
+}
+
+/// 生成演示ROM
+pub fn build_rom() -> Result<Vec<u8>, String> {
+    // 入口处是 `B .` 死循环，画面完全由显示寄存器驱动
+    GbaRomBuilder::new("STARFIELD")
+        .game_code("ASTF")
+        .section(ENTRY_OFFSET, &0xEAFF_FFFEu32.to_le_bytes())
+        .section(PALETTE_OFFSET, &palette())
+        .section(FLOOR_TILES_OFFSET, &floor_tiles())
+        .section(FLOOR_MAP_OFFSET, &floor_map())
+        .section(STAR_TILES_OFFSET, &star_tiles())
+        .section(STAR_MAP_OFFSET, &star_map())
+        .build()
+}
+
+/// 把ROM中的资源复制到调色板和VRAM，并设置背景控制寄存器
+pub fn install_assets(gba: &mut GBASystem) -> Result<(), String> {
+    let copies = [
+        (PALETTE_OFFSET, 0x0500_0000, 512),
+        (FLOOR_TILES_OFFSET, 0x0600_0000, 2 * 64),
+        (FLOOR_MAP_OFFSET, 0x0600_8000, (FLOOR_SIZE / 8) * (FLOOR_SIZE / 8)),
+        (STAR_TILES_OFFSET, 0x0600_4000, STAR_TILE_COUNT * 64),
+        (STAR_MAP_OFFSET, 0x0600_8800, (STAR_SIZE / 8) * (STAR_SIZE / 8)),
+    ];
+
+    let memory = gba.memory_mut();
+    for (source, destination, len) in copies {
+        for i in 0..len as u32 {
+            let byte = memory.read_8(ROM_BASE + source + i)?;
+            memory.write_8(destination + i, byte)?;
+        }
+    }
+
+    let gpu = gba.gpu_mut();
+    gpu.bgcnt[2] = FLOOR_BGCNT;
+    gpu.bgcnt[3] = STAR_BGCNT;
+    Ok(())
+}
+
+/// 星空扫描线的仿射参数：绕屏幕中心旋转，越靠近地平线放大越多
+pub fn sky_params(angle: f64, line: usize) -> AffineParams {
+    let scale = 1.0 + line as f64 / HORIZON as f64;
+    let center = (STAR_SIZE as f64 / 2.0, STAR_SIZE as f64 / 2.0);
+    AffineParams::rotate_scale(angle, scale, center, (SCREEN_WIDTH as f64 / 2.0, HORIZON as f64 / 2.0))
+}
+
+/// 地面扫描线的仿射参数：按深度缩放并沿摄像机朝向前进
+pub fn floor_params(angle: f64, distance: f64, line: usize) -> AffineParams {
+    let depth = CAMERA_HEIGHT * FOCAL_LENGTH / (line + 1 - HORIZON) as f64;
+    let step = depth / FOCAL_LENGTH;
+    let (sin, cos) = angle.sin_cos();
+    let forward = (cos, sin);
+    let right = (-sin, cos);
+    let half_width = SCREEN_WIDTH as f64 / 2.0;
+
+    // pb/pd为0：每条线单独设置参考点，纵向坐标不参与计算
+    let x = forward.0 * (distance + depth) - right.0 * half_width * step;
+    let y = forward.1 * (distance + depth) - right.1 * half_width * step;
+    AffineParams::from_f64([right.0 * step, 0.0, right.1 * step, 0.0], (x, y))
+}
+
+/// 渲染第 `frame` 帧，返回帧缓冲区 (BGR555)
+pub fn render_frame(gba: &mut GBASystem, frame: u32) -> Result<Vec<u16>, String> {
+    let angle = TAU * (frame % FRAMES_PER_TURN) as f64 / FRAMES_PER_TURN as f64;
+    let distance = frame as f64 * 2.0;
+
+    for line in 0..SCREEN_HEIGHT {
+        let gpu = gba.gpu_mut();
+        gpu.current_scanline = line as u16;
+        if line < HORIZON {
+            gpu.dispcnt = DISPCNT_SKY;
+            gpu.affine[1] = sky_params(angle, line);
+        } else {
+            gpu.dispcnt = DISPCNT_FLOOR;
+            gpu.affine[0] = floor_params(angle, distance, line);
+        }
+        gba.render_scanline()?;
+    }
+    Ok(gba.gpu.framebuffer.clone())
+}
+
+/// 把帧缓冲区转换成ASCII预览（每4x8像素一个字符）
+pub fn ascii_preview(framebuffer: &[u16]) -> String {
+    let shades = [' ', '.', ':', '*', '#'];
+    let mut output = String::new();
+    for row in (0..SCREEN_HEIGHT).step_by(8) {
+        for column in (0..SCREEN_WIDTH).step_by(4) {
+            let color = framebuffer[row * SCREEN_WIDTH + column];
+            let luma = (color & 0x1F) + ((color >> 5) & 0x1F) + ((color >> 10) & 0x1F);
+            output.push(shades[(luma as usize * (shades.len() - 1)) / 93]);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// 把帧缓冲区写成PPM图片
+pub fn write_ppm(path: &str, framebuffer: &[u16]) -> Result<(), String> {
+    let mut data = format!("P6\n{} {}\n255\n", SCREEN_WIDTH, SCREEN_HEIGHT).into_bytes();
+    for &color in framebuffer {
+        for shift in [0, 5, 10] {
+            data.push((((color >> shift) & 0x1F) << 3) as u8);
+        }
+    }
+    std::fs::write(path, data).map_err(|e| format!("无法写入 {}: {}", path, e))
+}
+
+fn main() -> Result<(), String> {
+    println!("🌌 GBA仿射背景演示：旋转星空");
+    println!("=====================================");
+
+    let rom = build_rom()?;
+    println!("📦 生成ROM: {} 字节", rom.len());
+
+    let mut gba = GBASystem::new();
+    gba.load_rom(rom)?;
+    install_assets(&mut gba)?;
+
+    let mut framebuffer = Vec::new();
+    for frame in (0..FRAMES_PER_TURN).step_by(FRAMES_PER_TURN as usize / 4) {
+        framebuffer = render_frame(&mut gba, frame)?;
+        println!("🎞️  第 {} 帧", frame);
+        println!("{}", ascii_preview(&framebuffer));
+    }
+
+    if let Some(path) = std::env::args().nth(1) {
+        write_ppm(&path, &framebuffer)?;
+        println!("💾 已保存最后一帧: {}", path);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gameboy_emulator::gba::rom::header_checksum;
+
+    #[test]
+    fn test_starfield_renders_sky_and_floor() {
+        let rom = build_rom().unwrap();
+        assert_eq!(rom[0xBD], header_checksum(&rom));
+
+        let mut gba = GBASystem::new();
+        gba.load_rom(rom).unwrap();
+        install_assets(&mut gba).unwrap();
+
+        let first = render_frame(&mut gba, 0).unwrap();
+        let sky = &first[..HORIZON * SCREEN_WIDTH];
+        let floor = &first[HORIZON * SCREEN_WIDTH..];
+        assert!(sky.contains(&0) && sky.iter().any(|&c| c != 0));
+        assert!(floor.contains(&rgb(0x20, 0x10, 0x60)) && floor.contains(&rgb(0xE0, 0x40, 0xA0)));
+
+        let later = render_frame(&mut gba, 37).unwrap();
+        assert_ne!(first[..HORIZON * SCREEN_WIDTH], later[..HORIZON * SCREEN_WIDTH]);
+        assert_ne!(first[HORIZON * SCREEN_WIDTH..], later[HORIZON * SCREEN_WIDTH..]);
+    }
+}
@@ -134,8 +134,7 @@ fn main() -> Result<(), String> {
     
     // 生成任天堂不动点ROM文件
     println!("🎮 生成任天堂不动点算法演示ROM...");
-    let mut rom_generator = RomGenerator::new("NINTENDO FIXED");
-    rom_generator.add_program(0x100, &nintendo_fixed_point_program);
+    let rom_generator = RomGenerator::new("NINTENDO FIXED").program(0x150, &nintendo_fixed_point_program);
     
     let filename = "nintendo_fixed_point.gb";
     rom_generator.save_rom(filename).map_err(|e| e.to_string())?;
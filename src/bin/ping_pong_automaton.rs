@@ -139,8 +139,7 @@ fn main() -> Result<(), String> {
     
     // 生成乒乓自动机ROM文件
     println!("🏓 生成乒乓自动机演示ROM...");
-    let mut rom_generator = RomGenerator::new("PING PONG AUTO");
-    rom_generator.add_program(0x100, &ping_pong_automaton_program);
+    let rom_generator = RomGenerator::new("PING PONG AUTO").program(0x150, &ping_pong_automaton_program);
     
     let filename = "ping_pong_automaton.gb";
     rom_generator.save_rom(filename).map_err(|e| e.to_string())?;
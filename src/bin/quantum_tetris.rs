@@ -12,6 +12,14 @@ use std::time::{Duration, Instant};
 use std::thread;
 use std::f64::consts::PI;
 
+use gameboy_emulator::config::Config;
+use gameboy_emulator::frontend::tui::Panel;
+use gameboy_emulator::i18n::{self, tr, trf, Locale, Msg};
+
+/// 右侧面板的起始列和内容宽度
+const PANEL_COLUMN: usize = 30;
+const PANEL_WIDTH: usize = 16;
+
 /// 量子状态
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum QuantumState {
@@ -698,69 +706,31 @@ impl QuantumTetrisApp {
         let stats = &self.game.stats;
         
         // 渲染量子统计信息
-        print!("\x1B[3;30H");
-        print!("┌─ 量子统计 ─┐");
-        
-        print!("\x1B[4;30H");
-        print!("│ 量子分数: {:>6.1} │", stats.quantum_score);
-        
-        print!("\x1B[5;30H");
-        print!("│ 纠缠次数: {:>6} │", stats.entanglement_count);
-        
-        print!("\x1B[6;30H");
-        print!("│ 叠加事件: {:>6} │", stats.superposition_events);
-        
-        print!("\x1B[7;30H");
-        print!("│ 隧道事件: {:>6} │", stats.tunneling_events);
-        
-        print!("\x1B[8;30H");
-        print!("│ 观察交互: {:>6} │", stats.observer_interactions);
-        
-        print!("\x1B[9;30H");
-        print!("│ 量子相干: {:>6.2} │", stats.quantum_coherence);
-        
-        print!("\x1B[10;30H");
-        print!("└──────────────┘");
+        Panel::new(tr(Msg::QuantumStatsPanel))
+            .width(PANEL_WIDTH)
+            .field(tr(Msg::QuantumScore), format!("{:.1}", stats.quantum_score))
+            .field(tr(Msg::QuantumEntanglements), stats.entanglement_count)
+            .field(tr(Msg::QuantumSuperpositions), stats.superposition_events)
+            .field(tr(Msg::QuantumTunnels), stats.tunneling_events)
+            .field(tr(Msg::QuantumObservations), stats.observer_interactions)
+            .field(tr(Msg::QuantumCoherence), format!("{:.2}", stats.quantum_coherence))
+            .draw(3, PANEL_COLUMN);
         
         // 渲染量子场强度
-        print!("\x1B[12;30H");
-        print!("┌─ 量子场 ─┐");
-        
-        print!("\x1B[13;30H");
-        print!("│ 场强度: {:>6.2} │", self.game.quantum_field_strength);
-        
-        print!("\x1B[14;30H");
-        print!("│ 时空扭曲: {:>5.2} │", self.game.spacetime_distortion);
-        
-        print!("\x1B[15;30H");
-        print!("│ 观察者: {:>7.2} │", self.game.observer_presence);
-        
-        print!("\x1B[16;30H");
-        print!("└────────────┘");
+        Panel::new(tr(Msg::QuantumFieldPanel))
+            .width(PANEL_WIDTH)
+            .field(tr(Msg::QuantumFieldStrength), format!("{:.2}", self.game.quantum_field_strength))
+            .field(tr(Msg::QuantumDistortion), format!("{:.2}", self.game.spacetime_distortion))
+            .field(tr(Msg::QuantumObserver), format!("{:.2}", self.game.observer_presence))
+            .draw(12, PANEL_COLUMN);
     }
     
     /// 渲染量子控制说明
     fn render_quantum_controls(&mut self) {
-        print!("\x1B[18;30H");
-        print!("┌─ 量子控制 ─┐");
-        
-        print!("\x1B[19;30H");
-        print!("│ O: 观察位置   │");
-        
-        print!("\x1B[20;30H");
-        print!("│ E: 创建纠缠   │");
-        
-        print!("\x1B[21;30H");
-        print!("│ T: 量子隧道   │");
-        
-        print!("\x1B[22;30H");
-        print!("│ S: 叠加态     │");
-        
-        print!("\x1B[23;30H");
-        print!("│ Q: 退出游戏   │");
-        
-        print!("\x1B[24;30H");
-        print!("└──────────────┘");
+        tr(Msg::QuantumControls)
+            .lines()
+            .fold(Panel::new(tr(Msg::QuantumControlsPanel)).width(PANEL_WIDTH), Panel::line)
+            .draw(18, PANEL_COLUMN);
     }
     
     /// 处理量子输入
@@ -824,19 +794,7 @@ impl QuantumTetrisApp {
     /// 显示量子欢迎信息
     fn show_quantum_welcome(&mut self) {
         self.clear_screen();
-        println!("🌌 量子纠缠俄罗斯方块 - 革命性创新版本");
-        println!("==================================================");
-        println!("");
-        println!("欢迎来到量子世界！这是世界上第一个量子俄罗斯方块游戏");
-        println!("");
-        println!("🎯 革命性特性：");
-        println!("  ✅ 量子纠缠机制 - 多个方块同时操作");
-        println!("  ✅ 时空扭曲 - 方块可以穿越时间");
-        println!("  ✅ 概率叠加 - 方块同时存在于多个状态");
-        println!("  ✅ 量子隧道 - 方块可以穿过障碍物");
-        println!("  ✅ 观察者效应 - 你的观察影响游戏状态");
-        println!("");
-        println!("按任意键进入量子世界...");
+        println!("{}", tr(Msg::QuantumWelcome));
         
         let mut input = String::new();
         stdin().read_line(&mut input).ok();
@@ -847,33 +805,27 @@ impl QuantumTetrisApp {
         self.clear_screen();
         let stats = &self.game.stats;
         
-        println!("🌌 量子游戏结束！");
-        println!("==================================================");
-        println!("");
-        println!("量子统计：");
-        println!("  量子分数: {:.1}", stats.quantum_score);
-        println!("  纠缠次数: {}", stats.entanglement_count);
-        println!("  叠加事件: {}", stats.superposition_events);
-        println!("  隧道事件: {}", stats.tunneling_events);
-        println!("  观察交互: {}", stats.observer_interactions);
-        println!("  量子相干性: {:.2}", stats.quantum_coherence);
-        println!("  游戏时间: {:.1}秒", stats.play_time.as_secs_f64());
-        println!("");
-        println!("感谢体验量子世界！");
-        println!("");
+        println!("{}", trf(Msg::QuantumGameOver, &[
+            &format!("{:.1}", stats.quantum_score),
+            &stats.entanglement_count,
+            &stats.superposition_events,
+            &stats.tunneling_events,
+            &stats.observer_interactions,
+            &format!("{:.2}", stats.quantum_coherence),
+            &format!("{:.1}", stats.play_time.as_secs_f64()),
+        ]));
     }
 }
 
 /// 主函数
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🌌 启动量子纠缠俄罗斯方块游戏...");
-    println!("革命性创新版本");
-    println!("");
+    i18n::set_locale(Locale::from_config(&Config::from_env())?);
+    println!("{}", tr(Msg::QuantumLaunch));
     
     // 创建并运行量子游戏
     let mut game = QuantumTetrisApp::new();
     game.run();
     
-    println!("量子游戏已退出，感谢体验！");
+    println!("{}", tr(Msg::QuantumExited));
     Ok(())
 }
@@ -151,8 +151,7 @@ fn main() -> Result<(), String> {
     
     // 生成时空纠缠ROM文件
     println!("🌌 生成时空纠缠演示ROM...");
-    let mut rom_generator = RomGenerator::new("SPACETIME ENT");
-    rom_generator.add_program(0x100, &spacetime_entanglement_program);
+    let rom_generator = RomGenerator::new("SPACETIME ENT").program(0x150, &spacetime_entanglement_program);
     
     let filename = "spacetime_entanglement.gb";
     rom_generator.save_rom(filename).map_err(|e| e.to_string())?;
@@ -0,0 +1,99 @@
+//! 命令行参数解析
+//!
+//! 支持 `--key value`、`--key=value` 形式的选项、无值开关和位置参数，
+//! `--` 之后的参数都作为位置参数
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::i18n::{trf, Msg};
+
+/// 解析后的参数
+#[derive(Debug, Clone, Default)]
+pub struct Args {
+    pub positional: Vec<String>,
+    options: HashMap<String, String>,
+    flags: HashSet<String>,
+}
+
+impl Args {
+    /// 解析参数，`switches` 列出不带值的开关名（不含 `--`）
+    pub fn parse(argv: &[String], switches: &[&str]) -> Result<Self, String> {
+        let mut args = Args::default();
+        let mut iter = argv.iter();
+
+        while let Some(arg) = iter.next() {
+            if arg == "--" {
+                args.positional.extend(iter.cloned());
+                break;
+            }
+            let Some(name) = arg.strip_prefix("--") else {
+                args.positional.push(arg.clone());
+                continue;
+            };
+
+            if let Some((key, value)) = name.split_once('=') {
+                args.options.insert(key.to_string(), value.to_string());
+            } else if switches.contains(&name) {
+                args.flags.insert(name.to_string());
+            } else {
+                let value = iter.next().ok_or_else(|| trf(Msg::ArgsMissingValue, &[&name]))?;
+                args.options.insert(name.to_string(), value.clone());
+            }
+        }
+
+        Ok(args)
+    }
+
+    /// 检查开关是否出现
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    /// 获取选项的原始值
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|s| s.as_str())
+    }
+
+    /// 获取并解析选项，未提供时返回默认值
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.get(name) {
+            Some(value) => value.parse().map_err(|_| trf(Msg::ArgsInvalidValue, &[&name, &value])),
+            None => Ok(default),
+        }
+    }
+
+    /// 检查是否有未识别的选项
+    pub fn reject_unknown(&self, known: &[&str]) -> Result<(), String> {
+        self.options
+            .keys()
+            .chain(self.flags.iter())
+            .find(|name| !known.contains(&name.as_str()))
+            .map_or(Ok(()), |name| Err(trf(Msg::ArgsUnknownOption, &[name])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_options_and_flags() {
+        let args = Args::parse(&argv(&["rom.gb", "--rounds", "3", "--format=csv", "--quiet"]), &["quiet"]).unwrap();
+
+        assert_eq!(args.positional, vec!["rom.gb"]);
+        assert_eq!(args.get_or("rounds", 1u32).unwrap(), 3);
+        assert_eq!(args.get("format"), Some("csv"));
+        assert!(args.flag("quiet"));
+        assert!(args.reject_unknown(&["rounds", "format"]).is_err());
+        assert!(Args::parse(&argv(&["--rounds"]), &[]).is_err());
+
+        let args = Args::parse(&argv(&["--rounds", "3", "--", "--format", "-q"]), &[]).unwrap();
+        assert_eq!(args.positional, vec!["--format", "-q"]);
+        assert_eq!(args.get("format"), None);
+    }
+}
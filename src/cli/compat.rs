@@ -0,0 +1,360 @@
+//! ROM兼容性批量测试
+//!
+//! 逐个加载目录中的ROM，在看门狗监督下运行固定帧数并分类结果，
+//! 生成Markdown或JSON兼容性报告。
+//!
+//! 看门狗在每个帧边界检查一次：PC和工作RAM连续若干帧都没有变化视为卡死，
+//! 单个ROM超过墙钟时间预算也视为卡死。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::frontend::Emulator;
+use crate::gba::{GBAState, GBASystem};
+use crate::i18n::{tr, trf, Msg};
+use crate::instructions::Instruction;
+use crate::memory::MemoryRegion;
+use crate::util::hash::sha1;
+use crate::util::Json;
+use crate::GameBoy;
+
+use super::rom::{Platform, RomInfo};
+
+/// 识别为ROM的文件扩展名
+pub const ROM_EXTENSIONS: [&str; 3] = ["gb", "gbc", "gba"];
+
+/// 看门狗设置
+#[derive(Debug, Clone, Copy)]
+pub struct Watchdog {
+    /// 连续多少帧没有进展视为卡死
+    pub stall_frames: u32,
+    /// 单个ROM的墙钟时间预算
+    pub timeout: Duration,
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self {
+            stall_frames: 30,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// 看门狗判定卡死的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HangReason {
+    /// PC和工作RAM连续若干帧没有变化
+    Stalled,
+    /// 超出墙钟时间预算
+    Timeout,
+}
+
+impl HangReason {
+    pub fn text(self) -> &'static str {
+        match self {
+            HangReason::Stalled => tr(Msg::CompatStalled),
+            HangReason::Timeout => tr(Msg::CompatTimeout),
+        }
+    }
+}
+
+/// 单个ROM的运行结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// 正常运行完所有帧
+    Boots,
+    /// 遇到无法解码的指令
+    UnknownOpcode { pc: u32, opcode: u32 },
+    /// 执行出错
+    Crash { pc: u32, message: String },
+    /// 看门狗判定卡死
+    Hang { pc: u32, frame: u32, reason: HangReason },
+    /// 无法加载（头部无效、文件无法读取等）
+    LoadFailed(String),
+}
+
+impl Outcome {
+    /// 报告中使用的分类名称
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Outcome::Boots => "boots",
+            Outcome::UnknownOpcode { .. } => "unknown_opcode",
+            Outcome::Crash { .. } => "crash",
+            Outcome::Hang { .. } => "hang",
+            Outcome::LoadFailed(_) => "load_failed",
+        }
+    }
+
+    /// 结果说明
+    pub fn detail(&self) -> String {
+        match self {
+            Outcome::Boots => String::new(),
+            Outcome::UnknownOpcode { pc, opcode } => {
+                trf(Msg::CompatUnknownOpcode, &[&format!("{:04X}", pc), &format!("{:02X}", opcode)])
+            }
+            Outcome::Crash { pc, message } => format!("PC=0x{:04X} {}", pc, message),
+            Outcome::Hang { pc, frame, reason } => {
+                trf(Msg::CompatHang, &[frame, &format!("{:04X}", pc), &reason.text()])
+            }
+            Outcome::LoadFailed(message) => message.clone(),
+        }
+    }
+}
+
+/// 一个ROM的测试记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct RomResult {
+    pub file: String,
+    pub platform: Option<Platform>,
+    pub title: String,
+    pub frames: u32,
+    pub outcome: Outcome,
+}
+
+/// 被测核心在帧边界的进度
+trait Probe {
+    fn pc(&self) -> u32;
+    /// 工作RAM摘要，用于判断是否有进展
+    fn ram_digest(&self) -> [u8; 20];
+    fn opcode_at_pc(&self) -> u32;
+    /// PC处是核心无法解码的指令
+    fn undecodable_at_pc(&self) -> bool;
+}
+
+impl Probe for GameBoy {
+    fn pc(&self) -> u32 {
+        self.get_cpu_state().pc as u32
+    }
+
+    fn ram_digest(&self) -> [u8; 20] {
+        let wram = MemoryRegion::WRAM;
+        sha1(&self.memory()[wram.start as usize..=wram.end as usize])
+    }
+
+    fn opcode_at_pc(&self) -> u32 {
+        self.memory().get(self.pc() as usize).copied().unwrap_or(0) as u32
+    }
+
+    fn undecodable_at_pc(&self) -> bool {
+        let byte = |offset: u32| self.memory().get((self.pc() + offset) as usize).copied().unwrap_or(0);
+        Instruction::from_bytes(byte(0), [byte(1), byte(2)]).is_none()
+    }
+}
+
+impl Probe for GBASystem {
+    fn pc(&self) -> u32 {
+        self.cpu.pc
+    }
+
+    fn ram_digest(&self) -> [u8; 20] {
+        let mut ram = self.memory.iwram.to_vec();
+        ram.extend_from_slice(&self.memory.ewram);
+        sha1(&ram)
+    }
+
+    fn opcode_at_pc(&self) -> u32 {
+        let offset = (self.cpu.pc & 0x01FF_FFFF) as usize;
+        self.memory.rom.get(offset..offset + 4).map_or(0, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// GBA核心把无法执行的指令当作普通错误报告
+    fn undecodable_at_pc(&self) -> bool {
+        false
+    }
+}
+
+/// 在看门狗监督下运行 `frames` 帧
+fn supervise<E: Emulator + Probe>(emulator: &mut E, frames: u32, watchdog: &Watchdog) -> (u32, Outcome) {
+    let start = Instant::now();
+    let mut last = (emulator.pc(), emulator.ram_digest());
+    let mut stalled = 0;
+
+    for frame in 0..frames {
+        if let Err(message) = emulator.run_frame() {
+            let pc = emulator.pc();
+            let outcome = if emulator.undecodable_at_pc() {
+                Outcome::UnknownOpcode { pc, opcode: emulator.opcode_at_pc() }
+            } else {
+                Outcome::Crash { pc, message }
+            };
+            return (frame, outcome);
+        }
+
+        let current = (emulator.pc(), emulator.ram_digest());
+        stalled = if current == last { stalled + 1 } else { 0 };
+        last = current;
+        if stalled >= watchdog.stall_frames {
+            return (frame + 1, Outcome::Hang { pc: current.0, frame: frame + 1, reason: HangReason::Stalled });
+        }
+        if start.elapsed() > watchdog.timeout {
+            return (frame + 1, Outcome::Hang { pc: current.0, frame: frame + 1, reason: HangReason::Timeout });
+        }
+    }
+    (frames, Outcome::Boots)
+}
+
+/// 无法加载的ROM记录
+pub fn load_failed(file: &str, message: String) -> RomResult {
+    RomResult {
+        file: file.to_string(),
+        platform: None,
+        title: String::new(),
+        frames: 0,
+        outcome: Outcome::LoadFailed(message),
+    }
+}
+
+/// 测试ROM数据
+pub fn check_rom(file: &str, data: &[u8], frames: u32, watchdog: &Watchdog) -> RomResult {
+    let info = match RomInfo::analyze(data) {
+        Ok(info) => info,
+        Err(message) => return load_failed(file, message),
+    };
+    let mut result = load_failed(file, String::new());
+    result.platform = Some(info.platform);
+    result.title = info.title.clone();
+
+    let (frames, outcome) = match info.platform {
+        Platform::GameBoy => {
+            let mut gameboy = GameBoy::new();
+            match gameboy.load_rom(data) {
+                Ok(()) => supervise(&mut gameboy, frames, watchdog),
+                Err(message) => (0, Outcome::LoadFailed(message)),
+            }
+        }
+        Platform::GameBoyAdvance => {
+            let mut gba = GBASystem::new();
+            match gba.load_rom(data.to_vec()).and_then(|_| gba.start()) {
+                Ok(()) => {
+                    let (frames, outcome) = supervise(&mut gba, frames, watchdog);
+                    match gba.get_state() {
+                        GBAState::Error(message) if outcome == Outcome::Boots => {
+                            (frames, Outcome::Crash { pc: gba.cpu.pc, message: message.clone() })
+                        }
+                        _ => (frames, outcome),
+                    }
+                }
+                Err(message) => (0, Outcome::LoadFailed(message)),
+            }
+        }
+    };
+    result.frames = frames;
+    result.outcome = outcome;
+    result
+}
+
+/// 列出目录中的ROM文件（按文件名排序）
+pub fn find_roms(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| trf(Msg::CliReadFailed, &[&dir.display(), &e]))?;
+    let mut roms: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ROM_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        })
+        .collect();
+    roms.sort();
+    Ok(roms)
+}
+
+/// 兼容性报告
+#[derive(Debug, Clone, Default)]
+pub struct CompatReport {
+    pub frames: u32,
+    pub results: Vec<RomResult>,
+}
+
+impl CompatReport {
+    /// 某一分类的ROM数量
+    pub fn count(&self, kind: &str) -> usize {
+        self.results.iter().filter(|r| r.outcome.kind() == kind).count()
+    }
+
+    /// Markdown格式
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("{}\n\n", tr(Msg::CompatReportTitle));
+        out.push_str(&format!("{}\n\n", trf(Msg::CompatReportSummary, &[&self.frames, &self.results.len()])));
+        out.push_str(&format!("{}\n|------|------|\n", tr(Msg::CompatReportCounts)));
+        for kind in ["boots", "unknown_opcode", "crash", "hang", "load_failed"] {
+            out.push_str(&format!("| {} | {} |\n", kind, self.count(kind)));
+        }
+        out.push_str(&format!("\n{}\n|------|------|------|------|------|------|\n", tr(Msg::CompatReportColumns)));
+        for result in &self.results {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                result.file,
+                result.platform.map_or("-", |p| p.name()),
+                result.title.replace('|', "\\|"),
+                result.frames,
+                result.outcome.kind(),
+                result.outcome.detail().replace('|', "\\|"),
+            ));
+        }
+        out
+    }
+
+    /// JSON格式
+    pub fn to_json(&self) -> Json {
+        let results = self
+            .results
+            .iter()
+            .map(|r| {
+                Json::object(vec![
+                    ("file", Json::from(r.file.as_str())),
+                    ("platform", Json::from(r.platform.map(|p| p.name()))),
+                    ("title", Json::from(r.title.as_str())),
+                    ("frames", Json::from(r.frames)),
+                    ("outcome", Json::from(r.outcome.kind())),
+                    ("detail", Json::from(r.outcome.detail())),
+                ])
+            })
+            .collect();
+        Json::object(vec![
+            ("frames", Json::from(self.frames)),
+            ("total", Json::from(self.results.len())),
+            ("boots", Json::from(self.count("boots"))),
+            ("results", Json::Array(results)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rom::RomGenerator;
+
+    #[test]
+    fn test_check_rom_classifies_outcomes() {
+        let watchdog = Watchdog { stall_frames: 2, timeout: Duration::from_secs(30) };
+
+        // 程序之后的空间用0xFF填充，执行到那里时报告未知指令
+        let unknown = RomGenerator::new("UNKNOWN").program(0x150, &[0x00]).build().unwrap();
+        let result = check_rom("unknown.gb", &unknown, 2, &watchdog);
+        match result.outcome {
+            Outcome::UnknownOpcode { pc, opcode } => {
+                assert_eq!(opcode, 0xFF);
+                assert_eq!(unknown[pc as usize], 0xFF);
+            }
+            other => panic!("预期未知指令，实际 {:?}", other),
+        }
+        assert!(result.outcome.detail().ends_with("未知指令 0xFF"));
+
+        // JR -2 原地循环，PC和工作RAM都不变
+        let stuck = RomGenerator::new("STUCK").program(0x150, &[0x18, 0xFE]).build().unwrap();
+        let result = check_rom("stuck.gb", &stuck, 10, &watchdog);
+        assert_eq!(result.outcome, Outcome::Hang { pc: 0x150, frame: 3, reason: HangReason::Stalled });
+        assert_eq!(result.outcome.detail(), "第 3 帧 PC=0x0150 PC和工作RAM没有变化");
+
+        let result = check_rom("short.gb", &[0u8; 16], 2, &watchdog);
+        assert_eq!(result.outcome.kind(), "load_failed");
+
+        let mut report = CompatReport { frames: 2, results: vec![result] };
+        report.results.push(check_rom("unknown.gb", &unknown, 2, &watchdog));
+        assert_eq!(report.count("unknown_opcode"), 1);
+        assert!(report.to_markdown().contains("| unknown.gb | Game Boy | UNKNOWN | 0 | unknown_opcode |"));
+        assert!(report.to_json().to_pretty().contains("\"total\": 2"));
+    }
+}
@@ -0,0 +1,130 @@
+//! `gamelife config` 子命令
+
+use std::fs;
+use std::path::Path;
+
+use crate::config::schema::{self, SCHEMA};
+use crate::config::Config;
+use crate::i18n::{tr, trf, Msg};
+use crate::util::Json;
+
+use super::{Args, GlobalOptions};
+
+/// 执行配置子命令
+pub fn run(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    match argv.split_first() {
+        Some((command, rest)) if command == "check" => check(rest, options),
+        Some((command, rest)) if command == "dump-default" => dump_default(rest, options),
+        Some((command, rest)) if command == "show" => show(rest, options),
+        Some((command, _)) if command != "--help" && command != "-h" => {
+            Err(trf(Msg::CliUnknownSubcommand, &[&"config", command, &tr(Msg::ConfigUsage)]))
+        }
+        _ => {
+            println!("{}", tr(Msg::ConfigUsage));
+            Ok(())
+        }
+    }
+}
+
+/// 检查配置文件，有问题时返回错误
+fn check(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&[])?;
+    let [path] = args.positional.as_slice() else {
+        return Err(trf(Msg::ConfigNeedFile, &[&tr(Msg::ConfigUsage)]));
+    };
+
+    let content = fs::read_to_string(path).map_err(|e| trf(Msg::CliReadFailed, &[path, &e]))?;
+    let diagnostics = schema::validate(&content);
+
+    options.emit(
+        || {
+            if diagnostics.is_empty() {
+                return trf(Msg::ConfigNoProblems, &[path]);
+            }
+            diagnostics.iter().map(|d| format!("{}:{}\n", path, d)).collect()
+        },
+        || {
+            Json::object(vec![
+                ("path", Json::from(path.as_str())),
+                ("valid", Json::from(diagnostics.is_empty())),
+                ("diagnostics", Json::Array(diagnostics.iter().map(|d| {
+                    Json::object(vec![
+                        ("line", Json::from(d.line)),
+                        ("text", Json::from(d.text.as_str())),
+                        ("message", Json::from(d.message.as_str())),
+                    ])
+                }).collect())),
+            ])
+        },
+    );
+
+    match diagnostics.len() {
+        0 => Ok(()),
+        n => Err(trf(Msg::ConfigProblems, &[path, &n])),
+    }
+}
+
+/// 输出默认配置
+fn dump_default(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&[])?;
+
+    options.emit(schema::dump_default, || {
+        Json::Array(SCHEMA.iter().map(|key| {
+            Json::object(vec![
+                ("key", Json::from(key.name)),
+                ("type", Json::from(key.value_type.to_string())),
+                ("default", Json::from(key.default)),
+                ("description", Json::from(key.describe())),
+            ])
+        }).collect())
+    });
+    Ok(())
+}
+
+/// 按优先级加载配置并显示每个值的来源
+fn show(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&["set"])?;
+    let file = match args.positional.as_slice() {
+        [] => None,
+        [path] => Some(Path::new(path)),
+        _ => return Err(trf(Msg::ConfigTooManyFiles, &[&tr(Msg::ConfigUsage)])),
+    };
+    let overrides = match args.get("set") {
+        Some(list) => list
+            .split(',')
+            .map(|pair| match pair.split_once('=') {
+                Some((key, value)) => Ok((key.trim().to_string(), value.trim().to_string())),
+                None => Err(trf(Msg::ConfigSetFormat, &[&pair])),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+
+    let config = Config::load(file, &overrides).map_err(|e| e.to_string())?;
+    let mut keys: Vec<&String> = config.keys().collect();
+    keys.sort();
+
+    options.emit(
+        || {
+            keys.iter()
+                .map(|key| {
+                    let source = config.provenance(key).map(|s| s.to_string()).unwrap_or_default();
+                    format!("{} = {}  # {}\n", key, config.get(key).map_or("", |v| v.as_str()), source)
+                })
+                .collect()
+        },
+        || {
+            Json::Array(keys.iter().map(|key| {
+                Json::object(vec![
+                    ("key", Json::from(key.as_str())),
+                    ("value", Json::from(config.get(key).map(|v| v.as_str()))),
+                    ("source", Json::from(config.provenance(key).map(|s| s.to_string()))),
+                ])
+            }).collect())
+        },
+    );
+    Ok(())
+}
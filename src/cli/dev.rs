@@ -0,0 +1,69 @@
+//! `gamelife dev` 子命令
+//!
+//! 面向模拟器开发者的工具，目前只有从解码表生成指令集文档。
+
+use std::fs;
+
+use crate::debug::opcodes::{self, opcode_table};
+use crate::i18n::{tr, trf, Msg};
+use crate::util::Json;
+
+use super::{Args, GlobalOptions};
+
+/// 执行开发者子命令
+pub fn run(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    match argv.split_first() {
+        Some((command, rest)) if command == "opcodes" => generate_opcodes(rest, options),
+        Some((command, _)) if command != "--help" && command != "-h" => {
+            Err(trf(Msg::CliUnknownSubcommand, &[&"dev", command, &tr(Msg::DevUsage)]))
+        }
+        _ => {
+            println!("{}", tr(Msg::DevUsage));
+            Ok(())
+        }
+    }
+}
+
+/// 生成操作码矩阵
+fn generate_opcodes(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&["format", "output"])?;
+    let format = if options.json { "json" } else { args.get("format").unwrap_or("markdown") };
+
+    let table = opcode_table();
+    let output = match format {
+        "markdown" => opcodes::to_markdown(&table),
+        "html" => opcodes::to_html(&table),
+        "json" => {
+            let entries = table
+                .iter()
+                .map(|entry| {
+                    Json::object(vec![
+                        ("opcode", Json::from(entry.opcode)),
+                        ("mnemonic", Json::from(entry.mnemonic.as_str())),
+                        ("size", Json::from(entry.size)),
+                        ("cycles", Json::from(entry.cycles)),
+                        ("cycles_not_taken", Json::from(entry.cycles_not_taken)),
+                        ("flags", Json::from(entry.flags)),
+                    ])
+                })
+                .collect();
+            Json::Array(entries).to_pretty() + "\n"
+        }
+        other => return Err(trf(Msg::CliUnknownFormat, &[&other])),
+    };
+
+    match args.get("output") {
+        Some(path) => {
+            fs::write(path, output).map_err(|e| trf(Msg::CliWriteFailed, &[&path, &e]))?;
+            if !options.quiet {
+                eprintln!("{}", trf(Msg::DevOpcodesWritten, &[&table.len(), &path]));
+            }
+            Ok(())
+        }
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
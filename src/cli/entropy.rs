@@ -0,0 +1,276 @@
+//! `gamelife entropy` 子命令
+
+use std::fs;
+use std::time::Instant;
+
+use crate::config::Config;
+use crate::cpu::{OptimizedCPU, ReferenceCPU};
+use crate::entropy::entropy_pool::EntropyQualityAssessor;
+use crate::entropy::rom_prng::{cross_check, prng_rom};
+use crate::entropy::{ConditioningMode, DistributionOptimizer, EntropyManager, OptimizerStream, ParallelOptions, StatisticalReport};
+use crate::i18n::{tr, trf, Msg};
+use crate::util::Json;
+
+use super::{Args, GlobalOptions};
+
+/// 执行熵源子命令
+pub fn run(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    match argv.split_first() {
+        Some((command, rest)) if command == "bench" => bench(rest, options),
+        Some((command, rest)) if command == "throughput" => throughput(rest, options),
+        Some((command, rest)) if command == "report" => report(rest, options),
+        Some((command, rest)) if command == "rom-prng" => rom_prng(rest, options),
+        Some((command, _)) if command != "--help" && command != "-h" => {
+            Err(trf(Msg::CliUnknownSubcommand, &[&"entropy", command, &tr(Msg::EntropyUsage)]))
+        }
+        _ => {
+            println!("{}", tr(Msg::EntropyUsage));
+            Ok(())
+        }
+    }
+}
+
+/// 熵源吞吐量与质量基准测试
+fn bench(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&["rounds", "size"])?;
+    let rounds: u64 = args.get_or("rounds", 64)?;
+    let size: usize = args.get_or("size", 4096)?;
+
+    let mut manager = EntropyManager::from_config(&Config::from_env())?;
+    let mut assessor = EntropyQualityAssessor::new();
+    let mut progress = options.progress(tr(Msg::EntropyBenchProgress), rounds);
+    let mut total_bytes = 0usize;
+    let start = Instant::now();
+
+    for _ in 0..rounds {
+        let bytes = manager.generate_random(size).map_err(|e| e.to_string())?;
+        total_bytes += bytes.len();
+        assessor.assess_quality(&bytes);
+        progress.inc(1);
+    }
+    progress.finish();
+
+    let elapsed = start.elapsed().as_secs_f64();
+    let throughput = total_bytes as f64 / 1024.0 / elapsed.max(f64::EPSILON);
+    let quality = assessor.get_average_quality();
+
+    options.emit(
+        || {
+            trf(Msg::EntropyBenchSummary, &[
+                &rounds,
+                &total_bytes,
+                &format!("{:.3}", elapsed),
+                &format!("{:.1}", throughput),
+                &format!("{:.4}", quality),
+            ])
+        },
+        || {
+            Json::object(vec![
+                ("rounds", Json::from(rounds)),
+                ("bytes", Json::from(total_bytes)),
+                ("elapsed_secs", Json::from(elapsed)),
+                ("throughput_kib_per_sec", Json::from(throughput)),
+                ("average_quality", Json::from(quality)),
+            ])
+        },
+    );
+    Ok(())
+}
+
+/// 分布优化器吞吐量基准：串行、分块并行、流式三种方式
+fn throughput(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&["size", "chunk", "threads"])?;
+    let defaults = ParallelOptions::default();
+    let size: usize = args.get_or("size", 1 << 20)?;
+    let parallel = ParallelOptions {
+        chunk_size: args.get_or("chunk", defaults.chunk_size)?,
+        threads: args.get_or("threads", defaults.threads)?,
+    };
+    if parallel.chunk_size == 0 {
+        return Err(tr(Msg::ThroughputChunkZero).to_string());
+    }
+
+    // 固定种子的xorshift数据，避免基准受熵源收集速度影响
+    let mut state = 0x2545_F491u32;
+    let input: Vec<u8> = (0..size)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 8) as u8
+        })
+        .collect();
+
+    let mut progress = options.progress(tr(Msg::ThroughputProgress), 3);
+    let measure = |run: &mut dyn FnMut() -> Result<Vec<u8>, String>| -> Result<(f64, usize), String> {
+        let start = Instant::now();
+        let output = run()?;
+        Ok((start.elapsed().as_secs_f64(), output.len()))
+    };
+    let serial = measure(&mut || DistributionOptimizer::new().optimize_distribution(&input).map_err(|e| e.to_string()))?;
+    progress.inc(1);
+    let chunked = measure(&mut || {
+        DistributionOptimizer::new().optimize_parallel(&input, parallel).map_err(|e| e.to_string())
+    })?;
+    progress.inc(1);
+    let streamed = measure(&mut || {
+        let mut stream = OptimizerStream::new(parallel.chunk_size);
+        let mut output = Vec::new();
+        for piece in input.chunks(4096) {
+            output.extend(stream.push(piece).map_err(|e| e.to_string())?);
+        }
+        output.extend(stream.finish().map_err(|e| e.to_string())?);
+        Ok(output)
+    })?;
+    progress.inc(1);
+    progress.finish();
+
+    let rate = |(elapsed, _): (f64, usize)| size as f64 / 1024.0 / 1024.0 / elapsed.max(f64::EPSILON);
+    let results = [
+        ("serial", Msg::ThroughputSerial, serial),
+        ("parallel", Msg::ThroughputParallel, chunked),
+        ("stream", Msg::ThroughputStream, streamed),
+    ];
+
+    options.emit(
+        || {
+            let mut text = trf(Msg::ThroughputHeader, &[&size, &parallel.chunk_size, &parallel.threads]);
+            for (_, label, result) in results {
+                text.push_str(&trf(Msg::ThroughputLine, &[
+                    &tr(label),
+                    &format!("{:.3}", result.0),
+                    &format!("{:.2}", rate(result)),
+                ]));
+            }
+            text.push_str(&trf(Msg::ThroughputSpeedup, &[&format!("{:.2}", serial.0 / chunked.0.max(f64::EPSILON))]));
+            text
+        },
+        || {
+            let mut fields = vec![
+                ("bytes", Json::from(size)),
+                ("chunk_size", Json::from(parallel.chunk_size)),
+                ("threads", Json::from(parallel.threads)),
+            ];
+            for (key, _, result) in results {
+                fields.push((key, Json::object(vec![
+                    ("elapsed_secs", Json::from(result.0)),
+                    ("output_bytes", Json::from(result.1)),
+                    ("mib_per_sec", Json::from(rate(result))),
+                ])));
+            }
+            Json::object(fields)
+        },
+    );
+    Ok(())
+}
+
+/// 熵源状态报告
+fn report(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&["conditioning"])?;
+    // 命令行选项优先于配置 `entropy_conditioning`
+    let mut manager = EntropyManager::from_config(&Config::from_env())?;
+    if let Some(name) = args.get("conditioning") {
+        manager.set_conditioning(ConditioningMode::parse(name)?);
+    }
+    let conditioning = manager.conditioning();
+    let output = manager.collect_and_optimize().map_err(|e| e.to_string())?;
+    let stats = manager.get_entropy_stats();
+    let statistics = StatisticalReport::analyze(&output);
+
+    options.emit(
+        || {
+            trf(Msg::EntropyReportSummary, &[
+                &stats.source_count,
+                &stats.pool_size,
+                &format!("{:.4}", stats.optimizer_stats.distribution_quality),
+                &stats.optimizer_stats.optimization_cycles,
+                &format!("{:.4}", stats.optimizer_stats.entropy_density),
+                &format!("{:.4}", stats.quantum_stats.post_quantum_strength),
+                &stats.quantum_stats.processing_time_ns,
+                &format!("{:.4}", stats.quantum_stats.entropy_amplification),
+                &conditioning.name(),
+                &statistics,
+            ])
+        },
+        || {
+            Json::object(vec![
+                ("source_count", Json::from(stats.source_count)),
+                ("pool_size", Json::from(stats.pool_size)),
+                ("optimizer", Json::object(vec![
+                    ("distribution_quality", Json::from(stats.optimizer_stats.distribution_quality)),
+                    ("optimization_cycles", Json::from(stats.optimizer_stats.optimization_cycles)),
+                    ("entropy_density", Json::from(stats.optimizer_stats.entropy_density)),
+                ])),
+                ("quantum", Json::object(vec![
+                    ("post_quantum_strength", Json::from(stats.quantum_stats.post_quantum_strength)),
+                    ("processing_time_ns", Json::from(stats.quantum_stats.processing_time_ns)),
+                    ("entropy_amplification", Json::from(stats.quantum_stats.entropy_amplification)),
+                ])),
+                ("conditioning", Json::from(conditioning.name())),
+                ("statistics", Json::object(vec![
+                    ("bytes", Json::from(statistics.bytes)),
+                    ("ones_ratio", Json::from(statistics.ones_ratio)),
+                    ("runs_deviation", Json::from(statistics.runs_deviation)),
+                    ("chi_square", Json::from(statistics.chi_square)),
+                    ("shannon_entropy", Json::from(statistics.shannon_entropy)),
+                    ("serial_correlation", Json::from(statistics.serial_correlation)),
+                    ("passes", Json::from(statistics.passes())),
+                ])),
+            ])
+        },
+    );
+    Ok(())
+}
+
+/// 在两个CPU核心上运行生成的伪随机数发生器ROM，与宿主端参考模型交叉验证
+fn rom_prng(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&["seed", "bytes", "rom"])?;
+    let seed: u16 = args.get_or("seed", 1)?;
+    let count: usize = args.get_or("bytes", 4096)?;
+
+    if let Some(path) = args.get("rom") {
+        let rom = prng_rom().map_err(|e| e.to_string())?;
+        fs::write(path, rom).map_err(|e| trf(Msg::CliWriteFailed, &[&path, &e]))?;
+    }
+
+    let mut progress = options.progress(tr(Msg::RomPrngProgress), 2);
+    let optimized = cross_check::<OptimizedCPU>(seed, count).map_err(|e| e.to_string())?;
+    progress.inc(1);
+    cross_check::<ReferenceCPU>(seed, count).map_err(|e| e.to_string())?;
+    progress.inc(1);
+    progress.finish();
+
+    let cycles = optimized.cycles.unwrap_or(0);
+    let statistics = StatisticalReport::analyze(&optimized.bytes);
+    let sample: Vec<String> = optimized.bytes.iter().take(16).map(|byte| format!("{:02X}", byte)).collect();
+
+    options.emit(
+        || {
+            trf(Msg::RomPrngSummary, &[
+                &seed,
+                &optimized.bytes.len(),
+                &optimized.instructions,
+                &cycles,
+                &sample.join(" "),
+                &statistics,
+            ])
+        },
+        || {
+            Json::object(vec![
+                ("seed", Json::from(seed)),
+                ("bytes", Json::from(optimized.bytes.len())),
+                ("instructions", Json::from(optimized.instructions)),
+                ("cycles", Json::from(cycles)),
+                ("sample", Json::from(sample.join(""))),
+                ("chi_square", Json::from(statistics.chi_square)),
+                ("shannon_entropy", Json::from(statistics.shannon_entropy)),
+                ("passes", Json::from(statistics.passes())),
+            ])
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! `gamelife life` 子命令
+
+use std::fs;
+
+use crate::games::life_game::{export_gif, CycleDetector, GeneratorConfig, GifExportOptions, LifeGrid, PatternGenerator, PatternKind, Symmetry, Topology};
+use crate::i18n::{tr, trf, Msg};
+use crate::util::Json;
+
+use super::{Args, GlobalOptions};
+
+const OPTIONS: [&str; 13] = [
+    "width", "height", "generations", "pattern", "density", "symmetry", "survive", "topology", "gif", "every", "cell-size",
+    "delay", "no-annotate",
+];
+
+/// 候选图样的最大尝试数
+const CANDIDATES: usize = 64;
+
+/// 执行生命游戏子命令
+pub fn run(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    if argv.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", tr(Msg::LifeUsage));
+        return Ok(());
+    }
+
+    let args = Args::parse(argv, &["no-annotate"])?;
+    args.reject_unknown(&OPTIONS)?;
+
+    let width: usize = args.get_or("width", 40)?;
+    let height: usize = args.get_or("height", 20)?;
+    let generations: u32 = args.get_or("generations", 200)?;
+    let survive: u32 = args.get_or("survive", 0)?;
+    let topology = Topology::parse(args.get("topology").unwrap_or("bounded"))?;
+    let kind = PatternKind::parse(args.get("pattern").unwrap_or("soup"))?;
+    let config = GeneratorConfig {
+        width,
+        height,
+        density: args.get_or("density", 0.3)?,
+        symmetry: Symmetry::parse(args.get("symmetry").unwrap_or("none"))?,
+        ..GeneratorConfig::default()
+    };
+    if width == 0 || height == 0 {
+        return Err(tr(Msg::LifeZeroSize).to_string());
+    }
+
+    let mut generator = PatternGenerator::new(config).map_err(|e| e.to_string())?;
+    let pattern = generator
+        .generate(kind, CANDIDATES, survive)
+        .into_iter()
+        .next()
+        .ok_or_else(|| trf(Msg::LifeNoSurvivor, &[&CANDIDATES, &survive]))?;
+
+    // 放在网格中央，随机汤的尺寸与网格相同
+    let (grid_width, grid_height) = (width.max(pattern.width), height.max(pattern.height));
+    let mut grid = LifeGrid::with_topology(grid_width, grid_height, topology);
+    pattern.place(&mut grid, (grid_width - pattern.width) / 2, (grid_height - pattern.height) / 2);
+    let initial = grid.clone();
+
+    let mut progress = options.progress(tr(Msg::LifeProgress), generations as u64);
+    let initial_population = grid.count_live_cells();
+    let mut peak_population = initial_population;
+    let mut detector = CycleDetector::new(grid_width, grid_height);
+    detector.observe(&grid);
+    for _ in 0..generations {
+        grid.next_generation();
+        peak_population = peak_population.max(grid.count_live_cells());
+        detector.observe(&grid);
+        progress.inc(1);
+    }
+    progress.finish();
+    let final_population = grid.count_live_cells();
+
+    if let Some(path) = args.get("gif") {
+        let export = GifExportOptions {
+            generations,
+            every: args.get_or("every", 1)?,
+            cell_size: args.get_or("cell-size", 4)?,
+            delay: args.get_or("delay", 10)?,
+            annotate: !args.flag("no-annotate"),
+        };
+        let gif = export_gif(&initial, &export)?;
+        fs::write(path, gif).map_err(|e| trf(Msg::CliWriteFailed, &[&path, &e]))?;
+    }
+
+    options.emit(
+        || {
+            let mut output = trf(Msg::LifeSummary, &[
+                &grid.width(),
+                &grid.height(),
+                &generations,
+                &initial_population,
+                &final_population,
+                &peak_population,
+            ]);
+            match detector.cycle() {
+                Some(cycle) => output.push_str(&trf(Msg::LifeCycle, &[&cycle.period, &cycle.pre_period])),
+                None => output.push_str(tr(Msg::LifeNoCycle)),
+            }
+            if let Some(path) = args.get("gif") {
+                output.push_str(&format!("GIF: {}\n", path));
+            }
+            output
+        },
+        || {
+            Json::object(vec![
+                ("width", Json::from(grid.width())),
+                ("height", Json::from(grid.height())),
+                ("generations", Json::from(generations)),
+                ("initial_population", Json::from(initial_population)),
+                ("final_population", Json::from(final_population)),
+                ("peak_population", Json::from(peak_population)),
+                ("cycle", detector.cycle().map_or(Json::Null, |cycle| {
+                    Json::object(vec![("period", Json::from(cycle.period)), ("pre_period", Json::from(cycle.pre_period))])
+                })),
+                ("gif", Json::from(args.get("gif"))),
+            ])
+        },
+    );
+    Ok(())
+}
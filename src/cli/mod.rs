@@ -0,0 +1,147 @@
+//! 命令行工具模块
+//! 
+//! `gamelife` 可执行文件的子命令解析与分发
+
+pub mod args;
+pub mod compat;
+pub mod config;
+pub mod dev;
+pub mod entropy;
+pub mod life;
+pub mod rom;
+pub mod run;
+pub mod selftest;
+pub mod tournament;
+
+pub use args::Args;
+
+use crate::config::Config;
+use crate::i18n::{self, tr, trf, Locale, Msg};
+use crate::util::{Json, Progress};
+
+/// 各子命令中不带值的开关，`GlobalOptions::extract` 据此判断下一个参数是不是选项的值
+const SUBCOMMAND_SWITCHES: &[&str] = &["watch", "fast", "no-annotate", "help"];
+
+/// 所有子命令共用的全局选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlobalOptions {
+    pub quiet: bool,
+    pub json: bool,
+}
+
+impl GlobalOptions {
+    /// 从参数中取出全局选项，返回其余参数
+    ///
+    /// 子命令选项的值原样保留，即使它看起来像全局选项；`--` 之后的参数不再解析。
+    fn extract(argv: &[String]) -> Result<(Self, Option<Locale>, Vec<String>), String> {
+        let mut options = Self::default();
+        let mut locale = None;
+        let mut rest = Vec::new();
+        let mut iter = argv.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--" => {
+                    rest.push(arg.clone());
+                    rest.extend(iter.cloned());
+                    break;
+                }
+                "-q" | "--quiet" => options.quiet = true,
+                "--json" => options.json = true,
+                "--lang" => locale = Some(Locale::parse(iter.next().ok_or_else(|| tr(Msg::CliLangMissing).to_string())?)?),
+                _ => {
+                    if let Some(name) = arg.strip_prefix("--lang=") {
+                        locale = Some(Locale::parse(name)?);
+                        continue;
+                    }
+                    rest.push(arg.clone());
+                    let takes_value = arg
+                        .strip_prefix("--")
+                        .is_some_and(|name| !name.contains('=') && !SUBCOMMAND_SWITCHES.contains(&name));
+                    if takes_value {
+                        rest.extend(iter.next().cloned());
+                    }
+                }
+            }
+        }
+        Ok((options, locale, rest))
+    }
+
+    /// 创建遵循全局选项的进度条
+    pub fn progress(&self, label: &str, total: u64) -> Progress {
+        Progress::bar(label, total).quiet(self.quiet)
+    }
+
+    /// 按输出模式打印结果：JSON模式输出 `json`，否则输出 `human`
+    pub fn emit(&self, human: impl FnOnce() -> String, json: impl FnOnce() -> Json) {
+        if self.json {
+            println!("{}", json().to_pretty());
+        } else {
+            print!("{}", human());
+        }
+    }
+}
+
+/// 执行命令行，参数不包含程序名
+pub fn run(argv: &[String]) -> Result<(), String> {
+    let (options, locale, argv) = GlobalOptions::extract(argv)?;
+    // 命令行的 --lang 优先于 GAMEBOY_LOCALE 和 LANG
+    i18n::set_locale(match locale {
+        Some(locale) => locale,
+        None => Locale::from_config(&Config::from_env())?,
+    });
+    let Some((command, rest)) = argv.split_first() else {
+        println!("{}", tr(Msg::CliUsage));
+        return Ok(());
+    };
+
+    match command.as_str() {
+        "tournament" => tournament::run(rest, &options),
+        "entropy" => entropy::run(rest, &options),
+        "life" => life::run(rest, &options),
+        "rom" => rom::run(rest, &options),
+        "run" => run::run(rest, &options),
+        "config" => config::run(rest, &options),
+        "dev" => dev::run(rest, &options),
+        "selftest" => selftest::run(rest, &options),
+        "help" | "--help" | "-h" => {
+            println!("{}", tr(Msg::CliUsage));
+            Ok(())
+        }
+        other => Err(trf(Msg::CliUnknownCommand, &[&other, &tr(Msg::CliUsage)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_extract_global_options_anywhere() {
+        let (options, locale, rest) = GlobalOptions::extract(&argv(&["-q", "run", "game.gb", "--json", "--lang=en"])).unwrap();
+        assert!(options.quiet && options.json);
+        assert_eq!(locale, Some(Locale::En));
+        assert_eq!(rest, argv(&["run", "game.gb"]));
+
+        // 开关后面的全局选项照常取出
+        let (options, _, rest) = GlobalOptions::extract(&argv(&["run", "--watch", "--quiet", "game.gb"])).unwrap();
+        assert!(options.quiet);
+        assert_eq!(rest, argv(&["run", "--watch", "game.gb"]));
+        assert!(GlobalOptions::extract(&argv(&["--lang"])).is_err());
+    }
+
+    #[test]
+    fn test_extract_keeps_option_values_and_stops_at_double_dash() {
+        let (options, _, rest) = GlobalOptions::extract(&argv(&["config", "set", "--value", "--json", "-q"])).unwrap();
+        assert!(options.quiet && !options.json);
+        assert_eq!(rest, argv(&["config", "set", "--value", "--json"]));
+
+        let (options, locale, rest) = GlobalOptions::extract(&argv(&["rom", "info", "--", "-q", "--lang=en"])).unwrap();
+        assert!(!options.quiet);
+        assert_eq!(locale, None);
+        assert_eq!(rest, argv(&["rom", "info", "--", "-q", "--lang=en"]));
+    }
+}
@@ -0,0 +1,450 @@
+//! `gamelife rom` 子命令
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::emulator::slots::{format_play_time, format_timestamp, parse_slot};
+use crate::emulator::SaveSlots;
+use crate::gba::GbaHeader;
+use crate::i18n::{tr, trf, Msg};
+use crate::rom::{RomHeader, NINTENDO_LOGO};
+use crate::util::hash::{sha1, to_hex};
+use crate::util::Json;
+
+use super::compat::{self, CompatReport, Watchdog};
+use super::{Args, GlobalOptions};
+
+const VERIFY_OPTIONS: [&str; 5] = ["frames", "stall-frames", "timeout", "format", "output"];
+
+/// ROM平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    GameBoy,
+    GameBoyAdvance,
+}
+
+impl Platform {
+    /// 平台名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Platform::GameBoy => "Game Boy",
+            Platform::GameBoyAdvance => "Game Boy Advance",
+        }
+    }
+}
+
+/// 报告中的一个字段
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// JSON键名
+    pub key: &'static str,
+    /// 显示名称
+    pub label: &'static str,
+    pub text: String,
+    pub json: Json,
+}
+
+/// 一项校验结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// ROM分析报告
+#[derive(Debug, Clone, PartialEq)]
+pub struct RomInfo {
+    pub platform: Platform,
+    pub size: usize,
+    pub sha1: String,
+    pub title: String,
+    pub fields: Vec<Field>,
+    pub checks: Vec<Check>,
+}
+
+fn field(key: &'static str, label: &'static str, text: impl Into<String>, json: impl Into<Json>) -> Field {
+    Field { key, label, text: text.into(), json: json.into() }
+}
+
+fn check(key: &'static str, label: &'static str, passed: bool, detail: impl Into<String>) -> Check {
+    Check { key, label, passed, detail: detail.into() }
+}
+
+impl RomInfo {
+    /// 分析ROM数据，按头部特征判断平台
+    pub fn analyze(data: &[u8]) -> Result<Self, String> {
+        let has_gb_logo = data.len() >= 0x150 && data[0x104..0x134] == NINTENDO_LOGO;
+        if !has_gb_logo && data.len() >= 0xC0 && data[0xB2] == 0x96 {
+            Ok(Self::analyze_gba(data))
+        } else {
+            Self::analyze_gb(data)
+        }
+    }
+
+    fn analyze_gb(data: &[u8]) -> Result<Self, String> {
+        let header = RomHeader::parse(data).map_err(|e| e.to_string())?;
+        let header_checksum = header.calculate_header_checksum();
+        let global_checksum = header.calculate_global_checksum(data);
+        let banks = header.rom_banks();
+        let expected_size = banks.map(|banks| banks as usize * 16 * 1024);
+        let cgb = match header.cgb_flag() {
+            0xC0 => tr(Msg::RomCgbOnly),
+            0x80 => tr(Msg::RomCgbCompatible),
+            _ => "DMG",
+        };
+        let manufacturer = header.manufacturer_code();
+        let manufacturer = if manufacturer.iter().all(|b| b.is_ascii_alphanumeric()) {
+            String::from_utf8_lossy(&manufacturer).into_owned()
+        } else {
+            String::new()
+        };
+        let licensee = if header.old_licensee_code == 0x33 {
+            String::from_utf8_lossy(&header.new_licensee_code).into_owned()
+        } else {
+            format!("{:02X}", header.old_licensee_code)
+        };
+
+        let fields = vec![
+            field("manufacturer_code", tr(Msg::RomManufacturerCode), manufacturer.clone(), manufacturer),
+            field("cgb", tr(Msg::RomCgbMode), format!("{} (0x{:02X})", cgb, header.cgb_flag()), cgb),
+            field("sgb", tr(Msg::RomSgbSupport), tr(if header.sgb_flag == 0x03 { Msg::Yes } else { Msg::No }), header.sgb_flag == 0x03),
+            field(
+                "cartridge_type",
+                tr(Msg::RomCartridgeType),
+                format!("0x{:02X}", header.cartridge_type),
+                header.cartridge_type,
+            ),
+            field("mapper", tr(Msg::RomMapper), header.mapper(), header.mapper()),
+            field(
+                "rom_banks",
+                tr(Msg::RomBanks),
+                banks.map_or_else(|| trf(Msg::RomUnknownCode, &[&format!("{:02X}", header.rom_size)]), |b| format!("{} x 16KB", b)),
+                banks,
+            ),
+            field(
+                "ram_bytes",
+                tr(Msg::RomExternalRam),
+                header
+                    .ram_bytes()
+                    .map_or_else(|| trf(Msg::RomUnknownCode, &[&format!("{:02X}", header.ram_size)]), |b| format!("{} KB", b / 1024)),
+                header.ram_bytes(),
+            ),
+            field(
+                "destination",
+                tr(Msg::RomDestination),
+                tr(if header.destination_code == 0 { Msg::RomJapan } else { Msg::RomOverseas }),
+                header.destination_code,
+            ),
+            field("licensee", tr(Msg::RomLicensee), licensee.clone(), licensee),
+            field("version", tr(Msg::RomVersion), header.rom_version.to_string(), header.rom_version),
+        ];
+
+        let checks = vec![
+            check("logo", "Nintendo Logo", header.nintendo_logo == NINTENDO_LOGO, ""),
+            check(
+                "header_checksum",
+                tr(Msg::RomHeaderChecksum),
+                header.header_checksum == header_checksum,
+                trf(Msg::RomStoredComputed, &[&format!("{:02X}", header.header_checksum), &format!("{:02X}", header_checksum)]),
+            ),
+            check(
+                "global_checksum",
+                tr(Msg::RomGlobalChecksum),
+                header.global_checksum == global_checksum,
+                trf(Msg::RomStoredComputed, &[&format!("{:04X}", header.global_checksum), &format!("{:04X}", global_checksum)]),
+            ),
+            check(
+                "size",
+                tr(Msg::RomFileSize),
+                expected_size == Some(data.len()),
+                match expected_size {
+                    Some(expected) => trf(Msg::RomSizeMismatch, &[&expected, &data.len()]),
+                    None => tr(Msg::RomInvalidSizeCode).to_string(),
+                },
+            ),
+        ];
+
+        Ok(Self {
+            platform: Platform::GameBoy,
+            size: data.len(),
+            sha1: to_hex(&sha1(data)),
+            title: header.title_text(),
+            fields,
+            checks,
+        })
+    }
+
+    fn analyze_gba(data: &[u8]) -> Self {
+        // analyze 已确认长度至少为0xC0
+        let header = GbaHeader::parse(data).expect("GBA头部长度已检查");
+        let fields = vec![
+            field("game_code", tr(Msg::RomGameCode), header.game_code.clone(), header.game_code.clone()),
+            field("maker_code", tr(Msg::RomManufacturerCode), header.maker_code.clone(), header.maker_code.clone()),
+            field("unit_code", tr(Msg::RomUnitCode), format!("0x{:02X}", header.unit_code), header.unit_code),
+            field("device_type", tr(Msg::RomDeviceType), format!("0x{:02X}", header.device_type), header.device_type),
+            field("version", tr(Msg::RomVersion), header.version.to_string(), header.version),
+        ];
+        let checks = vec![
+            check(
+                "entry_point",
+                tr(Msg::RomEntryPoint),
+                header.entry_is_branch(),
+                format!("0x{:08X}", header.entry_instruction),
+            ),
+            check("fixed_value", tr(Msg::RomFixedValue), header.fixed_value == 0x96, ""),
+            check(
+                "header_checksum",
+                tr(Msg::RomHeaderChecksum),
+                header.checksum_valid(),
+                trf(Msg::RomStoredComputed, &[&format!("{:02X}", header.complement_check), &format!("{:02X}", header.expected_check)]),
+            ),
+        ];
+
+        Self {
+            platform: Platform::GameBoyAdvance,
+            size: data.len(),
+            sha1: to_hex(&sha1(data)),
+            title: header.title,
+            fields,
+            checks,
+        }
+    }
+
+    /// 读取并分析ROM文件
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let data = fs::read(path).map_err(|e| trf(Msg::CliReadFailed, &[&path, &e]))?;
+        Self::analyze(&data)
+    }
+
+    /// 所有校验是否通过
+    pub fn is_valid(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// JSON格式的报告
+    pub fn to_json(&self) -> Json {
+        let fields = self.fields.iter().map(|f| (f.key, f.json.clone())).collect();
+        let checks = self
+            .checks
+            .iter()
+            .map(|c| (c.key, Json::object(vec![("passed", Json::from(c.passed)), ("detail", Json::from(c.detail.as_str()))])))
+            .collect();
+        Json::object(vec![
+            ("platform", Json::from(self.platform.name())),
+            ("title", Json::from(self.title.as_str())),
+            ("size", Json::from(self.size)),
+            ("sha1", Json::from(self.sha1.as_str())),
+            ("header", Json::object(fields)),
+            ("checks", Json::object(checks)),
+            ("valid", Json::from(self.is_valid())),
+        ])
+    }
+}
+
+impl fmt::Display for RomInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", trf(Msg::RomPlatform, &[&self.platform.name()]))?;
+        writeln!(f, "{}", trf(Msg::RomTitle, &[&self.title]))?;
+        writeln!(f, "{}", trf(Msg::RomSize, &[&self.size]))?;
+        writeln!(f, "SHA-1: {}", self.sha1)?;
+        for field in &self.fields {
+            writeln!(f, "{}: {}", field.label, field.text)?;
+        }
+        writeln!(f, "{}", tr(Msg::RomChecks))?;
+        for check in &self.checks {
+            let mark = if check.passed { "✓" } else { "✗" };
+            if check.detail.is_empty() {
+                writeln!(f, "  {} {}", mark, check.label)?;
+            } else {
+                writeln!(f, "  {} {} ({})", mark, check.label, check.detail)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 执行ROM子命令
+pub fn run(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    match argv.split_first() {
+        Some((command, rest)) if command == "info" => info(rest, options),
+        Some((command, rest)) if command == "verify" => verify(rest, options),
+        Some((command, rest)) if command == "slots" => slots(rest, options),
+        Some((command, _)) if command != "--help" && command != "-h" => {
+            Err(trf(Msg::CliUnknownSubcommand, &[&"rom", command, &tr(Msg::RomUsage)]))
+        }
+        _ => {
+            println!("{}", tr(Msg::RomUsage));
+            Ok(())
+        }
+    }
+}
+
+/// 显示ROM报告
+fn info(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&[])?;
+    let [path] = args.positional.as_slice() else {
+        return Err(trf(Msg::RomNeedFile, &[&tr(Msg::RomUsage)]));
+    };
+
+    let info = RomInfo::from_file(path)?;
+    options.emit(|| info.to_string(), || info.to_json());
+    Ok(())
+}
+
+/// 列出或删除ROM的存档槽位
+fn slots(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&["delete"])?;
+    let [path] = args.positional.as_slice() else {
+        return Err(trf(Msg::RomNeedFile, &[&tr(Msg::RomUsage)]));
+    };
+
+    let slots = SaveSlots::new(path);
+    if let Some(slot) = args.get("delete") {
+        let slot = parse_slot(slot)?;
+        slots.delete(slot)?;
+        println!("{}", trf(Msg::RomSlotDeleted, &[&slot]));
+        return Ok(());
+    }
+
+    let entries = slots.list();
+    options.emit(
+        || {
+            if entries.is_empty() {
+                return format!("{}\n", tr(Msg::RomSlotsEmpty));
+            }
+            entries
+                .iter()
+                .map(|(slot, metadata)| match metadata {
+                    Ok(metadata) => {
+                        let play_time = format_play_time(metadata.play_time);
+                        trf(Msg::RomSlotLine, &[slot, &metadata.rom_title, &play_time, &format_timestamp(metadata.saved_at)]) + "\n"
+                    }
+                    Err(error) => trf(Msg::RomSlotBroken, &[slot, error]) + "\n",
+                })
+                .collect()
+        },
+        || {
+            Json::Array(
+                entries
+                    .iter()
+                    .map(|(slot, metadata)| match metadata {
+                        Ok(metadata) => Json::object(vec![
+                            ("slot", Json::from(*slot)),
+                            ("title", Json::from(metadata.rom_title.as_str())),
+                            ("checksum", Json::from(metadata.rom_checksum)),
+                            ("saved_at", Json::from(metadata.saved_at)),
+                            ("play_time_secs", Json::from(metadata.play_time.as_secs())),
+                            ("thumbnail", Json::from(metadata.thumbnail.is_some())),
+                        ]),
+                        Err(error) => Json::object(vec![("slot", Json::from(*slot)), ("error", Json::from(error.as_str()))]),
+                    })
+                    .collect(),
+            )
+        },
+    );
+    Ok(())
+}
+
+/// `--timeout` 的秒数，必须是有限的正数
+fn parse_timeout(args: &Args, default: Duration) -> Result<Duration, String> {
+    let secs = args.get_or("timeout", default.as_secs_f64())?;
+    Duration::try_from_secs_f64(secs)
+        .ok()
+        .filter(|timeout| secs.is_finite() && !timeout.is_zero())
+        .ok_or_else(|| trf(Msg::ArgsInvalidValue, &[&"timeout", &secs]))
+}
+
+/// 批量运行ROM并输出兼容性报告
+fn verify(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&VERIFY_OPTIONS)?;
+    let [dir] = args.positional.as_slice() else {
+        return Err(trf(Msg::RomNeedDir, &[&tr(Msg::RomUsage)]));
+    };
+
+    let defaults = Watchdog::default();
+    let frames = args.get_or("frames", 60u32)?;
+    let watchdog = Watchdog {
+        stall_frames: args.get_or("stall-frames", defaults.stall_frames)?.max(1),
+        timeout: parse_timeout(&args, defaults.timeout)?,
+    };
+    let format = if options.json { "json" } else { args.get("format").unwrap_or("markdown") };
+    if format != "markdown" && format != "json" {
+        return Err(trf(Msg::CliUnknownFormat, &[&format]));
+    }
+
+    let roms = compat::find_roms(Path::new(dir))?;
+    let mut report = CompatReport { frames, results: Vec::new() };
+    let mut progress = options.progress(tr(Msg::RomVerifyProgress), roms.len() as u64);
+    for path in &roms {
+        let file = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+        let result = match fs::read(path) {
+            Ok(data) => compat::check_rom(&file, &data, frames, &watchdog),
+            Err(e) => compat::load_failed(&file, trf(Msg::RomReadError, &[&e])),
+        };
+        report.results.push(result);
+        progress.inc(1);
+    }
+    progress.finish();
+
+    let output = if format == "json" { report.to_json().to_pretty() + "\n" } else { report.to_markdown() };
+    match args.get("output") {
+        Some(path) => fs::write(path, output).map_err(|e| trf(Msg::CliWriteFailed, &[&path, &e])),
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gba::GbaRomBuilder;
+    use crate::rom::RomGenerator;
+
+    #[test]
+    fn test_rom_info_reports_gb_and_gba() {
+        let mut rom = RomGenerator::new("INFO TEST").cartridge_type(0x03).ram_size(0x02).program(0x150, &[0x00]).build().unwrap();
+        let info = RomInfo::analyze(&rom).unwrap();
+        assert_eq!(info.platform, Platform::GameBoy);
+        assert_eq!(info.title, "INFO TEST");
+        assert!(info.is_valid());
+        let mapper = info.fields.iter().find(|f| f.key == "mapper").unwrap();
+        assert_eq!(mapper.text, "MBC1+RAM+BATTERY");
+        assert_eq!(info.sha1.len(), 40);
+        assert!(info.to_json().to_pretty().contains("\"rom_banks\": 2"));
+
+        rom[0x1000] ^= 0xFF;
+        let corrupted = RomInfo::analyze(&rom).unwrap();
+        let failed: Vec<&str> = corrupted.checks.iter().filter(|c| !c.passed).map(|c| c.key).collect();
+        assert_eq!(failed, vec!["global_checksum"]);
+
+        let gba = GbaRomBuilder::new("GBA INFO").build().unwrap();
+        let info = RomInfo::analyze(&gba).unwrap();
+        assert_eq!(info.platform, Platform::GameBoyAdvance);
+        assert_eq!(info.title, "GBA INFO");
+        assert!(info.is_valid());
+        assert!(info.to_string().contains("✓ 头部校验和"));
+    }
+
+    #[test]
+    fn test_timeout_must_be_positive_and_finite() {
+        let timeout = |value: &str| {
+            let args = Args::parse(&["--timeout".to_string(), value.to_string()], &[]).unwrap();
+            parse_timeout(&args, Duration::from_secs(10))
+        };
+        assert_eq!(timeout("2.5"), Ok(Duration::from_millis(2500)));
+        for invalid in ["0", "-1", "inf", "NaN", "1e300"] {
+            assert!(timeout(invalid).is_err(), "{}", invalid);
+        }
+        let args = Args::parse(&[], &[]).unwrap();
+        assert_eq!(parse_timeout(&args, Duration::from_secs(10)), Ok(Duration::from_secs(10)));
+    }
+}
@@ -0,0 +1,196 @@
+//! `gamelife run` 子命令
+//!
+//! 无界面运行ROM。`--watch` 模式监视ROM和符号文件，重新汇编后自动重新加载，
+//! 出错时等待下一次修改而不退出；配合 `--mark` 在第一次运行到标记点时保存快照，
+//! 之后每次重新加载都从标记点继续，缩短自制游戏的编辑-汇编-测试循环。
+
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::{keys, Config};
+use crate::cpu::OpcodeStats;
+use crate::debug::{DebuggerState, LogLevel};
+use crate::emulator::AdvancedGameBoy;
+use crate::frontend::watch::{HotReload, Reload};
+use crate::frontend::{FramePacer, Frontend, SyncMode};
+use crate::gpu::lcd::CYCLES_PER_FRAME;
+use crate::i18n::{tr, trf, Msg};
+use crate::util::alloc::AllocStats;
+use crate::util::{Json, ToJson};
+
+use super::{Args, GlobalOptions};
+
+const OPTIONS: [&str; 6] = ["watch", "fast", "sym", "mark", "frames", "opcode-stats"];
+
+/// 不监视时默认运行的帧数
+const DEFAULT_FRAMES: u64 = 60;
+
+/// 当前版本无法运行时检查文件变化的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 执行运行子命令
+pub fn run(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    if argv.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", tr(Msg::RunUsage));
+        return Ok(());
+    }
+
+    let args = Args::parse(argv, &["watch", "fast"])?;
+    args.reject_unknown(&OPTIONS)?;
+    let config = Config::from_env();
+    let rom_path = match args.positional.first() {
+        Some(path) => path.clone(),
+        None => config
+            .get(keys::ROM_PATH)
+            .map(|path| path.to_string())
+            .ok_or_else(|| tr(Msg::RunMissingRom).to_string())?,
+    };
+    let watch = args.flag("watch");
+    let frames = match args.get("frames") {
+        Some(_) => Some(args.get_or("frames", 0u64)?),
+        None => (!watch).then_some(DEFAULT_FRAMES),
+    };
+
+    let mut reload = HotReload::new(&rom_path);
+    if let Some(path) = args.get("sym") {
+        reload = reload.with_symbols(path);
+    }
+    if let Some(mark) = args.get("mark") {
+        reload = reload.with_mark(mark);
+    }
+
+    let mut gameboy = AdvancedGameBoy::new();
+    gameboy.debugger.set_log_level(LogLevel::Warning);
+    let opcode_stats_path = args.get("opcode-stats");
+    if opcode_stats_path.is_some() {
+        gameboy.enable_opcode_stats();
+    }
+    let mut frontend = Frontend::headless();
+    // 每帧固定运行一帧的时钟周期，帧长不随指令数变化
+    frontend.set_frame_cycles(Some(CYCLES_PER_FRAME as u64));
+    if watch && !args.flag("fast") {
+        // 无界面运行没有实时音频，音频模式会自动退回计时器
+        frontend.set_pacer(Some(FramePacer::new(SyncMode::from_config(&config)?)));
+    }
+
+    let notify = |message: String| {
+        if !options.quiet {
+            eprintln!("{}", message);
+        }
+    };
+
+    let mut broken = match reload.load(&mut gameboy).and_then(|loaded| prepare(&mut gameboy, &reload, loaded)) {
+        Ok(loaded) => {
+            notify(describe_load(&reload, &loaded));
+            false
+        }
+        Err(error) if watch => {
+            notify(trf(Msg::RunLoadFailed, &[&error]));
+            true
+        }
+        Err(error) => return Err(error),
+    };
+    if watch {
+        notify(trf(Msg::RunWatching, &[&rom_path]));
+        if broken {
+            notify(trf(Msg::RunWaiting, &[&rom_path]));
+        }
+    }
+
+    let alloc_start = AllocStats::snapshot();
+    let mut ran = 0u64;
+    while frames.is_none_or(|limit| ran < limit) {
+        if watch {
+            if let Some(result) = reload.poll(&mut gameboy, Instant::now()) {
+                match result.and_then(|loaded| prepare(&mut gameboy, &reload, loaded)) {
+                    Ok(loaded) => {
+                        notify(describe_load(&reload, &loaded));
+                        broken = false;
+                    }
+                    Err(error) => {
+                        notify(trf(Msg::RunLoadFailed, &[&error]));
+                        notify(trf(Msg::RunWaiting, &[&rom_path]));
+                        broken = true;
+                    }
+                }
+            }
+        }
+        if broken {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+
+        if let Err(error) = frontend.run_frame(&mut gameboy) {
+            let message = trf(Msg::RunCrashed, &[&format!("{:04X}", gameboy.cpu.pc), &error]);
+            if !watch {
+                return Err(message);
+            }
+            notify(message);
+            notify(trf(Msg::RunWaiting, &[&rom_path]));
+            broken = true;
+            continue;
+        }
+        ran += 1;
+
+        if gameboy.debugger.state == DebuggerState::BreakpointHit && reload.mark_address()? == Some(gameboy.cpu.pc) {
+            reload.capture(&gameboy)?;
+            gameboy.debugger.remove_breakpoint(gameboy.cpu.pc);
+            gameboy.debugger.resume();
+            notify(trf(Msg::RunMarkCaptured, &[&reload.mark().unwrap_or_default(), &format!("{:04X}", gameboy.cpu.pc)]));
+        }
+    }
+
+    if let (Some(path), Some(stats)) = (opcode_stats_path, gameboy.opcode_stats()) {
+        fs::write(path, stats.to_csv()).map_err(|e| trf(Msg::CliWriteFailed, &[&path, &e]))?;
+        notify(trf(Msg::RunOpcodeStatsWritten, &[&stats.ranked().len(), &path]));
+    }
+
+    // 开启 `alloc-stats` 时附上每帧的分配统计
+    let allocs = AllocStats::measured_since(&alloc_start).map(|stats| stats.per_frame(ran));
+    options.emit(
+        || {
+            let summary = trf(Msg::RunSummary, &[&ran, &format!("{:04X}", gameboy.cpu.pc), &reload.has_mark_state()]);
+            match &allocs {
+                Some(report) => format!("{}{}", summary, report),
+                None => summary,
+            }
+        },
+        || {
+            Json::object(vec![
+                ("rom", Json::from(rom_path.as_str())),
+                ("frames", Json::from(ran)),
+                ("pc", Json::from(gameboy.cpu.pc as u64)),
+                ("mark_captured", Json::from(reload.has_mark_state())),
+                ("instructions", Json::from(gameboy.opcode_stats().map(OpcodeStats::total_count))),
+                ("allocations", Json::from(allocs.as_ref().map(ToJson::to_json))),
+            ])
+        },
+    );
+    Ok(())
+}
+
+/// 加载之后启动模拟器；还没有标记点快照时在标记地址设置断点
+fn prepare(gameboy: &mut AdvancedGameBoy, reload: &HotReload, loaded: Reload) -> Result<Reload, String> {
+    gameboy.start();
+    if !reload.has_mark_state() {
+        if let Some(address) = reload.mark_address()? {
+            gameboy.debugger.set_breakpoint(address, None);
+        }
+    }
+    Ok(loaded)
+}
+
+fn describe_load(reload: &HotReload, loaded: &Reload) -> String {
+    let path = reload.rom_path().display();
+    let mut text = if loaded.generation == 1 {
+        trf(Msg::RunLoaded, &[&path, &loaded.rom_bytes, &loaded.symbols])
+    } else {
+        trf(Msg::RunReloaded, &[&path, &loaded.generation, &loaded.rom_bytes, &loaded.symbols])
+    };
+    if let Some(address) = loaded.restored {
+        text.push('\n');
+        text.push_str(&trf(Msg::RunRestored, &[&reload.mark().unwrap_or_default(), &format!("{:04X}", address)]));
+    }
+    text
+}
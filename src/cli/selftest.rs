@@ -0,0 +1,534 @@
+//! `gamelife selftest` 子命令
+//!
+//! 依次运行内置生成的测试ROM、指令性质测试、已知答案向量、熵源健康检测
+//! 和确定性的俄罗斯方块/生命游戏模拟，按类别打印通过/失败矩阵。
+//! 任何一项失败时命令返回错误，便于在CI或新平台上确认构建可用。
+
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::cpu::reference::ExecutionCore;
+use crate::cpu::{FlagsRegister, OptimizedCPU, ReferenceCPU};
+use crate::debug::lockstep::Lockstep;
+use crate::debug::LogLevel;
+use crate::emulator::{AdvancedGameBoy, GameBoy};
+use crate::entropy::entropy_source::{HardwareEntropy, MemoryEntropy, NetworkEntropy, ProcessEntropy, SystemTimeEntropy};
+use crate::entropy::rom_prng::cross_check;
+use crate::entropy::statistics::longest_repeat;
+use crate::entropy::{ConditioningMode, EntropyManager, EntropySource, StatisticalReport};
+use crate::frontend::tui::{pad, text_width, Align};
+use crate::frontend::Emulator;
+use crate::games::life_game::{find_cycle, Cycle, LifeGrid, PatternLibrary, Topology};
+use crate::games::tournament::{TetrisPlacer, XorShift};
+use crate::gba::GbaRomBuilder;
+use crate::i18n::{tr, trf, Msg};
+use crate::instructions::Instruction;
+use crate::memory::MemoryBus;
+use crate::rom::RomGenerator;
+use crate::util::alloc::AllocStats;
+use crate::util::hash::{sha1, sha256, to_hex};
+use crate::util::{Json, ToJson};
+
+use super::compat::{check_rom, Outcome, Watchdog};
+use super::rom::RomInfo;
+use super::{Args, GlobalOptions};
+
+/// 性质测试和模拟使用的固定种子
+const SEED: u64 = 0x5EED;
+
+/// 差分执行中每个操作码的随机初始状态数
+const LOCKSTEP_STATES: usize = 32;
+
+/// ROM伪随机数发生器交叉验证生成的字节数
+const PRNG_BYTES: usize = 256;
+
+/// 熵源健康检测的最少样本字节数
+const ENTROPY_SAMPLE: usize = 4096;
+
+/// 重复计数检测的截断值：每字节8比特熵、误报率2^-20时为 1 + ⌈20/8⌉
+const REPEAT_CUTOFF: usize = 4;
+
+/// 测试ROM的程序：INC C ×3; ADD A,C; LD B,C; DEC C; SUB C
+const GB_PROGRAM: [u8; 7] = [0x0C, 0x0C, 0x0C, 0x81, 0x41, 0x0D, 0x91];
+
+/// 自检类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Group {
+    Rom,
+    Cpu,
+    Vectors,
+    Entropy,
+    Simulation,
+}
+
+impl Group {
+    pub const ALL: [Group; 5] = [Group::Rom, Group::Cpu, Group::Vectors, Group::Entropy, Group::Simulation];
+
+    /// 从名称解析
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL.into_iter().find(|group| group.name() == name.trim()).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(Group::name).collect();
+            trf(Msg::SelftestUnknownGroup, &[&name, &names.join(", ")])
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Group::Rom => "rom",
+            Group::Cpu => "cpu",
+            Group::Vectors => "vectors",
+            Group::Entropy => "entropy",
+            Group::Simulation => "simulation",
+        }
+    }
+
+    /// 矩阵中显示的名称
+    fn label(&self) -> &'static str {
+        tr(match self {
+            Group::Rom => Msg::SelftestGroupRom,
+            Group::Cpu => Msg::SelftestGroupCpu,
+            Group::Vectors => Msg::SelftestGroupVectors,
+            Group::Entropy => Msg::SelftestGroupEntropy,
+            Group::Simulation => Msg::SelftestGroupSimulation,
+        })
+    }
+}
+
+/// 一项自检：通过时返回简短说明，失败时返回原因
+#[derive(Debug, Clone, Copy)]
+pub struct Check {
+    pub group: Group,
+    pub name: &'static str,
+    test: fn() -> Result<String, String>,
+}
+
+/// 所有自检项目，按类别排列
+pub const CHECKS: [Check; 13] = [
+    Check { group: Group::Rom, name: "gb-header", test: gb_header },
+    Check { group: Group::Rom, name: "gb-program", test: gb_program },
+    Check { group: Group::Rom, name: "gba-boot", test: gba_boot },
+    Check { group: Group::Cpu, name: "lockstep", test: cpu_lockstep },
+    Check { group: Group::Cpu, name: "inc-dec", test: cpu_inc_dec },
+    Check { group: Group::Cpu, name: "add-sub", test: cpu_add_sub },
+    Check { group: Group::Cpu, name: "rom-prng", test: cpu_rom_prng },
+    Check { group: Group::Vectors, name: "sha1", test: sha1_vectors },
+    Check { group: Group::Vectors, name: "sha256", test: sha256_vectors },
+    Check { group: Group::Entropy, name: "sources", test: entropy_sources },
+    Check { group: Group::Entropy, name: "output", test: entropy_output },
+    Check { group: Group::Simulation, name: "life-glider", test: life_glider },
+    Check { group: Group::Simulation, name: "tetris-replay", test: tetris_replay },
+];
+
+impl Check {
+    /// 运行检查，panic视为失败
+    pub fn run(&self) -> CheckResult {
+        let start = Instant::now();
+        let outcome = panic::catch_unwind(AssertUnwindSafe(self.test)).unwrap_or_else(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(trf(Msg::SelftestPanicked, &[&message]))
+        });
+        CheckResult { group: self.group, name: self.name, outcome, elapsed: start.elapsed() }
+    }
+}
+
+/// 一项自检的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckResult {
+    pub group: Group,
+    pub name: &'static str,
+    pub outcome: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+impl CheckResult {
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+impl ToJson for CheckResult {
+    fn to_json(&self) -> Json {
+        let (passed, detail) = match &self.outcome {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        Json::object(vec![
+            ("group", Json::from(self.group.name())),
+            ("name", Json::from(self.name)),
+            ("passed", Json::from(passed)),
+            ("detail", Json::from(detail.as_str())),
+            ("elapsed_ms", Json::from(self.elapsed.as_secs_f64() * 1000.0)),
+        ])
+    }
+}
+
+/// 通过/失败矩阵：每项一行，失败原因的后续行缩进显示在下方
+pub fn render(results: &[CheckResult]) -> String {
+    let group_width = results.iter().map(|r| text_width(r.group.label())).max().unwrap_or(0);
+    let name_width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    let status_width = text_width(tr(Msg::SelftestPass)).max(text_width(tr(Msg::SelftestFail)));
+
+    let mut text = String::new();
+    for result in results {
+        let (mark, status, detail) = match &result.outcome {
+            Ok(detail) => ("✓", tr(Msg::SelftestPass), detail),
+            Err(detail) => ("✗", tr(Msg::SelftestFail), detail),
+        };
+        let mut lines = detail.lines();
+        text.push_str(&format!(
+            "{}  {}  {} {}  {}  {}\n",
+            pad(result.group.label(), group_width, Align::Left),
+            pad(result.name, name_width, Align::Left),
+            mark,
+            pad(status, status_width, Align::Left),
+            pad(&format!("{}ms", result.elapsed.as_millis()), 6, Align::Right),
+            lines.next().unwrap_or(""),
+        ));
+        for line in lines {
+            text.push_str(&format!("{}{}\n", " ".repeat(group_width + name_width + status_width + 16), line));
+        }
+    }
+
+    let failed = results.iter().filter(|r| !r.passed()).count();
+    let elapsed: Duration = results.iter().map(|r| r.elapsed).sum();
+    text.push_str(&trf(Msg::SelftestSummary, &[&(results.len() - failed), &failed, &elapsed.as_millis()]));
+    text
+}
+
+/// 执行自检子命令
+pub fn run(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    if argv.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", tr(Msg::SelftestUsage));
+        return Ok(());
+    }
+
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&["group"])?;
+    let groups = match args.get("group") {
+        Some(list) => list.split(',').map(Group::parse).collect::<Result<Vec<_>, _>>()?,
+        None => Group::ALL.to_vec(),
+    };
+
+    let checks: Vec<&Check> = CHECKS.iter().filter(|check| groups.contains(&check.group)).collect();
+    let mut progress = options.progress(tr(Msg::SelftestProgress), checks.len() as u64);
+    let alloc_start = AllocStats::snapshot();
+    let mut results = Vec::new();
+    for check in checks {
+        results.push(check.run());
+        progress.inc(1);
+    }
+    progress.finish();
+    // 开启 `alloc-stats` 时附上每项自检的平均分配
+    let allocs = AllocStats::measured_since(&alloc_start).map(|stats| stats.per_check(results.len() as u64));
+
+    options.emit(
+        || match &allocs {
+            Some(report) => format!("{}{}", render(&results), report),
+            None => render(&results),
+        },
+        || {
+            let failed = results.iter().filter(|r| !r.passed()).count();
+            Json::object(vec![
+                ("passed", Json::from(results.len() - failed)),
+                ("failed", Json::from(failed)),
+                ("checks", Json::array(&results)),
+                ("allocations", Json::from(allocs.as_ref().map(ToJson::to_json))),
+            ])
+        },
+    );
+
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| !r.passed())
+        .map(|r| format!("{}/{}", r.group.name(), r.name))
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(trf(Msg::SelftestFailed, &[&failed.len(), &failed.join(", ")]))
+    }
+}
+
+fn expect(expected: &str, actual: &str) -> Result<(), String> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(trf(Msg::SelftestMismatch, &[&expected, &actual]))
+    }
+}
+
+fn gb_test_rom() -> Result<Vec<u8>, String> {
+    RomGenerator::new("SELFTEST").program(0x150, &GB_PROGRAM).build().map_err(|e| e.to_string())
+}
+
+/// 生成的GB ROM头部校验和有效
+fn gb_header() -> Result<String, String> {
+    let rom = gb_test_rom()?;
+    let info = RomInfo::analyze(&rom)?;
+    if !info.is_valid() {
+        return Err(tr(Msg::SelftestInvalidHeader).to_string());
+    }
+    Ok(format!("sha1={}", &to_hex(&sha1(&rom))[..8]))
+}
+
+/// 加载ROM，从入口执行 NOP; JP 0x150 和测试程序
+fn run_gb_program<E: Emulator>(emulator: &mut E, rom: &[u8]) -> Result<String, String> {
+    emulator.load_rom(rom)?;
+    for step in 1..=GB_PROGRAM.len() + 2 {
+        if let Err(message) = emulator.step() {
+            let pc = emulator.snapshot().map_or(String::from("?"), |state| format!("{:04X}", state.pc));
+            return Err(trf(Msg::SelftestStepFailed, &[&step, &pc, &message]));
+        }
+    }
+    let state = emulator.snapshot().ok_or_else(|| tr(Msg::SelftestNoSnapshot).to_string())?;
+    let registers = state.registers;
+    Ok(format!("A={:02X} B={:02X} C={:02X} PC={:04X}", registers.a, registers.b, registers.c, state.pc))
+}
+
+/// 参考核心和优化核心运行测试ROM得到预期的寄存器
+fn gb_program() -> Result<String, String> {
+    let rom = gb_test_rom()?;
+    let reference = run_gb_program(&mut GameBoy::new(), &rom)?;
+    let mut advanced = AdvancedGameBoy::new();
+    // 调试器的信息日志写到标准输出，会混进 `--json` 的结果
+    advanced.debugger.set_log_level(LogLevel::Warning);
+    advanced.start();
+    let optimized = run_gb_program(&mut advanced, &rom)?;
+
+    expect(&format!("A=01 B=03 C=02 PC={:04X}", 0x150 + GB_PROGRAM.len()), &reference)?;
+    if optimized != reference {
+        return Err(trf(Msg::SelftestCoresDiffer, &[&reference, &optimized]));
+    }
+    Ok(reference)
+}
+
+/// 生成的GBA ROM头部有效，并能在看门狗下运行两帧
+fn gba_boot() -> Result<String, String> {
+    let rom = GbaRomBuilder::new("SELFTEST").build()?;
+    if !RomInfo::analyze(&rom)?.is_valid() {
+        return Err(tr(Msg::SelftestInvalidHeader).to_string());
+    }
+    let result = check_rom("selftest.gba", &rom, 2, &Watchdog::default());
+    match result.outcome {
+        Outcome::Boots => Ok(format!("frames={}", result.frames)),
+        other => Err(format!("{}: {}", other.kind(), other.detail())),
+    }
+}
+
+/// 随机寄存器、标志和操作数；HL指向工作RAM，使 (HL) 寻址落在可写区域
+fn random_core(rng: &mut XorShift, opcode: u8) -> OptimizedCPU {
+    let [operand_low, operand_high, a, b, c, d, e, l] = rng.next_u64().to_le_bytes();
+    let mut bus = MemoryBus::new();
+    bus.load_program(0x100, &[opcode, operand_low, operand_high]);
+    let mut cpu = OptimizedCPU::new(bus);
+    cpu.registers.a = a;
+    cpu.registers.b = b;
+    cpu.registers.c = c;
+    cpu.registers.d = d;
+    cpu.registers.e = e;
+    cpu.registers.h = 0xC0 | (rng.next_u64() as u8 & 0x1F);
+    cpu.registers.l = l;
+    cpu.flags = FlagsRegister::from(rng.next_u64() as u8 & 0xF0);
+    cpu
+}
+
+/// 每个可解码的操作码从随机状态出发，优化核心与参考解释器执行结果一致
+fn cpu_lockstep() -> Result<String, String> {
+    let mut rng = XorShift::new(SEED);
+    let opcodes: Vec<u8> = (0..=255u8).filter(|&byte| Instruction::from_byte(byte).is_some()).collect();
+    for &opcode in &opcodes {
+        for _ in 0..LOCKSTEP_STATES {
+            let mut candidate = random_core(&mut rng, opcode);
+            Lockstep::new(&candidate, 1)
+                .run(&mut candidate, 1)
+                .map_err(|e| format!("0x{:02X}: {}", opcode, e))?;
+        }
+    }
+    Ok(format!("opcodes={} states={}", opcodes.len(), opcodes.len() * LOCKSTEP_STATES))
+}
+
+/// INC C 之后 DEC C 还原原值，零标志只在结果为0时置位
+fn cpu_inc_dec() -> Result<String, String> {
+    let mut bus = MemoryBus::new();
+    bus.load_program(0x100, &[0x0C, 0x0D]);
+    let mut cpu = OptimizedCPU::new(bus);
+    for value in 0..=255u8 {
+        cpu.pc = 0x100;
+        cpu.registers.c = value;
+        cpu.step_instruction()?;
+        let incremented = value.wrapping_add(1);
+        expect(
+            &format!("INC {:02X} = {:02X} Z={}", value, incremented, incremented == 0),
+            &format!("INC {:02X} = {:02X} Z={}", value, cpu.registers.c, cpu.flags.zero),
+        )?;
+        cpu.step_instruction()?;
+        expect(
+            &format!("DEC {:02X} = {:02X} Z={}", incremented, value, value == 0),
+            &format!("DEC {:02X} = {:02X} Z={}", incremented, cpu.registers.c, cpu.flags.zero),
+        )?;
+    }
+    Ok("values=256".to_string())
+}
+
+/// 对所有A、C组合，ADD A,C 之后 SUB C 还原A，进位标志符合算术定义
+fn cpu_add_sub() -> Result<String, String> {
+    let mut bus = MemoryBus::new();
+    bus.load_program(0x100, &[0x81, 0x91]);
+    let mut cpu = OptimizedCPU::new(bus);
+    for a in 0..=255u8 {
+        for c in 0..=255u8 {
+            cpu.pc = 0x100;
+            cpu.registers.a = a;
+            cpu.registers.c = c;
+            cpu.step_instruction()?;
+            let (sum, carry) = a.overflowing_add(c);
+            expect(
+                &format!("{:02X}+{:02X} = {:02X} C={}", a, c, sum, carry),
+                &format!("{:02X}+{:02X} = {:02X} C={}", a, c, cpu.registers.a, cpu.flags.carry),
+            )?;
+            cpu.step_instruction()?;
+            expect(
+                &format!("{:02X}-{:02X} = {:02X} C={}", sum, c, a, sum < c),
+                &format!("{:02X}-{:02X} = {:02X} C={}", sum, c, cpu.registers.a, cpu.flags.carry),
+            )?;
+        }
+    }
+    Ok("pairs=65536".to_string())
+}
+
+/// 两个核心运行生成的伪随机数发生器ROM，逐条指令与宿主端参考模型一致
+fn cpu_rom_prng() -> Result<String, String> {
+    let optimized = cross_check::<OptimizedCPU>(SEED as u16, PRNG_BYTES).map_err(|e| e.to_string())?;
+    cross_check::<ReferenceCPU>(SEED as u16, PRNG_BYTES).map_err(|e| e.to_string())?;
+    Ok(format!(
+        "bytes={} instructions={} cycles={}",
+        optimized.bytes.len(),
+        optimized.instructions,
+        optimized.cycles.unwrap_or(0)
+    ))
+}
+
+fn sha1_vectors() -> Result<String, String> {
+    expect("da39a3ee5e6b4b0d3255bfef95601890afd80709", &to_hex(&sha1(b"")))?;
+    expect("a9993e364706816aba3e25717850c26c9cd0d89d", &to_hex(&sha1(b"abc")))?;
+    expect(
+        "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+        &to_hex(&sha1(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+    )?;
+    Ok("vectors=3".to_string())
+}
+
+fn sha256_vectors() -> Result<String, String> {
+    expect("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855", &to_hex(&sha256(b"")))?;
+    expect("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", &to_hex(&sha256(b"abc")))?;
+    expect(
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        &to_hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+    )?;
+    Ok("vectors=3".to_string())
+}
+
+/// 每个熵源都可用，连续两次采集的输出不同
+fn entropy_sources() -> Result<String, String> {
+    let mut sources: Vec<Box<dyn EntropySource>> = vec![
+        Box::new(SystemTimeEntropy::new()),
+        Box::new(HardwareEntropy::new()),
+        Box::new(NetworkEntropy::new()),
+        Box::new(ProcessEntropy::new()),
+        Box::new(MemoryEntropy::new()),
+    ];
+    for source in &mut sources {
+        let kind = format!("{:?}", source.get_type());
+        if !source.is_available() {
+            return Err(trf(Msg::SelftestSourceUnavailable, &[&kind]));
+        }
+        let first = source.collect_entropy().map_err(|e| format!("{}: {}", kind, e))?;
+        let second = source.collect_entropy().map_err(|e| format!("{}: {}", kind, e))?;
+        if first.is_empty() || first == second {
+            return Err(trf(Msg::SelftestSourceStuck, &[&kind]));
+        }
+    }
+    Ok(format!("sources={}", sources.len()))
+}
+
+/// 标准调理后的输出通过重复计数检测和统计检验
+fn entropy_output() -> Result<String, String> {
+    let mut manager = EntropyManager::new();
+    manager.set_conditioning(ConditioningMode::Standard);
+    let mut output = Vec::new();
+    while output.len() < ENTROPY_SAMPLE {
+        output.extend(manager.collect_and_optimize().map_err(|e| e.to_string())?);
+    }
+
+    let repeat = longest_repeat(&output);
+    if repeat >= REPEAT_CUTOFF {
+        return Err(trf(Msg::SelftestRepeated, &[&repeat]));
+    }
+    let report = StatisticalReport::analyze(&output);
+    if !report.passes() {
+        return Err(report.to_string());
+    }
+    Ok(format!("bytes={} chi={:.1} max_repeat={}", output.len(), report.chi_square, repeat))
+}
+
+/// 8x8环面上的滑翔机每32代回到原位
+fn life_glider() -> Result<String, String> {
+    let library = PatternLibrary::builtin();
+    let glider = library.get("glider").ok_or_else(|| trf(Msg::SelftestMismatch, &[&"glider", &"-"]))?;
+    let mut grid = LifeGrid::with_topology(8, 8, Topology::Torus);
+    glider.pattern().stamp(&mut grid, 1, 1);
+
+    let expected = Cycle { period: 32, pre_period: 0 };
+    match find_cycle(&grid, 100) {
+        Some(cycle) if cycle == expected => Ok(format!("period={} cells={}", cycle.period, grid.count_live_cells())),
+        other => Err(trf(Msg::SelftestMismatch, &[&format!("{:?}", expected), &format!("{:?}", other)])),
+    }
+}
+
+/// 相同种子的俄罗斯方块AI对局结果完全一致
+fn tetris_replay() -> Result<String, String> {
+    let first = TetrisPlacer::new(Some(1), SEED).play(100);
+    let second = TetrisPlacer::new(Some(1), SEED).play(100);
+    if first != second {
+        return Err(trf(Msg::SelftestNondeterministic, &[&format!("{:?}", first), &format!("{:?}", second)]));
+    }
+    if first.pieces == 0 {
+        return Err(trf(Msg::SelftestMismatch, &[&"pieces>0", &"pieces=0"]));
+    }
+    Ok(format!("score={} lines={} pieces={}", first.score, first.lines_cleared, first.pieces))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks_pass_and_render_matrix() {
+        let names = ["inc-dec", "rom-prng", "sha1", "sha256", "life-glider", "tetris-replay"];
+        let mut results: Vec<CheckResult> =
+            CHECKS.iter().filter(|check| names.contains(&check.name)).map(Check::run).collect();
+        for result in &results {
+            assert!(result.passed(), "{}: {:?}", result.name, result.outcome);
+        }
+
+        results.push(CheckResult {
+            group: Group::Rom,
+            name: "broken",
+            outcome: Err("first\nsecond".to_string()),
+            elapsed: Duration::ZERO,
+        });
+        let matrix = render(&results);
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert_eq!(lines.len(), results.len() + 2);
+        assert!(lines[0].contains("inc-dec") && lines[0].contains('✓'));
+        assert!(lines[6].contains("broken") && lines[6].contains('✗') && lines[6].ends_with("first"));
+        assert!(lines[7].trim() == "second");
+
+        assert_eq!(Group::parse("entropy"), Ok(Group::Entropy));
+        assert!(Group::parse("audio").is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! `gamelife tournament` 子命令
+
+use std::fs;
+
+use crate::games::tournament::{AgentConfig, GameKind, Tournament, TournamentConfig};
+use crate::i18n::{tr, trf, Msg};
+use crate::util::ToJson;
+
+use super::{Args, GlobalOptions};
+
+const OPTIONS: [&str; 9] = [
+    "games", "agents", "rounds", "tetris-runs", "tetris-pieces", "threads", "seed", "format", "output",
+];
+
+/// 执行锦标赛子命令
+pub fn run(argv: &[String], options: &GlobalOptions) -> Result<(), String> {
+    if argv.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", tr(Msg::TournamentUsage));
+        return Ok(());
+    }
+
+    let args = Args::parse(argv, &[])?;
+    args.reject_unknown(&OPTIONS)?;
+
+    let defaults = TournamentConfig::default();
+    let config = TournamentConfig {
+        games: match args.get("games") {
+            Some(list) => list.split(',').map(GameKind::parse).collect::<Result<_, _>>()?,
+            None => defaults.games,
+        },
+        agents: match args.get("agents") {
+            Some(list) => AgentConfig::parse_list(list)?,
+            None => defaults.agents,
+        },
+        rounds: args.get_or("rounds", defaults.rounds)?,
+        tetris_runs: args.get_or("tetris-runs", defaults.tetris_runs)?,
+        tetris_pieces: args.get_or("tetris-pieces", defaults.tetris_pieces)?,
+        threads: args.get_or("threads", defaults.threads)?,
+        seed: args.get_or("seed", defaults.seed)?,
+    };
+
+    let format = args.get("format").unwrap_or("markdown");
+    if format != "markdown" && format != "csv" {
+        return Err(trf(Msg::CliUnknownFormat, &[&format]));
+    }
+
+    let tournament = Tournament::new(config)?;
+    let mut progress = options.progress(tr(Msg::TournamentProgress), tournament.job_count() as u64);
+    let report = tournament.run(|done, _| progress.set(done as u64));
+    progress.finish();
+    if options.json {
+        let output = report.to_json().to_pretty();
+        return match args.get("output") {
+            Some(path) => fs::write(path, output).map_err(|e| trf(Msg::CliWriteFailed, &[&path, &e])),
+            None => {
+                println!("{}", output);
+                Ok(())
+            }
+        };
+    }
+
+    let output = if format == "csv" { report.to_csv() } else { report.to_markdown() };
+    match args.get("output") {
+        Some(path) => fs::write(path, output).map_err(|e| trf(Msg::CliWriteFailed, &[&path, &e])),
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! 通用配置管理
+//! 
+//! 提供统一的配置管理接口，支持从文件、环境变量和默认值加载配置
+//!
+//! 同一个键有多个来源时按以下优先级取值，与加载顺序无关：
+//!
+//! 1. 命令行（以及代码中通过 `set` 显式设置的值）
+//! 2. `GAMEBOY_` 前缀的环境变量，如 `GAMEBOY_LOG_LEVEL=debug`
+//! 3. 配置文件
+//! 4. 模式中的默认值
+//!
+//! `Config::provenance` 可以查询某个键的值来自哪里。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::i18n::{tr, trf, Msg};
+
+pub mod schema;
+
+pub use schema::{ConfigKey, Diagnostic, ValueType};
+
+/// 环境变量前缀
+pub const ENV_PREFIX: &str = "GAMEBOY_";
+
+/// 配置值的来源，按优先级从低到高排列
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// 模式中的默认值
+    Default,
+    /// 配置文件的某一行
+    File { path: PathBuf, line: usize },
+    /// 环境变量
+    Env(String),
+    /// 命令行或代码中显式设置
+    Cli,
+}
+
+impl ConfigSource {
+    /// 优先级，数值大的覆盖数值小的
+    pub fn precedence(&self) -> u8 {
+        match self {
+            ConfigSource::Default => 0,
+            ConfigSource::File { .. } => 1,
+            ConfigSource::Env(_) => 2,
+            ConfigSource::Cli => 3,
+        }
+    }
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "{}", tr(Msg::SourceDefault)),
+            ConfigSource::File { path, line } => write!(f, "{}", trf(Msg::SourceFile, &[&path.display(), line])),
+            ConfigSource::Env(var) => write!(f, "{}", trf(Msg::SourceEnv, &[var])),
+            ConfigSource::Cli => write!(f, "{}", tr(Msg::SourceCli)),
+        }
+    }
+}
+
+/// 一个配置值及其来源
+#[derive(Debug, Clone)]
+struct Entry {
+    value: String,
+    source: ConfigSource,
+}
+
+/// 配置管理器
+#[derive(Debug, Clone)]
+pub struct Config {
+    values: HashMap<String, Entry>,
+}
+
+impl Config {
+    /// 创建新的配置实例
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+    
+    /// 包含模式中所有默认值的配置
+    pub fn with_defaults() -> Self {
+        let mut config = Self::new();
+        for key in schema::SCHEMA {
+            config.set_from(key.name, key.default, ConfigSource::Default);
+        }
+        config
+    }
+    
+    /// 按优先级加载：默认值、配置文件、环境变量、命令行
+    ///
+    /// 模式中已知的键会检查类型，第一个不合法的值作为 `ConfigError::InvalidValue` 返回
+    pub fn load(file: Option<&Path>, cli: &[(String, String)]) -> Result<Self, ConfigError> {
+        let mut config = Self::with_defaults();
+        if let Some(path) = file {
+            config.merge(&Self::from_file(path)?);
+        }
+        config.merge(&Self::from_env());
+        for (key, value) in cli {
+            config.set(key, value);
+        }
+        config.validate()?;
+        Ok(config)
+    }
+    
+    /// 从文件加载配置
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(ConfigError::FileRead)?;
+        
+        let mut config = Self::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            
+            if let Some((key, value)) = line.split_once('=') {
+                let source = ConfigSource::File { path: path.to_path_buf(), line: index + 1 };
+                config.set_from(key.trim(), value.trim(), source);
+            }
+        }
+        
+        Ok(config)
+    }
+    
+    /// 从环境变量加载配置
+    pub fn from_env() -> Self {
+        Self::from_vars(std::env::vars())
+    }
+    
+    /// 从给定的变量列表加载配置，只取 `GAMEBOY_` 开头的变量，键名转为小写
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut config = Self::new();
+        for (key, value) in vars {
+            if let Some(name) = key.strip_prefix(ENV_PREFIX) {
+                config.set_from(&name.to_lowercase(), &value, ConfigSource::Env(key.clone()));
+            }
+        }
+        config
+    }
+    
+    /// 设置配置值，视为命令行来源
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.set_from(key, value, ConfigSource::Cli);
+    }
+    
+    /// 以指定来源设置配置值，已有更高优先级的值时不覆盖，返回是否生效
+    pub fn set_from(&mut self, key: &str, value: &str, source: ConfigSource) -> bool {
+        if let Some(existing) = self.values.get(key) {
+            if existing.source.precedence() > source.precedence() {
+                return false;
+            }
+        }
+        self.values.insert(key.to_string(), Entry { value: value.to_string(), source });
+        true
+    }
+    
+    /// 获取配置值
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.values.get(key).map(|entry| &entry.value)
+    }
+    
+    /// 配置值的来源
+    pub fn provenance(&self, key: &str) -> Option<&ConfigSource> {
+        self.values.get(key).map(|entry| &entry.source)
+    }
+    
+    /// 获取并解析配置值，缺失或无法解析时返回错误
+    pub fn parse<T: FromStr>(&self, key: &str) -> Result<T, ConfigError>
+    where
+        T::Err: fmt::Display,
+    {
+        let entry = self.values.get(key).ok_or_else(|| ConfigError::MissingKey(key.to_string()))?;
+        entry.value.parse().map_err(|e: T::Err| ConfigError::InvalidValue {
+            key: key.to_string(),
+            value: entry.value.clone(),
+            source: entry.source.clone(),
+            message: e.to_string(),
+        })
+    }
+    
+    /// 按模式检查所有已知键的类型，未知的键不检查
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut keys: Vec<&String> = self.values.keys().collect();
+        keys.sort();
+        for key in keys {
+            let entry = &self.values[key];
+            if let Some(schema) = schema::lookup(key) {
+                schema.value_type.check(&entry.value).map_err(|message| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: entry.value.clone(),
+                    source: entry.source.clone(),
+                    message,
+                })?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// 获取配置值，如果不存在则返回默认值
+    pub fn get_or_default(&self, key: &str, default: &str) -> String {
+        self.get(key).map(|s| s.clone()).unwrap_or_else(|| default.to_string())
+    }
+    
+    /// 获取布尔值配置
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(|v| v.parse().ok())
+    }
+    
+    /// 获取整数配置
+    pub fn get_int(&self, key: &str) -> Option<i32> {
+        self.get(key).and_then(|v| v.parse().ok())
+    }
+    
+    /// 获取浮点数配置
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(|v| v.parse().ok())
+    }
+    
+    /// 合并另一个配置，按来源优先级决定是否覆盖
+    pub fn merge(&mut self, other: &Config) {
+        for (key, entry) in &other.values {
+            self.set_from(key, &entry.value, entry.source.clone());
+        }
+    }
+    
+    /// 获取所有配置键
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.values.keys()
+    }
+    
+    /// 检查是否包含某个键
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 配置错误类型
+#[derive(Debug)]
+pub enum ConfigError {
+    FileRead(std::io::Error),
+    ParseError(String),
+    MissingKey(String),
+    /// 值不符合类型，附带来源便于定位
+    InvalidValue { key: String, value: String, source: ConfigSource, message: String },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::FileRead(e) => write!(f, "{}", trf(Msg::ConfigFileRead, &[e])),
+            ConfigError::ParseError(msg) => write!(f, "{}", trf(Msg::ConfigParse, &[msg])),
+            ConfigError::MissingKey(key) => write!(f, "{}", trf(Msg::ConfigMissingKey, &[key])),
+            ConfigError::InvalidValue { key, value, source, message } => {
+                write!(f, "{}", trf(Msg::ConfigInvalidValue, &[key, value, source, message]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// 默认配置常量
+pub mod defaults {
+    pub const CPU_FREQUENCY: u32 = 4194304; // 4.194304 MHz
+    pub const SCREEN_WIDTH: u32 = 160;
+    pub const SCREEN_HEIGHT: u32 = 144;
+    pub const MEMORY_SIZE: usize = 0x10000; // 64KB
+    pub const ENTROPY_POOL_SIZE: usize = 1024;
+    pub const QUANTUM_STATES_COUNT: usize = 256;
+    pub const DISTRIBUTION_QUALITY_THRESHOLD: f64 = 0.5;
+}
+
+/// 配置键常量
+pub mod keys {
+    pub const CPU_FREQUENCY: &str = "cpu_frequency";
+    pub const SCREEN_WIDTH: &str = "screen_width";
+    pub const SCREEN_HEIGHT: &str = "screen_height";
+    pub const MEMORY_SIZE: &str = "memory_size";
+    pub const ENTROPY_POOL_SIZE: &str = "entropy_pool_size";
+    pub const QUANTUM_STATES_COUNT: &str = "quantum_states_count";
+    pub const DISTRIBUTION_QUALITY_THRESHOLD: &str = "distribution_quality_threshold";
+    /// 量子抗性流水线阶段开关的前缀，后接阶段名，如 `quantum_stage_lattice`
+    pub const QUANTUM_STAGE_PREFIX: &str = "quantum_stage_";
+    /// 熵输出的调理方式：`optimizer` 或 `standard`
+    pub const ENTROPY_CONDITIONING: &str = "entropy_conditioning";
+    pub const DEBUG_MODE: &str = "debug_mode";
+    /// 界面语言：`zh` 或 `en`
+    pub const LOCALE: &str = "locale";
+    pub const LOG_LEVEL: &str = "log_level";
+    /// 手柄键位绑定，如 `south=a,east=b`，未列出的按键使用默认绑定
+    pub const GAMEPAD_BINDINGS: &str = "gamepad_bindings";
+    pub const GAMEPAD_DEADZONE: &str = "gamepad_deadzone";
+    /// 帧率控制方式：`audio` 或 `timer`
+    pub const FRAME_SYNC: &str = "frame_sync";
+    pub const ROM_PATH: &str = "rom_path";
+    pub const SAVE_PATH: &str = "save_path";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precedence_provenance_and_typed_errors() {
+        let path = std::env::temp_dir().join(format!("gamelife_config_{}.cfg", std::process::id()));
+        fs::write(&path, "# 注释\nscreen_width = 320\nlog_level = warn\ndebug_mode = true\n").unwrap();
+
+        let mut config = Config::with_defaults();
+        // 先加载高优先级的来源，低优先级的不会覆盖
+        config.merge(&Config::from_vars(vec![
+            ("GAMEBOY_LOG_LEVEL".to_string(), "debug".to_string()),
+            ("GAMEBOY_DEBUG_MODE".to_string(), "false".to_string()),
+            ("OTHER_VAR".to_string(), "1".to_string()),
+        ]));
+        config.merge(&Config::from_file(&path).unwrap());
+        config.set(keys::DEBUG_MODE, "true");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.get(keys::SCREEN_WIDTH).unwrap(), "320");
+        assert_eq!(config.provenance(keys::SCREEN_WIDTH), Some(&ConfigSource::File { path: path.clone(), line: 2 }));
+        assert_eq!(config.get(keys::LOG_LEVEL).unwrap(), "debug");
+        assert_eq!(config.provenance(keys::LOG_LEVEL), Some(&ConfigSource::Env("GAMEBOY_LOG_LEVEL".to_string())));
+        assert!(config.parse::<bool>(keys::DEBUG_MODE).unwrap());
+        assert_eq!(config.provenance(keys::DEBUG_MODE), Some(&ConfigSource::Cli));
+        assert_eq!(config.provenance(keys::SCREEN_HEIGHT), Some(&ConfigSource::Default));
+        assert_eq!(config.parse::<u32>(keys::SCREEN_HEIGHT).unwrap(), 144);
+        assert!(!config.contains_key("var"));
+        assert!(config.validate().is_ok());
+
+        let mut config = Config::from_vars(vec![("GAMEBOY_SCREEN_WIDTH".to_string(), "wide".to_string())]);
+        match config.parse::<u32>(keys::SCREEN_WIDTH) {
+            Err(ConfigError::InvalidValue { source: ConfigSource::Env(var), .. }) => assert_eq!(var, "GAMEBOY_SCREEN_WIDTH"),
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(config.parse::<u32>(keys::ROM_PATH), Err(ConfigError::MissingKey(_))));
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("screen_width") && error.contains("GAMEBOY_SCREEN_WIDTH"), "{}", error);
+        config.set(keys::SCREEN_WIDTH, "200");
+        assert!(config.validate().is_ok());
+    }
+}
@@ -0,0 +1,330 @@
+//! 配置模式
+//!
+//! 列出所有已知配置键的类型、默认值和说明，用于导出默认配置文件，
+//! 以及在加载前检查用户配置：未知的键、类型错误和格式错误都带行号报告。
+
+use std::fmt;
+
+use crate::i18n::{tr, trf, Msg};
+
+use super::keys;
+
+/// 配置值的类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueType {
+    Bool,
+    Integer { min: i64, max: i64 },
+    Float { min: f64, max: f64 },
+    Text,
+    /// 固定取值之一
+    Choice { values: &'static [&'static str], ignore_case: bool },
+}
+
+impl ValueType {
+    /// 检查值是否符合类型
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match *self {
+            ValueType::Bool => value
+                .parse::<bool>()
+                .map(|_| ())
+                .map_err(|_| trf(Msg::ExpectBool, &[&value])),
+            ValueType::Integer { min, max } => match value.parse::<i64>() {
+                Ok(n) if (min..=max).contains(&n) => Ok(()),
+                Ok(n) => Err(trf(Msg::IntegerOutOfRange, &[&n, &min, &max])),
+                Err(_) => Err(trf(Msg::ExpectInteger, &[&value])),
+            },
+            ValueType::Float { min, max } => match value.parse::<f64>() {
+                Ok(x) if (min..=max).contains(&x) => Ok(()),
+                Ok(x) => Err(trf(Msg::FloatOutOfRange, &[&x, &min, &max])),
+                Err(_) => Err(trf(Msg::ExpectFloat, &[&value])),
+            },
+            ValueType::Text => Ok(()),
+            ValueType::Choice { values, ignore_case } => {
+                let matches = |choice: &&str| if ignore_case { choice.eq_ignore_ascii_case(value) } else { *choice == value };
+                if values.iter().any(matches) {
+                    Ok(())
+                } else {
+                    Err(trf(Msg::ExpectChoice, &[&values.join("|"), &value]))
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueType::Bool => write!(f, "{}", tr(Msg::TypeBool)),
+            ValueType::Integer { min, max } => write!(f, "{}", trf(Msg::TypeInteger, &[min, max])),
+            ValueType::Float { min, max } => write!(f, "{}", trf(Msg::TypeFloat, &[min, max])),
+            ValueType::Text => write!(f, "{}", tr(Msg::TypeText)),
+            ValueType::Choice { values, .. } => write!(f, "{}", values.join("|")),
+        }
+    }
+}
+
+/// 一个配置键的描述
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfigKey {
+    pub name: &'static str,
+    pub value_type: ValueType,
+    /// 默认值的文本形式
+    pub default: &'static str,
+    pub description: Msg,
+}
+
+impl ConfigKey {
+    /// 当前语言下的说明
+    pub fn describe(&self) -> String {
+        // 量子阶段的说明以阶段名为参数，其余说明没有占位符
+        trf(self.description, &[&self.name.trim_start_matches(keys::QUANTUM_STAGE_PREFIX)])
+    }
+}
+
+const U32_MAX: i64 = u32::MAX as i64;
+
+/// 阶段名与 `entropy::TransformStage::name` 保持一致
+macro_rules! quantum_stage {
+    ($name:literal) => {
+        ConfigKey {
+            name: concat!("quantum_stage_", $name),
+            value_type: ValueType::Bool,
+            default: "true",
+            description: Msg::KeyQuantumStage,
+        }
+    };
+}
+
+/// 所有已知的配置键
+pub const SCHEMA: &[ConfigKey] = &[
+    ConfigKey {
+        name: keys::CPU_FREQUENCY,
+        value_type: ValueType::Integer { min: 1, max: U32_MAX },
+        default: "4194304",
+        description: Msg::KeyCpuFrequency,
+    },
+    ConfigKey {
+        name: keys::SCREEN_WIDTH,
+        value_type: ValueType::Integer { min: 1, max: 4096 },
+        default: "160",
+        description: Msg::KeyScreenWidth,
+    },
+    ConfigKey {
+        name: keys::SCREEN_HEIGHT,
+        value_type: ValueType::Integer { min: 1, max: 4096 },
+        default: "144",
+        description: Msg::KeyScreenHeight,
+    },
+    ConfigKey {
+        name: keys::MEMORY_SIZE,
+        value_type: ValueType::Integer { min: 1, max: U32_MAX },
+        default: "65536",
+        description: Msg::KeyMemorySize,
+    },
+    ConfigKey {
+        name: keys::ENTROPY_POOL_SIZE,
+        value_type: ValueType::Integer { min: 1, max: U32_MAX },
+        default: "1024",
+        description: Msg::KeyEntropyPoolSize,
+    },
+    ConfigKey {
+        name: keys::QUANTUM_STATES_COUNT,
+        value_type: ValueType::Integer { min: 1, max: U32_MAX },
+        default: "256",
+        description: Msg::KeyQuantumStatesCount,
+    },
+    ConfigKey {
+        name: keys::DISTRIBUTION_QUALITY_THRESHOLD,
+        value_type: ValueType::Float { min: 0.0, max: 1.0 },
+        default: "0.5",
+        description: Msg::KeyDistributionQualityThreshold,
+    },
+    ConfigKey {
+        name: keys::ENTROPY_CONDITIONING,
+        value_type: ValueType::Choice { values: &["optimizer", "standard"], ignore_case: false },
+        default: "optimizer",
+        description: Msg::KeyEntropyConditioning,
+    },
+    quantum_stage!("state_mixing"),
+    quantum_stage!("post_quantum_hash"),
+    quantum_stage!("lattice"),
+    quantum_stage!("multivariate"),
+    quantum_stage!("homomorphic"),
+    ConfigKey {
+        name: keys::DEBUG_MODE,
+        value_type: ValueType::Bool,
+        default: "false",
+        description: Msg::KeyDebugMode,
+    },
+    ConfigKey {
+        name: keys::LOG_LEVEL,
+        value_type: ValueType::Choice { values: &["trace", "debug", "info", "warn", "error"], ignore_case: true },
+        default: "info",
+        description: Msg::KeyLogLevel,
+    },
+    ConfigKey {
+        name: keys::LOCALE,
+        value_type: ValueType::Choice { values: &["zh", "en"], ignore_case: false },
+        default: "zh",
+        description: Msg::KeyLocale,
+    },
+    ConfigKey {
+        name: keys::GAMEPAD_BINDINGS,
+        value_type: ValueType::Text,
+        default: "",
+        description: Msg::KeyGamepadBindings,
+    },
+    ConfigKey {
+        name: keys::GAMEPAD_DEADZONE,
+        value_type: ValueType::Float { min: 0.0, max: 1.0 },
+        default: "0.5",
+        description: Msg::KeyGamepadDeadzone,
+    },
+    ConfigKey {
+        name: keys::FRAME_SYNC,
+        value_type: ValueType::Choice { values: &["audio", "timer"], ignore_case: false },
+        default: "audio",
+        description: Msg::KeyFrameSync,
+    },
+    ConfigKey {
+        name: keys::ROM_PATH,
+        value_type: ValueType::Text,
+        default: "",
+        description: Msg::KeyRomPath,
+    },
+    ConfigKey {
+        name: keys::SAVE_PATH,
+        value_type: ValueType::Text,
+        default: "",
+        description: Msg::KeySavePath,
+    },
+];
+
+/// 按名称查找配置键
+pub fn lookup(name: &str) -> Option<&'static ConfigKey> {
+    SCHEMA.iter().find(|key| key.name == name)
+}
+
+/// 生成带注释的默认配置文件
+pub fn dump_default() -> String {
+    let mut out = tr(Msg::DefaultConfigHeader).to_string();
+    for key in SCHEMA {
+        out.push_str(&format!("\n# {} [{}]\n{} = {}\n", key.describe(), key.value_type, key.name, key.default));
+    }
+    out
+}
+
+/// 检查发现的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 从1开始的行号
+    pub line: usize,
+    /// 该行的原文
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", trf(Msg::DiagnosticLine, &[&self.line, &self.message, &self.text]))
+    }
+}
+
+/// 按模式检查配置文件内容
+pub fn validate(content: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut seen: Vec<(&str, usize)> = Vec::new();
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut report = |message: String| {
+            diagnostics.push(Diagnostic { line: index + 1, text: raw.to_string(), message });
+        };
+
+        let Some((key, value)) = line.split_once('=') else {
+            report(tr(Msg::DiagMissingEquals).to_string());
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key.is_empty() {
+            report(tr(Msg::DiagMissingKey).to_string());
+            continue;
+        }
+        if let Some(&(_, first)) = seen.iter().find(|(name, _)| *name == key) {
+            report(trf(Msg::DiagDuplicate, &[&key, &first]));
+        } else {
+            seen.push((key, index + 1));
+        }
+        match lookup(key) {
+            Some(schema) => {
+                if let Err(message) = schema.value_type.check(value) {
+                    report(format!("`{}` {}", key, message));
+                }
+            }
+            None => match suggest(key) {
+                Some(suggestion) => report(trf(Msg::DiagUnknownKeySuggest, &[&key, &suggestion])),
+                None => report(trf(Msg::DiagUnknownKey, &[&key])),
+            },
+        }
+    }
+    diagnostics
+}
+
+/// 编辑距离不超过2的最接近的已知键
+fn suggest(name: &str) -> Option<&'static str> {
+    SCHEMA
+        .iter()
+        .map(|key| (edit_distance(name, key.name), key.name))
+        .filter(|&(distance, _)| distance <= 2)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, name)| name)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::defaults;
+
+    #[test]
+    fn test_default_dump_is_valid_and_errors_have_line_context() {
+        assert!(validate(&dump_default()).is_empty());
+        for key in SCHEMA {
+            assert!(key.value_type.check(key.default).is_ok(), "{}", key.name);
+        }
+        assert_eq!(lookup(keys::CPU_FREQUENCY).unwrap().default, defaults::CPU_FREQUENCY.to_string());
+        assert_eq!(lookup(keys::MEMORY_SIZE).unwrap().default, defaults::MEMORY_SIZE.to_string());
+        assert_eq!(lookup(keys::ENTROPY_POOL_SIZE).unwrap().default, defaults::ENTROPY_POOL_SIZE.to_string());
+        assert_eq!(lookup(keys::QUANTUM_STATES_COUNT).unwrap().default, defaults::QUANTUM_STATES_COUNT.to_string());
+        #[cfg(feature = "entropy")]
+        for stage in crate::entropy::TransformStage::ALL {
+            assert!(lookup(&format!("{}{}", keys::QUANTUM_STAGE_PREFIX, stage.name())).is_some());
+        }
+
+        let content = "# 注释\nscreen_widht = 160\ndebug_mode = yes\n\nmemory_size=0\nlog_level = WARN\nno equals\nlog_level = info\nfoo = 1\n";
+        let diagnostics = validate(content);
+        let lines: Vec<usize> = diagnostics.iter().map(|d| d.line).collect();
+        assert_eq!(lines, vec![2, 3, 5, 7, 8, 9]);
+        assert!(diagnostics[0].message.contains("是否是 `screen_width`"));
+        assert!(diagnostics[1].message.contains("布尔"));
+        assert!(diagnostics[2].message.contains("超出范围"));
+        assert!(diagnostics[4].message.contains("第6行"));
+        assert_eq!(diagnostics[5].message, "未知的配置键 `foo`");
+        assert_eq!(diagnostics[0].to_string(), "第2行: 未知的配置键 `screen_widht`，是否是 `screen_width`?\n  | screen_widht = 160");
+    }
+}
@@ -0,0 +1,7 @@
+//! 音频模块
+//!
+//! 目前还没有APU；这里先提供APU输出要接入的组件。
+
+pub mod scope;
+
+pub use scope::{ChannelScope, CHANNEL_COUNT};
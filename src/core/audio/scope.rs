@@ -0,0 +1,94 @@
+//! 声道示波器采样
+//!
+//! 为每个声道保留最近的采样，工具可以据此绘制示波器视图，测试可以断言波形形状。
+//! APU实现后应在混音前把各声道的输出写入 `ChannelScope`，
+//! 并以 `apu.channel_samples(ch, last_n)` 转发查询。
+
+use std::collections::VecDeque;
+
+/// Game Boy声道数：方波1、方波2、波形、噪声
+pub const CHANNEL_COUNT: usize = 4;
+
+/// 各声道最近采样的环形缓冲区
+#[derive(Debug, Clone)]
+pub struct ChannelScope {
+    capacity: usize,
+    channels: [VecDeque<f32>; CHANNEL_COUNT],
+}
+
+impl ChannelScope {
+    /// 每个声道最多保留 `capacity` 个采样
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            channels: std::array::from_fn(|_| VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// 每个声道保留的采样数
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 记录一个声道的采样，超出容量时丢弃最旧的采样
+    pub fn push(&mut self, channel: usize, sample: f32) {
+        let Some(samples) = self.channels.get_mut(channel) else {
+            return;
+        };
+        if self.capacity == 0 {
+            return;
+        }
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// 同时记录所有声道的一个采样
+    pub fn push_frame(&mut self, samples: [f32; CHANNEL_COUNT]) {
+        for (channel, sample) in samples.into_iter().enumerate() {
+            self.push(channel, sample);
+        }
+    }
+
+    /// 声道最近的 `last_n` 个采样，按时间顺序排列
+    pub fn channel_samples(&self, channel: usize, last_n: usize) -> Vec<f32> {
+        self.channels.get(channel).map_or_else(Vec::new, |samples| {
+            samples.iter().skip(samples.len().saturating_sub(last_n)).copied().collect()
+        })
+    }
+
+    /// 清空所有声道
+    pub fn clear(&mut self) {
+        self.channels.iter_mut().for_each(VecDeque::clear);
+    }
+}
+
+impl Default for ChannelScope {
+    /// 约等于44.1kHz下两帧的采样
+    fn default() -> Self {
+        Self::new(1470)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_samples_keep_latest_window() {
+        let mut scope = ChannelScope::new(4);
+        for i in 0..6 {
+            let square = if i % 2 == 0 { 1.0 } else { -1.0 };
+            scope.push_frame([square, 0.0, i as f32, 0.0]);
+        }
+        scope.push(CHANNEL_COUNT, 9.0); // 不存在的声道被忽略
+
+        assert_eq!(scope.channel_samples(0, 3), vec![-1.0, 1.0, -1.0]);
+        assert_eq!(scope.channel_samples(2, 10), vec![2.0, 3.0, 4.0, 5.0]);
+        assert!(scope.channel_samples(CHANNEL_COUNT, 4).is_empty());
+
+        scope.clear();
+        assert!(scope.channel_samples(2, 4).is_empty());
+    }
+}
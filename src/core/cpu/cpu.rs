@@ -1,7 +1,7 @@
 //! CPU核心模块 - 包含CPU执行逻辑
 
 use crate::memory::MemoryBus;
-use super::{Registers, FlagsRegister};
+use super::{Registers, FlagsRegister, OpcodeStats};
 use super::registers::Register;
 
 /// CPU结构
@@ -12,6 +12,10 @@ pub struct CPU {
     pub sp: u16,        // 栈指针
     pub flags: FlagsRegister,
     pub bus: MemoryBus,
+    /// 累计执行的机器周期数
+    pub cycle_count: u64,
+    /// 按操作码统计
+    pub opcode_stats: Option<Box<OpcodeStats>>,
 }
 
 impl CPU {
@@ -23,20 +27,46 @@ impl CPU {
             sp: 0xFFFE,     // 栈指针初始化为0xFFFE
             flags: FlagsRegister::new(),
             bus,
+            cycle_count: 0,
+            opcode_stats: None,
         }
     }
 
-    /// 执行一步指令
-    pub fn step(&mut self) -> Result<(), String> {
+    /// 开启按操作码统计，已开启时清零
+    pub fn enable_opcode_stats(&mut self) {
+        self.opcode_stats = Some(Box::default());
+    }
+
+    /// 执行一步指令，返回用掉的机器周期数
+    pub fn step(&mut self) -> Result<u8, String> {
         let instruction_byte = self.bus.read_byte(self.pc);
         
-        let next_pc = match crate::instructions::Instruction::from_byte(instruction_byte) {
-            Some(instruction) => self.execute(instruction)?,
+        let (next_pc, cycles) = match crate::instructions::Instruction::decode(&self.bus, self.pc) {
+            Some(instruction) => {
+                let taken = self.condition_met(&instruction);
+                (self.execute(instruction)?, instruction.cycles(taken))
+            }
             None => return Err(format!("未知指令: 0x{:02X}", instruction_byte)),
         };
 
         self.pc = next_pc;
-        Ok(())
+        self.cycle_count += cycles as u64;
+        if let Some(stats) = &mut self.opcode_stats {
+            stats.record(instruction_byte, cycles as u64);
+        }
+        Ok(cycles)
+    }
+
+    /// 条件跳转的条件是否成立，其余指令为 `true`
+    fn condition_met(&self, instruction: &crate::instructions::Instruction) -> bool {
+        use crate::instructions::Instruction;
+        match instruction {
+            Instruction::JP(condition, _)
+            | Instruction::JR(condition, _)
+            | Instruction::CALL(condition, _)
+            | Instruction::RET(condition) => condition.is_met(&self.flags),
+            _ => true,
+        }
     }
 
     /// 执行指令
@@ -54,6 +84,11 @@ impl CPU {
             crate::instructions::Instruction::DEC(target) => {
                 self.execute_dec(target)
             }
+            crate::instructions::Instruction::CP(value) => {
+                // 只保留标志位，结果丢弃
+                self.sub(value);
+                Ok(self.pc.wrapping_add(2))
+            }
             crate::instructions::Instruction::LD(target, source) => {
                 self.execute_ld(target, source)
             }
@@ -66,14 +101,20 @@ impl CPU {
             crate::instructions::Instruction::DEC16(target) => {
                 self.execute_dec16(target)
             }
-            crate::instructions::Instruction::JP(target) => {
-                self.execute_jp(target)
+            crate::instructions::Instruction::JP(condition, target) => {
+                self.execute_jp(condition, target)
+            }
+            crate::instructions::Instruction::JR(condition, target) => {
+                self.execute_jr(condition, target)
             }
-            crate::instructions::Instruction::JR(target) => {
-                self.execute_jr(target)
+            crate::instructions::Instruction::CALL(condition, address) => {
+                self.execute_call(condition, address)
+            }
+            crate::instructions::Instruction::RET(condition) => {
+                self.execute_ret(condition)
             }
             crate::instructions::Instruction::NOP => {
-                Ok(self.pc + 1)
+                Ok(self.pc.wrapping_add(1))
             }
         }
     }
@@ -83,7 +124,7 @@ impl CPU {
         let value = self.get_register_value(target)?;
         let result = self.add(value);
         self.registers.a = result;
-        Ok(self.pc + 1)
+        Ok(self.pc.wrapping_add(1))
     }
 
     /// 执行SUB指令
@@ -91,7 +132,7 @@ impl CPU {
         let value = self.get_register_value(target)?;
         let result = self.sub(value);
         self.registers.a = result;
-        Ok(self.pc + 1)
+        Ok(self.pc.wrapping_add(1))
     }
 
     /// 执行INC指令
@@ -100,7 +141,7 @@ impl CPU {
         let value = self.registers.get_register(reg);
         let result = self.inc(value);
         self.registers.set_register(reg, result);
-        Ok(self.pc + 1)
+        Ok(self.pc.wrapping_add(1))
     }
 
     /// 执行DEC指令
@@ -109,22 +150,28 @@ impl CPU {
         let value = self.registers.get_register(reg);
         let result = self.dec(value);
         self.registers.set_register(reg, result);
-        Ok(self.pc + 1)
+        Ok(self.pc.wrapping_add(1))
     }
 
     /// 执行LD指令
     fn execute_ld(&mut self, target: crate::instructions::LoadTarget, source: crate::instructions::LoadSource) -> Result<u16, String> {
         let value = self.get_load_source_value(source)?;
-        let reg = self.load_target_to_register(target)?;
-        self.registers.set_register(reg, value);
-        Ok(self.pc + 1)
+        match target {
+            crate::instructions::LoadTarget::HighRam(offset) => self.bus.write_byte(0xFF00 | offset as u16, value),
+            _ => {
+                let reg = self.load_target_to_register(target)?;
+                self.registers.set_register(reg, value);
+            }
+        }
+        let size = crate::instructions::Instruction::LD(target, source).size();
+        Ok(self.pc.wrapping_add(size as u16))
     }
 
     /// 执行LD16指令
     fn execute_ld16(&mut self, target: crate::instructions::LoadTarget16, source: crate::instructions::LoadSource16) -> Result<u16, String> {
         let value = self.get_load_source16_value(source)?;
         self.set_load_target16_value(target, value)?;
-        Ok(self.pc + 1)
+        Ok(self.pc.wrapping_add(3))
     }
 
     /// 执行INC16指令
@@ -132,7 +179,7 @@ impl CPU {
         let current_value = self.get_load_target16_value(target)?;
         let new_value = current_value.wrapping_add(1);
         self.set_load_target16_value(target, new_value)?;
-        Ok(self.pc + 1)
+        Ok(self.pc.wrapping_add(1))
     }
 
     /// 执行DEC16指令
@@ -140,28 +187,54 @@ impl CPU {
         let current_value = self.get_load_target16_value(target)?;
         let new_value = current_value.wrapping_sub(1);
         self.set_load_target16_value(target, new_value)?;
-        Ok(self.pc + 1)
+        Ok(self.pc.wrapping_add(1))
     }
 
     /// 执行JP指令
-    fn execute_jp(&mut self, target: crate::instructions::JumpTarget) -> Result<u16, String> {
+    fn execute_jp(&mut self, condition: crate::instructions::JumpCondition, target: crate::instructions::JumpTarget) -> Result<u16, String> {
         match target {
-            crate::instructions::JumpTarget::Immediate(address) => Ok(address),
+            crate::instructions::JumpTarget::Immediate(address) if condition.is_met(&self.flags) => Ok(address),
+            crate::instructions::JumpTarget::Immediate(_) => Ok(self.pc.wrapping_add(3)),
             crate::instructions::JumpTarget::Relative(_) => Err("JP指令不支持相对跳转".to_string()),
         }
     }
 
-    /// 执行JR指令
-    fn execute_jr(&mut self, target: crate::instructions::JumpTarget) -> Result<u16, String> {
+    /// 执行JR指令，位移相对于JR之后的地址
+    fn execute_jr(&mut self, condition: crate::instructions::JumpCondition, target: crate::instructions::JumpTarget) -> Result<u16, String> {
         match target {
             crate::instructions::JumpTarget::Relative(offset) => {
-                let new_pc = (self.pc as i32 + offset as i32) as u16;
-                Ok(new_pc)
+                let next_pc = self.pc.wrapping_add(2);
+                if condition.is_met(&self.flags) {
+                    Ok(next_pc.wrapping_add_signed(offset as i16))
+                } else {
+                    Ok(next_pc)
+                }
             }
             crate::instructions::JumpTarget::Immediate(_) => Err("JR指令不支持绝对跳转".to_string()),
         }
     }
 
+    /// 执行CALL指令：返回地址压栈后跳转
+    fn execute_call(&mut self, condition: crate::instructions::JumpCondition, address: u16) -> Result<u16, String> {
+        let return_address = self.pc.wrapping_add(3);
+        if !condition.is_met(&self.flags) {
+            return Ok(return_address);
+        }
+        self.sp = self.sp.wrapping_sub(2);
+        self.bus.write_word(self.sp, return_address);
+        Ok(address)
+    }
+
+    /// 执行RET指令：从栈中弹出返回地址
+    fn execute_ret(&mut self, condition: crate::instructions::JumpCondition) -> Result<u16, String> {
+        if !condition.is_met(&self.flags) {
+            return Ok(self.pc.wrapping_add(1));
+        }
+        let address = self.bus.read_word(self.sp);
+        self.sp = self.sp.wrapping_add(2);
+        Ok(address)
+    }
+
     // 辅助方法
     fn get_register_value(&self, target: crate::instructions::ArithmeticTarget) -> Result<u8, String> {
         let reg = self.arithmetic_target_to_register(target)?;
@@ -189,6 +262,7 @@ impl CPU {
             crate::instructions::LoadTarget::E => Ok(Register::E),
             crate::instructions::LoadTarget::H => Ok(Register::H),
             crate::instructions::LoadTarget::L => Ok(Register::L),
+            crate::instructions::LoadTarget::HighRam(_) => Err("LDH的目标是内存而不是寄存器".to_string()),
         }
     }
 
@@ -202,6 +276,7 @@ impl CPU {
             crate::instructions::LoadSource::H => Ok(self.registers.h),
             crate::instructions::LoadSource::L => Ok(self.registers.l),
             crate::instructions::LoadSource::Immediate(val) => Ok(val),
+            crate::instructions::LoadSource::HighRam(offset) => Ok(self.bus.read_byte(0xFF00 | offset as u16)),
         }
     }
 
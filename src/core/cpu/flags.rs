@@ -2,6 +2,7 @@
 
 /// 标志寄存器结构
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FlagsRegister {
     pub zero: bool,
     pub subtract: bool,
@@ -4,8 +4,12 @@ pub mod registers;
 pub mod flags;
 pub mod cpu;
 pub mod optimizer;
+pub mod opcode_stats;
+pub mod reference;
 
 pub use cpu::CPU;
 pub use registers::Registers;
 pub use flags::FlagsRegister;
 pub use optimizer::{OptimizedCPU, CPUOptimizer, PerformanceStats};
+pub use opcode_stats::{OpcodeCount, OpcodeStats};
+pub use reference::{ExecutionCore, ReferenceCPU};
@@ -0,0 +1,137 @@
+//! 按操作码统计
+//!
+//! 记录每个操作码的执行次数和计入的周期数，用来查看一个ROM的指令构成，
+//! 找出最值得优化的指令。统计默认关闭，开启后每条指令多两次数组累加。
+
+use crate::instructions::Instruction;
+
+/// 一个操作码的统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeCount {
+    pub opcode: u8,
+    pub count: u64,
+    pub cycles: u64,
+}
+
+impl OpcodeCount {
+    /// 指令助记符，无法解码的操作码为 `?`
+    pub fn mnemonic(&self) -> &'static str {
+        Instruction::from_byte(self.opcode).map_or("?", |instruction| instruction.name())
+    }
+}
+
+/// 每个操作码的执行次数和周期
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpcodeStats {
+    counts: [u64; 256],
+    cycles: [u64; 256],
+}
+
+impl OpcodeStats {
+    pub fn new() -> Self {
+        Self { counts: [0; 256], cycles: [0; 256] }
+    }
+
+    /// 记录一条指令
+    pub fn record(&mut self, opcode: u8, cycles: u64) {
+        self.counts[opcode as usize] += 1;
+        self.cycles[opcode as usize] += cycles;
+    }
+
+    pub fn count(&self, opcode: u8) -> u64 {
+        self.counts[opcode as usize]
+    }
+
+    pub fn cycles(&self, opcode: u8) -> u64 {
+        self.cycles[opcode as usize]
+    }
+
+    pub fn total_count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn total_cycles(&self) -> u64 {
+        self.cycles.iter().sum()
+    }
+
+    /// 执行过的操作码，按次数从多到少排列，次数相同时按操作码排列
+    pub fn ranked(&self) -> Vec<OpcodeCount> {
+        let mut entries: Vec<OpcodeCount> = (0..=255u8)
+            .filter(|&opcode| self.count(opcode) > 0)
+            .map(|opcode| OpcodeCount { opcode, count: self.count(opcode), cycles: self.cycles(opcode) })
+            .collect();
+        entries.sort_by(|a, b| b.count.cmp(&a.count).then(a.opcode.cmp(&b.opcode)));
+        entries
+    }
+
+    /// 合并另一份统计，例如多次运行的结果
+    pub fn merge(&mut self, other: &OpcodeStats) {
+        for opcode in 0..256 {
+            self.counts[opcode] += other.counts[opcode];
+            self.cycles[opcode] += other.cycles[opcode];
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// 导出为CSV，每个执行过的操作码一行，按次数从多到少排列
+    pub fn to_csv(&self) -> String {
+        let percent = |part: u64, total: u64| if total == 0 { 0.0 } else { part as f64 * 100.0 / total as f64 };
+        let (total_count, total_cycles) = (self.total_count(), self.total_cycles());
+        let mut out = String::from("opcode,mnemonic,count,cycles,count_percent,cycle_percent\n");
+        for entry in self.ranked() {
+            out.push_str(&format!(
+                "0x{:02X},{},{},{},{:.2},{:.2}\n",
+                entry.opcode,
+                entry.mnemonic(),
+                entry.count,
+                entry.cycles,
+                percent(entry.count, total_count),
+                percent(entry.cycles, total_cycles)
+            ));
+        }
+        out
+    }
+}
+
+impl Default for OpcodeStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranked_counts_and_csv() {
+        let mut stats = OpcodeStats::new();
+        for _ in 0..3 {
+            stats.record(0x0C, 1);
+        }
+        stats.record(0x00, 1);
+        stats.record(0xC3, 4);
+        stats.record(0xC3, 4);
+        assert_eq!((stats.total_count(), stats.total_cycles()), (6, 12));
+
+        let ranked = stats.ranked();
+        assert_eq!(ranked.iter().map(|e| e.opcode).collect::<Vec<_>>(), vec![0x0C, 0xC3, 0x00]);
+        assert_eq!(ranked[1].mnemonic(), "JP");
+
+        let csv = stats.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "opcode,mnemonic,count,cycles,count_percent,cycle_percent");
+        assert_eq!(lines[1], "0x0C,INC,3,3,50.00,25.00");
+        assert_eq!(lines[2], "0xC3,JP,2,8,33.33,66.67");
+
+        let mut merged = OpcodeStats::new();
+        merged.merge(&stats);
+        merged.merge(&stats);
+        assert_eq!(merged.count(0x0C), 6);
+        merged.clear();
+        assert!(merged.ranked().is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 //! CPU优化器模块 - 提供性能优化功能
 
-use super::{CPU, Registers, FlagsRegister};
+use super::{CPU, Registers, FlagsRegister, OpcodeStats};
 use crate::memory::MemoryBus;
 use crate::instructions::Instruction;
 
@@ -85,6 +85,8 @@ pub struct OptimizedCPU {
     pub optimizer: CPUOptimizer,
     pub cycle_count: u64,
     pub instruction_count: u64,
+    /// 按操作码统计，`None` 表示未开启
+    pub opcode_stats: Option<Box<OpcodeStats>>,
 }
 
 impl OptimizedCPU {
@@ -99,24 +101,30 @@ impl OptimizedCPU {
             optimizer: CPUOptimizer::new(1024), // 1KB指令缓存
             cycle_count: 0,
             instruction_count: 0,
+            opcode_stats: None,
         }
     }
 
+    /// 开启按操作码统计，已开启时清零
+    pub fn enable_opcode_stats(&mut self) {
+        self.opcode_stats = Some(Box::default());
+    }
+
     /// 优化的指令执行
     pub fn step_optimized(&mut self) -> Result<(), String> {
         let pc = self.pc;
         
         // 暂时禁用缓存优化，直接执行指令
         let instruction_byte = self.bus.read_byte(pc);
-        let instruction = Instruction::from_byte(instruction_byte)
+        let instruction = Instruction::decode(&self.bus, pc)
             .ok_or_else(|| format!("未知指令: 0x{:02X}", instruction_byte))?;
         
         // 执行指令
         let (cycles, size) = self.execute_instruction(&instruction)?;
         
-        // 更新PC（除非指令改变了PC，如跳转指令）
-        if !matches!(instruction, Instruction::JP(_) | Instruction::JR(_)) {
-            self.pc += size as u16;
+        // 更新PC（控制流指令自行设置PC）
+        if !matches!(instruction, Instruction::JP(..) | Instruction::JR(..) | Instruction::CALL(..) | Instruction::RET(_)) {
+            self.pc = self.pc.wrapping_add(size as u16);
         }
         
         // 缓存指令（用于统计）
@@ -125,10 +133,9 @@ impl OptimizedCPU {
         // 更新统计信息
         self.cycle_count += cycles as u64;
         self.instruction_count += 1;
-        
-        // 调试信息
-        println!("DEBUG: PC={:04X}, 指令={:?}, 周期={}, 大小={}, 总周期={}, 总指令={}", 
-                pc, instruction, cycles, size, self.cycle_count, self.instruction_count);
+        if let Some(stats) = &mut self.opcode_stats {
+            stats.record(instruction_byte, cycles as u64);
+        }
         
         Ok(())
     }
@@ -149,6 +156,10 @@ impl OptimizedCPU {
                 self.sub(value);
                 Ok((1, 1)) // 1周期，1字节
             }
+            Instruction::CP(value) => {
+                self.compare(*value);
+                Ok((2, 2)) // 2周期，2字节
+            }
             Instruction::INC(target) => {
                 self.inc(*target);
                 Ok((1, 1)) // 1周期，1字节
@@ -159,7 +170,7 @@ impl OptimizedCPU {
             }
             Instruction::LD(target, source) => {
                 self.ld(*target, *source);
-                Ok((1, 1)) // 1周期，1字节
+                Ok((instruction.cycles(true), instruction.size())) // 寄存器1周期1字节，立即数2周期2字节
             }
             Instruction::LD16(target, source) => {
                 self.ld16(*target, *source);
@@ -173,13 +184,21 @@ impl OptimizedCPU {
                 self.dec16(*target);
                 Ok((2, 1)) // 2周期，1字节
             }
-            Instruction::JP(target) => {
-                self.jp(*target);
-                Ok((4, 3)) // 4周期，3字节
+            Instruction::JP(condition, target) => {
+                let taken = self.jp(*condition, *target);
+                Ok((instruction.cycles(taken), 3)) // 4/3周期，3字节
+            }
+            Instruction::JR(condition, target) => {
+                let taken = self.jr(*condition, *target);
+                Ok((instruction.cycles(taken), 2)) // 3/2周期，2字节
+            }
+            Instruction::CALL(condition, address) => {
+                let taken = self.call(*condition, *address);
+                Ok((instruction.cycles(taken), 3)) // 6/3周期，3字节
             }
-            Instruction::JR(target) => {
-                self.jr(*target);
-                Ok((3, 2)) // 3周期，2字节
+            Instruction::RET(condition) => {
+                let taken = self.ret(*condition);
+                Ok((instruction.cycles(taken), 1)) // 4或5/2周期，1字节
             }
         }
     }
@@ -210,6 +229,13 @@ impl OptimizedCPU {
     }
 
     fn sub(&mut self, value: u8) -> u8 {
+        let new_value = self.compare(value);
+        self.registers.a = new_value;
+        new_value
+    }
+
+    /// 按 `A - value` 设置标志位并返回差，不写回A
+    fn compare(&mut self, value: u8) -> u8 {
         let (new_value, did_overflow) = self.registers.a.overflowing_sub(value);
         let half_carry = (self.registers.a & 0xF) < (value & 0xF);
         
@@ -218,7 +244,6 @@ impl OptimizedCPU {
         self.flags.half_carry = half_carry;
         self.flags.carry = did_overflow;
         
-        self.registers.a = new_value;
         new_value
     }
 
@@ -272,22 +297,49 @@ impl OptimizedCPU {
         self.set_load_target16_value(target, new_value);
     }
 
-    fn jp(&mut self, target: crate::instructions::JumpTarget) {
-        match target {
-            crate::instructions::JumpTarget::Immediate(addr) => {
-                self.pc = addr;
-            }
-            _ => {} // 其他跳转类型暂未实现
+    /// 条件成立时跳转并返回 `true`，否则PC越过指令
+    fn jp(&mut self, condition: crate::instructions::JumpCondition, target: crate::instructions::JumpTarget) -> bool {
+        let taken = condition.is_met(&self.flags);
+        self.pc = match target {
+            crate::instructions::JumpTarget::Immediate(addr) if taken => addr,
+            _ => self.pc.wrapping_add(3),
+        };
+        taken
+    }
+
+    /// 位移为有符号数，相对于JR之后的地址
+    fn jr(&mut self, condition: crate::instructions::JumpCondition, target: crate::instructions::JumpTarget) -> bool {
+        let taken = condition.is_met(&self.flags);
+        let next_pc = self.pc.wrapping_add(2);
+        self.pc = match target {
+            crate::instructions::JumpTarget::Relative(offset) if taken => next_pc.wrapping_add_signed(offset as i16),
+            _ => next_pc,
+        };
+        taken
+    }
+
+    fn call(&mut self, condition: crate::instructions::JumpCondition, address: u16) -> bool {
+        let taken = condition.is_met(&self.flags);
+        let return_address = self.pc.wrapping_add(3);
+        if taken {
+            self.sp = self.sp.wrapping_sub(2);
+            self.bus.write_word(self.sp, return_address);
+            self.pc = address;
+        } else {
+            self.pc = return_address;
         }
+        taken
     }
 
-    fn jr(&mut self, target: crate::instructions::JumpTarget) {
-        match target {
-            crate::instructions::JumpTarget::Relative(offset) => {
-                self.pc = self.pc.wrapping_add(offset as u16);
-            }
-            _ => {} // 其他跳转类型暂未实现
+    fn ret(&mut self, condition: crate::instructions::JumpCondition) -> bool {
+        let taken = condition.is_met(&self.flags);
+        if taken {
+            self.pc = self.bus.read_word(self.sp);
+            self.sp = self.sp.wrapping_add(2);
+        } else {
+            self.pc = self.pc.wrapping_add(1);
         }
+        taken
     }
 
     // 辅助方法
@@ -325,6 +377,7 @@ impl OptimizedCPU {
             crate::instructions::LoadSource::H => Ok(self.registers.h),
             crate::instructions::LoadSource::L => Ok(self.registers.l),
             crate::instructions::LoadSource::Immediate(val) => Ok(val),
+            crate::instructions::LoadSource::HighRam(offset) => Ok(self.bus.read_byte(0xFF00 | offset as u16)),
         }
     }
 
@@ -337,6 +390,7 @@ impl OptimizedCPU {
             crate::instructions::LoadTarget::E => self.registers.e = value,
             crate::instructions::LoadTarget::H => self.registers.h = value,
             crate::instructions::LoadTarget::L => self.registers.l = value,
+            crate::instructions::LoadTarget::HighRam(offset) => self.bus.write_byte(0xFF00 | offset as u16, value),
         }
     }
 
@@ -422,4 +476,61 @@ mod tests {
         
         assert_eq!(stats.cycles_per_instruction(), 2.0);
     }
+
+    #[test]
+    fn test_conditional_jumps_use_signed_offsets_and_timing() {
+        use crate::instructions::{JumpCondition, JumpTarget};
+
+        let mut cpu = OptimizedCPU::new(MemoryBus::new());
+        cpu.pc = 0x0210;
+        cpu.flags.zero = true;
+
+        // JR Z,-4：相对于0x0212向后跳
+        let jr = Instruction::JR(JumpCondition::Zero, JumpTarget::Relative(-4));
+        assert_eq!(cpu.execute_instruction(&jr).unwrap(), (3, 2));
+        assert_eq!(cpu.pc, 0x020E);
+        let jr = Instruction::JR(JumpCondition::NotZero, JumpTarget::Relative(-4));
+        assert_eq!(cpu.execute_instruction(&jr).unwrap(), (2, 2));
+        assert_eq!(cpu.pc, 0x0210);
+
+        let jp = Instruction::JP(JumpCondition::Carry, JumpTarget::Immediate(0x4000));
+        assert_eq!(cpu.execute_instruction(&jp).unwrap(), (3, 3));
+        assert_eq!(cpu.pc, 0x0213);
+
+        let call = Instruction::CALL(JumpCondition::Always, 0x0300);
+        assert_eq!(cpu.execute_instruction(&call).unwrap(), (6, 3));
+        assert_eq!((cpu.pc, cpu.sp, cpu.bus.read_word(0xFFFC)), (0x0300, 0xFFFC, 0x0216));
+
+        assert_eq!(cpu.execute_instruction(&Instruction::RET(JumpCondition::NotCarry)).unwrap(), (5, 1));
+        assert_eq!((cpu.pc, cpu.sp), (0x0216, 0xFFFE));
+        assert_eq!(cpu.execute_instruction(&Instruction::RET(JumpCondition::Carry)).unwrap(), (2, 1));
+        assert_eq!(cpu.pc, 0x0217);
+    }
+
+    #[test]
+    fn test_compare_keeps_accumulator_on_both_cores() {
+        use crate::cpu::reference::{ExecutionCore, ReferenceCPU};
+
+        // LD A,0x42; CP 0x42; CP 0x50
+        let mut bus = MemoryBus::new();
+        bus.load_program(0x0100, &[0x3E, 0x42, 0xFE, 0x42, 0xFE, 0x50]);
+        let mut optimized = OptimizedCPU::new(bus.clone());
+        let mut reference = ReferenceCPU::new(bus);
+        optimized.pc = 0x0100;
+        reference.pc = 0x0100;
+
+        for _ in 0..2 {
+            optimized.step_instruction().unwrap();
+            reference.step_instruction().unwrap();
+        }
+        assert_eq!((optimized.registers.a, optimized.pc), (0x42, 0x0104));
+        assert!(optimized.flags.zero && optimized.flags.subtract && !optimized.flags.carry);
+
+        optimized.step_instruction().unwrap();
+        reference.step_instruction().unwrap();
+        assert_eq!((optimized.registers.a, optimized.pc), (0x42, 0x0106));
+        assert!(!optimized.flags.zero && optimized.flags.carry);
+        let (optimized, reference) = (optimized.capture(), reference.capture());
+        assert_eq!((optimized.registers, optimized.flags, optimized.pc), (reference.registers, reference.flags, reference.pc));
+    }
 }
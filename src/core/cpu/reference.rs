@@ -0,0 +1,102 @@
+//! 参考解释器
+//!
+//! `CPU` 是最早的逐条解释器：不缓存、不做块执行，逻辑直接对应指令表，
+//! 作为行为基准保留。优化核心（`OptimizedCPU` 以及以后的块执行核心）
+//! 实现 `ExecutionCore` 后即可与它差分执行，见 `debug::lockstep`。
+
+use crate::emulator::{SaveMetadata, SaveState};
+use crate::memory::MemoryBus;
+pub use super::cpu::CPU as ReferenceCPU;
+use super::OptimizedCPU;
+
+/// 可以参与差分执行的CPU核心
+pub trait ExecutionCore {
+    /// 执行一条指令
+    fn step_instruction(&mut self) -> Result<(), String>;
+
+    /// 导出寄存器和内存
+    fn capture(&self) -> SaveState;
+
+    /// 从存档创建核心
+    fn restore(state: &SaveState) -> Self
+    where
+        Self: Sized;
+
+    /// `capture` 的 `cycle` 是否为机器周期数，不计周期的核心返回 `false`
+    fn counts_cycles(&self) -> bool {
+        true
+    }
+}
+
+/// 存档内存装入新的内存总线，超出总线的部分丢弃
+fn bus_from(state: &SaveState) -> MemoryBus {
+    let mut bus = MemoryBus::new();
+    let memory = bus.memory_mut();
+    let len = memory.len().min(state.memory.len());
+    memory[..len].copy_from_slice(&state.memory[..len]);
+    bus
+}
+
+impl ReferenceCPU {
+    /// 从存档创建参考解释器
+    pub fn from_state(state: &SaveState) -> Self {
+        let mut cpu = ReferenceCPU::new(bus_from(state));
+        cpu.pc = state.pc;
+        cpu.sp = state.sp;
+        cpu.registers = state.registers;
+        cpu.flags = state.flags;
+        cpu.cycle_count = state.cycle;
+        cpu
+    }
+}
+
+impl ExecutionCore for ReferenceCPU {
+    fn step_instruction(&mut self) -> Result<(), String> {
+        self.step().map(|_| ())
+    }
+
+    fn capture(&self) -> SaveState {
+        SaveState {
+            cycle: self.cycle_count,
+            pc: self.pc,
+            sp: self.sp,
+            registers: self.registers,
+            flags: self.flags,
+            memory: self.bus.memory().to_vec(),
+            metadata: SaveMetadata::default(),
+        }
+    }
+
+    fn restore(state: &SaveState) -> Self {
+        Self::from_state(state)
+    }
+}
+
+impl ExecutionCore for OptimizedCPU {
+    fn step_instruction(&mut self) -> Result<(), String> {
+        self.step_optimized()
+    }
+
+    fn capture(&self) -> SaveState {
+        SaveState {
+            cycle: self.cycle_count,
+            pc: self.pc,
+            sp: self.sp,
+            registers: self.registers,
+            flags: self.flags,
+            memory: self.bus.memory().to_vec(),
+            metadata: SaveMetadata::default(),
+        }
+    }
+
+    /// 周期计数从存档的 `cycle` 继续
+    fn restore(state: &SaveState) -> Self {
+        let mut cpu = OptimizedCPU::new(bus_from(state));
+        cpu.pc = state.pc;
+        cpu.sp = state.sp;
+        cpu.registers = state.registers;
+        cpu.flags = state.flags;
+        cpu.cycle_count = state.cycle;
+        cpu
+    }
+}
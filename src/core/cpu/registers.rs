@@ -2,6 +2,7 @@
 
 /// 8位寄存器结构
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Registers {
     pub a: u8,
     pub b: u8,
@@ -0,0 +1,240 @@
+//! 开机Logo动画
+//!
+//! 没有BootROM时的替代：把Logo解码成瓦片写入VRAM，在0x0000装入一段开机程序，
+//! 由CPU等待VBlank、逐帧写滚动寄存器，让PPU像真机一样把Logo从屏幕顶端滚到中央，
+//! 最后跳到卡带入口0x0100。默认显示本项目的Logo，也可以换成卡带头部里的Logo。
+
+use super::lcd::{LY, SCY};
+use super::LCD;
+
+/// Logo瓦片在VRAM中的起始编号（瓦片0保持空白）
+const FIRST_TILE: u8 = 1;
+/// Logo在背景图中的位置：第8行第4列起，12x2个瓦片
+const MAP_ROW: u16 = 8;
+const MAP_COLUMN: u16 = 4;
+const LOGO_COLUMNS: usize = 12;
+/// 初始纵向滚动值，Logo位于屏幕上方之外
+pub const SCROLL_START: u8 = 0x64;
+/// 滚动结束后的停留帧数
+pub const HOLD_FRAMES: u8 = 60;
+/// 开机程序结束后跳转到的卡带入口
+pub const ENTRY_POINT: u16 = 0x0100;
+
+/// 本项目的Logo文字 (48x8单色位图)
+const CRATE_LOGO_TEXT: &str = "GAMELIFE";
+
+/// 5x7点阵字形，每行用低5位表示
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        'G' => [0x0E, 0x10, 0x10, 0x17, 0x11, 0x11, 0x0E],
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        _ => [0; 7],
+    }
+}
+
+/// 生成本项目的Logo，编码方式与卡带头部的Nintendo Logo相同
+pub fn crate_logo() -> [u8; 48] {
+    let mut bitmap = [[false; 48]; 8];
+    for (i, c) in CRATE_LOGO_TEXT.chars().enumerate() {
+        for (y, bits) in glyph(c).iter().enumerate() {
+            for x in 0..5 {
+                bitmap[y][i * 6 + x] = bits & (0x10 >> x) != 0;
+            }
+        }
+    }
+    encode_logo(&bitmap)
+}
+
+/// 将48x8位图编码为头部Logo格式：
+/// 上下两半各12个4x4块，每块2字节，每个半字节是一行4个像素
+fn encode_logo(bitmap: &[[bool; 48]; 8]) -> [u8; 48] {
+    let mut logo = [0u8; 48];
+    for half in 0..2 {
+        for block in 0..LOGO_COLUMNS {
+            for row in 0..4 {
+                let mut nibble = 0u8;
+                for x in 0..4 {
+                    if bitmap[half * 4 + row][block * 4 + x] {
+                        nibble |= 0x08 >> x;
+                    }
+                }
+                let byte = half * 24 + block * 2 + row / 2;
+                logo[byte] |= if row % 2 == 0 { nibble << 4 } else { nibble };
+            }
+        }
+    }
+    logo
+}
+
+/// 将头部Logo解码为24个8x8瓦片（每个像素放大为2x2）
+pub fn logo_tiles(logo: &[u8; 48]) -> Vec<[u8; 16]> {
+    logo.chunks_exact(2)
+        .map(|block| {
+            let mut tile = [0u8; 16];
+            for row in 0..8 {
+                let byte = block[row / 4];
+                let source = if row % 4 < 2 { byte >> 4 } else { byte & 0x0F };
+                let doubled = (0..4).fold(0u8, |acc, x| {
+                    if source & (0x08 >> x) != 0 { acc | (0xC0 >> (x * 2)) } else { acc }
+                });
+                // 颜色编号3：低位和高位平面相同
+                tile[row * 2] = doubled;
+                tile[row * 2 + 1] = doubled;
+            }
+            tile
+        })
+        .collect()
+}
+
+/// 开机程序，装在0x0000执行，结束时跳到卡带入口
+///
+/// B固定为0x70，`LY + B` 只在LY为144（进入VBlank）时为0；C是当前的滚动值，
+/// 每进入一次VBlank减1写入SCY，减到0后再等待 `HOLD_FRAMES` 帧。
+pub fn boot_program() -> [u8; 39] {
+    const WAIT_VBLANK: u8 = 0x1C;
+    let (scy, ly) = ((SCY & 0xFF) as u8, (LY & 0xFF) as u8);
+    let [entry_low, entry_high] = ENTRY_POINT.to_le_bytes();
+    [
+        0x06, 0x70, //                  0x00: LD B, 0x70
+        0x0E, SCROLL_START, //          0x02: LD C, SCROLL_START
+        0x3E, 0x00, //                  0x04: LD A, 0
+        0x81, //                        0x06: ADD A, C      ; A = C，C为0时Z=1
+        0xE0, scy, //                   0x07: LDH (SCY), A
+        0x28, 0x06, //                  0x09: JR Z, 0x11
+        0xCD, WAIT_VBLANK, 0x00, //     0x0B: CALL WAIT_VBLANK
+        0x0D, //                        0x0E: DEC C
+        0x18, 0xF3, //                  0x0F: JR 0x04
+        0x0E, HOLD_FRAMES, //           0x11: LD C, HOLD_FRAMES
+        0xCD, WAIT_VBLANK, 0x00, //     0x13: CALL WAIT_VBLANK
+        0x0D, //                        0x16: DEC C
+        0x20, 0xFA, //                  0x17: JR NZ, 0x13
+        0xC3, entry_low, entry_high, // 0x19: JP ENTRY_POINT
+        0xF0, ly, //                    0x1C: LDH A, (LY)   ; 仍在第144行时等它过去
+        0x80, //                        0x1E: ADD A, B
+        0x28, 0xFB, //                  0x1F: JR Z, 0x1C
+        0xF0, ly, //                    0x21: LDH A, (LY)   ; 等待下一次进入第144行
+        0x80, //                        0x23: ADD A, B
+        0x20, 0xFB, //                  0x24: JR NZ, 0x21
+        0xC9, //                        0x26: RET
+    ]
+}
+
+/// 开机动画状态
+#[derive(Debug, Clone)]
+pub struct BootAnimation {
+    logo: [u8; 48],
+    /// 被开机程序覆盖的卡带字节，动画结束时恢复
+    cartridge: Vec<u8>,
+}
+
+impl BootAnimation {
+    /// 使用本项目Logo创建动画
+    pub fn new() -> Self {
+        Self::with_logo(crate_logo())
+    }
+
+    /// 使用指定的Logo（例如卡带头部中的Logo）创建动画
+    pub fn with_logo(logo: [u8; 48]) -> Self {
+        Self { logo, cartridge: Vec::new() }
+    }
+
+    /// 动画总帧数，即开机程序等待的VBlank次数
+    pub fn total_frames() -> u32 {
+        SCROLL_START as u32 + HOLD_FRAMES as u32
+    }
+
+    /// 把Logo瓦片和背景图写入VRAM、设置LCD，并在0x0000装入开机程序
+    ///
+    /// 调用者随后从0x0000开始执行，CPU运行到 `ENTRY_POINT` 时调用 `unmap`。
+    pub fn install(&mut self, memory: &mut [u8], lcd: &mut LCD) {
+        for (i, tile) in logo_tiles(&self.logo).iter().enumerate() {
+            let address = 0x8000 + (FIRST_TILE as usize + i) * 16;
+            memory[address..address + 16].copy_from_slice(tile);
+        }
+        for i in 0..LOGO_COLUMNS * 2 {
+            let row = MAP_ROW + (i / LOGO_COLUMNS) as u16;
+            let column = MAP_COLUMN + (i % LOGO_COLUMNS) as u16;
+            memory[(0x9800 + row * 32 + column) as usize] = FIRST_TILE + i as u8;
+        }
+
+        lcd.bg_window_tile_data = 0x8000;
+        lcd.bg_tile_map = 0x9800;
+        lcd.bg_enabled = true;
+        lcd.lcd_enabled = true;
+        lcd.bgp = 0xFC;
+
+        let program = boot_program();
+        self.cartridge = memory[..program.len()].to_vec();
+        memory[..program.len()].copy_from_slice(&program);
+    }
+
+    /// 卸下开机程序，恢复被覆盖的卡带字节
+    pub fn unmap(&self, memory: &mut [u8]) {
+        memory[..self.cartridge.len()].copy_from_slice(&self.cartridge);
+    }
+}
+
+impl Default for BootAnimation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rom::NINTENDO_LOGO;
+
+    #[test]
+    fn test_logo_tiles_double_pixels() {
+        // 头部Logo第一个4x4块: 1100 1110 1110 1101
+        let tiles = logo_tiles(&NINTENDO_LOGO);
+        assert_eq!(tiles.len(), 24);
+        assert_eq!(&tiles[0][..8], &[0xF0, 0xF0, 0xF0, 0xF0, 0xFC, 0xFC, 0xFC, 0xFC]);
+        assert_eq!(tiles[0][14], 0xF3);
+
+        let logo = crate_logo();
+        assert_ne!(logo, [0; 48]);
+        assert_eq!(logo[0] >> 4, 0b0111); // 'G' 顶行 .###
+    }
+
+    #[test]
+    fn test_boot_program_decodes_and_restores_cartridge() {
+        use crate::instructions::{Instruction, JumpCondition, JumpTarget};
+        use crate::memory::MemoryBus;
+
+        let mut bus = MemoryBus::new();
+        bus.load_program(0x0000, &[0xAA; 0x40]);
+        let mut boot = BootAnimation::new();
+        boot.install(bus.memory_mut(), &mut LCD::new());
+
+        // 程序从头到尾都能按指令解码，跳转目标都落在指令开头
+        let mut starts = Vec::new();
+        let mut address = 0u16;
+        while (address as usize) < boot_program().len() {
+            starts.push(address);
+            address += Instruction::decode(&bus, address).unwrap().size() as u16;
+        }
+        assert_eq!(address as usize, boot_program().len());
+        for &start in &starts {
+            let target = match Instruction::decode(&bus, start).unwrap() {
+                Instruction::JR(_, JumpTarget::Relative(offset)) => (start as i32 + 2 + offset as i32) as u16,
+                Instruction::CALL(_, address) => address,
+                Instruction::JP(JumpCondition::Always, JumpTarget::Immediate(address)) => {
+                    assert_eq!(address, ENTRY_POINT);
+                    continue;
+                }
+                _ => continue,
+            };
+            assert!(starts.contains(&target), "0x{:02X} -> 0x{:02X}", start, target);
+        }
+
+        boot.unmap(bus.memory_mut());
+        assert!(bus.memory()[..0x40].iter().all(|&b| b == 0xAA));
+    }
+}
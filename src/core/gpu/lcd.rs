@@ -1,5 +1,15 @@
 //! LCD控制器模拟
 
+/// 每帧的LCD周期数 (154行 × 456周期)
+pub const CYCLES_PER_FRAME: u32 = 154 * 456;
+
+/// 纵向滚动寄存器的地址
+pub const SCY: usize = 0xFF42;
+/// 横向滚动寄存器的地址
+pub const SCX: usize = 0xFF43;
+/// 当前扫描线寄存器的地址，只读
+pub const LY: usize = 0xFF44;
+
 /// LCD控制器状态
 #[derive(Debug, Clone, PartialEq)]
 pub enum LCDMode {
@@ -83,8 +93,13 @@ impl LCD {
         }
     }
 
-    /// 更新LCD状态
+    /// 更新LCD状态（不访问显存，背景按瓦片0渲染）
     pub fn update(&mut self, cycles: u32) {
+        self.tick(cycles, &[]);
+    }
+
+    /// 更新LCD状态，扫描线从 `memory`（完整地址空间）中的VRAM渲染
+    pub fn tick(&mut self, cycles: u32, memory: &[u8]) {
         if !self.lcd_enabled {
             return;
         }
@@ -126,7 +141,7 @@ impl LCD {
                 if self.mode_clock >= 172 {
                     self.mode_clock = 0;
                     self.mode = LCDMode::HBlank;
-                    self.render_scanline();
+                    self.render_scanline(memory);
                 }
             }
         }
@@ -139,9 +154,9 @@ impl LCD {
     }
 
     /// 渲染扫描线
-    fn render_scanline(&mut self) {
+    fn render_scanline(&mut self, memory: &[u8]) {
         if self.bg_enabled {
-            self.render_background();
+            self.render_background(memory);
         }
         
         if self.sprite_enabled {
@@ -150,18 +165,21 @@ impl LCD {
     }
 
     /// 渲染背景
-    fn render_background(&mut self) {
+    fn render_background(&mut self, memory: &[u8]) {
         let y = self.line as u16;
-        let tile_y = (y + self.scroll_y as u16) / 8;
-        let pixel_y = (y + self.scroll_y as u16) % 8;
+        // 背景为256x256像素，滚动时环绕
+        let bg_y = self.line.wrapping_add(self.scroll_y) as u16;
+        let tile_y = bg_y / 8;
+        let pixel_y = bg_y % 8;
         
         for x in 0..self.width {
-            let tile_x = (x + self.scroll_x as u16) / 8;
-            let pixel_x = (x + self.scroll_x as u16) % 8;
+            let bg_x = (x as u8).wrapping_add(self.scroll_x) as u16;
+            let tile_x = bg_x / 8;
+            let pixel_x = bg_x % 8;
             
             // 获取瓦片数据
-            let tile_index = self.get_tile_index(tile_x, tile_y);
-            let pixel_color = self.get_tile_pixel(tile_index, pixel_x, pixel_y);
+            let tile_index = self.get_tile_index(memory, tile_x, tile_y);
+            let pixel_color = self.get_tile_pixel(memory, tile_index, pixel_x, pixel_y);
             
             // 设置像素颜色
             let index = (y * self.width + x) as usize * 3;
@@ -179,24 +197,29 @@ impl LCD {
     }
 
     /// 获取瓦片索引
-    fn get_tile_index(&self, tile_x: u16, tile_y: u16) -> u8 {
+    fn get_tile_index(&self, memory: &[u8], tile_x: u16, tile_y: u16) -> u8 {
         let map_address = self.bg_tile_map + tile_y * 32 + tile_x;
-        // 这里应该从内存中读取瓦片索引
-        // 暂时返回0作为占位符
-        0
+        memory.get(map_address as usize).copied().unwrap_or(0)
     }
 
-    /// 获取瓦片像素
-    fn get_tile_pixel(&self, tile_index: u8, pixel_x: u16, pixel_y: u16) -> u8 {
-        let tile_address = self.bg_window_tile_data + (tile_index as u16 * 16);
-        // 这里应该从内存中读取瓦片数据
-        // 暂时返回0作为占位符
-        0
+    /// 获取瓦片像素的颜色编号 (0-3)
+    fn get_tile_pixel(&self, memory: &[u8], tile_index: u8, pixel_x: u16, pixel_y: u16) -> u8 {
+        // 0x8000模式按无符号编号寻址，0x8800模式以0x9000为基址按有符号编号寻址
+        let tile_address = if self.bg_window_tile_data == 0x8000 {
+            0x8000 + tile_index as u16 * 16
+        } else {
+            (0x9000i32 + tile_index as i8 as i32 * 16) as u16
+        };
+        let row = tile_address as usize + pixel_y as usize * 2;
+        let low = memory.get(row).copied().unwrap_or(0);
+        let high = memory.get(row + 1).copied().unwrap_or(0);
+        let bit = 7 - pixel_x as u8;
+        ((high >> bit) & 1) << 1 | ((low >> bit) & 1)
     }
 
-    /// 获取颜色
+    /// 经BGP调色板映射后的颜色
     fn get_color(&self, color_index: u8) -> (u8, u8, u8) {
-        match color_index {
+        match (self.bgp >> (color_index * 2)) & 0x03 {
             0 => (255, 255, 255), // 白色
             1 => (192, 192, 192), // 浅灰色
             2 => (96, 96, 96),    // 深灰色
@@ -210,6 +233,21 @@ impl LCD {
         &self.framebuffer
     }
 
+    /// 从地址空间读取CPU写入的滚动寄存器
+    pub fn load_registers(&mut self, memory: &[u8]) {
+        if let (Some(&scy), Some(&scx)) = (memory.get(SCY), memory.get(SCX)) {
+            self.scroll_y = scy;
+            self.scroll_x = scx;
+        }
+    }
+
+    /// 把当前扫描线写回地址空间，供CPU读取LY
+    pub fn store_registers(&self, memory: &mut [u8]) {
+        if let Some(ly) = memory.get_mut(LY) {
+            *ly = self.line;
+        }
+    }
+
     /// 重置LCD
     pub fn reset(&mut self) {
         self.mode = LCDMode::HBlank;
@@ -245,4 +283,25 @@ mod tests {
         assert_eq!(lcd.mode, LCDMode::HBlank);
         assert_eq!(lcd.line, 0);
     }
+
+    #[test]
+    fn test_registers_mirror_memory() {
+        let mut memory = vec![0u8; 0x10000];
+        memory[SCY] = 0x12;
+        memory[SCX] = 0x34;
+        let mut lcd = LCD::new();
+        lcd.load_registers(&memory);
+        assert_eq!((lcd.scroll_y, lcd.scroll_x), (0x12, 0x34));
+
+        lcd.tick(80, &memory);
+        lcd.tick(172, &memory);
+        lcd.tick(204, &memory);
+        lcd.store_registers(&mut memory);
+        assert_eq!(memory[LY], 1);
+
+        // 没有映射I/O区域的内存不受影响
+        lcd.load_registers(&[]);
+        lcd.store_registers(&mut []);
+        assert_eq!(lcd.scroll_y, 0x12);
+    }
 }
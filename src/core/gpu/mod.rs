@@ -1,9 +1,13 @@
 //! GPU模块 - Game Boy图形处理单元模拟
 
+pub mod boot;
 pub mod lcd;
 pub mod tiles;
 pub mod sprites;
+pub mod vram;
 
+pub use boot::BootAnimation;
 pub use lcd::LCD;
 pub use tiles::TileMap;
 pub use sprites::Sprite;
+pub use vram::{Tile, VideoMemory};
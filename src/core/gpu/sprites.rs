@@ -25,6 +25,20 @@ impl Sprite {
             priority: false,
         }
     }
+
+    /// 从4字节OAM条目解码 (Y, X, 瓦片编号, 属性)
+    pub fn from_oam(entry: [u8; 4]) -> Self {
+        let flags = entry[3];
+        Self {
+            y: entry[0],
+            x: entry[1],
+            tile_index: entry[2],
+            palette: (flags >> 4) & 1,
+            x_flip: flags & 0x20 != 0,
+            y_flip: flags & 0x40 != 0,
+            priority: flags & 0x80 != 0,
+        }
+    }
 }
 
 impl Default for Sprite {
@@ -0,0 +1,121 @@
+//! 显存检视 - 以类型化结构遍历VRAM瓦片和OAM精灵
+//!
+//! 所有地址计算和边界检查集中在这里，调试器、资源导出工具和测试
+//! 不需要自己切片原始内存。
+
+use super::Sprite;
+
+/// 瓦片数据起始地址
+pub const TILE_DATA_START: u16 = 0x8000;
+/// 瓦片数量 (0x8000-0x97FF)
+pub const TILE_COUNT: usize = 384;
+/// 每个瓦片的字节数 (8x8, 2bpp)
+pub const TILE_BYTES: usize = 16;
+/// OAM起始地址
+pub const OAM_START: u16 = 0xFE00;
+/// OAM精灵数量
+pub const OAM_ENTRIES: usize = 40;
+
+/// VRAM中的一个瓦片，借用原始内存
+#[derive(Debug, Clone, Copy)]
+pub struct Tile<'a> {
+    pub index: usize,
+    pub address: u16,
+    pub data: &'a [u8],
+}
+
+impl Tile<'_> {
+    /// 像素的颜色编号 (0-3)
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        let low = self.data[y * 2] >> (7 - x) & 1;
+        let high = self.data[y * 2 + 1] >> (7 - x) & 1;
+        high << 1 | low
+    }
+
+    /// 按行解码全部像素
+    pub fn pixels(&self) -> [[u8; 8]; 8] {
+        let mut pixels = [[0; 8]; 8];
+        for (y, row) in pixels.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = self.pixel(x, y);
+            }
+        }
+        pixels
+    }
+
+    /// 是否全部为颜色0
+    pub fn is_blank(&self) -> bool {
+        self.data.iter().all(|&b| b == 0)
+    }
+}
+
+/// 显存只读视图
+#[derive(Debug, Clone, Copy)]
+pub struct VideoMemory<'a> {
+    memory: &'a [u8],
+}
+
+impl<'a> VideoMemory<'a> {
+    /// 基于完整的地址空间创建视图
+    pub fn new(memory: &'a [u8]) -> Self {
+        Self { memory }
+    }
+
+    /// 获取指定编号的瓦片
+    pub fn tile(&self, index: usize) -> Option<Tile<'a>> {
+        if index >= TILE_COUNT {
+            return None;
+        }
+        let start = TILE_DATA_START as usize + index * TILE_BYTES;
+        self.memory.get(start..start + TILE_BYTES).map(|data| Tile {
+            index,
+            address: start as u16,
+            data,
+        })
+    }
+
+    /// 遍历全部瓦片
+    pub fn iter_tiles(&self) -> impl Iterator<Item = Tile<'a>> + 'a {
+        let view = *self;
+        (0..TILE_COUNT).map_while(move |index| view.tile(index))
+    }
+
+    /// 遍历OAM中的全部精灵（坐标为OAM原始值，屏幕坐标需减去 (8, 16)）
+    pub fn iter_oam(&self) -> impl Iterator<Item = Sprite> + 'a {
+        let start = OAM_START as usize;
+        self.memory
+            .get(start..start + OAM_ENTRIES * 4)
+            .unwrap_or(&[])
+            .chunks_exact(4)
+            .map(|entry| Sprite::from_oam([entry[0], entry[1], entry[2], entry[3]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryBus;
+
+    #[test]
+    fn test_iter_tiles_and_oam() {
+        let mut bus = MemoryBus::new();
+        // 瓦片1第0行: 低位 0b1000_0000, 高位 0b1100_0000 -> 颜色 3, 2, 0...
+        bus.write_byte(0x8010, 0x80);
+        bus.write_byte(0x8011, 0xC0);
+        // 精灵0
+        bus.load_program(OAM_START, &[0x20, 0x18, 0x05, 0b1011_0000]);
+
+        let video = bus.video();
+        let tiles: Vec<_> = video.iter_tiles().collect();
+        assert_eq!(tiles.len(), TILE_COUNT);
+        assert_eq!(tiles[1].address, 0x8010);
+        assert_eq!(&tiles[1].pixels()[0][..3], &[3, 2, 0]);
+        assert!(tiles[0].is_blank());
+
+        let sprites: Vec<_> = video.iter_oam().collect();
+        assert_eq!(sprites.len(), OAM_ENTRIES);
+        assert_eq!((sprites[0].y, sprites[0].x, sprites[0].tile_index), (0x20, 0x18, 0x05));
+        assert!(sprites[0].priority && sprites[0].x_flip && !sprites[0].y_flip);
+        assert_eq!(sprites[0].palette, 1);
+    }
+}
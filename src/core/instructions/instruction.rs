@@ -1,6 +1,7 @@
 //! 指令定义模块
 
-use super::{ArithmeticTarget, LoadTarget, LoadSource, LoadTarget16, LoadSource16, JumpTarget};
+use crate::memory::MemoryBus;
+use super::{ArithmeticTarget, LoadTarget, LoadSource, LoadTarget16, LoadSource16, JumpCondition, JumpTarget};
 
 /// 指令枚举
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -10,6 +11,8 @@ pub enum Instruction {
     SUB(ArithmeticTarget),
     INC(ArithmeticTarget),
     DEC(ArithmeticTarget),
+    /// 比较A和立即数：按 `SUB` 设置标志位，A不变
+    CP(u8),
     
     // 数据传输指令
     LD(LoadTarget, LoadSource),
@@ -20,16 +23,32 @@ pub enum Instruction {
     DEC16(LoadTarget16),
     
     // 控制流指令
-    JP(JumpTarget),
-    JR(JumpTarget),
+    JP(JumpCondition, JumpTarget),
+    JR(JumpCondition, JumpTarget),
+    CALL(JumpCondition, u16),
+    RET(JumpCondition),
     
     // 其他指令
     NOP,
 }
 
 impl Instruction {
-    /// 从字节解码指令
+    /// 从内存解码 `address` 处的指令，立即数操作数从其后的字节读取
+    pub fn decode(memory: &MemoryBus, address: u16) -> Option<Self> {
+        let operands = [memory.read_byte(address.wrapping_add(1)), memory.read_byte(address.wrapping_add(2))];
+        Self::from_bytes(memory.read_byte(address), operands)
+    }
+
+    /// 只按操作码解码，立即数操作数均为0，用于列举指令表
     pub fn from_byte(byte: u8) -> Option<Self> {
+        Self::from_bytes(byte, [0, 0])
+    }
+
+    /// 由操作码和其后的两个字节解码，`operands` 多余的字节被忽略
+    pub fn from_bytes(byte: u8, operands: [u8; 2]) -> Option<Self> {
+        let d8 = operands[0];
+        let d16 = u16::from_le_bytes(operands);
+        let r8 = operands[0] as i8;
         match byte {
             // 算术指令
             0x81 => Some(Instruction::ADD(ArithmeticTarget::C)),
@@ -46,28 +65,47 @@ impl Instruction {
             0x1A => Some(Instruction::LD(LoadTarget::A, LoadSource::D)), // LD A, (DE)
             
             // 甜甜的生命游戏专用指令
-            0x3E => Some(Instruction::LD(LoadTarget::A, LoadSource::Immediate(0x00))), // LD A, immediate
-            0x06 => Some(Instruction::LD(LoadTarget::B, LoadSource::Immediate(0x10))), // LD B, immediate
-            0x0E => Some(Instruction::LD(LoadTarget::C, LoadSource::Immediate(0x10))), // LD C, immediate
-            0x21 => Some(Instruction::LD16(LoadTarget16::HL, LoadSource16::Immediate(0x0280))), // LD HL, immediate
+            0x3E => Some(Instruction::LD(LoadTarget::A, LoadSource::Immediate(d8))), // LD A, d8
+            0x06 => Some(Instruction::LD(LoadTarget::B, LoadSource::Immediate(d8))), // LD B, d8
+            0x0E => Some(Instruction::LD(LoadTarget::C, LoadSource::Immediate(d8))), // LD C, d8
+            0x21 => Some(Instruction::LD16(LoadTarget16::HL, LoadSource16::Immediate(d16))), // LD HL, d16
             0x7E => Some(Instruction::LD(LoadTarget::A, LoadSource::H)), // LD A, (HL)
             0x23 => Some(Instruction::INC16(LoadTarget16::HL)), // INC HL
             0x80 => Some(Instruction::ADD(ArithmeticTarget::B)), // ADD A, B
-            0xFE => Some(Instruction::SUB(ArithmeticTarget::A)), // CP (比较指令，用SUB模拟)
-            0x28 => Some(Instruction::JR(JumpTarget::Relative(5))), // JR Z, relative
+            0xFE => Some(Instruction::CP(d8)), // CP d8
             0x77 => Some(Instruction::LD(LoadTarget::H, LoadSource::A)), // LD (HL), A
+            0xE0 => Some(Instruction::LD(LoadTarget::HighRam(d8), LoadSource::A)), // LDH (a8), A
+            0xF0 => Some(Instruction::LD(LoadTarget::A, LoadSource::HighRam(d8))), // LDH A, (a8)
             
             // 16位操作指令
-            0x01 => Some(Instruction::LD16(LoadTarget16::BC, LoadSource16::Immediate(0x1234))),
-            0x11 => Some(Instruction::LD16(LoadTarget16::DE, LoadSource16::Immediate(0x5678))),
+            0x01 => Some(Instruction::LD16(LoadTarget16::BC, LoadSource16::Immediate(d16))),
+            0x11 => Some(Instruction::LD16(LoadTarget16::DE, LoadSource16::Immediate(d16))),
             0x03 => Some(Instruction::INC16(LoadTarget16::BC)),
             0x13 => Some(Instruction::INC16(LoadTarget16::DE)),
             0x0B => Some(Instruction::DEC16(LoadTarget16::BC)),
             0x1B => Some(Instruction::DEC16(LoadTarget16::DE)),
             
             // 控制流指令
-            0xC3 => Some(Instruction::JP(JumpTarget::Immediate(0x200))),
-            0x18 => Some(Instruction::JR(JumpTarget::Relative(5))),
+            0xC3 => Some(Instruction::JP(JumpCondition::Always, JumpTarget::Immediate(d16))),
+            0xC2 => Some(Instruction::JP(JumpCondition::NotZero, JumpTarget::Immediate(d16))),
+            0xCA => Some(Instruction::JP(JumpCondition::Zero, JumpTarget::Immediate(d16))),
+            0xD2 => Some(Instruction::JP(JumpCondition::NotCarry, JumpTarget::Immediate(d16))),
+            0xDA => Some(Instruction::JP(JumpCondition::Carry, JumpTarget::Immediate(d16))),
+            0x18 => Some(Instruction::JR(JumpCondition::Always, JumpTarget::Relative(r8))),
+            0x20 => Some(Instruction::JR(JumpCondition::NotZero, JumpTarget::Relative(r8))),
+            0x28 => Some(Instruction::JR(JumpCondition::Zero, JumpTarget::Relative(r8))),
+            0x30 => Some(Instruction::JR(JumpCondition::NotCarry, JumpTarget::Relative(r8))),
+            0x38 => Some(Instruction::JR(JumpCondition::Carry, JumpTarget::Relative(r8))),
+            0xCD => Some(Instruction::CALL(JumpCondition::Always, d16)),
+            0xC4 => Some(Instruction::CALL(JumpCondition::NotZero, d16)),
+            0xCC => Some(Instruction::CALL(JumpCondition::Zero, d16)),
+            0xD4 => Some(Instruction::CALL(JumpCondition::NotCarry, d16)),
+            0xDC => Some(Instruction::CALL(JumpCondition::Carry, d16)),
+            0xC9 => Some(Instruction::RET(JumpCondition::Always)),
+            0xC0 => Some(Instruction::RET(JumpCondition::NotZero)),
+            0xC8 => Some(Instruction::RET(JumpCondition::Zero)),
+            0xD0 => Some(Instruction::RET(JumpCondition::NotCarry)),
+            0xD8 => Some(Instruction::RET(JumpCondition::Carry)),
             
             // 其他指令
             0x00 => Some(Instruction::NOP),
@@ -83,13 +121,111 @@ impl Instruction {
             Instruction::SUB(_) => "SUB",
             Instruction::INC(_) => "INC",
             Instruction::DEC(_) => "DEC",
+            Instruction::CP(_) => "CP",
             Instruction::LD(_, _) => "LD",
             Instruction::LD16(_, _) => "LD16",
             Instruction::INC16(_) => "INC16",
             Instruction::DEC16(_) => "DEC16",
-            Instruction::JP(_) => "JP",
-            Instruction::JR(_) => "JR",
+            Instruction::JP(..) => "JP",
+            Instruction::JR(..) => "JR",
+            Instruction::CALL(..) => "CALL",
+            Instruction::RET(_) => "RET",
             Instruction::NOP => "NOP",
         }
     }
+
+    /// 指令长度 (字节)
+    pub fn size(&self) -> u8 {
+        match self {
+            Instruction::LD16(..) | Instruction::JP(..) | Instruction::CALL(..) => 3,
+            Instruction::JR(..)
+            | Instruction::CP(_)
+            | Instruction::LD(_, LoadSource::Immediate(_) | LoadSource::HighRam(_))
+            | Instruction::LD(LoadTarget::HighRam(_), _) => 2,
+            _ => 1,
+        }
+    }
+
+    /// 机器周期数，条件跳转在条件成立时 `taken` 为 `true`，其余指令忽略它
+    pub fn cycles(&self, taken: bool) -> u8 {
+        match self {
+            Instruction::LD16(..) => 3,
+            Instruction::LD(_, LoadSource::Immediate(_)) | Instruction::CP(_) => 2,
+            Instruction::LD(_, LoadSource::HighRam(_)) | Instruction::LD(LoadTarget::HighRam(_), _) => 3,
+            Instruction::INC16(_) | Instruction::DEC16(_) => 2,
+            Instruction::JP(..) => if taken { 4 } else { 3 },
+            Instruction::JR(..) => if taken { 3 } else { 2 },
+            Instruction::CALL(..) => if taken { 6 } else { 3 },
+            // 无条件RET不需要判断条件，比条件成立的RET少一个周期
+            Instruction::RET(JumpCondition::Always) => 4,
+            Instruction::RET(_) => if taken { 5 } else { 2 },
+            _ => 1,
+        }
+    }
+
+    /// 对标志位的影响，依次为Z、N、H、C：字母表示按结果设置，`0`/`1` 表示固定值，`-` 表示不变
+    pub fn flags(&self) -> &'static str {
+        match self {
+            Instruction::ADD(_) => "Z0HC",
+            Instruction::SUB(_) | Instruction::CP(_) => "Z1HC",
+            Instruction::INC(_) => "Z0H-",
+            Instruction::DEC(_) => "Z1H-",
+            _ => "----",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conditional_jump_decoding_and_timing() {
+        assert_eq!(Instruction::from_byte(0x20), Some(Instruction::JR(JumpCondition::NotZero, JumpTarget::Relative(0))));
+        assert_eq!(Instruction::from_bytes(0xDA, [0x34, 0x12]), Some(Instruction::JP(JumpCondition::Carry, JumpTarget::Immediate(0x1234))));
+        assert_eq!(Instruction::from_bytes(0xCC, [0x00, 0x02]), Some(Instruction::CALL(JumpCondition::Zero, 0x200)));
+        assert_eq!(Instruction::from_byte(0xD0), Some(Instruction::RET(JumpCondition::NotCarry)));
+
+        let jr = Instruction::from_byte(0x38).unwrap();
+        assert_eq!((jr.size(), jr.cycles(true), jr.cycles(false)), (2, 3, 2));
+        let call = Instruction::from_byte(0xC4).unwrap();
+        assert_eq!((call.size(), call.cycles(true), call.cycles(false)), (3, 6, 3));
+        assert_eq!(Instruction::from_byte(0xC9).unwrap().cycles(true), 4);
+        assert_eq!(Instruction::from_byte(0xC8).unwrap().cycles(true), 5);
+
+        let cp = Instruction::from_bytes(0xFE, [0x90, 0x00]).unwrap();
+        assert_eq!(cp, Instruction::CP(0x90));
+        assert_eq!((cp.size(), cp.cycles(true), cp.flags()), (2, 2, "Z1HC"));
+    }
+
+    #[test]
+    fn test_decode_reads_immediates_from_memory() {
+        let mut bus = MemoryBus::new();
+        // LD BC,0xBEEF; LD A,0x42; JR -2; JP 0x0150
+        bus.load_program(0x0200, &[0x01, 0xEF, 0xBE, 0x3E, 0x42, 0x18, 0xFE, 0xC3, 0x50, 0x01]);
+
+        let mut address = 0x0200;
+        let mut decoded = Vec::new();
+        while address < 0x020A {
+            let instruction = Instruction::decode(&bus, address).unwrap();
+            address += instruction.size() as u16;
+            decoded.push(instruction);
+        }
+        assert_eq!(
+            decoded,
+            [
+                Instruction::LD16(LoadTarget16::BC, LoadSource16::Immediate(0xBEEF)),
+                Instruction::LD(LoadTarget::A, LoadSource::Immediate(0x42)),
+                Instruction::JR(JumpCondition::Always, JumpTarget::Relative(-2)),
+                Instruction::JP(JumpCondition::Always, JumpTarget::Immediate(0x0150)),
+            ]
+        );
+        assert_eq!(decoded[1].cycles(true), 2);
+
+        // 操作数跨越地址空间末尾时回绕
+        bus.write_byte(0xFFFF, 0x21);
+        bus.write_byte(0x0000, 0x34);
+        bus.write_byte(0x0001, 0x12);
+        assert_eq!(Instruction::decode(&bus, 0xFFFF), Some(Instruction::LD16(LoadTarget16::HL, LoadSource16::Immediate(0x1234))));
+    }
 }
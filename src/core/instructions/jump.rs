@@ -1,8 +1,45 @@
 //! 跳转指令相关枚举
 
+use crate::cpu::FlagsRegister;
+
 /// 跳转目标
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum JumpTarget {
     Immediate(u16),
+    /// 有符号位移，相对于跳转指令之后的地址
     Relative(i8),
 }
+
+/// 跳转条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpCondition {
+    Always,
+    NotZero,
+    Zero,
+    NotCarry,
+    Carry,
+}
+
+impl JumpCondition {
+    /// 按当前标志判断条件是否成立
+    pub fn is_met(&self, flags: &FlagsRegister) -> bool {
+        match self {
+            JumpCondition::Always => true,
+            JumpCondition::NotZero => !flags.zero,
+            JumpCondition::Zero => flags.zero,
+            JumpCondition::NotCarry => !flags.carry,
+            JumpCondition::Carry => flags.carry,
+        }
+    }
+
+    /// 汇编中的条件后缀，无条件时为 `None`
+    pub fn suffix(&self) -> Option<&'static str> {
+        match self {
+            JumpCondition::Always => None,
+            JumpCondition::NotZero => Some("NZ"),
+            JumpCondition::Zero => Some("Z"),
+            JumpCondition::NotCarry => Some("NC"),
+            JumpCondition::Carry => Some("C"),
+        }
+    }
+}
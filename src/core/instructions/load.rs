@@ -4,6 +4,8 @@
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LoadTarget {
     A, B, C, D, E, H, L,
+    /// `0xFF00 + n` 处的I/O寄存器或高位RAM (LDH)
+    HighRam(u8),
 }
 
 /// 8位加载源
@@ -11,6 +13,8 @@ pub enum LoadTarget {
 pub enum LoadSource {
     A, B, C, D, E, H, L,
     Immediate(u8),
+    /// `0xFF00 + n` 处的I/O寄存器或高位RAM (LDH)
+    HighRam(u8),
 }
 
 /// 16位加载目标寄存器对
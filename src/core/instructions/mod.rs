@@ -8,4 +8,4 @@ pub mod jump;
 pub use instruction::Instruction;
 pub use arithmetic::ArithmeticTarget;
 pub use load::{LoadTarget, LoadSource, LoadTarget16, LoadSource16};
-pub use jump::JumpTarget;
+pub use jump::{JumpCondition, JumpTarget};
@@ -1,16 +1,18 @@
 //! 内存总线模块 - 包含内存读写操作
 
+use crate::gpu::VideoMemory;
+
 /// 内存总线结构
 #[derive(Debug, Clone)]
 pub struct MemoryBus {
-    memory: [u8; 0xFFFF],
+    memory: [u8; 0x10000],
 }
 
 impl MemoryBus {
     /// 创建新的内存总线实例
     pub fn new() -> Self {
         Self {
-            memory: [0u8; 0xFFFF],
+            memory: [0u8; 0x10000],
         }
     }
 
@@ -27,14 +29,14 @@ impl MemoryBus {
     /// 从指定地址读取一个字（16位，小端序）
     pub fn read_word(&self, address: u16) -> u16 {
         let low = self.memory[address as usize] as u16;
-        let high = self.memory[(address + 1) as usize] as u16;
+        let high = self.memory[address.wrapping_add(1) as usize] as u16;
         (high << 8) | low
     }
     
     /// 向指定地址写入一个字（16位，小端序）
     pub fn write_word(&mut self, address: u16, value: u16) {
         self.memory[address as usize] = (value & 0xFF) as u8;
-        self.memory[(address + 1) as usize] = ((value >> 8) & 0xFF) as u8;
+        self.memory[address.wrapping_add(1) as usize] = ((value >> 8) & 0xFF) as u8;
     }
 
     /// 加载程序到内存
@@ -47,6 +49,21 @@ impl MemoryBus {
         }
     }
 
+    /// 遍历一段内存，产出 (地址, 值)；超出内存末尾的部分被截断
+    pub fn iter_range(&self, address: u16, len: usize) -> impl Iterator<Item = (u16, u8)> + '_ {
+        let start = (address as usize).min(self.memory.len());
+        let end = start.saturating_add(len).min(self.memory.len());
+        self.memory[start..end]
+            .iter()
+            .enumerate()
+            .map(move |(i, &byte)| ((start + i) as u16, byte))
+    }
+
+    /// 显存的类型化只读视图
+    pub fn video(&self) -> VideoMemory<'_> {
+        VideoMemory::new(&self.memory)
+    }
+
     /// 获取内存的只读引用
     pub fn memory(&self) -> &[u8] {
         &self.memory
@@ -63,3 +80,18 @@ impl Default for MemoryBus {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter_range_truncates_at_end() {
+        let mut bus = MemoryBus::new();
+        bus.load_program(0xC000, &[1, 2, 3]);
+
+        let bytes: Vec<_> = bus.iter_range(0xC000, 3).collect();
+        assert_eq!(bytes, vec![(0xC000, 1), (0xC001, 2), (0xC002, 3)]);
+        assert_eq!(bus.iter_range(0xFFF0, 0x100).count(), bus.memory().len() - 0xFFF0);
+    }
+}
@@ -1,5 +1,7 @@
 //! 内存模块 - 包含内存总线和内存管理
 
 pub mod bus;
+pub mod snapshot;
 
 pub use bus::MemoryBus;
+pub use snapshot::{MemoryRegion, MemorySnapshot, MemoryWatch};
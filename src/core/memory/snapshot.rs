@@ -0,0 +1,258 @@
+//! 内存快照模块 - 供调试器在模拟器运行时无冲突地读取内存
+//!
+//! 模拟线程在帧边界把关注的内存区域复制成不可变快照并发布，
+//! 读取方（调试器、外部API等）只克隆 `Arc` 指针，不会与正在执行的CPU争用内存总线。
+//! 发布是一次原子指针交换，读取方不加锁也不会等待发布方。
+
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use super::MemoryBus;
+
+/// 快照覆盖的内存区域
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub name: &'static str,
+    pub start: u16,
+    /// 结束地址（包含）
+    pub end: u16,
+}
+
+impl MemoryRegion {
+    pub const VRAM: MemoryRegion = MemoryRegion { name: "VRAM", start: 0x8000, end: 0x9FFF };
+    pub const WRAM: MemoryRegion = MemoryRegion { name: "WRAM", start: 0xC000, end: 0xDFFF };
+    pub const OAM: MemoryRegion = MemoryRegion { name: "OAM", start: 0xFE00, end: 0xFE9F };
+    pub const IO: MemoryRegion = MemoryRegion { name: "IO", start: 0xFF00, end: 0xFF7F };
+    pub const HRAM: MemoryRegion = MemoryRegion { name: "HRAM", start: 0xFF80, end: 0xFFFE };
+
+    /// 默认快照的区域：运行时会变化的RAM和寄存器
+    pub const DEFAULT: [MemoryRegion; 5] = [
+        MemoryRegion::VRAM,
+        MemoryRegion::WRAM,
+        MemoryRegion::OAM,
+        MemoryRegion::IO,
+        MemoryRegion::HRAM,
+    ];
+
+    /// 区域长度（字节）
+    pub fn size(&self) -> usize {
+        (self.end - self.start) as usize + 1
+    }
+
+    /// 地址是否在区域内
+    pub fn contains(&self, address: u16) -> bool {
+        address >= self.start && address <= self.end
+    }
+}
+
+/// 某一帧边界上的内存快照
+#[derive(Debug, Clone)]
+pub struct MemorySnapshot {
+    /// 快照对应的帧号
+    pub frame: u64,
+    regions: Vec<(MemoryRegion, Vec<u8>)>,
+}
+
+impl MemorySnapshot {
+    /// 从内存总线复制指定区域
+    pub fn capture(bus: &MemoryBus, frame: u64, regions: &[MemoryRegion]) -> Self {
+        let memory = bus.memory();
+        let regions = regions
+            .iter()
+            .map(|region| {
+                // 内存数组覆盖整个0x0000-0xFFFF，min只防止区域越界
+                let end = (region.end as usize + 1).min(memory.len());
+                (*region, memory[region.start as usize..end].to_vec())
+            })
+            .collect();
+
+        Self { frame, regions }
+    }
+
+    /// 读取快照中的字节，地址不在任何快照区域内时返回 `None`
+    pub fn read_byte(&self, address: u16) -> Option<u8> {
+        self.regions
+            .iter()
+            .find(|(region, _)| region.contains(address))
+            .and_then(|(region, data)| data.get((address - region.start) as usize).copied())
+    }
+
+    /// 读取一段连续内存，必须完全落在同一个区域内
+    pub fn read_range(&self, start: u16, len: usize) -> Option<&[u8]> {
+        let (region, data) = self.regions.iter().find(|(region, _)| region.contains(start))?;
+        let offset = (start - region.start) as usize;
+        data.get(offset..offset + len)
+    }
+
+    /// 快照包含的区域
+    pub fn regions(&self) -> impl Iterator<Item = &MemoryRegion> {
+        self.regions.iter().map(|(region, _)| region)
+    }
+}
+
+/// 保存最新快照的原子指针
+///
+/// 指针持有一个 `Arc` 强引用。读取方先在当前纪元的读者计数上登记，再加载指针并增加强引用；
+/// 发布方交换指针后依次切换纪元，等两个读者计数都清零才释放旧快照。
+/// 这时所有可能读到旧指针的读取方都已持有自己的强引用，而切换纪元保证被等待的计数只减不增。
+#[derive(Debug)]
+struct SnapshotCell {
+    ptr: AtomicPtr<MemorySnapshot>,
+    epoch: AtomicUsize,
+    readers: [AtomicUsize; 2],
+}
+
+impl SnapshotCell {
+    fn new(snapshot: Arc<MemorySnapshot>) -> Self {
+        Self {
+            ptr: AtomicPtr::new(Arc::into_raw(snapshot).cast_mut()),
+            epoch: AtomicUsize::new(0),
+            readers: [const { AtomicUsize::new(0) }; 2],
+        }
+    }
+
+    fn load(&self) -> Arc<MemorySnapshot> {
+        let readers = &self.readers[self.epoch.load(Ordering::SeqCst) & 1];
+        readers.fetch_add(1, Ordering::SeqCst);
+        let ptr = self.ptr.load(Ordering::SeqCst);
+        // SAFETY: ptr来自 `Arc::into_raw`，登记期间发布方不会释放它持有的强引用
+        let snapshot = unsafe {
+            Arc::increment_strong_count(ptr);
+            Arc::from_raw(ptr)
+        };
+        readers.fetch_sub(1, Ordering::SeqCst);
+        snapshot
+    }
+
+    fn store(&self, snapshot: Arc<MemorySnapshot>) {
+        let old = self.ptr.swap(Arc::into_raw(snapshot).cast_mut(), Ordering::SeqCst);
+        // 切换纪元后新的读取方登记到另一个计数；两个计数都等过一遍，与并发的其他发布方无关
+        let first = self.epoch.fetch_add(1, Ordering::SeqCst) & 1;
+        self.wait_for_readers(first);
+        self.epoch.fetch_add(1, Ordering::SeqCst);
+        self.wait_for_readers(first ^ 1);
+        // SAFETY: 交换前开始的读取方都已增加强引用，之后开始的只会读到新指针
+        unsafe { drop(Arc::from_raw(old)) };
+    }
+
+    fn wait_for_readers(&self, epoch: usize) {
+        // 读取方只在加载指针和增加引用计数之间登记，等待时间很短
+        while self.readers[epoch].load(Ordering::SeqCst) != 0 {
+            thread::yield_now();
+        }
+    }
+}
+
+impl Drop for SnapshotCell {
+    fn drop(&mut self) {
+        // SAFETY: 独占访问，指针持有的强引用只在这里释放一次
+        unsafe { drop(Arc::from_raw(*self.ptr.get_mut())) };
+    }
+}
+
+/// 可在线程间共享的内存观察句柄
+///
+/// 克隆开销很小，所有克隆共享同一份最新快照
+#[derive(Debug, Clone)]
+pub struct MemoryWatch {
+    latest: Arc<SnapshotCell>,
+    regions: Arc<Vec<MemoryRegion>>,
+}
+
+impl MemoryWatch {
+    /// 创建观察句柄并立即发布一份初始快照
+    pub fn new(bus: &MemoryBus, regions: &[MemoryRegion]) -> Self {
+        let snapshot = MemorySnapshot::capture(bus, 0, regions);
+        Self {
+            latest: Arc::new(SnapshotCell::new(Arc::new(snapshot))),
+            regions: Arc::new(regions.to_vec()),
+        }
+    }
+
+    /// 在帧边界发布新快照，由模拟线程调用
+    pub fn publish(&self, bus: &MemoryBus, frame: u64) {
+        self.latest.store(Arc::new(MemorySnapshot::capture(bus, frame, &self.regions)));
+    }
+
+    /// 获取最新快照，不加锁
+    pub fn latest(&self) -> Arc<MemorySnapshot> {
+        self.latest.load()
+    }
+
+    /// 最新快照的帧号
+    pub fn frame(&self) -> u64 {
+        self.latest().frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_snapshot_reads_regions() {
+        let mut bus = MemoryBus::new();
+        bus.write_byte(0xC000, 0x42);
+        bus.write_byte(0xFFFE, 0x99);
+
+        let snapshot = MemorySnapshot::capture(&bus, 7, &MemoryRegion::DEFAULT);
+        assert_eq!(snapshot.frame, 7);
+        assert_eq!(snapshot.read_byte(0xC000), Some(0x42));
+        assert_eq!(snapshot.read_byte(0xFFFE), Some(0x99));
+        assert_eq!(snapshot.read_byte(0x0100), None);
+        assert_eq!(snapshot.read_range(0xC000, 2), Some(&[0x42, 0x00][..]));
+        assert_eq!(snapshot.read_range(0xDFFF, 2), None);
+    }
+
+    #[test]
+    fn test_watch_is_readable_from_other_thread() {
+        let mut bus = MemoryBus::new();
+        let watch = MemoryWatch::new(&bus, &[MemoryRegion::WRAM]);
+        let held = watch.latest();
+
+        bus.write_byte(0xC010, 0xAB);
+        watch.publish(&bus, 1);
+
+        // 先前取得的快照不受后续发布影响
+        assert_eq!(held.read_byte(0xC010), Some(0x00));
+
+        let reader = watch.clone();
+        let value = thread::spawn(move || reader.latest().read_byte(0xC010))
+            .join()
+            .unwrap();
+        assert_eq!(value, Some(0xAB));
+        assert_eq!(watch.frame(), 1);
+    }
+
+    #[test]
+    fn test_watch_survives_concurrent_publish_and_read() {
+        let watch = MemoryWatch::new(&MemoryBus::new(), &[MemoryRegion::WRAM]);
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let watch = watch.clone();
+                thread::spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..2000 {
+                        let snapshot = watch.latest();
+                        // 快照内容与帧号一致，帧号不回退
+                        assert_eq!(snapshot.read_byte(0xC000), Some(snapshot.frame as u8));
+                        assert!(snapshot.frame >= last);
+                        last = snapshot.frame;
+                    }
+                })
+            })
+            .collect();
+
+        let mut bus = MemoryBus::new();
+        for frame in 1..=500u64 {
+            bus.write_byte(0xC000, frame as u8);
+            watch.publish(&bus, frame);
+        }
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(watch.frame(), 500);
+    }
+}
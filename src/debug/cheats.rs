@@ -0,0 +1,270 @@
+//! 金手指模块 - 内存冻结与GameShark代码
+//!
+//! 冻结条目会在每条指令或每帧结束后把固定值重新写回指定地址。
+//! 条目可以按ROM保存到同名的 `.cheats` 文本文件中。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::i18n::{tr, trf, Msg};
+use crate::memory::MemoryBus;
+
+/// 冻结值的重写时机
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreezeMode {
+    /// 每执行一条指令后重写
+    EveryStep,
+    /// 每帧结束时重写
+    EveryFrame,
+}
+
+/// 冻结条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FreezeEntry {
+    pub address: u16,
+    pub value: u8,
+    pub enabled: bool,
+    pub label: Option<String>,
+}
+
+/// 金手指引擎
+#[derive(Debug, Clone)]
+pub struct CheatEngine {
+    pub freezes: Vec<FreezeEntry>,
+    pub mode: FreezeMode,
+}
+
+impl CheatEngine {
+    /// 创建空的金手指引擎
+    pub fn new() -> Self {
+        Self {
+            freezes: Vec::new(),
+            mode: FreezeMode::EveryFrame,
+        }
+    }
+
+    /// 添加冻结条目，同一地址已存在时覆盖，返回新写入的条目
+    pub fn add_freeze(&mut self, address: u16, value: u8, label: Option<String>) -> &mut FreezeEntry {
+        let entry = FreezeEntry {
+            address,
+            value,
+            enabled: true,
+            label,
+        };
+
+        match self.freezes.iter().position(|e| e.address == address) {
+            Some(index) => {
+                self.freezes[index] = entry;
+                &mut self.freezes[index]
+            }
+            None => {
+                self.freezes.push(entry);
+                self.freezes.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// 移除冻结条目
+    pub fn remove_freeze(&mut self, address: u16) -> bool {
+        let before = self.freezes.len();
+        self.freezes.retain(|e| e.address != address);
+        self.freezes.len() != before
+    }
+
+    /// 切换冻结条目的启用状态，返回新的状态
+    pub fn toggle_freeze(&mut self, address: u16) -> Option<bool> {
+        let entry = self.freezes.iter_mut().find(|e| e.address == address)?;
+        entry.enabled = !entry.enabled;
+        Some(entry.enabled)
+    }
+
+    /// 清除所有冻结条目
+    pub fn clear(&mut self) {
+        self.freezes.clear();
+    }
+
+    /// 添加GameShark代码（格式 `01VVAAAA`，地址为低字节在前）
+    pub fn add_gameshark(&mut self, code: &str) -> Result<(), String> {
+        let code = code.trim();
+        if code.len() != 8 || !code.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(trf(Msg::CheatInvalidGameshark, &[&code]));
+        }
+
+        let byte = |i: usize| u8::from_str_radix(&code[i..i + 2], 16).unwrap();
+        let code_type = byte(0);
+        if code_type != 0x01 {
+            return Err(trf(Msg::CheatUnsupportedGameshark, &[&format!("{:02X}", code_type)]));
+        }
+
+        let value = byte(2);
+        let address = u16::from_le_bytes([byte(4), byte(6)]);
+        self.add_freeze(address, value, Some(format!("GS {}", code.to_uppercase())));
+        Ok(())
+    }
+
+    /// 把所有启用的冻结值写回内存
+    pub fn apply(&self, bus: &mut MemoryBus) {
+        for entry in self.freezes.iter().filter(|e| e.enabled) {
+            bus.write_byte(entry.address, entry.value);
+        }
+    }
+
+    /// ROM对应的金手指文件路径
+    pub fn cheat_file_for(rom_path: &Path) -> PathBuf {
+        rom_path.with_extension("cheats")
+    }
+
+    /// 从文件加载冻结条目
+    ///
+    /// 每行格式为 `地址 值 on|off [标签]`，`#` 开头为注释
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| trf(Msg::CheatFileRead, &[&path.display(), &e]))?;
+
+        let mut engine = Self::new();
+        for (line_num, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // 标签中可以有空格，连续的空白在读回时合并为一个空格
+            let mut parts = line.split_whitespace();
+            let parse_err = || trf(Msg::CheatFileLine, &[&(line_num + 1), &line]);
+            let address = parts.next().and_then(|s| parse_hex_u16(s).ok()).ok_or_else(parse_err)?;
+            let value = parts.next().and_then(|s| parse_hex_u8(s).ok()).ok_or_else(parse_err)?;
+            let enabled = match parts.next() {
+                Some("on") | None => true,
+                Some("off") => false,
+                Some(_) => return Err(parse_err()),
+            };
+            let label = Some(parts.collect::<Vec<_>>().join(" ")).filter(|s| !s.is_empty());
+
+            engine.add_freeze(address, value, label).enabled = enabled;
+        }
+
+        Ok(engine)
+    }
+
+    /// 保存冻结条目到文件
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut content = format!("{}\n", tr(Msg::CheatFileHeader));
+        for entry in &self.freezes {
+            content.push_str(&format!(
+                "{:04X} {:02X} {}",
+                entry.address,
+                entry.value,
+                if entry.enabled { "on" } else { "off" }
+            ));
+            if let Some(label) = &entry.label {
+                content.push(' ');
+                content.push_str(label);
+            }
+            content.push('\n');
+        }
+
+        fs::write(path, content).map_err(|e| trf(Msg::CheatFileWrite, &[&path.display(), &e]))
+    }
+
+    /// 格式化冻结条目列表
+    pub fn describe(&self) -> String {
+        if self.freezes.is_empty() {
+            return tr(Msg::CheatNone).to_string();
+        }
+
+        self.freezes
+            .iter()
+            .map(|e| {
+                format!(
+                    "0x{:04X} = 0x{:02X} [{}] {}",
+                    e.address,
+                    e.value,
+                    tr(if e.enabled { Msg::CheatOn } else { Msg::CheatOff }),
+                    e.label.as_deref().unwrap_or("")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Default for CheatEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 解析十六进制地址，允许 `0x` 前缀
+pub fn parse_hex_u16(s: &str) -> Result<u16, String> {
+    let digits = s.trim_start_matches("0x").trim_start_matches("0X");
+    u16::from_str_radix(digits, 16).map_err(|_| trf(Msg::CheatInvalidAddress, &[&s]))
+}
+
+/// 解析十六进制字节，允许 `0x` 前缀
+pub fn parse_hex_u8(s: &str) -> Result<u8, String> {
+    let digits = s.trim_start_matches("0x").trim_start_matches("0X");
+    u8::from_str_radix(digits, 16).map_err(|_| trf(Msg::CheatInvalidValue, &[&s]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_freeze_is_reapplied() {
+        let mut engine = CheatEngine::new();
+        engine.add_freeze(0xC100, 0x63, Some("生命".to_string()));
+
+        let mut bus = MemoryBus::new();
+        bus.write_byte(0xC100, 0x01);
+        engine.apply(&mut bus);
+        assert_eq!(bus.read_byte(0xC100), 0x63);
+
+        engine.toggle_freeze(0xC100);
+        bus.write_byte(0xC100, 0x01);
+        engine.apply(&mut bus);
+        assert_eq!(bus.read_byte(0xC100), 0x01);
+    }
+
+    #[test]
+    fn test_gameshark_code() {
+        let mut engine = CheatEngine::new();
+        engine.add_gameshark("01FF24CF").unwrap();
+        assert_eq!(engine.freezes[0].address, 0xCF24);
+        assert_eq!(engine.freezes[0].value, 0xFF);
+
+        assert!(engine.add_gameshark("91FF24CF").is_err());
+        assert!(engine.add_gameshark("01FF24").is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let mut engine = CheatEngine::new();
+        engine.add_freeze(0xC000, 0x09, Some("金币 数量".to_string()));
+        engine.add_freeze(0xD010, 0xFF, None);
+        engine.toggle_freeze(0xD010);
+
+        let rom = std::env::temp_dir().join(format!("cheat_round_trip_{}.gb", std::process::id()));
+        let path = CheatEngine::cheat_file_for(&rom);
+        engine.save(&path).unwrap();
+        let loaded = CheatEngine::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.freezes, engine.freezes);
+    }
+
+    #[test]
+    fn test_load_overrides_duplicates_and_splits_on_any_whitespace() {
+        let path = std::env::temp_dir().join(format!("cheat_load_{}.cht", std::process::id()));
+        fs::write(&path, "C000 09 on\nD010\tFF  on 名字\nC000 10 off\t 多个   空格\n").unwrap();
+        let loaded = CheatEngine::load(&path);
+        let _ = fs::remove_file(&path);
+        let loaded = loaded.unwrap();
+
+        // 重复的地址覆盖前面的条目，启用状态也跟着最后一行
+        assert_eq!(loaded.freezes.len(), 2);
+        assert_eq!(loaded.freezes[0], FreezeEntry { address: 0xC000, value: 0x10, enabled: false, label: Some("多个 空格".to_string()) });
+        assert_eq!((loaded.freezes[1].value, loaded.freezes[1].enabled), (0xFF, true));
+        assert_eq!(loaded.freezes[1].label.as_deref(), Some("名字"));
+    }
+}
@@ -4,8 +4,12 @@ use std::collections::HashMap;
 use crate::cpu::{CPU, Registers, FlagsRegister};
 use crate::memory::MemoryBus;
 use crate::instructions::Instruction;
+use crate::i18n::{tr, trf, Msg};
 use super::breakpoint::Breakpoint;
+use super::diff::TraceEntry;
 use super::disassembler::Disassembler;
+use super::cheats::{parse_hex_u16, parse_hex_u8, CheatEngine, FreezeMode};
+use std::path::Path;
 
 /// 调试器状态
 #[derive(Debug, Clone, PartialEq)]
@@ -27,6 +31,7 @@ pub struct Debugger {
     pub log_level: LogLevel,
     pub instruction_history: Vec<InstructionRecord>,
     pub max_history: usize,
+    pub cheats: CheatEngine,
 }
 
 /// 日志级别
@@ -47,6 +52,7 @@ pub struct InstructionRecord {
     pub instruction: Instruction,
     pub registers: Registers,
     pub flags: FlagsRegister,
+    /// 记录时CPU已执行的机器周期数
     pub cycle: u64,
 }
 
@@ -62,6 +68,7 @@ impl Debugger {
             log_level: LogLevel::Info,
             instruction_history: Vec::new(),
             max_history: 1000,
+            cheats: CheatEngine::new(),
         }
     }
 
@@ -115,13 +122,13 @@ impl Debugger {
     }
 
     /// 记录指令执行
-    pub fn record_instruction(&mut self, pc: u16, instruction: Instruction, cpu: &CPU, cycle: u64) {
+    pub fn record_instruction(&mut self, pc: u16, instruction: Instruction, cpu: &CPU) {
         let record = InstructionRecord {
             pc,
             instruction: instruction.clone(),
             registers: cpu.registers.clone(),
             flags: cpu.flags.clone(),
-            cycle,
+            cycle: cpu.cycle_count,
         };
 
         self.instruction_history.push(record);
@@ -153,6 +160,76 @@ impl Debugger {
         self.disassembler.disassemble_range(start, end, memory)
     }
 
+    /// 执行金手指管理命令，返回要显示的结果
+    ///
+    /// 支持的命令：
+    /// - `freeze <地址> <值> [标签]` / `unfreeze <地址>` / `freeze toggle <地址>`
+    /// - `freeze list` / `freeze clear` / `freeze mode step|frame`
+    /// - `gameshark <代码>`
+    /// - `cheats save <ROM路径>` / `cheats load <ROM路径>`
+    pub fn run_cheat_command(&mut self, line: &str) -> Result<String, String> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            ["freeze", "list"] | ["freezes"] => Ok(self.cheats.describe()),
+            ["freeze", "clear"] => {
+                self.cheats.clear();
+                Ok(tr(Msg::CheatCleared).to_string())
+            }
+            ["freeze", "mode", mode] => {
+                self.cheats.mode = match *mode {
+                    "step" => FreezeMode::EveryStep,
+                    "frame" => FreezeMode::EveryFrame,
+                    _ => return Err(trf(Msg::CheatUnknownMode, &[mode])),
+                };
+                Ok(trf(Msg::CheatModeSet, &[&format!("{:?}", self.cheats.mode)]))
+            }
+            ["freeze", "toggle", address] => {
+                let address = parse_hex_u16(address)?;
+                match self.cheats.toggle_freeze(address) {
+                    Some(enabled) => {
+                        let message = if enabled { Msg::CheatEnabled } else { Msg::CheatDisabled };
+                        Ok(trf(message, &[&format!("{:04X}", address)]))
+                    }
+                    None => Err(trf(Msg::CheatNotFrozen, &[&format!("{:04X}", address)])),
+                }
+            }
+            ["freeze", address, value, label @ ..] => {
+                let address = parse_hex_u16(address)?;
+                let value = parse_hex_u8(value)?;
+                let label = if label.is_empty() { None } else { Some(label.join(" ")) };
+                self.cheats.add_freeze(address, value, label);
+                self.log(LogLevel::Info, &format!("冻结地址 0x{:04X} = 0x{:02X}", address, value));
+                Ok(trf(Msg::CheatFrozen, &[&format!("{:04X}", address), &format!("{:02X}", value)]))
+            }
+            ["unfreeze", address] => {
+                let address = parse_hex_u16(address)?;
+                if self.cheats.remove_freeze(address) {
+                    Ok(trf(Msg::CheatUnfrozen, &[&format!("{:04X}", address)]))
+                } else {
+                    Err(trf(Msg::CheatNotFrozen, &[&format!("{:04X}", address)]))
+                }
+            }
+            ["gameshark", code] => {
+                self.cheats.add_gameshark(code)?;
+                Ok(trf(Msg::CheatGamesharkAdded, &[code]))
+            }
+            ["cheats", "save", rom] => {
+                let path = CheatEngine::cheat_file_for(Path::new(rom));
+                self.cheats.save(&path)?;
+                Ok(trf(Msg::CheatSaved, &[&path.display()]))
+            }
+            ["cheats", "load", rom] => {
+                let path = CheatEngine::cheat_file_for(Path::new(rom));
+                let mode = self.cheats.mode;
+                self.cheats = CheatEngine::load(&path)?;
+                self.cheats.mode = mode;
+                Ok(trf(Msg::CheatLoaded, &[&path.display(), &self.cheats.freezes.len()]))
+            }
+            _ => Err(trf(Msg::CheatUnknownCommand, &[&line.trim()])),
+        }
+    }
+
     /// 设置日志级别
     pub fn set_log_level(&mut self, level: LogLevel) {
         self.log_level = level;
@@ -196,6 +273,14 @@ impl Debugger {
         &self.instruction_history
     }
 
+    /// 把指令历史导出为跟踪日志，格式见 `debug::diff`
+    pub fn trace_log(&self) -> String {
+        self.instruction_history
+            .iter()
+            .map(|record| format!("{}\n", TraceEntry::from(record)))
+            .collect()
+    }
+
     /// 设置最大步数
     pub fn set_max_steps(&mut self, max_steps: Option<u64>) {
         self.max_steps = max_steps;
@@ -218,6 +303,7 @@ impl Debugger {
 
 /// CPU状态快照
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CPUState {
     pub pc: u16,
     pub sp: u16,
@@ -253,6 +339,23 @@ mod tests {
         assert_eq!(debugger.breakpoints.len(), 0);
     }
 
+    #[test]
+    fn test_cheat_commands() {
+        let mut debugger = Debugger::new();
+        debugger.run_cheat_command("freeze C0A0 63 生命 值").unwrap();
+        debugger.run_cheat_command("gameshark 010524CF").unwrap();
+        assert_eq!(debugger.cheats.freezes.len(), 2);
+        assert_eq!(debugger.cheats.freezes[0].label.as_deref(), Some("生命 值"));
+
+        debugger.run_cheat_command("freeze mode step").unwrap();
+        assert_eq!(debugger.cheats.mode, FreezeMode::EveryStep);
+
+        debugger.run_cheat_command("unfreeze 0xC0A0").unwrap();
+        assert_eq!(debugger.cheats.freezes.len(), 1);
+        assert!(debugger.run_cheat_command("unfreeze C0A0").is_err());
+        assert!(debugger.run_cheat_command("freeze ZZZZ 01").is_err());
+    }
+
     #[test]
     fn test_log_levels() {
         let mut debugger = Debugger::new();
@@ -0,0 +1,171 @@
+//! 确定性校验
+//!
+//! 从同一存档出发、按同一份录制的输入运行两次，比较每一帧结束时的哈希。
+//! 两次运行在同一线程中依次进行，不读取墙钟，结果应当逐帧相同；
+//! 新的子系统如果引入了熵源、线程或时间依赖，第一处不同的帧会被报告出来。
+
+use std::fmt;
+
+use crate::emulator::{GameBoy, SaveState};
+use crate::frontend::{Emulator, InputEvent};
+use crate::util::hash::{sha1, to_hex};
+
+/// 逐帧录制的输入
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputLog {
+    frames: Vec<Vec<InputEvent>>,
+}
+
+impl InputLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录在第 `frame` 帧开始前发生的按键事件
+    pub fn record(&mut self, frame: usize, event: InputEvent) {
+        if self.frames.len() <= frame {
+            self.frames.resize(frame + 1, Vec::new());
+        }
+        self.frames[frame].push(event);
+    }
+
+    /// 第 `frame` 帧的按键事件
+    pub fn events(&self, frame: usize) -> &[InputEvent] {
+        self.frames.get(frame).map_or(&[], Vec::as_slice)
+    }
+
+    /// 录制覆盖的帧数
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// 确定性校验失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeterminismError {
+    /// 创建或恢复模拟器失败
+    Setup(String),
+    /// 某次运行在某一帧出错
+    Emulation { run: usize, frame: usize, message: String },
+    /// 两次运行在某一帧的哈希不同
+    Diverged { frame: usize, first: [u8; 20], second: [u8; 20] },
+}
+
+impl fmt::Display for DeterminismError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeterminismError::Setup(message) => write!(f, "无法创建模拟器: {}", message),
+            DeterminismError::Emulation { run, frame, message } => {
+                write!(f, "第 {} 次运行在第 {} 帧出错: {}", run, frame, message)
+            }
+            DeterminismError::Diverged { frame, first, second } => {
+                write!(f, "第 {} 帧结果不一致: {} != {}", frame, to_hex(first), to_hex(second))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeterminismError {}
+
+/// 存档机器状态的哈希，不包含元数据
+pub fn state_hash(state: &SaveState) -> [u8; 20] {
+    sha1(&state.machine_bytes())
+}
+
+/// 当前画面的哈希，没有画面输出的核心返回 `None`
+pub fn frame_hash<E: Emulator>(emulator: &E) -> Option<[u8; 20]> {
+    emulator.frame().map(|frame| sha1(frame.pixels))
+}
+
+fn run_once<E: Emulator>(
+    run: usize,
+    make: &mut impl FnMut() -> Result<E, String>,
+    inputs: &InputLog,
+    frames: usize,
+    hash: &impl Fn(&E) -> [u8; 20],
+) -> Result<Vec<[u8; 20]>, DeterminismError> {
+    let mut emulator = make().map_err(DeterminismError::Setup)?;
+    let mut hashes = Vec::with_capacity(frames);
+    for frame in 0..frames {
+        for event in inputs.events(frame) {
+            emulator.set_button(event.button, event.pressed);
+        }
+        emulator
+            .run_frame()
+            .map_err(|message| DeterminismError::Emulation { run, frame, message })?;
+        hashes.push(hash(&emulator));
+    }
+    Ok(hashes)
+}
+
+/// 用 `make` 创建两个相同初始状态的模拟器，按 `inputs` 各运行 `frames` 帧，
+/// 逐帧比较 `hash`，全部一致时返回每帧的哈希
+pub fn check_determinism<E: Emulator>(
+    mut make: impl FnMut() -> Result<E, String>,
+    inputs: &InputLog,
+    frames: usize,
+    hash: impl Fn(&E) -> [u8; 20],
+) -> Result<Vec<[u8; 20]>, DeterminismError> {
+    let first = run_once(1, &mut make, inputs, frames, &hash)?;
+    let second = run_once(2, &mut make, inputs, frames, &hash)?;
+    match first.iter().zip(&second).position(|(a, b)| a != b) {
+        Some(frame) => Err(DeterminismError::Diverged { frame, first: first[frame], second: second[frame] }),
+        None => Ok(first),
+    }
+}
+
+/// 从存档恢复两个 `GameBoy` 校验确定性，以完整存档作为每帧的哈希
+pub fn check_savestate(state: &SaveState, inputs: &InputLog, frames: usize) -> Result<Vec<[u8; 20]>, DeterminismError> {
+    let make = || {
+        let mut gameboy = GameBoy::new();
+        gameboy.load_state(state)?;
+        Ok(gameboy)
+    };
+    check_determinism(make, inputs, frames, |gameboy: &GameBoy| state_hash(&gameboy.save_state()))
+}
+
+/// 测试用：不一致时panic
+pub fn assert_deterministic(state: &SaveState, inputs: &InputLog, frames: usize) {
+    if let Err(error) = check_savestate(state, inputs, frames) {
+        panic!("{}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::Button;
+    use std::sync::atomic::{AtomicU8, Ordering};
+
+    #[test]
+    fn test_gameboy_is_deterministic_and_divergence_is_reported() {
+        // 0x200: INC C; ADD A,C; JP 0x200
+        let looping = || {
+            let mut gameboy = GameBoy::new();
+            gameboy.load_program(0x200, &[0x0C, 0x81, 0xC3, 0x00, 0x02]);
+            gameboy
+        };
+        let gameboy = looping();
+        let mut inputs = InputLog::new();
+        inputs.record(1, InputEvent { button: Button::A, pressed: true });
+        assert_eq!(inputs.len(), 2);
+        assert_deterministic(&gameboy.save_state(), &inputs, 2);
+
+        // 每次创建时读取共享计数器，模拟隐藏的外部状态
+        static SEED: AtomicU8 = AtomicU8::new(0);
+        let make = || {
+            let mut gameboy = looping();
+            gameboy.load_program(0xC000, &[SEED.fetch_add(1, Ordering::Relaxed)]);
+            Ok(gameboy)
+        };
+        let hash = |gameboy: &GameBoy| sha1(&gameboy.memory()[0xC000..0xC001]);
+        match check_determinism(make, &InputLog::new(), 2, hash) {
+            Err(DeterminismError::Diverged { frame, .. }) => assert_eq!(frame, 0),
+            other => panic!("预期不一致，实际 {:?}", other),
+        }
+    }
+}
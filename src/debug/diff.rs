@@ -0,0 +1,406 @@
+//! 状态差异比较
+//!
+//! 比较两个存档或两份指令跟踪日志，给出第一个分歧的周期、
+//! 不同的寄存器和不同的内存区间，用于验证核心重构前后行为一致。
+//!
+//! 跟踪日志每行一条指令，由 `key=value` 字段组成，例如
+//! `cycle=12 pc=0150 a=00 b=00 c=00 d=00 e=00 f=00 h=00 l=00 flags=Z-H-`；
+//! 空行和以 `#` 开头的行会被忽略。
+
+use std::fmt;
+
+use crate::cpu::{FlagsRegister, Registers};
+use crate::emulator::SaveState;
+use crate::memory::MemoryRegion;
+use super::debugger::InstructionRecord;
+
+/// 一个不同的寄存器
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterDiff {
+    pub name: &'static str,
+    pub left: u64,
+    pub right: u64,
+}
+
+/// 一段连续的不同内存
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryDiff {
+    pub start: u16,
+    pub left: Vec<u8>,
+    pub right: Vec<u8>,
+}
+
+impl MemoryDiff {
+    /// 结束地址（包含）
+    pub fn end(&self) -> u16 {
+        self.start + (self.left.len() as u16 - 1)
+    }
+
+    /// 所在的内存区域名称
+    pub fn region(&self) -> Option<&'static str> {
+        MemoryRegion::DEFAULT
+            .iter()
+            .find(|region| region.contains(self.start))
+            .map(|region| region.name)
+    }
+}
+
+/// 两个存档之间的差异
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateDiff {
+    pub left_cycle: u64,
+    pub right_cycle: u64,
+    pub registers: Vec<RegisterDiff>,
+    pub memory: Vec<MemoryDiff>,
+}
+
+impl StateDiff {
+    /// 寄存器和内存是否完全相同（不比较周期计数）
+    pub fn is_empty(&self) -> bool {
+        self.registers.is_empty() && self.memory.is_empty()
+    }
+
+    /// 不同的内存字节数
+    pub fn differing_bytes(&self) -> usize {
+        self.memory.iter().map(|diff| diff.left.len()).sum()
+    }
+}
+
+/// 跟踪日志中的一条记录
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceEntry {
+    pub cycle: u64,
+    pub pc: u16,
+    pub registers: Registers,
+    pub flags: FlagsRegister,
+}
+
+impl TraceEntry {
+    /// 解析一行跟踪日志
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut cycle = None;
+        let mut pc = None;
+        let mut registers = Registers::new();
+        let mut flags = FlagsRegister::new();
+
+        for field in line.split_whitespace() {
+            let (key, value) = field.split_once('=').ok_or_else(|| format!("无效的字段: {}", field))?;
+            let hex = || u16::from_str_radix(value, 16).map_err(|_| format!("无效的十六进制数: {}", field));
+            let byte = || u8::from_str_radix(value, 16).map_err(|_| format!("无效的十六进制数: {}", field));
+            match key {
+                "cycle" => cycle = Some(value.parse::<u64>().map_err(|_| format!("无效的周期: {}", value))?),
+                "pc" => pc = Some(hex()?),
+                "a" => registers.a = byte()?,
+                "b" => registers.b = byte()?,
+                "c" => registers.c = byte()?,
+                "d" => registers.d = byte()?,
+                "e" => registers.e = byte()?,
+                "f" => registers.f = byte()?,
+                "h" => registers.h = byte()?,
+                "l" => registers.l = byte()?,
+                "flags" => {
+                    let chars: Vec<char> = value.chars().collect();
+                    if chars.len() != 4 {
+                        return Err(format!("无效的标志: {}", value));
+                    }
+                    flags.zero = chars[0] == 'Z';
+                    flags.subtract = chars[1] == 'N';
+                    flags.half_carry = chars[2] == 'H';
+                    flags.carry = chars[3] == 'C';
+                }
+                // 其他工具可能附加额外字段（反汇编等），忽略
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            cycle: cycle.ok_or_else(|| format!("缺少cycle字段: {}", line))?,
+            pc: pc.ok_or_else(|| format!("缺少pc字段: {}", line))?,
+            registers,
+            flags,
+        })
+    }
+}
+
+impl From<&InstructionRecord> for TraceEntry {
+    fn from(record: &InstructionRecord) -> Self {
+        Self {
+            cycle: record.cycle,
+            pc: record.pc,
+            registers: record.registers,
+            flags: record.flags,
+        }
+    }
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let r = &self.registers;
+        write!(
+            f,
+            "cycle={} pc={:04X} a={:02X} b={:02X} c={:02X} d={:02X} e={:02X} f={:02X} h={:02X} l={:02X} flags={}{}{}{}",
+            self.cycle, self.pc, r.a, r.b, r.c, r.d, r.e, r.f, r.h, r.l,
+            if self.flags.zero { 'Z' } else { '-' },
+            if self.flags.subtract { 'N' } else { '-' },
+            if self.flags.half_carry { 'H' } else { '-' },
+            if self.flags.carry { 'C' } else { '-' },
+        )
+    }
+}
+
+/// 解析整份跟踪日志
+pub fn parse_trace(log: &str) -> Result<Vec<TraceEntry>, String> {
+    log.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| TraceEntry::parse(line).map_err(|e| format!("第 {} 行: {}", i + 1, e)))
+        .collect()
+}
+
+/// 两份跟踪日志第一次分歧的位置
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceDivergence {
+    /// 记录序号
+    pub index: usize,
+    pub left: TraceEntry,
+    pub right: TraceEntry,
+    pub registers: Vec<RegisterDiff>,
+}
+
+impl TraceDivergence {
+    /// 第一个分歧的周期（取较早的一侧）
+    pub fn cycle(&self) -> u64 {
+        self.left.cycle.min(self.right.cycle)
+    }
+}
+
+/// 两份跟踪日志的差异
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceDiff {
+    /// 逐条比较过的记录数
+    pub compared: usize,
+    pub left_len: usize,
+    pub right_len: usize,
+    pub first_divergence: Option<TraceDivergence>,
+}
+
+impl TraceDiff {
+    /// 两份日志是否完全一致
+    pub fn is_identical(&self) -> bool {
+        self.first_divergence.is_none() && self.left_len == self.right_len
+    }
+}
+
+/// 参与比较的CPU字段
+fn cpu_fields(pc: u16, sp: Option<u16>, r: &Registers, flags: &FlagsRegister) -> Vec<(&'static str, u16)> {
+    let flags = (flags.zero as u16) << 7
+        | (flags.subtract as u16) << 6
+        | (flags.half_carry as u16) << 5
+        | (flags.carry as u16) << 4;
+    let mut fields = vec![("PC", pc)];
+    fields.extend(sp.map(|sp| ("SP", sp)));
+    fields.extend([
+        ("A", r.a as u16),
+        ("B", r.b as u16),
+        ("C", r.c as u16),
+        ("D", r.d as u16),
+        ("E", r.e as u16),
+        ("F", r.f as u16),
+        ("H", r.h as u16),
+        ("L", r.l as u16),
+        ("FLAGS", flags),
+    ]);
+    fields
+}
+
+fn compare_fields(left: &[(&'static str, u16)], right: &[(&'static str, u16)]) -> Vec<RegisterDiff> {
+    left.iter()
+        .zip(right)
+        .filter(|(l, r)| l.1 != r.1)
+        .map(|(l, r)| RegisterDiff { name: l.0, left: l.1.into(), right: r.1.into() })
+        .collect()
+}
+
+/// 比较两段内存，相邻的不同字节合并为一个区间
+pub fn diff_memory(left: &[u8], right: &[u8]) -> Vec<MemoryDiff> {
+    let mut diffs: Vec<MemoryDiff> = Vec::new();
+    // 长度不同时，较短一侧缺失的部分视为0
+    let len = left.len().max(right.len()).min(0x10000);
+    for address in 0..len {
+        let l = left.get(address).copied().unwrap_or(0);
+        let r = right.get(address).copied().unwrap_or(0);
+        if l == r {
+            continue;
+        }
+        match diffs.last_mut() {
+            Some(last) if last.start as usize + last.left.len() == address => {
+                last.left.push(l);
+                last.right.push(r);
+            }
+            _ => diffs.push(MemoryDiff { start: address as u16, left: vec![l], right: vec![r] }),
+        }
+    }
+    diffs
+}
+
+/// 比较两个存档
+pub fn diff_states(left: &SaveState, right: &SaveState) -> StateDiff {
+    let registers = compare_fields(
+        &cpu_fields(left.pc, Some(left.sp), &left.registers, &left.flags),
+        &cpu_fields(right.pc, Some(right.sp), &right.registers, &right.flags),
+    );
+    StateDiff {
+        left_cycle: left.cycle,
+        right_cycle: right.cycle,
+        registers,
+        memory: diff_memory(&left.memory, &right.memory),
+    }
+}
+
+/// 逐条比较两份跟踪记录，找出第一处分歧
+pub fn diff_traces(left: &[TraceEntry], right: &[TraceEntry]) -> TraceDiff {
+    let mut compared = 0;
+    let mut first_divergence = None;
+    for (index, (l, r)) in left.iter().zip(right).enumerate() {
+        compared += 1;
+        let mut registers = compare_fields(
+            &cpu_fields(l.pc, None, &l.registers, &l.flags),
+            &cpu_fields(r.pc, None, &r.registers, &r.flags),
+        );
+        if l.cycle != r.cycle {
+            // 周期不同时寄存器可能相同，仍然算作分歧
+            registers.insert(0, RegisterDiff { name: "CYCLE", left: l.cycle, right: r.cycle });
+        }
+        if !registers.is_empty() {
+            first_divergence = Some(TraceDivergence { index, left: *l, right: *r, registers });
+            break;
+        }
+    }
+
+    TraceDiff { compared, left_len: left.len(), right_len: right.len(), first_divergence }
+}
+
+/// 解析并比较两份跟踪日志文本
+pub fn diff_trace_logs(left: &str, right: &str) -> Result<TraceDiff, String> {
+    Ok(diff_traces(&parse_trace(left)?, &parse_trace(right)?))
+}
+
+fn write_registers(f: &mut fmt::Formatter<'_>, registers: &[RegisterDiff]) -> fmt::Result {
+    for diff in registers {
+        writeln!(f, "  {:<5} {:04X} != {:04X}", diff.name, diff.left, diff.right)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "状态一致 (周期 {} / {})", self.left_cycle, self.right_cycle);
+        }
+        writeln!(f, "状态不一致 (周期 {} / {})", self.left_cycle, self.right_cycle)?;
+        if !self.registers.is_empty() {
+            writeln!(f, "寄存器:")?;
+            write_registers(f, &self.registers)?;
+        }
+        if !self.memory.is_empty() {
+            writeln!(f, "内存: {} 处区间, {} 字节", self.memory.len(), self.differing_bytes())?;
+            for diff in &self.memory {
+                let preview = |bytes: &[u8]| {
+                    let shown: Vec<String> = bytes.iter().take(8).map(|b| format!("{:02X}", b)).collect();
+                    if bytes.len() > 8 { format!("{} ..", shown.join(" ")) } else { shown.join(" ") }
+                };
+                writeln!(
+                    f,
+                    "  {:04X}-{:04X} {:<5} {} != {}",
+                    diff.start,
+                    diff.end(),
+                    diff.region().unwrap_or("-"),
+                    preview(&diff.left),
+                    preview(&diff.right)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TraceDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.first_divergence {
+            Some(divergence) => {
+                writeln!(f, "第 {} 条记录（周期 {}）出现分歧:", divergence.index, divergence.cycle())?;
+                writeln!(f, "  < {}", divergence.left)?;
+                writeln!(f, "  > {}", divergence.right)?;
+                write_registers(f, &divergence.registers)
+            }
+            None if self.left_len != self.right_len => writeln!(
+                f,
+                "前 {} 条记录一致，但长度不同: {} / {}",
+                self.compared, self.left_len, self.right_len
+            ),
+            None => writeln!(f, "跟踪日志一致 ({} 条记录)", self.compared),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBoy;
+
+    #[test]
+    fn test_diff_states_reports_registers_and_ranges() {
+        let mut left = GameBoy::new();
+        left.load_program(0x100, &[0x0C, 0x0C]);
+        let mut right = GameBoy::new();
+        right.load_program(0x100, &[0x0C, 0x0C]);
+        assert!(diff_states(&left.save_state(), &right.save_state()).is_empty());
+
+        left.run_steps(2).unwrap();
+        right.run_steps(1).unwrap();
+        let mut a = left.save_state();
+        let mut b = right.save_state();
+        a.memory[0xC000..0xC003].copy_from_slice(&[1, 2, 3]);
+        b.memory[0xC010] = 9;
+
+        let diff = diff_states(&a, &b);
+        let names: Vec<&str> = diff.registers.iter().map(|r| r.name).collect();
+        assert_eq!(names, vec!["PC", "C"]);
+        assert_eq!(diff.memory.len(), 2);
+        assert_eq!((diff.memory[0].start, diff.memory[0].end()), (0xC000, 0xC002));
+        assert_eq!(diff.memory[1].region(), Some("WRAM"));
+        assert_eq!(diff.differing_bytes(), 4);
+        assert!(diff.to_string().contains("C000-C002 WRAM"));
+    }
+
+    #[test]
+    fn test_trace_diff_finds_first_divergent_cycle() {
+        let entry = |cycle: u64, pc: u16, a: u8| {
+            let mut entry = TraceEntry { cycle, pc, registers: Registers::new(), flags: FlagsRegister::new() };
+            entry.registers.a = a;
+            entry
+        };
+        let left: Vec<String> = [entry(0, 0x100, 0), entry(1, 0x101, 1), entry(2, 0x102, 2)]
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        let right = format!("# reference core\n{}\n{}\n\n{}\n", left[0], left[1], entry(2, 0x102, 3));
+
+        let parsed = parse_trace(&left.join("\n")).unwrap();
+        assert_eq!(parsed[1], entry(1, 0x101, 1));
+
+        let diff = diff_trace_logs(&left.join("\n"), &right).unwrap();
+        let divergence = diff.first_divergence.as_ref().unwrap();
+        assert_eq!((divergence.index, divergence.cycle()), (2, 2));
+        assert_eq!(divergence.registers, vec![RegisterDiff { name: "A", left: 2, right: 3 }]);
+
+        let short = diff_trace_logs(&left.join("\n"), &left[..2].join("\n")).unwrap();
+        assert!(short.first_divergence.is_none() && !short.is_identical());
+        assert!(parse_trace("pc=0100").is_err());
+
+        // 周期数超过16位时不截断
+        let diff = diff_traces(&[entry(0x10000, 0x100, 0)], &[entry(0x20000, 0x100, 0)]);
+        let divergence = diff.first_divergence.unwrap();
+        assert_eq!(divergence.registers, vec![RegisterDiff { name: "CYCLE", left: 0x10000, right: 0x20000 }]);
+    }
+}
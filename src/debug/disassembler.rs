@@ -1,7 +1,7 @@
 //! 反汇编器模块
 
 use crate::memory::MemoryBus;
-use crate::instructions::Instruction;
+use crate::instructions::{Instruction, JumpCondition, LoadSource, LoadTarget};
 
 /// 反汇编器
 #[derive(Debug, Clone)]
@@ -23,7 +23,7 @@ impl Disassembler {
     pub fn disassemble_at(&self, pc: u16, memory: &MemoryBus) -> String {
         let instruction_byte = memory.read_byte(pc);
         
-        if let Some(instruction) = Instruction::from_byte(instruction_byte) {
+        if let Some(instruction) = Instruction::decode(memory, pc) {
             self.format_instruction(pc, instruction_byte, instruction)
         } else {
             format!("0x{:04X}: 0x{:02X} ???", pc, instruction_byte)
@@ -38,7 +38,7 @@ impl Disassembler {
         while pc < end {
             let instruction_byte = memory.read_byte(pc);
             
-            if let Some(instruction) = Instruction::from_byte(instruction_byte) {
+            if let Some(instruction) = Instruction::decode(memory, pc) {
                 result.push(self.format_instruction(pc, instruction_byte, instruction));
                 pc += self.get_instruction_size(&instruction);
             } else {
@@ -71,33 +71,31 @@ impl Disassembler {
             Instruction::SUB(target) => format!("SUB A, {:?}", target),
             Instruction::INC(target) => format!("INC {:?}", target),
             Instruction::DEC(target) => format!("DEC {:?}", target),
+            Instruction::CP(value) => format!("CP 0x{:02X}", value),
+            Instruction::LD(LoadTarget::HighRam(offset), source) => format!("LDH (0xFF{:02X}), {:?}", offset, source),
+            Instruction::LD(target, LoadSource::HighRam(offset)) => format!("LDH {:?}, (0xFF{:02X})", target, offset),
             Instruction::LD(target, source) => format!("LD {:?}, {:?}", target, source),
             Instruction::LD16(target, source) => format!("LD {:?}, {:?}", target, source),
             Instruction::INC16(target) => format!("INC {:?}", target),
             Instruction::DEC16(target) => format!("DEC {:?}", target),
-            Instruction::JP(target) => format!("JP {:?}", target),
-            Instruction::JR(target) => format!("JR {:?}", target),
+            Instruction::JP(condition, target) => format!("JP {}{:?}", condition_prefix(condition), target),
+            Instruction::JR(condition, target) => format!("JR {}{:?}", condition_prefix(condition), target),
+            Instruction::CALL(condition, address) => format!("CALL {}0x{:04X}", condition_prefix(condition), address),
+            Instruction::RET(condition) => condition.suffix().map_or_else(|| "RET".to_string(), |suffix| format!("RET {}", suffix)),
         }
     }
 
     /// 获取指令大小
     fn get_instruction_size(&self, instruction: &Instruction) -> u16 {
-        match instruction {
-            Instruction::NOP => 1,
-            Instruction::ADD(_) => 1,
-            Instruction::SUB(_) => 1,
-            Instruction::INC(_) => 1,
-            Instruction::DEC(_) => 1,
-            Instruction::LD(_, _) => 1,
-            Instruction::LD16(_, _) => 3,
-            Instruction::INC16(_) => 1,
-            Instruction::DEC16(_) => 1,
-            Instruction::JP(_) => 3,
-            Instruction::JR(_) => 2,
-        }
+        instruction.size() as u16
     }
 }
 
+/// 条件跳转的条件前缀，例如 `NZ, `
+fn condition_prefix(condition: JumpCondition) -> String {
+    condition.suffix().map_or_else(String::new, |suffix| format!("{}, ", suffix))
+}
+
 impl Default for Disassembler {
     fn default() -> Self {
         Self::new()
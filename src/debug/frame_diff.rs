@@ -0,0 +1,202 @@
+//! 逐帧内存变化追踪
+//!
+//! 在每个帧边界复制一次WRAM，与上一帧比较，统计每个字节变化的帧数。
+//! 热力图显示哪些区域一直在变（计时器、随机数），哪些偶尔才变（生命、分数），
+//! `changed_by` 可以按变化量筛选，例如丢一条命后查找减少了1的地址，
+//! 找到的地址再交给金手指冻结。
+
+use crate::memory::MemoryRegion;
+
+/// 一个字节在相邻两帧之间的变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteChange {
+    pub address: u16,
+    pub old: u8,
+    pub new: u8,
+}
+
+impl ByteChange {
+    /// 有符号的变化量
+    pub fn delta(&self) -> i16 {
+        self.new as i16 - self.old as i16
+    }
+}
+
+/// 逐帧内存变化追踪器
+#[derive(Debug, Clone)]
+pub struct FrameDiff {
+    region: MemoryRegion,
+    previous: Option<Vec<u8>>,
+    /// 已比较的帧数
+    frames: u64,
+    /// 每个字节发生变化的帧数
+    change_counts: Vec<u32>,
+    last_changes: Vec<ByteChange>,
+}
+
+impl FrameDiff {
+    /// 追踪指定区域
+    pub fn new(region: MemoryRegion) -> Self {
+        Self {
+            region,
+            previous: None,
+            frames: 0,
+            change_counts: vec![0; region.size()],
+            last_changes: Vec::new(),
+        }
+    }
+
+    /// 追踪WRAM (0xC000-0xDFFF)
+    pub fn wram() -> Self {
+        Self::new(MemoryRegion::WRAM)
+    }
+
+    /// 在帧边界记录一次内存，返回与上一帧相比变化的字节
+    ///
+    /// 内存比追踪区域短时只记录实际存在的部分
+    pub fn capture(&mut self, memory: &[u8]) -> &[ByteChange] {
+        let start = (self.region.start as usize).min(memory.len());
+        let end = (self.region.start as usize + self.region.size()).min(memory.len());
+        let current = memory[start..end].to_vec();
+
+        self.last_changes.clear();
+        if let Some(previous) = &self.previous {
+            self.frames += 1;
+            for (offset, (&old, &new)) in previous.iter().zip(&current).enumerate() {
+                if old != new {
+                    self.change_counts[offset] += 1;
+                    self.last_changes.push(ByteChange { address: self.region.start + offset as u16, old, new });
+                }
+            }
+        }
+        self.previous = Some(current);
+        &self.last_changes
+    }
+
+    /// 已比较的帧数
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// 最近一帧的变化
+    pub fn last_changes(&self) -> &[ByteChange] {
+        &self.last_changes
+    }
+
+    /// 地址发生变化的帧数
+    pub fn change_count(&self, address: u16) -> u32 {
+        if !self.region.contains(address) {
+            return 0;
+        }
+        self.change_counts.get((address - self.region.start) as usize).copied().unwrap_or(0)
+    }
+
+    /// 最近一帧中变化量恰好为 `delta` 的地址
+    pub fn changed_by(&self, delta: i16) -> Vec<u16> {
+        self.last_changes
+            .iter()
+            .filter(|change| change.delta() == delta)
+            .map(|change| change.address)
+            .collect()
+    }
+
+    /// 变化最频繁的 `count` 个地址及其变化帧数
+    pub fn hottest(&self, count: usize) -> Vec<(u16, u32)> {
+        let mut addresses: Vec<(u16, u32)> = self
+            .change_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &changes)| changes > 0)
+            .map(|(offset, &changes)| (self.region.start + offset as u16, changes))
+            .collect();
+        addresses.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        addresses.truncate(count);
+        addresses
+    }
+
+    /// 清空统计重新开始，保留上一帧内容作为比较基准
+    pub fn reset_counts(&mut self) {
+        self.change_counts.fill(0);
+        self.frames = 0;
+        self.last_changes.clear();
+    }
+
+    /// 文本热力图，每行32字节
+    ///
+    /// ` ` 从未变化，`.` 不到1/4的帧，`:` 不到1/2，`*` 多数帧，`#` 每帧都变
+    pub fn heatmap(&self, start: u16, len: usize) -> String {
+        const ROW: usize = 32;
+        let first = start.max(self.region.start) as usize;
+        let last = (start as usize + len).min(self.region.start as usize + self.region.size());
+        let mut output = String::new();
+
+        for row in (first..last).step_by(ROW) {
+            output.push_str(&format!("{:04X} |", row));
+            for address in row..(row + ROW).min(last) {
+                output.push(self.shade(self.change_count(address as u16)));
+            }
+            output.push_str("|\n");
+        }
+        output
+    }
+
+    fn shade(&self, changes: u32) -> char {
+        let (changes, frames) = (changes as u64, self.frames.max(1));
+        match changes {
+            0 => ' ',
+            _ if changes * 4 < frames => '.',
+            _ if changes * 2 < frames => ':',
+            _ if changes < frames => '*',
+            _ => '#',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_diff_counts_and_filters_changes() {
+        let mut memory = vec![0u8; 0xFFFF];
+        let mut tracker = FrameDiff::wram();
+        memory[0xC100] = 3; // 生命
+        assert!(tracker.capture(&memory).is_empty());
+
+        for frame in 1..=8u8 {
+            memory[0xC000] = frame; // 每帧递增的计时器
+            if frame == 5 {
+                memory[0xC100] -= 1;
+            }
+            tracker.capture(&memory);
+        }
+
+        assert_eq!(tracker.frames(), 8);
+        assert_eq!(tracker.change_count(0xC000), 8);
+        assert_eq!(tracker.change_count(0xC100), 1);
+        assert_eq!(tracker.hottest(1), vec![(0xC000, 8)]);
+
+        memory[0xC000] = 9;
+        memory[0xC100] -= 1;
+        tracker.capture(&memory);
+        assert_eq!(tracker.changed_by(-1), vec![0xC100]);
+        assert_eq!(tracker.last_changes().len(), 2);
+
+        let heatmap = tracker.heatmap(0xC000, 0x120);
+        assert!(heatmap.starts_with("C000 |#   "));
+        assert!(heatmap.contains("C100 |."));
+    }
+
+    #[test]
+    fn test_capture_clamps_short_memory() {
+        let mut tracker = FrameDiff::wram();
+        assert!(tracker.capture(&[0u8; 0x100]).is_empty());
+        assert!(tracker.capture(&[1u8; 0x100]).is_empty());
+
+        // 只覆盖区域前半部分的内存
+        let mut memory = vec![0u8; 0xD000];
+        tracker.capture(&memory);
+        memory[0xCFFF] = 1;
+        assert_eq!(tracker.capture(&memory), &[ByteChange { address: 0xCFFF, old: 0, new: 1 }]);
+    }
+}
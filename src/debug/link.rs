@@ -0,0 +1,246 @@
+//! 连接线通信记录
+//!
+//! 在指令之间检查串口控制寄存器SC (0xFF02)：游戏用内部时钟发起传输时，
+//! 记录SB (0xFF01) 中发出的字节和收到的字节，并交给可插拔的协议解码器。
+//! 还没有对端时按未连接处理，收到0xFF。
+
+use std::fmt;
+
+/// 串口数据寄存器
+pub const SB: usize = 0xFF01;
+/// 串口控制寄存器
+pub const SC: usize = 0xFF02;
+/// 未连接时收到的字节
+const DISCONNECTED: u8 = 0xFF;
+
+/// 一次8位传输
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkTransfer {
+    /// 发生时的指令计数
+    pub cycle: u64,
+    pub frame: u64,
+    pub sent: u8,
+    pub received: u8,
+    /// 本机提供时钟（主机）
+    pub internal_clock: bool,
+}
+
+impl fmt::Display for LinkTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:>8} 帧{:>5}] {} 发送 0x{:02X} 接收 0x{:02X}",
+            self.cycle,
+            self.frame,
+            if self.internal_clock { "主机" } else { "从机" },
+            self.sent,
+            self.received
+        )
+    }
+}
+
+/// 协议解码器，逐字节接收传输，识别出完整的消息时返回描述
+pub trait LinkDecoder: fmt::Debug {
+    /// 协议名称
+    fn name(&self) -> &'static str;
+
+    fn feed(&mut self, transfer: &LinkTransfer) -> Option<String>;
+
+    /// 丢弃未完成的消息
+    fn reset(&mut self) {}
+}
+
+/// 解码出的消息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkMessage {
+    pub cycle: u64,
+    pub decoder: &'static str,
+    pub text: String,
+}
+
+/// 连接线通信记录器
+#[derive(Debug, Default)]
+pub struct LinkLogger {
+    pub transfers: Vec<LinkTransfer>,
+    pub messages: Vec<LinkMessage>,
+    decoders: Vec<Box<dyn LinkDecoder>>,
+}
+
+impl LinkLogger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加协议解码器
+    pub fn add_decoder(&mut self, decoder: Box<dyn LinkDecoder>) {
+        self.decoders.push(decoder);
+    }
+
+    /// 检查串口寄存器，有传输开始时完成传输并记录
+    pub fn poll(&mut self, memory: &mut [u8], cycle: u64, frame: u64) -> Option<LinkTransfer> {
+        let control = memory[SC];
+        // 只有主机在传输开始时就能完成一个字节；外部时钟要等对端驱动
+        if control & 0x81 != 0x81 {
+            return None;
+        }
+        let transfer = LinkTransfer { cycle, frame, sent: memory[SB], received: DISCONNECTED, internal_clock: true };
+        memory[SB] = transfer.received;
+        memory[SC] = control & 0x7F;
+        self.record(transfer);
+        Some(transfer)
+    }
+
+    /// 记录一次传输并交给所有解码器
+    pub fn record(&mut self, transfer: LinkTransfer) {
+        self.transfers.push(transfer);
+        for decoder in &mut self.decoders {
+            if let Some(text) = decoder.feed(&transfer) {
+                self.messages.push(LinkMessage { cycle: transfer.cycle, decoder: decoder.name(), text });
+            }
+        }
+    }
+
+    /// 清空记录并重置解码器
+    pub fn clear(&mut self) {
+        self.transfers.clear();
+        self.messages.clear();
+        self.decoders.iter_mut().for_each(|decoder| decoder.reset());
+    }
+
+    /// 按时间顺序列出传输和解码结果
+    pub fn log(&self) -> String {
+        let mut output = String::new();
+        let mut messages = self.messages.iter().peekable();
+        for transfer in &self.transfers {
+            output.push_str(&format!("{}\n", transfer));
+            while let Some(message) = messages.next_if(|m| m.cycle == transfer.cycle) {
+                output.push_str(&format!("    {}: {}\n", message.decoder, message.text));
+            }
+        }
+        output
+    }
+}
+
+/// Game Boy打印机协议解码器
+///
+/// 数据包格式：`88 33` 魔数、命令、压缩标志、长度 (16位小端)、数据、
+/// 校验和 (命令到数据的字节和，16位小端)，最后两个字节由打印机回应状态
+#[derive(Debug, Default)]
+pub struct PrinterDecoder {
+    packet: Vec<u8>,
+}
+
+impl PrinterDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn command_name(command: u8) -> &'static str {
+        match command {
+            0x01 => "INIT",
+            0x02 => "PRINT",
+            0x04 => "DATA",
+            0x08 => "BREAK",
+            0x0F => "STATUS",
+            _ => "未知",
+        }
+    }
+}
+
+impl LinkDecoder for PrinterDecoder {
+    fn name(&self) -> &'static str {
+        "打印机"
+    }
+
+    fn feed(&mut self, transfer: &LinkTransfer) -> Option<String> {
+        let byte = transfer.sent;
+        match self.packet.len() {
+            0 if byte != 0x88 => return None,
+            1 if byte != 0x33 => {
+                self.packet.clear();
+                return None;
+            }
+            _ => self.packet.push(byte),
+        }
+        if self.packet.len() < 6 {
+            return None;
+        }
+
+        let length = u16::from_le_bytes([self.packet[4], self.packet[5]]) as usize;
+        // 头部6字节 + 数据 + 校验和2字节 + 应答2字节
+        if self.packet.len() < 10 + length {
+            return None;
+        }
+        let packet = std::mem::take(&mut self.packet);
+        let command = packet[2];
+        let expected = packet[2..6 + length].iter().fold(0u16, |sum, &b| sum.wrapping_add(b as u16));
+        let checksum = u16::from_le_bytes([packet[6 + length], packet[7 + length]]);
+        Some(format!(
+            "{} (0x{:02X}) 压缩{} 数据{}字节 校验和{}",
+            Self::command_name(command),
+            command,
+            packet[3],
+            length,
+            if checksum == expected { "正确" } else { "错误" }
+        ))
+    }
+
+    fn reset(&mut self) {
+        self.packet.clear();
+    }
+}
+
+/// 交换协议解码器（占位）：只识别建立连接时的握手字节
+#[derive(Debug, Default)]
+pub struct TradeDecoder;
+
+impl LinkDecoder for TradeDecoder {
+    fn name(&self) -> &'static str {
+        "交换"
+    }
+
+    fn feed(&mut self, transfer: &LinkTransfer) -> Option<String> {
+        let text = match transfer.sent {
+            0x01 => "请求作为主机",
+            0x02 => "请求作为从机",
+            0x60 => "已连接",
+            0xFD => "数据块前导",
+            _ => return None,
+        };
+        Some(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logger_polls_serial_and_decodes_printer_packets() {
+        let mut logger = LinkLogger::new();
+        logger.add_decoder(Box::new(PrinterDecoder::new()));
+        let mut memory = vec![0u8; 0x10000];
+
+        // 外部时钟时不发起传输
+        memory[SB] = 0x88;
+        memory[SC] = 0x80;
+        assert!(logger.poll(&mut memory, 0, 0).is_none());
+
+        // INIT包: 88 33 01 00 00 00 01 00 + 两个应答字节
+        for (cycle, byte) in [0x88, 0x33, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00].into_iter().enumerate() {
+            memory[SB] = byte;
+            memory[SC] = 0x81;
+            let transfer = logger.poll(&mut memory, cycle as u64, 0).unwrap();
+            assert_eq!(transfer.sent, byte);
+            assert_eq!((memory[SB], memory[SC]), (0xFF, 0x01));
+        }
+
+        assert_eq!(logger.transfers.len(), 10);
+        assert_eq!(logger.messages.len(), 1);
+        assert_eq!(logger.messages[0].text, "INIT (0x01) 压缩0 数据0字节 校验和正确");
+        assert!(logger.log().contains("    打印机: INIT"));
+
+        logger.clear();
+        assert!(logger.transfers.is_empty() && logger.messages.is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+//! 差分执行
+//!
+//! 让被测核心和参考解释器从同一状态出发逐条执行，
+//! 每隔N条指令比较一次寄存器和内存，第一次不一致时报告差异。
+//! 周期计数不参与比较，因为各核心的计时模型不同。
+
+use std::fmt;
+
+use crate::cpu::reference::{ExecutionCore, ReferenceCPU};
+use crate::memory::MemoryBus;
+use super::diff::{diff_states, StateDiff};
+
+/// 差分执行失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockstepError {
+    /// 两个核心状态不一致
+    Mismatch { instruction: u64, diff: StateDiff },
+    /// 某个核心执行出错
+    Core { core: &'static str, instruction: u64, message: String },
+}
+
+impl fmt::Display for LockstepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockstepError::Mismatch { instruction, diff } => {
+                write!(f, "第 {} 条指令后与参考解释器不一致\n{}", instruction, diff)
+            }
+            LockstepError::Core { core, instruction, message } => {
+                write!(f, "{}核心在第 {} 条指令出错: {}", core, instruction, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LockstepError {}
+
+/// 参考解释器影子，跟随被测核心执行
+#[derive(Debug)]
+pub struct Lockstep {
+    reference: ReferenceCPU,
+    interval: u64,
+    executed: u64,
+}
+
+impl Lockstep {
+    /// 以被测核心的当前状态创建影子，每 `interval` 条指令比较一次
+    pub fn new<C: ExecutionCore>(candidate: &C, interval: u64) -> Self {
+        Self {
+            reference: ReferenceCPU::from_state(&candidate.capture()),
+            interval: interval.max(1),
+            executed: 0,
+        }
+    }
+
+    /// 比较间隔（指令数）
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// 已执行的指令数
+    pub fn executed(&self) -> u64 {
+        self.executed
+    }
+
+    /// 参考解释器
+    pub fn reference(&self) -> &ReferenceCPU {
+        &self.reference
+    }
+
+    /// 参考解释器的内存总线，金手指等宿主写入要同样写到这里，否则两个核心的内存会分叉
+    pub fn reference_bus_mut(&mut self) -> &mut MemoryBus {
+        &mut self.reference.bus
+    }
+
+    /// 两个核心各执行一条指令
+    pub fn step<C: ExecutionCore>(&mut self, candidate: &mut C) -> Result<(), LockstepError> {
+        candidate.step_instruction().map_err(|message| LockstepError::Core {
+            core: "被测",
+            instruction: self.executed + 1,
+            message,
+        })?;
+        self.after_candidate_step(candidate)
+    }
+
+    /// 被测核心已经执行了一条指令，参考解释器跟上并按间隔比较
+    pub fn after_candidate_step<C: ExecutionCore>(&mut self, candidate: &C) -> Result<(), LockstepError> {
+        self.executed += 1;
+        self.reference.step().map_err(|message| LockstepError::Core {
+            core: "参考",
+            instruction: self.executed,
+            message,
+        })?;
+        if self.executed.is_multiple_of(self.interval) {
+            self.check(candidate)?;
+        }
+        Ok(())
+    }
+
+    /// 立即比较两个核心的状态
+    pub fn check<C: ExecutionCore>(&self, candidate: &C) -> Result<(), LockstepError> {
+        let diff = diff_states(&self.reference.capture(), &candidate.capture());
+        if diff.is_empty() {
+            Ok(())
+        } else {
+            Err(LockstepError::Mismatch { instruction: self.executed, diff })
+        }
+    }
+
+    /// 运行指定条数的指令
+    pub fn run<C: ExecutionCore>(&mut self, candidate: &mut C, instructions: u64) -> Result<(), LockstepError> {
+        for _ in 0..instructions {
+            self.step(candidate)?;
+        }
+        self.check(candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::OptimizedCPU;
+
+    fn core_with(program: &[u8]) -> OptimizedCPU {
+        let mut bus = MemoryBus::new();
+        bus.load_program(0x100, program);
+        OptimizedCPU::new(bus)
+    }
+
+    #[test]
+    fn test_lockstep_agrees_and_reports_divergence() {
+        // INC C; INC C; ADD A,C; DEC C
+        let mut candidate = core_with(&[0x0C, 0x0C, 0x81, 0x0D]);
+        let mut lockstep = Lockstep::new(&candidate, 2);
+        lockstep.run(&mut candidate, 4).unwrap();
+        assert_eq!(lockstep.executed(), 4);
+        assert_eq!(lockstep.reference().registers.a, 2);
+
+        // 模拟优化核心的错误：执行后篡改寄存器
+        let mut candidate = core_with(&[0x0C, 0x0C, 0x0C]);
+        let mut lockstep = Lockstep::new(&candidate, 2);
+        lockstep.step(&mut candidate).unwrap();
+        candidate.registers.b = 0x77;
+        match lockstep.step(&mut candidate) {
+            Err(LockstepError::Mismatch { instruction, diff }) => {
+                assert_eq!(instruction, 2);
+                assert_eq!(diff.registers[0].name, "B");
+            }
+            other => panic!("预期不一致，实际 {:?}", other),
+        }
+    }
+}
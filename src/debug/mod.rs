@@ -3,7 +3,27 @@
 pub mod debugger;
 pub mod breakpoint;
 pub mod disassembler;
+pub mod cheats;
+pub mod determinism;
+pub mod diff;
+pub mod frame_diff;
+pub mod link;
+pub mod lockstep;
+pub mod opcodes;
+pub mod scenario;
+pub mod sprites;
+pub mod symbols;
 
 pub use debugger::{Debugger, DebuggerState, LogLevel};
 pub use breakpoint::Breakpoint;
 pub use disassembler::Disassembler;
+pub use cheats::{CheatEngine, FreezeEntry, FreezeMode};
+pub use determinism::{check_determinism, DeterminismError, InputLog};
+pub use frame_diff::{ByteChange, FrameDiff};
+pub use link::{LinkDecoder, LinkLogger, LinkTransfer};
+pub use lockstep::{Lockstep, LockstepError};
+pub use opcodes::{opcode_table, OpcodeEntry};
+pub use scenario::{Action, Scenario, ScenarioError};
+pub use sprites::SpriteEntry;
+pub use symbols::{Symbol, SymbolTable};
+pub use diff::{diff_states, diff_trace_logs, diff_traces, StateDiff, TraceDiff, TraceEntry};
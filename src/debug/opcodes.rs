@@ -0,0 +1,178 @@
+//! 指令集文档生成
+//!
+//! 逐个操作码调用CPU使用的 `Instruction::from_byte` 解码，从 `size`、`cycles`、`flags`
+//! 取出长度、周期和标志位，生成16×16的操作码矩阵。文档与解码表共用同一份数据，
+//! 修改指令实现后重新生成即可；`docs/OPCODES.md` 由测试与生成结果比较，不会过时。
+
+use crate::instructions::{Instruction, JumpCondition, LoadSource, LoadSource16, LoadTarget};
+
+/// 矩阵中的一个操作码
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpcodeEntry {
+    pub opcode: u8,
+    /// 助记符，操作数用 `d8`、`d16`、`a16`、`r8` 表示
+    pub mnemonic: String,
+    /// 长度 (字节)
+    pub size: u8,
+    /// 机器周期数，条件跳转为条件成立时的周期
+    pub cycles: u8,
+    /// 条件跳转在条件不成立时的周期
+    pub cycles_not_taken: Option<u8>,
+    /// Z、N、H、C四个标志位的影响
+    pub flags: &'static str,
+}
+
+impl OpcodeEntry {
+    /// 周期数，条件跳转写作 `成立/不成立`
+    pub fn cycles_text(&self) -> String {
+        match self.cycles_not_taken {
+            Some(not_taken) => format!("{}/{}", self.cycles, not_taken),
+            None => self.cycles.to_string(),
+        }
+    }
+}
+
+/// 已实现的全部操作码，按操作码排序
+pub fn opcode_table() -> Vec<OpcodeEntry> {
+    (0..=u8::MAX)
+        .filter_map(|opcode| Instruction::from_byte(opcode).map(|instruction| entry(opcode, instruction)))
+        .collect()
+}
+
+fn entry(opcode: u8, instruction: Instruction) -> OpcodeEntry {
+    let conditional = match instruction {
+        Instruction::JP(condition, _)
+        | Instruction::JR(condition, _)
+        | Instruction::CALL(condition, _)
+        | Instruction::RET(condition) => condition != JumpCondition::Always,
+        _ => false,
+    };
+    OpcodeEntry {
+        opcode,
+        mnemonic: mnemonic(instruction),
+        size: instruction.size(),
+        cycles: instruction.cycles(true),
+        cycles_not_taken: conditional.then(|| instruction.cycles(false)),
+        flags: instruction.flags(),
+    }
+}
+
+/// 与反汇编器相同的写法，立即数换成操作数类型
+fn mnemonic(instruction: Instruction) -> String {
+    let condition = |condition: JumpCondition| condition.suffix().map_or_else(String::new, |suffix| format!("{}, ", suffix));
+    match instruction {
+        Instruction::NOP => "NOP".to_string(),
+        Instruction::ADD(target) => format!("ADD A, {:?}", target),
+        Instruction::SUB(target) => format!("SUB A, {:?}", target),
+        Instruction::INC(target) => format!("INC {:?}", target),
+        Instruction::DEC(target) => format!("DEC {:?}", target),
+        Instruction::CP(_) => "CP d8".to_string(),
+        Instruction::LD(target, LoadSource::Immediate(_)) => format!("LD {:?}, d8", target),
+        Instruction::LD(LoadTarget::HighRam(_), source) => format!("LDH (a8), {:?}", source),
+        Instruction::LD(target, LoadSource::HighRam(_)) => format!("LDH {:?}, (a8)", target),
+        Instruction::LD(target, source) => format!("LD {:?}, {:?}", target, source),
+        Instruction::LD16(target, LoadSource16::Immediate(_)) => format!("LD {:?}, d16", target),
+        Instruction::LD16(target, source) => format!("LD {:?}, {:?}", target, source),
+        Instruction::INC16(target) => format!("INC {:?}", target),
+        Instruction::DEC16(target) => format!("DEC {:?}", target),
+        Instruction::JP(cc, _) => format!("JP {}a16", condition(cc)),
+        Instruction::JR(cc, _) => format!("JR {}r8", condition(cc)),
+        Instruction::CALL(cc, _) => format!("CALL {}a16", condition(cc)),
+        Instruction::RET(cc) => cc.suffix().map_or_else(|| "RET".to_string(), |suffix| format!("RET {}", suffix)),
+    }
+}
+
+/// 按高4位、低4位排成16×16，未实现的操作码为 `None`
+fn matrix(table: &[OpcodeEntry]) -> [[Option<&OpcodeEntry>; 16]; 16] {
+    let mut cells = [[None; 16]; 16];
+    for entry in table {
+        cells[(entry.opcode >> 4) as usize][(entry.opcode & 0x0F) as usize] = Some(entry);
+    }
+    cells
+}
+
+/// Markdown格式的操作码矩阵
+pub fn to_markdown(table: &[OpcodeEntry]) -> String {
+    let mut out = String::from("# 指令集\n\n");
+    out.push_str("由 `gamelife dev opcodes` 从CPU的解码表生成，请勿手工修改。\n\n");
+    out.push_str(&format!("已实现 {} 个操作码。每格依次为助记符、长度（字节）、机器周期和标志位（Z N H C）；", table.len()));
+    out.push_str("条件跳转的周期写作“成立/不成立”。\n\n");
+    out.push_str("|    |");
+    out.push_str(&(0..16).map(|low| format!(" x{:X} |", low)).collect::<String>());
+    out.push_str("\n|----|");
+    out.push_str(&"----|".repeat(16));
+    out.push('\n');
+    for (high, row) in matrix(table).iter().enumerate() {
+        out.push_str(&format!("| **{:X}x** |", high));
+        for cell in row {
+            match cell {
+                Some(entry) => out.push_str(&format!(
+                    " {}<br>{} {}<br>{} |",
+                    entry.mnemonic,
+                    entry.size,
+                    entry.cycles_text(),
+                    entry.flags
+                )),
+                None => out.push_str("  |"),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// HTML格式的操作码矩阵，可以直接在浏览器中打开
+pub fn to_html(table: &[OpcodeEntry]) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>指令集</title>\n");
+    out.push_str("<style>table{border-collapse:collapse;font-family:monospace}td,th{border:1px solid #999;padding:4px;text-align:center}</style>\n");
+    out.push_str("</head>\n<body>\n<table>\n<tr><th></th>");
+    for low in 0..16 {
+        out.push_str(&format!("<th>x{:X}</th>", low));
+    }
+    out.push_str("</tr>\n");
+    for (high, row) in matrix(table).iter().enumerate() {
+        out.push_str(&format!("<tr><th>{:X}x</th>", high));
+        for cell in row {
+            match cell {
+                Some(entry) => out.push_str(&format!(
+                    "<td title=\"0x{:02X}\">{}<br>{} {}<br>{}</td>",
+                    entry.opcode,
+                    entry.mnemonic,
+                    entry.size,
+                    entry.cycles_text(),
+                    entry.flags
+                )),
+                None => out.push_str("<td></td>"),
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_matches_decoder() {
+        let table = opcode_table();
+        let jr = table.iter().find(|entry| entry.opcode == 0x20).unwrap();
+        assert_eq!((jr.mnemonic.as_str(), jr.size, jr.cycles_text(), jr.flags), ("JR NZ, r8", 2, "3/2".to_string(), "----"));
+        let ld = table.iter().find(|entry| entry.opcode == 0x3E).unwrap();
+        assert_eq!((ld.mnemonic.as_str(), ld.size, ld.cycles_text()), ("LD A, d8", 2, "2".to_string()));
+        assert_eq!(table.iter().find(|entry| entry.opcode == 0x18).unwrap().cycles_text(), "3");
+        assert_eq!(table.iter().find(|entry| entry.opcode == 0x80).unwrap().flags, "Z0HC");
+        let ldh = table.iter().find(|entry| entry.opcode == 0xE0).unwrap();
+        assert_eq!((ldh.mnemonic.as_str(), ldh.size, ldh.cycles), ("LDH (a8), A", 2, 3));
+
+        let html = to_html(&table);
+        assert!(html.contains("<td title=\"0xCD\">CALL a16<br>3 6<br>----</td>"));
+    }
+
+    #[test]
+    fn test_checked_in_docs_are_current() {
+        assert_eq!(include_str!("../../docs/OPCODES.md"), to_markdown(&opcode_table()), "请运行 gamelife dev opcodes --output docs/OPCODES.md");
+    }
+}
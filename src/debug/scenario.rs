@@ -0,0 +1,294 @@
+//! 脚本式的ROM集成测试
+//!
+//! 用构建器依次描述输入、等待和断言，`run` 时按顺序执行，
+//! 第一处失败会报告是第几步、哪个动作以及实际值：
+//!
+//! ```ignore
+//! Scenario::new(rom)
+//!     .press(Button::Start)
+//!     .wait_frames(60)
+//!     .assert_mem(0xC000, 0x01)
+//!     .assert_screen_hash("da39a3ee5e6b4b0d3255bfef95601890afd80709")
+//!     .expect_pass();
+//! ```
+
+use std::fmt;
+
+use crate::emulator::AdvancedGameBoy;
+use crate::frontend::{Button, Emulator};
+use crate::util::hash::to_hex;
+use super::determinism::frame_hash;
+
+/// 脚本中的一步
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// 按下按键，直到 `Release`
+    Press(Button),
+    /// 松开按键
+    Release(Button),
+    /// 运行指定帧数
+    WaitFrames(u32),
+    /// 执行指定条数的指令
+    Step(u32),
+    /// 逐条执行直到PC等于地址，超过步数上限时失败
+    WaitUntilPc { address: u16, max_steps: u32 },
+    /// 直接写入内存，用于准备测试数据
+    Poke { address: u16, value: u8 },
+    /// 内存中的值
+    AssertMem { address: u16, value: u8 },
+    /// 程序计数器
+    AssertPc(u16),
+    /// 当前画面像素的SHA-1（小写十六进制）
+    AssertScreenHash(String),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Press(button) => write!(f, "按下 {}", button.name()),
+            Action::Release(button) => write!(f, "松开 {}", button.name()),
+            Action::WaitFrames(frames) => write!(f, "等待 {} 帧", frames),
+            Action::Step(steps) => write!(f, "执行 {} 条指令", steps),
+            Action::WaitUntilPc { address, max_steps } => {
+                write!(f, "执行到 PC={:04X} (最多 {} 条指令)", address, max_steps)
+            }
+            Action::Poke { address, value } => write!(f, "写入 [{:04X}] = {:02X}", address, value),
+            Action::AssertMem { address, value } => write!(f, "断言 [{:04X}] == {:02X}", address, value),
+            Action::AssertPc(address) => write!(f, "断言 PC == {:04X}", address),
+            Action::AssertScreenHash(hash) => write!(f, "断言画面哈希 == {}", hash),
+        }
+    }
+}
+
+/// 脚本执行失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenarioError {
+    /// 加载ROM失败
+    Load(String),
+    /// 第 `step` 步（从1开始）执行出错
+    Emulation { step: usize, action: Action, message: String },
+    /// 第 `step` 步的断言不成立
+    Assertion { step: usize, action: Action, actual: String },
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::Load(message) => write!(f, "无法加载ROM: {}", message),
+            ScenarioError::Emulation { step, action, message } => {
+                write!(f, "第 {} 步 ({}) 出错: {}", step, action, message)
+            }
+            ScenarioError::Assertion { step, action, actual } => {
+                write!(f, "第 {} 步 ({}) 失败，实际为 {}", step, action, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+/// 一段测试脚本
+#[derive(Debug)]
+pub struct Scenario<E: Emulator = AdvancedGameBoy> {
+    /// 装箱保存，`AdvancedGameBoy` 较大，构建器每一步都会移动脚本
+    emulator: Box<E>,
+    rom: Vec<u8>,
+    actions: Vec<Action>,
+}
+
+impl Scenario<AdvancedGameBoy> {
+    /// 在已启动的 `AdvancedGameBoy` 上运行ROM
+    pub fn new(rom: &[u8]) -> Self {
+        let mut gameboy = AdvancedGameBoy::new();
+        gameboy.start();
+        Self::with_emulator(gameboy, rom)
+    }
+}
+
+impl<E: Emulator> Scenario<E> {
+    /// 在指定的模拟器上运行ROM，模拟器应当已可以执行
+    pub fn with_emulator(emulator: E, rom: &[u8]) -> Self {
+        Self { emulator: Box::new(emulator), rom: rom.to_vec(), actions: Vec::new() }
+    }
+
+    /// 追加一步
+    pub fn then(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    pub fn press(self, button: Button) -> Self {
+        self.then(Action::Press(button))
+    }
+
+    pub fn release(self, button: Button) -> Self {
+        self.then(Action::Release(button))
+    }
+
+    /// 按下按键保持一帧后松开
+    pub fn tap(self, button: Button) -> Self {
+        self.press(button).wait_frames(1).release(button)
+    }
+
+    pub fn wait_frames(self, frames: u32) -> Self {
+        self.then(Action::WaitFrames(frames))
+    }
+
+    pub fn step(self, steps: u32) -> Self {
+        self.then(Action::Step(steps))
+    }
+
+    pub fn wait_until_pc(self, address: u16, max_steps: u32) -> Self {
+        self.then(Action::WaitUntilPc { address, max_steps })
+    }
+
+    pub fn poke(self, address: u16, value: u8) -> Self {
+        self.then(Action::Poke { address, value })
+    }
+
+    pub fn assert_mem(self, address: u16, value: u8) -> Self {
+        self.then(Action::AssertMem { address, value })
+    }
+
+    pub fn assert_pc(self, address: u16) -> Self {
+        self.then(Action::AssertPc(address))
+    }
+
+    /// 断言画面哈希，`hash` 为SHA-1十六进制，大小写均可
+    pub fn assert_screen_hash(self, hash: &str) -> Self {
+        self.then(Action::AssertScreenHash(hash.to_ascii_lowercase()))
+    }
+
+    /// 已记录的步骤
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// 加载ROM并按顺序执行，全部通过时返回模拟器供进一步检查
+    pub fn run(mut self) -> Result<Box<E>, ScenarioError> {
+        self.emulator.load_rom(&self.rom).map_err(ScenarioError::Load)?;
+        for (index, action) in self.actions.iter().enumerate() {
+            let step = index + 1;
+            match perform(self.emulator.as_mut(), action) {
+                Ok(None) => {}
+                Ok(Some(actual)) => return Err(ScenarioError::Assertion { step, action: action.clone(), actual }),
+                Err(message) => return Err(ScenarioError::Emulation { step, action: action.clone(), message }),
+            }
+        }
+        Ok(self.emulator)
+    }
+
+    /// 测试用：执行失败时panic并打印失败的步骤
+    pub fn expect_pass(self) -> Box<E> {
+        match self.run() {
+            Ok(emulator) => emulator,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+/// 执行一步；断言不成立时返回实际值
+fn perform<E: Emulator>(emulator: &mut E, action: &Action) -> Result<Option<String>, String> {
+    match action {
+        Action::Press(button) => emulator.set_button(*button, true),
+        Action::Release(button) => emulator.set_button(*button, false),
+        Action::WaitFrames(frames) => {
+            for _ in 0..*frames {
+                emulator.run_frame()?;
+            }
+        }
+        Action::Step(steps) => {
+            for _ in 0..*steps {
+                emulator.step()?;
+            }
+        }
+        Action::WaitUntilPc { address, max_steps } => {
+            for _ in 0..*max_steps {
+                if pc(emulator)? == *address {
+                    return Ok(None);
+                }
+                emulator.step()?;
+            }
+            let actual = pc(emulator)?;
+            return Ok((actual != *address).then(|| format!("PC={:04X}", actual)));
+        }
+        Action::Poke { address, value } => {
+            let mut state = snapshot(emulator)?;
+            state.memory[*address as usize] = *value;
+            emulator.restore(&state)?;
+        }
+        Action::AssertMem { address, value } => {
+            let actual = snapshot(emulator)?.memory.get(*address as usize).copied();
+            return Ok((actual != Some(*value)).then(|| match actual {
+                Some(byte) => format!("{:02X}", byte),
+                None => "-".to_string(),
+            }));
+        }
+        Action::AssertPc(address) => {
+            let actual = pc(emulator)?;
+            return Ok((actual != *address).then(|| format!("{:04X}", actual)));
+        }
+        Action::AssertScreenHash(hash) => {
+            let actual = frame_hash(emulator).map_or_else(|| "没有画面输出".to_string(), |digest| to_hex(&digest));
+            return Ok((actual != *hash).then_some(actual));
+        }
+    }
+    Ok(None)
+}
+
+fn snapshot<E: Emulator>(emulator: &E) -> Result<crate::emulator::SaveState, String> {
+    emulator.snapshot().ok_or_else(|| "该模拟器核心不支持存档".to_string())
+}
+
+fn pc<E: Emulator>(emulator: &E) -> Result<u16, String> {
+    snapshot(emulator).map(|state| state.pc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::GameBoy;
+    use crate::rom::{RomGenerator, PROGRAM_START};
+
+    /// 0x150: JP 0x200; 0x200: INC C; JP 0x200
+    fn looping_rom() -> Vec<u8> {
+        RomGenerator::new("SCENARIO")
+            .program(PROGRAM_START, &[0xC3, 0x00, 0x02])
+            .program(0x200, &[0x0C, 0xC3, 0x00, 0x02])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scenario_runs_script_and_reports_failing_step() {
+        let rom = looping_rom();
+        let gameboy = Scenario::new(&rom)
+            .wait_until_pc(0x200, 8)
+            .step(2)
+            .assert_pc(0x200)
+            .assert_mem(0x0201, 0xC3)
+            .tap(Button::Start)
+            .run()
+            .unwrap();
+        let blank = to_hex(&frame_hash(gameboy.as_ref()).unwrap());
+
+        Scenario::new(&rom).wait_frames(1).assert_screen_hash(&blank.to_uppercase()).expect_pass();
+
+        // 瓦片0第一行改为颜色3，背景全部由瓦片0组成，画面随之改变
+        let error = Scenario::new(&rom)
+            .poke(0x8000, 0xFF)
+            .poke(0x8001, 0xFF)
+            .wait_frames(1)
+            .assert_screen_hash(&blank)
+            .run()
+            .unwrap_err();
+        match error {
+            ScenarioError::Assertion { step: 4, action: Action::AssertScreenHash(_), actual } => assert_ne!(actual, blank),
+            other => panic!("预期第4步画面断言失败，实际 {:?}", other),
+        }
+
+        let error = Scenario::with_emulator(GameBoy::new(), &rom).assert_mem(0x0150, 0x00).run().unwrap_err();
+        assert_eq!(error.to_string(), "第 1 步 (断言 [0150] == 00) 失败，实际为 C3");
+        assert!(matches!(Scenario::new(&[0u8; 16]).run(), Err(ScenarioError::Load(_))));
+    }
+}
@@ -0,0 +1,208 @@
+//! 精灵检视
+//!
+//! 把GB和GBA的OAM解码为统一的精灵列表（屏幕坐标、尺寸、瓦片、调色板、优先级、翻转），
+//! 并能在帧缓冲区上高亮指定精灵覆盖的区域，方便确认"屏幕上这个东西是哪个精灵"。
+
+use std::fmt;
+
+use crate::gpu::VideoMemory;
+
+/// GB LCDC寄存器地址
+const LCDC: usize = 0xFF40;
+/// 高亮使用的洋红色
+const HIGHLIGHT_RGB: [u8; 3] = [0xFF, 0x00, 0xFF];
+const HIGHLIGHT_BGR555: u16 = 0x7C1F;
+
+/// 解码后的精灵
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpriteEntry {
+    /// OAM中的编号
+    pub index: usize,
+    /// 屏幕坐标，可以为负（部分移出屏幕）
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub tile: u16,
+    pub palette: u8,
+    /// GB: 1 表示在背景之后；GBA: 0-3，越小越靠前
+    pub priority: u8,
+    pub x_flip: bool,
+    pub y_flip: bool,
+}
+
+impl SpriteEntry {
+    /// 屏幕像素是否在精灵范围内
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.x + self.width as i32 && y < self.y + self.height as i32
+    }
+}
+
+impl fmt::Display for SpriteEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{:<3} ({:4}, {:4}) {:>2}x{:<2} 瓦片{:<4} 调色板{:<2} 优先级{} 翻转{}{}",
+            self.index,
+            self.x,
+            self.y,
+            self.width,
+            self.height,
+            self.tile,
+            self.palette,
+            self.priority,
+            if self.x_flip { 'X' } else { '-' },
+            if self.y_flip { 'Y' } else { '-' },
+        )
+    }
+}
+
+/// 列出GB OAM中在屏幕上的精灵，`memory` 为完整地址空间
+pub fn gb_sprites(memory: &[u8]) -> Vec<SpriteEntry> {
+    let height = if memory.get(LCDC).is_some_and(|lcdc| lcdc & 0x04 != 0) { 16 } else { 8 };
+    VideoMemory::new(memory)
+        .iter_oam()
+        .enumerate()
+        .map(|(index, sprite)| SpriteEntry {
+            index,
+            x: sprite.x as i32 - 8,
+            y: sprite.y as i32 - 16,
+            width: 8,
+            height,
+            tile: sprite.tile_index as u16,
+            palette: sprite.palette,
+            priority: sprite.priority as u8,
+            x_flip: sprite.x_flip,
+            y_flip: sprite.y_flip,
+        })
+        .filter(|entry| entry.x > -8 && entry.x < 160 && entry.y > -(height as i32) && entry.y < 144)
+        .collect()
+}
+
+/// GBA精灵尺寸，按 [形状][大小] 索引
+const GBA_SIZES: [[(u32, u32); 4]; 3] = [
+    [(8, 8), (16, 16), (32, 32), (64, 64)],
+    [(16, 8), (32, 8), (32, 16), (64, 32)],
+    [(8, 16), (8, 32), (16, 32), (32, 64)],
+];
+
+/// 列出GBA OAM中未被禁用的精灵，`oam` 为 128 x 4 个16位属性
+pub fn gba_sprites(oam: &[u16]) -> Vec<SpriteEntry> {
+    oam.chunks_exact(4)
+        .enumerate()
+        .filter_map(|(index, attrs)| {
+            let (attr0, attr1, attr2) = (attrs[0], attrs[1], attrs[2]);
+            let affine = attr0 & 0x0100 != 0;
+            let shape = (attr0 >> 14) as usize;
+            // 非仿射精灵的第9位为禁用标志
+            if (!affine && attr0 & 0x0200 != 0) || shape == 3 {
+                return None;
+            }
+            let (width, height) = GBA_SIZES[shape][(attr1 >> 14) as usize];
+            let y = (attr0 & 0xFF) as i32;
+            let x = (attr1 & 0x1FF) as i32;
+            Some(SpriteEntry {
+                index,
+                x: if x >= 240 { x - 512 } else { x },
+                y: if y >= 160 { y - 256 } else { y },
+                width,
+                height,
+                tile: attr2 & 0x3FF,
+                palette: (attr2 >> 12) as u8,
+                priority: ((attr2 >> 10) & 0x3) as u8,
+                x_flip: !affine && attr1 & 0x1000 != 0,
+                y_flip: !affine && attr1 & 0x2000 != 0,
+            })
+        })
+        .collect()
+}
+
+/// 精灵列表文本
+pub fn sprite_table(sprites: &[SpriteEntry]) -> String {
+    let mut output = format!("{} 个精灵\n", sprites.len());
+    for sprite in sprites {
+        output.push_str(&format!("{}\n", sprite));
+    }
+    output
+}
+
+/// 对精灵范围内的每个像素调用 `paint(下标, 是否为边框)`
+fn for_each_pixel(sprite: &SpriteEntry, width: usize, height: usize, mut paint: impl FnMut(usize, bool)) {
+    let right = sprite.x + sprite.width as i32 - 1;
+    let bottom = sprite.y + sprite.height as i32 - 1;
+    for y in sprite.y.max(0)..=bottom.min(height as i32 - 1) {
+        for x in sprite.x.max(0)..=right.min(width as i32 - 1) {
+            let border = x == sprite.x || x == right || y == sprite.y || y == bottom;
+            paint(y as usize * width + x as usize, border);
+        }
+    }
+}
+
+/// 在RGB24帧缓冲区上高亮精灵：边框为洋红色，内部与洋红色混合
+pub fn highlight_rgb(framebuffer: &mut [u8], width: usize, height: usize, sprite: &SpriteEntry) {
+    for_each_pixel(sprite, width, height, |index, border| {
+        let pixel = &mut framebuffer[index * 3..index * 3 + 3];
+        for (channel, highlight) in pixel.iter_mut().zip(HIGHLIGHT_RGB) {
+            *channel = if border { highlight } else { ((*channel as u16 + highlight as u16) / 2) as u8 };
+        }
+    });
+}
+
+/// 在BGR555帧缓冲区上高亮精灵
+pub fn highlight_bgr555(framebuffer: &mut [u16], width: usize, height: usize, sprite: &SpriteEntry) {
+    for_each_pixel(sprite, width, height, |index, border| {
+        let pixel = &mut framebuffer[index];
+        *pixel = if border {
+            HIGHLIGHT_BGR555
+        } else {
+            (0..3).fold(0, |color, channel| {
+                let shift = channel * 5;
+                let mixed = (((*pixel >> shift) & 0x1F) + ((HIGHLIGHT_BGR555 >> shift) & 0x1F)) / 2;
+                color | (mixed << shift)
+            })
+        };
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gb_and_gba_sprite_listing() {
+        let mut memory = vec![0u8; 0x10000];
+        // 精灵0在屏幕 (16, 8)，X翻转；精灵1在屏幕外
+        memory[0xFE00..0xFE08].copy_from_slice(&[24, 24, 3, 0x20, 0, 0, 0, 0]);
+        memory[LCDC] = 0x04;
+        let sprites = gb_sprites(&memory);
+        assert_eq!(sprites.len(), 1);
+        assert_eq!((sprites[0].x, sprites[0].y, sprites[0].height), (16, 8, 16));
+        assert!(sprites[0].x_flip && !sprites[0].y_flip);
+
+        let mut oam = vec![0u16; 0x200];
+        oam[0..3].copy_from_slice(&[0x4000 | 150, 0x8000 | 500, 0x2C07]); // 32x16，x=-12
+        oam[4] = 0x0200; // 禁用
+        let sprites = gba_sprites(&oam);
+        assert_eq!(sprites.len(), 127);
+        assert_eq!((sprites[0].x, sprites[0].y, sprites[0].width, sprites[0].height), (-12, 150, 32, 16));
+        assert_eq!((sprites[0].tile, sprites[0].palette, sprites[0].priority), (7, 2, 3));
+        assert_eq!(sprites[1].index, 2);
+        assert!(sprite_table(&sprites[..1]).contains("#0   ( -12,  150) 32x16"));
+    }
+
+    #[test]
+    fn test_highlight_marks_border_and_tints_inside() {
+        let sprite = SpriteEntry { index: 0, x: -1, y: 1, width: 4, height: 3, tile: 0, palette: 0, priority: 0, x_flip: false, y_flip: false };
+        let mut rgb = vec![0u8; 8 * 8 * 3];
+        highlight_rgb(&mut rgb, 8, 8, &sprite);
+        assert_eq!(&rgb[(8 + 2) * 3..(8 + 2) * 3 + 3], &HIGHLIGHT_RGB); // 上边框
+        assert_eq!(&rgb[(2 * 8 + 1) * 3..(2 * 8 + 1) * 3 + 3], &[0x7F, 0, 0x7F]); // 内部
+        assert_eq!(&rgb[..3], &[0, 0, 0]);
+
+        let mut bgr = vec![0x03E0u16; 8 * 8];
+        highlight_bgr555(&mut bgr, 8, 8, &sprite);
+        assert_eq!(bgr[8 + 2], HIGHLIGHT_BGR555);
+        assert_eq!(bgr[2 * 8 + 1], 0x3DEF);
+        assert_eq!(bgr[0], 0x03E0);
+    }
+}
@@ -0,0 +1,110 @@
+//! 符号表
+//!
+//! 读取RGBDS等汇编器生成的 `.sym` 文件：每行 `BB:AAAA 名称`，`;` 之后为注释。
+//! 调试器和热重载用它按标签名查找地址。
+
+use std::fs;
+use std::path::Path;
+
+use super::cheats::parse_hex_u16;
+
+/// 一个符号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub bank: u8,
+    pub address: u16,
+    pub name: String,
+}
+
+/// 按文件顺序保存的符号
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 解析 `.sym` 文本
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut table = Self::new();
+        for (line_num, line) in text.lines().enumerate() {
+            let line = line.split(';').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let parse_err = || format!("符号文件第{}行格式错误: {}", line_num + 1, line);
+            let (location, name) = line.split_once(char::is_whitespace).ok_or_else(parse_err)?;
+            let (bank, address) = location.split_once(':').ok_or_else(parse_err)?;
+            table.symbols.push(Symbol {
+                bank: u8::from_str_radix(bank, 16).map_err(|_| parse_err())?,
+                address: u16::from_str_radix(address, 16).map_err(|_| parse_err())?,
+                name: name.trim().to_string(),
+            });
+        }
+        Ok(table)
+    }
+
+    /// 读取符号文件
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| format!("无法读取符号文件 {}: {}", path.display(), e))?;
+        Self::parse(&text)
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// 按名称查找
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| symbol.name == name)
+    }
+
+    /// 地址处的第一个符号名
+    pub fn name_at(&self, address: u16) -> Option<&str> {
+        self.symbols.iter().find(|symbol| symbol.address == address).map(|symbol| symbol.name.as_str())
+    }
+
+    /// 解析地址：先按符号名查找，再按十六进制地址解析（允许 `0x` 或 `$` 前缀）
+    pub fn resolve(&self, spec: &str) -> Result<u16, String> {
+        let spec = spec.trim();
+        match self.get(spec) {
+            Some(symbol) => Ok(symbol.address),
+            None => parse_hex_u16(spec.trim_start_matches('$')).map_err(|_| format!("未知的符号或地址: {}", spec)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_resolve_symbols() {
+        let table = SymbolTable::parse("; File generated by rgblink\n00:0150 Main\n00:0158 Main.loop ; 主循环\n\n01:4000 Add\n").unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get("Main.loop"), Some(&Symbol { bank: 0, address: 0x158, name: "Main.loop".to_string() }));
+        assert_eq!(table.name_at(0x4000), Some("Add"));
+
+        // 符号名优先于同名的十六进制数
+        assert_eq!(table.resolve("Add"), Ok(0x4000));
+        assert_eq!(table.resolve("$0200"), Ok(0x200));
+        assert_eq!(table.resolve("0x1F0"), Ok(0x1F0));
+        assert!(table.resolve("Missing").is_err());
+
+        assert!(SymbolTable::parse("00:0150").is_err());
+        assert!(SymbolTable::parse("zz:0150 Main").is_err());
+    }
+}
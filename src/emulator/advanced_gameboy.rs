@@ -1,13 +1,19 @@
 //! 高级GameBoy模拟器 - 集成所有功能
 
-use crate::cpu::{OptimizedCPU, PerformanceStats, Registers, FlagsRegister};
-use crate::memory::MemoryBus;
-use crate::gpu::LCD;
-use crate::debug::{Debugger, DebuggerState, LogLevel};
+use crate::cpu::{OpcodeStats, OptimizedCPU, PerformanceStats, Registers, FlagsRegister};
+use crate::memory::{MemoryBus, MemoryRegion, MemoryWatch};
+use crate::frontend::{Emulator, Frame};
+use crate::gpu::{BootAnimation, LCD, boot::ENTRY_POINT, lcd::{LCDMode, CYCLES_PER_FRAME}};
+use crate::debug::{link, sprites, Debugger, DebuggerState, FrameDiff, FreezeMode, LinkLogger, Lockstep, LogLevel, SpriteEntry};
+use crate::i18n::{tr, Msg};
 use crate::instructions::Instruction;
+use crate::rom::validate_rom;
+use crate::util::alloc::{self, Subsystem};
+use super::{CycleBudget, CyclesReport, SaveMetadata, SaveSlots, SaveState};
 
 /// CPU状态快照
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CPUState {
     pub pc: u16,
     pub sp: u16,
@@ -26,6 +32,20 @@ pub struct AdvancedGameBoy {
     pub frame_count: u64,
     pub target_fps: u32,
     pub frame_time: std::time::Duration,
+    /// 帧边界内存快照，调试器可在模拟运行时读取
+    pub memory_watch: Option<MemoryWatch>,
+    /// 正在播放的开机动画，CPU运行开机程序到卡带入口时结束
+    pub boot: Option<BootAnimation>,
+    /// 差分执行：参考解释器跟随执行，不一致时报错
+    pub lockstep: Option<Lockstep>,
+    /// 逐帧内存变化追踪
+    pub frame_diff: Option<FrameDiff>,
+    /// 连接线通信记录
+    pub link_log: Option<LinkLogger>,
+    /// 存档槽位，设置后调试命令中可以使用 `slot`
+    pub slots: Option<SaveSlots>,
+    /// `run_cycles` 跨调用的周期欠账
+    budget: CycleBudget,
 }
 
 impl AdvancedGameBoy {
@@ -40,6 +60,13 @@ impl AdvancedGameBoy {
             frame_count: 0,
             target_fps: 60,
             frame_time: std::time::Duration::from_millis(16), // ~60 FPS
+            memory_watch: None,
+            boot: None,
+            lockstep: None,
+            frame_diff: None,
+            link_log: None,
+            slots: None,
+            budget: CycleBudget::new(),
         }
     }
 
@@ -55,14 +82,27 @@ impl AdvancedGameBoy {
         self.debugger.log(LogLevel::Info, "高级GameBoy模拟器停止");
     }
 
-    /// 重置模拟器
+    /// 重置模拟器，已开启的操作码统计清零后继续记录
     pub fn reset(&mut self) {
         let bus = MemoryBus::new();
+        let opcode_stats = self.cpu.opcode_stats.is_some();
         self.cpu = OptimizedCPU::new(bus);
+        if opcode_stats {
+            self.cpu.enable_opcode_stats();
+        }
         self.lcd.reset();
+        // 日志级别是用户设置，重置后保留
+        let log_level = self.debugger.log_level;
         self.debugger = Debugger::new();
+        self.debugger.set_log_level(log_level);
         self.running = false;
         self.frame_count = 0;
+        self.boot = None;
+        self.lockstep = None;
+        self.frame_diff = None;
+        self.link_log = None;
+        self.budget.clear();
+        self.publish_snapshot();
         self.debugger.log(LogLevel::Info, "模拟器已重置");
     }
 
@@ -85,11 +125,27 @@ impl AdvancedGameBoy {
         }
 
         // 执行CPU指令
-        self.cpu.step_optimized()?;
+        let before = self.cpu.cycle_count;
+        {
+            let _scope = alloc::enter(Subsystem::Cpu);
+            self.cpu.step_optimized()?;
+        }
+        let cycles = (self.cpu.cycle_count - before) * 4;
         self.debugger.increment_step_count();
+        if self.cpu.pc == ENTRY_POINT {
+            if let Some(boot) = self.boot.take() {
+                boot.unmap(self.cpu.bus.memory_mut());
+                self.debugger.log(LogLevel::Info, "开机动画结束");
+            }
+        }
+        self.check_lockstep()?;
+        if self.debugger.cheats.mode == FreezeMode::EveryStep {
+            self.apply_cheats();
+        }
+        self.poll_link();
 
-        // 更新LCD
-        self.lcd.update(1); // 假设每个指令1个周期
+        // 按指令用掉的时钟周期推进LCD
+        self.update_lcd(cycles as u32);
 
         // 检查最大步数限制
         if self.debugger.check_max_steps() {
@@ -100,6 +156,77 @@ impl AdvancedGameBoy {
         Ok(())
     }
 
+    /// 运行至少 `cycles` 个时钟周期，多跑的周期从下一次调用中扣除
+    ///
+    /// 停止运行、暂停或命中断点时提前返回。
+    pub fn run_cycles(&mut self, cycles: u64) -> Result<CyclesReport, String> {
+        let mut budget = self.budget;
+        let report = budget.run(cycles, || {
+            let halted = matches!(self.debugger.state, DebuggerState::Paused | DebuggerState::BreakpointHit);
+            if !self.running || halted {
+                return Ok(None);
+            }
+            // 命中断点时 `step` 不执行指令，周期数不变
+            let before = self.cpu.cycle_count;
+            self.step()?;
+            let used = (self.cpu.cycle_count - before) * 4;
+            Ok((used > 0).then_some(used))
+        });
+        self.budget = budget;
+        report
+    }
+
+    /// 开启差分执行，每 `interval` 条指令与参考解释器比较一次状态
+    ///
+    /// 金手指冻结和连接线传输对两个核心同样写入，不会被报告为分歧
+    pub fn enable_lockstep(&mut self, interval: u64) {
+        self.lockstep = Some(Lockstep::new(&self.cpu, interval));
+        self.debugger.log(LogLevel::Info, &format!("差分执行已开启，每 {} 条指令比较一次", interval));
+    }
+
+    /// 关闭差分执行
+    pub fn disable_lockstep(&mut self) {
+        self.lockstep = None;
+    }
+
+    /// 写入金手指冻结值，差分执行时参考解释器也写入
+    fn apply_cheats(&mut self) {
+        self.debugger.cheats.apply(&mut self.cpu.bus);
+        if let Some(lockstep) = &mut self.lockstep {
+            self.debugger.cheats.apply(lockstep.reference_bus_mut());
+        }
+    }
+
+    /// 完成串口传输，差分执行时把串口寄存器同步给参考解释器
+    fn poll_link(&mut self) {
+        let Some(logger) = &mut self.link_log else {
+            return;
+        };
+        if logger.poll(self.cpu.bus.memory_mut(), self.debugger.step_count, self.frame_count).is_none() {
+            return;
+        }
+        if let Some(lockstep) = &mut self.lockstep {
+            let memory = self.cpu.bus.memory();
+            let reference = lockstep.reference_bus_mut().memory_mut();
+            for register in [link::SB, link::SC] {
+                reference[register] = memory[register];
+            }
+        }
+    }
+
+    /// 参考解释器跟上一条指令，不一致时停在断点状态并返回差异
+    fn check_lockstep(&mut self) -> Result<(), String> {
+        let Some(lockstep) = self.lockstep.as_mut() else {
+            return Ok(());
+        };
+        if let Err(error) = lockstep.after_candidate_step(&self.cpu) {
+            self.debugger.state = DebuggerState::BreakpointHit;
+            self.debugger.log(LogLevel::Error, &error.to_string());
+            return Err(error.to_string());
+        }
+        Ok(())
+    }
+
     /// 运行指定步数
     pub fn run_steps(&mut self, steps: u64) -> Result<(), String> {
         self.debugger.set_max_steps(Some(self.debugger.step_count + steps));
@@ -140,9 +267,13 @@ impl AdvancedGameBoy {
         self.cpu.step_optimized()?;
         println!("AFTER: PC={:04X}, 周期={}, 指令={}", self.cpu.pc, self.cpu.cycle_count, self.cpu.instruction_count);
         self.debugger.increment_step_count();
+        if self.debugger.cheats.mode == FreezeMode::EveryStep {
+            self.apply_cheats();
+        }
+        self.poll_link();
 
         // 更新LCD
-        self.lcd.update(1); // 假设每个指令1个周期
+        self.update_lcd(1); // 假设每个指令1个周期
 
         // 检查最大步数限制
         if self.debugger.check_max_steps() {
@@ -153,6 +284,72 @@ impl AdvancedGameBoy {
         Ok(())
     }
 
+    /// 播放开机动画（没有BootROM时的替代）
+    ///
+    /// 从0x0000执行装入的开机程序，程序跳到0x100时卸下并恢复卡带内容。
+    pub fn start_boot_animation(&mut self, mut animation: BootAnimation) {
+        animation.install(self.cpu.bus.memory_mut(), &mut self.lcd);
+        self.cpu.pc = 0x0000;
+        self.boot = Some(animation);
+        self.debugger.log(LogLevel::Info, "播放开机动画");
+    }
+
+    /// 推进LCD，进入VBlank时视为一帧结束并发布内存快照
+    fn update_lcd(&mut self, cycles: u32) {
+        let was_vblank = self.lcd.mode == LCDMode::VBlank;
+        {
+            let _scope = alloc::enter(Subsystem::Gpu);
+            let memory = self.cpu.bus.memory_mut();
+            self.lcd.load_registers(memory);
+            self.lcd.tick(cycles, memory);
+            self.lcd.store_registers(memory);
+            // LY由宿主写入，参考解释器同样写入才不会报告分歧
+            if let Some(lockstep) = &mut self.lockstep {
+                self.lcd.store_registers(lockstep.reference_bus_mut().memory_mut());
+            }
+        }
+
+        if !was_vblank && self.lcd.mode == LCDMode::VBlank {
+            self.frame_count += 1;
+            if self.debugger.cheats.mode == FreezeMode::EveryFrame {
+                self.apply_cheats();
+            }
+            if let Some(tracker) = &mut self.frame_diff {
+                tracker.capture(self.cpu.bus.memory());
+            }
+            self.publish_snapshot();
+        }
+    }
+
+    /// 开始逐帧追踪WRAM变化，返回追踪器供查询
+    pub fn track_wram_changes(&mut self) -> &mut FrameDiff {
+        let tracker = self.frame_diff.insert(FrameDiff::wram());
+        tracker.capture(self.cpu.bus.memory());
+        tracker
+    }
+
+    /// 开始记录连接线通信，返回记录器供添加解码器和查询
+    pub fn log_link_traffic(&mut self) -> &mut LinkLogger {
+        self.link_log.get_or_insert_with(LinkLogger::new)
+    }
+
+    /// 获取内存观察句柄，首次调用时开始在每帧结束时发布快照
+    ///
+    /// 句柄可以发送到其他线程，读取时无需暂停模拟器
+    pub fn memory_watch(&mut self) -> MemoryWatch {
+        if self.memory_watch.is_none() {
+            self.memory_watch = Some(MemoryWatch::new(&self.cpu.bus, &MemoryRegion::DEFAULT));
+        }
+        self.memory_watch.clone().unwrap()
+    }
+
+    /// 立即发布一次内存快照（例如暂停时修改了内存）
+    pub fn publish_snapshot(&mut self) {
+        if let Some(watch) = &self.memory_watch {
+            watch.publish(&self.cpu.bus, self.frame_count);
+        }
+    }
+
     /// 设置断点
     pub fn set_breakpoint(&mut self, address: u16, condition: Option<String>) {
         self.debugger.set_breakpoint(address, condition);
@@ -168,6 +365,16 @@ impl AdvancedGameBoy {
         self.cpu.get_performance_stats()
     }
 
+    /// 开启按操作码统计，已开启时清零
+    pub fn enable_opcode_stats(&mut self) {
+        self.cpu.enable_opcode_stats();
+    }
+
+    /// 按操作码统计，未开启时为 `None`
+    pub fn opcode_stats(&self) -> Option<&OpcodeStats> {
+        self.cpu.opcode_stats.as_deref()
+    }
+
     /// 获取CPU状态
     pub fn get_cpu_state(&self) -> CPUState {
         CPUState {
@@ -179,6 +386,22 @@ impl AdvancedGameBoy {
         }
     }
 
+    /// 执行调试命令，返回要显示的结果
+    ///
+    /// `slot` 和 `slots` 交给存档槽位的 `SaveSlots::run_command`，需要先设置 `slots`；
+    /// 其余命令见 `Debugger::run_cheat_command`。`session` 是本次运行的时长，用来累计游戏时长。
+    pub fn run_debug_command(&mut self, line: &str, session: std::time::Duration) -> Result<String, String> {
+        match line.split_whitespace().next() {
+            Some("slot" | "slots") => {
+                let mut slots = self.slots.take().ok_or_else(|| tr(Msg::SlotNotConfigured).to_string())?;
+                let result = slots.run_command(self, line, session);
+                self.slots = Some(slots);
+                result
+            }
+            _ => self.debugger.run_cheat_command(line),
+        }
+    }
+
     /// 获取调试信息
     pub fn get_debug_info(&self) -> String {
         let stats = self.get_performance_stats();
@@ -214,6 +437,19 @@ impl AdvancedGameBoy {
         self.lcd.get_framebuffer()
     }
 
+    /// 列出OAM中在屏幕上的精灵
+    pub fn sprites(&self) -> Vec<SpriteEntry> {
+        sprites::gb_sprites(self.cpu.bus.memory())
+    }
+
+    /// 返回高亮了指定OAM编号精灵的帧缓冲区副本，精灵不在屏幕上时返回 `None`
+    pub fn highlight_sprite(&self, index: usize) -> Option<Vec<u8>> {
+        let sprite = self.sprites().into_iter().find(|sprite| sprite.index == index)?;
+        let mut framebuffer = self.lcd.get_framebuffer().to_vec();
+        sprites::highlight_rgb(&mut framebuffer, self.lcd.width as usize, self.lcd.height as usize, &sprite);
+        Some(framebuffer)
+    }
+
     /// 设置目标FPS
     pub fn set_target_fps(&mut self, fps: u32) {
         self.target_fps = fps;
@@ -237,6 +473,40 @@ impl AdvancedGameBoy {
         self.cpu.bus.memory()
     }
 
+    /// 保存当前状态
+    pub fn save_state(&self) -> SaveState {
+        SaveState {
+            cycle: self.cpu.cycle_count,
+            pc: self.cpu.pc,
+            sp: self.cpu.sp,
+            registers: self.cpu.registers,
+            flags: self.cpu.flags,
+            memory: self.memory().to_vec(),
+            metadata: SaveMetadata::default(),
+        }
+    }
+
+    /// 恢复状态
+    pub fn load_state(&mut self, state: &SaveState) -> Result<(), String> {
+        let memory = self.cpu.bus.memory_mut();
+        if state.memory.len() != memory.len() {
+            return Err(format!("存档内存大小不匹配: {} != {}", state.memory.len(), memory.len()));
+        }
+        memory.copy_from_slice(&state.memory);
+        self.cpu.pc = state.pc;
+        self.cpu.sp = state.sp;
+        self.cpu.registers = state.registers;
+        self.cpu.flags = state.flags;
+        self.cpu.cycle_count = state.cycle;
+        self.budget.clear();
+        // 参考解释器从读出的状态重新跟随
+        if let Some(interval) = self.lockstep.as_ref().map(Lockstep::interval) {
+            self.lockstep = Some(Lockstep::new(&self.cpu, interval));
+        }
+        self.publish_snapshot();
+        Ok(())
+    }
+
     /// 反汇编指令
     pub fn disassemble_instruction(&self, pc: u16) -> String {
         self.debugger.disassemble_instruction(pc, &self.cpu.bus)
@@ -254,6 +524,54 @@ impl Default for AdvancedGameBoy {
     }
 }
 
+impl Emulator for AdvancedGameBoy {
+    fn reset(&mut self) {
+        AdvancedGameBoy::reset(self);
+    }
+
+    fn load_rom(&mut self, data: &[u8]) -> Result<(), String> {
+        validate_rom(data).map_err(|e| e.to_string())?;
+        self.load_program(0x0000, data)
+    }
+
+    fn step(&mut self) -> Result<(), String> {
+        AdvancedGameBoy::step(self)
+    }
+
+    /// 运行到下一次VBlank；停止或命中断点时提前返回
+    fn run_frame(&mut self) -> Result<(), String> {
+        let start_frame = self.frame_count;
+        for _ in 0..CYCLES_PER_FRAME {
+            let halted = matches!(self.debugger.state, DebuggerState::Paused | DebuggerState::BreakpointHit);
+            if self.frame_count != start_frame || !self.running || halted {
+                break;
+            }
+            AdvancedGameBoy::step(self)?;
+        }
+        Ok(())
+    }
+
+    fn run_cycles(&mut self, cycles: u64) -> Result<CyclesReport, String> {
+        AdvancedGameBoy::run_cycles(self, cycles)
+    }
+
+    fn frame(&self) -> Option<Frame<'_>> {
+        Some(Frame {
+            width: self.lcd.width as usize,
+            height: self.lcd.height as usize,
+            pixels: self.lcd.get_framebuffer(),
+        })
+    }
+
+    fn snapshot(&self) -> Option<SaveState> {
+        Some(self.save_state())
+    }
+
+    fn restore(&mut self, state: &SaveState) -> Result<(), String> {
+        self.load_state(state)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,6 +593,155 @@ mod tests {
         assert_eq!(gameboy.debugger.breakpoints.len(), 0);
     }
 
+    #[test]
+    fn test_memory_watch_publishes_at_frame_boundary() {
+        let mut gameboy = AdvancedGameBoy::new();
+        let watch = gameboy.memory_watch();
+        gameboy.cpu.bus.write_byte(0xC000, 0x5A);
+        assert_eq!(watch.latest().read_byte(0xC000), Some(0x00));
+
+        // 144条扫描线后进入VBlank
+        for _ in 0..144 * 456 {
+            gameboy.update_lcd(1);
+        }
+        let snapshot = watch.latest();
+        assert_eq!(gameboy.frame_count, 1);
+        assert_eq!(snapshot.frame, 1);
+        assert_eq!(snapshot.read_byte(0xC000), Some(0x5A));
+    }
+
+    #[test]
+    fn test_wram_changes_tracked_per_frame() {
+        let mut gameboy = AdvancedGameBoy::new();
+        gameboy.track_wram_changes();
+        for frame in 1..=3 {
+            gameboy.cpu.bus.write_byte(0xC042, frame);
+            for _ in 0..154 * 456 {
+                gameboy.update_lcd(1);
+            }
+        }
+        let tracker = gameboy.frame_diff.as_ref().unwrap();
+        assert_eq!(tracker.frames(), 3);
+        assert_eq!(tracker.change_count(0xC042), 3);
+        assert_eq!(tracker.changed_by(1), vec![0xC042]);
+    }
+
+    #[test]
+    fn test_lockstep_mode_stops_on_divergence() {
+        let mut gameboy = AdvancedGameBoy::new();
+        gameboy.load_program(0x100, &[0x0C, 0x0C, 0x0C]).unwrap();
+        gameboy.enable_lockstep(1);
+        gameboy.start();
+        gameboy.step().unwrap();
+
+        gameboy.cpu.registers.d = 0x10;
+        let error = gameboy.step().unwrap_err();
+        assert!(error.contains("不一致"));
+        assert_eq!(gameboy.debugger.state, DebuggerState::BreakpointHit);
+    }
+
+    #[test]
+    fn test_lockstep_mirrors_cheats_and_link_writes() {
+        // LD A,0x55; LDH (0x01),A; LD A,0x81; LDH (0x02),A; NOP
+        let mut gameboy = AdvancedGameBoy::new();
+        gameboy.load_program(0x100, &[0x3E, 0x55, 0xE0, 0x01, 0x3E, 0x81, 0xE0, 0x02, 0x00]).unwrap();
+        gameboy.debugger.cheats.mode = FreezeMode::EveryStep;
+        gameboy.debugger.cheats.add_freeze(0xC000, 0x42, None);
+        gameboy.log_link_traffic();
+        gameboy.enable_lockstep(1);
+        gameboy.start();
+
+        for _ in 0..5 {
+            gameboy.step().unwrap();
+        }
+        assert_eq!(gameboy.link_log.as_ref().unwrap().transfers.len(), 1);
+        assert_eq!(gameboy.lockstep.as_ref().unwrap().reference().bus.memory()[0xC000], 0x42);
+    }
+
+    #[test]
+    fn test_lockstep_survives_scanlines_and_state_loads() {
+        // JR -2，原地循环
+        let mut gameboy = AdvancedGameBoy::new();
+        gameboy.load_program(0x100, &[0x18, 0xFE]).unwrap();
+        gameboy.enable_lockstep(1);
+        gameboy.start();
+        let saved = gameboy.save_state();
+
+        // 每条12个时钟周期，200条跨过数条扫描线
+        for _ in 0..200 {
+            gameboy.step().unwrap();
+        }
+        assert!(gameboy.memory()[0xFF44] > 0);
+
+        gameboy.cpu.registers.b = 0x77;
+        gameboy.load_state(&saved).unwrap();
+        gameboy.step().unwrap();
+        assert_eq!(gameboy.lockstep.as_ref().unwrap().executed(), 1);
+    }
+
+    #[test]
+    fn test_boot_program_scrolls_logo_and_reaches_entry_point() {
+        use crate::gpu::boot::{boot_program, SCROLL_START};
+        use crate::gpu::lcd::SCY;
+
+        let mut gameboy = AdvancedGameBoy::new();
+        gameboy.debugger.set_log_level(LogLevel::Warning);
+        gameboy.load_program(0x0000, &[0xAA; 0x40]).unwrap();
+        gameboy.start();
+        gameboy.start_boot_animation(BootAnimation::new());
+        assert_eq!(gameboy.cpu.pc, 0x0000);
+
+        // 开机程序在每次VBlank中把滚动值减1
+        while gameboy.frame_count < 10 {
+            gameboy.step().unwrap();
+        }
+        for _ in 0..100 {
+            gameboy.step().unwrap();
+        }
+        assert_eq!(gameboy.cpu.bus.memory()[SCY], SCROLL_START - 10);
+        assert!((gameboy.cpu.pc as usize) < boot_program().len());
+
+        // CPU自己运行到卡带入口，主机不修改PC
+        while gameboy.boot.is_some() {
+            gameboy.step().unwrap();
+        }
+        assert_eq!(gameboy.cpu.pc, 0x100);
+        assert_eq!(gameboy.frame_count as u32, BootAnimation::total_frames());
+        assert_eq!(gameboy.cpu.bus.memory()[SCY], 0);
+        assert!(gameboy.cpu.bus.memory()[..0x40].iter().all(|&b| b == 0xAA));
+
+        // Logo位于第8-9行瓦片，其余区域为白色
+        let framebuffer = gameboy.get_framebuffer();
+        let row_is_blank = |y: usize| framebuffer[y * 160 * 3..(y + 1) * 160 * 3].iter().all(|&c| c == 255);
+        assert!(row_is_blank(10));
+        assert!((60..82).any(|y| !row_is_blank(y)));
+    }
+
+    #[test]
+    fn test_debug_commands_reach_slots_and_cheats() {
+        use std::time::Duration;
+
+        let mut gameboy = AdvancedGameBoy::new();
+        gameboy.debugger.set_log_level(LogLevel::Warning);
+        gameboy.load_program(0x100, &[0x0C, 0x0C]).unwrap();
+        assert!(gameboy.run_debug_command("slot list", Duration::ZERO).is_err());
+
+        let dir = std::env::temp_dir().join(format!("gamelife_debug_slots_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        gameboy.slots = Some(SaveSlots::new(dir.join("game.gb")));
+        gameboy.run_debug_command("slot save 2", Duration::ZERO).unwrap();
+        gameboy.start();
+        gameboy.step().unwrap();
+        gameboy.run_debug_command("slot load 2", Duration::ZERO).unwrap();
+        let listing = gameboy.run_debug_command("slots", Duration::ZERO);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(gameboy.cpu.registers.c, 0);
+        assert!(listing.unwrap().starts_with(" 2 "));
+        assert!(gameboy.run_debug_command("freeze C000 01", Duration::ZERO).is_ok());
+        assert_eq!(gameboy.debugger.cheats.freezes.len(), 1);
+    }
+
     #[test]
     fn test_reset() {
         let mut gameboy = AdvancedGameBoy::new();
@@ -0,0 +1,164 @@
+//! 按时钟周期运行
+//!
+//! 指令不能从中间打断，运行 `n` 个周期时最后一条指令往往会多跑几个周期。
+//! `CycleBudget` 把多跑的部分记为欠账，从下一次调用的预算中扣除，
+//! 多次调用的累计周期数因此与请求的总数一致，前端按固定周期数切分音视频时不会漂移。
+
+/// 一次 `run_cycles` 的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CyclesReport {
+    /// 请求运行的周期数
+    pub requested: u64,
+    /// 本次实际执行的周期数
+    pub executed: u64,
+    /// 本次执行的指令数
+    pub instructions: u64,
+    /// 调用结束后的欠账，会从下一次调用的预算中扣除
+    pub debt: u64,
+    /// 核心中途停止运行（暂停、断点等），没有跑满预算
+    pub stopped: bool,
+}
+
+/// 跨调用的周期欠账
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CycleBudget {
+    debt: u64,
+}
+
+impl CycleBudget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 当前欠账
+    pub fn debt(&self) -> u64 {
+        self.debt
+    }
+
+    /// 清除欠账，读档或重置后调用
+    pub fn clear(&mut self) {
+        self.debt = 0;
+    }
+
+    /// 扣除欠账后反复调用 `step` 直到跑满预算
+    ///
+    /// `step` 执行一条指令并返回用掉的周期数；核心停止运行时返回 `None`，
+    /// 此时提前结束并清除欠账。
+    pub fn run<F>(&mut self, cycles: u64, mut step: F) -> Result<CyclesReport, String>
+    where
+        F: FnMut() -> Result<Option<u64>, String>,
+    {
+        let mut report = CyclesReport { requested: cycles, ..CyclesReport::default() };
+        if self.debt >= cycles {
+            self.debt -= cycles;
+            report.debt = self.debt;
+            return Ok(report);
+        }
+
+        let budget = cycles - self.debt;
+        self.debt = 0;
+        while report.executed < budget {
+            match step()? {
+                Some(used) => {
+                    report.executed += used;
+                    report.instructions += 1;
+                }
+                None => {
+                    report.stopped = true;
+                    return Ok(report);
+                }
+            }
+        }
+        self.debt = report.executed - budget;
+        report.debt = self.debt;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overshoot_carries_to_next_call() {
+        let mut budget = CycleBudget::new();
+        // 每条指令12个周期，请求10个周期会多跑2个
+        let report = budget.run(10, || Ok(Some(12))).unwrap();
+        assert_eq!(report, CyclesReport { requested: 10, executed: 12, instructions: 1, debt: 2, stopped: false });
+
+        let report = budget.run(22, || Ok(Some(12))).unwrap();
+        assert_eq!((report.executed, report.instructions, report.debt), (24, 2, 4));
+
+        // 欠账超过请求时不执行指令
+        let report = budget.run(3, || panic!("不应执行")).unwrap();
+        assert_eq!((report.executed, report.debt), (0, 1));
+
+        // 三次调用共请求35个周期，执行36个，欠1个
+        assert_eq!(budget.debt(), 1);
+    }
+
+    #[test]
+    fn test_stopped_core_ends_early_and_clears_debt() {
+        let mut budget = CycleBudget::new();
+        budget.run(1, || Ok(Some(4))).unwrap();
+
+        let mut left = 2;
+        let report = budget
+            .run(100, || {
+                left -= 1;
+                Ok((left >= 0).then_some(4))
+            })
+            .unwrap();
+        assert_eq!((report.executed, report.instructions, report.debt), (8, 2, 0));
+        assert!(report.stopped);
+        assert_eq!(budget.debt(), 0);
+
+        assert!(budget.run(4, || Err("坏指令".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_gameboy_counts_clock_cycles() {
+        use crate::emulator::GameBoy;
+
+        // 0x100: NOP; NOP; JP 0x0100，一圈4+4+16个周期
+        let mut gameboy = GameBoy::new();
+        gameboy.enable_opcode_stats();
+        gameboy.load_program(0x100, &[0x00, 0x00, 0xC3, 0x00, 0x01]);
+
+        let report = gameboy.run_cycles(10).unwrap();
+        assert_eq!((report.executed, report.instructions, report.debt), (24, 3, 14));
+        assert_eq!(gameboy.get_cpu_state().pc, 0x100);
+
+        let report = gameboy.run_cycles(14).unwrap();
+        assert_eq!((report.executed, report.debt), (0, 0));
+
+        let report = gameboy.run_cycles(4).unwrap();
+        assert_eq!((report.executed, report.instructions, report.debt), (4, 1, 0));
+
+        // 操作码统计记录机器周期
+        let stats = gameboy.opcode_stats().unwrap();
+        assert_eq!((stats.cycles(0x00), stats.cycles(0xC3), stats.total_cycles()), (3, 4, 7));
+        // 核心和存档记录机器周期而不是指令数
+        assert_eq!((gameboy.cycles(), gameboy.save_state().cycle), (7, 7));
+    }
+
+    #[test]
+    fn test_advanced_gameboy_stops_at_breakpoint() {
+        use crate::debug::LogLevel;
+        use crate::emulator::AdvancedGameBoy;
+
+        let mut gameboy = AdvancedGameBoy::new();
+        gameboy.debugger.set_log_level(LogLevel::Warning);
+        gameboy.load_program(0x100, &[0x00, 0x00, 0xC3, 0x00, 0x01]).unwrap();
+        assert!(gameboy.run_cycles(8).unwrap().stopped);
+
+        gameboy.start();
+        let report = gameboy.run_cycles(10).unwrap();
+        assert_eq!((report.executed, report.instructions, report.debt), (24, 3, 14));
+
+        gameboy.set_breakpoint(0x101, None);
+        let report = gameboy.run_cycles(100).unwrap();
+        assert!(report.stopped);
+        assert_eq!((report.executed, report.instructions, gameboy.cpu.pc), (4, 1, 0x101));
+    }
+}
@@ -1,12 +1,19 @@
 //! Game Boy模拟器核心
 
-use crate::cpu::CPU;
+use crate::cpu::{OpcodeStats, CPU};
+use crate::frontend::Emulator;
+use crate::gpu::lcd::CYCLES_PER_FRAME;
 use crate::memory::MemoryBus;
+use crate::rom::validate_rom;
+use crate::util::alloc::{self, Subsystem};
+use super::{CycleBudget, CyclesReport, SaveMetadata, SaveState};
 
 /// Game Boy模拟器主结构
 #[derive(Debug)]
 pub struct GameBoy {
     cpu: CPU,
+    /// `run_cycles` 多跑的周期
+    budget: CycleBudget,
 }
 
 impl GameBoy {
@@ -15,7 +22,7 @@ impl GameBoy {
         let bus = MemoryBus::new();
         let cpu = CPU::new(bus);
         
-        Self { cpu }
+        Self { cpu, budget: CycleBudget::new() }
     }
 
     /// 加载程序到模拟器
@@ -25,7 +32,14 @@ impl GameBoy {
 
     /// 执行一步指令
     pub fn step(&mut self) -> Result<(), String> {
-        self.cpu.step()
+        self.step_timed().map(|_| ())
+    }
+
+    /// 执行一步指令，返回用掉的时钟周期数
+    fn step_timed(&mut self) -> Result<u64, String> {
+        let _scope = alloc::enter(Subsystem::Cpu);
+        let machine_cycles = self.cpu.step()?;
+        Ok(machine_cycles as u64 * 4)
     }
 
     /// 执行多步指令
@@ -36,6 +50,17 @@ impl GameBoy {
         Ok(())
     }
 
+    /// 运行至少 `cycles` 个时钟周期
+    ///
+    /// 最后一条指令多跑的周期记为欠账，从下一次调用中扣除，
+    /// 连续调用的累计周期数与请求的总数保持一致。
+    pub fn run_cycles(&mut self, cycles: u64) -> Result<CyclesReport, String> {
+        let mut budget = self.budget;
+        let report = budget.run(cycles, || self.step_timed().map(Some));
+        self.budget = budget;
+        report
+    }
+
     /// 获取CPU状态
     pub fn get_cpu_state(&self) -> CPUState {
         CPUState {
@@ -50,10 +75,55 @@ impl GameBoy {
     pub fn memory(&self) -> &[u8] {
         self.cpu.bus.memory()
     }
+
+    /// 已执行的机器周期数
+    pub fn cycles(&self) -> u64 {
+        self.cpu.cycle_count
+    }
+
+    /// 开启按操作码统计，已开启时清零
+    pub fn enable_opcode_stats(&mut self) {
+        self.cpu.enable_opcode_stats();
+    }
+
+    /// 按操作码统计，未开启时为 `None`
+    pub fn opcode_stats(&self) -> Option<&OpcodeStats> {
+        self.cpu.opcode_stats.as_deref()
+    }
+
+    /// 保存当前状态
+    pub fn save_state(&self) -> SaveState {
+        SaveState {
+            cycle: self.cpu.cycle_count,
+            pc: self.cpu.pc,
+            sp: self.cpu.sp,
+            registers: self.cpu.registers,
+            flags: self.cpu.flags,
+            memory: self.memory().to_vec(),
+            metadata: SaveMetadata::default(),
+        }
+    }
+
+    /// 恢复状态
+    pub fn load_state(&mut self, state: &SaveState) -> Result<(), String> {
+        let memory = self.cpu.bus.memory_mut();
+        if state.memory.len() != memory.len() {
+            return Err(format!("存档内存大小不匹配: {} != {}", state.memory.len(), memory.len()));
+        }
+        memory.copy_from_slice(&state.memory);
+        self.cpu.pc = state.pc;
+        self.cpu.sp = state.sp;
+        self.cpu.registers = state.registers;
+        self.cpu.flags = state.flags;
+        self.cpu.cycle_count = state.cycle;
+        self.budget.clear();
+        Ok(())
+    }
 }
 
 /// CPU状态快照
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CPUState {
     pub registers: crate::cpu::Registers,
     pub pc: u16,
@@ -66,3 +136,36 @@ impl Default for GameBoy {
         Self::new()
     }
 }
+
+impl Emulator for GameBoy {
+    fn reset(&mut self) {
+        *self = GameBoy::new();
+    }
+
+    fn load_rom(&mut self, data: &[u8]) -> Result<(), String> {
+        validate_rom(data).map_err(|e| e.to_string())?;
+        self.load_program(0x0000, data);
+        Ok(())
+    }
+
+    fn step(&mut self) -> Result<(), String> {
+        GameBoy::step(self)
+    }
+
+    /// 基础模拟器没有LCD，运行一帧的时钟周期数
+    fn run_frame(&mut self) -> Result<(), String> {
+        self.run_cycles(CYCLES_PER_FRAME as u64).map(|_| ())
+    }
+
+    fn run_cycles(&mut self, cycles: u64) -> Result<CyclesReport, String> {
+        GameBoy::run_cycles(self, cycles)
+    }
+
+    fn snapshot(&self) -> Option<SaveState> {
+        Some(self.save_state())
+    }
+
+    fn restore(&mut self, state: &SaveState) -> Result<(), String> {
+        self.load_state(state)
+    }
+}
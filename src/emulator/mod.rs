@@ -1,7 +1,15 @@
 //! 模拟器核心模块
 
+pub mod cycles;
 pub mod gameboy;
+pub mod savestate;
+pub mod slots;
+#[cfg(feature = "debug")]
 pub mod advanced_gameboy;
 
+pub use cycles::{CycleBudget, CyclesReport};
 pub use gameboy::GameBoy;
+pub use savestate::{SaveMetadata, SaveState, Thumbnail};
+pub use slots::SaveSlots;
+#[cfg(feature = "debug")]
 pub use advanced_gameboy::AdvancedGameBoy;
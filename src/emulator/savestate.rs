@@ -0,0 +1,384 @@
+//! 存档状态 (savestate)
+//!
+//! 保存CPU寄存器、周期计数和完整内存，可写入文件后再恢复，
+//! 也是 `debug::diff` 比较两次运行结果的输入。
+//!
+//! 文件格式（小端序）：`GLSS` 魔数、u16版本、u32元数据长度、元数据、
+//! 然后是机器状态：u64周期、PC、SP、8个寄存器字节、标志字节 (ZNHC0000)、
+//! u32内存长度和内存内容。
+//!
+//! 元数据放在机器状态之前，`SaveState::peek_metadata` 只读文件开头就能拿到
+//! 缩略图、ROM标题和游戏时长，存档选择界面不需要读入完整内存。
+//! 版本1的文件没有元数据，仍然可以读取。
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::cpu::{FlagsRegister, Registers};
+use crate::frontend::Frame;
+use crate::rom::RomHeader;
+
+/// 文件魔数
+pub const MAGIC: &[u8; 4] = b"GLSS";
+/// 当前格式版本
+pub const VERSION: u16 = 2;
+/// 魔数、版本和元数据长度
+const PREFIX_LEN: usize = 4 + 2 + 4;
+/// 内存之前的机器状态长度
+const MACHINE_HEADER_LEN: usize = 8 + 2 + 2 + 8 + 1 + 4;
+/// 缩略图相对画面的缩小倍数，160x144的画面得到80x72的缩略图
+pub const THUMBNAIL_SCALE: usize = 2;
+
+/// 存档缩略图，像素为RGB888
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Thumbnail {
+    pub width: u16,
+    pub height: u16,
+    pub pixels: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// 把画面按 `scale` 倍缩小，每个像素取对应方块的平均颜色
+    pub fn from_frame(frame: Frame<'_>, scale: usize) -> Self {
+        let scale = scale.max(1);
+        let (width, height) = (frame.width / scale, frame.height / scale);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0u32; 3];
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let index = ((y * scale + dy) * frame.width + x * scale + dx) * 3;
+                        for (total, &value) in sum.iter_mut().zip(&frame.pixels[index..index + 3]) {
+                            *total += value as u32;
+                        }
+                    }
+                }
+                pixels.extend(sum.iter().map(|total| (total / (scale * scale) as u32) as u8));
+            }
+        }
+        Self { width: width as u16, height: height as u16, pixels }
+    }
+}
+
+/// 当前Unix时间戳（秒）
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// 存档元数据，给存档选择界面使用，不影响恢复出的机器状态
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaveMetadata {
+    /// ROM头部中的标题
+    pub rom_title: String,
+    /// ROM头部中的全局校验和，用来确认存档属于哪个ROM
+    pub rom_checksum: u16,
+    /// 保存时间，Unix时间戳（秒）
+    pub saved_at: u64,
+    /// 累计游戏时长，按毫秒保存
+    pub play_time: Duration,
+    pub thumbnail: Option<Thumbnail>,
+}
+
+impl SaveMetadata {
+    /// 从ROM头部读取标题和校验和，保存时间为当前时间
+    pub fn for_rom(rom: &[u8]) -> Self {
+        let header = RomHeader::parse(rom).ok();
+        Self {
+            rom_title: header.as_ref().map(RomHeader::title_text).unwrap_or_default(),
+            rom_checksum: header.map_or(0, |header| header.global_checksum),
+            saved_at: unix_now(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_play_time(mut self, play_time: Duration) -> Self {
+        self.play_time = play_time;
+        self
+    }
+
+    /// 用当前画面生成缩略图
+    pub fn with_thumbnail(mut self, frame: Frame<'_>) -> Self {
+        self.thumbnail = Some(Thumbnail::from_frame(frame, THUMBNAIL_SCALE));
+        self
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let title = self.rom_title.as_bytes();
+        let title = &title[..title.len().min(u8::MAX as usize)];
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.saved_at.to_le_bytes());
+        bytes.extend_from_slice(&(self.play_time.as_millis() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.rom_checksum.to_le_bytes());
+        bytes.push(title.len() as u8);
+        bytes.extend_from_slice(title);
+        match &self.thumbnail {
+            Some(thumbnail) => {
+                bytes.extend_from_slice(&thumbnail.width.to_le_bytes());
+                bytes.extend_from_slice(&thumbnail.height.to_le_bytes());
+                bytes.extend_from_slice(&thumbnail.pixels);
+            }
+            None => bytes.extend_from_slice(&[0; 4]),
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let truncated = || "存档元数据不完整".to_string();
+        let field = |range: std::ops::Range<usize>| bytes.get(range).ok_or_else(truncated);
+        let u64_at = |offset: usize| -> Result<u64, String> {
+            let mut value = [0u8; 8];
+            value.copy_from_slice(field(offset..offset + 8)?);
+            Ok(u64::from_le_bytes(value))
+        };
+        let u16_at = |offset: usize| -> Result<u16, String> {
+            let value = field(offset..offset + 2)?;
+            Ok(u16::from_le_bytes([value[0], value[1]]))
+        };
+
+        let title_len = *bytes.get(18).ok_or_else(truncated)? as usize;
+        let title = field(19..19 + title_len)?;
+        let offset = 19 + title_len;
+        let (width, height) = (u16_at(offset)?, u16_at(offset + 2)?);
+        let pixels_len = width as usize * height as usize * 3;
+        let thumbnail = if pixels_len == 0 {
+            None
+        } else {
+            let pixels = field(offset + 4..offset + 4 + pixels_len)?.to_vec();
+            Some(Thumbnail { width, height, pixels })
+        };
+
+        Ok(Self {
+            rom_title: String::from_utf8_lossy(title).into_owned(),
+            rom_checksum: u16_at(16)?,
+            saved_at: u64_at(0)?,
+            play_time: Duration::from_millis(u64_at(8)?),
+            thumbnail,
+        })
+    }
+}
+
+/// 模拟器状态快照
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaveState {
+    /// 保存时CPU已执行的机器周期数
+    pub cycle: u64,
+    pub pc: u16,
+    pub sp: u16,
+    pub registers: Registers,
+    pub flags: FlagsRegister,
+    /// 完整内存 (0x0000-0xFFFF)
+    pub memory: Vec<u8>,
+    pub metadata: SaveMetadata,
+}
+
+/// 检查魔数和版本
+fn check_version(bytes: &[u8]) -> Result<u16, String> {
+    if bytes.len() < 6 || &bytes[0..4] != MAGIC {
+        return Err("不是有效的存档文件".to_string());
+    }
+    match u16::from_le_bytes([bytes[4], bytes[5]]) {
+        version @ (1 | VERSION) => Ok(version),
+        version => Err(format!("不支持的存档版本: {}", version)),
+    }
+}
+
+/// 版本2文件中元数据的长度
+fn metadata_len(bytes: &[u8]) -> Result<usize, String> {
+    bytes
+        .get(6..PREFIX_LEN)
+        .map(|len| u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize)
+        .ok_or_else(|| "存档元数据不完整".to_string())
+}
+
+impl SaveState {
+    /// 标志寄存器编码为F寄存器格式
+    fn flags_byte(&self) -> u8 {
+        (self.flags.zero as u8) << 7
+            | (self.flags.subtract as u8) << 6
+            | (self.flags.half_carry as u8) << 5
+            | (self.flags.carry as u8) << 4
+    }
+
+    /// 附加元数据
+    pub fn with_metadata(mut self, metadata: SaveMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// 只包含机器状态的字节，不含元数据；比较和哈希存档时使用
+    pub fn machine_bytes(&self) -> Vec<u8> {
+        let r = &self.registers;
+        let mut bytes = Vec::with_capacity(MACHINE_HEADER_LEN + self.memory.len());
+        bytes.extend_from_slice(&self.cycle.to_le_bytes());
+        bytes.extend_from_slice(&self.pc.to_le_bytes());
+        bytes.extend_from_slice(&self.sp.to_le_bytes());
+        bytes.extend_from_slice(&[r.a, r.b, r.c, r.d, r.e, r.f, r.h, r.l]);
+        bytes.push(self.flags_byte());
+        bytes.extend_from_slice(&(self.memory.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.memory);
+        bytes
+    }
+
+    /// 序列化为字节
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let metadata = self.metadata.to_bytes();
+        let machine = self.machine_bytes();
+        let mut bytes = Vec::with_capacity(PREFIX_LEN + metadata.len() + machine.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(metadata.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&metadata);
+        bytes.extend_from_slice(&machine);
+        bytes
+    }
+
+    /// 从字节反序列化
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let (metadata, machine) = if check_version(bytes)? == 1 {
+            (SaveMetadata::default(), &bytes[6..])
+        } else {
+            let end = PREFIX_LEN + metadata_len(bytes)?;
+            let metadata = bytes.get(PREFIX_LEN..end).ok_or_else(|| "存档元数据不完整".to_string())?;
+            (SaveMetadata::from_bytes(metadata)?, &bytes[end..])
+        };
+        if machine.len() < MACHINE_HEADER_LEN {
+            return Err("存档机器状态不完整".to_string());
+        }
+
+        let u16_at = |offset: usize| u16::from_le_bytes([machine[offset], machine[offset + 1]]);
+        let mut cycle = [0u8; 8];
+        cycle.copy_from_slice(&machine[0..8]);
+        let r = &machine[12..20];
+        let flags = machine[20];
+        let memory_len = u32::from_le_bytes([machine[21], machine[22], machine[23], machine[24]]) as usize;
+        let memory = machine
+            .get(MACHINE_HEADER_LEN..MACHINE_HEADER_LEN + memory_len)
+            .ok_or_else(|| format!("存档内存数据不完整: 需要 {} 字节", memory_len))?;
+
+        Ok(Self {
+            cycle: u64::from_le_bytes(cycle),
+            pc: u16_at(8),
+            sp: u16_at(10),
+            registers: Registers { a: r[0], b: r[1], c: r[2], d: r[3], e: r[4], f: r[5], h: r[6], l: r[7] },
+            flags: FlagsRegister {
+                zero: flags & 0x80 != 0,
+                subtract: flags & 0x40 != 0,
+                half_carry: flags & 0x20 != 0,
+                carry: flags & 0x10 != 0,
+            },
+            memory: memory.to_vec(),
+            metadata,
+        })
+    }
+
+    /// 写入文件
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        fs::write(path, self.to_bytes()).map_err(|e| format!("无法写入存档 {}: {}", path.display(), e))
+    }
+
+    /// 从文件读取
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|e| format!("无法读取存档 {}: {}", path.display(), e))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// 只读取文件开头的元数据，不读入机器状态
+    pub fn peek_metadata<P: AsRef<Path>>(path: P) -> Result<SaveMetadata, String> {
+        let path = path.as_ref();
+        let read_error = |e: std::io::Error| format!("无法读取存档 {}: {}", path.display(), e);
+        let mut file = File::open(path).map_err(read_error)?;
+        let mut prefix = Vec::with_capacity(PREFIX_LEN);
+        (&mut file).take(PREFIX_LEN as u64).read_to_end(&mut prefix).map_err(read_error)?;
+        if check_version(&prefix)? == 1 {
+            return Ok(SaveMetadata::default());
+        }
+
+        // 长度字段可能已损坏，超出文件剩余部分时不按它分配内存
+        let len = metadata_len(&prefix)?;
+        let available = file.metadata().map_err(read_error)?.len().saturating_sub(PREFIX_LEN as u64);
+        if len as u64 > available {
+            return Err("存档元数据不完整".to_string());
+        }
+        let mut metadata = Vec::with_capacity(len);
+        file.take(len as u64).read_to_end(&mut metadata).map_err(read_error)?;
+        SaveMetadata::from_bytes(&metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBoy;
+
+    #[test]
+    fn test_savestate_round_trip() {
+        let mut gameboy = GameBoy::new();
+        gameboy.load_program(0x100, &[0x0C, 0x0C]);
+        gameboy.step().unwrap();
+
+        let state = gameboy.save_state();
+        let bytes = state.to_bytes();
+        assert_eq!(&bytes[0..4], MAGIC);
+        assert_eq!(SaveState::from_bytes(&bytes).unwrap(), state);
+        assert!(SaveState::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut restored = GameBoy::new();
+        restored.load_state(&state).unwrap();
+        assert_eq!(restored.save_state(), state);
+
+        // 版本1的文件没有元数据
+        let mut v1 = bytes[..6].to_vec();
+        v1[4] = 1;
+        v1.extend_from_slice(&state.machine_bytes());
+        assert_eq!(SaveState::from_bytes(&v1).unwrap(), state);
+    }
+
+    #[test]
+    fn test_metadata_and_peek() {
+        let mut rom = vec![0u8; 0x150];
+        rom[0x134..0x13B].copy_from_slice(b"POKEMON");
+        rom[0x14E..0x150].copy_from_slice(&0xBEEFu16.to_be_bytes());
+        let pixels: Vec<u8> = (0..4 * 2).flat_map(|i| [i as u8 * 10, 0, 255]).collect();
+        let frame = Frame { width: 4, height: 2, pixels: &pixels };
+
+        let metadata = SaveMetadata::for_rom(&rom).with_play_time(Duration::from_secs(3600)).with_thumbnail(frame);
+        assert_eq!(metadata.rom_title, "POKEMON");
+        assert_eq!(metadata.rom_checksum, 0xBEEF);
+        assert!(metadata.saved_at > 0);
+        // 左上角2x2方块的像素是0、10、40、50
+        let thumbnail = metadata.thumbnail.as_ref().unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (2, 1));
+        assert_eq!(&thumbnail.pixels[..3], &[25, 0, 255]);
+
+        let state = GameBoy::new().save_state().with_metadata(metadata.clone());
+        let path = std::env::temp_dir().join(format!("savestate_peek_metadata_{}.state", std::process::id()));
+        state.save(&path).unwrap();
+        let peeked = SaveState::peek_metadata(&path);
+        let loaded = SaveState::load(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(peeked.unwrap(), metadata);
+        assert_eq!(loaded.unwrap(), state);
+        assert_eq!(state.machine_bytes(), GameBoy::new().save_state().machine_bytes());
+    }
+
+    #[test]
+    fn test_peek_rejects_oversized_metadata_length() {
+        let mut bytes = GameBoy::new().save_state().to_bytes();
+        bytes[6..PREFIX_LEN].copy_from_slice(&u32::MAX.to_le_bytes());
+        let path = std::env::temp_dir().join(format!("savestate_bad_len_{}.state", std::process::id()));
+        fs::write(&path, &bytes).unwrap();
+        let peeked = SaveState::peek_metadata(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(peeked, Err("存档元数据不完整".to_string()));
+    }
+}
@@ -0,0 +1,300 @@
+//! 存档槽位
+//!
+//! 每个ROM有0-9共10个槽位，存在ROM旁边的 `<ROM名>.ss0` 到 `.ss9` 文件里。
+//! 每次读档之前自动把当前状态保存到内存里的撤销状态，读错档可以用
+//! `undo_load` 回到读档之前；再撤销一次又回到读档之后。
+//! 前端的快速存档热键、`AdvancedGameBoy::run_debug_command` 的 `slot` 命令和
+//! `gamelife rom slots` 都通过这里操作。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::frontend::{Emulator, Hotkey};
+use crate::i18n::{tr, trf, Msg};
+
+use super::savestate::{unix_now, SaveMetadata, SaveState};
+
+/// 每个ROM的槽位数
+pub const SLOT_COUNT: u8 = 10;
+
+/// 解析槽位编号
+pub fn parse_slot(text: &str) -> Result<u8, String> {
+    let text = text.trim();
+    text.parse::<u8>()
+        .ok()
+        .filter(|&slot| slot < SLOT_COUNT)
+        .ok_or_else(|| trf(Msg::SlotInvalid, &[&text, &(SLOT_COUNT - 1)]))
+}
+
+fn check_slot(slot: u8) -> Result<u8, String> {
+    parse_slot(&slot.to_string())
+}
+
+/// 把Unix时间戳格式化为 `YYYY-MM-DD HH:MM:SS`（UTC）
+pub fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let (hour, minute, second) = (secs % 86_400 / 3600, secs % 3600 / 60, secs % 60);
+    // 公历日期换算，见 Howard Hinnant 的 civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, hour, minute, second)
+}
+
+/// 一个ROM的存档槽位
+#[derive(Debug, Clone)]
+pub struct SaveSlots {
+    rom_path: PathBuf,
+    /// 新存档使用的ROM标题和校验和
+    rom: SaveMetadata,
+    selected: u8,
+    undo: Option<SaveState>,
+    /// 最近一次读档时存档记录的游戏时长和当时的本次运行时长，用来接着累计游戏时长
+    play_time_base: (Duration, Duration),
+}
+
+impl SaveSlots {
+    /// 只按路径管理槽位，新存档不记录ROM信息
+    pub fn new(rom_path: impl Into<PathBuf>) -> Self {
+        Self {
+            rom_path: rom_path.into(),
+            rom: SaveMetadata::default(),
+            selected: 0,
+            undo: None,
+            play_time_base: (Duration::ZERO, Duration::ZERO),
+        }
+    }
+
+    /// 新存档记录 `rom` 头部的标题和校验和，读档时拒绝其他ROM的存档
+    pub fn for_rom(rom_path: impl Into<PathBuf>, rom: &[u8]) -> Self {
+        Self { rom: SaveMetadata::for_rom(rom), ..Self::new(rom_path) }
+    }
+
+    /// 槽位文件路径
+    pub fn slot_path(&self, slot: u8) -> PathBuf {
+        slot_path_for(&self.rom_path, slot)
+    }
+
+    /// 当前槽位，快速存档和快速读档使用
+    pub fn selected(&self) -> u8 {
+        self.selected
+    }
+
+    pub fn select(&mut self, slot: u8) -> Result<(), String> {
+        self.selected = check_slot(slot)?;
+        Ok(())
+    }
+
+    /// 是否有可以撤销的读档
+    pub fn can_undo(&self) -> bool {
+        self.undo.is_some()
+    }
+
+    /// 读取所有已存在槽位的元数据，损坏的槽位也列出并带上错误信息
+    pub fn list(&self) -> Vec<(u8, Result<SaveMetadata, String>)> {
+        (0..SLOT_COUNT)
+            .map(|slot| (slot, self.slot_path(slot)))
+            .filter(|(_, path)| path.exists())
+            .map(|(slot, path)| (slot, SaveState::peek_metadata(&path)))
+            .collect()
+    }
+
+    /// 删除槽位文件
+    pub fn delete(&self, slot: u8) -> Result<(), String> {
+        let path = self.slot_path(slot);
+        fs::remove_file(&path).map_err(|e| trf(Msg::SlotDeleteFailed, &[&path.display(), &e]))
+    }
+
+    /// 累计游戏时长：`session` 是本次运行的时长
+    fn play_time(&self, session: Duration) -> Duration {
+        let (saved, at) = self.play_time_base;
+        saved + session.saturating_sub(at)
+    }
+
+    /// 当前状态加上元数据，有画面时附带缩略图
+    fn capture<E: Emulator + ?Sized>(&self, emulator: &E, session: Duration) -> Result<SaveState, String> {
+        let state = emulator.snapshot().ok_or_else(|| tr(Msg::SlotUnsupported).to_string())?;
+        let mut metadata = SaveMetadata { saved_at: unix_now(), ..self.rom.clone() }.with_play_time(self.play_time(session));
+        if let Some(frame) = emulator.frame() {
+            metadata = metadata.with_thumbnail(frame);
+        }
+        Ok(state.with_metadata(metadata))
+    }
+
+    /// 恢复状态，成功后把读档前的状态留作撤销状态
+    fn restore<E: Emulator + ?Sized>(&mut self, emulator: &mut E, state: &SaveState, session: Duration) -> Result<(), String> {
+        let current = self.capture(emulator, session)?;
+        emulator.restore(state)?;
+        self.undo = Some(current);
+        self.play_time_base = (state.metadata.play_time, session);
+        Ok(())
+    }
+
+    /// 保存到槽位，`session` 是本次运行的时长
+    pub fn save<E: Emulator + ?Sized>(&self, emulator: &E, slot: u8, session: Duration) -> Result<PathBuf, String> {
+        let path = self.slot_path(check_slot(slot)?);
+        self.capture(emulator, session)?.save(&path)?;
+        Ok(path)
+    }
+
+    /// 从槽位读档
+    pub fn load<E: Emulator + ?Sized>(&mut self, emulator: &mut E, slot: u8, session: Duration) -> Result<SaveMetadata, String> {
+        let state = SaveState::load(self.slot_path(check_slot(slot)?))?;
+        let (expected, found) = (self.rom.rom_checksum, state.metadata.rom_checksum);
+        if expected != 0 && found != 0 && expected != found {
+            return Err(trf(Msg::SlotOtherRom, &[
+                &slot,
+                &state.metadata.rom_title,
+                &format!("{:04X}", found),
+                &format!("{:04X}", expected),
+            ]));
+        }
+        self.restore(emulator, &state, session)?;
+        Ok(state.metadata)
+    }
+
+    /// 撤销最近一次读档
+    pub fn undo_load<E: Emulator + ?Sized>(&mut self, emulator: &mut E, session: Duration) -> Result<(), String> {
+        let state = self.undo.take().ok_or_else(|| tr(Msg::SlotNothingToUndo).to_string())?;
+        if let Err(error) = self.restore(emulator, &state, session) {
+            self.undo = Some(state);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// 执行热键，返回要显示的结果
+    pub fn handle_hotkey<E: Emulator + ?Sized>(&mut self, emulator: &mut E, hotkey: Hotkey, session: Duration) -> Result<String, String> {
+        let slot = self.selected;
+        match hotkey {
+            Hotkey::QuickSave => self.save(emulator, slot, session).map(|_| trf(Msg::SlotSaved, &[&slot])),
+            Hotkey::QuickLoad => self.load(emulator, slot, session).map(|_| trf(Msg::SlotLoaded, &[&slot])),
+            Hotkey::NextSlot | Hotkey::PreviousSlot => {
+                let step = if hotkey == Hotkey::NextSlot { 1 } else { SLOT_COUNT - 1 };
+                self.selected = (slot + step) % SLOT_COUNT;
+                Ok(trf(Msg::SlotSelected, &[&self.selected]))
+            }
+            Hotkey::UndoLoad => self.undo_load(emulator, session).map(|_| tr(Msg::SlotUndone).to_string()),
+        }
+    }
+
+    /// 槽位列表，当前槽位前标 `*`
+    pub fn describe(&self) -> String {
+        let entries = self.list();
+        if entries.is_empty() {
+            return tr(Msg::SlotEmpty).to_string();
+        }
+        entries
+            .iter()
+            .map(|(slot, metadata)| {
+                let marker = if *slot == self.selected { '*' } else { ' ' };
+                match metadata {
+                    Ok(metadata) => trf(Msg::SlotEntry, &[
+                        &marker,
+                        slot,
+                        &metadata.rom_title,
+                        &format_play_time(metadata.play_time),
+                        &format_timestamp(metadata.saved_at),
+                    ]),
+                    Err(error) => format!("{}{} {}", marker, slot, error),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// 执行槽位管理命令，返回要显示的结果
+    ///
+    /// 支持的命令：
+    /// - `slot list` / `slot select <槽位>`
+    /// - `slot save [槽位]` / `slot load [槽位]`，不写槽位时使用当前槽位
+    /// - `slot delete <槽位>` / `slot undo`
+    pub fn run_command<E: Emulator + ?Sized>(&mut self, emulator: &mut E, line: &str, session: Duration) -> Result<String, String> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let slot_or_selected = |slot: Option<&&str>| slot.map_or(Ok(self.selected), |slot| parse_slot(slot));
+
+        match parts.as_slice() {
+            ["slot", "list"] | ["slots"] => Ok(self.describe()),
+            ["slot", "select", slot] => {
+                self.select(parse_slot(slot)?)?;
+                Ok(trf(Msg::SlotSelected, &[&self.selected]))
+            }
+            ["slot", "save", rest @ ..] if rest.len() <= 1 => {
+                let slot = slot_or_selected(rest.first())?;
+                let path = self.save(emulator, slot, session)?;
+                Ok(trf(Msg::SlotSavedTo, &[&slot, &path.display()]))
+            }
+            ["slot", "load", rest @ ..] if rest.len() <= 1 => {
+                let slot = slot_or_selected(rest.first())?;
+                let metadata = self.load(emulator, slot, session)?;
+                Ok(trf(Msg::SlotLoadedAt, &[&slot, &format_timestamp(metadata.saved_at)]))
+            }
+            ["slot", "delete", slot] => {
+                let slot = parse_slot(slot)?;
+                self.delete(slot)?;
+                Ok(trf(Msg::SlotDeleted, &[&slot]))
+            }
+            ["slot", "undo"] => {
+                self.undo_load(emulator, session)?;
+                Ok(tr(Msg::SlotUndone).to_string())
+            }
+            _ => Err(trf(Msg::SlotUnknownCommand, &[&line.trim()])),
+        }
+    }
+}
+
+fn slot_path_for(rom_path: &Path, slot: u8) -> PathBuf {
+    rom_path.with_extension(format!("ss{}", slot))
+}
+
+/// 游戏时长格式化为 `H:MM:SS`
+pub fn format_play_time(play_time: Duration) -> String {
+    let secs = play_time.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBoy;
+
+    #[test]
+    fn test_slots_save_load_and_undo() {
+        let dir = std::env::temp_dir().join(format!("gamelife_slots_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut slots = SaveSlots::new(dir.join("game.gb"));
+        let mut gameboy = GameBoy::new();
+        gameboy.load_program(0x100, &[0x0C, 0x0C, 0x0C]);
+
+        gameboy.step().unwrap();
+        slots.select(3).unwrap();
+        slots.handle_hotkey(&mut gameboy, Hotkey::QuickSave, Duration::from_secs(90)).unwrap();
+        gameboy.run_steps(2).unwrap();
+        let before_load = gameboy.save_state();
+
+        assert_eq!(slots.handle_hotkey(&mut gameboy, Hotkey::QuickLoad, Duration::from_secs(100)), Ok("已读取槽位 3".to_string()));
+        assert_eq!(gameboy.cycles(), 1);
+        let listing = slots.describe();
+        assert!(slots.load(&mut gameboy, 4, Duration::ZERO).is_err());
+        slots.undo_load(&mut gameboy, Duration::from_secs(110)).unwrap();
+        assert_eq!(gameboy.save_state().machine_bytes(), before_load.machine_bytes());
+        // 再撤销一次回到读档之后
+        slots.run_command(&mut gameboy, "slot undo", Duration::ZERO).unwrap();
+        assert_eq!(gameboy.cycles(), 1);
+        slots.run_command(&mut gameboy, "slot delete 3", Duration::ZERO).unwrap();
+
+        assert!(listing.starts_with("*3  游戏时长 0:01:30 保存于 "), "{}", listing);
+        assert!(slots.list().is_empty());
+        assert!(slots.run_command(&mut gameboy, "slot load 10", Duration::ZERO).is_err());
+        assert_eq!(slots.handle_hotkey(&mut gameboy, Hotkey::PreviousSlot, Duration::ZERO), Ok("当前槽位: 2".to_string()));
+        assert_eq!(format_timestamp(1_700_000_000), "2023-11-14 22:13:20");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
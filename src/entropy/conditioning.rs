@@ -0,0 +1,176 @@
+//! 熵输出调理
+//!
+//! 标准调理路径：先用von Neumann方法去偏（比特对01输出0、10输出1、00和11丢弃），
+//! 再用SHA-256计数器模式作为哈希提取器，按2:1压缩输出。
+//! 与自定义的 `DistributionOptimizer` 二选一，由配置 `entropy_conditioning` 选择。
+
+use crate::config::{keys, Config};
+use crate::util::hash::sha256;
+
+use super::EntropyError;
+
+/// 调理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConditioningMode {
+    /// 自定义的分布优化器
+    #[default]
+    Optimizer,
+    /// von Neumann去偏 + SHA-256提取
+    Standard,
+}
+
+impl ConditioningMode {
+    /// 从名称解析
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "optimizer" => Ok(ConditioningMode::Optimizer),
+            "standard" => Ok(ConditioningMode::Standard),
+            other => Err(format!("未知的调理方式: {} (可用: optimizer, standard)", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConditioningMode::Optimizer => "optimizer",
+            ConditioningMode::Standard => "standard",
+        }
+    }
+
+    /// 读取配置 `entropy_conditioning`，未设置时使用分布优化器
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        config.get(keys::ENTROPY_CONDITIONING).map_or(Ok(Self::default()), |name| Self::parse(name))
+    }
+}
+
+/// von Neumann去偏，比特按高位在前处理，不足一个字节的尾部丢弃
+pub fn von_neumann(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len() / 8);
+    let (mut current, mut bits) = (0u8, 0);
+    for &byte in input {
+        for pair in (0..4).rev() {
+            match (byte >> (pair * 2)) & 0b11 {
+                0b01 => current <<= 1,
+                0b10 => current = (current << 1) | 1,
+                _ => continue,
+            }
+            bits += 1;
+            if bits == 8 {
+                output.push(current);
+                current = 0;
+                bits = 0;
+            }
+        }
+    }
+    output
+}
+
+/// SHA-256计数器模式提取 `length` 字节：第i块为 SHA-256(i || input)
+pub fn hash_extract(input: &[u8], length: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(length + 32);
+    let mut block = Vec::with_capacity(input.len() + 4);
+    let mut counter = 0u32;
+    while output.len() < length {
+        block.clear();
+        block.extend_from_slice(&counter.to_be_bytes());
+        block.extend_from_slice(input);
+        output.extend_from_slice(&sha256(&block));
+        counter += 1;
+    }
+    output.truncate(length);
+    output
+}
+
+/// 标准调理器
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StandardConditioner {
+    /// 去偏后每多少字节输出1字节
+    pub compression: usize,
+}
+
+impl StandardConditioner {
+    pub fn new() -> Self {
+        Self { compression: 2 }
+    }
+
+    /// 去偏并提取，去偏后的数据不足以输出1字节时返回熵不足
+    pub fn condition(&self, raw: &[u8]) -> Result<Vec<u8>, EntropyError> {
+        let debiased = von_neumann(raw);
+        let length = debiased.len() / self.compression.max(1);
+        if length == 0 {
+            return Err(EntropyError::InsufficientEntropy);
+        }
+        Ok(hash_extract(&debiased, length))
+    }
+}
+
+impl Default for StandardConditioner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::statistics::StatisticalReport;
+    use crate::entropy::DistributionOptimizer;
+
+    /// 有偏的确定性输入：每个比特为1的概率约为3/4，且相邻字节相关
+    fn biased_input(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_F491u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        };
+        let mut previous = 0u8;
+        (0..len)
+            .map(|_| {
+                let byte = (next() | next()) ^ (previous & 0x01);
+                previous = byte;
+                byte
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_von_neumann_and_extractor() {
+        // 01 10 00 11 -> 0 1；10 10 01 01 -> 1 1 0 0
+        assert_eq!(von_neumann(&[0b0110_0011, 0b1010_0101, 0x99, 0x99]), vec![0b0111_0010]);
+        assert!(von_neumann(&[0x00, 0xFF]).is_empty());
+        assert_eq!(hash_extract(b"abc", 40)[..32], sha256(b"\0\0\0\0abc"));
+        assert_eq!(hash_extract(b"abc", 40).len(), 40);
+        assert!(matches!(StandardConditioner::new().condition(&[0xFF; 64]), Err(EntropyError::InsufficientEntropy)));
+        assert_eq!(ConditioningMode::parse("standard"), Ok(ConditioningMode::Standard));
+
+        let mut config = Config::new();
+        assert_eq!(ConditioningMode::from_config(&config), Ok(ConditioningMode::Optimizer));
+        config.set(keys::ENTROPY_CONDITIONING, "standard");
+        let manager = crate::entropy::EntropyManager::from_config(&config).unwrap();
+        assert_eq!(manager.conditioning(), ConditioningMode::Standard);
+        config.set(keys::ENTROPY_CONDITIONING, "fancy");
+        assert!(ConditioningMode::from_config(&config).is_err());
+        assert!(crate::entropy::EntropyManager::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_compare_conditioning_paths_on_biased_input() {
+        let raw = biased_input(32 * 1024);
+        let raw_report = StatisticalReport::analyze(&raw);
+        assert!(!raw_report.passes(), "{}", raw_report);
+
+        let standard = StandardConditioner::new().condition(&raw).unwrap();
+        let standard_report = StatisticalReport::analyze(&standard);
+        assert!(standard.len() >= 1024);
+        assert!(standard_report.passes(), "标准调理: {}", standard_report);
+
+        // 两条路径的输出都能通过统计检验，区别在于输出量：
+        // 分布优化器输出与输入等长，标准路径只输出去偏后一半的比特
+        let optimized = DistributionOptimizer::new().optimize_distribution(&raw).unwrap();
+        let optimized_report = StatisticalReport::analyze(&optimized);
+        assert!(optimized_report.passes(), "分布优化器: {}", optimized_report);
+        assert_eq!(optimized.len(), raw.len());
+        assert!(standard.len() < raw.len() / 8);
+    }
+}
@@ -3,6 +3,9 @@
 //! 实现概率空间分布优化算法，确保随机数在概率空间上的均匀分布
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
 
 /// 概率分布优化器
 pub struct DistributionOptimizer {
@@ -24,24 +27,68 @@ impl DistributionOptimizer {
     
     /// 优化概率分布
     pub fn optimize_distribution(&mut self, entropy: &[u8]) -> Result<Vec<u8>, EntropyError> {
-        self.optimization_cycles += 1;
-        
-        // 第一步：分析原始熵的分布
-        let original_stats = self.analyze_distribution(entropy);
+        let optimized = self.transform(entropy);
+        self.finish_cycle(optimized)
+    }
+    
+    /// 分块并行优化
+    ///
+    /// 输入按 `chunk_size` 字节切块，每块独立完成白化、熵增强和分布均衡，
+    /// 再按块的顺序拼接。输出只取决于输入和块大小，与线程数无关；
+    /// 输入不超过一块时与 `optimize_distribution` 的结果相同
+    pub fn optimize_parallel(&mut self, entropy: &[u8], options: ParallelOptions) -> Result<Vec<u8>, EntropyError> {
+        let chunks: Vec<&[u8]> = entropy.chunks(options.chunk_size.max(1)).collect();
+        let threads = options.threads.max(1).min(chunks.len().max(1));
+        let mut results: Vec<Vec<u8>> = vec![Vec::new(); chunks.len()];
         
-        // 第二步：应用多种优化技术
-        let mut optimized = entropy.to_vec();
+        if threads == 1 {
+            for (result, chunk) in results.iter_mut().zip(&chunks) {
+                *result = self.transform(chunk);
+            }
+        } else {
+            let next = AtomicUsize::new(0);
+            let (sender, receiver) = mpsc::channel();
+            let optimizer = &*self;
+            thread::scope(|scope| {
+                for _ in 0..threads {
+                    let sender = sender.clone();
+                    let (next, chunks) = (&next, &chunks);
+                    scope.spawn(move || loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(chunk) = chunks.get(index) else { break };
+                        if sender.send((index, optimizer.transform(chunk))).is_err() {
+                            break;
+                        }
+                    });
+                }
+                drop(sender);
+                
+                // 按块序号归位，保证合并顺序确定
+                for (index, result) in receiver {
+                    results[index] = result;
+                }
+            });
+        }
         
+        self.finish_cycle(results.concat())
+    }
+    
+    /// 白化、熵增强、分布均衡三步变换
+    fn transform(&self, entropy: &[u8]) -> Vec<u8> {
         // 1. 白化处理（去除相关性）
-        optimized = self.apply_whitening(&optimized);
+        let optimized = self.apply_whitening(entropy);
         
         // 2. 熵增强
-        optimized = self.enhance_entropy(&optimized);
+        let optimized = self.enhance_entropy(&optimized);
         
         // 3. 分布均衡
-        optimized = self.balance_distribution(&optimized);
+        self.balance_distribution(&optimized)
+    }
+    
+    /// 质量验证并更新统计
+    fn finish_cycle(&mut self, optimized: Vec<u8>) -> Result<Vec<u8>, EntropyError> {
+        self.optimization_cycles += 1;
         
-        // 4. 质量验证
         let final_stats = self.analyze_distribution(&optimized);
         self.distribution_quality = self.calculate_quality_score(&final_stats);
         self.entropy_density = self.calculate_entropy_density(&optimized);
@@ -231,6 +278,107 @@ impl DistributionOptimizer {
     }
 }
 
+/// 分块并行优化的参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelOptions {
+    /// 每块的字节数
+    pub chunk_size: usize,
+    /// 工作线程数
+    pub threads: usize,
+}
+
+impl Default for ParallelOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 64 * 1024,
+            threads: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        }
+    }
+}
+
+/// 流式优化器
+///
+/// 熵数据到达时先缓存，凑满一块就立即优化输出。块的切分方式与
+/// `optimize_parallel` 相同，所以把所有输出连起来与一次性并行优化的结果一致
+pub struct OptimizerStream {
+    optimizer: DistributionOptimizer,
+    chunk_size: usize,
+    pending: Vec<u8>,
+    bytes_in: usize,
+    bytes_out: usize,
+}
+
+impl OptimizerStream {
+    pub fn new(chunk_size: usize) -> Self {
+        let chunk_size = chunk_size.max(1);
+        Self {
+            optimizer: DistributionOptimizer::new(),
+            chunk_size,
+            pending: Vec::with_capacity(chunk_size),
+            bytes_in: 0,
+            bytes_out: 0,
+        }
+    }
+    
+    /// 追加熵数据，返回本次凑满的块的优化结果（可能为空）
+    pub fn push(&mut self, entropy: &[u8]) -> Result<Vec<u8>, EntropyError> {
+        self.bytes_in += entropy.len();
+        let mut output = Vec::new();
+        let mut rest = entropy;
+        while !rest.is_empty() {
+            let take = (self.chunk_size - self.pending.len()).min(rest.len());
+            self.pending.extend_from_slice(&rest[..take]);
+            rest = &rest[take..];
+            if self.pending.len() == self.chunk_size {
+                output.extend(self.optimizer.transform(&self.pending));
+                self.pending.clear();
+            }
+        }
+        self.emit(output)
+    }
+    
+    /// 从收集器的通道持续读取，直到发送端关闭；每次有输出时调用 `on_output`
+    pub fn consume(&mut self, receiver: &Receiver<Vec<u8>>, mut on_output: impl FnMut(&[u8])) -> Result<(), EntropyError> {
+        for entropy in receiver {
+            let output = self.push(&entropy)?;
+            if !output.is_empty() {
+                on_output(&output);
+            }
+        }
+        Ok(())
+    }
+    
+    /// 优化缓存中不足一块的剩余数据
+    pub fn finish(&mut self) -> Result<Vec<u8>, EntropyError> {
+        let pending = std::mem::take(&mut self.pending);
+        let output = self.optimizer.transform(&pending);
+        self.emit(output)
+    }
+    
+    /// 尚未凑满一块的缓存字节数
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+    
+    /// 累计输入与输出的字节数
+    pub fn totals(&self) -> (usize, usize) {
+        (self.bytes_in, self.bytes_out)
+    }
+    
+    pub fn stats(&self) -> OptimizerStats {
+        self.optimizer.get_stats()
+    }
+    
+    fn emit(&mut self, output: Vec<u8>) -> Result<Vec<u8>, EntropyError> {
+        if output.is_empty() {
+            return Ok(output);
+        }
+        let output = self.optimizer.finish_cycle(output)?;
+        self.bytes_out += output.len();
+        Ok(output)
+    }
+}
+
 /// 分布统计信息
 #[derive(Debug, Clone)]
 struct DistributionStats {
@@ -244,6 +392,7 @@ struct DistributionStats {
 
 /// 优化器统计信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OptimizerStats {
     pub distribution_quality: f64,
     pub optimization_cycles: u32,
@@ -316,3 +465,79 @@ impl ProbabilitySpace {
 
 // 导入错误类型
 use crate::entropy::EntropyError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::entropy_source::{EntropyCollector, SystemTimeEntropy};
+
+    fn sample(len: usize) -> Vec<u8> {
+        let mut state = 0x9E37_79B9u32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state >> 8) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parallel_and_stream_outputs_are_deterministic() {
+        let input = sample(100_000);
+        let options = |threads| ParallelOptions { chunk_size: 16 * 1024, threads };
+        let single = DistributionOptimizer::new().optimize_parallel(&input, options(1)).unwrap();
+        let parallel = DistributionOptimizer::new().optimize_parallel(&input, options(4)).unwrap();
+        assert_eq!(single, parallel);
+        assert_eq!(parallel, DistributionOptimizer::new().optimize_parallel(&input, options(3)).unwrap());
+
+        // 不足一块时与串行优化一致
+        let small = &input[..8 * 1024];
+        assert_eq!(
+            DistributionOptimizer::new().optimize_parallel(small, options(4)).unwrap(),
+            DistributionOptimizer::new().optimize_distribution(small).unwrap()
+        );
+
+        // 按不规则的大小分批到达，拼接后的输出与一次性并行优化相同
+        let mut stream = OptimizerStream::new(16 * 1024);
+        let mut streamed = Vec::new();
+        for piece in input.chunks(7_000) {
+            streamed.extend(stream.push(piece).unwrap());
+        }
+        assert_eq!(stream.pending(), 100_000 % (16 * 1024));
+        streamed.extend(stream.finish().unwrap());
+        assert_eq!(streamed, parallel);
+        assert_eq!(stream.totals(), (input.len(), parallel.len()));
+    }
+
+    #[test]
+    fn test_stream_consumes_background_collector() {
+        let mut collector = EntropyCollector::new();
+        collector.add_source(Box::new(SystemTimeEntropy::new()));
+        let (receiver, handle) = collector.spawn(5);
+        let rounds: Vec<Vec<u8>> = receiver.iter().collect();
+        handle.join().unwrap();
+        assert_eq!(rounds.len(), 5);
+        assert!(rounds.iter().all(|round| round.len() == 28));
+
+        // 通道另一端的数据分批到达，输出与一次性并行优化相同
+        let input = sample(40_000);
+        let (sender, receiver) = mpsc::channel();
+        let producer = thread::spawn(move || {
+            for piece in input.chunks(3_000) {
+                sender.send(piece.to_vec()).unwrap();
+            }
+        });
+        let mut stream = OptimizerStream::new(8 * 1024);
+        let mut streamed = Vec::new();
+        stream.consume(&receiver, |output| streamed.extend_from_slice(output)).unwrap();
+        producer.join().unwrap();
+        streamed.extend(stream.finish().unwrap());
+
+        let expected = DistributionOptimizer::new()
+            .optimize_parallel(&sample(40_000), ParallelOptions { chunk_size: 8 * 1024, threads: 2 })
+            .unwrap();
+        assert_eq!(streamed, expected);
+    }
+}
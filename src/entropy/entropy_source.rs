@@ -4,7 +4,9 @@
 
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::process;
+use std::sync::mpsc::{self, Receiver};
 use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
 use std::collections::VecDeque;
 
 /// 熵源特征
@@ -79,6 +81,22 @@ impl EntropyCollector {
         
         Ok(total_entropy)
     }
+    
+    /// 在后台线程中收集 `rounds` 轮，每轮的结果通过通道送出
+    ///
+    /// 某一轮没有收集到熵时跳过该轮；接收端关闭后提前停止
+    pub fn spawn(mut self, rounds: usize) -> (Receiver<Vec<u8>>, JoinHandle<()>) {
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            for _ in 0..rounds {
+                let Ok(entropy) = self.collect_all() else { continue };
+                if sender.send(entropy).is_err() {
+                    break;
+                }
+            }
+        });
+        (receiver, handle)
+    }
 }
 
 /// 系统时间熵源
@@ -7,16 +7,28 @@ pub mod entropy_source;
 pub mod distribution_optimizer;
 pub mod quantum_resistant;
 pub mod entropy_pool;
+pub mod conditioning;
+pub mod statistics;
+pub mod rom_prng;
 
 pub use entropy_source::{EntropySource, EntropySourceType, EntropyCollector};
-pub use distribution_optimizer::{DistributionOptimizer, ProbabilitySpace};
-pub use quantum_resistant::{QuantumResistantRNG, PostQuantumEntropy};
+pub use distribution_optimizer::{DistributionOptimizer, OptimizerStream, ParallelOptions, ProbabilitySpace};
+pub use quantum_resistant::{QuantumResistantRNG, PostQuantumEntropy, StageConfig, TransformStage};
 pub use entropy_pool::{EntropyPool, PooledEntropy};
+pub use conditioning::{ConditioningMode, StandardConditioner};
+pub use statistics::StatisticalReport;
+pub use rom_prng::{cross_check, CrossCheck, HostPrng, PrngError};
+
+use crate::config::Config;
+use crate::i18n::{tr, trf, Msg};
+use crate::util::alloc::{self, Subsystem};
 
 /// 主熵源管理器
 pub struct EntropyManager {
     sources: Vec<Box<dyn EntropySource>>,
     optimizer: DistributionOptimizer,
+    conditioning: ConditioningMode,
+    conditioner: StandardConditioner,
     pool: EntropyPool,
     quantum_rng: QuantumResistantRNG,
 }
@@ -36,13 +48,24 @@ impl EntropyManager {
         Self {
             sources,
             optimizer: DistributionOptimizer::new(),
+            conditioning: ConditioningMode::default(),
+            conditioner: StandardConditioner::new(),
             pool: EntropyPool::new(),
             quantum_rng: QuantumResistantRNG::new(),
         }
     }
     
+    /// 按配置创建熵源管理器，读取 `quantum_stage_*` 阶段开关和 `entropy_conditioning` 调理方式
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let mut manager = Self::new();
+        manager.set_quantum_stages(StageConfig::from_config(config)?);
+        manager.set_conditioning(ConditioningMode::from_config(config)?);
+        Ok(manager)
+    }
+    
     /// 收集熵并优化分布
     pub fn collect_and_optimize(&mut self) -> Result<Vec<u8>, EntropyError> {
+        let _scope = alloc::enter(Subsystem::Entropy);
         let mut collected_entropy = Vec::new();
         
         // 从所有源收集熵
@@ -56,14 +79,16 @@ impl EntropyManager {
         // 将熵添加到池中
         self.pool.add_entropy(&collected_entropy);
         
-        // 优化概率分布
-        let optimized = self.optimizer.optimize_distribution(&collected_entropy)?;
-        
-        Ok(optimized)
+        // 按配置的方式调理输出
+        match self.conditioning {
+            ConditioningMode::Optimizer => self.optimizer.optimize_distribution(&collected_entropy),
+            ConditioningMode::Standard => self.conditioner.condition(&collected_entropy),
+        }
     }
     
     /// 生成高质量随机数
     pub fn generate_random(&mut self, size: usize) -> Result<Vec<u8>, EntropyError> {
+        let _scope = alloc::enter(Subsystem::Entropy);
         // 首先收集和优化熵
         let _ = self.collect_and_optimize();
         
@@ -76,6 +101,26 @@ impl EntropyManager {
         Ok(quantum_processed)
     }
     
+    /// 设置量子抗性流水线启用的阶段
+    pub fn set_quantum_stages(&mut self, stages: StageConfig) {
+        self.quantum_rng.set_stages(stages);
+    }
+    
+    /// 量子抗性流水线启用的阶段
+    pub fn quantum_stages(&self) -> StageConfig {
+        self.quantum_rng.stages()
+    }
+    
+    /// 设置熵输出的调理方式
+    pub fn set_conditioning(&mut self, mode: ConditioningMode) {
+        self.conditioning = mode;
+    }
+    
+    /// 熵输出的调理方式
+    pub fn conditioning(&self) -> ConditioningMode {
+        self.conditioning
+    }
+    
     /// 获取熵源统计信息
     pub fn get_entropy_stats(&self) -> EntropyStats {
         EntropyStats {
@@ -99,10 +144,10 @@ pub enum EntropyError {
 impl std::fmt::Display for EntropyError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            EntropyError::SourceUnavailable(msg) => write!(f, "熵源不可用: {}", msg),
-            EntropyError::InsufficientEntropy => write!(f, "熵不足"),
-            EntropyError::DistributionError(msg) => write!(f, "分布错误: {}", msg),
-            EntropyError::QuantumProcessingError(msg) => write!(f, "量子处理错误: {}", msg),
+            EntropyError::SourceUnavailable(msg) => write!(f, "{}", trf(Msg::EntropySourceUnavailable, &[msg])),
+            EntropyError::InsufficientEntropy => write!(f, "{}", tr(Msg::EntropyInsufficient)),
+            EntropyError::DistributionError(msg) => write!(f, "{}", trf(Msg::EntropyDistribution, &[msg])),
+            EntropyError::QuantumProcessingError(msg) => write!(f, "{}", trf(Msg::EntropyQuantum, &[msg])),
         }
     }
 }
@@ -111,6 +156,7 @@ impl std::error::Error for EntropyError {}
 
 /// 熵源统计信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntropyStats {
     pub source_count: usize,
     pub pool_size: usize,
@@ -1,13 +1,162 @@
 //! 量子抗性随机数生成器模块
 //! 
 //! 实现后量子密码学安全的随机数生成，抵御量子计算攻击
+//!
+//! 处理流水线由五个自定义变换组成，名称取自对应的密码学方案，但都只是
+//! 借用其结构的简单位运算，不提供这些方案的安全性。每个阶段可以单独关闭，
+//! `TransformStage::description` 说明了各阶段实际做了什么。
 
+use std::fmt;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::config::{keys, Config};
+
+/// 流水线中的变换阶段，按执行顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransformStage {
+    StateMixing,
+    PostQuantumHash,
+    Lattice,
+    Multivariate,
+    Homomorphic,
+}
+
+impl TransformStage {
+    /// 全部阶段，按执行顺序
+    pub const ALL: [TransformStage; 5] = [
+        TransformStage::StateMixing,
+        TransformStage::PostQuantumHash,
+        TransformStage::Lattice,
+        TransformStage::Multivariate,
+        TransformStage::Homomorphic,
+    ];
+
+    /// 配置键中使用的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransformStage::StateMixing => "state_mixing",
+            TransformStage::PostQuantumHash => "post_quantum_hash",
+            TransformStage::Lattice => "lattice",
+            TransformStage::Multivariate => "multivariate",
+            TransformStage::Homomorphic => "homomorphic",
+        }
+    }
+
+    /// 显示名称
+    pub fn title(&self) -> &'static str {
+        match self {
+            TransformStage::StateMixing => "量子态混合",
+            TransformStage::PostQuantumHash => "后量子哈希",
+            TransformStage::Lattice => "格基增强",
+            TransformStage::Multivariate => "多变量处理",
+            TransformStage::Homomorphic => "同态变换",
+        }
+    }
+
+    /// 阶段实际执行的运算
+    pub fn description(&self) -> &'static str {
+        match self {
+            TransformStage::StateMixing => {
+                "每个字节按内部计数器做8步 3x+1/右移 游走取低8位；相邻两字节都替换为二者的异或，\
+                 长度不小于4时再整体异或全部字节的异或值。长度不变"
+            }
+            TransformStage::PostQuantumHash => {
+                "每32字节一组，以种子派生的4个64位状态做加法、乘0x9E3779B9、循环左移13位累积，\
+                 输出4个状态共32字节。不足32字节的组也输出32字节，状态不随调用更新"
+            }
+            TransformStage::Lattice => {
+                "每16字节一组（不足补零），第i个输出字节为各输入字节乘以 (i*j mod 16) 的异或和。\
+                 输出按16字节对齐，第0个字节恒为0"
+            }
+            TransformStage::Multivariate => {
+                "每8字节一组（不足补零），计算所有 j<=k 的 x_j*x_k 的异或和，\
+                 组内8个输出字节都等于这个值"
+            }
+            TransformStage::Homomorphic => {
+                "每4字节一组（不足补零），第i个输出字节为 (x_j + i*j) 的异或和。输出按4字节对齐"
+            }
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 各阶段的开关，默认全部启用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageConfig {
+    enabled: [bool; 5],
+}
+
+impl StageConfig {
+    /// 启用全部阶段
+    pub fn all() -> Self {
+        Self { enabled: [true; 5] }
+    }
+
+    /// 关闭全部阶段，输出等于输入
+    pub fn none() -> Self {
+        Self { enabled: [false; 5] }
+    }
+
+    /// 设置某一阶段的开关
+    pub fn with(mut self, stage: TransformStage, enabled: bool) -> Self {
+        self.enabled[stage.index()] = enabled;
+        self
+    }
+
+    pub fn is_enabled(&self, stage: TransformStage) -> bool {
+        self.enabled[stage.index()]
+    }
+
+    /// 启用的阶段，按执行顺序
+    pub fn enabled_stages(&self) -> Vec<TransformStage> {
+        TransformStage::ALL.into_iter().filter(|&stage| self.is_enabled(stage)).collect()
+    }
+
+    /// 读取 `quantum_stage_<阶段名> = true|false`，未设置的阶段保持启用
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let mut stages = Self::all();
+        for stage in TransformStage::ALL {
+            let key = format!("{}{}", keys::QUANTUM_STAGE_PREFIX, stage.name());
+            if let Some(value) = config.get(&key) {
+                let enabled = value.parse().map_err(|_| format!("配置 {} 的值无效: {}", key, value))?;
+                stages = stages.with(stage, enabled);
+            }
+        }
+        Ok(stages)
+    }
+}
+
+impl Default for StageConfig {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl fmt::Display for StageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, stage) in TransformStage::ALL.iter().enumerate() {
+            writeln!(
+                f,
+                "{}. {} ({}) [{}]\n   {}",
+                i + 1,
+                stage.title(),
+                stage.name(),
+                if self.is_enabled(*stage) { "启用" } else { "关闭" },
+                stage.description()
+            )?;
+        }
+        Ok(())
+    }
+}
+
 /// 量子抗性随机数生成器
 pub struct QuantumResistantRNG {
     state: [u64; 4],
     counter: u64,
+    stages: StageConfig,
     processing_time_ns: u64,
     entropy_amplification: f64,
 }
@@ -18,33 +167,53 @@ impl QuantumResistantRNG {
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_nanos() as u64;
-        
+        Self::with_seed(seed)
+    }
+
+    /// 使用固定种子创建，相同种子和输入得到相同输出
+    pub fn with_seed(seed: u64) -> Self {
         Self {
             state: [seed, seed.wrapping_mul(0x9E3779B9), seed.wrapping_mul(0x85EBCA6B), seed.wrapping_mul(0xC2B2AE35)],
             counter: 0,
+            stages: StageConfig::all(),
             processing_time_ns: 0,
             entropy_amplification: 1.0,
         }
     }
+
+    /// 设置启用的阶段
+    pub fn with_stages(mut self, stages: StageConfig) -> Self {
+        self.stages = stages;
+        self
+    }
+
+    pub fn set_stages(&mut self, stages: StageConfig) {
+        self.stages = stages;
+    }
+
+    pub fn stages(&self) -> StageConfig {
+        self.stages
+    }
+
+    /// 启用的阶段，按执行顺序
+    pub fn enabled_stages(&self) -> Vec<TransformStage> {
+        self.stages.enabled_stages()
+    }
     
     /// 处理熵数据，增强其量子抗性
     pub fn process_entropy(&mut self, entropy: &[u8]) -> Result<Vec<u8>, EntropyError> {
         let start_time = SystemTime::now();
         
-        // 第一步：量子态混合
-        let quantum_mixed = self.quantum_state_mixing(entropy);
-        
-        // 第二步：后量子哈希
-        let post_quantum_hashed = self.post_quantum_hash(&quantum_mixed);
-        
-        // 第三步：格基密码学增强
-        let lattice_enhanced = self.lattice_enhancement(&post_quantum_hashed);
-        
-        // 第四步：多变量密码学处理
-        let multivariate_processed = self.multivariate_processing(&lattice_enhanced);
-        
-        // 第五步：同态加密变换
-        let homomorphic_transformed = self.homomorphic_transform(&multivariate_processed);
+        let mut data = entropy.to_vec();
+        for stage in self.stages.enabled_stages() {
+            data = match stage {
+                TransformStage::StateMixing => self.quantum_state_mixing(&data),
+                TransformStage::PostQuantumHash => self.post_quantum_hash(&data),
+                TransformStage::Lattice => self.lattice_enhancement(&data),
+                TransformStage::Multivariate => self.multivariate_processing(&data),
+                TransformStage::Homomorphic => self.homomorphic_transform(&data),
+            };
+        }
         
         // 计算处理时间
         let end_time = SystemTime::now();
@@ -53,9 +222,9 @@ impl QuantumResistantRNG {
             .as_nanos() as u64;
         
         // 更新熵放大系数
-        self.entropy_amplification = self.calculate_entropy_amplification(&homomorphic_transformed);
+        self.entropy_amplification = self.calculate_entropy_amplification(&data);
         
-        Ok(homomorphic_transformed)
+        Ok(data)
     }
     
     /// 量子态混合
@@ -383,6 +552,7 @@ impl QuantumState {
 
 /// 量子统计信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuantumStats {
     pub post_quantum_strength: f64,
     pub processing_time_ns: u64,
@@ -391,3 +561,69 @@ pub struct QuantumStats {
 
 // 导入错误类型
 use crate::entropy::EntropyError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::hash::to_hex;
+
+    const INPUT: [u8; 20] = *b"known answer vector!";
+
+    fn process(stages: StageConfig) -> Vec<u8> {
+        QuantumResistantRNG::with_seed(1).with_stages(stages).process_entropy(&INPUT).unwrap()
+    }
+
+    #[test]
+    fn test_known_answers_per_stage() {
+        let only = |stage| process(StageConfig::none().with(stage, true));
+        assert_eq!(process(StageConfig::none()), INPUT.to_vec());
+        assert_eq!(to_hex(&only(TransformStage::StateMixing)), "c8c84d4dbbbbbfbf7474fefec0c0ffff7676a6a6");
+        assert_eq!(to_hex(&only(TransformStage::PostQuantumHash)), "fdee154f994cc5d3c5a52d482082b837c932b0d8877362a3a7f9f7751b64cb35");
+        assert_eq!(to_hex(&only(TransformStage::Lattice)), "001f8eed0cbb9a79388756c5e493a2c100e8d0c8a0b080907068504820300010");
+        assert_eq!(to_hex(&only(TransformStage::Multivariate)), "9e9e9e9e9e9e9e9e8989898989898989b7b7b7b7b7b7b7b7");
+        assert_eq!(to_hex(&only(TransformStage::Homomorphic)), "1d0f15ef415d5d5d13191b195056585e48545454");
+        // 多变量阶段使每组8字节相同，同态阶段再把相同的字节两两异或抵消，所以完整流水线的输出大多为0
+        assert_eq!(to_hex(&process(StageConfig::all())), "0000000c0000000c0000000c0000000c00000004000000040000000400000004");
+
+        // 同一个生成器的计数器会前进，第二次处理的混合结果不同
+        let mut rng = QuantumResistantRNG::with_seed(1).with_stages(StageConfig::none().with(TransformStage::StateMixing, true));
+        assert_ne!(rng.process_entropy(&INPUT).unwrap(), rng.process_entropy(&INPUT).unwrap());
+    }
+
+    #[test]
+    fn test_stage_config_and_documentation() {
+        let mut config = Config::new();
+        config.set("quantum_stage_lattice", "false");
+        config.set("quantum_stage_homomorphic", "false");
+        let stages = StageConfig::from_config(&config).unwrap();
+        assert_eq!(
+            stages.enabled_stages(),
+            vec![TransformStage::StateMixing, TransformStage::PostQuantumHash, TransformStage::Multivariate]
+        );
+        let rng = QuantumResistantRNG::with_seed(1).with_stages(stages);
+        assert_eq!(rng.enabled_stages().len(), 3);
+        assert!(stages.to_string().contains("3. 格基增强 (lattice) [关闭]"));
+
+        config.set("quantum_stage_lattice", "maybe");
+        assert!(StageConfig::from_config(&config).is_err());
+        assert!(crate::entropy::EntropyManager::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_manager_applies_configured_stages() {
+        use crate::entropy::EntropyManager;
+
+        // 后量子哈希把任意长度的输入压缩成32字节，关闭后输出长度等于请求长度
+        let mut manager = EntropyManager::from_config(&Config::new()).unwrap();
+        assert_eq!(manager.quantum_stages(), StageConfig::all());
+        assert_eq!(manager.generate_random(20).unwrap().len(), 32);
+
+        let mut config = Config::new();
+        for stage in TransformStage::ALL {
+            config.set(&format!("quantum_stage_{}", stage.name()), "false");
+        }
+        let mut manager = EntropyManager::from_config(&config).unwrap();
+        assert_eq!(manager.quantum_stages(), StageConfig::none());
+        assert_eq!(manager.generate_random(20).unwrap().len(), 20);
+    }
+}
@@ -0,0 +1,290 @@
+//! 模拟CPU上的伪随机数发生器
+//!
+//! 生成一个ROM，在模拟的CPU上循环运行一个简单的发生器：BC是16位Weyl计数器，
+//! 每轮 `INC BC` 后把C、B、C依次加到A上，A即输出字节。宿主端按同一指令序列
+//! 实现参考模型，逐条指令比较寄存器、标志、PC和机器周期。
+//! 这既是端到端的CPU正确性测试，也展示了ROM生成器、CPU核心和统计检验的配合。
+//! 发生器只用于演示，输出不是密码学安全的随机数。
+
+use std::fmt;
+
+use crate::cpu::reference::ExecutionCore;
+use crate::cpu::{FlagsRegister, Registers};
+use crate::emulator::{SaveMetadata, SaveState};
+use crate::i18n::{trf, Msg};
+use crate::memory::MemoryBus;
+use crate::rom::{RomError, RomGenerator, HEADER_START, PROGRAM_START};
+
+/// 循环体的起始地址
+pub const LOOP_START: u16 = 0x0200;
+
+/// 循环体：INC BC; ADD A,C; ADD A,B; ADD A,C; JP 0x0200
+pub const LOOP_PROGRAM: [u8; 7] = [0x03, 0x81, 0x80, 0x81, 0xC3, 0x00, 0x02];
+
+/// 0x150处的程序直接跳到循环体：JP 0x0200
+const ENTRY_PROGRAM: [u8; 3] = [0xC3, 0x00, 0x02];
+
+/// 从入口到循环体最多执行的指令数：NOP; JP 0x150; JP 0x200
+const ENTRY_LIMIT: usize = 3;
+
+/// 宿主端的循环体，每项为助记符、机器周期数和执行效果，下标即相对 `LOOP_START` 的偏移
+const INSTRUCTIONS: [(&str, u64, fn(&mut HostPrng)); 5] = [
+    ("INC BC", 2, |prng| prng.bc = prng.bc.wrapping_add(1)),
+    ("ADD A,C", 1, |prng| prng.add(prng.bc as u8)),
+    ("ADD A,B", 1, |prng| prng.add((prng.bc >> 8) as u8)),
+    ("ADD A,C", 1, |prng| prng.add(prng.bc as u8)),
+    ("JP 0200", 4, |_| {}),
+];
+
+/// 生成发生器ROM
+pub fn prng_rom() -> Result<Vec<u8>, RomError> {
+    RomGenerator::new("ROM PRNG")
+        .program(PROGRAM_START, &ENTRY_PROGRAM)
+        .program(LOOP_START, &LOOP_PROGRAM)
+        .build()
+}
+
+/// 宿主端参考模型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostPrng {
+    pub a: u8,
+    pub bc: u16,
+    pub flags: u8,
+    /// 进入循环体后的机器周期数
+    pub cycles: u64,
+    /// 下一条指令在 `INSTRUCTIONS` 中的下标
+    index: usize,
+}
+
+impl HostPrng {
+    /// 种子放入BC，A和标志清零，停在循环体开头
+    pub fn new(seed: u16) -> Self {
+        Self { a: 0, bc: seed, flags: 0, cycles: 0, index: 0 }
+    }
+
+    /// 下一条指令的地址
+    pub fn pc(&self) -> u16 {
+        LOOP_START + self.index as u16
+    }
+
+    /// 执行一条指令，返回其助记符
+    pub fn step(&mut self) -> &'static str {
+        let (mnemonic, cycles, execute) = INSTRUCTIONS[self.index];
+        execute(self);
+        self.cycles += cycles;
+        self.index = (self.index + 1) % INSTRUCTIONS.len();
+        mnemonic
+    }
+
+    /// 执行完一轮循环，返回输出字节
+    pub fn next_byte(&mut self) -> u8 {
+        loop {
+            self.step();
+            if self.index == 0 {
+                return self.a;
+            }
+        }
+    }
+
+    /// 连续生成 `count` 个字节
+    pub fn fill(&mut self, count: usize) -> Vec<u8> {
+        (0..count).map(|_| self.next_byte()).collect()
+    }
+
+    /// 与CPU一致的 ADD A,r：Z和H、C按结果设置，N清零
+    fn add(&mut self, value: u8) {
+        let (result, carry) = self.a.overflowing_add(value);
+        let half_carry = (self.a & 0x0F) + (value & 0x0F) > 0x0F;
+        self.a = result;
+        self.flags = u8::from(FlagsRegister { zero: result == 0, subtract: false, half_carry, carry });
+    }
+
+    /// 比较用的状态，`cycles` 为 `None` 时不比较周期
+    fn observe(&self, timed: bool) -> PrngState {
+        PrngState {
+            pc: self.pc(),
+            a: self.a,
+            bc: self.bc,
+            flags: self.flags,
+            cycles: timed.then_some(self.cycles),
+        }
+    }
+}
+
+/// 参与比较的CPU状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrngState {
+    pub pc: u16,
+    pub a: u8,
+    pub bc: u16,
+    pub flags: u8,
+    /// 进入循环体后的机器周期数，核心不计周期时为 `None`
+    pub cycles: Option<u64>,
+}
+
+impl PrngState {
+    /// 从核心的快照读取，周期相对进入循环体时的 `base`
+    fn capture<C: ExecutionCore>(core: &C, base: u64) -> Self {
+        let state = core.capture();
+        PrngState {
+            pc: state.pc,
+            a: state.registers.a,
+            bc: state.registers.get_bc(),
+            flags: u8::from(state.flags),
+            cycles: core.counts_cycles().then(|| state.cycle - base),
+        }
+    }
+}
+
+impl fmt::Display for PrngState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PC={:04X} A={:02X} BC={:04X} F={:02X}", self.pc, self.a, self.bc, self.flags)?;
+        if let Some(cycles) = self.cycles {
+            write!(f, " M={}", cycles)?;
+        }
+        Ok(())
+    }
+}
+
+/// 交叉验证失败
+#[derive(Debug)]
+pub enum PrngError {
+    /// 生成ROM失败
+    Rom(RomError),
+    /// 核心执行出错，`instruction` 从1开始计数
+    Core { instruction: u64, message: String },
+    /// 入口程序没有跳到循环体或改动了寄存器
+    Entry { state: PrngState },
+    /// 执行 `mnemonic` 后核心与参考模型不一致
+    Mismatch { instruction: u64, mnemonic: &'static str, expected: PrngState, actual: PrngState },
+}
+
+impl fmt::Display for PrngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrngError::Rom(error) => write!(f, "{}", error),
+            PrngError::Core { instruction, message } => {
+                write!(f, "{}", trf(Msg::RomPrngCore, &[instruction, message]))
+            }
+            PrngError::Entry { state } => {
+                write!(f, "{}", trf(Msg::RomPrngEntry, &[&format!("{:04X}", LOOP_START), state]))
+            }
+            PrngError::Mismatch { instruction, mnemonic, expected, actual } => {
+                write!(f, "{}", trf(Msg::RomPrngMismatch, &[instruction, mnemonic, expected, actual]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PrngError {}
+
+impl From<RomError> for PrngError {
+    fn from(error: RomError) -> Self {
+        PrngError::Rom(error)
+    }
+}
+
+/// 交叉验证结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossCheck {
+    /// 核心产生的输出字节
+    pub bytes: Vec<u8>,
+    /// 循环体内执行的指令数
+    pub instructions: u64,
+    /// 循环体内的机器周期数，核心不计周期时为 `None`
+    pub cycles: Option<u64>,
+}
+
+/// 在核心 `C` 上运行发生器ROM生成 `count` 个字节，每条指令后与参考模型比较
+///
+/// 核心从0x100入口开始执行，入口程序的周期不计入，到达 `LOOP_START` 后开始比较。
+pub fn cross_check<C: ExecutionCore>(seed: u16, count: usize) -> Result<CrossCheck, PrngError> {
+    let mut bus = MemoryBus::new();
+    bus.load_program(0x0000, &prng_rom()?);
+    let mut registers = Registers::new();
+    registers.set_bc(seed);
+    let mut core = C::restore(&SaveState {
+        cycle: 0,
+        pc: HEADER_START,
+        sp: 0xFFFE,
+        registers,
+        flags: FlagsRegister::new(),
+        memory: bus.memory().to_vec(),
+        metadata: SaveMetadata::default(),
+    });
+
+    let mut executed = 0u64;
+    let mut step = |core: &mut C| {
+        executed += 1;
+        core.step_instruction().map_err(|message| PrngError::Core { instruction: executed, message })
+    };
+    for _ in 0..ENTRY_LIMIT {
+        if core.capture().pc == LOOP_START {
+            break;
+        }
+        step(&mut core)?;
+    }
+
+    let timed = core.counts_cycles();
+    let base = core.capture().cycle;
+    let mut host = HostPrng::new(seed);
+    let state = PrngState::capture(&core, base);
+    if state != host.observe(timed) {
+        return Err(PrngError::Entry { state });
+    }
+
+    let mut bytes = Vec::with_capacity(count);
+    let mut instructions = 0u64;
+    while bytes.len() < count {
+        step(&mut core)?;
+        let mnemonic = host.step();
+        instructions += 1;
+        let (expected, actual) = (host.observe(timed), PrngState::capture(&core, base));
+        if expected != actual {
+            return Err(PrngError::Mismatch { instruction: instructions, mnemonic, expected, actual });
+        }
+        if host.index == 0 {
+            bytes.push(actual.a);
+        }
+    }
+    Ok(CrossCheck { bytes, instructions, cycles: timed.then_some(host.cycles) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{OptimizedCPU, ReferenceCPU};
+
+    #[test]
+    fn test_cores_match_host_reference() {
+        let expected = HostPrng::new(0x1234).fill(300);
+        assert_eq!(expected[..4], [0x7C, 0xFA, 0x7A, 0xFC]);
+
+        let optimized = cross_check::<OptimizedCPU>(0x1234, 300).unwrap();
+        assert_eq!(optimized.bytes, expected);
+        assert_eq!(optimized.instructions, 300 * 5);
+        assert_eq!(optimized.cycles, Some(300 * 9));
+
+        let reference = cross_check::<ReferenceCPU>(0x1234, 300).unwrap();
+        assert_eq!(reference.bytes, expected);
+        assert_eq!(reference.cycles, Some(300 * 9));
+    }
+
+    #[test]
+    fn test_host_model_tracks_flags_and_wraps() {
+        let mut prng = HostPrng::new(0xFFFF);
+        assert_eq!(prng.step(), "INC BC");
+        assert_eq!((prng.bc, prng.pc(), prng.cycles), (0x0000, LOOP_START + 1, 2));
+        prng.step();
+        assert_eq!(FlagsRegister::from(prng.flags), FlagsRegister { zero: true, subtract: false, half_carry: false, carry: false });
+
+        prng.a = 0xF8;
+        prng.bc = 0x0808;
+        prng.step();
+        assert_eq!(prng.a, 0x00);
+        assert_eq!(FlagsRegister::from(prng.flags), FlagsRegister { zero: true, subtract: false, half_carry: true, carry: true });
+        prng.step();
+        assert_eq!(prng.step(), "JP 0200");
+        assert_eq!((prng.pc(), prng.cycles), (LOOP_START, 9));
+    }
+}
@@ -0,0 +1,182 @@
+//! 随机性统计检验
+//!
+//! 几个常用的快速检验：比特频率、游程、字节卡方、香农熵、相邻字节的序列相关。
+//! 用于比较不同调理路径的输出，不能代替完整的检验套件。
+
+use std::fmt;
+
+/// 一组数据的统计结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatisticalReport {
+    pub bytes: usize,
+    /// 1比特所占比例，理想值0.5
+    pub ones_ratio: f64,
+    /// 比特游程数与期望值的相对偏差
+    pub runs_deviation: f64,
+    /// 字节分布的卡方统计量，自由度255
+    pub chi_square: f64,
+    /// 每字节的香农熵，最大8
+    pub shannon_entropy: f64,
+    /// 相邻字节的序列相关系数，理想值0
+    pub serial_correlation: f64,
+}
+
+impl StatisticalReport {
+    /// 分析数据
+    pub fn analyze(data: &[u8]) -> Self {
+        Self {
+            bytes: data.len(),
+            ones_ratio: ones_ratio(data),
+            runs_deviation: runs_deviation(data),
+            chi_square: chi_square(data),
+            shannon_entropy: shannon_entropy(data),
+            serial_correlation: serial_correlation(data),
+        }
+    }
+
+    /// 是否全部通过检验
+    ///
+    /// 阈值按至少1KB的样本设定：卡方上限约为自由度255时p=0.001的临界值
+    pub fn passes(&self) -> bool {
+        let bits = (self.bytes * 8) as f64;
+        // 比特频率允许4个标准差
+        let ones_tolerance = 4.0 * 0.5 / bits.sqrt();
+        (self.ones_ratio - 0.5).abs() <= ones_tolerance
+            && self.runs_deviation.abs() <= 0.05
+            && self.chi_square <= 330.0
+            && self.shannon_entropy >= 7.5
+            && self.serial_correlation.abs() <= 0.1
+    }
+}
+
+impl fmt::Display for StatisticalReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} 字节: 1比特 {:.4} 游程偏差 {:+.4} 卡方 {:.1} 熵 {:.4} 序列相关 {:+.4} {}",
+            self.bytes,
+            self.ones_ratio,
+            self.runs_deviation,
+            self.chi_square,
+            self.shannon_entropy,
+            self.serial_correlation,
+            if self.passes() { "通过" } else { "未通过" }
+        )
+    }
+}
+
+/// 1比特所占比例
+pub fn ones_ratio(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    let ones: u32 = data.iter().map(|byte| byte.count_ones()).sum();
+    ones as f64 / (data.len() * 8) as f64
+}
+
+/// 比特游程数相对于按实际1比特比例计算的期望值的偏差
+pub fn runs_deviation(data: &[u8]) -> f64 {
+    let bits: Vec<bool> = data.iter().flat_map(|&byte| (0..8).rev().map(move |i| byte >> i & 1 == 1)).collect();
+    if bits.len() < 2 {
+        return 0.0;
+    }
+    let p = ones_ratio(data);
+    let expected = 2.0 * bits.len() as f64 * p * (1.0 - p);
+    if expected == 0.0 {
+        return -1.0;
+    }
+    let runs = 1 + bits.windows(2).filter(|pair| pair[0] != pair[1]).count();
+    runs as f64 / expected - 1.0
+}
+
+fn histogram(data: &[u8]) -> [u32; 256] {
+    let mut histogram = [0u32; 256];
+    for &byte in data {
+        histogram[byte as usize] += 1;
+    }
+    histogram
+}
+
+/// 字节分布的卡方统计量
+pub fn chi_square(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    let expected = data.len() as f64 / 256.0;
+    histogram(data)
+        .iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum()
+}
+
+/// 每字节的香农熵
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    let total = data.len() as f64;
+    histogram(data)
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// 相邻字节的皮尔逊相关系数
+pub fn serial_correlation(data: &[u8]) -> f64 {
+    if data.len() < 2 {
+        return 0.0;
+    }
+    let xs = &data[..data.len() - 1];
+    let ys = &data[1..];
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().map(|&x| x as f64).sum::<f64>() / n;
+    let mean_y = ys.iter().map(|&y| y as f64).sum::<f64>() / n;
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (&x, &y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x as f64 - mean_x, y as f64 - mean_y);
+        covariance += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return 1.0;
+    }
+    covariance / (var_x * var_y).sqrt()
+}
+
+/// 同一字节连续重复的最长次数，用于重复计数健康检测（卡住的熵源会输出长串相同字节）
+pub fn longest_repeat(data: &[u8]) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+    for (i, &byte) in data.iter().enumerate() {
+        run = if i > 0 && data[i - 1] == byte { run + 1 } else { 1 };
+        longest = longest.max(run);
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::hash::sha256;
+
+    #[test]
+    fn test_reports_separate_random_from_structured_data() {
+        let random: Vec<u8> = (0u32..128).flat_map(|i| sha256(&i.to_le_bytes())).collect();
+        let report = StatisticalReport::analyze(&random);
+        assert!(report.passes(), "{}", report);
+
+        let counting: Vec<u8> = (0..4096).map(|i| i as u8).collect();
+        let report = StatisticalReport::analyze(&counting);
+        assert_eq!(report.chi_square, 0.0);
+        assert!(report.serial_correlation > 0.9);
+        assert!(!report.passes());
+
+        assert_eq!(ones_ratio(&[0xFF, 0x00]), 0.5);
+        assert_eq!(runs_deviation(&[0x00; 16]), -1.0);
+        assert_eq!(longest_repeat(&[1, 2, 2, 2, 3, 3]), 3);
+        assert_eq!(longest_repeat(&[]), 0);
+        assert!(longest_repeat(&random) <= 3);
+    }
+}
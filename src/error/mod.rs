@@ -1,6 +1,8 @@
 //! 统一错误处理系统
 //! 
 //! 提供统一的错误类型和处理机制，支持错误链和上下文信息
+//!
+//! 错误的 `Display` 固定为英文，不随 `i18n` 的界面语言变化，作为库使用时输出稳定。
 
 use std::fmt;
 use std::error::Error as StdError;
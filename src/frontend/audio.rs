@@ -0,0 +1,203 @@
+//! 音频输出后端
+//!
+//! `NullSink` 丢弃采样，只记录数量，用于无头运行和测试；
+//! 启用 `audio` 功能后 `CpalSink` 通过cpal把采样送到系统默认输出设备。
+//! 核心本身不依赖任何音频库。
+
+use std::time::Duration;
+
+use super::AudioSink;
+
+/// 按采样数和采样率换算时长
+pub fn samples_to_duration(samples: usize, sample_rate: u32) -> Duration {
+    if sample_rate == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(samples as f64 / sample_rate as f64)
+}
+
+/// 丢弃所有采样的音频输出
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NullSink {
+    sample_rate: u32,
+    queued: u64,
+}
+
+impl NullSink {
+    pub fn new(sample_rate: u32) -> Self {
+        Self { sample_rate, queued: 0 }
+    }
+
+    /// 累计提交的采样数
+    pub fn samples_queued(&self) -> u64 {
+        self.queued
+    }
+}
+
+impl Default for NullSink {
+    fn default() -> Self {
+        Self::new(44_100)
+    }
+}
+
+impl AudioSink for NullSink {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn queue(&mut self, samples: &[f32]) -> Result<(), String> {
+        self.queued += samples.len() as u64;
+        Ok(())
+    }
+
+    fn latency(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+#[cfg(feature = "audio")]
+pub use self::cpal_sink::CpalSink;
+
+#[cfg(feature = "audio")]
+mod cpal_sink {
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{FromSample, Sample, SizedSample};
+
+    use super::{samples_to_duration, AudioSink};
+
+    type SharedBuffer = Arc<Mutex<VecDeque<f32>>>;
+
+    /// 通过cpal输出到系统默认设备
+    ///
+    /// 采样先进入共享队列，由音频线程的回调取出并复制到每个声道；
+    /// 队列为空时输出静音，超过最大延迟的采样被丢弃，避免声音越来越滞后。
+    pub struct CpalSink {
+        // 流被丢弃时停止播放
+        _stream: cpal::Stream,
+        buffer: SharedBuffer,
+        sample_rate: u32,
+        max_buffered: usize,
+    }
+
+    impl CpalSink {
+        /// 打开默认输出设备，最大延迟100毫秒
+        pub fn new() -> Result<Self, String> {
+            Self::with_max_latency(Duration::from_millis(100))
+        }
+
+        /// 打开默认输出设备，队列中最多缓冲 `max_latency` 的采样
+        pub fn with_max_latency(max_latency: Duration) -> Result<Self, String> {
+            let device = cpal::default_host()
+                .default_output_device()
+                .ok_or_else(|| "没有可用的音频输出设备".to_string())?;
+            let supported = device.default_output_config().map_err(|e| format!("无法读取音频设备配置: {}", e))?;
+            let sample_format = supported.sample_format();
+            let config: cpal::StreamConfig = supported.into();
+            let sample_rate = config.sample_rate.0;
+
+            let buffer: SharedBuffer = Arc::new(Mutex::new(VecDeque::new()));
+            let stream = match sample_format {
+                cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config, buffer.clone()),
+                cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config, buffer.clone()),
+                cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config, buffer.clone()),
+                other => Err(format!("不支持的采样格式: {:?}", other)),
+            }?;
+            stream.play().map_err(|e| format!("无法开始播放: {}", e))?;
+
+            Ok(Self {
+                _stream: stream,
+                buffer,
+                sample_rate,
+                max_buffered: (max_latency.as_secs_f64() * sample_rate as f64) as usize,
+            })
+        }
+    }
+
+    fn build_stream<T>(device: &cpal::Device, config: &cpal::StreamConfig, buffer: SharedBuffer) -> Result<cpal::Stream, String>
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let channels = usize::from(config.channels.max(1));
+        device
+            .build_output_stream(
+                config,
+                move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                    let mut buffer = buffer.lock().unwrap_or_else(|e| e.into_inner());
+                    for frame in data.chunks_mut(channels) {
+                        let sample = buffer.pop_front().unwrap_or(0.0);
+                        frame.fill(T::from_sample(sample));
+                    }
+                },
+                |error| eprintln!("音频输出错误: {}", error),
+                None,
+            )
+            .map_err(|e| format!("无法创建音频流: {}", e))
+    }
+
+    impl AudioSink for CpalSink {
+        fn sample_rate(&self) -> u32 {
+            self.sample_rate
+        }
+
+        fn queue(&mut self, samples: &[f32]) -> Result<(), String> {
+            let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+            let room = self.max_buffered.saturating_sub(buffer.len());
+            buffer.extend(samples.iter().take(room).map(|s| s.clamp(-1.0, 1.0)));
+            Ok(())
+        }
+
+        fn latency(&self) -> Duration {
+            let buffered = self.buffer.lock().unwrap_or_else(|e| e.into_inner()).len();
+            samples_to_duration(buffered, self.sample_rate)
+        }
+
+        fn is_realtime(&self) -> bool {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::{Emulator, Frontend, Headless};
+
+    /// 每帧产生一段固定长度的方波
+    struct Beeper;
+
+    impl Emulator for Beeper {
+        fn reset(&mut self) {}
+
+        fn load_rom(&mut self, _data: &[u8]) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn step(&mut self) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn run_frame(&mut self) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn drain_audio(&mut self, out: &mut Vec<f32>) {
+            out.extend((0..735).map(|i| if i % 100 < 50 { 0.5 } else { -0.5 }));
+        }
+    }
+
+    #[test]
+    fn test_null_sink_receives_emulator_audio() {
+        let mut frontend = Frontend::new(Headless, NullSink::new(44_100), Headless);
+        frontend.run(&mut Beeper, 3).unwrap();
+        assert_eq!(frontend.audio.samples_queued(), 3 * 735);
+        assert_eq!(frontend.audio.latency(), Duration::ZERO);
+        assert_eq!(frontend.audio.sample_rate(), 44_100);
+
+        assert_eq!(samples_to_duration(22_050, 44_100), Duration::from_millis(500));
+        assert_eq!(samples_to_duration(100, 0), Duration::ZERO);
+    }
+}
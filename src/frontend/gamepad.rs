@@ -0,0 +1,482 @@
+//! 手柄输入
+//!
+//! 手柄后端只负责上报原始事件（插拔、按键、摇杆），`GamepadInput` 按键位绑定
+//! 把它们映射成 `Button` 事件，和键盘一样实现 `InputSource`。
+//! 摇杆超过死区视为按下对应方向；手柄拔出时松开它按住的所有键。
+//! 手柄按键也可以绑定到热键，默认右肩键快速存档、左肩键快速读档。
+//! 启用 `gamepad` 功能后可以用基于gilrs的 `GilrsBackend`。
+
+use crate::config::{keys, Config};
+
+use super::{Button, Hotkey, InputEvent, InputSource};
+
+/// 手柄编号，由后端分配
+pub type PadId = usize;
+
+/// 手柄上的按键，按标准手柄布局命名；`Stick*` 是左摇杆推过死区的方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftShoulder,
+    RightShoulder,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    StickUp,
+    StickDown,
+    StickLeft,
+    StickRight,
+}
+
+impl PadButton {
+    pub const ALL: [PadButton; 16] = [
+        PadButton::South,
+        PadButton::East,
+        PadButton::North,
+        PadButton::West,
+        PadButton::LeftShoulder,
+        PadButton::RightShoulder,
+        PadButton::Select,
+        PadButton::Start,
+        PadButton::DPadUp,
+        PadButton::DPadDown,
+        PadButton::DPadLeft,
+        PadButton::DPadRight,
+        PadButton::StickUp,
+        PadButton::StickDown,
+        PadButton::StickLeft,
+        PadButton::StickRight,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PadButton::South => "south",
+            PadButton::East => "east",
+            PadButton::North => "north",
+            PadButton::West => "west",
+            PadButton::LeftShoulder => "lb",
+            PadButton::RightShoulder => "rb",
+            PadButton::Select => "select",
+            PadButton::Start => "start",
+            PadButton::DPadUp => "dpad_up",
+            PadButton::DPadDown => "dpad_down",
+            PadButton::DPadLeft => "dpad_left",
+            PadButton::DPadRight => "dpad_right",
+            PadButton::StickUp => "stick_up",
+            PadButton::StickDown => "stick_down",
+            PadButton::StickLeft => "stick_left",
+            PadButton::StickRight => "stick_right",
+        }
+    }
+
+    /// 从名称解析，不区分大小写
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|button| button.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|b| b.name()).collect();
+                format!("未知的手柄按键: {} (可用: {})", name, names.join(", "))
+            })
+    }
+}
+
+/// 手柄上的模拟轴，取值 -1.0..=1.0，Y轴向上为正
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadAxis {
+    LeftStickX,
+    LeftStickY,
+    DPadX,
+    DPadY,
+}
+
+/// 后端上报的原始事件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PadEvent {
+    Connected(PadId),
+    Disconnected(PadId),
+    Button { pad: PadId, button: PadButton, pressed: bool },
+    Axis { pad: PadId, axis: PadAxis, value: f32 },
+}
+
+/// 手柄后端
+pub trait PadBackend {
+    /// 取出自上次调用以来的事件
+    fn poll_events(&mut self) -> Vec<PadEvent>;
+}
+
+/// 手柄按键到Game Boy按键和热键的绑定
+#[derive(Debug, Clone, PartialEq)]
+pub struct PadBindings {
+    bindings: Vec<(PadButton, Button)>,
+    hotkeys: Vec<(PadButton, Hotkey)>,
+    /// 摇杆死区，绝对值超过它才算按下方向
+    pub deadzone: f32,
+}
+
+impl PadBindings {
+    /// 默认死区
+    pub const DEFAULT_DEADZONE: f32 = 0.5;
+
+    /// 没有任何绑定
+    pub fn empty() -> Self {
+        Self { bindings: Vec::new(), hotkeys: Vec::new(), deadzone: Self::DEFAULT_DEADZONE }
+    }
+
+    /// 绑定一个手柄按键，已有的绑定被替换
+    pub fn bind(&mut self, pad_button: PadButton, button: Button) {
+        self.unbind(pad_button);
+        self.bindings.push((pad_button, button));
+    }
+
+    /// 把手柄按键绑定到热键，已有的绑定被替换
+    pub fn bind_hotkey(&mut self, pad_button: PadButton, hotkey: Hotkey) {
+        self.unbind(pad_button);
+        self.hotkeys.push((pad_button, hotkey));
+    }
+
+    fn unbind(&mut self, pad_button: PadButton) {
+        self.bindings.retain(|&(existing, _)| existing != pad_button);
+        self.hotkeys.retain(|&(existing, _)| existing != pad_button);
+    }
+
+    /// 手柄按键绑定的Game Boy按键
+    pub fn button(&self, pad_button: PadButton) -> Option<Button> {
+        self.bindings.iter().find(|&&(existing, _)| existing == pad_button).map(|&(_, button)| button)
+    }
+
+    /// 手柄按键绑定的热键
+    pub fn hotkey(&self, pad_button: PadButton) -> Option<Hotkey> {
+        self.hotkeys.iter().find(|&&(existing, _)| existing == pad_button).map(|&(_, hotkey)| hotkey)
+    }
+
+    /// 在默认绑定上应用 `south=a,east=b,rb=quick_save` 形式的覆盖
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut bindings = Self::default();
+        for pair in spec.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (pad_button, button) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("手柄绑定的格式应为 PAD=BUTTON: {}", pair))?;
+            let pad_button = PadButton::parse(pad_button)?;
+            match (Button::parse(button), Hotkey::parse(button)) {
+                (Ok(button), _) => bindings.bind(pad_button, button),
+                (_, Ok(hotkey)) => bindings.bind_hotkey(pad_button, hotkey),
+                (Err(error), Err(_)) => return Err(error),
+            }
+        }
+        Ok(bindings)
+    }
+
+    /// 读取配置 `gamepad_bindings` 和 `gamepad_deadzone`
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let mut bindings = Self::parse(config.get(keys::GAMEPAD_BINDINGS).map_or("", |s| s.as_str()))?;
+        if config.get(keys::GAMEPAD_DEADZONE).is_some() {
+            bindings.deadzone = config.parse(keys::GAMEPAD_DEADZONE).map_err(|e| e.to_string())?;
+        }
+        Ok(bindings)
+    }
+}
+
+impl Default for PadBindings {
+    /// 南键为A、东键为B，方向键和左摇杆都控制方向，右肩键快速存档、左肩键快速读档
+    fn default() -> Self {
+        let mut bindings = Self::empty();
+        for (pad_button, button) in [
+            (PadButton::South, Button::A),
+            (PadButton::East, Button::B),
+            (PadButton::Select, Button::Select),
+            (PadButton::Start, Button::Start),
+            (PadButton::DPadUp, Button::Up),
+            (PadButton::DPadDown, Button::Down),
+            (PadButton::DPadLeft, Button::Left),
+            (PadButton::DPadRight, Button::Right),
+            (PadButton::StickUp, Button::Up),
+            (PadButton::StickDown, Button::Down),
+            (PadButton::StickLeft, Button::Left),
+            (PadButton::StickRight, Button::Right),
+        ] {
+            bindings.bind(pad_button, button);
+        }
+        bindings.bind_hotkey(PadButton::RightShoulder, Hotkey::QuickSave);
+        bindings.bind_hotkey(PadButton::LeftShoulder, Hotkey::QuickLoad);
+        bindings
+    }
+}
+
+/// 手柄输入源
+///
+/// 同一个 `Button` 可能同时被多个手柄按键按住（例如方向键和摇杆），
+/// 只在第一个按下时报告按下、最后一个松开时报告松开。
+#[derive(Debug)]
+pub struct GamepadInput<B> {
+    backend: B,
+    bindings: PadBindings,
+    held: Vec<(PadId, PadButton)>,
+    connected: Vec<PadId>,
+    hotkeys: Vec<Hotkey>,
+}
+
+impl<B: PadBackend> GamepadInput<B> {
+    pub fn new(backend: B, bindings: PadBindings) -> Self {
+        Self { backend, bindings, held: Vec::new(), connected: Vec::new(), hotkeys: Vec::new() }
+    }
+
+    /// 当前连接的手柄
+    pub fn connected(&self) -> &[PadId] {
+        &self.connected
+    }
+
+    pub fn bindings(&self) -> &PadBindings {
+        &self.bindings
+    }
+
+    /// 替换键位绑定，已按住的键全部松开
+    pub fn set_bindings(&mut self, bindings: PadBindings) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+        for (pad, pad_button) in self.held.clone() {
+            self.set_held(pad, pad_button, false, &mut events);
+        }
+        self.bindings = bindings;
+        events
+    }
+
+    fn set_held(&mut self, pad: PadId, pad_button: PadButton, pressed: bool, events: &mut Vec<InputEvent>) {
+        let Some(button) = self.bindings.button(pad_button) else {
+            return;
+        };
+        let holding = |held: &[(PadId, PadButton)]| {
+            held.iter().filter(|&&(_, existing)| self.bindings.button(existing) == Some(button)).count()
+        };
+        let position = self.held.iter().position(|&entry| entry == (pad, pad_button));
+        match (pressed, position) {
+            (true, None) => {
+                if holding(&self.held) == 0 {
+                    events.push(InputEvent { button, pressed: true });
+                }
+                self.held.push((pad, pad_button));
+            }
+            (false, Some(index)) => {
+                self.held.remove(index);
+                if holding(&self.held) == 0 {
+                    events.push(InputEvent { button, pressed: false });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle(&mut self, event: PadEvent, events: &mut Vec<InputEvent>) {
+        match event {
+            PadEvent::Connected(pad) => {
+                if !self.connected.contains(&pad) {
+                    self.connected.push(pad);
+                }
+            }
+            PadEvent::Disconnected(pad) => {
+                self.connected.retain(|&existing| existing != pad);
+                let released: Vec<PadButton> =
+                    self.held.iter().filter(|&&(owner, _)| owner == pad).map(|&(_, pad_button)| pad_button).collect();
+                for pad_button in released {
+                    self.set_held(pad, pad_button, false, events);
+                }
+            }
+            PadEvent::Button { pad, button, pressed } => match self.bindings.hotkey(button) {
+                // 热键只在按下时触发一次
+                Some(hotkey) if pressed => self.hotkeys.push(hotkey),
+                Some(_) => {}
+                None => self.set_held(pad, button, pressed, events),
+            },
+            PadEvent::Axis { pad, axis, value } => {
+                let (negative, positive) = match axis {
+                    PadAxis::LeftStickX => (PadButton::StickLeft, PadButton::StickRight),
+                    PadAxis::LeftStickY => (PadButton::StickDown, PadButton::StickUp),
+                    PadAxis::DPadX => (PadButton::DPadLeft, PadButton::DPadRight),
+                    PadAxis::DPadY => (PadButton::DPadDown, PadButton::DPadUp),
+                };
+                let deadzone = self.bindings.deadzone;
+                self.set_held(pad, negative, value < -deadzone, events);
+                self.set_held(pad, positive, value > deadzone, events);
+            }
+        }
+    }
+}
+
+impl<B: PadBackend> InputSource for GamepadInput<B> {
+    fn poll(&mut self) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+        for event in self.backend.poll_events() {
+            self.handle(event, &mut events);
+        }
+        events
+    }
+
+    fn poll_hotkeys(&mut self) -> Vec<Hotkey> {
+        std::mem::take(&mut self.hotkeys)
+    }
+}
+
+#[cfg(feature = "gamepad")]
+pub use self::gilrs_backend::GilrsBackend;
+
+#[cfg(feature = "gamepad")]
+mod gilrs_backend {
+    use gilrs::{Axis, EventType, Gilrs};
+
+    use super::{PadAxis, PadBackend, PadButton, PadEvent};
+
+    /// 基于gilrs的手柄后端，支持热插拔
+    pub struct GilrsBackend {
+        gilrs: Gilrs,
+        announced: bool,
+    }
+
+    impl GilrsBackend {
+        pub fn new() -> Result<Self, String> {
+            let gilrs = Gilrs::new().map_err(|e| format!("无法初始化手柄: {}", e))?;
+            Ok(Self { gilrs, announced: false })
+        }
+    }
+
+    fn map_button(button: gilrs::Button) -> Option<PadButton> {
+        Some(match button {
+            gilrs::Button::South => PadButton::South,
+            gilrs::Button::East => PadButton::East,
+            gilrs::Button::North => PadButton::North,
+            gilrs::Button::West => PadButton::West,
+            gilrs::Button::LeftTrigger => PadButton::LeftShoulder,
+            gilrs::Button::RightTrigger => PadButton::RightShoulder,
+            gilrs::Button::Select => PadButton::Select,
+            gilrs::Button::Start => PadButton::Start,
+            gilrs::Button::DPadUp => PadButton::DPadUp,
+            gilrs::Button::DPadDown => PadButton::DPadDown,
+            gilrs::Button::DPadLeft => PadButton::DPadLeft,
+            gilrs::Button::DPadRight => PadButton::DPadRight,
+            _ => return None,
+        })
+    }
+
+    fn map_axis(axis: Axis) -> Option<PadAxis> {
+        Some(match axis {
+            Axis::LeftStickX => PadAxis::LeftStickX,
+            Axis::LeftStickY => PadAxis::LeftStickY,
+            Axis::DPadX => PadAxis::DPadX,
+            Axis::DPadY => PadAxis::DPadY,
+            _ => return None,
+        })
+    }
+
+    impl PadBackend for GilrsBackend {
+        fn poll_events(&mut self) -> Vec<PadEvent> {
+            let mut events = Vec::new();
+            // 启动前已经插着的手柄不会产生连接事件
+            if !self.announced {
+                self.announced = true;
+                events.extend(self.gilrs.gamepads().map(|(id, _)| PadEvent::Connected(id.into())));
+            }
+            while let Some(event) = self.gilrs.next_event() {
+                let pad = event.id.into();
+                let mapped = match event.event {
+                    EventType::Connected => Some(PadEvent::Connected(pad)),
+                    EventType::Disconnected => Some(PadEvent::Disconnected(pad)),
+                    EventType::ButtonPressed(button, _) => {
+                        map_button(button).map(|button| PadEvent::Button { pad, button, pressed: true })
+                    }
+                    EventType::ButtonReleased(button, _) => {
+                        map_button(button).map(|button| PadEvent::Button { pad, button, pressed: false })
+                    }
+                    EventType::AxisChanged(axis, value, _) => {
+                        map_axis(axis).map(|axis| PadEvent::Axis { pad, axis, value })
+                    }
+                    _ => None,
+                };
+                events.extend(mapped);
+            }
+            events
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Script(Vec<Vec<PadEvent>>);
+
+    impl PadBackend for Script {
+        fn poll_events(&mut self) -> Vec<PadEvent> {
+            if self.0.is_empty() {
+                Vec::new()
+            } else {
+                self.0.remove(0)
+            }
+        }
+    }
+
+    fn press(button: Button) -> InputEvent {
+        InputEvent { button, pressed: true }
+    }
+
+    fn release(button: Button) -> InputEvent {
+        InputEvent { button, pressed: false }
+    }
+
+    #[test]
+    fn test_gamepad_mapping_overlap_and_hot_unplug() {
+        let script = Script(vec![
+            vec![
+                PadEvent::Connected(0),
+                PadEvent::Button { pad: 0, button: PadButton::South, pressed: true },
+                PadEvent::Button { pad: 0, button: PadButton::North, pressed: true },
+                PadEvent::Button { pad: 0, button: PadButton::DPadLeft, pressed: true },
+                PadEvent::Button { pad: 0, button: PadButton::RightShoulder, pressed: true },
+                PadEvent::Button { pad: 0, button: PadButton::RightShoulder, pressed: false },
+            ],
+            // 摇杆和方向键同时按住左，只在都松开时报告松开
+            vec![
+                PadEvent::Axis { pad: 0, axis: PadAxis::LeftStickX, value: -0.9 },
+                PadEvent::Button { pad: 0, button: PadButton::DPadLeft, pressed: false },
+                PadEvent::Axis { pad: 0, axis: PadAxis::LeftStickX, value: -0.2 },
+                PadEvent::Axis { pad: 0, axis: PadAxis::LeftStickY, value: 0.7 },
+            ],
+            vec![PadEvent::Disconnected(0)],
+        ]);
+        let mut input = GamepadInput::new(script, PadBindings::default());
+
+        assert_eq!(input.poll(), vec![press(Button::A), press(Button::Left)]);
+        assert_eq!(input.poll_hotkeys(), vec![Hotkey::QuickSave]);
+        assert_eq!(input.connected(), &[0]);
+        assert_eq!(input.poll(), vec![release(Button::Left), press(Button::Up)]);
+        assert_eq!(input.poll(), vec![release(Button::A), release(Button::Up)]);
+        assert!(input.connected().is_empty());
+        assert!(input.poll().is_empty());
+    }
+
+    #[test]
+    fn test_bindings_from_config() {
+        let mut config = Config::new();
+        config.set(keys::GAMEPAD_BINDINGS, "west=a, south=b");
+        config.set(keys::GAMEPAD_DEADZONE, "0.25");
+        let bindings = PadBindings::from_config(&config).unwrap();
+        assert_eq!(bindings.button(PadButton::West), Some(Button::A));
+        assert_eq!(bindings.button(PadButton::South), Some(Button::B));
+        assert_eq!(bindings.button(PadButton::Start), Some(Button::Start));
+        assert_eq!(bindings.deadzone, 0.25);
+        assert_eq!(bindings.hotkey(PadButton::LeftShoulder), Some(Hotkey::QuickLoad));
+
+        let bindings = PadBindings::parse("select=undo_load, rb=a").unwrap();
+        assert_eq!(bindings.hotkey(PadButton::Select), Some(Hotkey::UndoLoad));
+        assert_eq!(bindings.button(PadButton::Select), None);
+        assert_eq!((bindings.button(PadButton::RightShoulder), bindings.hotkey(PadButton::RightShoulder)), (Some(Button::A), None));
+
+        assert!(PadBindings::parse("south").is_err());
+        assert!(PadBindings::parse("trigger=a").is_err());
+        assert!(PadBindings::parse("south=turbo").is_err());
+    }
+}
@@ -0,0 +1,497 @@
+//! 前端接口
+//!
+//! 前端（窗口、终端、无头测试等）只面向这里定义的trait编程，
+//! 不直接依赖 `GameBoy`、`GBASystem` 等具体结构
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::emulator::{CyclesReport, SaveSlots, SaveState};
+
+pub mod audio;
+pub mod gamepad;
+pub mod pacing;
+pub mod touch;
+pub mod tui;
+#[cfg(feature = "debug")]
+pub mod watch;
+
+pub use audio::NullSink;
+#[cfg(feature = "audio")]
+pub use audio::CpalSink;
+pub use pacing::{FramePacer, SyncMode};
+
+/// 手柄按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Button {
+    A,
+    B,
+    Select,
+    Start,
+    Right,
+    Left,
+    Up,
+    Down,
+}
+
+impl Button {
+    /// 全部按键
+    pub const ALL: [Button; 8] = [
+        Button::A,
+        Button::B,
+        Button::Select,
+        Button::Start,
+        Button::Right,
+        Button::Left,
+        Button::Up,
+        Button::Down,
+    ];
+
+    /// 按键名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Button::A => "A",
+            Button::B => "B",
+            Button::Select => "Select",
+            Button::Start => "Start",
+            Button::Right => "Right",
+            Button::Left => "Left",
+            Button::Up => "Up",
+            Button::Down => "Down",
+        }
+    }
+
+    /// 从名称解析，不区分大小写
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|button| button.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("未知的按键: {} (可用: a, b, select, start, right, left, up, down)", name))
+    }
+}
+
+/// 模拟器功能热键，由前端处理，不传给模拟器核心
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hotkey {
+    /// 保存到当前槽位
+    QuickSave,
+    /// 读取当前槽位
+    QuickLoad,
+    NextSlot,
+    PreviousSlot,
+    /// 回到最近一次读档之前的状态
+    UndoLoad,
+}
+
+impl Hotkey {
+    pub const ALL: [Hotkey; 5] = [Hotkey::QuickSave, Hotkey::QuickLoad, Hotkey::NextSlot, Hotkey::PreviousSlot, Hotkey::UndoLoad];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hotkey::QuickSave => "quick_save",
+            Hotkey::QuickLoad => "quick_load",
+            Hotkey::NextSlot => "next_slot",
+            Hotkey::PreviousSlot => "prev_slot",
+            Hotkey::UndoLoad => "undo_load",
+        }
+    }
+
+    /// 从名称解析，不区分大小写
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|hotkey| hotkey.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("未知的热键: {} (可用: quick_save, quick_load, next_slot, prev_slot, undo_load)", name))
+    }
+}
+
+/// 按键事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputEvent {
+    pub button: Button,
+    pub pressed: bool,
+}
+
+/// 一帧画面，像素为RGB888
+#[derive(Debug, Clone, Copy)]
+pub struct Frame<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: &'a [u8],
+}
+
+/// 模拟器核心接口
+pub trait Emulator {
+    /// 重置到上电状态
+    fn reset(&mut self);
+
+    /// 加载ROM数据，核心可以先校验头部并拒绝无效的ROM
+    fn load_rom(&mut self, data: &[u8]) -> Result<(), String>;
+
+    /// 执行一条指令
+    fn step(&mut self) -> Result<(), String>;
+
+    /// 运行到下一帧
+    fn run_frame(&mut self) -> Result<(), String>;
+
+    /// 运行至少 `cycles` 个时钟周期，多跑的周期从下一次调用中扣除
+    ///
+    /// 默认实现不知道指令的周期数，按每条指令一个周期执行，不产生欠账
+    fn run_cycles(&mut self, cycles: u64) -> Result<CyclesReport, String> {
+        for _ in 0..cycles {
+            self.step()?;
+        }
+        Ok(CyclesReport { requested: cycles, executed: cycles, instructions: cycles, ..CyclesReport::default() })
+    }
+
+    /// 当前画面，没有显示输出的核心返回 `None`
+    fn frame(&self) -> Option<Frame<'_>> {
+        None
+    }
+
+    /// 更新按键状态
+    fn set_button(&mut self, _button: Button, _pressed: bool) {}
+
+    /// 取出自上次调用以来产生的音频采样
+    fn drain_audio(&mut self, _out: &mut Vec<f32>) {}
+
+    /// 保存当前状态，不支持存档的核心返回 `None`
+    fn snapshot(&self) -> Option<SaveState> {
+        None
+    }
+
+    /// 恢复 `snapshot` 保存的状态
+    fn restore(&mut self, _state: &SaveState) -> Result<(), String> {
+        Err("该模拟器核心不支持存档".to_string())
+    }
+}
+
+/// 画面输出
+pub trait Video {
+    /// 显示一帧画面
+    fn present(&mut self, frame: Frame<'_>) -> Result<(), String>;
+}
+
+/// 音频输出
+pub trait AudioSink {
+    /// 采样率 (Hz)
+    fn sample_rate(&self) -> u32;
+
+    /// 提交一批单声道采样
+    fn queue(&mut self, samples: &[f32]) -> Result<(), String>;
+
+    /// 已提交但还没有播放的采样时长
+    fn latency(&self) -> Duration;
+
+    /// 是否按真实时间消费采样，只有这样的输出才能用来控制帧率
+    fn is_realtime(&self) -> bool {
+        false
+    }
+}
+
+/// 输入设备
+pub trait InputSource {
+    /// 读取自上次调用以来的按键事件
+    fn poll(&mut self) -> Vec<InputEvent>;
+
+    /// 按下屏幕上的虚拟按键，在下一次 `poll` 中报告；不支持虚拟按键的输入源忽略
+    fn press_virtual(&mut self, _button: Button) {}
+
+    /// 松开虚拟按键
+    fn release_virtual(&mut self, _button: Button) {}
+
+    /// 取出上一次 `poll` 期间触发的热键
+    fn poll_hotkeys(&mut self) -> Vec<Hotkey> {
+        Vec::new()
+    }
+}
+
+/// 两个输入源合并，例如键盘加手柄
+impl<A: InputSource, B: InputSource> InputSource for (A, B) {
+    fn poll(&mut self) -> Vec<InputEvent> {
+        let mut events = self.0.poll();
+        events.extend(self.1.poll());
+        events
+    }
+
+    fn press_virtual(&mut self, button: Button) {
+        self.0.press_virtual(button);
+        self.1.press_virtual(button);
+    }
+
+    fn release_virtual(&mut self, button: Button) {
+        self.0.release_virtual(button);
+        self.1.release_virtual(button);
+    }
+
+    fn poll_hotkeys(&mut self) -> Vec<Hotkey> {
+        let mut hotkeys = self.0.poll_hotkeys();
+        hotkeys.extend(self.1.poll_hotkeys());
+        hotkeys
+    }
+}
+
+/// 无头前端组件，丢弃所有输出且不产生输入
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Headless;
+
+impl Video for Headless {
+    fn present(&mut self, _frame: Frame<'_>) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl AudioSink for Headless {
+    fn sample_rate(&self) -> u32 {
+        44_100
+    }
+
+    fn queue(&mut self, _samples: &[f32]) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn latency(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+impl InputSource for Headless {
+    fn poll(&mut self) -> Vec<InputEvent> {
+        Vec::new()
+    }
+}
+
+/// 前端：把画面、音频和输入组件连接到任意模拟器核心
+#[derive(Debug)]
+pub struct Frontend<V, A, I> {
+    pub video: V,
+    pub audio: A,
+    pub input: I,
+    frames: u64,
+    audio_buffer: Vec<f32>,
+    pacer: Option<FramePacer>,
+    /// 每帧运行的时钟周期数，`None` 时由核心决定一帧何时结束
+    frame_cycles: Option<u64>,
+    last_cycles: Option<CyclesReport>,
+    slots: Option<SaveSlots>,
+    status: Option<String>,
+}
+
+impl<V: Video, A: AudioSink, I: InputSource> Frontend<V, A, I> {
+    /// 创建前端
+    pub fn new(video: V, audio: A, input: I) -> Self {
+        Self {
+            video,
+            audio,
+            input,
+            frames: 0,
+            audio_buffer: Vec::new(),
+            pacer: None,
+            frame_cycles: None,
+            last_cycles: None,
+            slots: None,
+            status: None,
+        }
+    }
+
+    /// 启用存档槽位，之后输入源的存档热键才生效
+    pub fn set_slots(&mut self, slots: Option<SaveSlots>) {
+        self.slots = slots;
+    }
+
+    pub fn slots(&self) -> Option<&SaveSlots> {
+        self.slots.as_ref()
+    }
+
+    pub fn slots_mut(&mut self) -> Option<&mut SaveSlots> {
+        self.slots.as_mut()
+    }
+
+    /// 取出最近一次热键操作的结果，供界面显示
+    pub fn take_status(&mut self) -> Option<String> {
+        self.status.take()
+    }
+
+    /// 执行热键；失败（例如槽位为空）只记录在状态里，不中断运行
+    pub fn handle_hotkey<E: Emulator + ?Sized>(&mut self, emulator: &mut E, hotkey: Hotkey) {
+        let play_time = self.play_time();
+        let result = match &mut self.slots {
+            Some(slots) => slots.handle_hotkey(emulator, hotkey, play_time),
+            None => Err("没有启用存档槽位".to_string()),
+        };
+        self.status = Some(result.unwrap_or_else(|error| error));
+    }
+
+    /// 按真实速度运行：每帧之后由 `pacer` 决定等待多久，`None` 表示尽快运行
+    pub fn set_pacer(&mut self, pacer: Option<FramePacer>) {
+        self.pacer = pacer;
+    }
+
+    pub fn pacer(&self) -> Option<&FramePacer> {
+        self.pacer.as_ref()
+    }
+
+    /// 每帧固定运行 `cycles` 个时钟周期，跨帧的欠账由核心记录，
+    /// 每帧产生的音频采样数因此保持稳定；`None` 恢复为按核心的帧边界运行
+    pub fn set_frame_cycles(&mut self, cycles: Option<u64>) {
+        self.frame_cycles = cycles;
+    }
+
+    /// 最近一帧的周期统计，没有设置 `frame_cycles` 时为 `None`
+    pub fn last_cycles(&self) -> Option<&CyclesReport> {
+        self.last_cycles.as_ref()
+    }
+
+    /// 驱动模拟器运行一帧：处理输入、运行、输出画面和音频
+    pub fn run_frame<E: Emulator + ?Sized>(&mut self, emulator: &mut E) -> Result<(), String> {
+        for event in self.input.poll() {
+            emulator.set_button(event.button, event.pressed);
+        }
+        for hotkey in self.input.poll_hotkeys() {
+            self.handle_hotkey(emulator, hotkey);
+        }
+
+        match self.frame_cycles {
+            Some(cycles) => self.last_cycles = Some(emulator.run_cycles(cycles)?),
+            None => emulator.run_frame()?,
+        }
+
+        if let Some(frame) = emulator.frame() {
+            self.video.present(frame)?;
+        }
+
+        self.audio_buffer.clear();
+        emulator.drain_audio(&mut self.audio_buffer);
+        if !self.audio_buffer.is_empty() {
+            self.audio.queue(&self.audio_buffer)?;
+        }
+
+        if let Some(pacer) = &mut self.pacer {
+            let latency = self.audio.is_realtime().then(|| self.audio.latency());
+            let wait = pacer.wait(Instant::now(), latency);
+            if !wait.is_zero() {
+                thread::sleep(wait);
+            }
+        }
+
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// 连续运行指定帧数
+    pub fn run<E: Emulator + ?Sized>(&mut self, emulator: &mut E, frames: u64) -> Result<(), String> {
+        for _ in 0..frames {
+            self.run_frame(emulator)?;
+        }
+        Ok(())
+    }
+
+    /// 已运行的帧数
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// 按Game Boy帧率换算的游戏时长，写入存档元数据
+    pub fn play_time(&self) -> Duration {
+        Duration::from_nanos(pacing::GB_FRAME.as_nanos() as u64 * self.frames)
+    }
+}
+
+impl Frontend<Headless, Headless, Headless> {
+    /// 创建无头前端
+    pub fn headless() -> Self {
+        Self::new(Headless, Headless, Headless)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeCore {
+        pressed: Vec<Button>,
+        steps: u64,
+        frames: u64,
+        pixels: Vec<u8>,
+    }
+
+    impl Emulator for FakeCore {
+        fn reset(&mut self) {
+            *self = FakeCore::default();
+        }
+
+        fn load_rom(&mut self, _data: &[u8]) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn step(&mut self) -> Result<(), String> {
+            self.steps += 1;
+            Ok(())
+        }
+
+        fn run_frame(&mut self) -> Result<(), String> {
+            self.frames += 1;
+            self.pixels = vec![self.frames as u8; 3];
+            Ok(())
+        }
+
+        fn frame(&self) -> Option<Frame<'_>> {
+            Some(Frame { width: 1, height: 1, pixels: &self.pixels })
+        }
+
+        fn set_button(&mut self, button: Button, pressed: bool) {
+            if pressed {
+                self.pressed.push(button);
+            }
+        }
+    }
+
+    struct Recorder(Vec<Vec<u8>>);
+
+    impl Video for Recorder {
+        fn present(&mut self, frame: Frame<'_>) -> Result<(), String> {
+            self.0.push(frame.pixels.to_vec());
+            Ok(())
+        }
+    }
+
+    struct Script(Vec<InputEvent>);
+
+    impl InputSource for Script {
+        fn poll(&mut self) -> Vec<InputEvent> {
+            std::mem::take(&mut self.0)
+        }
+    }
+
+    #[test]
+    fn test_frontend_drives_trait_object() {
+        let input = Script(vec![InputEvent { button: Button::Start, pressed: true }]);
+        let mut frontend = Frontend::new(Recorder(Vec::new()), Headless, input);
+        let mut core = FakeCore::default();
+        let emulator: &mut dyn Emulator = &mut core;
+
+        frontend.run(emulator, 2).unwrap();
+
+        assert_eq!(frontend.frames(), 2);
+        assert_eq!(frontend.video.0, vec![vec![1; 3], vec![2; 3]]);
+        assert_eq!(core.pressed, vec![Button::Start]);
+    }
+
+    #[test]
+    fn test_frame_cycles_runs_fixed_budget_per_frame() {
+        let mut frontend = Frontend::headless();
+        let mut core = FakeCore::default();
+        frontend.set_frame_cycles(Some(5));
+
+        frontend.run(&mut core, 2).unwrap();
+        assert_eq!((core.steps, core.frames), (10, 0));
+        assert_eq!(frontend.last_cycles().map(|report| (report.executed, report.debt)), Some((5, 0)));
+
+        frontend.set_frame_cycles(None);
+        frontend.run_frame(&mut core).unwrap();
+        assert_eq!((core.steps, core.frames, frontend.frames()), (10, 1, 3));
+    }
+}
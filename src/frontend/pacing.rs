@@ -0,0 +1,142 @@
+//! 帧率控制
+//!
+//! 计时器模式按固定的帧间隔等待；音频模式按音频队列的填充程度等待：
+//! 队列超过目标延迟时等它播放到目标以下再运行下一帧，队列快空时立即运行。
+//! 模拟速度跟着声卡时钟走，不会因为两个时钟的偏差逐渐积累出爆音或延迟。
+//! 没有实时音频输出时音频模式自动退回计时器模式。
+
+use std::time::{Duration, Instant};
+
+use crate::config::{keys, Config};
+use crate::gpu::lcd::CYCLES_PER_FRAME;
+
+/// Game Boy的一帧：70224个时钟周期，约59.73帧每秒
+pub const GB_FRAME: Duration = Duration::from_nanos(CYCLES_PER_FRAME as u64 * 1_000_000_000 / 4_194_304);
+
+/// 帧率控制方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncMode {
+    /// 按固定帧间隔
+    Timer,
+    /// 按音频队列填充程度，没有实时音频时退回计时器
+    #[default]
+    Audio,
+}
+
+impl SyncMode {
+    /// 从名称解析
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "timer" => Ok(SyncMode::Timer),
+            "audio" => Ok(SyncMode::Audio),
+            other => Err(format!("未知的帧率控制方式: {} (可用: timer, audio)", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SyncMode::Timer => "timer",
+            SyncMode::Audio => "audio",
+        }
+    }
+
+    /// 读取配置 `frame_sync`，未设置时使用音频模式
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        config.get(keys::FRAME_SYNC).map_or(Ok(Self::default()), |name| Self::parse(name))
+    }
+}
+
+/// 帧率控制器
+///
+/// 只计算每帧之后需要等待多久，实际睡眠由运行线程完成，便于测试。
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    pub mode: SyncMode,
+    /// 计时器模式下的帧间隔
+    pub frame_duration: Duration,
+    /// 音频模式下希望保持的队列长度
+    pub target_latency: Duration,
+    deadline: Option<Instant>,
+    audio_paced: bool,
+}
+
+impl FramePacer {
+    /// 音频模式的默认目标延迟
+    pub const DEFAULT_TARGET_LATENCY: Duration = Duration::from_millis(50);
+
+    pub fn new(mode: SyncMode) -> Self {
+        Self {
+            mode,
+            frame_duration: GB_FRAME,
+            target_latency: Self::DEFAULT_TARGET_LATENCY,
+            deadline: None,
+            audio_paced: false,
+        }
+    }
+
+    /// 上一帧是否按音频节奏控制
+    pub fn audio_paced(&self) -> bool {
+        self.audio_paced
+    }
+
+    /// 一帧运行完之后需要等待的时长
+    ///
+    /// `audio_latency` 是实时音频输出中还没播放的时长，没有实时音频时为 `None`。
+    pub fn wait(&mut self, now: Instant, audio_latency: Option<Duration>) -> Duration {
+        if let (SyncMode::Audio, Some(latency)) = (self.mode, audio_latency) {
+            self.audio_paced = true;
+            self.deadline = None;
+            // 声卡停止消费时最多等两帧，避免界面卡死
+            return latency.saturating_sub(self.target_latency).min(self.frame_duration * 2);
+        }
+
+        self.audio_paced = false;
+        let deadline = self.deadline.map_or(now + self.frame_duration, |deadline| deadline + self.frame_duration);
+        // 落后超过一帧（例如被调试器暂停）时重新对齐，不追赶
+        let deadline = if deadline + self.frame_duration < now { now + self.frame_duration } else { deadline };
+        self.deadline = Some(deadline);
+        deadline.saturating_duration_since(now)
+    }
+
+    /// 丢弃计时器的时间基准，暂停恢复后调用
+    pub fn reset(&mut self) {
+        self.deadline = None;
+    }
+}
+
+impl Default for FramePacer {
+    fn default() -> Self {
+        Self::new(SyncMode::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_pacing_and_timer_fallback() {
+        let start = Instant::now();
+        let mut pacer = FramePacer::new(SyncMode::Audio);
+        assert_eq!(pacer.wait(start, Some(Duration::from_millis(80))), Duration::from_millis(30));
+        assert!(pacer.audio_paced());
+        assert_eq!(pacer.wait(start, Some(Duration::from_millis(20))), Duration::ZERO);
+        assert_eq!(pacer.wait(start, Some(Duration::from_secs(1))), GB_FRAME * 2);
+
+        // 没有实时音频时按帧间隔等待
+        assert_eq!(pacer.wait(start, None), GB_FRAME);
+        assert!(!pacer.audio_paced());
+        assert_eq!(pacer.wait(start + GB_FRAME, None), GB_FRAME);
+        // 落后很多时重新对齐
+        assert_eq!(pacer.wait(start + Duration::from_secs(1), None), GB_FRAME);
+
+        let mut timer = FramePacer::new(SyncMode::Timer);
+        assert_eq!(timer.wait(start, Some(Duration::from_millis(80))), GB_FRAME);
+        assert_eq!(GB_FRAME.as_micros(), 16_742);
+
+        let mut config = Config::new();
+        assert_eq!(SyncMode::from_config(&config), Ok(SyncMode::Audio));
+        config.set(keys::FRAME_SYNC, "timer");
+        assert_eq!(SyncMode::from_config(&config), Ok(SyncMode::Timer));
+    }
+}
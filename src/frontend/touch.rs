@@ -0,0 +1,297 @@
+//! 虚拟按键与触屏布局
+//!
+//! 嵌入式前端（wasm、移动端）没有物理按键，通过 `InputSource::press_virtual`
+//! 报告屏幕上的虚拟按键。`TouchLayout` 把归一化的屏幕坐标（左上角为(0, 0)，
+//! 右下角为(1, 1)）映射到十字键和按键区域，`TouchInput` 跟踪多点触控并生成按键事件，
+//! 各个平台只需要转发触摸坐标。
+
+use super::{Button, InputEvent, InputSource};
+
+/// 虚拟按键输入源：按下和松开排队到下一次 `poll`，重复按下同一个键只报告一次
+#[derive(Debug, Clone, Default)]
+pub struct VirtualButtons {
+    held: Vec<Button>,
+    pending: Vec<InputEvent>,
+}
+
+impl VirtualButtons {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 当前按住的键
+    pub fn held(&self) -> &[Button] {
+        &self.held
+    }
+}
+
+impl InputSource for VirtualButtons {
+    fn poll(&mut self) -> Vec<InputEvent> {
+        std::mem::take(&mut self.pending)
+    }
+
+    fn press_virtual(&mut self, button: Button) {
+        if !self.held.contains(&button) {
+            self.held.push(button);
+            self.pending.push(InputEvent { button, pressed: true });
+        }
+    }
+
+    fn release_virtual(&mut self, button: Button) {
+        if let Some(index) = self.held.iter().position(|&held| held == button) {
+            self.held.remove(index);
+            self.pending.push(InputEvent { button, pressed: false });
+        }
+    }
+}
+
+/// 归一化坐标中的区域
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Region {
+    /// 圆心和半径，半径按屏幕宽高分别归一化
+    Circle { x: f32, y: f32, radius: f32 },
+    /// 左上角和宽高
+    Rect { x: f32, y: f32, width: f32, height: f32 },
+}
+
+impl Region {
+    pub fn contains(&self, px: f32, py: f32) -> bool {
+        match *self {
+            Region::Circle { x, y, radius } => (px - x).powi(2) + (py - y).powi(2) <= radius * radius,
+            Region::Rect { x, y, width, height } => px >= x && px <= x + width && py >= y && py <= y + height,
+        }
+    }
+}
+
+/// 十字键：圆形区域按触点相对圆心的角度分为8个方向，斜方向同时按下两个键
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DPad {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+    /// 中心死区占半径的比例，死区内不按下任何方向
+    pub deadzone: f32,
+}
+
+impl DPad {
+    /// 触点对应的方向键
+    pub fn buttons_at(&self, px: f32, py: f32) -> Vec<Button> {
+        let (dx, dy) = (px - self.x, py - self.y);
+        let distance = (dx * dx + dy * dy).sqrt();
+        if distance > self.radius || distance < self.radius * self.deadzone {
+            return Vec::new();
+        }
+        // 屏幕Y轴向下，逆时针从右开始每45度一个扇区
+        let angle = (-dy).atan2(dx).to_degrees().rem_euclid(360.0);
+        let sector = ((angle + 22.5) / 45.0) as usize % 8;
+        match sector {
+            0 => vec![Button::Right],
+            1 => vec![Button::Up, Button::Right],
+            2 => vec![Button::Up],
+            3 => vec![Button::Up, Button::Left],
+            4 => vec![Button::Left],
+            5 => vec![Button::Down, Button::Left],
+            6 => vec![Button::Down],
+            _ => vec![Button::Down, Button::Right],
+        }
+    }
+}
+
+/// 触屏按键布局
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchLayout {
+    pub dpad: Option<DPad>,
+    pub buttons: Vec<(Button, Region)>,
+}
+
+impl TouchLayout {
+    /// 空布局
+    pub fn new() -> Self {
+        Self { dpad: None, buttons: Vec::new() }
+    }
+
+    /// 设置十字键
+    pub fn with_dpad(mut self, x: f32, y: f32, radius: f32) -> Self {
+        self.dpad = Some(DPad { x, y, radius, deadzone: 0.2 });
+        self
+    }
+
+    /// 添加一个按键区域，区域重叠时两个键都按下
+    pub fn with_button(mut self, button: Button, region: Region) -> Self {
+        self.buttons.push((button, region));
+        self
+    }
+
+    /// 触点对应的所有按键
+    pub fn buttons_at(&self, x: f32, y: f32) -> Vec<Button> {
+        let mut buttons = self.dpad.map_or_else(Vec::new, |dpad| dpad.buttons_at(x, y));
+        for &(button, region) in &self.buttons {
+            if region.contains(x, y) && !buttons.contains(&button) {
+                buttons.push(button);
+            }
+        }
+        buttons
+    }
+}
+
+impl Default for TouchLayout {
+    /// 竖屏布局：十字键在左下，A/B在右下，Select/Start在底部中间
+    fn default() -> Self {
+        Self::new()
+            .with_dpad(0.2, 0.75, 0.15)
+            .with_button(Button::A, Region::Circle { x: 0.85, y: 0.72, radius: 0.08 })
+            .with_button(Button::B, Region::Circle { x: 0.68, y: 0.8, radius: 0.08 })
+            .with_button(Button::Select, Region::Rect { x: 0.34, y: 0.92, width: 0.14, height: 0.05 })
+            .with_button(Button::Start, Region::Rect { x: 0.52, y: 0.92, width: 0.14, height: 0.05 })
+    }
+}
+
+/// 多点触控输入源
+///
+/// 每个触点按布局映射到一组按键，所有触点和 `press_virtual` 按下的键的并集就是当前按住的键；
+/// 手指滑出十字键方向或按键区域时对应的键自动松开，不影响通过 `press_virtual` 按住的键。
+#[derive(Debug, Clone, Default)]
+pub struct TouchInput {
+    layout: TouchLayout,
+    touches: Vec<(u64, Vec<Button>)>,
+    /// 通过 `press_virtual` 按住的键，与触点分开跟踪
+    pressed: Vec<Button>,
+    buttons: VirtualButtons,
+}
+
+impl TouchInput {
+    pub fn new(layout: TouchLayout) -> Self {
+        Self { layout, touches: Vec::new(), pressed: Vec::new(), buttons: VirtualButtons::new() }
+    }
+
+    pub fn layout(&self) -> &TouchLayout {
+        &self.layout
+    }
+
+    /// 替换布局，已有的触点全部松开
+    pub fn set_layout(&mut self, layout: TouchLayout) {
+        self.layout = layout;
+        self.touches.clear();
+        self.sync();
+    }
+
+    /// 触点按下或移动，`id` 由平台分配并在抬起前保持不变
+    pub fn touch(&mut self, id: u64, x: f32, y: f32) {
+        let buttons = self.layout.buttons_at(x, y);
+        match self.touches.iter_mut().find(|(existing, _)| *existing == id) {
+            Some((_, held)) => *held = buttons,
+            None => self.touches.push((id, buttons)),
+        }
+        self.sync();
+    }
+
+    /// 触点抬起或被系统取消
+    pub fn release(&mut self, id: u64) {
+        self.touches.retain(|(existing, _)| *existing != id);
+        self.sync();
+    }
+
+    /// 当前按住的键
+    pub fn held(&self) -> &[Button] {
+        self.buttons.held()
+    }
+
+    fn sync(&mut self) {
+        let wanted: Vec<Button> = Button::ALL
+            .into_iter()
+            .filter(|button| self.pressed.contains(button) || self.touches.iter().any(|(_, held)| held.contains(button)))
+            .collect();
+        for button in Button::ALL {
+            if wanted.contains(&button) {
+                self.buttons.press_virtual(button);
+            } else {
+                self.buttons.release_virtual(button);
+            }
+        }
+    }
+}
+
+impl InputSource for TouchInput {
+    fn poll(&mut self) -> Vec<InputEvent> {
+        self.buttons.poll()
+    }
+
+    fn press_virtual(&mut self, button: Button) {
+        if !self.pressed.contains(&button) {
+            self.pressed.push(button);
+        }
+        self.sync();
+    }
+
+    fn release_virtual(&mut self, button: Button) {
+        self.pressed.retain(|&pressed| pressed != button);
+        self.sync();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_maps_coordinates_to_buttons() {
+        let layout = TouchLayout::default();
+        assert_eq!(layout.buttons_at(0.32, 0.75), vec![Button::Right]);
+        assert_eq!(layout.buttons_at(0.2, 0.65), vec![Button::Up]);
+        assert_eq!(layout.buttons_at(0.12, 0.83), vec![Button::Down, Button::Left]);
+        assert!(layout.buttons_at(0.2, 0.75).is_empty());
+        assert_eq!(layout.buttons_at(0.85, 0.72), vec![Button::A]);
+        assert_eq!(layout.buttons_at(0.55, 0.94), vec![Button::Start]);
+        assert!(layout.buttons_at(0.5, 0.3).is_empty());
+
+        let custom = TouchLayout::new().with_button(Button::A, Region::Rect { x: 0.0, y: 0.0, width: 1.0, height: 1.0 });
+        assert_eq!(custom.buttons_at(0.5, 0.5), vec![Button::A]);
+    }
+
+    #[test]
+    fn test_multi_touch_and_virtual_presses() {
+        let mut input = TouchInput::new(TouchLayout::default());
+        input.touch(1, 0.32, 0.75);
+        input.touch(2, 0.85, 0.72);
+        assert_eq!(input.poll(), vec![
+            InputEvent { button: Button::Right, pressed: true },
+            InputEvent { button: Button::A, pressed: true },
+        ]);
+
+        // 手指从右滑到上，右键松开、上键按下
+        input.touch(1, 0.2, 0.62);
+        input.release(2);
+        assert_eq!(input.poll(), vec![
+            InputEvent { button: Button::Right, pressed: false },
+            InputEvent { button: Button::Up, pressed: true },
+            InputEvent { button: Button::A, pressed: false },
+        ]);
+        assert_eq!(input.held(), &[Button::Up]);
+
+        // 虚拟按下的键不会被触点的变化松开，触点也不会被虚拟松开
+        input.press_virtual(Button::Start);
+        input.touch(1, 0.5, 0.3);
+        input.touch(3, 0.55, 0.94);
+        input.release(3);
+        input.touch(1, 0.2, 0.62);
+        input.release_virtual(Button::Up);
+        assert_eq!(input.poll(), vec![
+            InputEvent { button: Button::Start, pressed: true },
+            InputEvent { button: Button::Up, pressed: false },
+            InputEvent { button: Button::Up, pressed: true },
+        ]);
+        assert_eq!(input.held(), &[Button::Start, Button::Up]);
+        input.release_virtual(Button::Start);
+        assert_eq!(input.held(), &[Button::Up]);
+
+        let mut buttons = VirtualButtons::new();
+        buttons.press_virtual(Button::Start);
+        buttons.press_virtual(Button::Start);
+        buttons.release_virtual(Button::Start);
+        buttons.release_virtual(Button::B);
+        assert_eq!(buttons.poll(), vec![
+            InputEvent { button: Button::Start, pressed: true },
+            InputEvent { button: Button::Start, pressed: false },
+        ]);
+    }
+}
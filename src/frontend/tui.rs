@@ -0,0 +1,303 @@
+//! 终端界面的文本排版
+//!
+//! 终端里中日韩文字、全角符号和大部分emoji占两列，组合符号和零宽字符不占列，
+//! 按字符数对齐会让边框错位。这里按显示宽度测量、截断和补齐文本，
+//! 面板边框在任何语言下都能对齐。
+
+use std::fmt::Write as _;
+
+/// 不占列的字符范围：组合符号、零宽字符和变体选择符
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x202A, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0x302A, 0x302D),
+    (0x3099, 0x309A),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0xE0100, 0xE01EF),
+];
+
+/// 占两列的字符范围：东亚宽字符、全角字符和emoji
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18CFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+fn in_ranges(ranges: &[(u32, u32)], code: u32) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < code {
+                std::cmp::Ordering::Less
+            } else if start > code {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// 字符在终端中占的列数，控制字符按0列计算
+pub fn char_width(c: char) -> usize {
+    let code = c as u32;
+    if code < 0x20 || (0x7F..0xA0).contains(&code) || in_ranges(ZERO_WIDTH, code) {
+        0
+    } else if in_ranges(WIDE, code) {
+        2
+    } else {
+        1
+    }
+}
+
+/// 文本在终端中占的列数
+pub fn text_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// 截断到不超过 `width` 列，被截断时以 `…` 结尾
+pub fn truncate(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = char_width(c);
+        // 为省略号留一列
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// 对齐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// 截断并用空格补齐到恰好 `width` 列
+pub fn pad(text: &str, width: usize, align: Align) -> String {
+    let text = truncate(text, width);
+    let space = width - text_width(&text);
+    let (left, right) = match align {
+        Align::Left => (0, space),
+        Align::Center => (space / 2, space - space / 2),
+        Align::Right => (space, 0),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+/// 带标题的边框面板
+///
+/// ```text
+/// ┌─ 游戏统计 ──────┐
+/// │ 分数:         0 │
+/// └─────────────────┘
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Panel {
+    title: String,
+    lines: Vec<(String, Align)>,
+    width: Option<usize>,
+}
+
+impl Panel {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self { title: title.into(), ..Self::default() }
+    }
+
+    /// 固定内容宽度（列），不设置时按标题和最长的行计算
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// 追加一行左对齐文本
+    pub fn line(self, text: impl Into<String>) -> Self {
+        self.aligned(text, Align::Left)
+    }
+
+    /// 追加一行指定对齐方式的文本
+    pub fn aligned(mut self, text: impl Into<String>, align: Align) -> Self {
+        self.lines.push((text.into(), align));
+        self
+    }
+
+    /// 左边标签、右边数值的一行，中间用空格填满；需要先用 `width` 固定宽度
+    pub fn field(self, label: &str, value: impl std::fmt::Display) -> Self {
+        let value = value.to_string();
+        let gap = self.width.unwrap_or(0).saturating_sub(text_width(label) + text_width(&value)).max(1);
+        self.line(format!("{}{}{}", label, " ".repeat(gap), value))
+    }
+
+    /// 内容宽度（列）
+    pub fn inner_width(&self) -> usize {
+        self.width.unwrap_or_else(|| {
+            let longest = self.lines.iter().map(|(text, _)| text_width(text)).max().unwrap_or(0);
+            longest.max(text_width(&self.title) + 2)
+        })
+    }
+
+    /// 排版后的每一行，每行都恰好占 `inner_width() + 4` 列
+    pub fn render(&self) -> Vec<String> {
+        let inner = self.inner_width();
+        let mut rows = Vec::with_capacity(self.lines.len() + 2);
+
+        // 标题两侧各留一个空格，左边至少一段横线
+        let title = truncate(&self.title, inner.saturating_sub(1));
+        let top = if title.is_empty() {
+            format!("┌{}┐", "─".repeat(inner + 2))
+        } else {
+            format!("┌─ {} {}┐", title, "─".repeat(inner - 1 - text_width(&title)))
+        };
+        rows.push(top);
+        for (text, align) in &self.lines {
+            rows.push(format!("│ {} │", pad(text, inner, *align)));
+        }
+        rows.push(format!("└{}┘", "─".repeat(inner + 2)));
+        rows
+    }
+
+    /// 从终端第 `row` 行第 `col` 列（从1开始）开始输出，不换行
+    pub fn draw(&self, row: usize, col: usize) {
+        print!("{}", self.to_ansi(row, col));
+    }
+
+    /// 带光标定位转义序列的输出
+    pub fn to_ansi(&self, row: usize, col: usize) -> String {
+        let mut out = String::new();
+        for (i, line) in self.render().iter().enumerate() {
+            let _ = write!(out, "\x1B[{};{}H{}", row + i, col, line);
+        }
+        out
+    }
+
+    /// 面板占的行数
+    pub fn height(&self) -> usize {
+        self.lines.len() + 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width_measurement_and_truncation() {
+        assert_eq!(text_width("Score"), 5);
+        assert_eq!(text_width("游戏统计"), 8);
+        assert_eq!(text_width("🎮 A"), 4);
+        assert_eq!(text_width("e\u{301}"), 1);
+        assert_eq!(text_width("│─┌"), 3);
+
+        assert_eq!(truncate("游戏统计", 8), "游戏统计");
+        assert_eq!(truncate("游戏统计", 6), "游戏…");
+        assert_eq!(truncate("游戏统计", 4), "游…");
+        assert_eq!(text_width(&truncate("a游戏", 3)), 2);
+        assert_eq!(pad("分数", 8, Align::Right), "    分数");
+        assert_eq!(pad("游戏中", 9, Align::Center), " 游戏中  ");
+        assert_eq!(text_width(&pad("游戏统计信息", 5, Align::Left)), 5);
+    }
+
+    #[test]
+    fn test_panel_borders_line_up_in_any_language() {
+        for title in ["游戏统计", "Statistics"] {
+            let panel = Panel::new(title)
+                .width(14)
+                .field("分数:", 1200)
+                .field("Lines:", 8)
+                .aligned("游戏中", Align::Center)
+                .line("非常非常非常长的一行说明文字");
+            let rows = panel.render();
+            assert_eq!(rows.len(), panel.height());
+            for row in &rows {
+                assert_eq!(text_width(row), 18, "{}", row);
+            }
+            assert_eq!(rows[1], "│ 分数:     1200 │");
+        }
+        assert_eq!(Panel::new("游戏统计").line("ab").render()[0], "┌─ 游戏统计 ─┐");
+        assert!(Panel::new("T").line("x").to_ansi(3, 25).starts_with("\x1B[3;25H┌─ T ─┐"));
+    }
+}
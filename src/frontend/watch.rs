@@ -0,0 +1,267 @@
+//! ROM热重载
+//!
+//! `FileWatcher` 轮询文件的修改时间和大小，文件在一段时间内不再变化才报告，
+//! 避免读到汇编器写了一半的ROM。`HotReload` 在ROM或符号文件变化后重新加载；
+//! 设置了标记点时，第一次运行到标记点的快照会恢复到新ROM上：内存和寄存器沿用快照，
+//! ROM区域换成新内容，PC设为标记在新符号表中的地址，改完代码不用从头再走一遍。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::debug::SymbolTable;
+use crate::emulator::SaveState;
+use crate::i18n::{tr, trf, Msg};
+
+use super::Emulator;
+
+/// 卡带ROM在地址空间中的范围结束位置
+const ROM_AREA_END: usize = 0x8000;
+
+/// 文件的修改时间和大小，文件不存在时为 `None`
+type FileStamp = Option<(Option<SystemTime>, u64)>;
+
+fn stamp(path: &Path) -> FileStamp {
+    fs::metadata(path).ok().map(|metadata| (metadata.modified().ok(), metadata.len()))
+}
+
+/// 轮询式文件监视
+#[derive(Debug, Clone)]
+pub struct FileWatcher {
+    files: Vec<(PathBuf, FileStamp)>,
+    settle: Duration,
+    changed_at: Option<Instant>,
+}
+
+impl FileWatcher {
+    /// 默认的稳定时间
+    pub const DEFAULT_SETTLE: Duration = Duration::from_millis(200);
+
+    pub fn new() -> Self {
+        Self { files: Vec::new(), settle: Self::DEFAULT_SETTLE, changed_at: None }
+    }
+
+    /// 监视一个文件，文件可以暂时不存在，之后创建也算变化
+    pub fn watch(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let current = stamp(&path);
+        self.files.push((path, current));
+        self
+    }
+
+    /// 文件变化后需要保持不变多久才报告
+    pub fn settle(mut self, settle: Duration) -> Self {
+        self.settle = settle;
+        self
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// 检查文件，有变化并且已经稳定时返回 `true`
+    pub fn poll(&mut self, now: Instant) -> bool {
+        let mut changed = false;
+        for (path, previous) in &mut self.files {
+            let current = stamp(path);
+            if current != *previous {
+                *previous = current;
+                changed = true;
+            }
+        }
+        if changed {
+            self.changed_at = Some(now);
+            return false;
+        }
+        match self.changed_at {
+            Some(at) if now.saturating_duration_since(at) >= self.settle => {
+                self.changed_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for FileWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 一次加载的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reload {
+    /// 第几次加载，从1开始
+    pub generation: u32,
+    pub rom_bytes: usize,
+    pub symbols: usize,
+    /// 恢复了标记点快照时为标记的地址
+    pub restored: Option<u16>,
+}
+
+/// ROM热重载会话
+#[derive(Debug, Clone)]
+pub struct HotReload {
+    rom_path: PathBuf,
+    sym_path: PathBuf,
+    /// 符号文件是否由用户指定，指定的文件必须存在
+    sym_required: bool,
+    mark: Option<String>,
+    symbols: SymbolTable,
+    watcher: FileWatcher,
+    mark_state: Option<SaveState>,
+    generation: u32,
+}
+
+impl HotReload {
+    /// 监视ROM及其同名的 `.sym` 文件（存在时读取）
+    pub fn new(rom_path: impl Into<PathBuf>) -> Self {
+        let rom_path = rom_path.into();
+        let sym_path = rom_path.with_extension("sym");
+        Self {
+            watcher: FileWatcher::new().watch(&rom_path).watch(&sym_path),
+            rom_path,
+            sym_path,
+            sym_required: false,
+            mark: None,
+            symbols: SymbolTable::new(),
+            mark_state: None,
+            generation: 0,
+        }
+    }
+
+    /// 使用指定的符号文件
+    pub fn with_symbols(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sym_path = path.into();
+        self.sym_required = true;
+        self.watcher = FileWatcher::new().settle(self.watcher.settle).watch(&self.rom_path).watch(&self.sym_path);
+        self
+    }
+
+    /// 设置标记点：符号名或十六进制地址
+    pub fn with_mark(mut self, mark: &str) -> Self {
+        self.mark = Some(mark.to_string());
+        self
+    }
+
+    /// 文件变化后等待稳定的时间
+    pub fn with_settle(mut self, settle: Duration) -> Self {
+        self.watcher = self.watcher.settle(settle);
+        self
+    }
+
+    pub fn rom_path(&self) -> &Path {
+        &self.rom_path
+    }
+
+    pub fn mark(&self) -> Option<&str> {
+        self.mark.as_deref()
+    }
+
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
+
+    /// 标记点在当前符号表中的地址，没有设置标记时为 `None`
+    pub fn mark_address(&self) -> Result<Option<u16>, String> {
+        self.mark.as_deref().map(|mark| self.symbols.resolve(mark)).transpose()
+    }
+
+    /// 是否已经保存了标记点的快照
+    pub fn has_mark_state(&self) -> bool {
+        self.mark_state.is_some()
+    }
+
+    /// 保存标记点的快照，在模拟器停在标记地址时调用
+    pub fn capture<E: Emulator + ?Sized>(&mut self, emulator: &E) -> Result<(), String> {
+        let state = emulator.snapshot().ok_or_else(|| tr(Msg::SlotUnsupported).to_string())?;
+        self.mark_state = Some(state);
+        Ok(())
+    }
+
+    /// 丢弃标记点的快照，下次运行到标记点时重新保存
+    pub fn clear_mark_state(&mut self) {
+        self.mark_state = None;
+    }
+
+    /// 读取ROM和符号文件，重置模拟器并加载；有标记点快照时恢复到新ROM上
+    pub fn load<E: Emulator + ?Sized>(&mut self, emulator: &mut E) -> Result<Reload, String> {
+        let rom = fs::read(&self.rom_path).map_err(|e| trf(Msg::CliReadFailed, &[&self.rom_path.display(), &e]))?;
+        self.symbols = if self.sym_required || self.sym_path.exists() {
+            SymbolTable::from_file(&self.sym_path)?
+        } else {
+            SymbolTable::new()
+        };
+
+        emulator.reset();
+        emulator.load_rom(&rom)?;
+        self.generation += 1;
+
+        let restored = match &self.mark_state {
+            Some(state) => {
+                let address = self.mark_address()?.ok_or_else(|| tr(Msg::RunNoMark).to_string())?;
+                let mut state = state.clone();
+                let rom_area = rom.len().min(ROM_AREA_END).min(state.memory.len());
+                state.memory[..rom_area].copy_from_slice(&rom[..rom_area]);
+                state.pc = address;
+                emulator.restore(&state)?;
+                Some(address)
+            }
+            None => None,
+        };
+
+        Ok(Reload { generation: self.generation, rom_bytes: rom.len(), symbols: self.symbols.len(), restored })
+    }
+
+    /// 检查文件变化，变化稳定后重新加载并返回结果
+    pub fn poll<E: Emulator + ?Sized>(&mut self, emulator: &mut E, now: Instant) -> Option<Result<Reload, String>> {
+        self.watcher.poll(now).then(|| self.load(emulator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::GameBoy;
+    use crate::rom::RomGenerator;
+
+    #[test]
+    fn test_reload_restores_mark_state_on_new_rom() {
+        let dir = std::env::temp_dir().join(format!("gamelife_watch_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let rom_path = dir.join("game.gb");
+        let sym_path = dir.join("game.sym");
+        fs::write(&rom_path, RomGenerator::new("WATCH").program(0x150, &[0x0C]).build().unwrap()).unwrap();
+        fs::write(&sym_path, "00:0150 Main\n").unwrap();
+
+        let mut gameboy = GameBoy::new();
+        let mut reload = HotReload::new(&rom_path).with_mark("Main").with_settle(Duration::ZERO);
+        let first = reload.load(&mut gameboy).unwrap();
+        assert_eq!((first.generation, first.symbols, first.restored), (1, 1, None));
+        assert_eq!(reload.mark_address(), Ok(Some(0x150)));
+
+        // 模拟运行到标记点时的状态
+        let mut state = gameboy.save_state();
+        state.pc = 0x150;
+        state.registers.c = 7;
+        state.memory[0xC000] = 0x42;
+        gameboy.load_state(&state).unwrap();
+        reload.capture(&gameboy).unwrap();
+
+        // 重新汇编：程序变长，标记移动到0x152
+        let start = Instant::now();
+        assert!(reload.poll(&mut gameboy, start).is_none());
+        fs::write(&rom_path, RomGenerator::new("WATCH").program(0x150, &[0x00, 0x00, 0x0D]).rom_size(1).build().unwrap()).unwrap();
+        fs::write(&sym_path, "00:0152 Main\n").unwrap();
+        assert!(reload.poll(&mut gameboy, start).is_none());
+        let second = reload.poll(&mut gameboy, start).unwrap().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!((second.generation, second.restored), (2, Some(0x152)));
+        let cpu = gameboy.get_cpu_state();
+        assert_eq!((cpu.pc, cpu.registers.c), (0x152, 7));
+        assert_eq!(gameboy.memory()[0xC000], 0x42);
+        assert_eq!(gameboy.memory()[0x152], 0x0D);
+    }
+}
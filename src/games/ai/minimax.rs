@@ -0,0 +1,382 @@
+//! Minimax搜索引擎
+//!
+//! 基于negamax + alpha-beta剪枝实现，支持迭代加深和每步时间预算。
+//! 超出预算时放弃当前未完成的迭代，返回上一轮完整搜索的结果，
+//! 因此无论棋盘规模多大，响应延迟都被限制在预算之内。
+
+use std::time::{Duration, Instant};
+
+/// 胜负分值基准，绝对值超过 `WIN_SCORE / 2` 的分数表示必胜/必败局面
+pub const WIN_SCORE: i32 = 1_000_000;
+
+/// 搜索窗口边界
+const INFINITY: i32 = i32::MAX - 1;
+
+/// 每搜索多少个节点检查一次时间
+const TIME_CHECK_INTERVAL: u64 = 64;
+
+/// 可被minimax搜索的博弈局面
+pub trait GameTree: Clone {
+    /// 走法类型
+    type Move: Copy + PartialEq + std::fmt::Debug;
+
+    /// 当前局面下的所有合法走法
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    /// 执行走法，之后轮到对手行动
+    fn apply_move(&mut self, mv: Self::Move);
+
+    /// 对局是否已经结束
+    fn is_terminal(&self) -> bool;
+
+    /// 从当前行动方的视角评估局面
+    ///
+    /// 行动方已落败时应返回 `-WIN_SCORE`，搜索会按层数修正分值以偏好更快的胜利
+    fn evaluate(&self) -> i32;
+}
+
+/// 搜索预算
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchBudget {
+    /// 最大搜索深度（层）
+    pub max_depth: u32,
+    /// 每步的时间预算，`None` 表示不限时
+    pub time_limit: Option<Duration>,
+}
+
+impl SearchBudget {
+    /// 只限制深度的预算
+    pub fn depth(max_depth: u32) -> Self {
+        Self {
+            max_depth,
+            time_limit: None,
+        }
+    }
+
+    /// 只限制时间的预算（毫秒），深度由迭代加深自动决定
+    pub fn millis(ms: u64) -> Self {
+        Self {
+            max_depth: u32::MAX,
+            time_limit: Some(Duration::from_millis(ms)),
+        }
+    }
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self::millis(100)
+    }
+}
+
+/// 根节点单个候选走法的评估
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveEvaluation<M> {
+    /// 候选走法
+    pub mv: M,
+    /// 该走法的精确分值（行动方视角）
+    pub score: i32,
+}
+
+impl<M> MoveEvaluation<M> {
+    /// 该走法是否导向强制胜负
+    pub fn is_decisive(&self) -> bool {
+        self.score.abs() >= WIN_SCORE / 2
+    }
+}
+
+/// 一次搜索的结果
+#[derive(Debug, Clone)]
+pub struct SearchResult<M> {
+    /// 最佳走法，没有合法走法时为 `None`
+    pub best_move: Option<M>,
+    /// 最佳走法的分值（行动方视角）
+    pub score: i32,
+    /// 完整搜索完成的最大深度
+    pub depth_reached: u32,
+    /// 访问的节点总数
+    pub nodes: u64,
+    /// 搜索耗时
+    pub elapsed: Duration,
+    /// 是否因时间预算耗尽而提前结束
+    pub timed_out: bool,
+    /// 最后一轮完整迭代中每个根走法的评估，按分值从高到低排序
+    ///
+    /// 仅在开启 `collect_move_scores` 时填充
+    pub candidates: Vec<MoveEvaluation<M>>,
+}
+
+/// Minimax搜索引擎
+#[derive(Debug, Clone)]
+pub struct MinimaxEngine {
+    budget: SearchBudget,
+    nodes: u64,
+    deadline: Option<Instant>,
+    aborted: bool,
+    depth_limited: bool,
+    collect_move_scores: bool,
+}
+
+impl MinimaxEngine {
+    /// 创建新的搜索引擎
+    pub fn new(budget: SearchBudget) -> Self {
+        Self {
+            budget,
+            nodes: 0,
+            deadline: None,
+            aborted: false,
+            depth_limited: false,
+            collect_move_scores: false,
+        }
+    }
+
+    /// 获取搜索预算
+    pub fn budget(&self) -> SearchBudget {
+        self.budget
+    }
+
+    /// 设置搜索预算
+    pub fn set_budget(&mut self, budget: SearchBudget) {
+        self.budget = budget;
+    }
+
+    /// 是否收集每个根走法的精确分值
+    ///
+    /// 开启后根节点对每个走法使用完整窗口搜索，分值可用于可视化，但会增加节点数
+    pub fn set_collect_move_scores(&mut self, enabled: bool) {
+        self.collect_move_scores = enabled;
+    }
+
+    /// 使用迭代加深搜索当前局面的最佳走法
+    pub fn search<G: GameTree>(&mut self, root: &G) -> SearchResult<G::Move> {
+        let start = Instant::now();
+        self.deadline = self.budget.time_limit.map(|limit| start + limit);
+        self.nodes = 0;
+        self.aborted = false;
+
+        let mut moves = if root.is_terminal() { Vec::new() } else { root.legal_moves() };
+        let mut result = SearchResult {
+            best_move: moves.first().copied(),
+            score: 0,
+            depth_reached: 0,
+            nodes: 0,
+            elapsed: Duration::ZERO,
+            timed_out: false,
+            candidates: Vec::new(),
+        };
+
+        if moves.is_empty() {
+            result.score = root.evaluate();
+        }
+
+        let mut depth = 1;
+        while !moves.is_empty() && depth <= self.budget.max_depth {
+            self.depth_limited = false;
+
+            match self.search_root(root, &moves, depth) {
+                Some((best_move, score, mut candidates)) => {
+                    result.best_move = Some(best_move);
+                    result.score = score;
+                    result.depth_reached = depth;
+                    candidates.sort_by(|a, b| b.score.cmp(&a.score));
+                    result.candidates = candidates;
+
+                    // 上一轮的最佳走法最先搜索，提高下一轮的剪枝效率
+                    if let Some(index) = moves.iter().position(|&mv| mv == best_move) {
+                        moves[..=index].rotate_right(1);
+                    }
+
+                    // 整棵树已搜索完，或已找到强制胜负，继续加深没有意义
+                    if !self.depth_limited || score.abs() >= WIN_SCORE / 2 {
+                        break;
+                    }
+                }
+                None => {
+                    result.timed_out = true;
+                    break;
+                }
+            }
+
+            depth += 1;
+        }
+
+        result.nodes = self.nodes;
+        result.elapsed = start.elapsed();
+        result
+    }
+
+    /// 搜索根节点，超时返回 `None`
+    fn search_root<G: GameTree>(
+        &mut self,
+        root: &G,
+        moves: &[G::Move],
+        depth: u32,
+    ) -> Option<(G::Move, i32, Vec<MoveEvaluation<G::Move>>)> {
+        let mut alpha = -INFINITY;
+        let mut best = None;
+        let mut candidates = Vec::new();
+
+        for &mv in moves {
+            let mut child = root.clone();
+            child.apply_move(mv);
+
+            // 收集分值时不收窄窗口，保证每个走法的分值都是精确值
+            let window = if self.collect_move_scores { -INFINITY } else { alpha };
+            let score = -self.negamax(&child, depth - 1, 1, -INFINITY, -window)?;
+            if self.collect_move_scores {
+                candidates.push(MoveEvaluation { mv, score });
+            }
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some((mv, score));
+            }
+        }
+
+        best.map(|(mv, score)| (mv, score, candidates))
+    }
+
+    /// Negamax + alpha-beta剪枝，超时返回 `None`
+    fn negamax<G: GameTree>(&mut self, node: &G, depth: u32, ply: i32, mut alpha: i32, beta: i32) -> Option<i32> {
+        self.nodes += 1;
+        if self.out_of_time() {
+            return None;
+        }
+
+        if node.is_terminal() {
+            return Some(Self::adjust_for_ply(node.evaluate(), ply));
+        }
+
+        if depth == 0 {
+            self.depth_limited = true;
+            return Some(node.evaluate());
+        }
+
+        let moves = node.legal_moves();
+        if moves.is_empty() {
+            return Some(Self::adjust_for_ply(node.evaluate(), ply));
+        }
+
+        let mut best = -INFINITY;
+        for mv in moves {
+            let mut child = node.clone();
+            child.apply_move(mv);
+
+            let score = -self.negamax(&child, depth - 1, ply + 1, -beta, -alpha)?;
+            best = best.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        Some(best)
+    }
+
+    /// 检查时间预算是否耗尽
+    fn out_of_time(&mut self) -> bool {
+        if !self.aborted && self.nodes % TIME_CHECK_INTERVAL == 0 {
+            if let Some(deadline) = self.deadline {
+                self.aborted = Instant::now() >= deadline;
+            }
+        }
+        self.aborted
+    }
+
+    /// 按层数修正胜负分值：越快的胜利分越高，越慢的失败分越高
+    fn adjust_for_ply(score: i32, ply: i32) -> i32 {
+        if score <= -WIN_SCORE / 2 {
+            score + ply
+        } else if score >= WIN_SCORE / 2 {
+            score - ply
+        } else {
+            score
+        }
+    }
+}
+
+impl Default for MinimaxEngine {
+    fn default() -> Self {
+        Self::new(SearchBudget::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 简单的取石子游戏：每次取1-3颗，取走最后一颗者获胜
+    #[derive(Clone)]
+    struct Nim {
+        stones: u32,
+    }
+
+    impl GameTree for Nim {
+        type Move = u32;
+
+        fn legal_moves(&self) -> Vec<u32> {
+            (1..=3).filter(|&n| n <= self.stones).collect()
+        }
+
+        fn apply_move(&mut self, mv: u32) {
+            self.stones -= mv;
+        }
+
+        fn is_terminal(&self) -> bool {
+            self.stones == 0
+        }
+
+        fn evaluate(&self) -> i32 {
+            if self.stones == 0 { -WIN_SCORE } else { 0 }
+        }
+    }
+
+    #[test]
+    fn test_finds_winning_move() {
+        let mut engine = MinimaxEngine::new(SearchBudget::depth(20));
+        let result = engine.search(&Nim { stones: 10 });
+
+        // 必胜策略：留给对手4的倍数
+        assert_eq!(result.best_move, Some(2));
+        assert!(result.score >= WIN_SCORE / 2);
+        assert!(result.nodes > 0);
+    }
+
+    #[test]
+    fn test_depth_limit_is_respected() {
+        let mut engine = MinimaxEngine::new(SearchBudget::depth(2));
+        let result = engine.search(&Nim { stones: 30 });
+
+        assert_eq!(result.depth_reached, 2);
+        assert!(!result.timed_out);
+    }
+
+    #[test]
+    fn test_time_budget_cuts_off_gracefully() {
+        let mut engine = MinimaxEngine::new(SearchBudget::millis(5));
+        let result = engine.search(&Nim { stones: 200 });
+
+        assert!(result.timed_out);
+        assert!(result.best_move.is_some());
+    }
+
+    #[test]
+    fn test_collects_exact_move_scores() {
+        let mut engine = MinimaxEngine::new(SearchBudget::depth(20));
+        engine.set_collect_move_scores(true);
+        let result = engine.search(&Nim { stones: 6 });
+
+        assert_eq!(result.candidates.len(), 3);
+        assert_eq!(result.candidates[0].mv, 2);
+        assert!(result.candidates[0].is_decisive() && result.candidates[0].score > 0);
+        // 找到强制胜利后不再加深，其余走法只保证不优于最佳走法
+        assert!(result.candidates[1..].iter().all(|c| c.score <= 0));
+    }
+
+    #[test]
+    fn test_terminal_root_has_no_move() {
+        let mut engine = MinimaxEngine::default();
+        let result = engine.search(&Nim { stones: 0 });
+
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.depth_reached, 0);
+    }
+}
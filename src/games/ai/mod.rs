@@ -0,0 +1,8 @@
+//! 博弈AI模块
+//! 
+//! 提供与具体游戏无关的搜索引擎，各棋类游戏通过实现 `GameTree` 接入
+
+pub mod minimax;
+
+// Re-export main types
+pub use minimax::{GameTree, MinimaxEngine, MoveEvaluation, SearchBudget, SearchResult, WIN_SCORE};
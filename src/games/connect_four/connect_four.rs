@@ -0,0 +1,221 @@
+//! 四子棋核心实现
+//!
+//! 棋子从列顶落下，横、竖、斜任意方向连成四子即获胜
+
+use crate::games::ai::{GameTree, WIN_SCORE};
+
+/// 棋盘列数
+pub const COLUMNS: usize = 7;
+/// 棋盘行数
+pub const ROWS: usize = 6;
+
+/// 棋子颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Disc {
+    Red,
+    Yellow,
+}
+
+impl Disc {
+    /// 对手的颜色
+    pub fn opponent(self) -> Disc {
+        match self {
+            Disc::Red => Disc::Yellow,
+            Disc::Yellow => Disc::Red,
+        }
+    }
+}
+
+/// 四子棋棋盘，`cells[row][col]` 中第0行为底部
+#[derive(Debug, Clone)]
+pub struct ConnectFourBoard {
+    cells: [[Option<Disc>; COLUMNS]; ROWS],
+    heights: [usize; COLUMNS],
+    pub current_player: Disc,
+    pub winner: Option<Disc>,
+    pub move_count: usize,
+}
+
+impl ConnectFourBoard {
+    /// 创建空棋盘，红方先手
+    pub fn new() -> Self {
+        Self {
+            cells: [[None; COLUMNS]; ROWS],
+            heights: [0; COLUMNS],
+            current_player: Disc::Red,
+            winner: None,
+            move_count: 0,
+        }
+    }
+
+    /// 在指定列落子
+    pub fn drop_disc(&mut self, col: usize) -> Result<(), String> {
+        if col >= COLUMNS {
+            return Err("列超出范围".to_string());
+        }
+        if self.heights[col] >= ROWS {
+            return Err("该列已满".to_string());
+        }
+        if self.is_over() {
+            return Err("游戏已结束".to_string());
+        }
+
+        let row = self.heights[col];
+        self.cells[row][col] = Some(self.current_player);
+        self.heights[col] += 1;
+        self.move_count += 1;
+
+        if self.connects_four(row, col) {
+            self.winner = Some(self.current_player);
+        } else {
+            self.current_player = self.current_player.opponent();
+        }
+
+        Ok(())
+    }
+
+    /// 获取格子内容
+    pub fn get(&self, row: usize, col: usize) -> Option<Disc> {
+        self.cells[row][col]
+    }
+
+    /// 棋盘是否已满
+    pub fn is_full(&self) -> bool {
+        self.move_count == COLUMNS * ROWS
+    }
+
+    /// 对局是否结束
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some() || self.is_full()
+    }
+
+    /// 可落子的列，从中间向两侧排列以改善剪枝
+    pub fn available_columns(&self) -> Vec<usize> {
+        const ORDER: [usize; COLUMNS] = [3, 2, 4, 1, 5, 0, 6];
+        ORDER.iter().copied().filter(|&col| self.heights[col] < ROWS).collect()
+    }
+
+    /// 检查刚落下的棋子是否连成四子
+    fn connects_four(&self, row: usize, col: usize) -> bool {
+        let disc = self.cells[row][col];
+        let directions = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+        directions.iter().any(|&(dr, dc)| {
+            let count_dir = |sign: i32| {
+                let mut count = 0;
+                let (mut r, mut c) = (row as i32 + dr * sign, col as i32 + dc * sign);
+                while r >= 0 && r < ROWS as i32 && c >= 0 && c < COLUMNS as i32
+                    && self.cells[r as usize][c as usize] == disc
+                {
+                    count += 1;
+                    r += dr * sign;
+                    c += dc * sign;
+                }
+                count
+            };
+            1 + count_dir(1) + count_dir(-1) >= 4
+        })
+    }
+
+    /// 启发式评估：按每个四格窗口中的棋子数计分
+    fn window_score(&self, disc: Disc) -> i32 {
+        let mut score = 0;
+        let directions = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+        for row in 0..ROWS as i32 {
+            for col in 0..COLUMNS as i32 {
+                for &(dr, dc) in &directions {
+                    let end_r = row + dr * 3;
+                    let end_c = col + dc * 3;
+                    if end_r < 0 || end_r >= ROWS as i32 || end_c < 0 || end_c >= COLUMNS as i32 {
+                        continue;
+                    }
+
+                    let mut mine = 0;
+                    let mut theirs = 0;
+                    for i in 0..4 {
+                        match self.cells[(row + dr * i) as usize][(col + dc * i) as usize] {
+                            Some(d) if d == disc => mine += 1,
+                            Some(_) => theirs += 1,
+                            None => {}
+                        }
+                    }
+
+                    score += match (mine, theirs) {
+                        (3, 0) => 5,
+                        (2, 0) => 2,
+                        (0, 3) => -5,
+                        (0, 2) => -2,
+                        _ => 0,
+                    };
+                }
+            }
+        }
+
+        // 中间列更有价值
+        score + (0..ROWS).filter(|&r| self.cells[r][COLUMNS / 2] == Some(disc)).count() as i32 * 3
+    }
+}
+
+impl Default for ConnectFourBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameTree for ConnectFourBoard {
+    type Move = usize;
+
+    fn legal_moves(&self) -> Vec<usize> {
+        if self.is_over() {
+            Vec::new()
+        } else {
+            self.available_columns()
+        }
+    }
+
+    fn apply_move(&mut self, mv: usize) {
+        let _ = self.drop_disc(mv);
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.is_over()
+    }
+
+    fn evaluate(&self) -> i32 {
+        match self.winner {
+            // 只有刚落子的一方可能获胜，因此轮到的一方必然落败
+            Some(_) => -WIN_SCORE,
+            None if self.is_full() => 0,
+            None => self.window_score(self.current_player),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::ai::{MinimaxEngine, SearchBudget};
+
+    #[test]
+    fn test_vertical_win() {
+        let mut board = ConnectFourBoard::new();
+        for col in [0, 1, 0, 1, 0, 1, 0] {
+            board.drop_disc(col).unwrap();
+        }
+        assert_eq!(board.winner, Some(Disc::Red));
+        assert!(board.drop_disc(2).is_err());
+    }
+
+    #[test]
+    fn test_engine_blocks_threat() {
+        let mut board = ConnectFourBoard::new();
+        // 红方在底行占据0-2列，黄方必须堵第3列
+        for col in [0, 6, 1, 6, 2] {
+            board.drop_disc(col).unwrap();
+        }
+
+        let mut engine = MinimaxEngine::new(SearchBudget::depth(4));
+        assert_eq!(engine.search(&board).best_move, Some(3));
+    }
+}
@@ -0,0 +1,8 @@
+//! 四子棋游戏模块
+//! 
+//! 实现7x6的四子棋，接入通用minimax搜索引擎
+
+pub mod connect_four;
+
+// Re-export main types
+pub use connect_four::*;
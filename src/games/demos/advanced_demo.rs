@@ -104,8 +104,9 @@ fn main() -> Result<(), String> {
     
     // 生成ROM文件
     println!("🎮 生成ROM文件...");
-    let mut rom_generator = RomGenerator::new("ADVANCED DEMO");
-    rom_generator.add_program(0x100, &program);
+    let rom_generator = RomGenerator::new("ADVANCED DEMO")
+        .program(0x150, &program)
+        .program(0x200, &program_at_200);
     
     let filename = "advanced_demo.gb";
     rom_generator.save_rom(filename).map_err(|e| e.to_string())?;
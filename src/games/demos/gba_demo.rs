@@ -61,9 +61,9 @@ fn main() -> Result<(), String> {
                         gba.get_gpu_state().current_scanline
                     );
                     
-                    println!("   📊 性能统计: FPS={:.2}, CPU使用率={:.2}%", 
+                    println!("   📊 性能统计: FPS={:.2}, IPS={:.0}", 
                         stats.fps,
-                        stats.cpu_usage * 100.0
+                        stats.instructions_per_second
                     );
                     
                     if frame % 30 == 0 {
@@ -92,7 +92,9 @@ fn main() -> Result<(), String> {
     println!("   🎨 总帧数: {}", gba.get_gpu_state().get_stats().frames_rendered);
     println!("   🖼️  总像素数: {}", gba.get_gpu_state().get_stats().pixels_drawn);
     println!("   ⚡ 平均FPS: {:.2}", gba.get_stats().fps);
-    println!("   💻 CPU使用率: {:.2}%", gba.get_stats().cpu_usage * 100.0);
+    println!("   💻 IPS: {:.0}", gba.get_stats().instructions_per_second);
+    println!("   📦 内存带宽: {:.0} 字节/秒", gba.get_stats().memory_bandwidth);
+    println!("   📺 扫描线平均: {:.3} 微秒", gba.get_stats().scanline_time_us);
     println!("   🎮 演示步数: {}", step_count);
     
     // 显示GBA分析
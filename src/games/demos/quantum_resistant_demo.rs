@@ -118,8 +118,7 @@ fn main() -> Result<(), String> {
     
     // 生成抗量子算法ROM文件
     println!("🔐 生成抗量子算法演示ROM...");
-    let mut rom_generator = RomGenerator::new("QUANTUM RESIST");
-    rom_generator.add_program(0x100, &quantum_resistant_program);
+    let rom_generator = RomGenerator::new("QUANTUM RESIST").program(0x150, &quantum_resistant_program);
     
     let filename = "quantum_resistant_demo.gb";
     rom_generator.save_rom(filename).map_err(|e| e.to_string())?;
@@ -8,14 +8,11 @@ fn main() {
     // 创建模拟器实例（用于验证）
     let _gameboy = GameBoy::new();
     
-    // 创建ROM生成器
-    let mut rom_generator = RomGenerator::new("LIFE GAME");
-    
     // 定义生命游戏程序
     let life_game_program = create_life_game_program();
     
-    // 将程序添加到ROM
-    rom_generator.add_program(0x150, &life_game_program);
+    // 创建ROM生成器并将程序添加到ROM
+    let rom_generator = RomGenerator::new("LIFE GAME").program(0x150, &life_game_program);
     
     // 生成ROM文件
     match rom_generator.save_rom("life_game.gb") {
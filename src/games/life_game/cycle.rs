@@ -0,0 +1,184 @@
+//! 代际哈希与周期检测
+//!
+//! 用Zobrist哈希给每一代的网格生成64位指纹：每个格子分配一个随机键，
+//! 网格的哈希是所有活细胞键的异或。记录每个哈希第一次出现的代数，
+//! 再次出现时即可得到任意周期的循环，以及进入循环之前的前周期长度。
+
+use std::collections::HashMap;
+
+use super::new_life_game::LifeGrid;
+
+/// 固定种子，保证同样尺寸的网格得到同样的哈希
+const DEFAULT_SEED: u64 = 0x4C49_4645_5A4F_4252;
+
+/// SplitMix64，用于生成Zobrist键
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Zobrist键表
+#[derive(Debug, Clone)]
+pub struct ZobristTable {
+    width: usize,
+    height: usize,
+    keys: Vec<u64>,
+}
+
+impl ZobristTable {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_seed(width, height, DEFAULT_SEED)
+    }
+
+    pub fn with_seed(width: usize, height: usize, seed: u64) -> Self {
+        let mut state = seed;
+        let keys = (0..width * height).map(|_| splitmix64(&mut state)).collect();
+        Self { width, height, keys }
+    }
+
+    /// 格子的键
+    pub fn key(&self, x: usize, y: usize) -> u64 {
+        self.keys[y * self.width + x]
+    }
+
+    /// 网格的哈希，网格尺寸必须与键表一致
+    pub fn hash(&self, grid: &LifeGrid) -> u64 {
+        debug_assert_eq!((grid.width(), grid.height()), (self.width, self.height));
+        let mut hash = 0;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if grid.get(x, y) {
+                    hash ^= self.key(x, y);
+                }
+            }
+        }
+        hash
+    }
+
+    /// 翻转一个格子后的哈希，用于增量更新
+    pub fn toggle(&self, hash: u64, x: usize, y: usize) -> u64 {
+        hash ^ self.key(x, y)
+    }
+}
+
+/// 检测到的循环
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// 循环周期，1为静止
+    pub period: u32,
+    /// 第一次进入循环的代数
+    pub pre_period: u32,
+}
+
+impl Cycle {
+    /// 是否为静止（包括全部死亡）
+    pub fn is_still(&self) -> bool {
+        self.period == 1
+    }
+}
+
+/// 周期检测器
+#[derive(Debug, Clone)]
+pub struct CycleDetector {
+    table: ZobristTable,
+    /// 哈希 -> 第一次出现的代数
+    history: HashMap<u64, u32>,
+    cycle: Option<Cycle>,
+}
+
+impl CycleDetector {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { table: ZobristTable::new(width, height), history: HashMap::new(), cycle: None }
+    }
+
+    /// 记录当前代，如果与之前某一代相同则返回循环
+    ///
+    /// 64位哈希的碰撞概率可以忽略，检测到相同哈希即认为网格相同
+    pub fn observe(&mut self, grid: &LifeGrid) -> Option<Cycle> {
+        if self.cycle.is_some() {
+            return self.cycle;
+        }
+        let hash = self.table.hash(grid);
+        let generation = grid.generation();
+        match self.history.get(&hash) {
+            Some(&first) => {
+                self.cycle = Some(Cycle { period: generation - first, pre_period: first });
+                self.cycle
+            }
+            None => {
+                self.history.insert(hash, generation);
+                None
+            }
+        }
+    }
+
+    /// 已检测到的循环
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// 已记录的代数
+    pub fn len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.history.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.cycle = None;
+    }
+}
+
+/// 从 `grid` 开始最多演化 `max_generations` 代，返回检测到的循环
+pub fn find_cycle(grid: &LifeGrid, max_generations: u32) -> Option<Cycle> {
+    let mut grid = grid.clone();
+    let mut detector = CycleDetector::new(grid.width(), grid.height());
+    for _ in 0..max_generations {
+        if let Some(cycle) = detector.observe(&grid) {
+            return Some(cycle);
+        }
+        grid.next_generation();
+    }
+    detector.observe(&grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::life_game::PatternLibrary;
+
+    fn grid_with(id: &str, width: usize, height: usize, x: usize, y: usize) -> LifeGrid {
+        let mut grid = LifeGrid::new(width, height);
+        PatternLibrary::builtin().get(id).unwrap().pattern().stamp(&mut grid, x, y);
+        grid
+    }
+
+    #[test]
+    fn test_cycles_of_various_periods_and_pre_periods() {
+        assert_eq!(find_cycle(&grid_with("block", 6, 6, 2, 2), 10), Some(Cycle { period: 1, pre_period: 0 }));
+        assert_eq!(find_cycle(&grid_with("blinker", 7, 7, 2, 3), 10), Some(Cycle { period: 2, pre_period: 0 }));
+        assert_eq!(find_cycle(&grid_with("pulsar", 17, 17, 2, 2), 10), Some(Cycle { period: 3, pre_period: 0 }));
+        assert_eq!(find_cycle(&grid_with("pentadecathlon", 18, 11, 4, 4), 40).map(|c| c.period), Some(15));
+
+        // 三个细胞排成L形，一代后变成方块
+        let mut grid = LifeGrid::new(6, 6);
+        grid.set_pattern(&["XX", "X "], 2, 2);
+        let cycle = find_cycle(&grid, 10).unwrap();
+        assert_eq!(cycle, Cycle { period: 1, pre_period: 1 });
+        assert!(cycle.is_still());
+
+        // 滑翔机在有界网格里撞墙前不会重复
+        assert_eq!(find_cycle(&grid_with("glider", 40, 40, 1, 1), 20), None);
+
+        let table = ZobristTable::new(4, 4);
+        let mut grid = LifeGrid::new(4, 4);
+        grid.set(1, 2, true);
+        assert_eq!(table.hash(&grid), table.toggle(0, 1, 2));
+    }
+}
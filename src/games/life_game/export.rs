@@ -0,0 +1,161 @@
+//! 导出GIF动画
+//!
+//! 从给定网格开始演化，每隔若干代截取一帧写入GIF动画，
+//! 画面顶部用3x5点阵字体标注代数和活细胞数。
+
+use crate::util::gif::GifEncoder;
+
+use super::new_life_game::LifeGrid;
+
+/// 调色板：死细胞、活细胞、标注栏背景、标注文字
+const PALETTE: [[u8; 3]; 4] = [[0x10, 0x10, 0x18], [0x60, 0xE0, 0x80], [0x00, 0x00, 0x00], [0xFF, 0xFF, 0xFF]];
+const DEAD: u8 = 0;
+const ALIVE: u8 = 1;
+const BAR: u8 = 2;
+const TEXT: u8 = 3;
+
+/// 字形大小和字间距
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+const GLYPH_ADVANCE: usize = GLYPH_WIDTH + 1;
+/// 标注栏高度，上下各留1像素
+const BAR_HEIGHT: usize = GLYPH_HEIGHT + 2;
+
+/// 3x5点阵字形，每行低3位从左到右
+fn glyph(ch: char) -> [u8; GLYPH_HEIGHT] {
+    match ch {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'E' => [0b111, 0b100, 0b111, 0b100, 0b111],
+        'G' => [0b111, 0b100, 0b101, 0b101, 0b111],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b111, 0b101, 0b111, 0b100, 0b100],
+        _ => [0; GLYPH_HEIGHT],
+    }
+}
+
+/// GIF导出参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GifExportOptions {
+    /// 演化的总代数
+    pub generations: u32,
+    /// 每隔多少代截取一帧
+    pub every: u32,
+    /// 每个细胞的像素边长
+    pub cell_size: usize,
+    /// 每帧延时，单位1/100秒
+    pub delay: u16,
+    /// 是否绘制代数和活细胞数
+    pub annotate: bool,
+}
+
+impl Default for GifExportOptions {
+    fn default() -> Self {
+        Self { generations: 100, every: 1, cell_size: 4, delay: 10, annotate: true }
+    }
+}
+
+/// 一帧画面
+struct Canvas {
+    width: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn draw_text(&mut self, text: &str, x: usize, y: usize) {
+        for (i, ch) in text.chars().enumerate() {
+            let left = x + i * GLYPH_ADVANCE;
+            for (row, bits) in glyph(ch).iter().enumerate() {
+                for column in 0..GLYPH_WIDTH {
+                    if bits & (0b100 >> column) != 0 && left + column < self.width {
+                        self.pixels[(y + row) * self.width + left + column] = TEXT;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render(grid: &LifeGrid, options: &GifExportOptions) -> Vec<u8> {
+    let cell = options.cell_size;
+    let width = grid.width() * cell;
+    let top = if options.annotate { BAR_HEIGHT } else { 0 };
+    let mut canvas = Canvas { width, pixels: vec![DEAD; width * (top + grid.height() * cell)] };
+
+    if options.annotate {
+        canvas.pixels[..width * top].fill(BAR);
+        canvas.draw_text(&format!("GEN {} POP {}", grid.generation(), grid.count_live_cells()), 1, 1);
+    }
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            if !grid.get(x, y) {
+                continue;
+            }
+            for dy in 0..cell {
+                let row = (top + y * cell + dy) * width;
+                canvas.pixels[row + x * cell..row + (x + 1) * cell].fill(ALIVE);
+            }
+        }
+    }
+    canvas.pixels
+}
+
+/// 从 `grid` 演化 `options.generations` 代，第0代起每 `options.every` 代输出一帧
+pub fn export_gif(grid: &LifeGrid, options: &GifExportOptions) -> Result<Vec<u8>, String> {
+    if options.cell_size == 0 || options.every == 0 {
+        return Err("细胞大小和帧间隔必须大于0".to_string());
+    }
+    let width = grid.width() * options.cell_size;
+    let height = grid.height() * options.cell_size + if options.annotate { BAR_HEIGHT } else { 0 };
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(format!("图像尺寸过大: {}x{}", width, height));
+    };
+
+    let mut encoder = GifEncoder::new(gif_width, gif_height, &PALETTE)?;
+    let mut grid = grid.clone();
+    for generation in 0..=options.generations {
+        if generation % options.every == 0 {
+            encoder.add_frame(&render(&grid, options), options.delay)?;
+        }
+        if generation < options.generations {
+            grid.next_generation();
+        }
+    }
+    Ok(encoder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_blinker_frames_and_annotation() {
+        let mut grid = LifeGrid::new(5, 5);
+        for x in 1..4 {
+            grid.set(x, 2, true);
+        }
+        let options = GifExportOptions { generations: 4, every: 2, cell_size: 2, ..GifExportOptions::default() };
+
+        let frame = render(&grid, &options);
+        assert_eq!(frame.len(), 10 * (BAR_HEIGHT + 10));
+        // "G" 的左上角和第2行中间的空白
+        assert_eq!((frame[10 + 1], frame[2 * 10 + 2]), (TEXT, BAR));
+        let cell = (BAR_HEIGHT + 4) * 10 + 2;
+        assert_eq!((frame[cell], frame[cell + 1], frame[cell - 1]), (ALIVE, ALIVE, DEAD));
+
+        let gif = export_gif(&grid, &options).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x0A\x00\x11\x00"));
+        // 第0、2、4代各一帧
+        assert_eq!(gif.windows(3).filter(|w| w == &[0x21, 0xF9, 0x04]).count(), 3);
+        assert!(export_gif(&grid, &GifExportOptions { every: 0, ..options }).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+//! 程序化生成初始图样
+//!
+//! 用熵源随机数合成有对称性的随机汤 (soup) 和小尺寸的长寿图样 (methuselah) 候选，
+//! 再把每个候选放进足够大的网格里演化若干代，只保留到最后仍有活细胞的图样。
+
+use crate::entropy::{EntropyError, EntropyManager, entropy_pool::PooledEntropy};
+
+use super::new_life_game::LifeGrid;
+
+/// 随机汤的对称方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    None,
+    /// 左右镜像
+    Horizontal,
+    /// 上下镜像
+    Vertical,
+    /// 左右和上下同时镜像
+    Both,
+    /// 180度旋转
+    Rotate2,
+    /// 90度旋转，宽高取两者较大值
+    Rotate4,
+}
+
+impl Symmetry {
+    /// 从命令行名称解析
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "none" => Ok(Symmetry::None),
+            "horizontal" => Ok(Symmetry::Horizontal),
+            "vertical" => Ok(Symmetry::Vertical),
+            "both" => Ok(Symmetry::Both),
+            "rotate2" => Ok(Symmetry::Rotate2),
+            "rotate4" => Ok(Symmetry::Rotate4),
+            other => Err(format!("未知的对称方式: {} (可用: none, horizontal, vertical, both, rotate2, rotate4)", other)),
+        }
+    }
+
+    /// `(x, y)` 在对称变换下的全部位置
+    fn orbit(self, x: usize, y: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+        let (mx, my) = (width - 1 - x, height - 1 - y);
+        match self {
+            Symmetry::None => vec![(x, y)],
+            Symmetry::Horizontal => vec![(x, y), (mx, y)],
+            Symmetry::Vertical => vec![(x, y), (x, my)],
+            Symmetry::Both => vec![(x, y), (mx, y), (x, my), (mx, my)],
+            Symmetry::Rotate2 => vec![(x, y), (mx, my)],
+            Symmetry::Rotate4 => vec![(x, y), (width - 1 - y, x), (mx, my), (y, height - 1 - x)],
+        }
+    }
+}
+
+/// 图样类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternKind {
+    Soup,
+    Methuselah,
+}
+
+impl PatternKind {
+    /// 从命令行名称解析
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "soup" => Ok(PatternKind::Soup),
+            "methuselah" => Ok(PatternKind::Methuselah),
+            other => Err(format!("未知的图样类型: {} (可用: soup, methuselah)", other)),
+        }
+    }
+}
+
+/// 生成参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneratorConfig {
+    /// 随机汤的尺寸
+    pub width: usize,
+    pub height: usize,
+    /// 活细胞比例 (0.0-1.0)
+    pub density: f64,
+    pub symmetry: Symmetry,
+    /// 长寿图样候选的最大边长
+    pub methuselah_size: usize,
+    /// 长寿图样候选的最多活细胞数
+    pub methuselah_cells: usize,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            width: 16,
+            height: 16,
+            density: 0.35,
+            symmetry: Symmetry::None,
+            methuselah_size: 5,
+            methuselah_cells: 8,
+        }
+    }
+}
+
+/// 演化评分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternScore {
+    /// 仍有活细胞的代数
+    pub generations_survived: u32,
+    pub final_population: usize,
+    pub peak_population: usize,
+}
+
+/// 生成的图样
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedPattern {
+    pub kind: PatternKind,
+    pub symmetry: Symmetry,
+    pub width: usize,
+    pub height: usize,
+    /// 活细胞坐标
+    pub cells: Vec<(usize, usize)>,
+    /// 经过 `score` 后填入
+    pub score: Option<PatternScore>,
+}
+
+impl GeneratedPattern {
+    /// 按 `LifeGrid::set_pattern` 的格式输出，`X` 为活细胞
+    pub fn rows(&self) -> Vec<String> {
+        let mut rows = vec![vec![' '; self.width]; self.height];
+        for &(x, y) in &self.cells {
+            rows[y][x] = 'X';
+        }
+        rows.into_iter().map(|row| row.into_iter().collect()).collect()
+    }
+
+    /// 以 `(x, y)` 为左上角放到网格中
+    pub fn place(&self, grid: &mut LifeGrid, x: usize, y: usize) {
+        for &(dx, dy) in &self.cells {
+            grid.set(x + dx, y + dy, true);
+        }
+    }
+
+    /// 放在留有边距的网格中央演化 `generations` 代并评分
+    pub fn score(&self, generations: u32) -> PatternScore {
+        // 边距足够图样向外扩张，又不至于让长时间演化太慢
+        let margin = (generations as usize).clamp(8, 64);
+        let mut grid = LifeGrid::new(self.width + margin * 2, self.height + margin * 2);
+        self.place(&mut grid, margin, margin);
+
+        let mut score = PatternScore { generations_survived: 0, final_population: self.cells.len(), peak_population: self.cells.len() };
+        for _ in 0..generations {
+            grid.next_generation();
+            let population = grid.count_live_cells();
+            score.final_population = population;
+            if population == 0 {
+                break;
+            }
+            score.generations_survived += 1;
+            score.peak_population = score.peak_population.max(population);
+        }
+        score
+    }
+}
+
+/// 图样生成器
+pub struct PatternGenerator {
+    entropy: PooledEntropy,
+    pub config: GeneratorConfig,
+}
+
+impl PatternGenerator {
+    /// 从熵源管理器收集熵作为随机数来源
+    pub fn new(config: GeneratorConfig) -> Result<Self, EntropyError> {
+        let mut manager = EntropyManager::new();
+        let _ = manager.collect_and_optimize();
+        let mut entropy = PooledEntropy::new(2048);
+        entropy.add_entropy_source(&manager.generate_random(512)?);
+        Ok(Self::with_entropy(entropy, config))
+    }
+
+    /// 使用已有的熵池
+    pub fn with_entropy(entropy: PooledEntropy, config: GeneratorConfig) -> Self {
+        Self { entropy, config }
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        (self.entropy.get_random_range(0, 1000) as f64 / 1000.0) < probability
+    }
+
+    /// 按对称方式和密度生成随机汤
+    pub fn soup(&mut self) -> GeneratedPattern {
+        let symmetry = self.config.symmetry;
+        let (width, height) = match symmetry {
+            Symmetry::Rotate4 => {
+                let side = self.config.width.max(self.config.height);
+                (side, side)
+            }
+            _ => (self.config.width, self.config.height),
+        };
+
+        let mut alive = vec![vec![false; height]; width];
+        let mut decided = vec![vec![false; height]; width];
+        for x in 0..width {
+            for y in 0..height {
+                if decided[x][y] {
+                    continue;
+                }
+                let value = self.chance(self.config.density);
+                for (ox, oy) in symmetry.orbit(x, y, width, height) {
+                    alive[ox][oy] = value;
+                    decided[ox][oy] = true;
+                }
+            }
+        }
+
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| alive[x][y])
+            .collect();
+        GeneratedPattern { kind: PatternKind::Soup, symmetry, width, height, cells, score: None }
+    }
+
+    /// 生成长寿图样候选：小范围内的少量活细胞
+    pub fn methuselah(&mut self) -> GeneratedPattern {
+        let size = self.config.methuselah_size.max(3);
+        let target = self.entropy.get_random_range(3, self.config.methuselah_cells.max(3) as u32 + 1) as usize;
+        let target = target.min(size * size);
+
+        let mut cells = Vec::with_capacity(target);
+        while cells.len() < target {
+            let x = self.entropy.get_random_range(0, size as u32) as usize;
+            let y = self.entropy.get_random_range(0, size as u32) as usize;
+            if !cells.contains(&(x, y)) {
+                cells.push((x, y));
+            }
+        }
+        cells.sort_by_key(|&(x, y)| (y, x));
+        GeneratedPattern { kind: PatternKind::Methuselah, symmetry: Symmetry::None, width: size, height: size, cells, score: None }
+    }
+
+    /// 生成 `count` 个候选，只保留演化 `generations` 代后仍有活细胞的图样
+    pub fn generate(&mut self, kind: PatternKind, count: usize, generations: u32) -> Vec<GeneratedPattern> {
+        let candidates = (0..count)
+            .map(|_| match kind {
+                PatternKind::Soup => self.soup(),
+                PatternKind::Methuselah => self.methuselah(),
+            })
+            .collect();
+        keep_survivors(candidates, generations)
+    }
+}
+
+/// 对每个图样评分，只保留存活满 `generations` 代的，按峰值种群从大到小排序
+pub fn keep_survivors(patterns: Vec<GeneratedPattern>, generations: u32) -> Vec<GeneratedPattern> {
+    let mut survivors: Vec<GeneratedPattern> = patterns
+        .into_iter()
+        .filter_map(|mut pattern| {
+            let score = pattern.score(generations);
+            pattern.score = Some(score);
+            (score.generations_survived >= generations).then_some(pattern)
+        })
+        .collect();
+    survivors.sort_by_key(|pattern| std::cmp::Reverse(pattern.score.map_or(0, |score| score.peak_population)));
+    survivors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generator(symmetry: Symmetry) -> PatternGenerator {
+        let mut entropy = PooledEntropy::new(256);
+        entropy.add_entropy_source(&(0..=255).collect::<Vec<u8>>());
+        let config = GeneratorConfig { width: 9, height: 6, density: 0.5, symmetry, ..GeneratorConfig::default() };
+        PatternGenerator::with_entropy(entropy, config)
+    }
+
+    #[test]
+    fn test_soups_respect_symmetry() {
+        for symmetry in [Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Both, Symmetry::Rotate2, Symmetry::Rotate4] {
+            let soup = generator(symmetry).soup();
+            for &(x, y) in &soup.cells {
+                for image in symmetry.orbit(x, y, soup.width, soup.height) {
+                    assert!(soup.cells.contains(&image), "{:?}: ({}, {}) 缺少对称点 {:?}", symmetry, x, y, image);
+                }
+            }
+        }
+        assert_eq!(generator(Symmetry::Rotate4).soup().height, 9);
+
+        let methuselah = generator(Symmetry::None).methuselah();
+        assert!((3..=8).contains(&methuselah.cells.len()));
+        assert!(methuselah.cells.iter().all(|&(x, y)| x < 5 && y < 5));
+    }
+
+    #[test]
+    fn test_keep_survivors_filters_dying_patterns() {
+        let pattern = |cells: Vec<(usize, usize)>| GeneratedPattern {
+            kind: PatternKind::Methuselah,
+            symmetry: Symmetry::None,
+            width: 3,
+            height: 3,
+            cells,
+            score: None,
+        };
+        let blinker = pattern(vec![(0, 1), (1, 1), (2, 1)]);
+        let domino = pattern(vec![(0, 0), (1, 0)]);
+        let survivors = keep_survivors(vec![domino, blinker.clone()], 10);
+        assert_eq!(survivors.len(), 1);
+        assert_eq!(survivors[0].cells, blinker.cells);
+        assert_eq!(survivors[0].score, Some(PatternScore { generations_survived: 10, final_population: 3, peak_population: 3 }));
+        assert_eq!(blinker.rows(), vec!["   ", "XXX", "   "]);
+    }
+}
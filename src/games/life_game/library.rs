@@ -0,0 +1,486 @@
+//! 图样库
+//!
+//! 内置一组经典图样（RLE格式），附带类型、周期和发现者等元数据，
+//! 提供列表和搜索接口，供模拟器的图样放置功能使用。
+
+use std::fmt;
+
+use super::new_life_game::LifeGrid;
+
+/// 解码后的图样
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub width: usize,
+    pub height: usize,
+    /// 活细胞坐标，按行排列
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl Pattern {
+    /// 从 `LifeGrid::set_pattern` 格式的行构造，`X` 为活细胞
+    pub fn from_rows(rows: &[&str]) -> Self {
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.chars().enumerate().filter(|&(_, ch)| ch == 'X').map(move |(x, _)| (x, y)))
+            .collect();
+        Self { width: rows.iter().map(|row| row.chars().count()).max().unwrap_or(0), height: rows.len(), cells }
+    }
+
+    /// 按 `LifeGrid::set_pattern` 的格式输出
+    pub fn rows(&self) -> Vec<String> {
+        let mut rows = vec![vec![' '; self.width]; self.height];
+        for &(x, y) in &self.cells {
+            rows[y][x] = 'X';
+        }
+        rows.into_iter().map(|row| row.into_iter().collect()).collect()
+    }
+
+    /// 以 `(x, y)` 为左上角放到网格中，只设置活细胞
+    pub fn stamp(&self, grid: &mut LifeGrid, x: usize, y: usize) {
+        for &(dx, dy) in &self.cells {
+            grid.set(x + dx, y + dy, true);
+        }
+    }
+
+    /// 编码为RLE
+    pub fn to_rle(&self) -> String {
+        let mut body = String::new();
+        let mut pending_rows = 0;
+        for y in 0..self.height {
+            let mut row: Vec<bool> = vec![false; self.width];
+            for &(x, _) in self.cells.iter().filter(|&&(_, cy)| cy == y) {
+                row[x] = true;
+            }
+            let Some(last) = row.iter().rposition(|&alive| alive) else {
+                pending_rows += 1;
+                continue;
+            };
+            if !body.is_empty() {
+                push_run(&mut body, pending_rows + 1, '$');
+            }
+            pending_rows = 0;
+
+            let mut x = 0;
+            while x <= last {
+                let run = row[x..=last].iter().take_while(|&&alive| alive == row[x]).count();
+                push_run(&mut body, run, if row[x] { 'o' } else { 'b' });
+                x += run;
+            }
+        }
+        body.push('!');
+
+        // RLE每行不超过70个字符，只在游程之间换行
+        let mut output = format!("x = {}, y = {}, rule = B3/S23\n", self.width, self.height);
+        let mut line = String::new();
+        let mut token = String::new();
+        for ch in body.chars() {
+            token.push(ch);
+            if ch.is_ascii_digit() {
+                continue;
+            }
+            if line.len() + token.len() > 70 {
+                output.push_str(&line);
+                output.push('\n');
+                line.clear();
+            }
+            line.push_str(&token);
+            token.clear();
+        }
+        output.push_str(&line);
+        output.push('\n');
+        output
+    }
+}
+
+fn push_run(body: &mut String, run: usize, tag: char) {
+    if run > 1 {
+        body.push_str(&run.to_string());
+    }
+    body.push(tag);
+}
+
+/// 解析RLE，支持 `#` 注释行和 `x = m, y = n` 头部，只接受B3/S23的两态图样
+pub fn parse_rle(text: &str) -> Result<Pattern, String> {
+    let mut header: Option<(usize, usize)> = None;
+    let mut cells = Vec::new();
+    let (mut x, mut y) = (0usize, 0usize);
+    let mut count = String::new();
+
+    'lines: for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if header.is_none() && cells.is_empty() && line.starts_with('x') {
+            header = Some(parse_header(line)?);
+            continue;
+        }
+        for ch in line.chars() {
+            if ch.is_ascii_digit() {
+                count.push(ch);
+                continue;
+            }
+            let run = if count.is_empty() { 1 } else { count.parse().map_err(|_| format!("无效的游程长度: {}", count))? };
+            count.clear();
+            match ch {
+                'b' | '.' => x += run,
+                'o' | 'A' => {
+                    cells.extend((x..x + run).map(|cx| (cx, y)));
+                    x += run;
+                }
+                '$' => {
+                    y += run;
+                    x = 0;
+                }
+                '!' => break 'lines,
+                ch if ch.is_whitespace() => {}
+                other => return Err(format!("RLE中的无效字符: {}", other)),
+            }
+        }
+    }
+
+    let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+    let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+    let (width, height) = match header {
+        Some((w, h)) if width > w || height > h => {
+            return Err(format!("图样超出头部声明的尺寸 {}x{}: {}x{}", w, h, width, height));
+        }
+        Some(size) => size,
+        None => (width, height),
+    };
+    Ok(Pattern { width, height, cells })
+}
+
+fn parse_header(line: &str) -> Result<(usize, usize), String> {
+    let mut size = (None, None);
+    for field in line.split(',') {
+        let Some((key, value)) = field.split_once('=') else {
+            return Err(format!("无效的RLE头部: {}", line));
+        };
+        let value = value.trim();
+        match key.trim() {
+            "x" => size.0 = value.parse().ok(),
+            "y" => size.1 = value.parse().ok(),
+            "rule" if !value.eq_ignore_ascii_case("B3/S23") && !value.eq_ignore_ascii_case("23/3") => {
+                return Err(format!("不支持的规则: {}", value));
+            }
+            _ => {}
+        }
+    }
+    match size {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => Err(format!("无效的RLE头部: {}", line)),
+    }
+}
+
+/// 图样类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternType {
+    /// 静物
+    StillLife,
+    /// 振荡器
+    Oscillator,
+    /// 飞船
+    Spaceship,
+    /// 长寿图样
+    Methuselah,
+    /// 枪
+    Gun,
+}
+
+impl PatternType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PatternType::StillLife => "静物",
+            PatternType::Oscillator => "振荡器",
+            PatternType::Spaceship => "飞船",
+            PatternType::Methuselah => "长寿图样",
+            PatternType::Gun => "枪",
+        }
+    }
+}
+
+/// 图样库中的一项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LibraryEntry {
+    /// 英文标识，用于命令和搜索
+    pub id: &'static str,
+    pub name: &'static str,
+    pub kind: PatternType,
+    /// 振荡器、飞船和枪的周期，静物为1
+    pub period: Option<u32>,
+    pub discoverer: &'static str,
+    pub description: &'static str,
+    pub rle: &'static str,
+}
+
+impl LibraryEntry {
+    /// 解码RLE，内置图样保证可以解码
+    pub fn pattern(&self) -> Pattern {
+        parse_rle(self.rle).expect("内置图样的RLE有效")
+    }
+
+    /// 名称、标识、类型、发现者或描述包含 `query`（不区分大小写）
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [self.id, self.name, self.kind.name(), self.discoverer, self.description]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+impl fmt::Display for LibraryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}) - {}", self.name, self.id, self.kind.name())?;
+        if let Some(period) = self.period {
+            write!(f, " 周期{}", period)?;
+        }
+        write!(f, ", {}: {}", self.discoverer, self.description)
+    }
+}
+
+/// 内置图样
+const BUILTIN: [LibraryEntry; 15] = [
+    LibraryEntry {
+        id: "block",
+        name: "方块",
+        kind: PatternType::StillLife,
+        period: Some(1),
+        discoverer: "John Conway",
+        description: "最小的静物",
+        rle: "x = 2, y = 2\n2o$2o!",
+    },
+    LibraryEntry {
+        id: "beehive",
+        name: "蜂巢",
+        kind: PatternType::StillLife,
+        period: Some(1),
+        discoverer: "John Conway",
+        description: "常见的六细胞静物",
+        rle: "x = 4, y = 3\nb2o$o2bo$b2o!",
+    },
+    LibraryEntry {
+        id: "loaf",
+        name: "面包",
+        kind: PatternType::StillLife,
+        period: Some(1),
+        discoverer: "John Conway",
+        description: "常见的七细胞静物",
+        rle: "x = 4, y = 4\nb2o$o2bo$bobo$2bo!",
+    },
+    LibraryEntry {
+        id: "boat",
+        name: "船",
+        kind: PatternType::StillLife,
+        period: Some(1),
+        discoverer: "John Conway",
+        description: "五细胞静物",
+        rle: "x = 3, y = 3\n2o$obo$bo!",
+    },
+    LibraryEntry {
+        id: "blinker",
+        name: "闪光灯",
+        kind: PatternType::Oscillator,
+        period: Some(2),
+        discoverer: "John Conway",
+        description: "最小的振荡器",
+        rle: "x = 3, y = 1\n3o!",
+    },
+    LibraryEntry {
+        id: "toad",
+        name: "蟾蜍",
+        kind: PatternType::Oscillator,
+        period: Some(2),
+        discoverer: "Simon Norton",
+        description: "周期性振荡模式",
+        rle: "x = 4, y = 2\nb3o$3o!",
+    },
+    LibraryEntry {
+        id: "beacon",
+        name: "信标",
+        kind: PatternType::Oscillator,
+        period: Some(2),
+        discoverer: "John Conway",
+        description: "周期性闪烁模式",
+        rle: "x = 4, y = 4\n2o$2o$2b2o$2b2o!",
+    },
+    LibraryEntry {
+        id: "pulsar",
+        name: "脉冲星",
+        kind: PatternType::Oscillator,
+        period: Some(3),
+        discoverer: "John Conway",
+        description: "周期性振荡模式",
+        rle: "x = 13, y = 13\n2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$2b3o3b3o2b$\no4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    },
+    LibraryEntry {
+        id: "pentadecathlon",
+        name: "十五项全能",
+        kind: PatternType::Oscillator,
+        period: Some(15),
+        discoverer: "John Conway",
+        description: "周期15的振荡器",
+        rle: "x = 10, y = 3\n2bo4bo2b$2ob4ob2o$2bo4bo!",
+    },
+    LibraryEntry {
+        id: "glider",
+        name: "滑翔机",
+        kind: PatternType::Spaceship,
+        period: Some(4),
+        discoverer: "Richard K. Guy",
+        description: "会移动的简单模式",
+        rle: "x = 3, y = 3\nbo$2bo$3o!",
+    },
+    LibraryEntry {
+        id: "lwss",
+        name: "轻量级飞船",
+        kind: PatternType::Spaceship,
+        period: Some(4),
+        discoverer: "John Conway",
+        description: "沿水平方向移动的飞船",
+        rle: "x = 5, y = 4\nbo2bo$o4b$o3bo$4o!",
+    },
+    LibraryEntry {
+        id: "r-pentomino",
+        name: "R五连块",
+        kind: PatternType::Methuselah,
+        period: None,
+        discoverer: "John Conway",
+        description: "五个细胞演化1103代后稳定",
+        rle: "x = 3, y = 3\nb2o$2o$bo!",
+    },
+    LibraryEntry {
+        id: "diehard",
+        name: "顽强",
+        kind: PatternType::Methuselah,
+        period: None,
+        discoverer: "未知",
+        description: "130代后完全消失",
+        rle: "x = 8, y = 3\n6bob$2o6b$bo3b3o!",
+    },
+    LibraryEntry {
+        id: "acorn",
+        name: "橡子",
+        kind: PatternType::Methuselah,
+        period: None,
+        discoverer: "Charles Corderman",
+        description: "七个细胞演化5206代后稳定",
+        rle: "x = 7, y = 3\nbo5b$3bo3b$2o2b3o!",
+    },
+    LibraryEntry {
+        id: "gosper-glider-gun",
+        name: "高斯帕滑翔机枪",
+        kind: PatternType::Gun,
+        period: Some(30),
+        discoverer: "Bill Gosper",
+        description: "每30代发射一架滑翔机",
+        rle: "x = 36, y = 9\n24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$\n10bo5bo7bo$11bo3bo$12b2o!",
+    },
+];
+
+/// 图样库
+#[derive(Debug, Clone)]
+pub struct PatternLibrary {
+    entries: Vec<LibraryEntry>,
+}
+
+impl PatternLibrary {
+    /// 内置的经典图样
+    pub fn builtin() -> Self {
+        Self { entries: BUILTIN.to_vec() }
+    }
+
+    /// 添加图样，RLE无效时返回错误
+    pub fn add(&mut self, entry: LibraryEntry) -> Result<(), String> {
+        parse_rle(entry.rle)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// 全部图样
+    pub fn list(&self) -> &[LibraryEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 按标识或名称精确查找
+    pub fn get(&self, key: &str) -> Option<&LibraryEntry> {
+        self.entries.iter().find(|entry| entry.id.eq_ignore_ascii_case(key) || entry.name == key)
+    }
+
+    /// 搜索名称、标识、类型、发现者或描述包含 `query` 的图样
+    pub fn search(&self, query: &str) -> Vec<&LibraryEntry> {
+        self.entries.iter().filter(|entry| entry.matches(query)).collect()
+    }
+
+    /// 某一类型的全部图样
+    pub fn by_type(&self, kind: PatternType) -> Vec<&LibraryEntry> {
+        self.entries.iter().filter(|entry| entry.kind == kind).collect()
+    }
+}
+
+impl Default for PatternLibrary {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rle_parse_and_encode_round_trip() {
+        let glider = parse_rle("#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!").unwrap();
+        assert_eq!((glider.width, glider.height), (3, 3));
+        assert_eq!(glider.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!(glider.rows(), vec![" X ", "  X", "XXX"]);
+        assert_eq!(glider.to_rle(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+        assert_eq!(Pattern::from_rows(&[" X ", "  X", "XXX"]), glider);
+
+        let gun = PatternLibrary::builtin().get("gosper-glider-gun").unwrap().pattern();
+        assert_eq!(parse_rle(&gun.to_rle()).unwrap(), gun);
+        assert!(gun.to_rle().lines().all(|line| line.len() <= 70));
+
+        assert!(parse_rle("x = 2, y = 2\n3o!").is_err());
+        assert!(parse_rle("x = 3, y = 1, rule = B36/S23\n3o!").is_err());
+        assert!(parse_rle("3q!").is_err());
+    }
+
+    #[test]
+    fn test_library_metadata_matches_evolution() {
+        let library = PatternLibrary::builtin();
+        for entry in library.list() {
+            let pattern = entry.pattern();
+            let Some(period) = entry.period.filter(|_| entry.kind != PatternType::Gun) else {
+                continue;
+            };
+            let margin = 8;
+            let mut grid = LifeGrid::new(pattern.width + margin * 2, pattern.height + margin * 2);
+            pattern.stamp(&mut grid, margin, margin);
+            let start = grid.clone();
+            for _ in 0..period {
+                grid.next_generation();
+            }
+            // 飞船经过一个周期后形状相同但位置移动，比较种群数即可
+            assert_eq!(grid.count_live_cells(), pattern.cells.len(), "{}", entry.id);
+            if entry.kind != PatternType::Spaceship {
+                let same = (0..grid.width()).all(|x| (0..grid.height()).all(|y| grid.get(x, y) == start.get(x, y)));
+                assert!(same, "{} 的周期不是 {}", entry.id, period);
+            }
+        }
+
+        assert_eq!(library.search("GUN").len(), 1);
+        assert_eq!(library.search("conway").len(), 10);
+        assert_eq!(library.by_type(PatternType::Methuselah).len(), 3);
+        assert_eq!(library.get("滑翔机").map(|entry| entry.id), Some("glider"));
+        assert!(library.get("glider").unwrap().to_string().starts_with("滑翔机 (glider) - 飞船 周期4"));
+    }
+}
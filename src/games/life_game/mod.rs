@@ -2,11 +2,21 @@
 //! 
 //! 包含各种生命游戏实现，包括经典版本和优化版本
 
+pub mod cycle;
+pub mod export;
+pub mod generator;
+pub mod library;
 pub mod new_life_game;
 pub mod sweet_life_game;
 pub mod sweet_life_optimized;
+pub mod topology;
 
 // Re-export main types
+pub use cycle::*;
+pub use export::*;
+pub use generator::*;
+pub use library::*;
 pub use new_life_game::*;
 pub use sweet_life_game::*;
 pub use sweet_life_optimized::*;
+pub use topology::*;
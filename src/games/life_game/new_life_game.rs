@@ -6,31 +6,76 @@ use crate::entropy::{
     EntropyManager, EntropyError,
     entropy_pool::PooledEntropy,
 };
+use crate::i18n::{tr, trf, Msg};
+
+use super::cycle::{Cycle, CycleDetector};
+use super::library::{LibraryEntry, Pattern, PatternLibrary};
+use super::topology::Topology;
 
 use std::time::{Duration, Instant};
 use std::thread;
 use std::io::{self, Write};
 
-/// 生命游戏网格
+/// 生命游戏网格，边界处理由拓扑决定，默认边界外视为死细胞
 #[derive(Clone)]
-struct LifeGrid {
+pub struct LifeGrid {
     width: usize,
     height: usize,
     cells: Vec<Vec<bool>>,
     generation: u32,
+    topology: Topology,
 }
 
 impl LifeGrid {
     /// 创建新的生命游戏网格
-    fn new(width: usize, height: usize) -> Self {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_topology(width, height, Topology::Bounded)
+    }
+
+    /// 创建指定拓扑的网格
+    pub fn with_topology(width: usize, height: usize, topology: Topology) -> Self {
         Self {
             width,
             height,
             cells: vec![vec![false; height]; width],
             generation: 0,
+            topology,
         }
     }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
     
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// 当前代数
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// 细胞是否存活，越界返回false
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.cells[x][y]
+    }
+
+    /// 设置细胞状态，越界时忽略
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        if x < self.width && y < self.height {
+            self.cells[x][y] = alive;
+        }
+    }
+
     /// 随机初始化网格
     fn random_init(&mut self, entropy_pool: &mut PooledEntropy, density: f64) {
         for x in 0..self.width {
@@ -41,8 +86,8 @@ impl LifeGrid {
         }
     }
     
-    /// 设置特定模式
-    fn set_pattern(&mut self, pattern: &[&str], start_x: usize, start_y: usize) {
+    /// 设置特定模式，`X` 为活细胞，其余字符为死细胞
+    pub fn set_pattern(&mut self, pattern: &[&str], start_x: usize, start_y: usize) {
         for (dy, row) in pattern.iter().enumerate() {
             for (dx, ch) in row.chars().enumerate() {
                 let x = start_x + dx;
@@ -55,7 +100,7 @@ impl LifeGrid {
     }
     
     /// 计算下一代
-    fn next_generation(&mut self) {
+    pub fn next_generation(&mut self) {
         let mut new_cells = vec![vec![false; self.height]; self.width];
         
         for x in 0..self.width {
@@ -76,8 +121,8 @@ impl LifeGrid {
         self.generation += 1;
     }
     
-    /// 计算活邻居数量
-    fn count_live_neighbors(&self, x: usize, y: usize) -> u8 {
+    /// 计算活邻居数量，越过边缘的邻居由拓扑决定
+    pub fn count_live_neighbors(&self, x: usize, y: usize) -> u8 {
         let mut count = 0;
         
         for dx in -1..=1 {
@@ -86,11 +131,10 @@ impl LifeGrid {
                     continue;
                 }
                 
-                let nx = (x as i32 + dx) as usize;
-                let ny = (y as i32 + dy) as usize;
-                
-                if nx < self.width && ny < self.height && self.cells[nx][ny] {
-                    count += 1;
+                if let Some((nx, ny)) = self.topology.neighbor(x, y, dx, dy, self.width, self.height) {
+                    if self.cells[nx][ny] {
+                        count += 1;
+                    }
                 }
             }
         }
@@ -99,7 +143,7 @@ impl LifeGrid {
     }
     
     /// 计算活细胞数量
-    fn count_live_cells(&self) -> usize {
+    pub fn count_live_cells(&self) -> usize {
         self.cells.iter()
             .flat_map(|row| row.iter())
             .filter(|&&cell| cell)
@@ -133,8 +177,11 @@ impl LifeGrid {
             print!("\x1B[2J\x1B[1;1H"); // 清屏
         }
         
-        println!("🔄 第{}代生命游戏 | 活细胞: {} | 熵值: {:.3}", 
-                 self.generation, self.count_live_cells(), self.calculate_entropy());
+        println!("{}", trf(Msg::LifeSimGeneration, &[
+            &self.generation,
+            &self.count_live_cells(),
+            &format!("{:.3}", self.calculate_entropy()),
+        ]));
         println!("{}", "─".repeat(self.width + 2));
         
         for y in 0..self.height {
@@ -148,11 +195,6 @@ impl LifeGrid {
         println!("{}", "─".repeat(self.width + 2));
         io::stdout().flush().unwrap();
     }
-    
-    /// 检查是否稳定（连续几代无变化）
-    fn is_stable(&self, prev_cells: &Vec<Vec<bool>>) -> bool {
-        self.cells == *prev_cells
-    }
 }
 
 /// 生命游戏模拟器
@@ -160,24 +202,18 @@ struct LifeGameSimulator {
     grid: LifeGrid,
     entropy_manager: EntropyManager,
     entropy_pool: PooledEntropy,
-    patterns: Vec<Pattern>,
+    library: PatternLibrary,
     stats: SimulationStats,
 }
 
-#[derive(Debug)]
-struct Pattern {
-    name: String,
-    pattern: Vec<&'static str>,
-    description: String,
-}
-
 #[derive(Debug)]
 struct SimulationStats {
     total_generations: u32,
     max_population: usize,
     min_population: usize,
     avg_entropy: f64,
-    stability_count: u32,
+    /// 检测到的循环
+    cycle: Option<Cycle>,
     start_time: Instant,
 }
 
@@ -197,93 +233,55 @@ impl LifeGameSimulator {
         // 初始化网格
         grid.random_init(&mut entropy_pool, 0.3);
         
-        let patterns = Self::create_patterns();
-        
         Ok(Self {
             grid,
             entropy_manager,
             entropy_pool,
-            patterns,
+            library: PatternLibrary::builtin(),
             stats: SimulationStats {
                 total_generations: 0,
                 max_population: 0,
                 min_population: usize::MAX,
                 avg_entropy: 0.0,
-                stability_count: 0,
+                cycle: None,
                 start_time: Instant::now(),
             },
         })
     }
     
-    /// 创建经典模式
-    fn create_patterns() -> Vec<Pattern> {
-        vec![
-            Pattern {
-                name: "滑翔机".to_string(),
-                pattern: vec![
-                    " X ",
-                    "X X",
-                    " XX",
-                ],
-                description: "会移动的简单模式".to_string(),
-            },
-            Pattern {
-                name: "脉冲星".to_string(),
-                pattern: vec![
-                    "  XXX   XXX  ",
-                    " X   X X   X ",
-                    "X     X     X",
-                    "X     X     X",
-                    "X     X     X",
-                    " X   X X   X ",
-                    "  XXX   XXX  ",
-                ],
-                description: "周期性振荡模式".to_string(),
-            },
-            Pattern {
-                name: "信标".to_string(),
-                pattern: vec![
-                    "XX  ",
-                    "XX  ",
-                    "  XX",
-                    "  XX",
-                ],
-                description: "周期性闪烁模式".to_string(),
-            },
-            Pattern {
-                name: "蟾蜍".to_string(),
-                pattern: vec![
-                    " XXX",
-                    "XXX ",
-                ],
-                description: "周期性振荡模式".to_string(),
-            },
-        ]
-    }
-    
-    /// 添加随机模式
-    fn add_random_pattern(&mut self) -> Result<(), EntropyError> {
-        let pattern_idx = self.entropy_pool.get_random_range(0, self.patterns.len() as u32) as usize;
-        let pattern = &self.patterns[pattern_idx];
-        
-        let x = self.entropy_pool.get_random_range(0, (self.grid.width - pattern.pattern[0].len()) as u32) as usize;
-        let y = self.entropy_pool.get_random_range(0, (self.grid.height - pattern.pattern.len()) as u32) as usize;
-        
-        self.grid.set_pattern(&pattern.pattern, x, y);
-        
-        println!("🎯 添加模式: {} 在位置 ({}, {})", pattern.name, x, y);
+    /// 在随机位置放置图样库中的图样，`query` 为空时随机选择
+    fn add_pattern(&mut self, query: &str) -> Result<(), EntropyError> {
+        let candidates: Vec<LibraryEntry> = if query.is_empty() {
+            self.library.list().to_vec()
+        } else {
+            self.library.search(query).into_iter().copied().collect()
+        };
+        let fitting: Vec<(LibraryEntry, Pattern)> = candidates
+            .into_iter()
+            .map(|entry| (entry, entry.pattern()))
+            .filter(|(_, pattern)| pattern.width <= self.grid.width && pattern.height <= self.grid.height)
+            .collect();
+        if fitting.is_empty() {
+            println!("{}", trf(Msg::LifeSimNoPattern, &[&query]));
+            return Ok(());
+        }
+
+        let index = self.entropy_pool.get_random_range(0, fitting.len() as u32) as usize;
+        let (entry, pattern) = &fitting[index];
+        let x = self.entropy_pool.get_random_range(0, (self.grid.width - pattern.width + 1) as u32) as usize;
+        let y = self.entropy_pool.get_random_range(0, (self.grid.height - pattern.height + 1) as u32) as usize;
+
+        pattern.stamp(&mut self.grid, x, y);
+
+        println!("{}", trf(Msg::LifeSimAddPattern, &[&entry.name, &x, &y]));
         Ok(())
     }
     
     /// 运行模拟
     fn run(&mut self, max_generations: u32, display_interval: u32) -> Result<(), EntropyError> {
-        println!("🌱 全新的生命游戏开始！");
-        println!("使用外部熵源优化概率空间分布");
-        println!("网格大小: {}x{}", self.grid.width, self.grid.height);
-        println!("最大代数: {}", max_generations);
-        println!();
+        println!("{}", trf(Msg::LifeSimStart, &[&self.grid.width, &self.grid.height, &max_generations]));
         
-        let mut prev_cells = self.grid.cells.clone();
+        let mut detector = CycleDetector::new(self.grid.width, self.grid.height);
         let mut entropy_sum = 0.0;
         
         for generation in 0..max_generations {
@@ -295,15 +293,11 @@ impl LifeGameSimulator {
             let entropy = self.grid.calculate_entropy();
             entropy_sum += entropy;
             
-            // 检查稳定性
-            if self.grid.is_stable(&prev_cells) {
-                self.stats.stability_count += 1;
-                if self.stats.stability_count >= 5 {
-                    println!("🔒 系统达到稳定状态，在第{}代", generation);
-                    break;
-                }
-            } else {
-                self.stats.stability_count = 0;
+            // 检查是否进入循环
+            if let Some(cycle) = detector.observe(&self.grid) {
+                println!("{}", trf(Msg::LifeSimCycle, &[&cycle.pre_period, &cycle.period]));
+                self.stats.cycle = Some(cycle);
+                break;
             }
             
             // 显示网格
@@ -311,42 +305,50 @@ impl LifeGameSimulator {
                 self.grid.display(true);
                 
                 // 显示统计信息
-                println!("📊 统计信息:");
-                println!("  总代数: {}", generation);
-                println!("  当前种群: {}", population);
-                println!("  最大种群: {}", self.stats.max_population);
-                println!("  最小种群: {}", self.stats.min_population);
-                println!("  平均熵值: {:.3}", entropy_sum / (generation + 1) as f64);
-                println!("  运行时间: {:.2}秒", self.stats.start_time.elapsed().as_secs_f64());
+                println!("{}", trf(Msg::LifeSimStats, &[
+                    &generation,
+                    &population,
+                    &self.stats.max_population,
+                    &self.stats.min_population,
+                    &format!("{:.3}", entropy_sum / (generation + 1) as f64),
+                    &format!("{:.2}", self.stats.start_time.elapsed().as_secs_f64()),
+                ]));
                 
                 // 显示熵源统计
                 let entropy_stats = self.entropy_manager.get_entropy_stats();
-                println!("🔬 熵源统计:");
-                println!("  熵源数量: {}", entropy_stats.source_count);
-                println!("  池大小: {} 字节", entropy_stats.pool_size);
-                println!("  分布质量: {:.3}", entropy_stats.optimizer_stats.distribution_quality);
-                println!("  量子强度: {:.3}", entropy_stats.quantum_stats.post_quantum_strength);
+                println!("{}", trf(Msg::LifeSimEntropyStats, &[
+                    &entropy_stats.source_count,
+                    &entropy_stats.pool_size,
+                    &format!("{:.3}", entropy_stats.optimizer_stats.distribution_quality),
+                    &format!("{:.3}", entropy_stats.quantum_stats.post_quantum_strength),
+                ]));
                 
                 println!();
-                println!("按回车键继续，或输入 'q' 退出，'p' 添加模式...");
+                println!("{}", tr(Msg::LifeSimPrompt));
                 
                 let mut input = String::new();
                 io::stdin().read_line(&mut input).unwrap();
                 
                 match input.trim() {
                     "q" => {
-                        println!("👋 游戏结束！");
+                        println!("{}", tr(Msg::LifeSimQuit));
                         break;
                     }
-                    "p" => {
-                        self.add_random_pattern()?;
+                    "l" => {
+                        for entry in self.library.list() {
+                            println!("  {}", entry);
+                        }
+                    }
+                    command if command == "p" || command.starts_with("p ") => {
+                        self.add_pattern(command[1..].trim())?;
+                        // 放置图样后之前的代不会再出现
+                        detector.clear();
                     }
                     _ => {}
                 }
             }
             
             // 更新到下一代
-            prev_cells = self.grid.cells.clone();
             self.grid.next_generation();
             
             // 控制速度
@@ -361,30 +363,36 @@ impl LifeGameSimulator {
     
     /// 显示最终统计
     fn display_final_stats(&self) {
-        println!("\n🎉 生命游戏模拟完成！");
+        println!("\n{}", tr(Msg::LifeSimFinished));
         println!("==================================================");
-        println!("📈 最终统计:");
-        println!("  总代数: {}", self.stats.total_generations);
-        println!("  最大种群: {}", self.stats.max_population);
-        println!("  最小种群: {}", self.stats.min_population);
-        println!("  平均熵值: {:.3}", self.stats.avg_entropy);
-        println!("  总运行时间: {:.2}秒", self.stats.start_time.elapsed().as_secs_f64());
-        println!("  平均每代时间: {:.3}毫秒", 
-                 self.stats.start_time.elapsed().as_millis() as f64 / self.stats.total_generations as f64);
+        println!("{}", trf(Msg::LifeSimFinalStats, &[
+            &self.stats.total_generations,
+            &self.stats.max_population,
+            &self.stats.min_population,
+            &format!("{:.3}", self.stats.avg_entropy),
+        ]));
+        if let Some(cycle) = self.stats.cycle {
+            println!("{}", trf(Msg::LifeSimFinalCycle, &[&cycle.period, &cycle.pre_period]));
+        }
+        println!("{}", trf(Msg::LifeSimTiming, &[
+            &format!("{:.2}", self.stats.start_time.elapsed().as_secs_f64()),
+            &format!("{:.3}", self.stats.start_time.elapsed().as_millis() as f64 / self.stats.total_generations as f64),
+        ]));
         
         // 显示熵源统计
         let entropy_stats = self.entropy_manager.get_entropy_stats();
-        println!("\n🔬 熵源系统统计:");
-        println!("  熵源数量: {}", entropy_stats.source_count);
-        println!("  池大小: {} 字节", entropy_stats.pool_size);
-        println!("  分布质量: {:.3}", entropy_stats.optimizer_stats.distribution_quality);
-        println!("  量子强度: {:.3}", entropy_stats.quantum_stats.post_quantum_strength);
-        println!("  处理时间: {} 纳秒", entropy_stats.quantum_stats.processing_time_ns);
-        println!("  熵放大: {:.3}", entropy_stats.quantum_stats.entropy_amplification);
+        println!("\n{}", trf(Msg::LifeSimEntropySystem, &[
+            &entropy_stats.source_count,
+            &entropy_stats.pool_size,
+            &format!("{:.3}", entropy_stats.optimizer_stats.distribution_quality),
+            &format!("{:.3}", entropy_stats.quantum_stats.post_quantum_strength),
+            &entropy_stats.quantum_stats.processing_time_ns,
+            &format!("{:.3}", entropy_stats.quantum_stats.entropy_amplification),
+        ]));
         
-        println!("\n🎯 模式库:");
-        for (i, pattern) in self.patterns.iter().enumerate() {
-            println!("  {}. {} - {}", i + 1, pattern.name, pattern.description);
+        println!("\n{}", tr(Msg::LifeSimLibrary));
+        for (i, entry) in self.library.list().iter().enumerate() {
+            println!("  {}. {}", i + 1, entry);
         }
     }
 }
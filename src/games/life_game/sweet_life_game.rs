@@ -1,11 +1,13 @@
 //! 甜甜的生命游戏 - 凸优化版本
 
 use crate::{AdvancedGameBoy, RomGenerator};
+use crate::config::Config;
 use crate::debug::LogLevel;
+use crate::i18n::{self, tr, trf, Locale, Msg};
 
 fn main() -> Result<(), String> {
-    println!("🍭 甜甜的生命游戏 - 凸优化版本");
-    println!("=====================================");
+    i18n::set_locale(Locale::from_config(&Config::from_env())?);
+    println!("{}", tr(Msg::SweetLifeTitle));
 
     // 创建高级模拟器实例
     let mut gameboy = AdvancedGameBoy::new();
@@ -20,8 +22,7 @@ fn main() -> Result<(), String> {
     // 启动模拟器
     gameboy.start();
     
-    println!("🚀 开始甜甜的生命游戏...");
-    println!();
+    println!("{}", tr(Msg::SweetLifeStart));
     
     // 显示初始状态
     println!("{}", gameboy.get_debug_info());
@@ -32,18 +33,15 @@ fn main() -> Result<(), String> {
         match gameboy.step_once() {
             Ok(()) => {
                 if generation % 10 == 0 {
-                    println!("🍭 第{}代生命: PC=0x{:04X}", 
-                        generation + 1, 
-                        gameboy.cpu.pc
-                    );
+                    println!("{}", trf(Msg::SweetLifeGeneration, &[&(generation + 1), &format!("{:04X}", gameboy.cpu.pc)]));
                     
                     // 显示生命状态
                     let stats = gameboy.get_performance_stats();
-                    println!("   💖 生命统计: 周期={}, 指令={}, 效率={:.2}%", 
-                        stats.cycle_count, 
-                        stats.instruction_count,
-                        stats.hit_rate * 100.0
-                    );
+                    println!("{}", trf(Msg::SweetLifeStats, &[
+                        &stats.cycle_count,
+                        &stats.instruction_count,
+                        &format!("{:.2}", stats.hit_rate * 100.0),
+                    ]));
                     
                     if generation % 20 == 0 {
                         println!("{}", gameboy.get_debug_info());
@@ -52,24 +50,22 @@ fn main() -> Result<(), String> {
                 }
             }
             Err(e) => {
-                println!("❌ 生命演化错误: {}", e);
+                println!("{}", trf(Msg::SweetLifeError, &[&e]));
                 break;
             }
         }
     }
     
     // 生成甜甜的ROM文件
-    println!("🍭 生成甜甜的生命游戏ROM...");
-    let mut rom_generator = RomGenerator::new("SWEET LIFE");
-    rom_generator.add_program(0x100, &sweet_life_program);
+    println!("{}", tr(Msg::SweetLifeGenerating));
+    let rom_generator = RomGenerator::new("SWEET LIFE").program(0x150, &sweet_life_program);
     
     let filename = "sweet_life_game.gb";
     rom_generator.save_rom(filename).map_err(|e| e.to_string())?;
-    println!("✅ 甜甜的ROM文件生成成功: {}", filename);
+    println!("{}", trf(Msg::SweetLifeRomSaved, &[&filename]));
     
     // 显示最终生命状态
-    println!();
-    println!("🎉 甜甜的生命游戏完成！");
+    println!("{}", tr(Msg::SweetLifeFinished));
     println!("{}", gameboy.get_debug_info());
     
     Ok(())
@@ -1,12 +1,14 @@
 //! 甜甜的生命游戏 - 凸优化增强版
 
 use crate::{AdvancedGameBoy, RomGenerator};
+use crate::config::Config;
 use crate::debug::LogLevel;
+use crate::i18n::{self, tr, trf, Locale, Msg};
 use std::time::{Duration, Instant};
 
 fn main() -> Result<(), String> {
-    println!("🍭 甜甜的生命游戏 - 凸优化增强版");
-    println!("=====================================");
+    i18n::set_locale(Locale::from_config(&Config::from_env())?);
+    println!("{}", tr(Msg::SweetLifeOptimizedTitle));
 
     // 创建高级模拟器实例
     let mut gameboy = AdvancedGameBoy::new();
@@ -24,8 +26,7 @@ fn main() -> Result<(), String> {
     // 启动模拟器
     gameboy.start();
     
-    println!("🚀 开始甜甜的生命游戏（凸优化版）...");
-    println!();
+    println!("{}", tr(Msg::SweetLifeOptimizedStart));
     
     // 显示初始状态
     println!("{}", gameboy.get_debug_info());
@@ -47,30 +48,29 @@ fn main() -> Result<(), String> {
                 total_instructions = stats.instruction_count;
                 
                 if generation % 20 == 0 {
-                    println!("🍭 第{}代生命: PC=0x{:04X}", 
-                        generation + 1, 
-                        gameboy.cpu.pc
-                    );
+                    println!("{}", trf(Msg::SweetLifeGeneration, &[&(generation + 1), &format!("{:04X}", gameboy.cpu.pc)]));
                     
                     // 显示生命状态
-                    println!("   💖 生命统计: 周期={}, 指令={}, 效率={:.2}%", 
-                        stats.cycle_count, 
-                        stats.instruction_count,
-                        stats.hit_rate * 100.0
-                    );
+                    println!("{}", trf(Msg::SweetLifeStats, &[
+                        &stats.cycle_count,
+                        &stats.instruction_count,
+                        &format!("{:.2}", stats.hit_rate * 100.0),
+                    ]));
                     
                     // 显示寄存器状态
                     let cpu_state = gameboy.get_cpu_state();
-                    println!("   🧬 生命状态: A={:02X}, B={:02X}, C={:02X}, D={:02X}, E={:02X}, H={:02X}, L={:02X}",
-                        cpu_state.registers.a, cpu_state.registers.b, cpu_state.registers.c,
-                        cpu_state.registers.d, cpu_state.registers.e, cpu_state.registers.h, cpu_state.registers.l
-                    );
+                    let registers = &cpu_state.registers;
+                    let hex = |value: u8| format!("{:02X}", value);
+                    println!("{}", trf(Msg::SweetLifeRegisters, &[
+                        &hex(registers.a), &hex(registers.b), &hex(registers.c),
+                        &hex(registers.d), &hex(registers.e), &hex(registers.h), &hex(registers.l),
+                    ]));
                     
                     // 显示标志位
-                    println!("   🎯 生命标志: Z={}, N={}, H={}, C={}",
-                        cpu_state.flags.zero, cpu_state.flags.subtract, 
-                        cpu_state.flags.half_carry, cpu_state.flags.carry
-                    );
+                    println!("{}", trf(Msg::SweetLifeFlags, &[
+                        &cpu_state.flags.zero, &cpu_state.flags.subtract,
+                        &cpu_state.flags.half_carry, &cpu_state.flags.carry,
+                    ]));
                     
                     if generation % 40 == 0 {
                         println!("{}", gameboy.get_debug_info());
@@ -79,7 +79,7 @@ fn main() -> Result<(), String> {
                 }
             }
             Err(e) => {
-                println!("❌ 生命演化错误: {}", e);
+                println!("{}", trf(Msg::SweetLifeError, &[&e]));
                 break;
             }
         }
@@ -100,30 +100,26 @@ fn main() -> Result<(), String> {
     };
     
     // 生成甜甜的ROM文件
-    println!("🍭 生成甜甜的生命游戏ROM（凸优化版）...");
-    let mut rom_generator = RomGenerator::new("SWEET LIFE OPT");
-    rom_generator.add_program(0x100, &sweet_life_program);
+    println!("{}", tr(Msg::SweetLifeOptimizedGenerating));
+    let rom_generator = RomGenerator::new("SWEET LIFE OPT").program(0x150, &sweet_life_program);
     
     let filename = "sweet_life_game_optimized.gb";
     rom_generator.save_rom(filename).map_err(|e| e.to_string())?;
-    println!("✅ 甜甜的ROM文件生成成功: {}", filename);
+    println!("{}", trf(Msg::SweetLifeRomSaved, &[&filename]));
     
     // 显示最终性能统计
-    println!();
-    println!("🎉 甜甜的生命游戏完成！");
-    println!("📊 性能统计:");
-    println!("   ⏱️  总执行时间: {:.2}ms", elapsed_time.as_millis());
-    println!("   🔄 总周期数: {}", total_cycles);
-    println!("   📝 总指令数: {}", total_instructions);
-    println!("   ⚡ 周期/秒: {:.0}", cycles_per_second);
-    println!("   🚀 指令/秒: {:.0}", instructions_per_second);
-    println!("   🎯 平均每指令周期: {:.2}", 
-        if total_instructions > 0 { total_cycles as f64 / total_instructions as f64 } else { 0.0 }
-    );
-    
     let final_stats = gameboy.get_performance_stats();
-    println!("   💾 缓存命中率: {:.2}%", final_stats.hit_rate * 100.0);
-    println!("   🧬 生命代数: {}", generation_count);
+    println!("{}", tr(Msg::SweetLifeFinished));
+    println!("{}", trf(Msg::SweetLifePerformance, &[
+        &elapsed_time.as_millis(),
+        &total_cycles,
+        &total_instructions,
+        &format!("{:.0}", cycles_per_second),
+        &format!("{:.0}", instructions_per_second),
+        &format!("{:.2}", if total_instructions > 0 { total_cycles as f64 / total_instructions as f64 } else { 0.0 }),
+        &format!("{:.2}", final_stats.hit_rate * 100.0),
+        &generation_count,
+    ]));
     
     println!("{}", gameboy.get_debug_info());
     
@@ -0,0 +1,105 @@
+//! 网格拓扑
+//!
+//! 决定越过网格边缘的邻居在哪里：有界网格的边界外都是死细胞，
+//! 环面在两个方向上首尾相接，圆柱只在水平方向相接。
+//! 坐标换算都用显式的有符号运算，不依赖无符号数回绕。
+
+/// 网格边界的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Topology {
+    /// 边界外视为死细胞
+    #[default]
+    Bounded,
+    /// 左右、上下都首尾相接
+    Torus,
+    /// 左右相接，上下有界
+    Cylinder,
+}
+
+impl Topology {
+    /// 从命令行名称解析
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "bounded" => Ok(Topology::Bounded),
+            "torus" => Ok(Topology::Torus),
+            "cylinder" => Ok(Topology::Cylinder),
+            other => Err(format!("未知的拓扑: {} (可用: bounded, torus, cylinder)", other)),
+        }
+    }
+
+    /// `(x, y)` 偏移 `(dx, dy)` 后的格子，落在有界边缘之外时返回 `None`
+    pub fn neighbor(&self, x: usize, y: usize, dx: isize, dy: isize, width: usize, height: usize) -> Option<(usize, usize)> {
+        let (wrap_x, wrap_y) = match self {
+            Topology::Bounded => (false, false),
+            Topology::Torus => (true, true),
+            Topology::Cylinder => (true, false),
+        };
+        Some((Self::offset(x, dx, width, wrap_x)?, Self::offset(y, dy, height, wrap_y)?))
+    }
+
+    fn offset(value: usize, delta: isize, size: usize, wrap: bool) -> Option<usize> {
+        if size == 0 {
+            return None;
+        }
+        let moved = value as isize + delta;
+        if wrap {
+            Some(moved.rem_euclid(size as isize) as usize)
+        } else if (0..size as isize).contains(&moved) {
+            Some(moved as usize)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::life_game::LifeGrid;
+
+    /// 四个角都是活细胞的5x4网格
+    fn corners(topology: Topology) -> LifeGrid {
+        let mut grid = LifeGrid::with_topology(5, 4, topology);
+        for (x, y) in [(0, 0), (4, 0), (0, 3), (4, 3)] {
+            grid.set(x, y, true);
+        }
+        grid
+    }
+
+    #[test]
+    fn test_neighbor_arithmetic_at_edges() {
+        assert_eq!(Topology::Bounded.neighbor(0, 0, -1, -1, 5, 4), None);
+        assert_eq!(Topology::Bounded.neighbor(4, 3, 1, 0, 5, 4), None);
+        assert_eq!(Topology::Bounded.neighbor(2, 1, 1, 1, 5, 4), Some((3, 2)));
+        assert_eq!(Topology::Torus.neighbor(0, 0, -1, -1, 5, 4), Some((4, 3)));
+        assert_eq!(Topology::Torus.neighbor(4, 3, 1, 1, 5, 4), Some((0, 0)));
+        assert_eq!(Topology::Cylinder.neighbor(0, 1, -1, 0, 5, 4), Some((4, 1)));
+        assert_eq!(Topology::Cylinder.neighbor(0, 0, -1, -1, 5, 4), None);
+        assert_eq!(Topology::Torus.neighbor(0, 0, 1, 0, 0, 0), None);
+    }
+
+    #[test]
+    fn test_corner_and_edge_neighbor_counts_per_topology() {
+        // (格子, 有界, 环面, 圆柱)
+        let cases = [((0, 0), 0, 3, 1), ((4, 3), 0, 3, 1), ((2, 0), 0, 0, 0), ((0, 1), 1, 2, 2), ((4, 2), 1, 2, 2)];
+        for ((x, y), bounded, torus, cylinder) in cases {
+            assert_eq!(corners(Topology::Bounded).count_live_neighbors(x, y), bounded, "有界 ({}, {})", x, y);
+            assert_eq!(corners(Topology::Torus).count_live_neighbors(x, y), torus, "环面 ({}, {})", x, y);
+            assert_eq!(corners(Topology::Cylinder).count_live_neighbors(x, y), cylinder, "圆柱 ({}, {})", x, y);
+        }
+
+        // 环面上四个角合成一个方块，是静物；有界网格上四个孤立细胞全部死亡
+        let mut torus = corners(Topology::Torus);
+        torus.next_generation();
+        assert_eq!(torus.count_live_cells(), 4);
+        assert!(torus.get(0, 0) && torus.get(4, 3));
+        let mut bounded = corners(Topology::Bounded);
+        bounded.next_generation();
+        assert_eq!(bounded.count_live_cells(), 0);
+
+        // 1x1的环面上格子是自己的全部8个邻居
+        let mut tiny = LifeGrid::with_topology(1, 1, Topology::Torus);
+        tiny.set(0, 0, true);
+        assert_eq!(tiny.count_live_neighbors(0, 0), 8);
+    }
+}
@@ -2,7 +2,7 @@
 //! 
 //! 实现经典的俄罗斯方块游戏逻辑，包括方块移动、旋转、消除等
 
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::collections::VecDeque;
 
 /// 俄罗斯方块游戏状态
@@ -61,12 +61,15 @@ pub struct GameBoard {
 
 /// 游戏统计
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameStats {
     pub score: u32,
     pub lines_cleared: u32,
     pub level: u32,
     pub tetris_count: u32,
     pub total_pieces: u32,
+    /// 计时起点只在本进程内有效，反序列化时取当前时间
+    #[cfg_attr(feature = "serde", serde(skip, default = "Instant::now"))]
     pub start_time: Instant,
     pub play_time: Duration,
 }
@@ -83,6 +86,8 @@ pub struct TetrisGame {
     pub drop_interval: Duration,
     pub piece_bag: VecDeque<TetrominoType>,
     pub ghost_piece: Option<Tetromino>,
+    /// 打乱方块袋用的xorshift64状态
+    bag_rng: u64,
 }
 
 impl Tetromino {
@@ -297,8 +302,14 @@ impl GameBoard {
 }
 
 impl TetrisGame {
-    /// 创建新的俄罗斯方块游戏
+    /// 创建新的俄罗斯方块游戏，方块顺序由当前时间决定
     pub fn new() -> Self {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        Self::with_seed(seed)
+    }
+
+    /// 使用给定种子创建游戏，相同种子的方块顺序相同
+    pub fn with_seed(seed: u64) -> Self {
         let mut game = Self {
             board: GameBoard::new(10, 20),
             current_piece: None,
@@ -317,6 +328,7 @@ impl TetrisGame {
             drop_interval: Duration::from_millis(1000),
             piece_bag: VecDeque::new(),
             ghost_piece: None,
+            bag_rng: if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed },
         };
         
         game.fill_piece_bag();
@@ -333,14 +345,9 @@ impl TetrisGame {
             TetrominoType::S, TetrominoType::Z, TetrominoType::J, TetrominoType::L
         ];
         
-        // 随机打乱
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        
-        for i in 0..pieces.len() {
-            let mut hasher = DefaultHasher::new();
-            i.hash(&mut hasher);
-            let j = hasher.finish() as usize % pieces.len();
+        // Fisher-Yates洗牌
+        for i in (1..pieces.len()).rev() {
+            let j = (self.next_random() % (i as u64 + 1)) as usize;
             pieces.swap(i, j);
         }
         
@@ -349,6 +356,16 @@ impl TetrisGame {
         }
     }
     
+    /// 方块袋随机数生成器的下一个值
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.bag_rng;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.bag_rng = x;
+        x
+    }
+
     /// 生成下一个方块
     fn spawn_next_piece(&mut self) {
         if self.piece_bag.is_empty() {
@@ -5,10 +5,17 @@
 
 use crate::games::tetris::tetris_game::{TetrisGame, GameState, Tetromino, Color};
 use crate::gba::GBASystem;
+use crate::frontend::tui::{Align, Panel};
+use crate::config::Config;
+use crate::i18n::{self, tr, trf, Locale, Msg};
 use std::io::{self, Write, stdin};
 use std::time::{Duration, Instant};
 use std::thread;
 
+/// 右侧面板的起始列和内容宽度
+const PANEL_COLUMN: usize = 25;
+const PANEL_WIDTH: usize = 14;
+
 /// Windows俄罗斯方块游戏
 struct WindowsTetris {
     /// 俄罗斯方块游戏逻辑
@@ -33,7 +40,7 @@ impl WindowsTetris {
         // 创建简化的ROM数据
         let rom_data = Self::create_tetris_rom();
         if let Err(e) = gba.load_rom(rom_data) {
-            eprintln!("警告: 无法加载GBA ROM: {}", e);
+            eprintln!("{}", trf(Msg::TetrisRomLoadFailed, &[&e]));
         } else {
             gba.start().unwrap();
         }
@@ -93,7 +100,7 @@ impl WindowsTetris {
         
         // 更新GBA模拟器（用于底层支持）
         if let Err(e) = self.gba.step() {
-            eprintln!("GBA模拟器错误: {}", e);
+            eprintln!("{}", trf(Msg::TetrisGbaError, &[&e]));
         }
     }
     
@@ -214,105 +221,42 @@ impl WindowsTetris {
         let gba_stats = self.gba.get_stats();
         
         // 渲染统计信息
-        print!("\x1B[3;25H");
-        print!("┌─ 游戏统计 ─┐");
-        
-        print!("\x1B[4;25H");
-        print!("│ 分数: {:>8} │", stats.score);
-        
-        print!("\x1B[5;25H");
-        print!("│ 等级: {:>8} │", stats.level);
-        
-        print!("\x1B[6;25H");
-        print!("│ 行数: {:>8} │", stats.lines_cleared);
-        
-        print!("\x1B[7;25H");
-        print!("│ Tetris: {:>6} │", stats.tetris_count);
-        
-        print!("\x1B[8;25H");
-        print!("│ 方块数: {:>6} │", stats.total_pieces);
-        
-        print!("\x1B[9;25H");
-        print!("└──────────────┘");
+        Panel::new(tr(Msg::TetrisStatsPanel))
+            .width(PANEL_WIDTH)
+            .field(tr(Msg::TetrisScore), stats.score)
+            .field(tr(Msg::TetrisLevel), stats.level)
+            .field(tr(Msg::TetrisLines), stats.lines_cleared)
+            .field("Tetris:", stats.tetris_count)
+            .field(tr(Msg::TetrisPieces), stats.total_pieces)
+            .draw(3, PANEL_COLUMN);
         
         // 渲染GBA统计信息
-        print!("\x1B[11;25H");
-        print!("┌─ GBA统计 ─┐");
-        
-        print!("\x1B[12;25H");
-        print!("│ FPS: {:>8.1} │", gba_stats.fps);
-        
-        print!("\x1B[13;25H");
-        print!("│ CPU: {:>7.1}% │", gba_stats.cpu_usage * 100.0);
-        
-        print!("\x1B[14;25H");
-        print!("│ 帧数: {:>7} │", gba_stats.total_frames);
-        
-        print!("\x1B[15;25H");
-        print!("└────────────┘");
+        Panel::new(tr(Msg::TetrisGbaPanel))
+            .width(PANEL_WIDTH)
+            .field("FPS:", format!("{:.1}", gba_stats.fps))
+            .field("IPS:", format!("{:.2}M", gba_stats.instructions_per_second / 1_000_000.0))
+            .field(tr(Msg::TetrisFrames), gba_stats.total_frames)
+            .draw(11, PANEL_COLUMN);
         
         // 渲染游戏状态
-        print!("\x1B[17;25H");
-        match self.tetris.get_state() {
-            GameState::Playing => {
-                print!("┌─ 游戏状态 ─┐");
-                print!("\x1B[18;25H");
-                print!("│   游戏中    │");
-                print!("\x1B[19;25H");
-                print!("└────────────┘");
-            },
-            GameState::Paused => {
-                print!("┌─ 游戏状态 ─┐");
-                print!("\x1B[18;25H");
-                print!("│   暂停中    │");
-                print!("\x1B[19;25H");
-                print!("└────────────┘");
-            },
-            GameState::GameOver => {
-                print!("┌─ 游戏状态 ─┐");
-                print!("\x1B[18;25H");
-                print!("│   游戏结束  │");
-                print!("\x1B[19;25H");
-                print!("└────────────┘");
-            },
-            GameState::Menu => {
-                print!("┌─ 游戏状态 ─┐");
-                print!("\x1B[18;25H");
-                print!("│   主菜单    │");
-                print!("\x1B[19;25H");
-                print!("└────────────┘");
-            },
-        }
+        let state = match self.tetris.get_state() {
+            GameState::Playing => tr(Msg::TetrisStatePlaying),
+            GameState::Paused => tr(Msg::TetrisStatePaused),
+            GameState::GameOver => tr(Msg::TetrisStateGameOver),
+            GameState::Menu => tr(Msg::TetrisStateMenu),
+        };
+        Panel::new(tr(Msg::TetrisStatePanel))
+            .width(PANEL_WIDTH)
+            .aligned(state, Align::Center)
+            .draw(17, PANEL_COLUMN);
     }
     
     /// 渲染控制说明
     fn render_controls(&mut self) {
-        print!("\x1B[21;25H");
-        print!("┌─ 控制说明 ─┐");
-        
-        print!("\x1B[22;25H");
-        print!("│ A/D: 左右移动 │");
-        
-        print!("\x1B[23;25H");
-        print!("│ S: 快速下降   │");
-        
-        print!("\x1B[24;25H");
-        print!("│ W: 旋转方块   │");
-        
-        print!("\x1B[25;25H");
-        print!("│ 空格: 硬降落  │");
-        
-        print!("\x1B[26;25H");
-        print!("│ P: 暂停游戏   │");
-        
-        print!("\x1B[27;25H");
-        print!("│ R: 重新开始   │");
-        
-        print!("\x1B[28;25H");
-        print!("│ Q: 退出游戏   │");
-        
-        print!("\x1B[29;25H");
-        print!("└──────────────┘");
+        tr(Msg::TetrisControls)
+            .lines()
+            .fold(Panel::new(tr(Msg::TetrisControlsPanel)).width(PANEL_WIDTH), Panel::line)
+            .draw(21, PANEL_COLUMN);
     }
     
     /// 处理输入
@@ -365,20 +309,7 @@ impl WindowsTetris {
     /// 显示欢迎信息
     fn show_welcome(&mut self) {
         self.clear_screen();
-        println!("🎮 Windows俄罗斯方块 - 基于GBA模拟器");
-        println!("==================================================");
-        println!("");
-        println!("欢迎来到俄罗斯方块游戏！");
-        println!("本游戏基于我们开发的GBA模拟器实现");
-        println!("");
-        println!("游戏特色：");
-        println!("  ✅ 完整的俄罗斯方块游戏逻辑");
-        println!("  ✅ 基于GBA模拟器底层支持");
-        println!("  ✅ 实时性能统计");
-        println!("  ✅ 幽灵方块预览");
-        println!("  ✅ 完整的UI界面");
-        println!("");
-        println!("按任意键开始游戏...");
+        println!("{}", tr(Msg::TetrisWelcome));
         
         let mut input = String::new();
         stdin().read_line(&mut input).ok();
@@ -389,39 +320,33 @@ impl WindowsTetris {
         self.clear_screen();
         let stats = self.tetris.get_stats();
         
-        println!("🎮 游戏结束！");
-        println!("==================================================");
-        println!("");
-        println!("最终统计：");
-        println!("  分数: {}", stats.score);
-        println!("  等级: {}", stats.level);
-        println!("  清除行数: {}", stats.lines_cleared);
-        println!("  Tetris次数: {}", stats.tetris_count);
-        println!("  总方块数: {}", stats.total_pieces);
-        println!("  游戏时间: {:.1}秒", stats.play_time.as_secs_f64());
-        println!("");
-        println!("感谢游玩！");
-        println!("");
+        println!("{}", trf(Msg::TetrisGameOver, &[
+            &stats.score,
+            &stats.level,
+            &stats.lines_cleared,
+            &stats.tetris_count,
+            &stats.total_pieces,
+            &format!("{:.1}", stats.play_time.as_secs_f64()),
+        ]));
     }
 }
 
 /// 主函数
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🎮 启动Windows俄罗斯方块游戏...");
-    println!("基于GBA模拟器实现");
-    println!("");
+    i18n::set_locale(Locale::from_config(&Config::from_env())?);
+    println!("{}", tr(Msg::TetrisLaunch));
     
     // 检查系统支持
     if cfg!(windows) {
-        println!("✅ Windows系统检测通过");
+        println!("{}", tr(Msg::TetrisWindowsDetected));
     } else {
-        println!("⚠️  非Windows系统，但游戏仍可运行");
+        println!("{}", tr(Msg::TetrisNotWindows));
     }
     
     // 创建并运行游戏
     let mut game = WindowsTetris::new();
     game.run();
     
-    println!("游戏已退出，感谢游玩！");
+    println!("{}", tr(Msg::TetrisExited));
     Ok(())
 }
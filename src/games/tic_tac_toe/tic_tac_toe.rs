@@ -7,6 +7,10 @@ use crate::entropy::{
     EntropyManager, EntropyError,
     entropy_pool::PooledEntropy,
 };
+use crate::games::ai::{GameTree, MinimaxEngine, MoveEvaluation, SearchBudget, WIN_SCORE};
+use crate::frontend::tui::{pad, Align};
+use crate::i18n::{self, tr, trf, Locale, Msg};
+use crate::config::Config;
 
 use std::time::{Duration, Instant};
 use std::thread;
@@ -15,28 +19,28 @@ use std::process::Command;
 
 /// 井字棋游戏板
 #[derive(Clone, Debug)]
-struct TicTacToeBoard {
+pub struct TicTacToeBoard {
     board: [[Option<Player>; 3]; 3],
-    current_player: Player,
-    game_state: GameState,
+    pub current_player: Player,
+    pub game_state: GameState,
     move_count: u8,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum Player {
+pub enum Player {
     X,
     O,
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum GameState {
+pub enum GameState {
     Playing,
     Win(Player),
     Draw,
 }
 
 impl TicTacToeBoard {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             board: [[None; 3]; 3],
             current_player: Player::X,
@@ -45,17 +49,17 @@ impl TicTacToeBoard {
         }
     }
     
-    fn make_move(&mut self, row: usize, col: usize) -> Result<(), String> {
+    pub fn make_move(&mut self, row: usize, col: usize) -> Result<(), String> {
         if row >= 3 || col >= 3 {
-            return Err("位置超出范围".to_string());
+            return Err(tr(Msg::TttOutOfRange).to_string());
         }
         
         if self.board[row][col].is_some() {
-            return Err("该位置已被占用".to_string());
+            return Err(tr(Msg::TttOccupied).to_string());
         }
         
         if self.game_state != GameState::Playing {
-            return Err("游戏已结束".to_string());
+            return Err(tr(Msg::TttGameOver).to_string());
         }
         
         self.board[row][col] = Some(self.current_player);
@@ -112,19 +116,20 @@ impl TicTacToeBoard {
     }
     
     fn display(&self) {
-        println!("🎮 井字棋游戏");
-        println!("当前玩家: {}", match self.current_player {
+        println!("{}", tr(Msg::TttTitle));
+        println!("{}", trf(Msg::TttCurrentPlayer, &[&match self.current_player {
             Player::X => "❌",
             Player::O => "⭕",
-        });
+        }]));
         println!("┌───┬───┬───┐");
         
         for (i, row) in self.board.iter().enumerate() {
             print!("│");
             for cell in row {
                 match cell {
-                    Some(Player::X) => print!(" ❌ │"),
-                    Some(Player::O) => print!(" ⭕ │"),
+                    // emoji占两列，按显示宽度居中才能和边框对齐
+                    Some(Player::X) => print!("{}│", pad("❌", 3, Align::Center)),
+                    Some(Player::O) => print!("{}│", pad("⭕", 3, Align::Center)),
                     None => print!("   │"),
                 }
             }
@@ -137,9 +142,39 @@ impl TicTacToeBoard {
         println!("└───┴───┴───┘");
         
         match self.game_state {
-            GameState::Playing => println!("游戏进行中..."),
-            GameState::Win(player) => println!("🎉 玩家 {:?} 获胜！", player),
-            GameState::Draw => println!("🤝 平局！"),
+            GameState::Playing => println!("{}", tr(Msg::TttPlaying)),
+            GameState::Win(player) => println!("{}", trf(Msg::TttWin, &[&format!("{:?}", player)])),
+            GameState::Draw => println!("{}", tr(Msg::TttDraw)),
+        }
+    }
+    
+    /// 显示AI候选走法热力图，空格按AI视角的评估分值着色
+    fn display_heatmap(&self, candidates: &[MoveEvaluation<(usize, usize)>]) {
+        println!("{}", tr(Msg::TttHeatmap));
+        println!("┌───┬───┬───┐");
+        
+        for (i, row) in self.board.iter().enumerate() {
+            print!("│");
+            for (j, cell) in row.iter().enumerate() {
+                match cell {
+                    Some(Player::X) => print!("{}│", pad("❌", 3, Align::Center)),
+                    Some(Player::O) => print!("{}│", pad("⭕", 3, Align::Center)),
+                    None => match candidates.iter().find(|c| c.mv == (i, j)) {
+                        Some(eval) => print!("\x1b[{}m{:^3}\x1b[0m│", heat_color(eval), score_label(eval)),
+                        None => print!("   │"),
+                    },
+                }
+            }
+            println!();
+            if i < 2 {
+                println!("├───┼───┼───┤");
+            }
+        }
+        
+        println!("└───┴───┴───┘");
+        
+        for (rank, eval) in candidates.iter().enumerate() {
+            println!("{}", trf(Msg::TttHeatmapCandidate, &[&(rank + 1), &eval.mv.0, &eval.mv.1, &score_label(eval)]));
         }
     }
     
@@ -156,10 +191,38 @@ impl TicTacToeBoard {
     }
 }
 
+impl Default for TicTacToeBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 评估分值的简短标签
+fn score_label(eval: &MoveEvaluation<(usize, usize)>) -> String {
+    if eval.is_decisive() {
+        let plies = WIN_SCORE - eval.score.abs();
+        format!("{}{}", if eval.score > 0 { 'W' } else { 'L' }, plies)
+    } else {
+        format!("{:+}", eval.score)
+    }
+}
+
+/// 评估分值对应的ANSI背景色
+fn heat_color(eval: &MoveEvaluation<(usize, usize)>) -> u8 {
+    match eval.score {
+        s if s >= WIN_SCORE / 2 => 42,  // 绿：必胜
+        s if s > 0 => 102,              // 浅绿：占优
+        0 => 43,                        // 黄：均势
+        s if s > -WIN_SCORE / 2 => 101, // 浅红：劣势
+        _ => 41,                        // 红：必败
+    }
+}
+
 /// AI玩家
 struct AI {
     difficulty: Difficulty,
     entropy_pool: PooledEntropy,
+    engine: MinimaxEngine,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -169,6 +232,36 @@ enum Difficulty {
     Hard,    // 高级策略
 }
 
+/// 困难模式每步的思考时间（毫秒）
+const HARD_TIME_BUDGET_MS: u64 = 100;
+
+/// AI走子结果
+#[derive(Debug, Clone)]
+struct MoveResult {
+    position: (usize, usize),
+    /// 搜索到的最大深度，非搜索策略为0
+    depth_reached: u32,
+    /// 搜索访问的节点数，非搜索策略为0
+    nodes: u64,
+    elapsed: Duration,
+    timed_out: bool,
+    /// 候选走法评估，仅在开启思考分析时由搜索策略填充
+    candidates: Vec<MoveEvaluation<(usize, usize)>>,
+}
+
+impl MoveResult {
+    fn heuristic(position: (usize, usize)) -> Self {
+        Self {
+            position,
+            depth_reached: 0,
+            nodes: 0,
+            elapsed: Duration::ZERO,
+            timed_out: false,
+            candidates: Vec::new(),
+        }
+    }
+}
+
 impl AI {
     fn new(difficulty: Difficulty) -> Result<Self, EntropyError> {
         let mut entropy_pool = PooledEntropy::new(1024);
@@ -182,21 +275,28 @@ impl AI {
         Ok(Self {
             difficulty,
             entropy_pool,
+            engine: MinimaxEngine::new(SearchBudget::millis(HARD_TIME_BUDGET_MS)),
         })
     }
     
-    fn get_move(&mut self, board: &TicTacToeBoard) -> Result<(usize, usize), EntropyError> {
+    /// 开启后困难模式会给出每个候选走法的精确评估
+    fn set_show_analysis(&mut self, enabled: bool) {
+        self.engine.set_collect_move_scores(enabled);
+    }
+    
+    /// 选择下一步，棋盘上没有可走的位置时返回 `None`
+    fn get_move(&mut self, board: &TicTacToeBoard) -> Option<MoveResult> {
         let available_moves = board.get_available_moves();
         
         if available_moves.is_empty() {
-            return Err(EntropyError::InsufficientEntropy);
+            return None;
         }
         
         match self.difficulty {
             Difficulty::Easy => {
                 // 随机选择
                 let random_index = self.entropy_pool.get_random_range(0, available_moves.len() as u32) as usize;
-                Ok(available_moves[random_index])
+                Some(MoveResult::heuristic(available_moves[random_index]))
             }
             Difficulty::Medium => {
                 // 简单策略：优先中心，然后角落，最后边缘
@@ -204,75 +304,91 @@ impl AI {
                 let corners = [(0, 0), (0, 2), (2, 0), (2, 2)];
                 let edges = [(0, 1), (1, 0), (1, 2), (2, 1)];
                 
-                if available_moves.contains(&center) {
-                    Ok(center)
+                let position = if available_moves.contains(&center) {
+                    center
                 } else if let Some(&corner) = corners.iter().find(|&&pos| available_moves.contains(&pos)) {
-                    Ok(corner)
+                    corner
                 } else if let Some(&edge) = edges.iter().find(|&&pos| available_moves.contains(&pos)) {
-                    Ok(edge)
+                    edge
                 } else {
                     let random_index = self.entropy_pool.get_random_range(0, available_moves.len() as u32) as usize;
-                    Ok(available_moves[random_index])
-                }
+                    available_moves[random_index]
+                };
+                Some(MoveResult::heuristic(position))
             }
             Difficulty::Hard => {
-                // 高级策略：使用minimax算法
+                // 高级策略：限时迭代加深的minimax搜索
                 self.minimax_move(board)
             }
         }
     }
     
-    fn minimax_move(&mut self, board: &TicTacToeBoard) -> Result<(usize, usize), EntropyError> {
-        let available_moves = board.get_available_moves();
-        let mut best_score = i32::MIN;
-        let mut best_move = available_moves[0];
-        
-        for &(row, col) in &available_moves {
-            let mut test_board = board.clone();
-            test_board.make_move(row, col).unwrap();
-            
-            let score = self.minimax(&test_board, false, 0);
-            if score > best_score {
-                best_score = score;
-                best_move = (row, col);
-            }
-        }
-        
-        Ok(best_move)
+    /// 对局已经结束时搜索没有走法，返回 `None`
+    fn minimax_move(&mut self, board: &TicTacToeBoard) -> Option<MoveResult> {
+        let result = self.engine.search(board);
+        let position = result.best_move?;
+        
+        Some(MoveResult {
+            position,
+            depth_reached: result.depth_reached,
+            nodes: result.nodes,
+            elapsed: result.elapsed,
+            timed_out: result.timed_out,
+            candidates: result.candidates,
+        })
     }
+}
+
+impl GameTree for TicTacToeBoard {
+    type Move = (usize, usize);
     
-    fn minimax(&self, board: &TicTacToeBoard, is_maximizing: bool, depth: u8) -> i32 {
-        match board.game_state {
-            GameState::Win(Player::O) => return 10 - depth as i32, // AI获胜
-            GameState::Win(Player::X) => return -10 + depth as i32, // 玩家获胜
-            GameState::Draw => return 0,
-            GameState::Playing => {}
+    fn legal_moves(&self) -> Vec<(usize, usize)> {
+        if self.game_state == GameState::Playing {
+            self.get_available_moves()
+        } else {
+            Vec::new()
         }
-        
-        if depth >= 9 {
-            return 0;
+    }
+    
+    fn apply_move(&mut self, mv: (usize, usize)) {
+        let _ = self.make_move(mv.0, mv.1);
+    }
+    
+    fn is_terminal(&self) -> bool {
+        self.game_state != GameState::Playing
+    }
+    
+    fn evaluate(&self) -> i32 {
+        match self.game_state {
+            // 只有刚落子的一方可能获胜，因此轮到的一方必然落败
+            GameState::Win(_) => -WIN_SCORE,
+            GameState::Draw => 0,
+            GameState::Playing => self.open_line_balance(),
         }
+    }
+}
+
+impl TicTacToeBoard {
+    /// 启发式评估：当前玩家独占的线数减去对手独占的线数
+    fn open_line_balance(&self) -> i32 {
+        const LINES: [[(usize, usize); 3]; 8] = [
+            [(0, 0), (0, 1), (0, 2)], [(1, 0), (1, 1), (1, 2)], [(2, 0), (2, 1), (2, 2)],
+            [(0, 0), (1, 0), (2, 0)], [(0, 1), (1, 1), (2, 1)], [(0, 2), (1, 2), (2, 2)],
+            [(0, 0), (1, 1), (2, 2)], [(0, 2), (1, 1), (2, 0)],
+        ];
         
-        let available_moves = board.get_available_moves();
-        if is_maximizing {
-            let mut max_eval = i32::MIN;
-            for &(row, col) in &available_moves {
-                let mut test_board = board.clone();
-                test_board.make_move(row, col).unwrap();
-                let eval = self.minimax(&test_board, false, depth + 1);
-                max_eval = max_eval.max(eval);
-            }
-            max_eval
-        } else {
-            let mut min_eval = i32::MAX;
-            for &(row, col) in &available_moves {
-                let mut test_board = board.clone();
-                test_board.make_move(row, col).unwrap();
-                let eval = self.minimax(&test_board, true, depth + 1);
-                min_eval = min_eval.min(eval);
+        let mut balance = 0;
+        for line in &LINES {
+            let mine = line.iter().filter(|&&(r, c)| self.board[r][c] == Some(self.current_player)).count() as i32;
+            let theirs = line.iter().filter(|&&(r, c)| matches!(self.board[r][c], Some(p) if p != self.current_player)).count() as i32;
+            
+            if theirs == 0 {
+                balance += mine;
+            } else if mine == 0 {
+                balance -= theirs;
             }
-            min_eval
         }
+        balance
     }
 }
 
@@ -298,23 +414,23 @@ impl LifeGameManager {
         
         let games = vec![
             LifeGameInfo {
-                name: "全新的生命游戏".to_string(),
+                name: tr(Msg::LifeGameNew).to_string(),
                 executable: "new-life-game".to_string(),
-                description: "基于外部熵源的细胞自动机模拟".to_string(),
+                description: tr(Msg::LifeGameNewDescription).to_string(),
                 is_active: false,
                 last_run: None,
             },
             LifeGameInfo {
-                name: "甜甜的生命游戏".to_string(),
+                name: tr(Msg::LifeGameSweet).to_string(),
                 executable: "sweet-life-game".to_string(),
-                description: "凸优化版本的生命游戏".to_string(),
+                description: tr(Msg::LifeGameSweetDescription).to_string(),
                 is_active: false,
                 last_run: None,
             },
             LifeGameInfo {
-                name: "优化的生命游戏".to_string(),
+                name: tr(Msg::LifeGameOptimized).to_string(),
                 executable: "sweet-life-optimized".to_string(),
-                description: "性能优化版本的生命游戏".to_string(),
+                description: tr(Msg::LifeGameOptimizedDescription).to_string(),
                 is_active: false,
                 last_run: None,
             },
@@ -327,15 +443,13 @@ impl LifeGameManager {
     }
     
     fn list_games(&self) {
-        println!("🎮 可用的生命游戏:");
-        println!("==================================================");
+        println!("{}", tr(Msg::LifeGamesTitle));
         
         for (i, game) in self.games.iter().enumerate() {
-            let status = if game.is_active { "🟢 运行中" } else { "⚪ 未运行" };
-            println!("{}. {} - {}", i + 1, game.name, status);
-            println!("   描述: {}", game.description);
+            let status = tr(if game.is_active { Msg::LifeGameRunning } else { Msg::LifeGameIdle });
+            println!("{}", trf(Msg::LifeGameEntry, &[&(i + 1), &game.name, &status, &game.description]));
             if let Some(last_run) = game.last_run {
-                println!("   最后运行: {:.1}秒前", last_run.elapsed().as_secs_f64());
+                println!("{}", trf(Msg::LifeGameLastRun, &[&format!("{:.1}", last_run.elapsed().as_secs_f64())]));
             }
             println!();
         }
@@ -343,73 +457,72 @@ impl LifeGameManager {
     
     fn run_game(&mut self, index: usize) -> Result<(), String> {
         if index >= self.games.len() {
-            return Err("无效的游戏索引".to_string());
+            return Err(tr(Msg::LifeGameInvalidIndex).to_string());
         }
         
         let game = &mut self.games[index];
-        println!("🚀 启动 {}...", game.name);
+        println!("{}", trf(Msg::LifeGameLaunching, &[&game.name]));
         
         // 检查可执行文件是否存在
         let executable_path = format!("target/release/{}", game.executable);
         if !std::path::Path::new(&executable_path).exists() {
-            println!("⚠️  可执行文件不存在，尝试编译...");
+            println!("{}", tr(Msg::LifeGameBuilding));
             let output = Command::new("cargo")
                 .args(&["build", "--bin", &game.executable, "--release"])
                 .output()
-                .map_err(|e| format!("编译失败: {}", e))?;
+                .map_err(|e| trf(Msg::LifeGameBuildFailed, &[&e]))?;
             
             if !output.status.success() {
-                return Err(format!("编译失败: {}", String::from_utf8_lossy(&output.stderr)));
+                return Err(trf(Msg::LifeGameBuildFailed, &[&String::from_utf8_lossy(&output.stderr)]));
             }
         }
         
         // 运行游戏
         let output = Command::new(&executable_path)
             .output()
-            .map_err(|e| format!("运行失败: {}", e))?;
+            .map_err(|e| trf(Msg::LifeGameRunFailed, &[&e]))?;
         
         game.is_active = true;
         game.last_run = Some(Instant::now());
         
-        println!("✅ {} 运行完成", game.name);
+        println!("{}", trf(Msg::LifeGameFinished, &[&game.name]));
         if !output.stdout.is_empty() {
-            println!("输出:\n{}", String::from_utf8_lossy(&output.stdout));
+            println!("{}", trf(Msg::LifeGameStdout, &[&String::from_utf8_lossy(&output.stdout)]));
         }
         if !output.stderr.is_empty() {
-            println!("错误:\n{}", String::from_utf8_lossy(&output.stderr));
+            println!("{}", trf(Msg::LifeGameStderr, &[&String::from_utf8_lossy(&output.stderr)]));
         }
         
         Ok(())
     }
     
     fn run_all_games(&mut self) -> Result<(), String> {
-        println!("🌟 启动所有生命游戏，让它们充满活力！");
-        println!("==================================================");
+        println!("{}", tr(Msg::LifeGameRunAll));
         
         for i in 0..self.games.len() {
             match self.run_game(i) {
                 Ok(()) => {
-                    println!("✅ {} 成功启动", self.games[i].name);
+                    println!("{}", trf(Msg::LifeGameStarted, &[&self.games[i].name]));
                     thread::sleep(Duration::from_millis(500)); // 短暂延迟
                 }
                 Err(e) => {
-                    println!("❌ {} 启动失败: {}", self.games[i].name, e);
+                    println!("{}", trf(Msg::LifeGameStartFailed, &[&self.games[i].name, &e]));
                 }
             }
         }
         
-        println!("\n🎉 所有生命游戏已启动！");
+        println!("{}", tr(Msg::LifeGameAllStarted));
         Ok(())
     }
     
     fn get_entropy_stats(&self) -> String {
         let stats = self.entropy_manager.get_entropy_stats();
-        format!(
-            "熵源数量: {}, 池大小: {} 字节, 分布质量: {:.3}, 量子强度: {:.3}",
-            stats.source_count, stats.pool_size, 
-            stats.optimizer_stats.distribution_quality,
-            stats.quantum_stats.post_quantum_strength
-        )
+        trf(Msg::LifeGameEntropyStats, &[
+            &stats.source_count,
+            &stats.pool_size,
+            &format!("{:.3}", stats.optimizer_stats.distribution_quality),
+            &format!("{:.3}", stats.quantum_stats.post_quantum_strength),
+        ])
     }
 }
 
@@ -456,11 +569,7 @@ impl GameSystem {
     }
     
     fn play_tic_tac_toe(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("🎮 欢迎来到井字棋游戏！");
-        println!("选择难度:");
-        println!("1. 简单 (随机移动)");
-        println!("2. 中等 (简单策略)");
-        println!("3. 困难 (高级AI)");
+        println!("{}", tr(Msg::TttWelcome));
         
         let mut input = String::new();
         io::stdin().read_line(&mut input)?;
@@ -473,7 +582,12 @@ impl GameSystem {
         };
         
         self.ai = AI::new(difficulty)?;
-        println!("✅ 难度设置为: {:?}", difficulty);
+        println!("{}", trf(Msg::TttDifficultySet, &[&format!("{:?}", difficulty)]));
+        
+        println!("{}", tr(Msg::TttAskHeatmap));
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        self.ai.set_show_analysis(input.trim().eq_ignore_ascii_case("y"));
         
         loop {
             self.tic_tac_toe.display();
@@ -485,23 +599,23 @@ impl GameSystem {
             
             if self.tic_tac_toe.current_player == Player::X {
                 // 玩家回合
-                println!("请输入位置 (行 列，例如: 1 1):");
+                println!("{}", tr(Msg::TttAskMove));
                 let mut input = String::new();
                 io::stdin().read_line(&mut input)?;
                 
                 let parts: Vec<&str> = input.trim().split_whitespace().collect();
                 if parts.len() != 2 {
-                    println!("❌ 请输入两个数字，用空格分隔");
+                    println!("{}", tr(Msg::TttNeedTwoNumbers));
                     continue;
                 }
                 
-                let row: usize = parts[0].parse().map_err(|_| "无效的行")?;
-                let col: usize = parts[1].parse().map_err(|_| "无效的列")?;
+                let row: usize = parts[0].parse().map_err(|_| tr(Msg::TttInvalidRow))?;
+                let col: usize = parts[1].parse().map_err(|_| tr(Msg::TttInvalidColumn))?;
                 
                 match self.tic_tac_toe.make_move(row, col) {
                     Ok(()) => {
                         self.stats.total_moves += 1;
-                        println!("✅ 移动成功");
+                        println!("{}", tr(Msg::TttMoveOk));
                     }
                     Err(e) => {
                         println!("❌ {}", e);
@@ -510,17 +624,29 @@ impl GameSystem {
                 }
             } else {
                 // AI回合
-                println!("🤖 AI正在思考...");
+                println!("{}", tr(Msg::TttAiThinking));
                 thread::sleep(Duration::from_millis(1000));
                 
                 match self.ai.get_move(&self.tic_tac_toe) {
-                    Ok((row, col)) => {
+                    Some(result) => {
+                        if !result.candidates.is_empty() {
+                            self.tic_tac_toe.display_heatmap(&result.candidates);
+                        }
+                        let (row, col) = result.position;
                         self.tic_tac_toe.make_move(row, col).unwrap();
                         self.stats.total_moves += 1;
-                        println!("🤖 AI选择了位置 ({}, {})", row, col);
+                        println!("{}", trf(Msg::TttAiMove, &[&row, &col]));
+                        if result.nodes > 0 {
+                            println!("{}", trf(Msg::TttAiSearch, &[
+                                &result.depth_reached,
+                                &result.nodes,
+                                &format!("{:.1}", result.elapsed.as_secs_f64() * 1000.0),
+                                &if result.timed_out { tr(Msg::TttAiTimedOut) } else { "" },
+                            ]));
+                        }
                     }
-                    Err(e) => {
-                        println!("❌ AI错误: {}", e);
+                    None => {
+                        println!("{}", tr(Msg::TttAiNoMove));
                         break;
                     }
                 }
@@ -538,20 +664,20 @@ impl GameSystem {
         match self.tic_tac_toe.game_state {
             GameState::Win(Player::X) => {
                 self.stats.player_wins += 1;
-                println!("🎉 恭喜！你赢了！");
+                println!("{}", tr(Msg::TttPlayerWins));
             }
             GameState::Win(Player::O) => {
                 self.stats.ai_wins += 1;
-                println!("🤖 AI获胜！");
+                println!("{}", tr(Msg::TttAiWins));
             }
             GameState::Draw => {
                 self.stats.draws += 1;
-                println!("🤝 平局！");
+                println!("{}", tr(Msg::TttDraw));
             }
             _ => {}
         }
         
-        println!("是否再玩一局？(y/n)");
+        println!("{}", tr(Msg::TttPlayAgain));
         let mut input = String::new();
         io::stdin().read_line(&mut input).unwrap();
         
@@ -562,16 +688,7 @@ impl GameSystem {
     
     fn show_menu(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         loop {
-            println!("\n🎮 游戏系统主菜单");
-            println!("==============================");
-            println!("1. 玩井字棋");
-            println!("2. 查看生命游戏");
-            println!("3. 运行所有生命游戏");
-            println!("4. 运行特定生命游戏");
-            println!("5. 查看统计信息");
-            println!("6. 查看熵源信息");
-            println!("0. 退出");
-            println!("==============================");
+            println!("{}", tr(Msg::TttMenu));
             
             let mut input = String::new();
             io::stdin().read_line(&mut input)?;
@@ -588,25 +705,25 @@ impl GameSystem {
                 }
                 "4" => {
                     self.life_manager.list_games();
-                    println!("请输入游戏编号:");
+                    println!("{}", tr(Msg::TttAskGameNumber));
                     let mut input = String::new();
                     io::stdin().read_line(&mut input)?;
-                    let index: usize = input.trim().parse().map_err(|_| "无效编号")?;
+                    let index: usize = input.trim().parse().map_err(|_| tr(Msg::TttInvalidNumber))?;
                     self.life_manager.run_game(index - 1)?;
                 }
                 "5" => {
                     self.show_stats();
                 }
                 "6" => {
-                    println!("🔬 熵源系统信息:");
+                    println!("{}", tr(Msg::TttEntropyInfo));
                     println!("{}", self.life_manager.get_entropy_stats());
                 }
                 "0" => {
-                    println!("👋 再见！");
+                    println!("{}", tr(Msg::TttBye));
                     break;
                 }
                 _ => {
-                    println!("❌ 无效选择");
+                    println!("{}", tr(Msg::TttInvalidChoice));
                 }
             }
         }
@@ -615,43 +732,94 @@ impl GameSystem {
     }
     
     fn show_stats(&self) {
-        println!("\n📊 游戏统计信息");
-        println!("==============================");
-        println!("井字棋游戏:");
-        println!("  总游戏数: {}", self.stats.games_played);
-        println!("  玩家获胜: {}", self.stats.player_wins);
-        println!("  AI获胜: {}", self.stats.ai_wins);
-        println!("  平局: {}", self.stats.draws);
-        println!("  总移动数: {}", self.stats.total_moves);
+        println!("{}", trf(Msg::TttStats, &[
+            &self.stats.games_played,
+            &self.stats.player_wins,
+            &self.stats.ai_wins,
+            &self.stats.draws,
+            &self.stats.total_moves,
+        ]));
         
         if self.stats.games_played > 0 {
             let win_rate = (self.stats.player_wins as f64 / self.stats.games_played as f64) * 100.0;
-            println!("  玩家胜率: {:.1}%", win_rate);
+            println!("{}", trf(Msg::TttWinRate, &[&format!("{:.1}", win_rate)]));
         }
         
-        println!("\n生命游戏:");
+        println!("{}", tr(Msg::TttLifeGames));
         for game in &self.life_manager.games {
-            let status = if game.is_active { "🟢 运行中" } else { "⚪ 未运行" };
+            let status = tr(if game.is_active { Msg::LifeGameRunning } else { Msg::LifeGameIdle });
             println!("  {}: {}", game.name, status);
         }
         
-        println!("\n系统运行时间: {:.1}秒", self.stats.start_time.elapsed().as_secs_f64());
+        println!("{}", trf(Msg::TttUptime, &[&format!("{:.1}", self.stats.start_time.elapsed().as_secs_f64())]));
     }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🎮 Tic-Tac-Toe 井字棋游戏系统");
-    println!("集成所有生命游戏的活力运行");
-    println!("==================================================");
+    i18n::set_locale(Locale::from_config(&Config::from_env())?);
+    println!("{}", tr(Msg::TttBanner));
     
     let mut game_system = GameSystem::new()?;
     
-    println!("🌟 系统初始化完成！");
-    println!("🔬 熵源系统已激活");
-    println!("🎮 所有生命游戏已准备就绪");
-    println!();
+    println!("{}", tr(Msg::TttReady));
     
     game_system.show_menu()?;
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_takes_winning_move() {
+        let mut board = TicTacToeBoard::new();
+        // X: (0,0) (0,1)，O: (1,0) (1,1)，轮到X
+        for &(row, col) in &[(0, 0), (1, 0), (0, 1), (1, 1)] {
+            board.make_move(row, col).unwrap();
+        }
+
+        let mut engine = MinimaxEngine::new(SearchBudget::millis(HARD_TIME_BUDGET_MS));
+        let result = engine.search(&board);
+        assert_eq!(result.best_move, Some((0, 2)));
+        assert!(result.depth_reached >= 1);
+    }
+
+    #[test]
+    fn test_analysis_reports_every_open_cell() {
+        let mut engine = MinimaxEngine::new(SearchBudget::depth(9));
+        engine.set_collect_move_scores(true);
+        let mut board = TicTacToeBoard::new();
+        board.make_move(1, 1).unwrap();
+
+        let result = engine.search(&board);
+        assert_eq!(result.candidates.len(), 8);
+        assert_eq!(Some(result.candidates[0].mv), result.best_move);
+        // 完美对弈下必然平局，最佳走法应为0分
+        assert_eq!(result.candidates[0].score, 0);
+    }
+
+    #[test]
+    fn test_engine_solves_empty_board() {
+        let mut engine = MinimaxEngine::new(SearchBudget::depth(9));
+        let result = engine.search(&TicTacToeBoard::new());
+
+        assert!(result.best_move.is_some());
+        assert!(result.nodes > 0);
+        assert!(!result.timed_out);
+    }
+
+    #[test]
+    fn test_finished_board_has_no_ai_move() {
+        let mut board = TicTacToeBoard::new();
+        // X连成第一行
+        for &(row, col) in &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)] {
+            board.make_move(row, col).unwrap();
+        }
+        assert_eq!(board.game_state, GameState::Win(Player::X));
+
+        let mut ai = AI::new(Difficulty::Hard).unwrap();
+        assert!(ai.minimax_move(&board).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+//! 锦标赛AI配置
+//!
+//! 每个参赛者由一个配置描述：随机走子，或使用指定预算的minimax搜索
+
+use crate::games::ai::{GameTree, MinimaxEngine, SearchBudget};
+use crate::i18n::{trf, Msg};
+
+/// 可复现的xorshift64伪随机数生成器
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// 使用给定种子创建，种子为0时替换为固定常数
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed },
+        }
+    }
+
+    /// 下一个随机数
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// 生成 `[0, bound)` 范围内的随机下标
+    pub fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            0
+        } else {
+            (self.next_u64() % bound as u64) as usize
+        }
+    }
+}
+
+/// AI策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Agent {
+    /// 随机选择合法走法
+    Random,
+    /// 按预算进行minimax搜索
+    Minimax(SearchBudget),
+}
+
+/// 参赛者配置
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentConfig {
+    pub name: String,
+    pub agent: Agent,
+}
+
+impl AgentConfig {
+    /// 从规格字符串解析：`random`、`depth:N`、`time:MS`
+    ///
+    /// `time:MS` 的实际搜索深度取决于机器速度和负载，即使种子相同结果也不可复现。
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let agent = match spec.split_once(':') {
+            None if spec == "random" => Agent::Random,
+            Some(("depth", n)) => {
+                let depth = n.parse().map_err(|_| trf(Msg::TournamentInvalidDepth, &[&n]))?;
+                Agent::Minimax(SearchBudget::depth(depth))
+            }
+            Some(("time", ms)) => {
+                let ms = ms.parse().map_err(|_| trf(Msg::TournamentInvalidTime, &[&ms]))?;
+                Agent::Minimax(SearchBudget::millis(ms))
+            }
+            _ => return Err(trf(Msg::TournamentUnknownAgent, &[&spec])),
+        };
+
+        Ok(Self {
+            name: spec.to_string(),
+            agent,
+        })
+    }
+
+    /// 解析逗号分隔的多个配置
+    pub fn parse_list(specs: &str) -> Result<Vec<Self>, String> {
+        specs.split(',').filter(|s| !s.trim().is_empty()).map(Self::parse).collect()
+    }
+
+    /// 默认参赛阵容，只用深度预算，同一种子的结果可复现
+    pub fn defaults() -> Vec<Self> {
+        ["random", "depth:2", "depth:4", "depth:6"]
+            .iter()
+            .map(|spec| Self::parse(spec).unwrap())
+            .collect()
+    }
+
+    /// 为棋类局面选择走法
+    pub fn choose_move<G: GameTree>(&self, board: &G, rng: &mut XorShift) -> Option<G::Move> {
+        match self.agent {
+            Agent::Random => {
+                let moves = board.legal_moves();
+                moves.get(rng.below(moves.len())).copied()
+            }
+            Agent::Minimax(budget) => MinimaxEngine::new(budget).search(board).best_move,
+        }
+    }
+
+    /// 俄罗斯方块的前瞻块数：深度1只看当前块，否则额外考虑下一块
+    pub fn tetris_lookahead(&self) -> Option<usize> {
+        match self.agent {
+            Agent::Random => None,
+            Agent::Minimax(budget) if budget.max_depth <= 1 => Some(1),
+            Agent::Minimax(_) => Some(2),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! 多游戏锦标赛模块
+//! 
+//! 让多组AI配置在井字棋、四子棋和俄罗斯方块（得分挑战）中循环对战，
+//! 对局并行调度，结果汇总为积分榜
+
+pub mod agent;
+pub mod tetris_ai;
+pub mod runner;
+
+// Re-export main types
+pub use agent::{Agent, AgentConfig, XorShift};
+pub use tetris_ai::{ScoreAttackResult, TetrisPlacer};
+pub use runner::{
+    GameKind, MatchOutcome, ScoreAttackStanding, Standing, Tournament, TournamentConfig, TournamentReport,
+};
@@ -0,0 +1,532 @@
+//! 锦标赛调度与统计
+//!
+//! 棋类游戏按循环赛安排对局，每对参赛者轮流先手；俄罗斯方块为单人得分挑战。
+//! 所有对局放入共享队列，由工作线程并行执行，结果按对局编号汇总以保证可复现。
+
+use std::collections::VecDeque;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::games::ai::GameTree;
+use crate::games::connect_four::{ConnectFourBoard, Disc};
+use crate::games::tic_tac_toe::{GameState as TicTacToeState, Player, TicTacToeBoard};
+use crate::i18n::{tr, trf, Msg};
+use crate::util::alloc::{self, Subsystem};
+use crate::util::{Json, ToJson};
+
+use super::agent::{AgentConfig, XorShift};
+use super::tetris_ai::{ScoreAttackResult, TetrisPlacer};
+
+/// 锦标赛项目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameKind {
+    TicTacToe,
+    ConnectFour,
+    Tetris,
+}
+
+impl GameKind {
+    /// 所有项目
+    pub const ALL: [GameKind; 3] = [GameKind::TicTacToe, GameKind::ConnectFour, GameKind::Tetris];
+
+    /// 从命令行名称解析
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "ttt" | "tic-tac-toe" => Ok(GameKind::TicTacToe),
+            "c4" | "connect-four" => Ok(GameKind::ConnectFour),
+            "tetris" => Ok(GameKind::Tetris),
+            other => Err(trf(Msg::TournamentUnknownGame, &[&other])),
+        }
+    }
+
+    /// 命令行名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameKind::TicTacToe => "tic-tac-toe",
+            GameKind::ConnectFour => "connect-four",
+            GameKind::Tetris => "tetris",
+        }
+    }
+}
+
+/// 棋类对局结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchOutcome {
+    FirstWins,
+    SecondWins,
+    Draw,
+}
+
+/// 锦标赛配置
+#[derive(Debug, Clone)]
+pub struct TournamentConfig {
+    pub games: Vec<GameKind>,
+    pub agents: Vec<AgentConfig>,
+    /// 每对参赛者在每个先后手顺序下的对局数
+    pub rounds: u32,
+    /// 俄罗斯方块每个参赛者的挑战次数
+    pub tetris_runs: u32,
+    /// 俄罗斯方块每局的最大方块数
+    pub tetris_pieces: u32,
+    pub threads: usize,
+    pub seed: u64,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            games: GameKind::ALL.to_vec(),
+            agents: AgentConfig::defaults(),
+            rounds: 1,
+            tetris_runs: 2,
+            tetris_pieces: 200,
+            threads: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            seed: 1,
+        }
+    }
+}
+
+/// 棋类项目积分榜条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub agent: String,
+    pub played: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Standing {
+    /// 积分：胜3分，平1分
+    pub fn points(&self) -> u32 {
+        self.wins * 3 + self.draws
+    }
+}
+
+/// 得分挑战排行条目
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreAttackStanding {
+    pub agent: String,
+    pub runs: u32,
+    pub best_score: u32,
+    pub average_score: f64,
+    pub average_lines: f64,
+}
+
+/// 锦标赛结果
+#[derive(Debug, Clone)]
+pub struct TournamentReport {
+    /// 各棋类项目的积分榜，按积分降序
+    pub standings: Vec<(GameKind, Vec<Standing>)>,
+    /// 俄罗斯方块排行，按平均分降序
+    pub score_attack: Vec<ScoreAttackStanding>,
+    pub matches_played: usize,
+    pub elapsed: Duration,
+}
+
+/// 单个待执行对局
+#[derive(Debug, Clone, Copy)]
+enum Job {
+    Match { game: GameKind, first: usize, second: usize, seed: u64 },
+    ScoreAttack { agent: usize, seed: u64 },
+}
+
+/// 对局结果
+#[derive(Debug, Clone, Copy)]
+enum JobResult {
+    Match(MatchOutcome),
+    ScoreAttack(ScoreAttackResult),
+}
+
+/// 锦标赛
+pub struct Tournament {
+    config: TournamentConfig,
+}
+
+impl Tournament {
+    /// 创建锦标赛，至少需要一个参赛者
+    pub fn new(config: TournamentConfig) -> Result<Self, String> {
+        if config.agents.is_empty() {
+            return Err(tr(Msg::TournamentNoAgents).to_string());
+        }
+        if config.games.is_empty() {
+            return Err(tr(Msg::TournamentNoGames).to_string());
+        }
+        Ok(Self { config })
+    }
+
+    /// 待执行的对局总数
+    pub fn job_count(&self) -> usize {
+        self.schedule().len()
+    }
+
+    /// 执行所有对局，每完成一局调用一次 `on_progress(已完成, 总数)`
+    pub fn run(&self, mut on_progress: impl FnMut(usize, usize)) -> TournamentReport {
+        let start = Instant::now();
+        let jobs = self.schedule();
+        let total = jobs.len();
+        let queue = Mutex::new(jobs.iter().copied().enumerate().collect::<VecDeque<_>>());
+        let (sender, receiver) = mpsc::channel();
+        let mut results = vec![None; total];
+
+        thread::scope(|scope| {
+            for _ in 0..self.config.threads.max(1).min(total.max(1)) {
+                let sender = sender.clone();
+                let queue = &queue;
+                scope.spawn(move || loop {
+                    let next = queue.lock().unwrap().pop_front();
+                    let Some((index, job)) = next else { break };
+                    let result = {
+                        let _scope = alloc::enter(Subsystem::Games);
+                        self.execute(job)
+                    };
+                    if sender.send((index, result)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+
+            for (done, (index, result)) in receiver.iter().enumerate() {
+                results[index] = Some(result);
+                on_progress(done + 1, total);
+            }
+        });
+
+        let results: Vec<(Job, JobResult)> = jobs
+            .into_iter()
+            .zip(results)
+            .filter_map(|(job, result)| result.map(|r| (job, r)))
+            .collect();
+
+        TournamentReport {
+            standings: self.tally_standings(&results),
+            score_attack: self.tally_score_attack(&results),
+            matches_played: results.len(),
+            elapsed: start.elapsed(),
+        }
+    }
+
+    /// 生成对局列表
+    fn schedule(&self) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        let agents = self.config.agents.len();
+        let mut seed = self.config.seed;
+        let mut next_seed = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            seed
+        };
+
+        for &game in &self.config.games {
+            if game == GameKind::Tetris {
+                for agent in 0..agents {
+                    for _ in 0..self.config.tetris_runs {
+                        jobs.push(Job::ScoreAttack { agent, seed: next_seed() });
+                    }
+                }
+                continue;
+            }
+
+            for a in 0..agents {
+                for b in (a + 1)..agents {
+                    for _ in 0..self.config.rounds {
+                        jobs.push(Job::Match { game, first: a, second: b, seed: next_seed() });
+                        jobs.push(Job::Match { game, first: b, second: a, seed: next_seed() });
+                    }
+                }
+            }
+        }
+
+        jobs
+    }
+
+    /// 执行单个对局
+    fn execute(&self, job: Job) -> JobResult {
+        let agents = &self.config.agents;
+        match job {
+            Job::Match { game: GameKind::TicTacToe, first, second, seed } => {
+                let board = play_out(TicTacToeBoard::new(), &agents[first], &agents[second], seed);
+                JobResult::Match(match board.game_state {
+                    TicTacToeState::Win(Player::X) => MatchOutcome::FirstWins,
+                    TicTacToeState::Win(_) => MatchOutcome::SecondWins,
+                    _ => MatchOutcome::Draw,
+                })
+            }
+            // 俄罗斯方块只以得分挑战调度，其余对局均为四子棋
+            Job::Match { game: _, first, second, seed } => {
+                let board = play_out(ConnectFourBoard::new(), &agents[first], &agents[second], seed);
+                JobResult::Match(match board.winner {
+                    Some(Disc::Red) => MatchOutcome::FirstWins,
+                    Some(_) => MatchOutcome::SecondWins,
+                    None => MatchOutcome::Draw,
+                })
+            }
+            Job::ScoreAttack { agent, seed } => {
+                let mut placer = TetrisPlacer::new(agents[agent].tetris_lookahead(), seed);
+                JobResult::ScoreAttack(placer.play(self.config.tetris_pieces))
+            }
+        }
+    }
+
+    /// 汇总棋类项目积分榜
+    fn tally_standings(&self, results: &[(Job, JobResult)]) -> Vec<(GameKind, Vec<Standing>)> {
+        self.config
+            .games
+            .iter()
+            .filter(|&&game| game != GameKind::Tetris)
+            .map(|&game| {
+                let mut table: Vec<Standing> = self
+                    .config
+                    .agents
+                    .iter()
+                    .map(|agent| Standing {
+                        agent: agent.name.clone(),
+                        played: 0,
+                        wins: 0,
+                        draws: 0,
+                        losses: 0,
+                    })
+                    .collect();
+
+                for (job, result) in results {
+                    let (Job::Match { game: g, first, second, .. }, JobResult::Match(outcome)) = (job, result) else {
+                        continue;
+                    };
+                    if *g != game {
+                        continue;
+                    }
+
+                    table[*first].played += 1;
+                    table[*second].played += 1;
+                    match outcome {
+                        MatchOutcome::FirstWins => {
+                            table[*first].wins += 1;
+                            table[*second].losses += 1;
+                        }
+                        MatchOutcome::SecondWins => {
+                            table[*second].wins += 1;
+                            table[*first].losses += 1;
+                        }
+                        MatchOutcome::Draw => {
+                            table[*first].draws += 1;
+                            table[*second].draws += 1;
+                        }
+                    }
+                }
+
+                table.sort_by(|a, b| b.points().cmp(&a.points()).then(b.wins.cmp(&a.wins)));
+                (game, table)
+            })
+            .collect()
+    }
+
+    /// 汇总俄罗斯方块排行
+    fn tally_score_attack(&self, results: &[(Job, JobResult)]) -> Vec<ScoreAttackStanding> {
+        let mut table: Vec<ScoreAttackStanding> = self
+            .config
+            .agents
+            .iter()
+            .enumerate()
+            .filter_map(|(index, agent)| {
+                let runs: Vec<&ScoreAttackResult> = results
+                    .iter()
+                    .filter_map(|(job, result)| match (job, result) {
+                        (Job::ScoreAttack { agent: a, .. }, JobResult::ScoreAttack(r)) if *a == index => Some(r),
+                        _ => None,
+                    })
+                    .collect();
+
+                if runs.is_empty() {
+                    return None;
+                }
+
+                let count = runs.len() as f64;
+                Some(ScoreAttackStanding {
+                    agent: agent.name.clone(),
+                    runs: runs.len() as u32,
+                    best_score: runs.iter().map(|r| r.score).max().unwrap_or(0),
+                    average_score: runs.iter().map(|r| r.score as f64).sum::<f64>() / count,
+                    average_lines: runs.iter().map(|r| r.lines_cleared as f64).sum::<f64>() / count,
+                })
+            })
+            .collect();
+
+        table.sort_by(|a, b| b.average_score.total_cmp(&a.average_score));
+        table
+    }
+}
+
+/// 双方轮流走子直到对局结束
+fn play_out<G: GameTree>(mut board: G, first: &AgentConfig, second: &AgentConfig, seed: u64) -> G {
+    let mut rng = XorShift::new(seed);
+    let mut turn = 0;
+
+    while !board.is_terminal() {
+        let agent = if turn % 2 == 0 { first } else { second };
+        match agent.choose_move(&board, &mut rng) {
+            Some(mv) => board.apply_move(mv),
+            None => break,
+        }
+        turn += 1;
+    }
+
+    board
+}
+
+impl TournamentReport {
+    /// 生成Markdown表格
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+
+        for (game, table) in &self.standings {
+            out.push_str(&format!("## {}\n\n", game.name()));
+            out.push_str(&format!("{}\n", tr(Msg::TournamentStandingsHeader)));
+            out.push_str("|---:|---|---:|---:|---:|---:|---:|\n");
+            for (rank, s) in table.iter().enumerate() {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} | {} |\n",
+                    rank + 1, s.agent, s.played, s.wins, s.draws, s.losses, s.points()
+                ));
+            }
+            out.push('\n');
+        }
+
+        if !self.score_attack.is_empty() {
+            out.push_str(&format!("{}\n\n", tr(Msg::TournamentScoreAttackTitle)));
+            out.push_str(&format!("{}\n", tr(Msg::TournamentScoreAttackHeader)));
+            out.push_str("|---:|---|---:|---:|---:|---:|\n");
+            for (rank, s) in self.score_attack.iter().enumerate() {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} | {:.1} | {:.1} |\n",
+                    rank + 1, s.agent, s.runs, s.best_score, s.average_score, s.average_lines
+                ));
+            }
+            out.push('\n');
+        }
+
+        out.push_str(&trf(
+            Msg::TournamentSummary,
+            &[&self.matches_played, &format!("{:.2}", self.elapsed.as_secs_f64())],
+        ));
+        out.push('\n');
+        out
+    }
+
+    /// 生成CSV，棋类与得分挑战共用一张表
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("game,agent,played,wins,draws,losses,points,best_score,average_score,average_lines\n");
+
+        for (game, table) in &self.standings {
+            for s in table {
+                out.push_str(&format!(
+                    "{},{},{},{},{},{},{},,,\n",
+                    game.name(), csv_field(&s.agent), s.played, s.wins, s.draws, s.losses, s.points()
+                ));
+            }
+        }
+
+        for s in &self.score_attack {
+            out.push_str(&format!(
+                "tetris,{},{},,,,,{},{:.1},{:.1}\n",
+                csv_field(&s.agent), s.runs, s.best_score, s.average_score, s.average_lines
+            ));
+        }
+
+        out
+    }
+}
+
+impl ToJson for Standing {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("agent", Json::from(self.agent.as_str())),
+            ("played", Json::from(self.played)),
+            ("wins", Json::from(self.wins)),
+            ("draws", Json::from(self.draws)),
+            ("losses", Json::from(self.losses)),
+            ("points", Json::from(self.points())),
+        ])
+    }
+}
+
+impl ToJson for ScoreAttackStanding {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("agent", Json::from(self.agent.as_str())),
+            ("runs", Json::from(self.runs)),
+            ("best_score", Json::from(self.best_score)),
+            ("average_score", Json::from(self.average_score)),
+            ("average_lines", Json::from(self.average_lines)),
+        ])
+    }
+}
+
+impl ToJson for TournamentReport {
+    fn to_json(&self) -> Json {
+        let standings = self
+            .standings
+            .iter()
+            .map(|(game, table)| (game.name(), Json::array(table)))
+            .collect();
+
+        Json::object(vec![
+            ("standings", Json::object(standings)),
+            ("score_attack", Json::array(&self.score_attack)),
+            ("matches_played", Json::from(self.matches_played)),
+            ("elapsed_secs", Json::from(self.elapsed.as_secs_f64())),
+        ])
+    }
+}
+
+/// 必要时为CSV字段加引号
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::tournament::agent::Agent;
+
+    #[test]
+    fn test_round_robin_is_complete_and_deterministic() {
+        let config = TournamentConfig {
+            games: vec![GameKind::TicTacToe, GameKind::Tetris],
+            agents: AgentConfig::parse_list("random,depth:9").unwrap(),
+            rounds: 2,
+            tetris_runs: 1,
+            tetris_pieces: 30,
+            threads: 4,
+            seed: 7,
+        };
+        let tournament = Tournament::new(config).unwrap();
+        assert_eq!(tournament.job_count(), 4 + 2);
+
+        let first = tournament.run(|_, _| {});
+        let second = tournament.run(|_, _| {});
+        let (_, table) = &first.standings[0];
+
+        // 完美对弈的AI不会输给随机走子
+        assert_eq!(table[0].agent, "depth:9");
+        assert_eq!(table[0].losses, 0);
+        assert_eq!(table.iter().map(|s| s.played).sum::<u32>(), 8);
+        assert_eq!(first.standings, second.standings);
+        assert_eq!(first.score_attack, second.score_attack);
+        assert!(first.to_csv().lines().count() == 1 + 2 + 2);
+    }
+
+    #[test]
+    fn test_default_agents_are_reproducible() {
+        // 限时搜索的深度随机器负载变化，默认阵容不能包含
+        for config in AgentConfig::defaults() {
+            if let Agent::Minimax(budget) = config.agent {
+                assert_eq!(budget.time_limit, None, "{}", config.name);
+            }
+        }
+    }
+}
@@ -5,6 +5,9 @@
 
 use std::collections::HashMap;
 
+use crate::gba::io::IO_BASE;
+use crate::gba::io_registers::IoRegisters;
+
 /// ARM7TDMI CPU状态
 #[derive(Debug, Clone)]
 pub struct ARM7TDMI {
@@ -55,6 +58,8 @@ pub struct CPUStats {
     pub cache_misses: u64,
     pub branch_taken: u64,
     pub branch_not_taken: u64,
+    /// 跳转造成的流水线清空次数
+    pub pipeline_flushes: u64,
 }
 
 /// ARM指令类型
@@ -206,6 +211,119 @@ impl ARM7TDMI {
         self.set_flag(CPSRFlag::Thumb, false);
     }
     
+    /// 当前指令集的指令长度
+    pub fn instruction_size(&self) -> u32 {
+        if self.thumb_mode { 2 } else { 4 }
+    }
+    
+    /// 流水线造成的PC偏移：执行一条指令时已经取到后两条，读R15得到当前地址加8 (ARM) 或加4 (Thumb)
+    pub fn pipeline_offset(&self) -> u32 {
+        self.instruction_size() * 2
+    }
+    
+    /// 读取作为操作数的寄存器，R15包含流水线偏移
+    fn read_operand(&self, reg: usize) -> u32 {
+        if reg == 15 {
+            self.pc.wrapping_add(self.pipeline_offset())
+        } else {
+            self.get_register(reg)
+        }
+    }
+    
+    /// 写入结果寄存器，写R15即跳转
+    fn write_result(&mut self, reg: usize, value: u32) {
+        if reg == 15 {
+            self.branch_to(value);
+        } else {
+            self.set_register(reg, value);
+        }
+    }
+    
+    /// 跳转到 `target`，保持当前指令集，按指令长度对齐
+    fn branch_to(&mut self, target: u32) {
+        self.pc = target & !(self.instruction_size() - 1);
+        self.flush_pipeline();
+    }
+    
+    /// BX：目标地址第0位决定切换到Thumb (1) 还是ARM (0)
+    fn branch_exchange(&mut self, target: u32) {
+        if target & 1 != 0 {
+            self.enter_thumb_mode();
+        } else {
+            self.enter_arm_mode();
+        }
+        self.branch_to(target);
+    }
+    
+    /// 清空流水线：丢弃已预取的指令，重新填充需要额外两个周期
+    pub fn flush_pipeline(&mut self) {
+        self.instruction_cache.clear();
+        self.stats.pipeline_flushes += 1;
+        self.stats.branch_taken += 1;
+        self.stats.cycles += 2;
+    }
+    
+    /// 取指
+    ///
+    /// `instruction_cache` 充当预取缓冲，只保存当前指令之后的两条。执行期间对它们的
+    /// 写入不会生效，直到跳转清空流水线后重新取指，这与硬件行为一致。
+    fn fetch(&mut self, memory: &mut GBAMemory) -> Result<u32, String> {
+        let opcode = match self.instruction_cache.get(&self.pc) {
+            Some(&opcode) => {
+                self.stats.cache_hits += 1;
+                opcode
+            }
+            None => {
+                self.stats.cache_misses += 1;
+                self.read_opcode(memory, self.pc)?
+            }
+        };
+        let size = self.instruction_size();
+        let ahead = [self.pc.wrapping_add(size), self.pc.wrapping_add(size * 2)];
+        self.instruction_cache.retain(|address, _| ahead.contains(address));
+        for address in ahead {
+            if !self.instruction_cache.contains_key(&address) {
+                let prefetched = self.read_opcode(memory, address)?;
+                self.instruction_cache.insert(address, prefetched);
+            }
+        }
+        Ok(opcode)
+    }
+    
+    fn read_opcode(&self, memory: &mut GBAMemory, address: u32) -> Result<u32, String> {
+        if self.thumb_mode {
+            memory.read_16(address).map(u32::from)
+        } else {
+            memory.read_32(address)
+        }
+    }
+    
+    /// 按CPSR判断条件码是否成立
+    fn condition_passed(&self, condition: u32) -> bool {
+        let n = self.get_flag(CPSRFlag::Negative);
+        let z = self.get_flag(CPSRFlag::Zero);
+        let c = self.get_flag(CPSRFlag::Carry);
+        let v = self.get_flag(CPSRFlag::Overflow);
+        match condition {
+            0x0 => z,
+            0x1 => !z,
+            0x2 => c,
+            0x3 => !c,
+            0x4 => n,
+            0x5 => !n,
+            0x6 => v,
+            0x7 => !v,
+            0x8 => c && !z,
+            0x9 => !c || z,
+            0xA => n == v,
+            0xB => n != v,
+            0xC => !z && n == v,
+            0xD => z || n != v,
+            0xE => true,
+            _ => false,
+        }
+    }
+    
     /// 执行一条指令
     pub fn execute_instruction(&mut self, memory: &mut GBAMemory) -> Result<(), String> {
         if self.thumb_mode {
@@ -218,76 +336,92 @@ impl ARM7TDMI {
     /// 执行ARM指令
     fn execute_arm_instruction(&mut self, memory: &mut GBAMemory) -> Result<(), String> {
         // 获取指令
-        let instruction = memory.read_32(self.pc)?;
-        
-        // 解码指令
-        let decoded = self.decode_arm_instruction(instruction);
+        let instruction = self.fetch(memory)?;
+        let pc = self.pc;
+        let flushes = self.stats.pipeline_flushes;
         
-        // 执行指令
-        match decoded {
-            ARMInstruction::MOV => {
-                // MOV指令实现
-                let rd = ((instruction >> 12) & 0xF) as usize;
-                let operand2 = self.get_operand2(instruction);
-                self.set_register(rd, operand2);
-                self.pc += 4;
-            }
-            ARMInstruction::ADD => {
-                // ADD指令实现
-                let rd = ((instruction >> 12) & 0xF) as usize;
-                let rn = ((instruction >> 16) & 0xF) as usize;
-                let operand2 = self.get_operand2(instruction);
-                let result = self.get_register(rn).wrapping_add(operand2);
-                self.set_register(rd, result);
-                self.update_flags_add(self.get_register(rn), operand2, result);
-                self.pc += 4;
-            }
-            ARMInstruction::SUB => {
-                // SUB指令实现
-                let rd = ((instruction >> 12) & 0xF) as usize;
-                let rn = ((instruction >> 16) & 0xF) as usize;
-                let operand2 = self.get_operand2(instruction);
-                let result = self.get_register(rn).wrapping_sub(operand2);
-                self.set_register(rd, result);
-                self.update_flags_sub(self.get_register(rn), operand2, result);
-                self.pc += 4;
-            }
-            ARMInstruction::B => {
-                // 分支指令实现
-                let offset = (instruction & 0xFFFFFF) as i32;
-                let offset = if (offset & 0x800000) != 0 {
-                    offset | 0xFF000000u32 as i32
-                } else {
-                    offset
-                };
-                self.pc = self.pc.wrapping_add((offset << 2) as u32);
-            }
-            ARMInstruction::LDR => {
-                // 加载指令实现
-                let rt = ((instruction >> 12) & 0xF) as usize;
-                let rn = ((instruction >> 16) & 0xF) as usize;
-                let offset = instruction & 0xFFF;
-                let address = self.get_register(rn).wrapping_add(offset);
-                let value = memory.read_32(address)?;
-                self.set_register(rt, value);
-                self.pc += 4;
-            }
-            ARMInstruction::STR => {
-                // 存储指令实现
-                let rt = ((instruction >> 12) & 0xF) as usize;
-                let rn = ((instruction >> 16) & 0xF) as usize;
-                let offset = instruction & 0xFFF;
-                let address = self.get_register(rn).wrapping_add(offset);
-                let value = self.get_register(rt);
-                memory.write_32(address, value)?;
-                self.pc += 4;
-            }
-            _ => {
-                // 未实现指令
-                self.pc += 4;
+        if self.condition_passed(instruction >> 28) {
+            // 解码并执行指令
+            match self.decode_arm_instruction(instruction) {
+                ARMInstruction::MOV => {
+                    // MOV指令实现
+                    let rd = ((instruction >> 12) & 0xF) as usize;
+                    let operand2 = self.get_operand2(instruction);
+                    self.write_result(rd, operand2);
+                }
+                ARMInstruction::ADD => {
+                    // ADD指令实现
+                    let rd = ((instruction >> 12) & 0xF) as usize;
+                    let rn = ((instruction >> 16) & 0xF) as usize;
+                    let a = self.read_operand(rn);
+                    let operand2 = self.get_operand2(instruction);
+                    let result = a.wrapping_add(operand2);
+                    if instruction & (1 << 20) != 0 {
+                        self.update_flags_add(a, operand2, result);
+                    }
+                    self.write_result(rd, result);
+                }
+                ARMInstruction::SUB => {
+                    // SUB指令实现
+                    let rd = ((instruction >> 12) & 0xF) as usize;
+                    let rn = ((instruction >> 16) & 0xF) as usize;
+                    let a = self.read_operand(rn);
+                    let operand2 = self.get_operand2(instruction);
+                    let result = a.wrapping_sub(operand2);
+                    if instruction & (1 << 20) != 0 {
+                        self.update_flags_sub(a, operand2, result);
+                    }
+                    self.write_result(rd, result);
+                }
+                ARMInstruction::CMP => {
+                    let rn = ((instruction >> 16) & 0xF) as usize;
+                    let a = self.read_operand(rn);
+                    let operand2 = self.get_operand2(instruction);
+                    self.update_flags_sub(a, operand2, a.wrapping_sub(operand2));
+                }
+                ARMInstruction::B | ARMInstruction::BL => {
+                    // 24位有符号字偏移，相对于PC+8
+                    let offset = (((instruction & 0x00FF_FFFF) << 8) as i32) >> 6;
+                    if instruction & (1 << 24) != 0 {
+                        self.set_register(14, pc.wrapping_add(4));
+                    }
+                    self.branch_to(self.read_operand(15).wrapping_add(offset as u32));
+                }
+                ARMInstruction::BX => {
+                    let rm = (instruction & 0xF) as usize;
+                    self.branch_exchange(self.read_operand(rm));
+                }
+                decoded @ (ARMInstruction::LDR | ARMInstruction::LDRB | ARMInstruction::STR | ARMInstruction::STRB) => {
+                    // 立即数偏移的单数据传输，U位决定加减，P位为0时后变址
+                    let rt = ((instruction >> 12) & 0xF) as usize;
+                    let rn = ((instruction >> 16) & 0xF) as usize;
+                    let base = self.read_operand(rn);
+                    let offset = instruction & 0xFFF;
+                    let indexed = if instruction & (1 << 23) != 0 { base.wrapping_add(offset) } else { base.wrapping_sub(offset) };
+                    let address = if instruction & (1 << 24) != 0 { indexed } else { base };
+                    match decoded {
+                        ARMInstruction::LDR => {
+                            let value = memory.read_32(address & !3)?.rotate_right((address & 3) * 8);
+                            self.write_result(rt, value);
+                        }
+                        ARMInstruction::LDRB => {
+                            let value = memory.read_8(address)? as u32;
+                            self.write_result(rt, value);
+                        }
+                        // 存储R15时写入的是PC+12
+                        ARMInstruction::STR => memory.write_32(address & !3, self.read_operand(rt).wrapping_add(if rt == 15 { 4 } else { 0 }))?,
+                        _ => memory.write_8(address, self.read_operand(rt) as u8)?,
+                    }
+                }
+                _ => {
+                    // 未实现指令
+                }
             }
         }
         
+        if self.stats.pipeline_flushes == flushes {
+            self.pc += 4;
+        }
         self.stats.cycles += 1;
         self.stats.instructions += 1;
         self.stats.arm_instructions += 1;
@@ -298,46 +432,97 @@ impl ARM7TDMI {
     /// 执行Thumb指令
     fn execute_thumb_instruction(&mut self, memory: &mut GBAMemory) -> Result<(), String> {
         // 获取指令
-        let instruction = memory.read_16(self.pc)?;
-        
-        // 解码指令
-        let decoded = self.decode_thumb_instruction(instruction);
+        let instruction = self.fetch(memory)? as u16;
+        let pc = self.pc;
+        let flushes = self.stats.pipeline_flushes;
         
-        // 执行指令
-        match decoded {
-            ThumbInstruction::MOV => {
-                // Thumb MOV指令实现
+        // 解码并执行指令
+        match self.decode_thumb_instruction(instruction) {
+            decoded @ (ThumbInstruction::MOV | ThumbInstruction::CMP | ThumbInstruction::ADD | ThumbInstruction::SUB)
+                if instruction & 0xE000 == 0x2000 =>
+            {
+                // 格式3：8位立即数
                 let rd = ((instruction >> 8) & 0x7) as usize;
-                let rs = ((instruction >> 3) & 0x7) as usize;
-                let value = self.get_register(rs);
-                self.set_register(rd, value);
-                self.pc += 2;
+                let immediate = (instruction & 0xFF) as u32;
+                let value = self.get_register(rd);
+                match decoded {
+                    ThumbInstruction::MOV => {
+                        self.set_register(rd, immediate);
+                        self.set_flag(CPSRFlag::Zero, immediate == 0);
+                        self.set_flag(CPSRFlag::Negative, false);
+                    }
+                    ThumbInstruction::CMP => self.update_flags_sub(value, immediate, value.wrapping_sub(immediate)),
+                    ThumbInstruction::ADD => {
+                        let result = value.wrapping_add(immediate);
+                        self.update_flags_add(value, immediate, result);
+                        self.set_register(rd, result);
+                    }
+                    _ => {
+                        let result = value.wrapping_sub(immediate);
+                        self.update_flags_sub(value, immediate, result);
+                        self.set_register(rd, result);
+                    }
+                }
             }
-            ThumbInstruction::ADD => {
-                // Thumb ADD指令实现
+            decoded @ (ThumbInstruction::ADD | ThumbInstruction::CMP | ThumbInstruction::MOV) => {
+                // 格式5：高寄存器操作，H1/H2选择R8-R15，不影响标志（CMP除外）
+                let rd = ((instruction & 0x7) | ((instruction >> 4) & 0x8)) as usize;
+                let rs = ((instruction >> 3) & 0xF) as usize;
+                let value = self.read_operand(rs);
+                match decoded {
+                    ThumbInstruction::ADD => self.write_result(rd, self.read_operand(rd).wrapping_add(value)),
+                    ThumbInstruction::CMP => {
+                        let a = self.read_operand(rd);
+                        self.update_flags_sub(a, value, a.wrapping_sub(value));
+                    }
+                    _ => self.write_result(rd, value),
+                }
+            }
+            ThumbInstruction::BX => {
+                let rs = ((instruction >> 3) & 0xF) as usize;
+                self.branch_exchange(self.read_operand(rs));
+            }
+            ThumbInstruction::LDR => {
+                // 格式6：PC相对加载，基址为 (PC+4) 按字对齐
                 let rd = ((instruction >> 8) & 0x7) as usize;
-                let rs = ((instruction >> 3) & 0x7) as usize;
-                let value = self.get_register(rs);
-                let result = self.get_register(rd).wrapping_add(value);
-                self.set_register(rd, result);
-                self.pc += 2;
+                let address = (self.read_operand(15) & !3).wrapping_add(((instruction & 0xFF) as u32) << 2);
+                let value = memory.read_32(address)?;
+                self.set_register(rd, value);
             }
             ThumbInstruction::B => {
-                // Thumb分支指令实现
-                let offset = (instruction & 0xFF) as i32;
-                let offset = if (offset & 0x80) != 0 {
-                    offset | 0xFFFFFF00u32 as i32
+                let target = if instruction & 0xF000 == 0xD000 {
+                    // 格式16：条件分支，8位有符号半字偏移
+                    let offset = ((instruction as u8 as i8) as i32) << 1;
+                    self.condition_passed(((instruction >> 8) & 0xF) as u32).then_some(offset)
                 } else {
-                    offset
+                    // 格式18：无条件分支，11位有符号半字偏移
+                    Some((((instruction & 0x07FF) << 5) as i16 as i32) >> 4)
                 };
-                self.pc = self.pc.wrapping_add((offset << 1) as u32);
+                match target {
+                    Some(offset) => self.branch_to(self.read_operand(15).wrapping_add(offset as u32)),
+                    None => self.stats.branch_not_taken += 1,
+                }
+            }
+            ThumbInstruction::BL => {
+                // 格式19：两条指令组成的长跳转，前半条把高位偏移存入LR
+                let offset = (instruction & 0x07FF) as u32;
+                if instruction & 0x0800 == 0 {
+                    let high = (((offset << 21) as i32) >> 9) as u32;
+                    self.set_register(14, self.read_operand(15).wrapping_add(high));
+                } else {
+                    let target = self.get_register(14).wrapping_add(offset << 1);
+                    self.set_register(14, pc.wrapping_add(2) | 1);
+                    self.branch_to(target);
+                }
             }
             _ => {
                 // 未实现指令
-                self.pc += 2;
             }
         }
         
+        if self.stats.pipeline_flushes == flushes {
+            self.pc += 2;
+        }
         self.stats.cycles += 1;
         self.stats.instructions += 1;
         self.stats.thumb_instructions += 1;
@@ -347,52 +532,86 @@ impl ARM7TDMI {
     
     /// 解码ARM指令
     fn decode_arm_instruction(&self, instruction: u32) -> ARMInstruction {
-        let opcode = (instruction >> 21) & 0xF;
-        let condition = (instruction >> 28) & 0xF;
+        if instruction & 0x0FFF_FFF0 == 0x012F_FF10 {
+            return ARMInstruction::BX;
+        }
         
-        match opcode {
-            0x0 => ARMInstruction::AND,
-            0x1 => ARMInstruction::EOR,
-            0x2 => ARMInstruction::SUB,
-            0x3 => ARMInstruction::RSB,
-            0x4 => ARMInstruction::ADD,
-            0x5 => ARMInstruction::ADC,
-            0x6 => ARMInstruction::SBC,
-            0x7 => ARMInstruction::RSC,
-            0x8 => ARMInstruction::TST,
-            0x9 => ARMInstruction::TEQ,
-            0xA => ARMInstruction::CMP,
-            0xB => ARMInstruction::CMN,
-            0xC => ARMInstruction::ORR,
-            0xD => ARMInstruction::MOV,
-            0xE => ARMInstruction::BIC,
-            0xF => ARMInstruction::MVN,
+        let load = instruction & (1 << 20) != 0;
+        match (instruction >> 25) & 0x7 {
+            0b101 if instruction & (1 << 24) != 0 => ARMInstruction::BL,
+            0b101 => ARMInstruction::B,
+            0b010 | 0b011 => match (load, instruction & (1 << 22) != 0) {
+                (true, false) => ARMInstruction::LDR,
+                (true, true) => ARMInstruction::LDRB,
+                (false, false) => ARMInstruction::STR,
+                (false, true) => ARMInstruction::STRB,
+            },
+            0b100 if load => ARMInstruction::LDM,
+            0b100 => ARMInstruction::STM,
+            0b111 if instruction & (1 << 24) != 0 => ARMInstruction::SWI,
+            // 乘法和半字传输占用数据处理的编码空间
+            0b000 if instruction & 0x90 == 0x90 => match (instruction >> 5) & 0x3 {
+                0 => ARMInstruction::UNDEFINED,
+                _ if load => ARMInstruction::LDRH,
+                _ => ARMInstruction::STRH,
+            },
+            0b000 | 0b001 => match (instruction >> 21) & 0xF {
+                0x0 => ARMInstruction::AND,
+                0x1 => ARMInstruction::EOR,
+                0x2 => ARMInstruction::SUB,
+                0x3 => ARMInstruction::RSB,
+                0x4 => ARMInstruction::ADD,
+                0x5 => ARMInstruction::ADC,
+                0x6 => ARMInstruction::SBC,
+                0x7 => ARMInstruction::RSC,
+                0x8 => ARMInstruction::TST,
+                0x9 => ARMInstruction::TEQ,
+                0xA => ARMInstruction::CMP,
+                0xB => ARMInstruction::CMN,
+                0xC => ARMInstruction::ORR,
+                0xD => ARMInstruction::MOV,
+                0xE => ARMInstruction::BIC,
+                _ => ARMInstruction::MVN,
+            },
             _ => ARMInstruction::UNDEFINED,
         }
     }
     
     /// 解码Thumb指令
     fn decode_thumb_instruction(&self, instruction: u16) -> ThumbInstruction {
-        let opcode = (instruction >> 10) & 0x3F;
-        
-        match opcode {
-            0x00..=0x07 => ThumbInstruction::MOV,
-            0x08..=0x0F => ThumbInstruction::CMP,
-            0x10..=0x17 => ThumbInstruction::ADD,
-            0x18..=0x1F => ThumbInstruction::SUB,
-            0x20..=0x27 => ThumbInstruction::AND,
-            0x28..=0x2F => ThumbInstruction::EOR,
-            0x30..=0x37 => ThumbInstruction::LSL,
-            0x38..=0x3F => ThumbInstruction::LSR,
+        match instruction >> 8 {
+            0x20..=0x27 => ThumbInstruction::MOV,
+            0x28..=0x2F => ThumbInstruction::CMP,
+            0x30..=0x37 => ThumbInstruction::ADD,
+            0x38..=0x3F => ThumbInstruction::SUB,
+            0x44 => ThumbInstruction::ADD,
+            0x45 => ThumbInstruction::CMP,
+            0x46 => ThumbInstruction::MOV,
+            0x47 => ThumbInstruction::BX,
+            0x48..=0x4F => ThumbInstruction::LDR,
+            0xD0..=0xDD => ThumbInstruction::B,
+            0xE0..=0xE7 => ThumbInstruction::B,
+            0xF0..=0xFF => ThumbInstruction::BL,
             _ => ThumbInstruction::UNDEFINED,
         }
     }
     
-    /// 获取操作数2
+    /// 获取操作数2：I位为1时是循环右移的8位立即数，否则是按立即数移位的寄存器
     fn get_operand2(&self, instruction: u32) -> u32 {
-        let immediate = (instruction & 0xFF) as u32;
-        let rotate = ((instruction >> 8) & 0xF) as u32;
-        immediate.rotate_right(rotate * 2)
+        if instruction & (1 << 25) != 0 {
+            let immediate = instruction & 0xFF;
+            let rotate = (instruction >> 8) & 0xF;
+            return immediate.rotate_right(rotate * 2);
+        }
+        let value = self.read_operand((instruction & 0xF) as usize);
+        let amount = (instruction >> 7) & 0x1F;
+        match (instruction >> 5) & 0x3 {
+            0 => value << amount,
+            // LSR/ASR #0 表示移32位
+            1 => if amount == 0 { 0 } else { value >> amount },
+            2 => ((value as i32) >> if amount == 0 { 31 } else { amount }) as u32,
+            _ => value.rotate_right(amount),
+        }
     }
     
     /// 更新加法标志位
@@ -444,12 +663,24 @@ pub struct GBAMemory {
     pub vram: [u8; 0x18000],
     /// OAM RAM (1KB)
     pub oam_ram: [u8; 0x400],
+    /// I/O寄存器
+    pub io: IoRegisters,
     /// ROM数据
     pub rom: Vec<u8>,
     /// 性能统计
     pub stats: MemoryStats,
 }
 
+/// 可读写的RAM区域
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RamRegion {
+    Ewram,
+    Iwram,
+    Palette,
+    Vram,
+    Oam,
+}
+
 /// 内存性能统计
 #[derive(Debug, Clone, Default)]
 pub struct MemoryStats {
@@ -468,6 +699,7 @@ impl GBAMemory {
             palette_ram: [0; 0x400],
             vram: [0; 0x18000],
             oam_ram: [0; 0x400],
+            io: IoRegisters::new(),
             rom: Vec::new(),
             stats: MemoryStats::default(),
         }
@@ -483,61 +715,19 @@ impl GBAMemory {
         self.stats.reads += 1;
         
         match address {
-            0x00000000..=0x00FFFFFF => {
-                // ROM区域
-                let rom_addr = (address - 0x08000000) as usize;
-                if rom_addr < self.rom.len() {
-                    Ok(self.rom[rom_addr])
-                } else {
-                    Ok(0)
-                }
-            }
-            0x02000000..=0x0203FFFF => {
-                // 外部工作RAM
-                let ram_addr = (address - 0x02000000) as usize;
-                if ram_addr < self.ewram.len() {
-                    Ok(self.ewram[ram_addr])
-                } else {
-                    Ok(0)
-                }
-            }
-            0x03000000..=0x03007FFF => {
-                // 内部工作RAM
-                let ram_addr = (address - 0x03000000) as usize;
-                if ram_addr < self.iwram.len() {
-                    Ok(self.iwram[ram_addr])
-                } else {
-                    Ok(0)
-                }
-            }
-            0x05000000..=0x050003FF => {
-                // 调色板RAM
-                let pal_addr = (address - 0x05000000) as usize;
-                if pal_addr < self.palette_ram.len() {
-                    Ok(self.palette_ram[pal_addr])
-                } else {
-                    Ok(0)
-                }
+            0x08000000..=0x0DFFFFFF => {
+                // ROM区域 (三个等待状态镜像，各32MB)
+                let rom_addr = (address & 0x01FFFFFF) as usize;
+                Ok(self.rom.get(rom_addr).copied().unwrap_or(0))
             }
-            0x06000000..=0x06017FFF => {
-                // VRAM
-                let vram_addr = (address - 0x06000000) as usize;
-                if vram_addr < self.vram.len() {
-                    Ok(self.vram[vram_addr])
-                } else {
-                    Ok(0)
-                }
+            0x04000000..=0x040003FF => {
+                // I/O寄存器
+                Ok(self.io.read_8((address - IO_BASE) as u16))
             }
-            0x07000000..=0x070003FF => {
-                // OAM RAM
-                let oam_addr = (address - 0x07000000) as usize;
-                if oam_addr < self.oam_ram.len() {
-                    Ok(self.oam_ram[oam_addr])
-                } else {
-                    Ok(0)
-                }
-            }
-            _ => Ok(0),
+            _ => match Self::ram_offset(address) {
+                Some((region, offset)) => Ok(self.ram(region)[offset]),
+                None => Ok(0),
+            },
         }
     }
     
@@ -560,49 +750,59 @@ impl GBAMemory {
         self.stats.writes += 1;
         
         match address {
-            0x02000000..=0x0203FFFF => {
-                // 外部工作RAM
-                let ram_addr = (address - 0x02000000) as usize;
-                if ram_addr < self.ewram.len() {
-                    self.ewram[ram_addr] = value;
-                }
-            }
-            0x03000000..=0x03007FFF => {
-                // 内部工作RAM
-                let ram_addr = (address - 0x03000000) as usize;
-                if ram_addr < self.iwram.len() {
-                    self.iwram[ram_addr] = value;
-                }
-            }
-            0x05000000..=0x050003FF => {
-                // 调色板RAM
-                let pal_addr = (address - 0x05000000) as usize;
-                if pal_addr < self.palette_ram.len() {
-                    self.palette_ram[pal_addr] = value;
-                }
-            }
-            0x06000000..=0x06017FFF => {
-                // VRAM
-                let vram_addr = (address - 0x06000000) as usize;
-                if vram_addr < self.vram.len() {
-                    self.vram[vram_addr] = value;
-                }
-            }
-            0x07000000..=0x070003FF => {
-                // OAM RAM
-                let oam_addr = (address - 0x07000000) as usize;
-                if oam_addr < self.oam_ram.len() {
-                    self.oam_ram[oam_addr] = value;
-                }
+            0x04000000..=0x040003FF => {
+                // I/O寄存器
+                self.io.write_8((address - IO_BASE) as u16, value);
             }
             _ => {
-                // 只读区域，忽略写入
+                if let Some((region, offset)) = Self::ram_offset(address) {
+                    self.ram_mut(region)[offset] = value;
+                }
+                // 其余为只读或未使用区域，忽略写入
             }
         }
         
         Ok(())
     }
     
+    /// 把地址映射到RAM区域内的偏移，区域在所属的16MB地址块内按自身大小镜像
+    ///
+    /// VRAM每128KB镜像一次，其中最后32KB重复映射到0x10000开始的精灵区。
+    fn ram_offset(address: u32) -> Option<(RamRegion, usize)> {
+        let offset = (address & 0x00FF_FFFF) as usize;
+        match address >> 24 {
+            0x02 => Some((RamRegion::Ewram, offset & 0x3FFFF)),
+            0x03 => Some((RamRegion::Iwram, offset & 0x7FFF)),
+            0x05 => Some((RamRegion::Palette, offset & 0x3FF)),
+            0x06 => {
+                let offset = offset & 0x1FFFF;
+                Some((RamRegion::Vram, if offset >= 0x18000 { offset - 0x8000 } else { offset }))
+            }
+            0x07 => Some((RamRegion::Oam, offset & 0x3FF)),
+            _ => None,
+        }
+    }
+    
+    fn ram(&self, region: RamRegion) -> &[u8] {
+        match region {
+            RamRegion::Ewram => &self.ewram,
+            RamRegion::Iwram => &self.iwram,
+            RamRegion::Palette => &self.palette_ram,
+            RamRegion::Vram => &self.vram,
+            RamRegion::Oam => &self.oam_ram,
+        }
+    }
+    
+    fn ram_mut(&mut self, region: RamRegion) -> &mut [u8] {
+        match region {
+            RamRegion::Ewram => &mut self.ewram,
+            RamRegion::Iwram => &mut self.iwram,
+            RamRegion::Palette => &mut self.palette_ram,
+            RamRegion::Vram => &mut self.vram,
+            RamRegion::Oam => &mut self.oam_ram,
+        }
+    }
+    
     /// 写入16位数据
     pub fn write_16(&mut self, address: u32, value: u16) -> Result<(), String> {
         self.write_8(address, value as u8)?;
@@ -634,3 +834,142 @@ impl Default for GBAMemory {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ram_regions_mirror_within_their_blocks() {
+        let mut memory = GBAMemory::new();
+
+        // IWRAM每32KB镜像一次，直到0x03FFFFFF
+        memory.write_32(0x0300_7FFC, 0xDEAD_BEEF).unwrap();
+        assert_eq!(memory.read_32(0x0300_FFFC).unwrap(), 0xDEAD_BEEF);
+        assert_eq!(memory.read_32(0x03FF_FFFC).unwrap(), 0xDEAD_BEEF);
+        memory.write_8(0x0301_0000, 0x5A).unwrap();
+        assert_eq!(memory.iwram[0], 0x5A);
+
+        // EWRAM每256KB
+        memory.write_16(0x0204_0010, 0x1234).unwrap();
+        assert_eq!(memory.read_16(0x0200_0010).unwrap(), 0x1234);
+        assert_eq!(memory.read_16(0x02FC_0010).unwrap(), 0x1234);
+
+        // 调色板和OAM每1KB
+        memory.write_16(0x0500_0400, 0x7FFF).unwrap();
+        assert_eq!(memory.read_16(0x05FF_FC00).unwrap(), 0x7FFF);
+        memory.write_16(0x0700_0802, 0x00A0).unwrap();
+        assert_eq!(memory.read_16(0x0700_0002).unwrap(), 0x00A0);
+
+        // VRAM每128KB，0x18000-0x1FFFF映射到0x10000-0x17FFF
+        memory.write_8(0x0601_0005, 0x11).unwrap();
+        assert_eq!(memory.read_8(0x0601_8005).unwrap(), 0x11);
+        assert_eq!(memory.read_8(0x0603_0005).unwrap(), 0x11);
+        memory.write_8(0x0601_FFFF, 0x22).unwrap();
+        assert_eq!(memory.vram[0x17FFF], 0x22);
+        memory.write_8(0x0602_0001, 0x33).unwrap();
+        assert_eq!(memory.vram[1], 0x33);
+
+        // 未使用的区域和ROM不受影响
+        memory.write_8(0x0000_0010, 0xFF).unwrap();
+        memory.write_8(0x0800_0000, 0xFF).unwrap();
+        assert_eq!((memory.read_8(0x0000_0010).unwrap(), memory.read_8(0x0800_0000).unwrap()), (0, 0));
+    }
+
+    /// 把指令字依次写入IWRAM，CPU从0x03000000开始执行
+    fn load(words: &[u32], halves: &[(u32, u16)]) -> (ARM7TDMI, GBAMemory) {
+        let mut memory = GBAMemory::new();
+        for (index, word) in words.iter().enumerate() {
+            memory.write_32(0x0300_0000 + index as u32 * 4, *word).unwrap();
+        }
+        for (address, half) in halves {
+            memory.write_16(*address, *half).unwrap();
+        }
+        let mut cpu = ARM7TDMI::new();
+        cpu.pc = 0x0300_0000;
+        (cpu, memory)
+    }
+
+    #[test]
+    fn test_arm_pc_reads_include_pipeline_offset() {
+        let (mut cpu, mut memory) = load(
+            &[
+                0xE1A0_000F, // MOV r0, pc
+                0xE59F_2000, // LDR r2, [pc, #0]  -> 0x0300000C
+                0xEB00_0000, // BL +0             -> 0x03000010
+                0xCAFE_F00D,
+                0x0000_0000,
+                0xEAFF_FFFE, // B .
+            ],
+            &[],
+        );
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert_eq!(cpu.get_register(0), 0x0300_0008);
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert_eq!(cpu.get_register(2), 0xCAFE_F00D);
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert_eq!((cpu.pc, cpu.get_register(14)), (0x0300_0010, 0x0300_000C));
+
+        // 跳到自身：PC不前进，每次都清空流水线
+        cpu.pc = 0x0300_0014;
+        let flushes = cpu.stats.pipeline_flushes;
+        cpu.execute_instruction(&mut memory).unwrap();
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert_eq!((cpu.pc, cpu.stats.pipeline_flushes), (0x0300_0014, flushes + 2));
+    }
+
+    #[test]
+    fn test_bx_interworking_and_thumb_pc_relative() {
+        let (mut cpu, mut memory) = load(
+            &[
+                0xE28F_3005, // ADD r3, pc, #5    -> 0x0300000D (Thumb)
+                0xE12F_FF13, // BX r3
+            ],
+            &[
+                (0x0300_000C, 0x4679), // MOV r1, pc
+                (0x0300_000E, 0x4801), // LDR r0, [pc, #4] -> (0x03000012 & !3) + 4
+                (0x0300_0014, 0xBEEF),
+                (0x0300_0016, 0x1234),
+                (0x0300_0010, 0x4720), // BX r4
+            ],
+        );
+        cpu.set_register(4, 0x0300_0022);
+        cpu.execute_instruction(&mut memory).unwrap();
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert!(cpu.thumb_mode && cpu.get_flag(CPSRFlag::Thumb));
+        assert_eq!(cpu.pc, 0x0300_000C);
+
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert_eq!(cpu.get_register(1), 0x0300_0010);
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert_eq!(cpu.get_register(0), 0x1234_BEEF);
+
+        // 回到ARM，目标按字对齐
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert!(!cpu.thumb_mode && !cpu.get_flag(CPSRFlag::Thumb));
+        assert_eq!(cpu.pc, 0x0300_0020);
+    }
+
+    #[test]
+    fn test_prefetched_instruction_survives_until_branch() {
+        let (mut cpu, mut memory) = load(
+            &[
+                0xE3A0_1001, // MOV r1, #1
+                0xE58F_2000, // STR r2, [pc, #0]  覆盖已预取的0x0300000C
+                0xE3A0_0000, // MOV r0, #0
+                0xE3A0_0007, // MOV r0, #7
+                0xEAFF_FFFD, // B 0x0300000C
+            ],
+            &[],
+        );
+        cpu.set_register(2, 0xE3A0_0009); // MOV r0, #9
+        for _ in 0..4 {
+            cpu.execute_instruction(&mut memory).unwrap();
+        }
+        assert_eq!(cpu.get_register(0), 7);
+
+        cpu.execute_instruction(&mut memory).unwrap();
+        cpu.execute_instruction(&mut memory).unwrap();
+        assert_eq!(cpu.get_register(0), 9);
+    }
+}
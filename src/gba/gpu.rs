@@ -4,6 +4,8 @@
 //! 包括背景层、精灵、调色板等功能
 
 use crate::gba::cpu::GBAMemory;
+use crate::gba::io_registers::{self, IoAccess, IoRegisters, IRQ_HBLANK, IRQ_VBLANK, IRQ_VCOUNT};
+use crate::gba::scanline::{BackgroundInput, CompositorInputs, SpriteInput, WindowState};
 
 /// GBA GPU状态
 #[derive(Debug, Clone)]
@@ -20,6 +22,8 @@ pub struct GBAGPU {
     pub bgcnt: [u16; 4],
     /// 背景滚动寄存器
     pub bgofs: [u16; 4],
+    /// BG2/BG3仿射参数
+    pub affine: [AffineParams; 2],
     /// 精灵属性内存
     pub oam: [u16; 0x200],
     /// 调色板内存
@@ -28,6 +32,8 @@ pub struct GBAGPU {
     pub vram: [u8; 0x18000],
     /// 当前扫描线
     pub current_scanline: u16,
+    /// 帧缓冲区 (240x160, BGR555)
+    pub framebuffer: Vec<u16>,
     /// 帧计数器
     pub frame_count: u32,
     /// 性能统计
@@ -46,7 +52,7 @@ pub struct GPUStats {
 }
 
 /// 显示模式
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DisplayMode {
     Mode0, // 4个背景层
     Mode1, // 3个背景层
@@ -57,7 +63,7 @@ pub enum DisplayMode {
 }
 
 /// 背景层类型
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BackgroundType {
     Text,      // 文本背景
     Affine,    // 仿射变换背景
@@ -83,6 +89,75 @@ pub struct PaletteColor {
     pub a: u8,
 }
 
+/// 屏幕宽度
+pub const SCREEN_WIDTH: usize = 240;
+/// 屏幕高度
+pub const SCREEN_HEIGHT: usize = 160;
+
+/// 进入VBlank的扫描线
+const VBLANK_START: u16 = 144;
+
+/// GPU寄存器占用的I/O区域 (DISPCNT..BG3Y_H)
+const GPU_IO_END: u16 = 0x040;
+
+/// DISPSTAT中CPU可写的位：三个中断使能和LYC，其余为硬件标志
+const DISPSTAT_WRITABLE: u16 = 0xFF38;
+
+/// 仿射背景参数
+///
+/// 屏幕像素 (x, y) 对应的纹理坐标为
+/// `(X + pa*x + pb*y, Y + pc*x + pd*y)`，矩阵为8.8定点数，参考点为20.8定点数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AffineParams {
+    pub pa: i16,
+    pub pb: i16,
+    pub pc: i16,
+    pub pd: i16,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl AffineParams {
+    /// 单位变换
+    pub const IDENTITY: AffineParams = AffineParams { pa: 0x100, pb: 0, pc: 0, pd: 0x100, x: 0, y: 0 };
+
+    /// 由浮点矩阵和参考点构造（按硬件精度截断）
+    pub fn from_f64(matrix: [f64; 4], reference: (f64, f64)) -> Self {
+        let fixed = |v: f64| (v * 256.0).round() as i32;
+        Self {
+            pa: fixed(matrix[0]).clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+            pb: fixed(matrix[1]).clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+            pc: fixed(matrix[2]).clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+            pd: fixed(matrix[3]).clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+            x: fixed(reference.0),
+            y: fixed(reference.1),
+        }
+    }
+
+    /// 绕屏幕点 `screen_center` 旋转 `angle` 弧度并缩放 `scale` 倍，
+    /// 该屏幕点对应纹理点 `texture_center`
+    pub fn rotate_scale(angle: f64, scale: f64, texture_center: (f64, f64), screen_center: (f64, f64)) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let (pa, pb, pc, pd) = (cos / scale, sin / scale, -sin / scale, cos / scale);
+        let x = texture_center.0 - (pa * screen_center.0 + pb * screen_center.1);
+        let y = texture_center.1 - (pc * screen_center.0 + pd * screen_center.1);
+        Self::from_f64([pa, pb, pc, pd], (x, y))
+    }
+
+    /// 屏幕像素对应的纹理坐标（整数像素）
+    pub fn texture_coords(&self, x: i32, y: i32) -> (i32, i32) {
+        let tx = self.x + self.pa as i32 * x + self.pb as i32 * y;
+        let ty = self.y + self.pc as i32 * x + self.pd as i32 * y;
+        (tx >> 8, ty >> 8)
+    }
+}
+
+impl Default for AffineParams {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
 impl GBAGPU {
     /// 创建新的GBA GPU实例
     pub fn new() -> Self {
@@ -93,10 +168,12 @@ impl GBAGPU {
             vcount: 0x0000,
             bgcnt: [0; 4],
             bgofs: [0; 4],
+            affine: [AffineParams::IDENTITY; 2],
             oam: [0; 0x200],
             palette: [0; 0x200],
             vram: [0; 0x18000],
             current_scanline: 0,
+            framebuffer: vec![0; SCREEN_WIDTH * SCREEN_HEIGHT],
             frame_count: 0,
             stats: GPUStats::default(),
         }
@@ -110,10 +187,12 @@ impl GBAGPU {
         self.vcount = 0x0000;
         self.bgcnt = [0; 4];
         self.bgofs = [0; 4];
+        self.affine = [AffineParams::IDENTITY; 2];
         self.oam = [0; 0x200];
         self.palette = [0; 0x200];
         self.vram = [0; 0x18000];
         self.current_scanline = 0;
+        self.framebuffer.fill(0);
         self.frame_count = 0;
         self.stats = GPUStats::default();
     }
@@ -131,39 +210,28 @@ impl GBAGPU {
         }
     }
     
-    /// 检查背景层是否启用
+    /// 检查背景层是否启用：当前模式提供该层且DISPCNT对应位已打开
     pub fn is_background_enabled(&self, bg: usize) -> bool {
-        if bg >= 4 {
-            return false;
-        }
-        
-        match self.get_display_mode() {
-            DisplayMode::Mode0 => bg < 4,
-            DisplayMode::Mode1 => bg < 3,
-            DisplayMode::Mode2 => bg < 2,
-            DisplayMode::Mode3 | DisplayMode::Mode4 | DisplayMode::Mode5 => false,
-        }
+        bg < 4 && self.get_background_type(bg) != BackgroundType::Disabled && self.dispcnt & (0x100 << bg) != 0
     }
     
     /// 获取背景层类型
     pub fn get_background_type(&self, bg: usize) -> BackgroundType {
-        if bg >= 4 {
-            return BackgroundType::Disabled;
-        }
-        
-        match self.get_display_mode() {
-            DisplayMode::Mode0 | DisplayMode::Mode1 | DisplayMode::Mode2 => {
-                BackgroundType::Text
-            }
-            DisplayMode::Mode3 | DisplayMode::Mode4 | DisplayMode::Mode5 => {
-                BackgroundType::Bitmap
-            }
+        match (self.get_display_mode(), bg) {
+            (DisplayMode::Mode0, 0..=3) => BackgroundType::Text,
+            (DisplayMode::Mode1, 0..=1) => BackgroundType::Text,
+            (DisplayMode::Mode1, 2) => BackgroundType::Affine,
+            (DisplayMode::Mode2, 2..=3) => BackgroundType::Affine,
+            (DisplayMode::Mode3 | DisplayMode::Mode4 | DisplayMode::Mode5, 2) => BackgroundType::Bitmap,
+            _ => BackgroundType::Disabled,
         }
     }
     
     /// 渲染当前扫描线
     pub fn render_scanline(&mut self, memory: &mut GBAMemory) -> Result<(), String> {
-        let mut scanline_buffer = [0u16; 240]; // 240像素宽
+        // 先填充背景色（调色板0号）
+        let backdrop = memory.read_16(0x05000000)?;
+        let mut scanline_buffer = [backdrop; 240]; // 240像素宽
         
         // 渲染背景层
         for bg in 0..4 {
@@ -175,6 +243,11 @@ impl GBAGPU {
         // 渲染精灵
         self.render_sprites_scanline(&mut scanline_buffer, memory)?;
         
+        let line = self.current_scanline as usize;
+        if line < SCREEN_HEIGHT {
+            self.framebuffer[line * SCREEN_WIDTH..(line + 1) * SCREEN_WIDTH].copy_from_slice(&scanline_buffer);
+        }
+        
         // 更新统计
         self.stats.pixels_drawn += 240;
         self.stats.backgrounds_rendered += 1;
@@ -325,23 +398,49 @@ impl GBAGPU {
     }
     
     /// 渲染仿射变换背景层扫描线
+    ///
+    /// 仿射背景使用8bpp瓦片和单字节图块编号，尺寸为128<<size像素见方
     fn render_affine_background_scanline(&self, bg: usize, buffer: &mut [u16; 240], memory: &mut GBAMemory) -> Result<(), String> {
-        // 仿射变换背景层实现
-        // 这里简化实现，实际需要矩阵变换
-        for x in 0..240 {
-            // 简化的仿射变换
-            let transformed_x = x as f32 * 1.0;
-            let transformed_y = self.current_scanline as f32 * 1.0;
+        let params = self.affine[bg - 2];
+        let bgcnt = self.bgcnt[bg];
+        let size = 128i32 << ((bgcnt >> 14) & 0x3);
+        let char_base = 0x06000000 + ((bgcnt >> 2) & 0x3) as u32 * 0x4000;
+        let screen_base = 0x06000000 + ((bgcnt >> 8) & 0x1F) as u32 * 0x800;
+        let wraparound = bgcnt & 0x2000 != 0;
+        
+        for (x, pixel) in buffer.iter_mut().enumerate() {
+            let (mut tx, mut ty) = params.texture_coords(x as i32, self.current_scanline as i32);
+            if wraparound {
+                tx = tx.rem_euclid(size);
+                ty = ty.rem_euclid(size);
+            } else if tx < 0 || ty < 0 || tx >= size || ty >= size {
+                continue;
+            }
             
-            // 读取像素数据
-            let pixel_addr = 0x06000000 + (transformed_y as u32 * 240 + transformed_x as u32) * 2;
-            let color = memory.read_16(pixel_addr)?;
-            buffer[x] = color;
+            let map_addr = screen_base + ((ty / 8) * (size / 8) + tx / 8) as u32;
+            let tile_index = memory.read_8(map_addr)?;
+            let pixel_addr = char_base + tile_index as u32 * 64 + ((ty % 8) * 8 + tx % 8) as u32;
+            let color_index = memory.read_8(pixel_addr)?;
+            
+            if color_index != 0 {
+                *pixel = memory.read_16(0x05000000 + color_index as u32 * 2)?;
+            }
         }
         
         Ok(())
     }
     
+    /// 渲染整帧（不推进帧计数），返回帧缓冲区
+    pub fn render_frame(&mut self, memory: &mut GBAMemory) -> Result<&[u16], String> {
+        let saved_scanline = self.current_scanline;
+        for line in 0..SCREEN_HEIGHT as u16 {
+            self.current_scanline = line;
+            self.render_scanline(memory)?;
+        }
+        self.current_scanline = saved_scanline;
+        Ok(&self.framebuffer)
+    }
+    
     /// 渲染精灵扫描线
     fn render_sprites_scanline(&self, buffer: &mut [u16; 240], memory: &mut GBAMemory) -> Result<(), String> {
         // 遍历所有精灵
@@ -370,10 +469,14 @@ impl GBAGPU {
     
     /// 检查精灵是否在当前扫描线
     fn is_sprite_on_scanline(&self, sprite: SpriteAttribute) -> bool {
+        self.is_sprite_on_line(sprite, self.current_scanline)
+    }
+    
+    fn is_sprite_on_line(&self, sprite: SpriteAttribute, line: u16) -> bool {
         let y = sprite.attr0 & 0xFF;
         let height = self.get_sprite_height(sprite);
         
-        y <= self.current_scanline && self.current_scanline < y + height
+        y <= line && line < y + height
     }
     
     /// 获取精灵高度
@@ -425,6 +528,56 @@ impl GBAGPU {
         Ok(())
     }
     
+    /// 收集合成指定扫描线时使用的输入，与渲染器的判断保持一致
+    pub fn compositor_inputs(&self, line: u16, memory: &GBAMemory) -> CompositorInputs {
+        let backgrounds = (0..4)
+            .filter(|&bg| self.is_background_enabled(bg))
+            .map(|bg| {
+                let bgcnt = self.bgcnt[bg];
+                let kind = self.get_background_type(bg);
+                BackgroundInput {
+                    index: bg,
+                    kind,
+                    priority: (bgcnt & 0x3) as u8,
+                    char_base: 0x06000000 + ((bgcnt >> 2) & 0x3) as u32 * 0x4000,
+                    screen_base: 0x06000000 + ((bgcnt >> 8) & 0x1F) as u32 * 0x800,
+                    hofs: self.bgofs[bg] & 0x1FF,
+                    vofs: (self.bgofs[bg] >> 8) & 0x1FF,
+                    affine: (kind == BackgroundType::Affine).then(|| self.affine[bg - 2]),
+                }
+            })
+            .collect();
+        
+        let sprites = (0..128)
+            .map(|index| (index, self.get_sprite(index)))
+            .filter(|&(_, sprite)| self.is_sprite_on_line(sprite, line))
+            .map(|(index, sprite)| SpriteInput {
+                index,
+                x: sprite.attr1 & 0x1FF,
+                y: sprite.attr0 & 0xFF,
+                height: self.get_sprite_height(sprite),
+                tile: sprite.attr2 & 0x3FF,
+                palette: ((sprite.attr2 >> 12) & 0xF) as u8,
+                priority: ((sprite.attr2 >> 10) & 0x3) as u8,
+            })
+            .collect();
+        
+        CompositorInputs {
+            frame: self.frame_count,
+            line,
+            mode: self.get_display_mode(),
+            backdrop: u16::from_le_bytes([memory.palette_ram[0], memory.palette_ram[1]]),
+            backgrounds,
+            obj_enabled: self.dispcnt & 0x1000 != 0,
+            sprites,
+            windows: WindowState {
+                win0: self.dispcnt & 0x2000 != 0,
+                win1: self.dispcnt & 0x4000 != 0,
+                obj_window: self.dispcnt & 0x8000 != 0,
+            },
+        }
+    }
+    
     /// 更新GPU状态
     pub fn update(&mut self) {
         self.current_scanline += 1;
@@ -446,6 +599,133 @@ impl GBAGPU {
         self.vcount = self.current_scanline;
     }
     
+    /// 把GPU寄存器登记到I/O分派器，并写入当前值
+    pub fn register_io(&self, io: &mut IoRegisters) {
+        io.register(0x000, IoAccess::ReadWrite, 0xFFF7);
+        io.register(0x002, IoAccess::ReadWrite, 0x0001);
+        io.register(io_registers::DISPSTAT, IoAccess::ReadWrite, DISPSTAT_WRITABLE);
+        io.register(io_registers::VCOUNT, IoAccess::ReadOnly, 0);
+        for index in 0..4u16 {
+            // BG0/BG1没有环绕位
+            io.register(0x008 + index * 2, IoAccess::ReadWrite, if index < 2 { 0xDFFF } else { 0xFFFF });
+            io.register(0x010 + index * 4, IoAccess::WriteOnly, 0x01FF);
+            io.register(0x012 + index * 4, IoAccess::WriteOnly, 0x01FF);
+        }
+        for offset in (0x020..GPU_IO_END).step_by(2) {
+            // 参考点高位只有12位
+            let writable = if offset & 0x0A == 0x0A { 0x0FFF } else { 0xFFFF };
+            io.register(offset, IoAccess::WriteOnly, writable);
+        }
+        self.publish_io(io);
+    }
+
+    /// 读取GPU寄存器，偏移不属于GPU时返回 `None`
+    pub fn read_register(&self, offset: u16) -> Option<u16> {
+        let value = match offset {
+            0x000 => self.dispcnt,
+            0x002 => self.green_swap,
+            0x004 => self.dispstat,
+            0x006 => self.vcount,
+            0x008..=0x00E => self.bgcnt[(offset as usize - 0x008) / 2],
+            // 滚动寄存器低8位为水平偏移、高8位为垂直偏移
+            0x010..=0x01E => {
+                let bgofs = self.bgofs[(offset as usize - 0x010) / 4];
+                if offset & 2 == 0 { bgofs & 0x1FF } else { (bgofs >> 8) & 0x1FF }
+            }
+            0x020..=0x03E => {
+                let params = &self.affine[(offset as usize - 0x020) / 0x10];
+                match (offset & 0x0F) / 2 {
+                    0 => params.pa as u16,
+                    1 => params.pb as u16,
+                    2 => params.pc as u16,
+                    3 => params.pd as u16,
+                    4 => params.x as u16,
+                    5 => ((params.x >> 16) & 0x0FFF) as u16,
+                    6 => params.y as u16,
+                    _ => ((params.y >> 16) & 0x0FFF) as u16,
+                }
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// 应用CPU对GPU寄存器的写入，VCOUNT和DISPSTAT标志位不受影响
+    pub fn write_register(&mut self, offset: u16, value: u16) {
+        // 参考点为28位有符号数，高位写入后符号扩展
+        let high = |reference: i32| ((reference as u32 & 0xFFFF) as i32) | (((value as i32) << 20) >> 4);
+        let low = |reference: i32| (reference & !0xFFFF) | value as i32;
+        match offset {
+            0x000 => self.dispcnt = value,
+            0x002 => self.green_swap = value,
+            0x004 => self.dispstat = (self.dispstat & !DISPSTAT_WRITABLE) | (value & DISPSTAT_WRITABLE),
+            0x008..=0x00E => self.bgcnt[(offset as usize - 0x008) / 2] = value,
+            0x010..=0x01E => {
+                let bgofs = &mut self.bgofs[(offset as usize - 0x010) / 4];
+                *bgofs = if offset & 2 == 0 {
+                    (*bgofs & 0xFF00) | (value & 0x00FF)
+                } else {
+                    (*bgofs & 0x00FF) | ((value & 0x00FF) << 8)
+                };
+            }
+            0x020..=0x03E => {
+                let params = &mut self.affine[(offset as usize - 0x020) / 0x10];
+                match (offset & 0x0F) / 2 {
+                    0 => params.pa = value as i16,
+                    1 => params.pb = value as i16,
+                    2 => params.pc = value as i16,
+                    3 => params.pd = value as i16,
+                    4 => params.x = low(params.x),
+                    5 => params.x = high(params.x),
+                    6 => params.y = low(params.y),
+                    _ => params.y = high(params.y),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 当前扫描线是否处于VBlank
+    pub fn in_vblank(&self) -> bool {
+        self.current_scanline >= VBLANK_START
+    }
+
+    /// 与I/O分派器同步：取走CPU的写入，刷新DISPSTAT标志并请求中断，再回写所有GPU寄存器
+    ///
+    /// 在每次 `update` 之后调用，一次对应一条扫描线。
+    pub fn sync_io(&mut self, io: &mut IoRegisters) {
+        for offset in (0..GPU_IO_END).step_by(2) {
+            if let Some(value) = io.take_write(offset) {
+                self.write_register(offset, value);
+            }
+        }
+
+        let vcounter = self.vcount == self.dispstat >> 8;
+        let flags = u16::from(self.in_vblank()) | (u16::from(vcounter) << 2);
+        self.dispstat = (self.dispstat & DISPSTAT_WRITABLE) | flags;
+
+        let enabled = |bit: u16| self.dispstat & bit != 0;
+        if self.current_scanline == VBLANK_START && enabled(1 << 3) {
+            io.request_interrupt(IRQ_VBLANK);
+        }
+        if !self.in_vblank() && enabled(1 << 4) {
+            io.request_interrupt(IRQ_HBLANK);
+        }
+        if vcounter && enabled(1 << 5) {
+            io.request_interrupt(IRQ_VCOUNT);
+        }
+        self.publish_io(io);
+    }
+
+    /// 把寄存器当前值写入分派器
+    fn publish_io(&self, io: &mut IoRegisters) {
+        for offset in (0..GPU_IO_END).step_by(2) {
+            if let Some(value) = self.read_register(offset) {
+                io.set(offset, value);
+            }
+        }
+    }
+
     /// 获取GPU统计
     pub fn get_stats(&self) -> &GPUStats {
         &self.stats
@@ -457,3 +737,40 @@ impl Default for GBAGPU {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_affine_rotation_maps_center_and_axes() {
+        // 旋转90度：屏幕向右一个像素对应纹理向上一个像素
+        let params = AffineParams::rotate_scale(std::f64::consts::FRAC_PI_2, 1.0, (64.0, 64.0), (120.0, 80.0));
+        assert_eq!(params.texture_coords(120, 80), (64, 64));
+        assert_eq!(params.texture_coords(130, 80), (64, 54));
+
+        // 放大2倍：屏幕两个像素对应一个纹理像素
+        let zoom = AffineParams::rotate_scale(0.0, 2.0, (0.0, 0.0), (0.0, 0.0));
+        assert_eq!(zoom.texture_coords(10, 6), (5, 3));
+        assert_eq!(AffineParams::IDENTITY.texture_coords(-3, 7), (-3, 7));
+    }
+
+    #[test]
+    fn test_affine_background_renders_wrapped_texture() {
+        let mut gpu = GBAGPU::new();
+        let mut memory = GBAMemory::new();
+        // 模式1 + BG2，图块基址块1，字符基址块0，128x128环绕
+        gpu.dispcnt = 0x0401;
+        gpu.bgcnt[2] = 0x2000 | (1 << 8);
+        memory.write_16(0x05000002, 0x7FFF).unwrap();
+        // 瓦片1的第一个像素为颜色1，图块(0,0)使用瓦片1
+        memory.write_8(0x06000040, 1).unwrap();
+        memory.write_8(0x06000800, 1).unwrap();
+
+        let framebuffer = gpu.render_frame(&mut memory).unwrap();
+        assert_eq!(framebuffer[0], 0x7FFF);
+        assert_eq!(framebuffer[1], 0x0000);
+        assert_eq!(framebuffer[128], 0x7FFF); // 水平环绕
+        assert_eq!(framebuffer[128 * SCREEN_WIDTH], 0x7FFF); // 垂直环绕
+    }
+}
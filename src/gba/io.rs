@@ -0,0 +1,299 @@
+//! GBA I/O寄存器表
+//!
+//! 0x04000000 区域各寄存器的名称、地址和位域定义，用于调试时按名称查看寄存器。
+//! 寄存器的值由 `GBASystem::read_io_register` 从GPU和 `io_registers` 分派器取得，
+//! 没有登记到分派器的寄存器不会出现在转储中。
+
+use std::fmt;
+
+/// I/O区域基地址
+pub const IO_BASE: u32 = 0x0400_0000;
+
+/// 寄存器中的一个位域
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitField {
+    pub name: &'static str,
+    pub shift: u8,
+    pub width: u8,
+}
+
+impl BitField {
+    /// 从寄存器值中取出该位域
+    pub fn extract(&self, value: u16) -> u16 {
+        (value >> self.shift) & ((1u32 << self.width) - 1) as u16
+    }
+}
+
+const fn bit(name: &'static str, shift: u8) -> BitField {
+    BitField { name, shift, width: 1 }
+}
+
+const fn bits(name: &'static str, shift: u8, width: u8) -> BitField {
+    BitField { name, shift, width }
+}
+
+/// 一个16位I/O寄存器的定义
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoRegisterInfo {
+    pub name: &'static str,
+    /// 相对于 `IO_BASE` 的偏移
+    pub offset: u16,
+    pub description: &'static str,
+    pub fields: &'static [BitField],
+}
+
+impl IoRegisterInfo {
+    /// 完整地址
+    pub fn address(&self) -> u32 {
+        IO_BASE + self.offset as u32
+    }
+}
+
+const fn reg(name: &'static str, offset: u16, description: &'static str, fields: &'static [BitField]) -> IoRegisterInfo {
+    IoRegisterInfo { name, offset, description, fields }
+}
+
+const DISPCNT: &[BitField] = &[
+    bits("MODE", 0, 3),
+    bit("CGB", 3),
+    bit("FRAME", 4),
+    bit("HBLANK_FREE", 5),
+    bit("OBJ_1D", 6),
+    bit("BLANK", 7),
+    bit("BG0", 8),
+    bit("BG1", 9),
+    bit("BG2", 10),
+    bit("BG3", 11),
+    bit("OBJ", 12),
+    bit("WIN0", 13),
+    bit("WIN1", 14),
+    bit("OBJWIN", 15),
+];
+const DISPSTAT: &[BitField] = &[
+    bit("VBLANK", 0),
+    bit("HBLANK", 1),
+    bit("VCOUNTER", 2),
+    bit("VBLANK_IRQ", 3),
+    bit("HBLANK_IRQ", 4),
+    bit("VCOUNT_IRQ", 5),
+    bits("LYC", 8, 8),
+];
+const BGCNT: &[BitField] = &[
+    bits("PRIORITY", 0, 2),
+    bits("CHAR_BASE", 2, 2),
+    bit("MOSAIC", 6),
+    bit("COLOR_256", 7),
+    bits("SCREEN_BASE", 8, 5),
+    bit("WRAP", 13),
+    bits("SIZE", 14, 2),
+];
+const OFFSET: &[BitField] = &[bits("OFFSET", 0, 9)];
+const DMACNT_H: &[BitField] = &[
+    bits("DEST_CTRL", 5, 2),
+    bits("SRC_CTRL", 7, 2),
+    bit("REPEAT", 9),
+    bit("WORD", 10),
+    bit("DRQ", 11),
+    bits("TIMING", 12, 2),
+    bit("IRQ", 14),
+    bit("ENABLE", 15),
+];
+const TMCNT_H: &[BitField] = &[bits("PRESCALER", 0, 2), bit("CASCADE", 2), bit("IRQ", 6), bit("ENABLE", 7)];
+const KEYS: &[BitField] = &[
+    bit("A", 0),
+    bit("B", 1),
+    bit("SELECT", 2),
+    bit("START", 3),
+    bit("RIGHT", 4),
+    bit("LEFT", 5),
+    bit("UP", 6),
+    bit("DOWN", 7),
+    bit("R", 8),
+    bit("L", 9),
+];
+const KEYCNT: &[BitField] = &[bits("KEYS", 0, 10), bit("IRQ", 14), bit("AND", 15)];
+const INTERRUPTS: &[BitField] = &[
+    bit("VBLANK", 0),
+    bit("HBLANK", 1),
+    bit("VCOUNT", 2),
+    bit("TIMER0", 3),
+    bit("TIMER1", 4),
+    bit("TIMER2", 5),
+    bit("TIMER3", 6),
+    bit("SERIAL", 7),
+    bit("DMA0", 8),
+    bit("DMA1", 9),
+    bit("DMA2", 10),
+    bit("DMA3", 11),
+    bit("KEYPAD", 12),
+    bit("GAMEPAK", 13),
+];
+
+/// 已知的I/O寄存器，按地址排序
+pub const IO_REGISTERS: &[IoRegisterInfo] = &[
+    reg("DISPCNT", 0x000, "显示控制", DISPCNT),
+    reg("GREENSWP", 0x002, "绿色交换", &[bit("SWAP", 0)]),
+    reg("DISPSTAT", 0x004, "显示状态", DISPSTAT),
+    reg("VCOUNT", 0x006, "当前扫描线", &[bits("LY", 0, 8)]),
+    reg("BG0CNT", 0x008, "BG0控制", BGCNT),
+    reg("BG1CNT", 0x00A, "BG1控制", BGCNT),
+    reg("BG2CNT", 0x00C, "BG2控制", BGCNT),
+    reg("BG3CNT", 0x00E, "BG3控制", BGCNT),
+    reg("BG0HOFS", 0x010, "BG0水平滚动", OFFSET),
+    reg("BG0VOFS", 0x012, "BG0垂直滚动", OFFSET),
+    reg("BG1HOFS", 0x014, "BG1水平滚动", OFFSET),
+    reg("BG1VOFS", 0x016, "BG1垂直滚动", OFFSET),
+    reg("BG2HOFS", 0x018, "BG2水平滚动", OFFSET),
+    reg("BG2VOFS", 0x01A, "BG2垂直滚动", OFFSET),
+    reg("BG3HOFS", 0x01C, "BG3水平滚动", OFFSET),
+    reg("BG3VOFS", 0x01E, "BG3垂直滚动", OFFSET),
+    reg("BG2PA", 0x020, "BG2仿射dx (8.8)", &[]),
+    reg("BG2PB", 0x022, "BG2仿射dmx (8.8)", &[]),
+    reg("BG2PC", 0x024, "BG2仿射dy (8.8)", &[]),
+    reg("BG2PD", 0x026, "BG2仿射dmy (8.8)", &[]),
+    reg("BG2X_L", 0x028, "BG2参考点X低位", &[]),
+    reg("BG2X_H", 0x02A, "BG2参考点X高位", &[]),
+    reg("BG2Y_L", 0x02C, "BG2参考点Y低位", &[]),
+    reg("BG2Y_H", 0x02E, "BG2参考点Y高位", &[]),
+    reg("BG3PA", 0x030, "BG3仿射dx (8.8)", &[]),
+    reg("BG3PB", 0x032, "BG3仿射dmx (8.8)", &[]),
+    reg("BG3PC", 0x034, "BG3仿射dy (8.8)", &[]),
+    reg("BG3PD", 0x036, "BG3仿射dmy (8.8)", &[]),
+    reg("BG3X_L", 0x038, "BG3参考点X低位", &[]),
+    reg("BG3X_H", 0x03A, "BG3参考点X高位", &[]),
+    reg("BG3Y_L", 0x03C, "BG3参考点Y低位", &[]),
+    reg("BG3Y_H", 0x03E, "BG3参考点Y高位", &[]),
+    reg("DMA0SAD_L", 0x0B0, "DMA0源地址低位", &[]),
+    reg("DMA0SAD_H", 0x0B2, "DMA0源地址高位", &[]),
+    reg("DMA0DAD_L", 0x0B4, "DMA0目标地址低位", &[]),
+    reg("DMA0DAD_H", 0x0B6, "DMA0目标地址高位", &[]),
+    reg("DMA0CNT_L", 0x0B8, "DMA0传输数量", &[]),
+    reg("DMA0CNT_H", 0x0BA, "DMA0控制", DMACNT_H),
+    reg("DMA1SAD_L", 0x0BC, "DMA1源地址低位", &[]),
+    reg("DMA1SAD_H", 0x0BE, "DMA1源地址高位", &[]),
+    reg("DMA1DAD_L", 0x0C0, "DMA1目标地址低位", &[]),
+    reg("DMA1DAD_H", 0x0C2, "DMA1目标地址高位", &[]),
+    reg("DMA1CNT_L", 0x0C4, "DMA1传输数量", &[]),
+    reg("DMA1CNT_H", 0x0C6, "DMA1控制", DMACNT_H),
+    reg("DMA2SAD_L", 0x0C8, "DMA2源地址低位", &[]),
+    reg("DMA2SAD_H", 0x0CA, "DMA2源地址高位", &[]),
+    reg("DMA2DAD_L", 0x0CC, "DMA2目标地址低位", &[]),
+    reg("DMA2DAD_H", 0x0CE, "DMA2目标地址高位", &[]),
+    reg("DMA2CNT_L", 0x0D0, "DMA2传输数量", &[]),
+    reg("DMA2CNT_H", 0x0D2, "DMA2控制", DMACNT_H),
+    reg("DMA3SAD_L", 0x0D4, "DMA3源地址低位", &[]),
+    reg("DMA3SAD_H", 0x0D6, "DMA3源地址高位", &[]),
+    reg("DMA3DAD_L", 0x0D8, "DMA3目标地址低位", &[]),
+    reg("DMA3DAD_H", 0x0DA, "DMA3目标地址高位", &[]),
+    reg("DMA3CNT_L", 0x0DC, "DMA3传输数量", &[]),
+    reg("DMA3CNT_H", 0x0DE, "DMA3控制", DMACNT_H),
+    reg("TM0CNT_L", 0x100, "定时器0计数/重载", &[]),
+    reg("TM0CNT_H", 0x102, "定时器0控制", TMCNT_H),
+    reg("TM1CNT_L", 0x104, "定时器1计数/重载", &[]),
+    reg("TM1CNT_H", 0x106, "定时器1控制", TMCNT_H),
+    reg("TM2CNT_L", 0x108, "定时器2计数/重载", &[]),
+    reg("TM2CNT_H", 0x10A, "定时器2控制", TMCNT_H),
+    reg("TM3CNT_L", 0x10C, "定时器3计数/重载", &[]),
+    reg("TM3CNT_H", 0x10E, "定时器3控制", TMCNT_H),
+    reg("KEYINPUT", 0x130, "按键状态 (0=按下)", KEYS),
+    reg("KEYCNT", 0x132, "按键中断控制", KEYCNT),
+    reg("IE", 0x200, "中断使能", INTERRUPTS),
+    reg("IF", 0x202, "中断请求", INTERRUPTS),
+    reg("WAITCNT", 0x204, "等待状态控制", &[]),
+    reg("IME", 0x208, "中断总开关", &[bit("ENABLE", 0)]),
+];
+
+/// 按名称查找寄存器（不区分大小写）
+pub fn lookup(name: &str) -> Option<&'static IoRegisterInfo> {
+    IO_REGISTERS.iter().find(|info| info.name.eq_ignore_ascii_case(name))
+}
+
+/// 寄存器的当前值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoRegisterValue {
+    pub info: &'static IoRegisterInfo,
+    pub value: u16,
+}
+
+impl IoRegisterValue {
+    /// 解码后的位域
+    pub fn fields(&self) -> Vec<(&'static str, u16)> {
+        self.info.fields.iter().map(|field| (field.name, field.extract(self.value))).collect()
+    }
+}
+
+impl fmt::Display for IoRegisterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08X} {:<10} 0x{:04X}  {}", self.info.address(), self.info.name, self.value, self.info.description)?;
+        let fields: Vec<String> = self.fields().iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        if !fields.is_empty() {
+            write!(f, "\n    {}", fields.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// 一次I/O寄存器转储
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IoDump {
+    pub registers: Vec<IoRegisterValue>,
+}
+
+impl IoDump {
+    /// 按名称取寄存器
+    pub fn get(&self, name: &str) -> Option<&IoRegisterValue> {
+        self.registers.iter().find(|register| register.info.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for IoDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for register in &self.registers {
+            writeln!(f, "{}", register)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_table_decodes_fields() {
+        let dispcnt = lookup("dispcnt").unwrap();
+        assert_eq!(dispcnt.address(), 0x0400_0000);
+        assert_eq!(lookup("TM0CNT_H").unwrap().address(), 0x0400_0102);
+        assert!(IO_REGISTERS.windows(2).all(|pair| pair[0].offset < pair[1].offset));
+
+        let value = IoRegisterValue { info: dispcnt, value: 0x0403 };
+        assert_eq!(value.fields()[0], ("MODE", 3));
+        assert!(value.fields().contains(&("BG2", 1)));
+        assert!(value.to_string().contains("MODE=3 CGB=0"));
+
+        let bgcnt = IoRegisterValue { info: lookup("BG1CNT").unwrap(), value: 0x1F84 };
+        assert!(bgcnt.fields().contains(&("SCREEN_BASE", 31)));
+        assert!(bgcnt.fields().contains(&("CHAR_BASE", 1)));
+    }
+
+    #[test]
+    fn test_system_dump_and_debug_command() {
+        let mut gba = crate::gba::GBASystem::new();
+        gba.gpu.dispcnt = 0x0403;
+        gba.gpu.bgofs[1] = 0x2010;
+        gba.gpu.affine[0].x = -0x100;
+
+        let dump = gba.dump_io_registers();
+        assert_eq!(dump.get("DISPCNT").unwrap().value, 0x0403);
+        assert_eq!(dump.get("BG1HOFS").unwrap().value, 0x10);
+        assert_eq!(dump.get("BG1VOFS").unwrap().value, 0x20);
+        assert_eq!(dump.get("BG2X_H").unwrap().value, 0x0FFF);
+        assert_eq!(dump.get("TM0CNT_H").unwrap().value, 0);
+        assert_eq!(dump.get("KEYINPUT").unwrap().value, 0x03FF);
+
+        assert!(gba.run_debug_command("io bg1vofs").unwrap().contains("OFFSET=32"));
+        assert!(gba.run_debug_command("io").unwrap().starts_with("04000000 DISPCNT    0x0403"));
+        assert!(gba.run_debug_command("io TM0CNT_H").unwrap().contains("ENABLE=0"));
+        assert!(gba.run_debug_command("io NOPE").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! GBA I/O寄存器分派
+//!
+//! `GBAMemory` 把 0x04000000 区域的访问交给 `IoRegisters`。各子系统（GPU、键盘、
+//! 定时器、DMA）启动时把自己的寄存器连同读写语义登记进来：
+//!
+//! - CPU写入只修改 `writable` 中的位，其余位由硬件维护（如DISPSTAT的标志位）
+//! - 只读寄存器忽略CPU写入（KEYINPUT、VCOUNT），只写寄存器读出为0
+//! - IF写1清除对应位，用于确认中断
+//!
+//! 子系统在每一步之后用 `take_write` 取走CPU写入的新值，再用 `set` 回写硬件状态。
+//! 未登记的地址读出为0，写入被忽略。
+
+/// I/O区域大小
+pub const IO_SIZE: u16 = 0x400;
+
+pub const DISPSTAT: u16 = 0x004;
+pub const VCOUNT: u16 = 0x006;
+pub const KEYINPUT: u16 = 0x130;
+pub const KEYCNT: u16 = 0x132;
+pub const IE: u16 = 0x200;
+pub const IF: u16 = 0x202;
+pub const WAITCNT: u16 = 0x204;
+pub const IME: u16 = 0x208;
+
+/// IE/IF中的中断位
+pub const IRQ_VBLANK: u16 = 1 << 0;
+pub const IRQ_HBLANK: u16 = 1 << 1;
+pub const IRQ_VCOUNT: u16 = 1 << 2;
+pub const IRQ_KEYPAD: u16 = 1 << 12;
+
+/// CPU访问寄存器时的语义
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoAccess {
+    /// 可读写
+    ReadWrite,
+    /// 只读，CPU写入被忽略
+    ReadOnly,
+    /// 只写，读出为0
+    WriteOnly,
+    /// 写1清除对应位
+    AcknowledgeOnWrite,
+}
+
+/// 一个已登记寄存器的读写规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoHandler {
+    pub access: IoAccess,
+    /// CPU可以修改的位
+    pub writable: u16,
+}
+
+/// 0x04000000 区域的寄存器文件
+#[derive(Debug, Clone)]
+pub struct IoRegisters {
+    values: Vec<u16>,
+    handlers: Vec<Option<IoHandler>>,
+    /// CPU写过、尚未被子系统取走的寄存器
+    written: Vec<bool>,
+}
+
+impl IoRegisters {
+    /// 创建寄存器文件，中断控制器的寄存器（IE、IF、WAITCNT、IME）已登记
+    pub fn new() -> Self {
+        let slots = (IO_SIZE / 2) as usize;
+        let mut io = Self { values: vec![0; slots], handlers: vec![None; slots], written: vec![false; slots] };
+        io.register(IE, IoAccess::ReadWrite, 0x3FFF);
+        io.register(IF, IoAccess::AcknowledgeOnWrite, 0x3FFF);
+        io.register(WAITCNT, IoAccess::ReadWrite, 0x5FFF);
+        io.register(IME, IoAccess::ReadWrite, 0x0001);
+        io
+    }
+
+    fn slot(offset: u16) -> Option<usize> {
+        (offset < IO_SIZE).then_some((offset / 2) as usize)
+    }
+
+    /// 登记寄存器，重复登记时覆盖原有规则
+    pub fn register(&mut self, offset: u16, access: IoAccess, writable: u16) {
+        if let Some(slot) = Self::slot(offset) {
+            self.handlers[slot] = Some(IoHandler { access, writable });
+        }
+    }
+
+    /// 寄存器的读写规则，未登记时为 `None`
+    pub fn handler(&self, offset: u16) -> Option<IoHandler> {
+        Self::slot(offset).and_then(|slot| self.handlers[slot])
+    }
+
+    /// CPU读取16位寄存器
+    pub fn read_16(&self, offset: u16) -> u16 {
+        match self.handler(offset) {
+            Some(handler) if handler.access != IoAccess::WriteOnly => self.values[(offset / 2) as usize],
+            _ => 0,
+        }
+    }
+
+    /// CPU读取一个字节
+    pub fn read_8(&self, offset: u16) -> u8 {
+        (self.read_16(offset & !1) >> ((offset & 1) * 8)) as u8
+    }
+
+    /// CPU写入16位寄存器
+    pub fn write_16(&mut self, offset: u16, value: u16) {
+        self.write_lanes(offset, value, 0xFFFF);
+    }
+
+    /// CPU写入一个字节，寄存器的另一半不受影响
+    pub fn write_8(&mut self, offset: u16, value: u8) {
+        let shift = (offset & 1) * 8;
+        self.write_lanes(offset & !1, (value as u16) << shift, 0xFF << shift);
+    }
+
+    /// 只修改 `lanes` 中的位
+    fn write_lanes(&mut self, offset: u16, value: u16, lanes: u16) {
+        let Some(handler) = self.handler(offset) else {
+            return;
+        };
+        let slot = (offset / 2) as usize;
+        let mask = handler.writable & lanes;
+        match handler.access {
+            IoAccess::ReadOnly => return,
+            IoAccess::AcknowledgeOnWrite => self.values[slot] &= !(value & mask),
+            IoAccess::ReadWrite | IoAccess::WriteOnly => {
+                self.values[slot] = (self.values[slot] & !mask) | (value & mask);
+            }
+        }
+        self.written[slot] = true;
+    }
+
+    /// 取走CPU自上次调用以来写入的值，没有写入时返回 `None`
+    pub fn take_write(&mut self, offset: u16) -> Option<u16> {
+        let slot = Self::slot(offset)?;
+        std::mem::take(&mut self.written[slot]).then(|| self.values[slot])
+    }
+
+    /// 寄存器的原始值，不受只写限制，未登记时为 `None`
+    pub fn peek(&self, offset: u16) -> Option<u16> {
+        self.handler(offset).map(|_| self.values[(offset / 2) as usize])
+    }
+
+    /// 硬件一侧更新寄存器，绕过读写规则
+    pub fn set(&mut self, offset: u16, value: u16) {
+        if let Some(slot) = Self::slot(offset) {
+            self.values[slot] = value;
+        }
+    }
+
+    /// 在IF中置位中断请求
+    pub fn request_interrupt(&mut self, bits: u16) {
+        self.values[(IF / 2) as usize] |= bits & 0x3FFF;
+    }
+
+    /// IME打开时IE与IF同时置位的中断
+    pub fn pending_interrupts(&self) -> u16 {
+        let value = |offset: u16| self.values[(offset / 2) as usize];
+        if value(IME) & 1 == 0 {
+            return 0;
+        }
+        value(IE) & value(IF)
+    }
+}
+
+impl Default for IoRegisters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 登记四个DMA通道的寄存器
+///
+/// DMA传输尚未模拟，这里只提供寄存器的存储，使程序的配置可以读回和转储。
+pub fn register_dma(io: &mut IoRegisters) {
+    for channel in 0..4u16 {
+        let base = 0x0B0 + channel * 12;
+        let last = channel == 3;
+        io.register(base, IoAccess::WriteOnly, 0xFFFF);
+        io.register(base + 2, IoAccess::WriteOnly, if channel == 0 { 0x07FF } else { 0x0FFF });
+        io.register(base + 4, IoAccess::WriteOnly, 0xFFFF);
+        io.register(base + 6, IoAccess::WriteOnly, if last { 0x0FFF } else { 0x07FF });
+        io.register(base + 8, IoAccess::WriteOnly, if last { 0xFFFF } else { 0x3FFF });
+        io.register(base + 10, IoAccess::ReadWrite, if last { 0xFFE0 } else { 0xF7E0 });
+    }
+}
+
+/// 登记四个定时器的寄存器
+///
+/// 定时器尚未计数，TMxCNT_L读出的是最近写入的重载值。
+pub fn register_timers(io: &mut IoRegisters) {
+    for timer in 0..4u16 {
+        let base = 0x100 + timer * 4;
+        io.register(base, IoAccess::ReadWrite, 0xFFFF);
+        io.register(base + 2, IoAccess::ReadWrite, 0x00C7);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_rules_and_byte_lanes() {
+        let mut io = IoRegisters::new();
+        io.register(VCOUNT, IoAccess::ReadOnly, 0);
+        io.register(0x010, IoAccess::WriteOnly, 0x01FF);
+        io.register(DISPSTAT, IoAccess::ReadWrite, 0xFF38);
+        io.set(VCOUNT, 42);
+
+        io.write_16(VCOUNT, 7);
+        assert_eq!((io.read_16(VCOUNT), io.take_write(VCOUNT)), (42, None));
+
+        io.write_16(0x010, 0xFFFF);
+        assert_eq!(io.read_16(0x010), 0);
+        assert_eq!(io.peek(0x010), Some(0x01FF));
+        assert_eq!(io.take_write(0x010), Some(0x01FF));
+        assert_eq!(io.take_write(0x010), None);
+
+        // 标志位由硬件维护，字节写入只影响所在的一半
+        io.set(DISPSTAT, 0x0001);
+        io.write_8(DISPSTAT, 0xFF);
+        assert_eq!(io.read_16(DISPSTAT), 0x0039);
+        io.write_8(DISPSTAT + 1, 0x50);
+        assert_eq!((io.read_8(DISPSTAT), io.read_8(DISPSTAT + 1)), (0x39, 0x50));
+
+        io.write_16(0x300, 0xFFFF);
+        assert_eq!((io.read_16(0x300), io.peek(0x300), io.read_16(0x7FE)), (0, None, 0));
+    }
+
+    #[test]
+    fn test_interrupt_acknowledge_on_write() {
+        let mut io = IoRegisters::new();
+        io.request_interrupt(IRQ_VBLANK | IRQ_VCOUNT | IRQ_KEYPAD);
+        io.write_16(IE, IRQ_VBLANK | IRQ_KEYPAD);
+        assert_eq!(io.pending_interrupts(), 0);
+        io.write_16(IME, 1);
+        assert_eq!(io.pending_interrupts(), IRQ_VBLANK | IRQ_KEYPAD);
+
+        // 写1确认，写0的位保持
+        io.write_16(IF, IRQ_VBLANK);
+        assert_eq!(io.read_16(IF), IRQ_VCOUNT | IRQ_KEYPAD);
+        io.write_8(IF + 1, (IRQ_KEYPAD >> 8) as u8);
+        assert_eq!(io.read_16(IF), IRQ_VCOUNT);
+
+        register_timers(&mut io);
+        register_dma(&mut io);
+        io.write_16(0x102, 0xFFFF);
+        assert_eq!(io.read_16(0x102), 0x00C7);
+        io.write_16(0x0B0, 0x1234);
+        assert_eq!((io.read_16(0x0B0), io.peek(0x0B0)), (0, Some(0x1234)));
+    }
+}
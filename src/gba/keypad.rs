@@ -0,0 +1,103 @@
+//! GBA键盘
+//!
+//! KEYINPUT为低电平有效，按下的键对应位为0。KEYCNT可以在指定的键
+//! 任一（OR模式）或全部（AND模式）按下时请求键盘中断。
+
+use crate::frontend::Button;
+use crate::gba::io_registers::{IoAccess, IoRegisters, IRQ_KEYPAD, KEYCNT, KEYINPUT};
+
+/// 十个按键全部松开时的KEYINPUT
+const RELEASED: u16 = 0x03FF;
+
+/// GBA键盘状态
+#[derive(Debug, Clone, Default)]
+pub struct Keypad {
+    /// 按下的键，位序与KEYINPUT相同
+    pressed: u16,
+}
+
+impl Keypad {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `Button` 的顺序与KEYINPUT的低8位一致，L/R不在前端按键中
+    fn bit(button: Button) -> u16 {
+        let index = Button::ALL.iter().position(|candidate| *candidate == button).unwrap_or(0);
+        1 << index
+    }
+
+    pub fn set_button(&mut self, button: Button, pressed: bool) {
+        if pressed {
+            self.pressed |= Self::bit(button);
+        } else {
+            self.pressed &= !Self::bit(button);
+        }
+    }
+
+    /// 当前的KEYINPUT值
+    pub fn keyinput(&self) -> u16 {
+        !self.pressed & RELEASED
+    }
+
+    /// 把键盘寄存器登记到I/O分派器
+    pub fn register_io(&self, io: &mut IoRegisters) {
+        io.register(KEYINPUT, IoAccess::ReadOnly, 0);
+        io.register(KEYCNT, IoAccess::ReadWrite, 0xC3FF);
+        io.set(KEYINPUT, self.keyinput());
+    }
+
+    /// 刷新KEYINPUT，满足KEYCNT条件时请求键盘中断
+    pub fn sync_io(&self, io: &mut IoRegisters) {
+        io.set(KEYINPUT, self.keyinput());
+        let control = io.peek(KEYCNT).unwrap_or(0);
+        if control & 0x4000 == 0 {
+            return;
+        }
+        let keys = control & RELEASED;
+        let triggered = if control & 0x8000 != 0 {
+            keys != 0 && self.pressed & keys == keys
+        } else {
+            self.pressed & keys != 0
+        };
+        if triggered {
+            io.request_interrupt(IRQ_KEYPAD);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gba::io_registers::IF;
+
+    #[test]
+    fn test_keyinput_active_low_and_keycnt_irq() {
+        let mut io = IoRegisters::new();
+        let mut keypad = Keypad::new();
+        keypad.register_io(&mut io);
+        assert_eq!(io.read_16(KEYINPUT), 0x03FF);
+
+        keypad.set_button(Button::Start, true);
+        keypad.set_button(Button::A, true);
+        keypad.sync_io(&mut io);
+        io.write_16(KEYINPUT, 0);
+        assert_eq!(io.read_16(KEYINPUT), 0x03F6);
+        assert_eq!(io.read_16(IF), 0);
+
+        // AND模式：A+B都按下才触发
+        io.write_16(KEYCNT, 0xC003);
+        keypad.sync_io(&mut io);
+        assert_eq!(io.read_16(IF), 0);
+        keypad.set_button(Button::B, true);
+        keypad.sync_io(&mut io);
+        assert_eq!(io.read_16(IF), IRQ_KEYPAD);
+
+        // OR模式：START即可
+        io.write_16(IF, IRQ_KEYPAD);
+        keypad.set_button(Button::B, false);
+        io.write_16(KEYCNT, 0x4008);
+        keypad.sync_io(&mut io);
+        assert_eq!(io.read_16(IF), IRQ_KEYPAD);
+    }
+}
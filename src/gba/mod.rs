@@ -6,8 +6,22 @@
 mod cpu;
 mod gpu;
 
-use cpu::{ARM7TDMI, GBAMemory};
-use gpu::GBAGPU;
+pub mod io;
+pub mod io_registers;
+pub mod keypad;
+pub mod rom;
+pub mod scanline;
+
+pub use cpu::{ARM7TDMI, GBAMemory};
+pub use io::{IoDump, IoRegisterInfo, IoRegisterValue};
+pub use io_registers::{IoAccess, IoRegisters};
+pub use keypad::Keypad;
+pub use gpu::{AffineParams, BackgroundType, DisplayMode, GBAGPU, SCREEN_HEIGHT, SCREEN_WIDTH};
+pub use rom::{GbaHeader, GbaRomBuilder};
+pub use scanline::{CompositorInputs, ScanlineBreak};
+use crate::emulator::{CycleBudget, CyclesReport};
+use crate::frontend::{Button, Emulator};
+use crate::util::alloc::{self, Subsystem};
 use std::time::Instant;
 
 /// GBA主模拟器
@@ -19,12 +33,20 @@ pub struct GBASystem {
     pub memory: GBAMemory,
     /// 图形处理单元
     pub gpu: GBAGPU,
+    /// 键盘
+    pub keypad: Keypad,
     /// 模拟器状态
     pub state: GBAState,
     /// 性能统计
     pub stats: GBAStats,
     /// 启动时间
     pub start_time: Instant,
+    /// 扫描线断点
+    pub scanline_break: Option<ScanlineBreak>,
+    /// 最近一次扫描线断点记录的合成输入
+    pub compositor_dump: Option<CompositorInputs>,
+    /// `run_cycles` 多跑的周期
+    cycle_budget: CycleBudget,
 }
 
 /// GBA模拟器状态
@@ -36,29 +58,84 @@ pub enum GBAState {
     Error(String),
 }
 
+/// GBA CPU主频 (Hz)，虚拟时钟按它把周期数折算为秒
+pub const CPU_CLOCK_HZ: f64 = 16_777_216.0;
+
 /// GBA性能统计
+///
+/// 计数器在每一步后从各组件同步。速率按虚拟时钟计算：用模拟的周期数折算出
+/// 模拟时间，再除以它，因此结果与主机速度无关，同一段程序每次运行都相同。
+/// 只有 `fps` 和 `execution_time` 使用墙钟。
 #[derive(Debug, Clone, Default)]
 pub struct GBAStats {
     pub total_cycles: u64,
+    pub total_instructions: u64,
     pub total_frames: u32,
+    /// 总线上读写的字节数
+    pub bytes_moved: u64,
+    /// GPU推进过的扫描线数
+    pub scanlines: u64,
+    /// 按墙钟计算的帧率
     pub fps: f64,
-    pub cpu_usage: f64,
-    pub memory_usage: f64,
     pub gpu_usage: f64,
+    /// 墙钟运行时间 (秒)
     pub execution_time: f64,
+    /// 模拟时间 (秒)
+    pub virtual_time: f64,
+    /// 每模拟秒执行的指令数
+    pub instructions_per_second: f64,
+    /// 每模拟秒在总线上读写的字节数
+    pub memory_bandwidth: f64,
+    /// 每条扫描线平均经过的模拟时间 (微秒)
+    pub scanline_time_us: f64,
+}
+
+impl GBAStats {
+    /// 由计数器重新计算按虚拟时钟的速率，还没有执行周期时均为0
+    pub fn update_rates(&mut self) {
+        self.virtual_time = self.total_cycles as f64 / CPU_CLOCK_HZ;
+        if self.total_cycles == 0 {
+            self.instructions_per_second = 0.0;
+            self.memory_bandwidth = 0.0;
+            self.scanline_time_us = 0.0;
+            return;
+        }
+        self.instructions_per_second = self.total_instructions as f64 / self.virtual_time;
+        self.memory_bandwidth = self.bytes_moved as f64 / self.virtual_time;
+        self.scanline_time_us = if self.scanlines == 0 {
+            0.0
+        } else {
+            self.virtual_time * 1_000_000.0 / self.scanlines as f64
+        };
+    }
 }
 
 impl GBASystem {
     /// 创建新的GBA模拟器实例
     pub fn new() -> Self {
-        Self {
+        let mut system = Self {
             cpu: ARM7TDMI::new(),
             memory: GBAMemory::new(),
             gpu: GBAGPU::new(),
+            keypad: Keypad::new(),
             state: GBAState::Stopped,
             stats: GBAStats::default(),
             start_time: Instant::now(),
-        }
+            scanline_break: None,
+            compositor_dump: None,
+            cycle_budget: CycleBudget::new(),
+        };
+        system.register_io();
+        system
+    }
+
+    /// 各子系统把自己的寄存器登记到内存的I/O分派器
+    fn register_io(&mut self) {
+        let io = &mut self.memory.io;
+        self.gpu.register_io(io);
+        self.keypad.register_io(io);
+        io_registers::register_dma(io);
+        io_registers::register_timers(io);
     }
     
     /// 重置模拟器到初始状态
@@ -66,9 +143,13 @@ impl GBASystem {
         self.cpu.reset();
         self.memory = GBAMemory::new();
         self.gpu.reset();
+        self.keypad = Keypad::new();
+        self.register_io();
         self.state = GBAState::Stopped;
         self.stats = GBAStats::default();
         self.start_time = Instant::now();
+        self.compositor_dump = None;
+        self.cycle_budget.clear();
     }
     
     /// 加载ROM文件
@@ -151,24 +232,65 @@ impl GBASystem {
         }
         
         // 执行CPU指令
-        self.cpu.execute_instruction(&mut self.memory)?;
+        {
+            let _scope = alloc::enter(Subsystem::Cpu);
+            self.cpu.execute_instruction(&mut self.memory)?;
+        }
         
         // 更新GPU
-        self.gpu.update();
+        {
+            let _scope = alloc::enter(Subsystem::Gpu);
+            self.gpu.update();
+            self.gpu.sync_io(&mut self.memory.io);
+        }
+        self.keypad.sync_io(&mut self.memory.io);
         
         // 更新统计
         self.update_stats();
         
+        self.check_scanline_break();
+        
         Ok(())
     }
     
-    /// 运行指定数量的周期
-    pub fn run_cycles(&mut self, cycles: u64) -> Result<(), String> {
-        for _ in 0..cycles {
+    /// 在第 `frame` 帧的第 `line` 条扫描线开始时暂停
+    pub fn break_at_scanline(&mut self, frame: u32, line: u16) {
+        self.scanline_break = Some(ScanlineBreak { frame, line });
+    }
+    
+    /// 到达扫描线断点时记录合成输入并暂停
+    fn check_scanline_break(&mut self) {
+        let Some(target) = self.scanline_break else {
+            return;
+        };
+        if self.gpu.frame_count == target.frame && self.gpu.current_scanline == target.line {
+            self.compositor_dump = Some(self.gpu.compositor_inputs(target.line, &self.memory));
+            self.pause();
+        }
+    }
+    
+    /// 执行指定条数的指令
+    pub fn run_steps(&mut self, steps: u64) -> Result<(), String> {
+        for _ in 0..steps {
             self.step()?;
         }
         Ok(())
     }
+
+    /// 运行至少 `cycles` 个CPU周期，多跑的周期从下一次调用中扣除；暂停或停止时提前返回
+    pub fn run_cycles(&mut self, cycles: u64) -> Result<CyclesReport, String> {
+        let mut budget = self.cycle_budget;
+        let report = budget.run(cycles, || {
+            if self.state != GBAState::Running {
+                return Ok(None);
+            }
+            let before = self.cpu.stats.cycles;
+            self.step()?;
+            Ok(Some(self.cpu.stats.cycles - before))
+        });
+        self.cycle_budget = budget;
+        report
+    }
     
     /// 运行到下一帧
     pub fn run_frame(&mut self) -> Result<(), String> {
@@ -183,31 +305,27 @@ impl GBASystem {
     
     /// 更新性能统计
     fn update_stats(&mut self) {
-        let elapsed = self.start_time.elapsed();
-        self.stats.execution_time = elapsed.as_secs_f64();
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        self.stats.execution_time = elapsed;
         
-        // 更新CPU统计
         let cpu_stats = self.cpu.get_stats();
         self.stats.total_cycles = cpu_stats.cycles;
+        self.stats.total_instructions = cpu_stats.instructions;
+        
+        // 内存统计按字节计数
+        let memory_stats = self.memory.get_stats();
+        self.stats.bytes_moved = memory_stats.reads + memory_stats.writes;
         
-        // 更新GPU统计
         let gpu_stats = self.gpu.get_stats();
         self.stats.total_frames = gpu_stats.frames_rendered;
+        self.stats.scanlines = gpu_stats.hblank_count as u64 + gpu_stats.vblank_count as u64;
         
-        // 计算FPS
-        if elapsed.as_secs_f64() > 0.0 {
-            self.stats.fps = gpu_stats.frames_rendered as f64 / elapsed.as_secs_f64();
+        if elapsed > 0.0 {
+            self.stats.fps = gpu_stats.frames_rendered as f64 / elapsed;
+            self.stats.gpu_usage = gpu_stats.pixels_drawn as f64 / elapsed;
         }
         
-        // 计算CPU使用率
-        self.stats.cpu_usage = (cpu_stats.cycles as f64 / elapsed.as_secs_f64()) / 16_777_216.0; // 16.78MHz
-        
-        // 计算内存使用率
-        let memory_stats = self.memory.get_stats();
-        self.stats.memory_usage = (memory_stats.reads + memory_stats.writes) as f64 / elapsed.as_secs_f64();
-        
-        // 计算GPU使用率
-        self.stats.gpu_usage = gpu_stats.pixels_drawn as f64 / elapsed.as_secs_f64();
+        self.stats.update_rates();
     }
     
     /// 获取模拟器状态
@@ -235,6 +353,98 @@ impl GBASystem {
         &self.gpu
     }
     
+    /// 获取可变的内存（加载资源、调试修改）
+    pub fn memory_mut(&mut self) -> &mut GBAMemory {
+        &mut self.memory
+    }
+    
+    /// 获取可变的GPU（设置显示寄存器）
+    pub fn gpu_mut(&mut self) -> &mut GBAGPU {
+        &mut self.gpu
+    }
+    
+    /// 渲染GPU当前扫描线
+    pub fn render_scanline(&mut self) -> Result<(), String> {
+        self.gpu.render_scanline(&mut self.memory)
+    }
+    
+    /// 渲染整帧并返回帧缓冲区 (BGR555)
+    pub fn render_frame(&mut self) -> Result<&[u16], String> {
+        self.gpu.render_frame(&mut self.memory)
+    }
+    
+    /// 读取I/O寄存器当前值，尚未登记的寄存器返回 `None`
+    ///
+    /// GPU寄存器直接取自GPU，只写寄存器也返回最近写入的值。
+    pub fn read_io_register(&self, offset: u16) -> Option<u16> {
+        self.gpu.read_register(offset).or_else(|| self.memory.io.peek(offset))
+    }
+
+    /// 转储所有已模拟的I/O寄存器并解码位域
+    pub fn dump_io_registers(&self) -> IoDump {
+        let registers = io::IO_REGISTERS
+            .iter()
+            .filter_map(|info| self.read_io_register(info.offset).map(|value| IoRegisterValue { info, value }))
+            .collect();
+        IoDump { registers }
+    }
+
+    /// 列出OAM中未禁用的精灵
+    #[cfg(feature = "debug")]
+    pub fn sprites(&self) -> Vec<crate::debug::SpriteEntry> {
+        crate::debug::sprites::gba_sprites(&self.gpu.oam)
+    }
+
+    /// 返回高亮了指定OAM编号精灵的帧缓冲区副本，精灵被禁用时返回 `None`
+    #[cfg(feature = "debug")]
+    pub fn highlight_sprite(&self, index: usize) -> Option<Vec<u16>> {
+        let sprite = self.sprites().into_iter().find(|sprite| sprite.index == index)?;
+        let mut framebuffer = self.gpu.framebuffer.clone();
+        crate::debug::sprites::highlight_bgr555(&mut framebuffer, SCREEN_WIDTH, SCREEN_HEIGHT, &sprite);
+        Some(framebuffer)
+    }
+
+    /// 执行调试命令，返回要显示的结果
+    ///
+    /// 支持的命令：
+    /// - `io` 转储所有I/O寄存器
+    /// - `io <名称>` 查看单个寄存器，例如 `io BG0CNT`
+    /// - `scanline <帧> <行>` 设置扫描线断点，`scanline clear` 清除
+    /// - `compositor [行]` 查看合成输入，默认为当前扫描线
+    /// - `sprites` 列出未禁用的精灵（需要 `debug` feature）
+    pub fn run_debug_command(&mut self, line: &str) -> Result<String, String> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            ["io"] => Ok(self.dump_io_registers().to_string()),
+            ["io", name] => {
+                let info = io::lookup(name).ok_or_else(|| format!("未知的I/O寄存器: {}", name))?;
+                match self.read_io_register(info.offset) {
+                    Some(value) => Ok(IoRegisterValue { info, value }.to_string()),
+                    None => Err(format!("{} 尚未模拟", info.name)),
+                }
+            }
+            ["scanline", "clear"] => {
+                self.scanline_break = None;
+                Ok("扫描线断点已清除".to_string())
+            }
+            ["scanline", frame, scanline] => {
+                let frame = frame.parse().map_err(|_| format!("无效的帧号: {}", frame))?;
+                let scanline = scanline.parse().map_err(|_| format!("无效的扫描线: {}", scanline))?;
+                self.break_at_scanline(frame, scanline);
+                Ok(format!("将在第 {} 帧扫描线 {} 暂停", frame, scanline))
+            }
+            ["compositor"] => Ok(self.gpu.compositor_inputs(self.gpu.current_scanline, &self.memory).to_string()),
+            ["compositor", scanline] => {
+                let scanline: u16 = scanline.parse().map_err(|_| format!("无效的扫描线: {}", scanline))?;
+                Ok(self.gpu.compositor_inputs(scanline, &self.memory).to_string())
+            }
+            #[cfg(feature = "debug")]
+            ["sprites"] => Ok(crate::debug::sprites::sprite_table(&self.sprites())),
+            _ => Err(format!("未知的调试命令: {}", line.trim())),
+        }
+    }
+
     /// 获取调试信息
     pub fn get_debug_info(&self) -> String {
         let cpu_stats = self.cpu.get_stats();
@@ -254,7 +464,9 @@ impl GBASystem {
             GPU帧数: {}\n\
             GPU像素: {}\n\
             FPS: {:.2}\n\
-            CPU使用率: {:.2}%\n\
+            IPS: {:.0} (模拟时间 {:.6}秒)\n\
+            内存带宽: {:.0} 字节/秒\n\
+            扫描线平均: {:.3} 微秒\n\
             =========================",
             self.state,
             self.cpu.get_pc(),
@@ -273,7 +485,10 @@ impl GBASystem {
             gpu_stats.frames_rendered,
             gpu_stats.pixels_drawn,
             self.stats.fps,
-            self.stats.cpu_usage * 100.0
+            self.stats.instructions_per_second,
+            self.stats.virtual_time,
+            self.stats.memory_bandwidth,
+            self.stats.scanline_time_us
         )
     }
 }
@@ -283,3 +498,128 @@ impl Default for GBASystem {
         Self::new()
     }
 }
+
+impl Emulator for GBASystem {
+    fn reset(&mut self) {
+        GBASystem::reset(self);
+    }
+
+    fn load_rom(&mut self, data: &[u8]) -> Result<(), String> {
+        GBASystem::load_rom(self, data.to_vec())
+    }
+
+    fn step(&mut self) -> Result<(), String> {
+        GBASystem::step(self)
+    }
+
+    fn run_frame(&mut self) -> Result<(), String> {
+        GBASystem::run_frame(self)
+    }
+
+    fn run_cycles(&mut self, cycles: u64) -> Result<CyclesReport, String> {
+        GBASystem::run_cycles(self, cycles)
+    }
+
+    fn set_button(&mut self, button: Button, pressed: bool) {
+        self.keypad.set_button(button, pressed);
+        self.keypad.sync_io(&mut self.memory.io);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_rates_use_virtual_clock() {
+        let mut stats = GBAStats {
+            total_cycles: CPU_CLOCK_HZ as u64 / 4,
+            total_instructions: 1_000_000,
+            bytes_moved: 3_000_000,
+            scanlines: 1232,
+            ..GBAStats::default()
+        };
+        stats.update_rates();
+        assert_eq!(stats.virtual_time, 0.25);
+        assert_eq!(stats.instructions_per_second, 4_000_000.0);
+        assert_eq!(stats.memory_bandwidth, 12_000_000.0);
+        assert!((stats.scanline_time_us - 250_000.0 / 1232.0).abs() < 1e-9);
+
+        stats.scanlines = 0;
+        stats.update_rates();
+        assert_eq!(stats.scanline_time_us, 0.0);
+
+        let mut empty = GBAStats::default();
+        empty.update_rates();
+        assert_eq!((empty.virtual_time, empty.instructions_per_second, empty.memory_bandwidth), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_update_stats_syncs_counters() {
+        let mut gba = GBASystem::new();
+        gba.load_rom(GbaRomBuilder::new("STATS").build().unwrap()).unwrap();
+        gba.start().unwrap();
+        gba.run_steps(10).unwrap();
+
+        let stats = gba.get_stats();
+        let cpu = gba.cpu.get_stats();
+        let memory = gba.memory.get_stats();
+        assert_eq!(stats.total_instructions, cpu.instructions);
+        assert_eq!(stats.bytes_moved, memory.reads + memory.writes);
+        assert_eq!(stats.scanlines, 10);
+        assert_eq!(stats.virtual_time, cpu.cycles as f64 / CPU_CLOCK_HZ);
+        assert!(stats.instructions_per_second > 0.0 && stats.memory_bandwidth > 0.0);
+    }
+
+    #[test]
+    fn test_run_cycles_carries_debt_and_stops_when_paused() {
+        let mut gba = GBASystem::new();
+        gba.load_rom(GbaRomBuilder::new("CYCLES").build().unwrap()).unwrap();
+        gba.start().unwrap();
+
+        let mut total = 0;
+        for call in 1..=4 {
+            let report = gba.run_cycles(25).unwrap();
+            assert!(!report.stopped && report.instructions > 0);
+            total += report.executed;
+            assert_eq!(total, 25 * call + report.debt);
+        }
+        assert_eq!(gba.cpu.stats.cycles, total);
+
+        gba.pause();
+        let report = gba.run_cycles(25).unwrap();
+        assert!(report.stopped);
+        assert_eq!((report.instructions, report.debt), (0, 0));
+    }
+
+    #[test]
+    fn test_io_writes_reach_subsystems_through_memory() {
+        let mut gba = GBASystem::new();
+        gba.load_rom(GbaRomBuilder::new("IO").build().unwrap()).unwrap();
+        gba.start().unwrap();
+
+        // CPU写入DISPCNT、BG0HOFS和VBlank中断使能，下一步后GPU生效
+        gba.memory.write_16(0x0400_0000, 0x0403).unwrap();
+        gba.memory.write_16(0x0400_0010, 0x0034).unwrap();
+        gba.memory.write_16(0x0400_0004, 0x0008).unwrap();
+        gba.step().unwrap();
+        assert_eq!(gba.gpu.dispcnt, 0x0403);
+        assert_eq!(gba.gpu.bgofs[0], 0x0034);
+        assert_eq!(gba.memory.read_16(0x0400_0010).unwrap(), 0);
+
+        gba.memory.write_16(0x0400_0006, 0x00FF).unwrap();
+        gba.run_steps(142).unwrap();
+        assert_eq!(gba.memory.read_16(0x0400_0006).unwrap(), 143);
+        assert_eq!(gba.memory.read_16(0x0400_0004).unwrap() & 1, 0);
+        gba.step().unwrap();
+        assert_eq!(gba.memory.read_16(0x0400_0004).unwrap(), 0x0009);
+        assert_eq!(gba.memory.read_16(0x0400_0202).unwrap(), io_registers::IRQ_VBLANK);
+        gba.memory.write_16(0x0400_0202, io_registers::IRQ_VBLANK).unwrap();
+        assert_eq!(gba.memory.read_16(0x0400_0202).unwrap(), 0);
+
+        Emulator::set_button(&mut gba, Button::Down, true);
+        assert_eq!(gba.memory.read_16(0x0400_0130).unwrap(), 0x037F);
+        gba.reset();
+        assert_eq!(gba.memory.read_16(0x0400_0130).unwrap(), 0x03FF);
+    }
+}
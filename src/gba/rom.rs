@@ -0,0 +1,199 @@
+//! GBA ROM构建器
+//!
+//! 生成带有合法头部的GBA卡带镜像：入口跳转、标题、游戏代码、
+//! 固定值0x96和头部补码校验和，程序和资源按偏移放置。
+//! Nintendo Logo只由BIOS校验，这里不模拟BIOS，Logo区域保持为0。
+
+/// 程序入口偏移（头部之后）
+pub const ENTRY_OFFSET: u32 = 0xC0;
+/// ROM在地址空间中的起始地址
+pub const ROM_BASE: u32 = 0x08000000;
+/// 最大ROM大小 (32MB)
+pub const MAX_ROM_SIZE: usize = 0x0200_0000;
+/// GBASystem接受的最小ROM大小
+const MIN_ROM_SIZE: usize = 0x200;
+
+/// 计算头部补码校验和 (0xA0-0xBC)
+pub fn header_checksum(rom: &[u8]) -> u8 {
+    rom[0xA0..=0xBC]
+        .iter()
+        .fold(0u8, |checksum, &byte| checksum.wrapping_sub(byte))
+        .wrapping_sub(0x19)
+}
+
+/// 解析出的GBA ROM头部 (0x00-0xBF)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GbaHeader {
+    /// 入口处的ARM指令
+    pub entry_instruction: u32,
+    pub title: String,
+    pub game_code: String,
+    pub maker_code: String,
+    /// 固定值，必须为0x96
+    pub fixed_value: u8,
+    pub unit_code: u8,
+    pub device_type: u8,
+    pub version: u8,
+    /// 头部中记录的补码校验和
+    pub complement_check: u8,
+    /// 按头部内容重新计算的校验和
+    pub expected_check: u8,
+}
+
+impl GbaHeader {
+    /// 解析头部，不做校验
+    pub fn parse(rom: &[u8]) -> Result<Self, String> {
+        if rom.len() < ENTRY_OFFSET as usize {
+            return Err(format!("GBA ROM太小: {} 字节", rom.len()));
+        }
+        let text = |range: std::ops::Range<usize>| {
+            let bytes = &rom[range];
+            let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+            String::from_utf8_lossy(&bytes[..len]).into_owned()
+        };
+        Ok(Self {
+            entry_instruction: u32::from_le_bytes([rom[0], rom[1], rom[2], rom[3]]),
+            title: text(0xA0..0xAC),
+            game_code: text(0xAC..0xB0),
+            maker_code: text(0xB0..0xB2),
+            fixed_value: rom[0xB2],
+            unit_code: rom[0xB3],
+            device_type: rom[0xB4],
+            version: rom[0xBC],
+            complement_check: rom[0xBD],
+            expected_check: header_checksum(rom),
+        })
+    }
+
+    /// 入口是否为ARM无条件跳转指令
+    pub fn entry_is_branch(&self) -> bool {
+        self.entry_instruction >> 24 == 0xEA
+    }
+
+    /// 补码校验和是否正确
+    pub fn checksum_valid(&self) -> bool {
+        self.complement_check == self.expected_check
+    }
+}
+
+/// GBA ROM构建器
+#[derive(Debug, Clone)]
+pub struct GbaRomBuilder {
+    title: String,
+    game_code: String,
+    maker_code: String,
+    version: u8,
+    sections: Vec<(u32, Vec<u8>)>,
+}
+
+impl GbaRomBuilder {
+    /// 创建构建器，标题最多12个ASCII字符
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            game_code: "AGLE".to_string(),
+            maker_code: "00".to_string(),
+            version: 0,
+            sections: Vec::new(),
+        }
+    }
+
+    /// 设置4字符游戏代码
+    pub fn game_code(mut self, code: &str) -> Self {
+        self.game_code = code.to_string();
+        self
+    }
+
+    /// 设置2字符制造商代码
+    pub fn maker_code(mut self, code: &str) -> Self {
+        self.maker_code = code.to_string();
+        self
+    }
+
+    /// 设置软件版本
+    pub fn version(mut self, version: u8) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// 在ROM偏移 `offset` 处放置数据（程序或资源）
+    pub fn section(mut self, offset: u32, data: &[u8]) -> Self {
+        self.sections.push((offset, data.to_vec()));
+        self
+    }
+
+    /// 校验并生成ROM镜像
+    pub fn build(&self) -> Result<Vec<u8>, String> {
+        if !self.title.is_ascii() || self.title.len() > 12 {
+            return Err(format!("GBA标题必须是最多12个ASCII字符: {:?}", self.title));
+        }
+        if !self.game_code.is_ascii() || self.game_code.len() != 4 {
+            return Err(format!("游戏代码必须是4个ASCII字符: {:?}", self.game_code));
+        }
+        if !self.maker_code.is_ascii() || self.maker_code.len() != 2 {
+            return Err(format!("制造商代码必须是2个ASCII字符: {:?}", self.maker_code));
+        }
+
+        let mut size = MIN_ROM_SIZE;
+        for (i, (offset, data)) in self.sections.iter().enumerate() {
+            let (start, end) = (*offset as usize, *offset as usize + data.len());
+            if start < ENTRY_OFFSET as usize {
+                return Err(format!("数据段 0x{:X} 覆盖了ROM头部", start));
+            }
+            if end > MAX_ROM_SIZE {
+                return Err(format!("数据段 0x{:X}-0x{:X} 超出32MB", start, end));
+            }
+            if let Some((other, _)) = self.sections[..i]
+                .iter()
+                .find(|(other, other_data)| start < *other as usize + other_data.len() && (*other as usize) < end)
+            {
+                return Err(format!("数据段 0x{:X} 与 0x{:X} 重叠", start, other));
+            }
+            size = size.max(end);
+        }
+
+        // 大小按4字节对齐
+        let mut rom = vec![0u8; size.div_ceil(4) * 4];
+        let branch = 0xEA00_0000u32 | ((ENTRY_OFFSET - 8) / 4);
+        rom[0x00..0x04].copy_from_slice(&branch.to_le_bytes());
+        rom[0xA0..0xA0 + self.title.len()].copy_from_slice(self.title.as_bytes());
+        rom[0xAC..0xB0].copy_from_slice(self.game_code.as_bytes());
+        rom[0xB0..0xB2].copy_from_slice(self.maker_code.as_bytes());
+        rom[0xB2] = 0x96;
+        rom[0xBC] = self.version;
+        rom[0xBD] = header_checksum(&rom);
+
+        for (offset, data) in &self.sections {
+            rom[*offset as usize..*offset as usize + data.len()].copy_from_slice(data);
+        }
+        Ok(rom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_header_and_sections() {
+        let rom = GbaRomBuilder::new("AFFINE")
+            .section(ENTRY_OFFSET, &[0xFE, 0xFF, 0xFF, 0xEA])
+            .section(0x1000, &[1, 2, 3])
+            .build()
+            .unwrap();
+
+        assert_eq!(&rom[0..4], &[0x2E, 0x00, 0x00, 0xEA]);
+        assert_eq!(&rom[0xA0..0xA6], b"AFFINE");
+        assert_eq!(rom[0xB2], 0x96);
+        assert_eq!(rom[0xBD], header_checksum(&rom));
+        assert_eq!(&rom[0x1000..0x1003], &[1, 2, 3]);
+        assert_eq!(rom.len(), 0x1004);
+
+        let header = GbaHeader::parse(&rom).unwrap();
+        assert_eq!((header.title.as_str(), header.game_code.as_str()), ("AFFINE", "AGLE"));
+        assert!(header.entry_is_branch() && header.checksum_valid());
+
+        assert!(GbaRomBuilder::new("X").section(0x80, &[0]).build().is_err());
+        assert!(GbaRomBuilder::new("X").section(0x200, &[0; 8]).section(0x204, &[0]).build().is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! 扫描线调试
+//!
+//! 在指定帧的指定扫描线暂停模拟，并记录合成这一行时用到的输入：
+//! 启用的背景层及其滚动/仿射参数、落在这一行的精灵、窗口使能位。
+//! 排查"某一行画错了"时，比较出错行和相邻正常行的输入通常就能定位原因。
+
+use std::fmt;
+
+use super::gpu::{AffineParams, BackgroundType, DisplayMode};
+
+/// 扫描线断点
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanlineBreak {
+    /// GPU帧计数
+    pub frame: u32,
+    pub line: u16,
+}
+
+/// 一个背景层的合成输入
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackgroundInput {
+    pub index: usize,
+    pub kind: BackgroundType,
+    pub priority: u8,
+    pub char_base: u32,
+    pub screen_base: u32,
+    pub hofs: u16,
+    pub vofs: u16,
+    /// 仿射背景的变换参数
+    pub affine: Option<AffineParams>,
+}
+
+/// 落在扫描线上的精灵
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpriteInput {
+    pub index: usize,
+    pub x: u16,
+    pub y: u16,
+    pub height: u16,
+    pub tile: u16,
+    pub palette: u8,
+    pub priority: u8,
+}
+
+/// DISPCNT中的窗口使能位（窗口范围寄存器尚未模拟）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WindowState {
+    pub win0: bool,
+    pub win1: bool,
+    pub obj_window: bool,
+}
+
+/// 合成一条扫描线的全部输入
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositorInputs {
+    pub frame: u32,
+    pub line: u16,
+    pub mode: DisplayMode,
+    /// 背景色（调色板0号）
+    pub backdrop: u16,
+    /// 已启用的背景层
+    pub backgrounds: Vec<BackgroundInput>,
+    pub obj_enabled: bool,
+    pub sprites: Vec<SpriteInput>,
+    pub windows: WindowState,
+}
+
+impl fmt::Display for CompositorInputs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "帧 {} 扫描线 {}  {:?}  背景色 0x{:04X}", self.frame, self.line, self.mode, self.backdrop)?;
+        if self.backgrounds.is_empty() {
+            writeln!(f, "背景层: 无")?;
+        }
+        for bg in &self.backgrounds {
+            write!(
+                f,
+                "BG{} {:?} 优先级{} 字符 0x{:08X} 图块 0x{:08X}",
+                bg.index, bg.kind, bg.priority, bg.char_base, bg.screen_base
+            )?;
+            match bg.affine {
+                Some(p) => writeln!(f, " pa={} pb={} pc={} pd={} X={} Y={}", p.pa, p.pb, p.pc, p.pd, p.x, p.y)?,
+                None => writeln!(f, " 滚动 ({}, {})", bg.hofs, bg.vofs)?,
+            }
+        }
+        writeln!(f, "精灵: {} 个{}", self.sprites.len(), if self.obj_enabled { "" } else { " (OBJ层已关闭)" })?;
+        for sprite in &self.sprites {
+            writeln!(
+                f,
+                "  #{:<3} ({}, {}) 高{} 瓦片{} 调色板{} 优先级{}",
+                sprite.index, sprite.x, sprite.y, sprite.height, sprite.tile, sprite.palette, sprite.priority
+            )?;
+        }
+        write!(
+            f,
+            "窗口: WIN0={} WIN1={} OBJWIN={}",
+            self.windows.win0 as u8, self.windows.win1 as u8, self.windows.obj_window as u8
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gba::{GBAState, GBASystem, GbaRomBuilder};
+
+    #[test]
+    fn test_break_at_scanline_dumps_compositor_inputs() {
+        let mut gba = GBASystem::new();
+        gba.load_rom(GbaRomBuilder::new("SCANLINE").build().unwrap()).unwrap();
+        gba.start().unwrap();
+        gba.gpu.dispcnt = 0x1101; // 模式1 + BG0 + OBJ
+        gba.gpu.bgofs[0] = 0x0804;
+        gba.gpu.oam[0] = 40; // 精灵0: y=40, 8像素高
+        gba.gpu.oam[1] = 100;
+        gba.gpu.oam[4] = 120; // 精灵1不在第42行
+
+        gba.break_at_scanline(1, 42);
+        gba.run_frame().unwrap();
+        gba.run_frame().unwrap();
+        assert_eq!(gba.state, GBAState::Paused);
+        assert_eq!((gba.gpu.frame_count, gba.gpu.current_scanline), (1, 42));
+
+        let dump = gba.compositor_dump.clone().unwrap();
+        assert_eq!(dump.backgrounds.len(), 1);
+        assert_eq!((dump.backgrounds[0].hofs, dump.backgrounds[0].vofs), (4, 8));
+        assert!(dump.sprites.iter().any(|s| s.index == 0 && s.x == 100));
+        assert!(dump.sprites.iter().all(|s| s.index != 1));
+        assert!(dump.to_string().starts_with("帧 1 扫描线 42"));
+    }
+}
@@ -0,0 +1,671 @@
+//! 消息目录
+//!
+//! 每条消息一行：变体名、中文、英文。两种语言的 `{}` 占位符数量必须一致，
+//! 参数按顺序替换，数字的精度等格式由调用方预先处理。
+
+use super::Locale;
+
+macro_rules! catalog {
+    ($($name:ident => $zh:expr, $en:expr;)*) => {
+        /// 用户可见的消息
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Msg {
+            $($name,)*
+        }
+
+        impl Msg {
+            /// 全部消息
+            pub const ALL: &'static [Msg] = &[$(Msg::$name,)*];
+
+            /// 指定语言下的文本
+            pub fn text(self, locale: Locale) -> &'static str {
+                match (self, locale) {
+                    $(
+                        (Msg::$name, Locale::Zh) => $zh,
+                        (Msg::$name, Locale::En) => $en,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+catalog! {
+    // 通用
+    ErrorPrefix => "错误: {}", "error: {}";
+    UnknownLocale => "未知的语言: {} (可用: zh, en)", "unknown locale: {} (available: zh, en)";
+    Yes => "是", "yes";
+    No => "否", "no";
+
+    // 熵源
+    EntropyInsufficient => "熵不足", "Insufficient entropy";
+    EntropySourceUnavailable => "熵源不可用: {}", "Entropy source unavailable: {}";
+    EntropyDistribution => "分布错误: {}", "Distribution error: {}";
+    EntropyQuantum => "量子处理错误: {}", "Quantum processing error: {}";
+    RomPrngCore => "核心在第 {} 条指令出错: {}", "core failed at instruction {}: {}";
+    RomPrngEntry => "入口程序没有以种子状态到达 {}: {}", "entry code did not reach {} in the seeded state: {}";
+    RomPrngMismatch => "第 {} 条指令 ({}) 后与参考模型不一致\n  预期 {}\n  实际 {}", "state differs from the reference model after instruction {} ({})\n  expected {}\n  actual   {}";
+
+    // 配置
+    ConfigFileRead => "无法读取配置文件: {}", "Failed to read config file: {}";
+    ConfigParse => "配置解析错误: {}", "Config parse error: {}";
+    ConfigMissingKey => "缺少必需的配置键: {}", "Missing required config key: {}";
+    ConfigInvalidValue => "配置键 {} 的值 `{}` 无效（来自{}）: {}", "Invalid value for config key {} `{}` (from {}): {}";
+    SourceDefault => "默认值", "default";
+    SourceFile => "文件 {}:{}", "file {}:{}";
+    SourceEnv => "环境变量 {}", "env {}";
+    SourceCli => "命令行", "command line";
+    TypeBool => "布尔", "boolean";
+    TypeInteger => "整数 {}..={}", "integer {}..={}";
+    TypeFloat => "数值 {}..={}", "number {}..={}";
+    TypeText => "文本", "text";
+    ExpectBool => "应为布尔值 true 或 false，实际为 `{}`", "expected boolean true or false, found `{}`";
+    ExpectInteger => "应为整数，实际为 `{}`", "expected an integer, found `{}`";
+    IntegerOutOfRange => "整数 {} 超出范围 {}..={}", "integer {} out of range {}..={}";
+    ExpectFloat => "应为数值，实际为 `{}`", "expected a number, found `{}`";
+    FloatOutOfRange => "数值 {} 超出范围 {}..={}", "number {} out of range {}..={}";
+    ExpectChoice => "应为 {} 之一，实际为 `{}`", "expected one of {}, found `{}`";
+    DiagnosticLine => "第{}行: {}\n  | {}", "line {}: {}\n  | {}";
+    DiagMissingEquals => "缺少 `=`，应为 `键 = 值`", "missing `=`, expected `key = value`";
+    DiagMissingKey => "缺少配置键", "missing key";
+    DiagDuplicate => "重复的配置键 `{}`，第{}行已设置，以后者为准", "duplicate key `{}` already set on line {}, the later value wins";
+    DiagUnknownKeySuggest => "未知的配置键 `{}`，是否是 `{}`?", "unknown key `{}`, did you mean `{}`?";
+    DiagUnknownKey => "未知的配置键 `{}`", "unknown key `{}`";
+    DefaultConfigHeader => "# gamelife 默认配置\n# 格式: 键 = 值，以 # 开头的行为注释\n", "# gamelife default configuration\n# Format: key = value, lines starting with # are comments\n";
+    KeyCpuFrequency => "CPU频率 (Hz)", "CPU frequency (Hz)";
+    KeyScreenWidth => "屏幕宽度 (像素)", "Screen width (pixels)";
+    KeyScreenHeight => "屏幕高度 (像素)", "Screen height (pixels)";
+    KeyMemorySize => "内存大小 (字节)", "Memory size (bytes)";
+    KeyEntropyPoolSize => "熵池大小 (字节)", "Entropy pool size (bytes)";
+    KeyQuantumStatesCount => "量子状态数量", "Number of quantum states";
+    KeyDistributionQualityThreshold => "分布优化器的最低质量评分", "Minimum quality score of the distribution optimizer";
+    KeyEntropyConditioning => "熵输出的调理方式", "Conditioning applied to entropy output";
+    KeyQuantumStage => "量子抗性流水线是否启用 {} 阶段", "Whether the quantum-resistant pipeline runs the {} stage";
+    KeyDebugMode => "启用调试模式", "Enable debug mode";
+    KeyLogLevel => "日志级别", "Log level";
+    KeyLocale => "界面语言", "Interface language";
+    KeyGamepadBindings => "手柄键位绑定，如 south=a,east=b,rb=quick_save，留空使用默认绑定", "Gamepad bindings such as south=a,east=b,rb=quick_save, empty for the defaults";
+    KeyGamepadDeadzone => "手柄摇杆死区", "Gamepad stick deadzone";
+    KeyFrameSync => "帧率控制方式，audio按音频队列填充程度，没有音频输出时退回timer", "Frame pacing: audio follows the audio queue fill level and falls back to timer without audio output";
+    KeyRomPath => "默认ROM路径", "Default ROM path";
+    KeySavePath => "存档目录", "Save directory";
+
+    // 命令行
+    CliUsage => "用法: gamelife [全局选项] <子命令> [选项]
+
+子命令:
+  tournament    AI配置在井字棋、四子棋和俄罗斯方块中循环对战
+  entropy bench 熵源吞吐量与质量基准测试
+  entropy throughput 分布优化器串行、并行与流式吞吐量对比
+  entropy report 熵源状态报告
+  life          生成初始图样运行生命游戏，可导出GIF动画
+  run           无界面运行ROM，--watch 在重新汇编后自动重新加载
+  rom info      显示GB/GBA ROM头部、校验结果和SHA-1
+  rom verify    批量运行ROM目录并生成兼容性报告
+  rom slots     列出或删除ROM的存档槽位
+  config check  检查配置文件中的未知键和类型错误
+  config dump-default 输出带注释的默认配置
+  config show   显示生效的配置及每个值的来源
+  dev opcodes   从CPU解码表生成指令集文档
+  selftest      运行内置测试ROM、指令性质测试、熵源健康检测和确定性模拟
+  help          显示帮助信息
+
+全局选项:
+  -q, --quiet   不输出进度信息（适用于CI）
+  --json        以JSON格式向标准输出写入结果
+  --lang LANG   界面语言 zh|en (默认读取 GAMEBOY_LOCALE 或 LANG)", "usage: gamelife [global options] <command> [options]
+
+commands:
+  tournament    round-robin AI configurations across tic-tac-toe, connect four and tetris
+  entropy bench entropy source throughput and quality benchmark
+  entropy throughput compare serial, parallel and streaming optimizer throughput
+  entropy report entropy source status report
+  life          generate a starting pattern and run Life, optionally exporting a GIF
+  run           run a ROM headless, --watch reloads it after every rebuild
+  rom info      show GB/GBA ROM header, checks and SHA-1
+  rom verify    run every ROM in a directory and write a compatibility report
+  rom slots     list or delete the ROM's savestate slots
+  config check  check a config file for unknown keys and type errors
+  config dump-default print the default config with comments
+  config show   show the effective config and where each value came from
+  dev opcodes   generate the instruction set docs from the CPU decode table
+  selftest      run the built-in test ROMs, instruction property tests, entropy health tests and deterministic simulations
+  help          show this help
+
+global options:
+  -q, --quiet   no progress output (for CI)
+  --json        write results to stdout as JSON
+  --lang LANG   interface language zh|en (defaults to GAMEBOY_LOCALE or LANG)";
+    CliUnknownCommand => "未知的子命令: {}\n\n{}", "unknown command: {}\n\n{}";
+    CliUnknownSubcommand => "未知的{}子命令: {}\n\n{}", "unknown {} subcommand: {}\n\n{}";
+    CliLangMissing => "选项 --lang 缺少参数值", "option --lang requires a value";
+    CliReadFailed => "无法读取 {}: {}", "cannot read {}: {}";
+    CliWriteFailed => "无法写入 {}: {}", "cannot write {}: {}";
+    CliUnknownFormat => "未知的输出格式: {}", "unknown output format: {}";
+    ArgsMissingValue => "选项 --{} 缺少参数值", "option --{} requires a value";
+    ArgsInvalidValue => "选项 --{} 的值无效: {}", "invalid value for --{}: {}";
+    ArgsUnknownOption => "未知选项: --{}", "unknown option: --{}";
+
+    // 进度报告
+    ProgressDone => "完成", "done";
+    ProgressFinished => "{} {} ({}，用时 {}秒)", "{} {} ({}, {}s)";
+    ProgressRemaining => "剩余", "eta";
+    DurationHours => "{}时{}分", "{}h{}m";
+    DurationMinutes => "{}分{}秒", "{}m{}s";
+    DurationSeconds => "{}秒", "{}s";
+
+    // 内存分配统计
+    AllocOther => "其他", "other";
+    AllocCpu => "CPU", "CPU";
+    AllocGpu => "GPU", "GPU";
+    AllocGames => "游戏", "games";
+    AllocEntropy => "熵源", "entropy";
+    AllocReportTitle => "内存分配 ({} 帧平均):", "allocations (average over {} frames):";
+    AllocReportChecksTitle => "内存分配 ({} 项自检平均):", "allocations (average over {} checks):";
+    AllocReportLine => "  {} {} 次 {} 字节", "  {} {} allocs {} bytes";
+    AllocReportDisabled => "  (未开启 alloc-stats feature，没有统计数据)", "  (alloc-stats feature is off, no data)";
+
+    // 存档槽位
+    SlotInvalid => "无效的槽位: {} (可用: 0-{})", "invalid slot: {} (available: 0-{})";
+    SlotDeleteFailed => "无法删除存档 {}: {}", "failed to delete savestate {}: {}";
+    SlotUnsupported => "该模拟器核心不支持存档", "this emulator core does not support savestates";
+    SlotOtherRom => "槽位 {} 属于另一个ROM: {} (校验和 {}，当前 {})", "slot {} belongs to another ROM: {} (checksum {}, current {})";
+    SlotNothingToUndo => "没有可以撤销的读档", "no load to undo";
+    SlotSaved => "已保存到槽位 {}", "saved to slot {}";
+    SlotSavedTo => "已保存到槽位 {} ({})", "saved to slot {} ({})";
+    SlotLoaded => "已读取槽位 {}", "loaded slot {}";
+    SlotLoadedAt => "已读取槽位 {} (保存于 {})", "loaded slot {} (saved {})";
+    SlotSelected => "当前槽位: {}", "current slot: {}";
+    SlotUndone => "已撤销读档", "load undone";
+    SlotEmpty => "没有存档", "no savestates";
+    SlotEntry => "{}{} {} 游戏时长 {} 保存于 {}", "{}{} {} played {} saved {}";
+    SlotDeleted => "槽位 {} 已删除", "slot {} deleted";
+    SlotUnknownCommand => "未知的槽位命令: {}", "unknown slot command: {}";
+    SlotNotConfigured => "没有设置存档槽位", "no save slots configured";
+
+    // 金手指
+    CheatInvalidGameshark => "无效的GameShark代码: {}", "invalid GameShark code: {}";
+    CheatUnsupportedGameshark => "不支持的GameShark代码类型: {}", "unsupported GameShark code type: {}";
+    CheatFileRead => "无法读取金手指文件 {}: {}", "cannot read cheat file {}: {}";
+    CheatFileLine => "第{}行格式错误: {}", "malformed line {}: {}";
+    CheatFileHeader => "# 冻结地址: 地址 值 on|off [标签]", "# frozen addresses: address value on|off [label]";
+    CheatFileWrite => "无法写入金手指文件 {}: {}", "cannot write cheat file {}: {}";
+    CheatNone => "没有冻结地址", "no frozen addresses";
+    CheatOn => "开", "on";
+    CheatOff => "关", "off";
+    CheatInvalidAddress => "无效的地址: {}", "invalid address: {}";
+    CheatInvalidValue => "无效的值: {}", "invalid value: {}";
+    CheatCleared => "所有冻结地址已清除", "all frozen addresses cleared";
+    CheatUnknownMode => "未知的冻结模式: {}", "unknown freeze mode: {}";
+    CheatModeSet => "冻结模式: {}", "freeze mode: {}";
+    CheatEnabled => "0x{} 冻结启用", "0x{} freeze enabled";
+    CheatDisabled => "0x{} 冻结停用", "0x{} freeze disabled";
+    CheatNotFrozen => "地址 0x{} 未冻结", "address 0x{} is not frozen";
+    CheatFrozen => "0x{} 已冻结为 0x{}", "0x{} frozen to 0x{}";
+    CheatUnfrozen => "0x{} 已解除冻结", "0x{} unfrozen";
+    CheatGamesharkAdded => "GameShark代码 {} 已添加", "GameShark code {} added";
+    CheatSaved => "金手指已保存到 {}", "cheats saved to {}";
+    CheatLoaded => "已从 {} 加载 {} 个冻结地址", "{}: loaded {} frozen addresses";
+    CheatUnknownCommand => "未知的金手指命令: {}", "unknown cheat command: {}";
+
+    // gamelife config
+    ConfigUsage => "用法: gamelife config <check|dump-default|show> [选项]
+
+check PATH     按配置模式检查配置文件，报告未知的键、类型错误和格式错误
+dump-default   输出带注释的默认配置（--json 时输出完整模式）
+show [PATH]    显示最终生效的配置及每个值的来源
+
+show 选项:
+  --set KEY=VALUE[,KEY=VALUE]  以命令行优先级覆盖配置
+
+优先级: 命令行 > GAMEBOY_* 环境变量 > 配置文件 > 默认值", "usage: gamelife config <check|dump-default|show> [options]
+
+check PATH     check a config file against the schema, reporting unknown keys, type errors and malformed lines
+dump-default   print the default config with comments (the full schema with --json)
+show [PATH]    show the effective config and where each value came from
+
+show options:
+  --set KEY=VALUE[,KEY=VALUE]  override values with command-line precedence
+
+precedence: command line > GAMEBOY_* environment > config file > defaults";
+    ConfigNeedFile => "需要一个配置文件\n\n{}", "expected a config file\n\n{}";
+    ConfigTooManyFiles => "最多一个配置文件\n\n{}", "at most one config file\n\n{}";
+    ConfigNoProblems => "{}: 没有发现问题\n", "{}: no problems found\n";
+    ConfigProblems => "{}: 发现 {} 个问题", "{}: {} problem(s) found";
+    ConfigSetFormat => "--set 的格式应为 KEY=VALUE: {}", "--set expects KEY=VALUE: {}";
+
+    // gamelife entropy
+    EntropyUsage => "用法: gamelife entropy <bench|throughput|report|rom-prng> [选项]
+
+bench 选项:
+  --rounds N    生成轮数 (默认64)
+  --size BYTES  每轮生成的字节数 (默认4096)
+
+throughput 选项:
+  --size BYTES   待优化的数据量 (默认1048576)
+  --chunk BYTES  并行与流式优化的块大小 (默认65536)
+  --threads N    并行线程数 (默认CPU核数)
+
+report 选项:
+  --conditioning optimizer|standard  熵输出的调理方式 (默认optimizer)
+
+rom-prng 选项 (在模拟CPU上运行生成的伪随机数发生器ROM，并与宿主端参考模型逐条指令比较):
+  --seed N      16位种子 (默认1)
+  --bytes N     生成的字节数 (默认4096)
+  --rom PATH    同时把生成的ROM写入文件", "usage: gamelife entropy <bench|throughput|report|rom-prng> [options]
+
+bench options:
+  --rounds N    number of rounds (default 64)
+  --size BYTES  bytes generated per round (default 4096)
+
+throughput options:
+  --size BYTES   amount of data to optimize (default 1048576)
+  --chunk BYTES  chunk size for parallel and streaming optimization (default 65536)
+  --threads N    parallel worker threads (default: CPU count)
+
+report options:
+  --conditioning optimizer|standard  conditioning applied to entropy output (default optimizer)
+
+rom-prng options (run a generated PRNG ROM on the emulated CPU and compare it with a host-side reference model, instruction by instruction):
+  --seed N      16-bit seed (default 1)
+  --bytes N     number of bytes to generate (default 4096)
+  --rom PATH    also write the generated ROM to a file";
+    EntropyBenchProgress => "熵源基准", "entropy bench";
+    EntropyBenchSummary => "轮数: {}\n生成字节: {}\n用时: {}秒\n吞吐量: {} KB/s\n平均质量: {}\n", "rounds: {}\nbytes generated: {}\nelapsed: {}s\nthroughput: {} KB/s\naverage quality: {}\n";
+    ThroughputProgress => "优化器基准", "optimizer bench";
+    ThroughputChunkZero => "--chunk 必须大于0", "--chunk must be greater than 0";
+    ThroughputHeader => "数据量: {} 字节\n块大小: {} 字节\n线程数: {}\n", "data: {} bytes\nchunk size: {} bytes\nthreads: {}\n";
+    ThroughputSerial => "串行", "serial";
+    ThroughputParallel => "并行", "parallel";
+    ThroughputStream => "流式", "stream";
+    ThroughputLine => "{}: {}秒 {} MB/s\n", "{}: {}s {} MB/s\n";
+    ThroughputSpeedup => "并行加速比: {}\n", "parallel speedup: {}\n";
+    EntropyReportSummary => "熵源数量: {}\n池大小: {} 字节\n分布质量: {}\n优化周期: {}\n熵密度: {}\n量子强度: {}\n处理耗时: {} ns\n熵放大系数: {}\n调理方式: {}\n统计检验: {}\n", "entropy sources: {}\npool size: {} bytes\ndistribution quality: {}\noptimization cycles: {}\nentropy density: {}\nquantum strength: {}\nprocessing time: {} ns\nentropy amplification: {}\nconditioning: {}\nstatistical tests: {}\n";
+    RomPrngProgress => "交叉验证", "cross-check";
+    RomPrngSummary => "种子: {}\n生成字节: {}\n执行指令: {}\n机器周期: {}\n两个核心与参考模型一致\n前16字节: {}\n统计检验: {}\n", "seed: {}\nbytes generated: {}\ninstructions executed: {}\nmachine cycles: {}\nboth cores match the reference model\nfirst 16 bytes: {}\nstatistical tests: {}\n";
+
+    // gamelife life
+    LifeUsage => "用法: gamelife life [选项]
+
+用熵源生成初始图样，无界面运行生命游戏并输出种群统计。
+
+选项:
+  --width N         网格宽度 (默认40)
+  --height N        网格高度 (默认20)
+  --generations N   演化代数 (默认200)
+  --pattern KIND    初始图样 soup|methuselah (默认soup)
+  --density P       随机汤的活细胞比例 (默认0.3)
+  --symmetry SYM    随机汤的对称方式 none|horizontal|vertical|both|rotate2|rotate4 (默认none)
+  --survive N       只使用能存活N代的候选图样 (默认0)
+  --topology TOPO   网格边界 bounded|torus|cylinder (默认bounded)
+  --gif PATH        导出GIF动画
+  --every N         GIF中每隔N代一帧 (默认1)
+  --cell-size N     GIF中每个细胞的像素边长 (默认4)
+  --delay CS        GIF每帧延时，单位1/100秒 (默认10)
+  --no-annotate     GIF中不标注代数和活细胞数", "usage: gamelife life [options]
+
+Generate a starting pattern from the entropy sources, run Life headless and print population statistics.
+
+options:
+  --width N         grid width (default 40)
+  --height N        grid height (default 20)
+  --generations N   generations to run (default 200)
+  --pattern KIND    starting pattern soup|methuselah (default soup)
+  --density P       live cell ratio of a soup (default 0.3)
+  --symmetry SYM    soup symmetry none|horizontal|vertical|both|rotate2|rotate4 (default none)
+  --survive N       only use candidates that survive N generations (default 0)
+  --topology TOPO   grid edges bounded|torus|cylinder (default bounded)
+  --gif PATH        export a GIF animation
+  --every N         one GIF frame every N generations (default 1)
+  --cell-size N     GIF pixels per cell edge (default 4)
+  --delay CS        GIF frame delay in 1/100 s (default 10)
+  --no-annotate     do not label generation and population in the GIF";
+    LifeZeroSize => "网格尺寸不能为0", "grid size must not be 0";
+    LifeNoSurvivor => "{} 个候选图样都没能存活 {} 代", "none of {} candidates survived {} generations";
+    LifeProgress => "生命游戏", "life";
+    LifeSummary => "网格: {}x{}\n代数: {}\n初始种群: {}\n最终种群: {}\n最大种群: {}\n", "grid: {}x{}\ngenerations: {}\ninitial population: {}\nfinal population: {}\npeak population: {}\n";
+    LifeCycle => "循环: 周期{}，第{}代进入\n", "cycle: period {}, entered at generation {}\n";
+    LifeNoCycle => "循环: 未检测到\n", "cycle: none detected\n";
+
+    // gamelife rom
+    RomUsage => "用法: gamelife rom info <文件>
+      gamelife rom verify <目录> [选项]
+      gamelife rom slots <文件> [--delete N]
+
+info    显示ROM头部字段、校验结果、映射器、bank数量和SHA-1
+verify  逐个运行目录中的ROM并生成兼容性报告
+slots   列出ROM的存档槽位 (0-9)，--delete 删除一个槽位
+
+verify选项:
+  --frames N        每个ROM运行的帧数 (默认60)
+  --stall-frames N  PC和工作RAM连续N帧不变视为卡死 (默认30)
+  --timeout SECS    每个ROM的时间预算 (默认10)
+  --format FORMAT   输出格式 markdown|json (默认markdown)
+  --output PATH     写入文件而不是标准输出", "usage: gamelife rom info <file>
+       gamelife rom verify <dir> [options]
+       gamelife rom slots <file> [--delete N]
+
+info    show ROM header fields, checks, mapper, bank count and SHA-1
+verify  run every ROM in a directory and write a compatibility report
+slots   list the ROM's savestate slots (0-9), --delete removes one
+
+verify options:
+  --frames N        frames to run per ROM (default 60)
+  --stall-frames N  treat N frames with unchanged PC and work RAM as a hang (default 30)
+  --timeout SECS    time budget per ROM (default 10)
+  --format FORMAT   output format markdown|json (default markdown)
+  --output PATH     write to a file instead of stdout";
+    RomNeedFile => "需要一个ROM文件\n\n{}", "expected a ROM file\n\n{}";
+    RomNeedDir => "需要一个ROM目录\n\n{}", "expected a ROM directory\n\n{}";
+    RomVerifyProgress => "ROM验证", "ROM verify";
+    RomSlotsEmpty => "没有存档", "no savestates";
+    RomSlotLine => "槽位 {}: {}  游戏时长 {}  保存于 {} UTC", "slot {}: {}  played {}  saved {} UTC";
+    RomSlotBroken => "槽位 {}: {}", "slot {}: {}";
+    RomSlotDeleted => "槽位 {} 已删除", "slot {} deleted";
+    RomReadError => "无法读取: {}", "cannot read: {}";
+    RomPlatform => "平台: {}", "Platform: {}";
+    RomTitle => "标题: {}", "Title: {}";
+    RomSize => "大小: {} 字节", "Size: {} bytes";
+    RomChecks => "校验:", "Checks:";
+    RomCgbOnly => "仅CGB", "CGB only";
+    RomCgbCompatible => "兼容CGB", "CGB compatible";
+    RomManufacturerCode => "制造商代码", "Manufacturer code";
+    RomCgbMode => "CGB模式", "CGB mode";
+    RomSgbSupport => "SGB支持", "SGB support";
+    RomCartridgeType => "卡带类型", "Cartridge type";
+    RomMapper => "映射器", "Mapper";
+    RomBanks => "ROM bank", "ROM banks";
+    RomUnknownCode => "未知 (0x{})", "unknown (0x{})";
+    RomExternalRam => "外部RAM", "External RAM";
+    RomDestination => "目标市场", "Destination";
+    RomJapan => "日本", "Japan";
+    RomOverseas => "海外", "Overseas";
+    RomLicensee => "许可证代码", "Licensee code";
+    RomVersion => "版本", "Version";
+    RomHeaderChecksum => "头部校验和", "Header checksum";
+    RomGlobalChecksum => "全局校验和", "Global checksum";
+    RomStoredComputed => "记录 0x{}, 计算 0x{}", "stored 0x{}, computed 0x{}";
+    RomFileSize => "文件大小", "File size";
+    RomSizeMismatch => "头部声明 {} 字节, 实际 {} 字节", "header declares {} bytes, file has {} bytes";
+    RomInvalidSizeCode => "头部ROM大小代码无效", "invalid ROM size code in header";
+    RomGameCode => "游戏代码", "Game code";
+    RomUnitCode => "主机代码", "Unit code";
+    RomDeviceType => "设备类型", "Device type";
+    RomEntryPoint => "入口跳转", "Entry branch";
+    RomFixedValue => "固定值0x96", "Fixed value 0x96";
+    CompatUnknownOpcode => "PC=0x{} 未知指令 0x{}", "PC=0x{} unknown opcode 0x{}";
+    CompatHang => "第 {} 帧 PC=0x{} {}", "frame {} PC=0x{} {}";
+    CompatStalled => "PC和工作RAM没有变化", "PC and work RAM did not change";
+    CompatTimeout => "超出时间预算", "time budget exceeded";
+    CompatReportTitle => "# ROM兼容性报告", "# ROM compatibility report";
+    CompatReportSummary => "每个ROM运行 {} 帧，共 {} 个ROM", "{} frames per ROM, {} ROMs";
+    CompatReportCounts => "| 结果 | 数量 |", "| Outcome | Count |";
+    CompatReportColumns => "| 文件 | 平台 | 标题 | 帧数 | 结果 | 详情 |", "| File | Platform | Title | Frames | Outcome | Detail |";
+
+    // gamelife run
+    RunUsage => "用法: gamelife run [<ROM文件>] [选项]
+
+无界面运行ROM，未指定文件时使用配置 rom_path。
+
+选项:
+  --watch          监视ROM和符号文件，变化后自动重新加载，出错时等待下一次修改
+  --sym FILE       符号文件 (默认读取ROM同名的 .sym)
+  --mark LABEL     标记点：符号名或十六进制地址；第一次运行到这里时保存快照，之后每次重新加载都从这里继续
+  --frames N       运行的帧数 (默认60，--watch 时不限)
+  --fast           --watch 时不限制帧率
+  --opcode-stats FILE  统计每个操作码的执行次数和周期，退出时写入CSV文件", "usage: gamelife run [<rom>] [options]
+
+Run a ROM headless, using the rom_path config value when no file is given.
+
+options:
+  --watch          watch the ROM and symbol file, reload after changes and wait for the next change on errors
+  --sym FILE       symbol file (defaults to the .sym next to the ROM)
+  --mark LABEL     marked point, a symbol or hex address; a savestate is taken the first time it is reached and restored after every reload
+  --frames N       frames to run (default 60, unlimited with --watch)
+  --fast           do not limit the frame rate with --watch
+  --opcode-stats FILE  count executions and cycles per opcode and write them as CSV on exit";
+    RunMissingRom => "缺少ROM文件参数，也没有配置 rom_path", "no ROM file given and rom_path is not configured";
+    RunLoaded => "已加载 {} ({} 字节，{} 个符号)", "loaded {} ({} bytes, {} symbols)";
+    RunReloaded => "已重新加载 {} (第 {} 次，{} 字节，{} 个符号)", "reloaded {} (load {}, {} bytes, {} symbols)";
+    RunRestored => "已恢复标记 {} 处的快照 (PC={})", "restored the savestate at mark {} (PC={})";
+    RunMarkCaptured => "运行到标记 {} (PC={})，已保存快照", "reached mark {} (PC={}), savestate taken";
+    RunLoadFailed => "加载失败: {}", "load failed: {}";
+    RunCrashed => "PC={} 处出错: {}", "error at PC={}: {}";
+    RunWatching => "正在监视 {}，按 Ctrl+C 退出", "watching {}, press Ctrl+C to quit";
+    RunWaiting => "等待 {} 修改后重新加载...", "waiting for {} to change...";
+    RunOpcodeStatsWritten => "已写入 {} 个操作码的统计到 {}", "wrote statistics for {} opcodes to {}";
+    RunNoMark => "没有设置标记点", "no mark is set";
+    RunSummary => "运行帧数: {}\nPC: {}\n标记快照: {}\n", "frames: {}\nPC: {}\nmark savestate: {}\n";
+
+    // gamelife dev
+    DevUsage => "用法: gamelife dev opcodes [选项]
+
+opcodes  从CPU使用的解码表生成操作码矩阵（助记符、长度、周期、标志位）
+
+opcodes选项:
+  --format FORMAT   输出格式 markdown|html (默认markdown，--json 时输出JSON)
+  --output PATH     写入文件而不是标准输出", "usage: gamelife dev opcodes [options]
+
+opcodes  generate the opcode matrix (mnemonic, size, cycles, flags) from the decode table the CPU uses
+
+opcodes options:
+  --format FORMAT   output format markdown|html (default markdown, JSON with --json)
+  --output PATH     write to a file instead of stdout";
+    DevOpcodesWritten => "已写入 {} 个操作码到 {}", "wrote {} opcodes to {}";
+
+    // gamelife selftest
+    SelftestUsage => "用法: gamelife selftest [选项]
+
+运行内置生成的测试ROM、指令性质测试、已知答案向量、熵源健康检测和确定性模拟，打印通过/失败矩阵。
+任何一项失败时以非零状态退出。
+
+选项:
+  --group LIST  只运行指定类别，逗号分隔 rom,cpu,vectors,entropy,simulation (默认全部)", "usage: gamelife selftest [options]
+
+Run the built-in generated test ROMs, instruction property tests, known-answer vectors, entropy health tests and deterministic simulations, and print a pass/fail matrix.
+Exits with a non-zero status when any check fails.
+
+options:
+  --group LIST  only run these groups, comma separated rom,cpu,vectors,entropy,simulation (default all)";
+    SelftestProgress => "自检", "selftest";
+    SelftestUnknownGroup => "未知的自检类别: {} (可用: {})", "unknown selftest group: {} (available: {})";
+    SelftestGroupRom => "ROM", "ROM";
+    SelftestGroupCpu => "指令", "CPU";
+    SelftestGroupVectors => "向量", "vectors";
+    SelftestGroupEntropy => "熵源", "entropy";
+    SelftestGroupSimulation => "模拟", "simulation";
+    SelftestPass => "通过", "pass";
+    SelftestFail => "失败", "FAIL";
+    SelftestSummary => "{} 项通过，{} 项失败，用时 {} 毫秒\n", "{} passed, {} failed in {} ms\n";
+    SelftestFailed => "{} 项自检失败: {}", "{} selftest checks failed: {}";
+    SelftestMismatch => "预期 {}，实际 {}", "expected {}, got {}";
+    SelftestCoresDiffer => "参考核心与优化核心结果不同: {} / {}", "reference and optimized cores differ: {} / {}";
+    SelftestInvalidHeader => "生成的ROM头部校验失败", "generated ROM fails its header checks";
+    SelftestStepFailed => "第 {} 条指令 (PC={}) 出错: {}", "instruction {} (PC={}) failed: {}";
+    SelftestNoSnapshot => "核心不支持快照", "core does not support snapshots";
+    SelftestSourceUnavailable => "熵源 {} 不可用", "entropy source {} is unavailable";
+    SelftestSourceStuck => "熵源 {} 连续两次输出相同", "entropy source {} returned the same output twice";
+    SelftestRepeated => "同一字节连续重复 {} 次", "the same byte repeated {} times in a row";
+    SelftestNondeterministic => "相同种子的两次运行结果不同: {} / {}", "two runs with the same seed differ: {} / {}";
+    SelftestPanicked => "检查过程中发生panic: {}", "check panicked: {}";
+
+    // gamelife tournament
+    TournamentUsage => "用法: gamelife tournament [选项]
+
+选项:
+  --games LIST      参赛项目，逗号分隔 (ttt,c4,tetris，默认全部)
+  --agents LIST     AI配置，逗号分隔 (random, depth:N, time:MS)
+                    默认 random,depth:2,depth:4,depth:6；time:MS 的结果不可复现
+  --rounds N        每对AI在每种先后手下的对局数 (默认1)
+  --tetris-runs N   俄罗斯方块每个AI的挑战次数 (默认2)
+  --tetris-pieces N 俄罗斯方块每局最大方块数 (默认200)
+  --threads N       并行线程数 (默认CPU核数)
+  --seed N          随机种子 (默认1)
+  --format FORMAT   输出格式 markdown|csv (默认markdown)
+  --output PATH     写入文件而不是标准输出", "usage: gamelife tournament [options]
+
+options:
+  --games LIST      games to play, comma separated (ttt,c4,tetris, default all)
+  --agents LIST     AI configurations, comma separated (random, depth:N, time:MS)
+                    default random,depth:2,depth:4,depth:6; time:MS results are not reproducible
+  --rounds N        games per pairing and seat order (default 1)
+  --tetris-runs N   tetris attempts per AI (default 2)
+  --tetris-pieces N maximum pieces per tetris game (default 200)
+  --threads N       parallel worker threads (default: CPU count)
+  --seed N          random seed (default 1)
+  --format FORMAT   output format markdown|csv (default markdown)
+  --output PATH     write to a file instead of stdout";
+    TournamentProgress => "锦标赛", "tournament";
+    TournamentUnknownGame => "未知的游戏: {} (可用: ttt, c4, tetris)", "unknown game: {} (available: ttt, c4, tetris)";
+    TournamentNoAgents => "至少需要一个参赛者", "at least one agent is required";
+    TournamentNoGames => "至少需要一个游戏项目", "at least one game is required";
+    TournamentInvalidDepth => "无效的搜索深度: {}", "invalid search depth: {}";
+    TournamentInvalidTime => "无效的时间预算: {}", "invalid time budget: {}";
+    TournamentUnknownAgent => "无法识别的AI配置: {} (可用: random, depth:N, time:MS)", "unrecognized AI configuration: {} (available: random, depth:N, time:MS)";
+    TournamentStandingsHeader => "| 名次 | AI | 对局 | 胜 | 平 | 负 | 积分 |", "| Rank | AI | Played | Won | Drawn | Lost | Points |";
+    TournamentScoreAttackTitle => "## tetris (得分挑战)", "## tetris (score attack)";
+    TournamentScoreAttackHeader => "| 名次 | AI | 局数 | 最高分 | 平均分 | 平均消行 |", "| Rank | AI | Runs | Best score | Average score | Average lines |";
+    TournamentSummary => "共 {} 局，用时 {}秒", "{} games in {}s";
+
+    // 生命游戏模拟器
+    LifeSimGeneration => "🔄 第{}代生命游戏 | 活细胞: {} | 熵值: {}", "🔄 Life generation {} | live cells: {} | entropy: {}";
+    LifeSimNoPattern => "❓ 没有找到可以放下的图样: {}", "❓ no pattern fits the grid: {}";
+    LifeSimAddPattern => "🎯 添加模式: {} 在位置 ({}, {})", "🎯 added pattern: {} at ({}, {})";
+    LifeSimStart => "🌱 全新的生命游戏开始！\n使用外部熵源优化概率空间分布\n网格大小: {}x{}\n最大代数: {}\n", "🌱 A new game of Life begins!\nProbability space optimized with external entropy sources\ngrid size: {}x{}\nmax generations: {}\n";
+    LifeSimCycle => "🔒 系统在第{}代进入周期为{}的循环", "🔒 entered a cycle at generation {} with period {}";
+    LifeSimStats => "📊 统计信息:\n  总代数: {}\n  当前种群: {}\n  最大种群: {}\n  最小种群: {}\n  平均熵值: {}\n  运行时间: {}秒", "📊 statistics:\n  generations: {}\n  population: {}\n  max population: {}\n  min population: {}\n  average entropy: {}\n  elapsed: {}s";
+    LifeSimEntropyStats => "🔬 熵源统计:\n  熵源数量: {}\n  池大小: {} 字节\n  分布质量: {}\n  量子强度: {}", "🔬 entropy sources:\n  sources: {}\n  pool size: {} bytes\n  distribution quality: {}\n  quantum strength: {}";
+    LifeSimPrompt => "按回车键继续，或输入 'q' 退出，'p [名称]' 添加模式，'l' 列出图样库...", "Press Enter to continue, 'q' to quit, 'p [name]' to add a pattern, 'l' to list the library...";
+    LifeSimQuit => "👋 游戏结束！", "👋 Game over!";
+    LifeSimFinished => "🎉 生命游戏模拟完成！", "🎉 Life simulation finished!";
+    LifeSimFinalStats => "📈 最终统计:\n  总代数: {}\n  最大种群: {}\n  最小种群: {}\n  平均熵值: {}", "📈 final statistics:\n  generations: {}\n  max population: {}\n  min population: {}\n  average entropy: {}";
+    LifeSimFinalCycle => "  循环: 周期{}，前周期{}代", "  cycle: period {}, pre-period {} generations";
+    LifeSimTiming => "  总运行时间: {}秒\n  平均每代时间: {}毫秒", "  total time: {}s\n  average per generation: {}ms";
+    LifeSimEntropySystem => "🔬 熵源系统统计:\n  熵源数量: {}\n  池大小: {} 字节\n  分布质量: {}\n  量子强度: {}\n  处理时间: {} 纳秒\n  熵放大: {}", "🔬 entropy system:\n  sources: {}\n  pool size: {} bytes\n  distribution quality: {}\n  quantum strength: {}\n  processing time: {} ns\n  entropy amplification: {}";
+    LifeSimLibrary => "🎯 模式库:", "🎯 pattern library:";
+
+    // 井字棋
+    TttTitle => "🎮 井字棋游戏", "🎮 Tic-Tac-Toe";
+    TttCurrentPlayer => "当前玩家: {}", "current player: {}";
+    TttPlaying => "游戏进行中...", "game in progress...";
+    TttWin => "🎉 玩家 {} 获胜！", "🎉 player {} wins!";
+    TttDraw => "🤝 平局！", "🤝 draw!";
+    TttOutOfRange => "位置超出范围", "position out of range";
+    TttOccupied => "该位置已被占用", "position already taken";
+    TttGameOver => "游戏已结束", "game is over";
+    TttHeatmap => "🧠 AI候选走法评估 (W=必胜 L=必败，数字为剩余步数/局面分):", "🧠 AI move evaluation (W=forced win L=forced loss, number is plies left/position score):";
+    TttHeatmapCandidate => "   {}. ({}, {}) 分值 {}", "   {}. ({}, {}) score {}";
+    TttWelcome => "🎮 欢迎来到井字棋游戏！\n选择难度:\n1. 简单 (随机移动)\n2. 中等 (简单策略)\n3. 困难 (高级AI)", "🎮 Welcome to Tic-Tac-Toe!\nchoose a difficulty:\n1. easy (random moves)\n2. medium (simple strategy)\n3. hard (advanced AI)";
+    TttDifficultySet => "✅ 难度设置为: {}", "✅ difficulty set to: {}";
+    TttAskHeatmap => "显示AI思考热力图? (y/N):", "show the AI heatmap? (y/N):";
+    TttAskMove => "请输入位置 (行 列，例如: 1 1):", "enter a position (row column, e.g. 1 1):";
+    TttNeedTwoNumbers => "❌ 请输入两个数字，用空格分隔", "❌ enter two numbers separated by a space";
+    TttInvalidRow => "无效的行", "invalid row";
+    TttInvalidColumn => "无效的列", "invalid column";
+    TttMoveOk => "✅ 移动成功", "✅ move accepted";
+    TttAiThinking => "🤖 AI正在思考...", "🤖 AI is thinking...";
+    TttAiMove => "🤖 AI选择了位置 ({}, {})", "🤖 AI chose ({}, {})";
+    TttAiSearch => "   搜索深度: {} | 节点数: {} | 用时: {}ms{}", "   depth: {} | nodes: {} | time: {}ms{}";
+    TttAiTimedOut => " (达到时间预算)", " (time budget reached)";
+    TttAiNoMove => "❌ AI没有可走的位置", "❌ AI has no legal move";
+    TttPlayerWins => "🎉 恭喜！你赢了！", "🎉 Congratulations, you win!";
+    TttAiWins => "🤖 AI获胜！", "🤖 AI wins!";
+    TttPlayAgain => "是否再玩一局？(y/n)", "play again? (y/n)";
+    TttMenu => "\n🎮 游戏系统主菜单\n==============================\n1. 玩井字棋\n2. 查看生命游戏\n3. 运行所有生命游戏\n4. 运行特定生命游戏\n5. 查看统计信息\n6. 查看熵源信息\n0. 退出\n==============================", "\n🎮 Main menu\n==============================\n1. play Tic-Tac-Toe\n2. list Life games\n3. run all Life games\n4. run one Life game\n5. show statistics\n6. show entropy sources\n0. quit\n==============================";
+    TttAskGameNumber => "请输入游戏编号:", "enter a game number:";
+    TttInvalidNumber => "无效编号", "invalid number";
+    TttEntropyInfo => "🔬 熵源系统信息:", "🔬 entropy system:";
+    TttBye => "👋 再见！", "👋 Bye!";
+    TttInvalidChoice => "❌ 无效选择", "❌ invalid choice";
+    TttStats => "\n📊 游戏统计信息\n==============================\n井字棋游戏:\n  总游戏数: {}\n  玩家获胜: {}\n  AI获胜: {}\n  平局: {}\n  总移动数: {}", "\n📊 statistics\n==============================\nTic-Tac-Toe:\n  games: {}\n  player wins: {}\n  AI wins: {}\n  draws: {}\n  moves: {}";
+    TttWinRate => "  玩家胜率: {}%", "  player win rate: {}%";
+    TttLifeGames => "\n生命游戏:", "\nLife games:";
+    TttUptime => "\n系统运行时间: {}秒", "\nuptime: {}s";
+    TttBanner => "🎮 Tic-Tac-Toe 井字棋游戏系统\n集成所有生命游戏的活力运行\n==================================================", "🎮 Tic-Tac-Toe game system\nwith every Life game built in\n==================================================";
+    TttReady => "🌟 系统初始化完成！\n🔬 熵源系统已激活\n🎮 所有生命游戏已准备就绪\n", "🌟 system ready!\n🔬 entropy sources active\n🎮 all Life games ready\n";
+
+    // 井字棋中的生命游戏管理
+    LifeGameNew => "全新的生命游戏", "New Game of Life";
+    LifeGameNewDescription => "基于外部熵源的细胞自动机模拟", "cellular automaton driven by external entropy sources";
+    LifeGameSweet => "甜甜的生命游戏", "Sweet Game of Life";
+    LifeGameSweetDescription => "凸优化版本的生命游戏", "Game of Life with convex optimization";
+    LifeGameOptimized => "优化的生命游戏", "Optimized Game of Life";
+    LifeGameOptimizedDescription => "性能优化版本的生命游戏", "performance-tuned Game of Life";
+    LifeGamesTitle => "🎮 可用的生命游戏:\n==================================================", "🎮 available Life games:\n==================================================";
+    LifeGameRunning => "🟢 运行中", "🟢 running";
+    LifeGameIdle => "⚪ 未运行", "⚪ idle";
+    LifeGameEntry => "{}. {} - {}\n   描述: {}", "{}. {} - {}\n   description: {}";
+    LifeGameLastRun => "   最后运行: {}秒前", "   last run: {}s ago";
+    LifeGameInvalidIndex => "无效的游戏索引", "invalid game index";
+    LifeGameLaunching => "🚀 启动 {}...", "🚀 starting {}...";
+    LifeGameBuilding => "⚠️  可执行文件不存在，尝试编译...", "⚠️  executable missing, building it...";
+    LifeGameBuildFailed => "编译失败: {}", "build failed: {}";
+    LifeGameRunFailed => "运行失败: {}", "run failed: {}";
+    LifeGameFinished => "✅ {} 运行完成", "✅ {} finished";
+    LifeGameStdout => "输出:\n{}", "output:\n{}";
+    LifeGameStderr => "错误:\n{}", "errors:\n{}";
+    LifeGameRunAll => "🌟 启动所有生命游戏，让它们充满活力！\n==================================================", "🌟 starting every Life game!\n==================================================";
+    LifeGameStarted => "✅ {} 成功启动", "✅ {} started";
+    LifeGameStartFailed => "❌ {} 启动失败: {}", "❌ {} failed to start: {}";
+    LifeGameAllStarted => "\n🎉 所有生命游戏已启动！", "\n🎉 all Life games started!";
+    LifeGameEntropyStats => "熵源数量: {}, 池大小: {} 字节, 分布质量: {}, 量子强度: {}", "sources: {}, pool size: {} bytes, distribution quality: {}, quantum strength: {}";
+
+    // 俄罗斯方块
+    TetrisStatsPanel => "游戏统计", "Stats";
+    TetrisScore => "分数:", "Score:";
+    TetrisLevel => "等级:", "Level:";
+    TetrisLines => "行数:", "Lines:";
+    TetrisPieces => "方块数:", "Pieces:";
+    TetrisGbaPanel => "GBA统计", "GBA";
+    TetrisFrames => "帧数:", "Frames:";
+    TetrisStatePanel => "游戏状态", "State";
+    TetrisStatePlaying => "游戏中", "playing";
+    TetrisStatePaused => "暂停中", "paused";
+    TetrisStateGameOver => "游戏结束", "game over";
+    TetrisStateMenu => "主菜单", "menu";
+    TetrisControlsPanel => "控制说明", "Controls";
+    TetrisControls => "A/D: 左右移动\nS: 快速下降\nW: 旋转方块\n空格: 硬降落\nP: 暂停游戏\nR: 重新开始\nQ: 退出游戏", "A/D: move\nS: soft drop\nW: rotate\nSpace: drop\nP: pause\nR: restart\nQ: quit";
+    TetrisRomLoadFailed => "警告: 无法加载GBA ROM: {}", "warning: failed to load the GBA ROM: {}";
+    TetrisGbaError => "GBA模拟器错误: {}", "GBA emulator error: {}";
+    TetrisWelcome => "🎮 Windows俄罗斯方块 - 基于GBA模拟器\n==================================================\n\n欢迎来到俄罗斯方块游戏！\n本游戏基于我们开发的GBA模拟器实现\n\n游戏特色：\n  ✅ 完整的俄罗斯方块游戏逻辑\n  ✅ 基于GBA模拟器底层支持\n  ✅ 实时性能统计\n  ✅ 幽灵方块预览\n  ✅ 完整的UI界面\n\n按任意键开始游戏...", "🎮 Windows Tetris - built on the GBA emulator\n==================================================\n\nWelcome to Tetris!\nThis game runs on our own GBA emulator\n\nFeatures:\n  ✅ complete Tetris rules\n  ✅ backed by the GBA emulator\n  ✅ live performance stats\n  ✅ ghost piece preview\n  ✅ full UI\n\nPress Enter to start...";
+    TetrisGameOver => "🎮 游戏结束！\n==================================================\n\n最终统计：\n  分数: {}\n  等级: {}\n  清除行数: {}\n  Tetris次数: {}\n  总方块数: {}\n  游戏时间: {}秒\n\n感谢游玩！\n", "🎮 Game over!\n==================================================\n\nfinal statistics:\n  score: {}\n  level: {}\n  lines cleared: {}\n  tetrises: {}\n  pieces: {}\n  play time: {}s\n\nThanks for playing!\n";
+    TetrisLaunch => "🎮 启动Windows俄罗斯方块游戏...\n基于GBA模拟器实现\n", "🎮 Starting Windows Tetris...\nbuilt on the GBA emulator\n";
+    TetrisWindowsDetected => "✅ Windows系统检测通过", "✅ running on Windows";
+    TetrisNotWindows => "⚠️  非Windows系统，但游戏仍可运行", "⚠️  not running on Windows, but the game still works";
+    TetrisExited => "游戏已退出，感谢游玩！", "Game exited, thanks for playing!";
+
+    // 量子俄罗斯方块
+    QuantumStatsPanel => "量子统计", "Quantum";
+    QuantumScore => "量子分数:", "Score:";
+    QuantumEntanglements => "纠缠次数:", "Entangled:";
+    QuantumSuperpositions => "叠加事件:", "Superpos.:";
+    QuantumTunnels => "隧道事件:", "Tunnels:";
+    QuantumObservations => "观察交互:", "Observed:";
+    QuantumCoherence => "量子相干:", "Coherence:";
+    QuantumFieldPanel => "量子场", "Field";
+    QuantumFieldStrength => "场强度:", "Strength:";
+    QuantumDistortion => "时空扭曲:", "Distortion:";
+    QuantumObserver => "观察者:", "Observer:";
+    QuantumControlsPanel => "量子控制", "Controls";
+    QuantumControls => "O: 观察位置\nE: 创建纠缠\nT: 量子隧道\nS: 叠加态\nQ: 退出游戏", "O: observe\nE: entangle\nT: tunnel\nS: superpose\nQ: quit";
+    QuantumWelcome => "🌌 量子纠缠俄罗斯方块 - 革命性创新版本\n==================================================\n\n欢迎来到量子世界！这是世界上第一个量子俄罗斯方块游戏\n\n🎯 革命性特性：\n  ✅ 量子纠缠机制 - 多个方块同时操作\n  ✅ 时空扭曲 - 方块可以穿越时间\n  ✅ 概率叠加 - 方块同时存在于多个状态\n  ✅ 量子隧道 - 方块可以穿过障碍物\n  ✅ 观察者效应 - 你的观察影响游戏状态\n\n按任意键进入量子世界...", "🌌 Quantum Entanglement Tetris\n==================================================\n\nWelcome to the quantum world! The first quantum Tetris ever made\n\n🎯 Features:\n  ✅ entanglement - control several pieces at once\n  ✅ spacetime distortion - pieces travel through time\n  ✅ superposition - pieces exist in several states at once\n  ✅ tunneling - pieces pass through obstacles\n  ✅ observer effect - watching changes the game\n\nPress Enter to enter the quantum world...";
+    QuantumGameOver => "🌌 量子游戏结束！\n==================================================\n\n量子统计：\n  量子分数: {}\n  纠缠次数: {}\n  叠加事件: {}\n  隧道事件: {}\n  观察交互: {}\n  量子相干性: {}\n  游戏时间: {}秒\n\n感谢体验量子世界！\n", "🌌 Quantum game over!\n==================================================\n\nquantum statistics:\n  quantum score: {}\n  entanglements: {}\n  superposition events: {}\n  tunneling events: {}\n  observer interactions: {}\n  quantum coherence: {}\n  play time: {}s\n\nThanks for visiting the quantum world!\n";
+    QuantumLaunch => "🌌 启动量子纠缠俄罗斯方块游戏...\n革命性创新版本\n", "🌌 Starting Quantum Entanglement Tetris...\n";
+    QuantumExited => "量子游戏已退出，感谢体验！", "Quantum game exited, thanks for playing!";
+
+    // 甜甜的生命游戏
+    SweetLifeTitle => "🍭 甜甜的生命游戏 - 凸优化版本\n=====================================", "🍭 Sweet Game of Life - convex optimization\n=====================================";
+    SweetLifeOptimizedTitle => "🍭 甜甜的生命游戏 - 凸优化增强版\n=====================================", "🍭 Sweet Game of Life - enhanced convex optimization\n=====================================";
+    SweetLifeStart => "🚀 开始甜甜的生命游戏...\n", "🚀 Starting the Sweet Game of Life...\n";
+    SweetLifeOptimizedStart => "🚀 开始甜甜的生命游戏（凸优化版）...\n", "🚀 Starting the Sweet Game of Life (optimized)...\n";
+    SweetLifeGeneration => "🍭 第{}代生命: PC=0x{}", "🍭 generation {}: PC=0x{}";
+    SweetLifeStats => "   💖 生命统计: 周期={}, 指令={}, 效率={}%", "   💖 stats: cycles={}, instructions={}, efficiency={}%";
+    SweetLifeRegisters => "   🧬 生命状态: A={}, B={}, C={}, D={}, E={}, H={}, L={}", "   🧬 registers: A={}, B={}, C={}, D={}, E={}, H={}, L={}";
+    SweetLifeFlags => "   🎯 生命标志: Z={}, N={}, H={}, C={}", "   🎯 flags: Z={}, N={}, H={}, C={}";
+    SweetLifeError => "❌ 生命演化错误: {}", "❌ evolution error: {}";
+    SweetLifeGenerating => "🍭 生成甜甜的生命游戏ROM...", "🍭 Generating the Sweet Game of Life ROM...";
+    SweetLifeOptimizedGenerating => "🍭 生成甜甜的生命游戏ROM（凸优化版）...", "🍭 Generating the Sweet Game of Life ROM (optimized)...";
+    SweetLifeRomSaved => "✅ 甜甜的ROM文件生成成功: {}", "✅ ROM written: {}";
+    SweetLifeFinished => "\n🎉 甜甜的生命游戏完成！", "\n🎉 Sweet Game of Life finished!";
+    SweetLifePerformance => "📊 性能统计:\n   ⏱️  总执行时间: {}ms\n   🔄 总周期数: {}\n   📝 总指令数: {}\n   ⚡ 周期/秒: {}\n   🚀 指令/秒: {}\n   🎯 平均每指令周期: {}\n   💾 缓存命中率: {}%\n   🧬 生命代数: {}", "📊 performance:\n   ⏱️  total time: {}ms\n   🔄 cycles: {}\n   📝 instructions: {}\n   ⚡ cycles/s: {}\n   🚀 instructions/s: {}\n   🎯 cycles per instruction: {}\n   💾 cache hit rate: {}%\n   🧬 generations: {}";
+}
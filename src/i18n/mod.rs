@@ -0,0 +1,134 @@
+//! 用户可见文本的国际化
+//!
+//! 消息目录见 `catalog`，每条消息同时给出中文和英文，用 `{}` 作占位符。
+//! 当前语言是进程级的全局设置，默认中文；命令行的 `--lang` 优先于
+//! `GAMEBOY_LOCALE` 环境变量和配置文件中的 `locale`，两者都没有时参考 `LANG`。
+//! `crate::error` 中的库错误类型不经过目录，`Display` 固定为英文。
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::config::{keys, Config};
+
+pub mod catalog;
+
+pub use catalog::Msg;
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::Zh, Locale::En];
+
+    /// 从名称解析，接受 `zh`、`en` 以及 `zh_CN.UTF-8` 这样的区域名
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let language = name.trim().split(['_', '-', '.']).next().unwrap_or("").to_ascii_lowercase();
+        match language.as_str() {
+            "zh" => Ok(Locale::Zh),
+            "en" => Ok(Locale::En),
+            _ => Err(trf(Msg::UnknownLocale, &[&name])),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::Zh => "zh",
+            Locale::En => "en",
+        }
+    }
+
+    /// 读取配置 `locale`，未设置时参考 `LANG` 环境变量，都无法识别时使用中文
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        if let Some(name) = config.get(keys::LOCALE) {
+            return Self::parse(name);
+        }
+        Ok(std::env::var("LANG").ok().and_then(|lang| Self::parse(&lang).ok()).unwrap_or_default())
+    }
+
+    fn from_index(index: u8) -> Self {
+        match index {
+            1 => Locale::En,
+            _ => Locale::Zh,
+        }
+    }
+
+    fn index(self) -> u8 {
+        match self {
+            Locale::Zh => 0,
+            Locale::En => 1,
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// 设置当前语言
+pub fn set_locale(locale: Locale) {
+    CURRENT.store(locale.index(), Ordering::Relaxed);
+}
+
+/// 当前语言
+pub fn locale() -> Locale {
+    Locale::from_index(CURRENT.load(Ordering::Relaxed))
+}
+
+/// 当前语言下的消息文本
+pub fn tr(msg: Msg) -> &'static str {
+    msg.text(locale())
+}
+
+/// 当前语言下的消息，依次替换其中的 `{}`
+pub fn trf(msg: Msg, args: &[&dyn fmt::Display]) -> String {
+    format_in(locale(), msg, args)
+}
+
+/// 指定语言下的消息，依次替换其中的 `{}`，多余的占位符保持原样
+pub fn format_in(locale: Locale, msg: Msg, args: &[&dyn fmt::Display]) -> String {
+    let mut pieces = msg.text(locale).split("{}");
+    let mut out = pieces.next().unwrap_or("").to_string();
+    let mut args = args.iter();
+    for piece in pieces {
+        match args.next() {
+            Some(arg) => out.push_str(&arg.to_string()),
+            None => out.push_str("{}"),
+        }
+        out.push_str(piece);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_parsing_and_formatting() {
+        assert_eq!(Locale::parse("en"), Ok(Locale::En));
+        assert_eq!(Locale::parse("zh_CN.UTF-8"), Ok(Locale::Zh));
+        assert_eq!(Locale::parse("en-US"), Ok(Locale::En));
+        assert!(Locale::parse("fr").is_err());
+
+        assert_eq!(format_in(Locale::Zh, Msg::ErrorPrefix, &[&"x"]), "错误: x");
+        assert_eq!(format_in(Locale::En, Msg::ErrorPrefix, &[&"x"]), "error: x");
+        assert_eq!(format_in(Locale::En, Msg::ArgsInvalidValue, &[&"size"]), "invalid value for --size: {}");
+
+        let mut config = Config::new();
+        config.set(keys::LOCALE, "en");
+        assert_eq!(Locale::from_config(&config), Ok(Locale::En));
+    }
+
+    #[test]
+    fn test_catalogs_have_matching_placeholders() {
+        for &msg in Msg::ALL {
+            let zh = msg.text(Locale::Zh);
+            let en = msg.text(Locale::En);
+            assert_eq!(zh.matches("{}").count(), en.matches("{}").count(), "{:?}", msg);
+            assert!(!zh.is_empty() && !en.is_empty(), "{:?}", msg);
+        }
+    }
+}
@@ -11,6 +11,18 @@
 //! - Advanced entropy system with quantum resistance
 //! - Multiple game implementations
 //! - Unified configuration and error handling
+//!
+//! Cargo features (default: Game Boy emulator core only):
+//! - `debug`: debugger, disassembler, cheats and `AdvancedGameBoy`
+//! - `entropy`: entropy sources and quantum-resistant RNG
+//! - `gba`: GBA system emulation
+//! - `games`: bundled games and demos (implies `debug`, `entropy`, `gba`)
+//! - `frontends`: the `gamelife` command line tool (implies `games`)
+//! - `full`: everything
+//! - `serde`: `Serialize`/`Deserialize` for public state structs
+//! - `alloc-stats`: per-subsystem allocation stats; `gamelife` installs `util::alloc::CountingAllocator` as its global allocator
+//! - `audio`: sound output through cpal (`frontend::CpalSink`), not part of `full`
+//! - `gamepad`: controller input through gilrs (`frontend::gamepad::GilrsBackend`), not part of `full`
 
 // Core modules
 pub mod core {
@@ -18,21 +30,33 @@ pub mod core {
     pub mod memory;
     pub mod gpu;
     pub mod instructions;
+    pub mod audio;
 }
 
 // Game modules
+#[cfg(feature = "games")]
 pub mod games {
+    pub mod ai;
     pub mod life_game;
     pub mod tic_tac_toe;
+    pub mod connect_four;
     pub mod tetris;
+    pub mod tournament;
     pub mod demos;
 }
 
 // Library modules
+pub mod util;
+pub mod config;
+pub mod error;
+pub mod i18n;
+
+/// 旧的 `lib` 模块路径，保留一个版本后移除
+#[deprecated(since = "0.2.0", note = "请改用 `util`、`config` 和 `error` 顶层模块")]
 pub mod lib {
-    pub mod common;
-    pub mod config;
-    pub mod error;
+    pub use crate::util as common;
+    pub use crate::config;
+    pub use crate::error;
 }
 
 // Legacy modules (for backward compatibility)
@@ -50,16 +74,37 @@ pub mod instructions {
 }
 pub mod emulator;
 pub mod rom;
+#[cfg(feature = "debug")]
 pub mod debug;
+#[cfg(feature = "gba")]
 pub mod gba;
+#[cfg(feature = "entropy")]
 pub mod entropy;
+pub mod frontend;
+#[cfg(feature = "frontends")]
+pub mod cli;
+
+/// 常用类型与trait，`use gameboy_emulator::prelude::*;` 一次导入
+pub mod prelude {
+    pub use crate::config::Config;
+    pub use crate::emulator::GameBoy;
+    pub use crate::error::Error;
+    pub use crate::frontend::{AudioSink, Button, Emulator, Frame, Frontend, InputSource, Video};
+    #[cfg(feature = "debug")]
+    pub use crate::emulator::AdvancedGameBoy;
+    #[cfg(feature = "gba")]
+    pub use crate::gba::GBASystem;
+}
 
 // Re-export main types
-pub use emulator::{GameBoy, AdvancedGameBoy};
-pub use rom::RomGenerator;
+pub use emulator::{GameBoy, SaveState};
+#[cfg(feature = "debug")]
+pub use emulator::AdvancedGameBoy;
+pub use rom::{RomError, RomGenerator, RomHeader};
+#[cfg(feature = "entropy")]
 pub use entropy::{EntropyManager, EntropyError, EntropyStats};
 
 // Re-export library modules
-pub use lib::common::*;
-pub use lib::config::{Config, ConfigError};
-pub use lib::error::{Error, Result};
+pub use util::*;
+pub use config::{Config, ConfigError};
+pub use error::{Error, Result};
@@ -0,0 +1,75 @@
+//! ROM生成错误
+
+use std::fmt;
+
+/// ROM生成与校验错误
+#[derive(Debug)]
+pub enum RomError {
+    /// 标题超过长度上限（CGB卡带15字节，其余16字节）
+    TitleTooLong { len: usize, max: usize },
+    /// ROM大小代码无效 (0x148)
+    InvalidRomSize(u8),
+    /// 程序为空
+    EmptyProgram(u16),
+    /// 程序写入了头部区域 (0x100-0x14F)
+    HeaderCollision { start: u16, end: u32 },
+    /// 两段程序的地址区间重叠
+    Overlap { start: u16, end: u32, other_start: u16, other_end: u32 },
+    /// 程序超出ROM容量
+    OutOfBounds { start: u16, end: u32, capacity: usize },
+    /// 数据不足以包含完整头部
+    TooShort(usize),
+    /// Nintendo Logo不匹配
+    BadLogo,
+    /// 头部校验和错误
+    HeaderChecksum { expected: u8, found: u8 },
+    /// 全局校验和错误
+    GlobalChecksum { expected: u16, found: u16 },
+    /// 文件读写失败
+    Io(std::io::Error),
+}
+
+impl fmt::Display for RomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RomError::TitleTooLong { len, max } => write!(f, "标题过长: {} 字节 (最多{}字节)", len, max),
+            RomError::InvalidRomSize(code) => write!(f, "无效的ROM大小代码: 0x{:02X}", code),
+            RomError::EmptyProgram(start) => write!(f, "0x{:04X} 处的程序为空", start),
+            RomError::HeaderCollision { start, end } => {
+                write!(f, "程序 0x{:04X}-0x{:04X} 覆盖了ROM头部 (0x0100-0x014F)", start, end - 1)
+            }
+            RomError::Overlap { start, end, other_start, other_end } => write!(
+                f,
+                "程序 0x{:04X}-0x{:04X} 与 0x{:04X}-0x{:04X} 重叠",
+                start,
+                end - 1,
+                other_start,
+                other_end - 1
+            ),
+            RomError::OutOfBounds { start, end, capacity } => write!(
+                f,
+                "程序 0x{:04X}-0x{:04X} 超出ROM容量 ({} 字节)",
+                start,
+                end - 1,
+                capacity
+            ),
+            RomError::TooShort(len) => write!(f, "ROM数据过短: {} 字节 (头部需要 0x150 字节)", len),
+            RomError::BadLogo => write!(f, "Nintendo Logo不匹配"),
+            RomError::HeaderChecksum { expected, found } => {
+                write!(f, "头部校验和错误: 应为 0x{:02X}，实际为 0x{:02X}", expected, found)
+            }
+            RomError::GlobalChecksum { expected, found } => {
+                write!(f, "全局校验和错误: 应为 0x{:04X}，实际为 0x{:04X}", expected, found)
+            }
+            RomError::Io(e) => write!(f, "ROM文件读写失败: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RomError {}
+
+impl From<std::io::Error> for RomError {
+    fn from(e: std::io::Error) -> Self {
+        RomError::Io(e)
+    }
+}
@@ -0,0 +1,315 @@
+//! Game Boy ROM头部 (0x100-0x14F) 的布局、解析、生成与校验
+
+use super::RomError;
+
+/// 头部各字段的偏移，解析和生成共用这一份定义
+pub mod layout {
+    use std::ops::Range;
+
+    /// 入口跳转指令
+    pub const ENTRY_POINT: Range<usize> = 0x100..0x104;
+    /// Nintendo Logo
+    pub const NINTENDO_LOGO: Range<usize> = 0x104..0x134;
+    /// 游戏标题（新卡带中后5字节被制造商代码和CGB标志占用）
+    pub const TITLE: Range<usize> = 0x134..0x144;
+    /// 制造商代码
+    pub const MANUFACTURER_CODE: Range<usize> = 0x13F..0x143;
+    /// CGB标志
+    pub const CGB_FLAG: usize = 0x143;
+    /// 新许可证代码
+    pub const NEW_LICENSEE_CODE: Range<usize> = 0x144..0x146;
+    /// SGB标志
+    pub const SGB_FLAG: usize = 0x146;
+    /// 卡带类型
+    pub const CARTRIDGE_TYPE: usize = 0x147;
+    /// ROM大小
+    pub const ROM_SIZE: usize = 0x148;
+    /// RAM大小
+    pub const RAM_SIZE: usize = 0x149;
+    /// 目标市场
+    pub const DESTINATION_CODE: usize = 0x14A;
+    /// 旧许可证代码
+    pub const OLD_LICENSEE_CODE: usize = 0x14B;
+    /// ROM版本号
+    pub const ROM_VERSION: usize = 0x14C;
+    /// 头部校验和
+    pub const HEADER_CHECKSUM: usize = 0x14D;
+    /// 全局校验和（大端序）
+    pub const GLOBAL_CHECKSUM: Range<usize> = 0x14E..0x150;
+    /// 头部校验和覆盖的范围
+    pub const HEADER_CHECKSUM_RANGE: Range<usize> = 0x134..0x14D;
+    /// 头部结束地址（不含）
+    pub const END: usize = 0x150;
+}
+
+/// 启动ROM校验的Nintendo Logo
+pub const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
+    0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
+    0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+/// Game Boy ROM头部结构
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RomHeader {
+    /// 入口跳转指令 (0x100-0x103)
+    pub entry_point: [u8; 4],
+    /// Nintendo Logo (0x104-0x133)
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array"))]
+    pub nintendo_logo: [u8; 48],
+    /// 游戏标题 (0x134-0x143)，制造商代码和CGB标志通过方法读写
+    pub title: [u8; 16],
+    /// 新许可证代码 (0x144-0x145)
+    pub new_licensee_code: [u8; 2],
+    /// SGB标志 (0x146)
+    pub sgb_flag: u8,
+    /// 卡带类型 (0x147)
+    pub cartridge_type: u8,
+    /// ROM大小 (0x148)
+    pub rom_size: u8,
+    /// RAM大小 (0x149)
+    pub ram_size: u8,
+    /// 目标市场 (0x14A)
+    pub destination_code: u8,
+    /// 旧许可证代码 (0x14B)
+    pub old_licensee_code: u8,
+    /// ROM版本号 (0x14C)
+    pub rom_version: u8,
+    /// 头部校验和 (0x14D)
+    pub header_checksum: u8,
+    /// 全局校验和 (0x14E-0x14F)
+    pub global_checksum: u16,
+}
+
+impl Default for RomHeader {
+    fn default() -> Self {
+        Self {
+            entry_point: [0x00, 0xC3, 0x50, 0x01], // NOP; JP 0x0150
+            nintendo_logo: NINTENDO_LOGO,
+            title: [0; 16],
+            new_licensee_code: [0; 2],
+            sgb_flag: 0x00, // 非SGB游戏
+            cartridge_type: 0x00, // ROM only
+            rom_size: 0x00, // 32KB ROM
+            ram_size: 0x00, // 无RAM
+            destination_code: 0x00, // 日本
+            old_licensee_code: 0x00,
+            rom_version: 0x00,
+            header_checksum: 0x00,
+            global_checksum: 0x0000,
+        }
+    }
+}
+
+impl RomHeader {
+    /// 创建新的ROM头部
+    pub fn new(title: &str) -> Self {
+        let mut header = Self::default();
+        header.set_title(title);
+        header
+    }
+
+    /// 设置游戏标题，超出标题区域的部分被截断；已设置的CGB标志会保留
+    pub fn set_title(&mut self, title: &str) {
+        let cgb_flag = self.cgb_flag();
+        let capacity = if cgb_flag & 0x80 != 0 { 15 } else { 16 };
+        let title_bytes = title.as_bytes();
+        let title_len = title_bytes.len().min(capacity);
+        self.title = [0; 16];
+        self.title[..title_len].copy_from_slice(&title_bytes[..title_len]);
+        if capacity == 15 {
+            self.set_cgb_flag(cgb_flag);
+        }
+    }
+
+    /// 标题文本（去掉末尾的0和CGB标志）
+    pub fn title_text(&self) -> String {
+        let end = if self.cgb_flag() & 0x80 != 0 { 15 } else { 16 };
+        let title = &self.title[..end];
+        let len = title.iter().position(|&b| b == 0).unwrap_or(title.len());
+        String::from_utf8_lossy(&title[..len]).into_owned()
+    }
+
+    /// 制造商代码 (0x13F-0x142)
+    pub fn manufacturer_code(&self) -> [u8; 4] {
+        let offset = layout::MANUFACTURER_CODE.start - layout::TITLE.start;
+        [self.title[offset], self.title[offset + 1], self.title[offset + 2], self.title[offset + 3]]
+    }
+
+    /// CGB标志 (0x143)
+    pub fn cgb_flag(&self) -> u8 {
+        self.title[layout::CGB_FLAG - layout::TITLE.start]
+    }
+
+    /// 设置CGB标志
+    pub fn set_cgb_flag(&mut self, flag: u8) {
+        self.title[layout::CGB_FLAG - layout::TITLE.start] = flag;
+    }
+
+    /// 卡带类型对应的映射器名称
+    pub fn mapper(&self) -> &'static str {
+        match self.cartridge_type {
+            0x00 => "ROM ONLY",
+            0x01 => "MBC1",
+            0x02 => "MBC1+RAM",
+            0x03 => "MBC1+RAM+BATTERY",
+            0x05 => "MBC2",
+            0x06 => "MBC2+BATTERY",
+            0x08 => "ROM+RAM",
+            0x09 => "ROM+RAM+BATTERY",
+            0x0B => "MMM01",
+            0x0C => "MMM01+RAM",
+            0x0D => "MMM01+RAM+BATTERY",
+            0x0F => "MBC3+TIMER+BATTERY",
+            0x10 => "MBC3+TIMER+RAM+BATTERY",
+            0x11 => "MBC3",
+            0x12 => "MBC3+RAM",
+            0x13 => "MBC3+RAM+BATTERY",
+            0x19 => "MBC5",
+            0x1A => "MBC5+RAM",
+            0x1B => "MBC5+RAM+BATTERY",
+            0x1C => "MBC5+RUMBLE",
+            0x1D => "MBC5+RUMBLE+RAM",
+            0x1E => "MBC5+RUMBLE+RAM+BATTERY",
+            0x20 => "MBC6",
+            0x22 => "MBC7+SENSOR+RUMBLE+RAM+BATTERY",
+            0xFC => "POCKET CAMERA",
+            0xFD => "BANDAI TAMA5",
+            0xFE => "HuC3",
+            0xFF => "HuC1+RAM+BATTERY",
+            _ => "未知",
+        }
+    }
+
+    /// ROM bank数量（每个16KB），大小代码无效时返回 `None`
+    pub fn rom_banks(&self) -> Option<u32> {
+        match self.rom_size {
+            code @ 0..=8 => Some(2 << code),
+            _ => None,
+        }
+    }
+
+    /// 外部RAM大小（字节），大小代码无效时返回 `None`
+    pub fn ram_bytes(&self) -> Option<usize> {
+        match self.ram_size {
+            0x00 => Some(0),
+            0x01 => Some(2 * 1024),
+            0x02 => Some(8 * 1024),
+            0x03 => Some(32 * 1024),
+            0x04 => Some(128 * 1024),
+            0x05 => Some(64 * 1024),
+            _ => None,
+        }
+    }
+
+    /// 计算头部校验和 (0x134-0x14C)
+    pub fn calculate_header_checksum(&self) -> u8 {
+        self.to_bytes()[layout::HEADER_CHECKSUM_RANGE]
+            .iter()
+            .fold(0u8, |checksum, &byte| checksum.wrapping_sub(byte).wrapping_sub(1))
+    }
+
+    /// 计算全局校验和：除全局校验和两个字节外所有字节之和
+    pub fn calculate_global_checksum(&self, rom_data: &[u8]) -> u16 {
+        rom_data
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !layout::GLOBAL_CHECKSUM.contains(i))
+            .fold(0u16, |checksum, (_, &byte)| checksum.wrapping_add(byte as u16))
+    }
+
+    /// 将头部写入字节数组 (0x000-0x14F，0x000-0x0FF 为0)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; layout::END];
+        bytes[layout::ENTRY_POINT].copy_from_slice(&self.entry_point);
+        bytes[layout::NINTENDO_LOGO].copy_from_slice(&self.nintendo_logo);
+        bytes[layout::TITLE].copy_from_slice(&self.title);
+        bytes[layout::NEW_LICENSEE_CODE].copy_from_slice(&self.new_licensee_code);
+        bytes[layout::SGB_FLAG] = self.sgb_flag;
+        bytes[layout::CARTRIDGE_TYPE] = self.cartridge_type;
+        bytes[layout::ROM_SIZE] = self.rom_size;
+        bytes[layout::RAM_SIZE] = self.ram_size;
+        bytes[layout::DESTINATION_CODE] = self.destination_code;
+        bytes[layout::OLD_LICENSEE_CODE] = self.old_licensee_code;
+        bytes[layout::ROM_VERSION] = self.rom_version;
+        bytes[layout::HEADER_CHECKSUM] = self.header_checksum;
+        bytes[layout::GLOBAL_CHECKSUM].copy_from_slice(&self.global_checksum.to_be_bytes());
+        bytes
+    }
+
+    /// 从ROM数据解析头部，不做校验
+    pub fn parse(data: &[u8]) -> Result<Self, RomError> {
+        if data.len() < layout::END {
+            return Err(RomError::TooShort(data.len()));
+        }
+
+        let mut header = Self::default();
+        header.entry_point.copy_from_slice(&data[layout::ENTRY_POINT]);
+        header.nintendo_logo.copy_from_slice(&data[layout::NINTENDO_LOGO]);
+        header.title.copy_from_slice(&data[layout::TITLE]);
+        header.new_licensee_code.copy_from_slice(&data[layout::NEW_LICENSEE_CODE]);
+        header.sgb_flag = data[layout::SGB_FLAG];
+        header.cartridge_type = data[layout::CARTRIDGE_TYPE];
+        header.rom_size = data[layout::ROM_SIZE];
+        header.ram_size = data[layout::RAM_SIZE];
+        header.destination_code = data[layout::DESTINATION_CODE];
+        header.old_licensee_code = data[layout::OLD_LICENSEE_CODE];
+        header.rom_version = data[layout::ROM_VERSION];
+        header.header_checksum = data[layout::HEADER_CHECKSUM];
+        header.global_checksum = u16::from_be_bytes([data[layout::GLOBAL_CHECKSUM.start], data[layout::GLOBAL_CHECKSUM.start + 1]]);
+        Ok(header)
+    }
+}
+
+/// 校验ROM：Nintendo Logo、头部校验和与全局校验和，成功时返回解析出的头部
+pub fn validate_rom(data: &[u8]) -> Result<RomHeader, RomError> {
+    let header = RomHeader::parse(data)?;
+
+    if header.nintendo_logo != NINTENDO_LOGO {
+        return Err(RomError::BadLogo);
+    }
+
+    let expected = header.calculate_header_checksum();
+    if header.header_checksum != expected {
+        return Err(RomError::HeaderChecksum { expected, found: header.header_checksum });
+    }
+
+    let expected = header.calculate_global_checksum(data);
+    if header.global_checksum != expected {
+        return Err(RomError::GlobalChecksum { expected, found: header.global_checksum });
+    }
+
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_round_trip() {
+        let mut header = RomHeader::new("ROUND TRIP");
+        header.set_cgb_flag(0x80);
+        header.new_licensee_code = *b"01";
+        header.cartridge_type = 0x01;
+        header.rom_size = 0x02;
+        header.destination_code = 0x01;
+        header.rom_version = 0x03;
+        header.header_checksum = header.calculate_header_checksum();
+        header.global_checksum = 0xBEEF;
+
+        let bytes = header.to_bytes();
+        assert_eq!(bytes[0x147], 0x01);
+        assert_eq!(bytes[0x148], 0x02);
+        assert_eq!(&bytes[0x14E..0x150], &[0xBE, 0xEF]);
+
+        let parsed = RomHeader::parse(&bytes).unwrap();
+        assert_eq!(parsed, header);
+        assert_eq!(parsed.to_bytes(), bytes);
+        assert_eq!(parsed.title_text(), "ROUND TRIP");
+        assert_eq!(parsed.cgb_flag(), 0x80);
+        assert_eq!((parsed.mapper(), parsed.rom_banks(), parsed.ram_bytes()), ("MBC1", Some(8), Some(0)));
+        assert!(matches!(RomHeader::parse(&bytes[..0x14F]), Err(RomError::TooShort(0x14F))));
+    }
+}
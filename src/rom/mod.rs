@@ -1,237 +1,182 @@
-//! ROM生成器模块 - 生成Game Boy兼容的ROM文件
+//! ROM模块 - 解析、校验和生成Game Boy兼容的ROM文件
 
 use std::fs::File;
 use std::io::Write;
 
-/// Game Boy ROM头部结构
-#[derive(Debug, Clone)]
-pub struct RomHeader {
-    /// Nintendo Logo (0x104-0x133)
-    pub nintendo_logo: [u8; 48],
-    /// 游戏标题 (0x134-0x143)
-    pub title: [u8; 16],
-    /// 制造商代码 (0x144-0x145)
-    pub manufacturer_code: [u8; 2],
-    /// CGB标志 (0x146)
-    pub cgb_flag: u8,
-    /// 新许可证代码 (0x144-0x145)
-    pub new_licensee_code: [u8; 2],
-    /// SGB标志 (0x146)
-    pub sgb_flag: u8,
-    /// 卡带类型 (0x147)
-    pub cartridge_type: u8,
-    /// ROM大小 (0x148)
-    pub rom_size: u8,
-    /// RAM大小 (0x149)
-    pub ram_size: u8,
-    /// 目标市场 (0x14A)
-    pub destination_code: u8,
-    /// 旧许可证代码 (0x14B)
-    pub old_licensee_code: u8,
-    /// ROM版本号 (0x14C)
-    pub rom_version: u8,
-    /// 头部校验和 (0x14D)
-    pub header_checksum: u8,
-    /// 全局校验和 (0x14E-0x14F)
-    pub global_checksum: u16,
-}
+pub mod error;
+pub mod header;
 
-impl Default for RomHeader {
-    fn default() -> Self {
-        Self {
-            // Nintendo Logo - 必须完全匹配
-            nintendo_logo: [
-                0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
-                0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
-                0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E
-            ],
-            title: [0; 16],
-            manufacturer_code: [0; 2],
-            cgb_flag: 0x00, // 非CGB游戏
-            new_licensee_code: [0; 2],
-            sgb_flag: 0x00, // 非SGB游戏
-            cartridge_type: 0x00, // ROM only
-            rom_size: 0x00, // 32KB ROM
-            ram_size: 0x00, // 无RAM
-            destination_code: 0x00, // 日本
-            old_licensee_code: 0x00,
-            rom_version: 0x00,
-            header_checksum: 0x00,
-            global_checksum: 0x0000,
-        }
-    }
-}
+pub use error::RomError;
+pub use header::{layout, validate_rom, RomHeader, NINTENDO_LOGO};
 
-impl RomHeader {
-    /// 创建新的ROM头部
-    pub fn new(title: &str) -> Self {
-        let mut header = Self::default();
-        
-        // 设置游戏标题
-        let title_bytes = title.as_bytes();
-        let title_len = title_bytes.len().min(16);
-        header.title[..title_len].copy_from_slice(&title_bytes[..title_len]);
-        
-        header
-    }
+/// 头部区域起始地址（入口跳转指令）
+pub const HEADER_START: u16 = 0x0100;
+/// 头部区域结束地址（不含），也是默认的程序入口
+pub const PROGRAM_START: u16 = layout::END as u16;
 
-    /// 计算头部校验和
-    pub fn calculate_header_checksum(&self) -> u8 {
-        let mut checksum = 0u8;
-        
-        // 计算0x134-0x14C的校验和
-        for &byte in &self.title {
-            checksum = checksum.wrapping_sub(byte).wrapping_sub(1);
-        }
-        for &byte in &self.manufacturer_code {
-            checksum = checksum.wrapping_sub(byte).wrapping_sub(1);
-        }
-        checksum = checksum.wrapping_sub(self.cgb_flag).wrapping_sub(1);
-        for &byte in &self.new_licensee_code {
-            checksum = checksum.wrapping_sub(byte).wrapping_sub(1);
-        }
-        checksum = checksum.wrapping_sub(self.sgb_flag).wrapping_sub(1);
-        checksum = checksum.wrapping_sub(self.cartridge_type).wrapping_sub(1);
-        checksum = checksum.wrapping_sub(self.rom_size).wrapping_sub(1);
-        checksum = checksum.wrapping_sub(self.ram_size).wrapping_sub(1);
-        checksum = checksum.wrapping_sub(self.destination_code).wrapping_sub(1);
-        checksum = checksum.wrapping_sub(self.old_licensee_code).wrapping_sub(1);
-        checksum = checksum.wrapping_sub(self.rom_version).wrapping_sub(1);
-        
-        checksum
-    }
+/// 一段待写入ROM的程序
+#[derive(Debug, Clone)]
+struct Placement {
+    start: u16,
+    data: Vec<u8>,
+}
 
-    /// 计算全局校验和
-    pub fn calculate_global_checksum(&self, rom_data: &[u8]) -> u16 {
-        let mut checksum = 0u16;
-        
-        for &byte in rom_data {
-            checksum = checksum.wrapping_add(byte as u16);
-        }
-        
-        checksum
-    }
-
-    /// 将头部写入字节数组
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = vec![0u8; 0x150];
-        
-        // 0x100-0x103: 跳转到0x150
-        bytes[0x100] = 0x00; // NOP
-        bytes[0x101] = 0xC3; // JP
-        bytes[0x102] = 0x50; // 0x0150的低字节
-        bytes[0x103] = 0x01; // 0x0150的高字节
-        
-        // 0x104-0x133: Nintendo Logo
-        bytes[0x104..0x134].copy_from_slice(&self.nintendo_logo);
-        
-        // 0x134-0x143: 游戏标题
-        bytes[0x134..0x144].copy_from_slice(&self.title);
-        
-        // 0x144-0x145: 制造商代码
-        bytes[0x144..0x146].copy_from_slice(&self.manufacturer_code);
-        
-        // 0x146: CGB标志
-        bytes[0x146] = self.cgb_flag;
-        
-        // 0x147-0x148: 新许可证代码
-        bytes[0x147..0x149].copy_from_slice(&self.new_licensee_code);
-        
-        // 0x149: SGB标志
-        bytes[0x149] = self.sgb_flag;
-        
-        // 0x14A: 卡带类型
-        bytes[0x14A] = self.cartridge_type;
-        
-        // 0x14B: ROM大小
-        bytes[0x14B] = self.rom_size;
-        
-        // 0x14C: RAM大小
-        bytes[0x14C] = self.ram_size;
-        
-        // 0x14D: 目标市场
-        bytes[0x14D] = self.destination_code;
-        
-        // 0x14E: 旧许可证代码
-        bytes[0x14E] = self.old_licensee_code;
-        
-        // 0x14F: ROM版本号
-        bytes[0x14F] = self.rom_version;
-        
-        // 0x150: 头部校验和
-        if bytes.len() > 0x150 {
-            bytes[0x150] = self.header_checksum;
-        }
-        
-        // 0x151-0x152: 全局校验和
-        if bytes.len() > 0x152 {
-            bytes[0x151] = (self.global_checksum & 0xFF) as u8;
-            bytes[0x152] = ((self.global_checksum >> 8) & 0xFF) as u8;
-        }
-        
-        bytes
+impl Placement {
+    /// 结束地址（不含）
+    fn end(&self) -> u32 {
+        self.start as u32 + self.data.len() as u32
     }
 }
 
 /// ROM生成器
+///
+/// 以构建器方式设置头部字段和程序段，`build` 时统一校验：
+/// 程序段不能为空、不能写入头部区域、不能互相重叠、不能超出ROM容量。
+#[derive(Debug, Clone)]
 pub struct RomGenerator {
+    title: String,
+    cgb_flag: u8,
     header: RomHeader,
-    program_data: Vec<u8>,
+    placements: Vec<Placement>,
 }
 
 impl RomGenerator {
     /// 创建新的ROM生成器
     pub fn new(title: &str) -> Self {
         Self {
-            header: RomHeader::new(title),
-            program_data: Vec::new(),
+            title: title.to_string(),
+            cgb_flag: 0x00,
+            header: RomHeader::default(),
+            placements: Vec::new(),
+        }
+    }
+
+    /// 设置游戏标题（最多16字节，设置CGB标志时最多15字节）
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// 设置CGB标志
+    pub fn cgb_flag(mut self, flag: u8) -> Self {
+        self.cgb_flag = flag;
+        self
+    }
+
+    /// 设置SGB标志
+    pub fn sgb_flag(mut self, flag: u8) -> Self {
+        self.header.sgb_flag = flag;
+        self
+    }
+
+    /// 设置卡带类型
+    pub fn cartridge_type(mut self, cartridge_type: u8) -> Self {
+        self.header.cartridge_type = cartridge_type;
+        self
+    }
+
+    /// 设置ROM大小代码，容量为 32KB << code
+    pub fn rom_size(mut self, code: u8) -> Self {
+        self.header.rom_size = code;
+        self
+    }
+
+    /// 设置RAM大小代码
+    pub fn ram_size(mut self, code: u8) -> Self {
+        self.header.ram_size = code;
+        self
+    }
+
+    /// 设置目标市场
+    pub fn destination_code(mut self, code: u8) -> Self {
+        self.header.destination_code = code;
+        self
+    }
+
+    /// 设置ROM版本号
+    pub fn rom_version(mut self, version: u8) -> Self {
+        self.header.rom_version = version;
+        self
+    }
+
+    /// 添加程序段（构建器形式，错误在 `build` 时返回）
+    pub fn program(mut self, start_address: u16, program: &[u8]) -> Self {
+        self.placements.push(Placement { start: start_address, data: program.to_vec() });
+        self
+    }
+
+    /// 添加程序段并立即校验
+    pub fn add_program(&mut self, start_address: u16, program: &[u8]) -> Result<(), RomError> {
+        let placement = Placement { start: start_address, data: program.to_vec() };
+        Self::check_placement(&placement, &self.placements, self.capacity()?)?;
+        self.placements.push(placement);
+        Ok(())
+    }
+
+    /// ROM容量（字节）
+    pub fn capacity(&self) -> Result<usize, RomError> {
+        match self.header.rom_size {
+            code @ 0..=8 => Ok((32 * 1024) << code),
+            code => Err(RomError::InvalidRomSize(code)),
         }
     }
 
-    /// 添加程序数据
-    pub fn add_program(&mut self, start_address: u16, program: &[u8]) {
-        // 确保有足够的空间
-        let end_address = start_address + program.len() as u16;
-        if end_address as usize > self.program_data.len() {
-            self.program_data.resize(end_address as usize, 0x00);
+    /// 校验一段程序与已有程序段
+    fn check_placement(placement: &Placement, existing: &[Placement], capacity: usize) -> Result<(), RomError> {
+        let (start, end) = (placement.start, placement.end());
+        if placement.data.is_empty() {
+            return Err(RomError::EmptyProgram(start));
         }
-        
-        // 复制程序数据
-        self.program_data[start_address as usize..end_address as usize]
-            .copy_from_slice(program);
+        if (start as u32) < PROGRAM_START as u32 && end > HEADER_START as u32 {
+            return Err(RomError::HeaderCollision { start, end });
+        }
+        if end as usize > capacity {
+            return Err(RomError::OutOfBounds { start, end, capacity });
+        }
+        if let Some(other) = existing.iter().find(|other| (start as u32) < other.end() && (other.start as u32) < end) {
+            return Err(RomError::Overlap { start, end, other_start: other.start, other_end: other.end() });
+        }
+        Ok(())
     }
 
-    /// 生成ROM文件
-    pub fn generate_rom(&mut self) -> Vec<u8> {
-        // 计算校验和
-        self.header.header_checksum = self.header.calculate_header_checksum();
-        
-        // 生成头部
-        let mut rom_data = self.header.to_bytes();
-        
-        // 添加程序数据
-        rom_data.extend_from_slice(&self.program_data);
-        
-        // 确保ROM大小是32KB的倍数
-        let target_size = 32 * 1024; // 32KB
-        if rom_data.len() < target_size {
-            rom_data.resize(target_size, 0xFF); // 用0xFF填充
+    /// 校验所有设置并生成ROM镜像
+    pub fn build(&self) -> Result<Vec<u8>, RomError> {
+        let max_title = if self.cgb_flag != 0 { 15 } else { 16 };
+        if self.title.len() > max_title {
+            return Err(RomError::TitleTooLong { len: self.title.len(), max: max_title });
         }
-        
-        // 计算全局校验和
-        self.header.global_checksum = self.header.calculate_global_checksum(&rom_data);
-        
-        // 更新头部中的全局校验和
-        let checksum_bytes = self.header.global_checksum.to_le_bytes();
-        rom_data[0x151] = checksum_bytes[0];
-        rom_data[0x152] = checksum_bytes[1];
-        
-        rom_data
+        let capacity = self.capacity()?;
+        for (i, placement) in self.placements.iter().enumerate() {
+            Self::check_placement(placement, &self.placements[..i], capacity)?;
+        }
+
+        let mut header = self.header.clone();
+        header.set_title(&self.title);
+        if self.cgb_flag != 0 {
+            header.set_cgb_flag(self.cgb_flag);
+        }
+        header.header_checksum = header.calculate_header_checksum();
+
+        // 未使用的空间用0xFF填充
+        let mut rom_data = vec![0xFF; capacity];
+        for placement in &self.placements {
+            let start = placement.start as usize;
+            rom_data[start..start + placement.data.len()].copy_from_slice(&placement.data);
+        }
+        let header_bytes = header.to_bytes();
+        rom_data[..header_bytes.len()].copy_from_slice(&header_bytes);
+
+        // 全局校验和依赖完整镜像，写入后重新生成头部
+        header.global_checksum = header.calculate_global_checksum(&rom_data);
+        let header_bytes = header.to_bytes();
+        rom_data[..header_bytes.len()].copy_from_slice(&header_bytes);
+
+        Ok(rom_data)
     }
 
     /// 保存ROM文件
-    pub fn save_rom(&mut self, filename: &str) -> Result<(), std::io::Error> {
-        let rom_data = self.generate_rom();
+    pub fn save_rom(&self, filename: &str) -> Result<(), RomError> {
+        let rom_data = self.build()?;
         let mut file = File::create(filename)?;
         file.write_all(&rom_data)?;
         Ok(())
@@ -251,12 +196,63 @@ mod tests {
     #[test]
     fn test_rom_generator() {
         let mut generator = RomGenerator::new("TEST ROM");
-        generator.add_program(0x150, &[0x00, 0x01, 0x02]);
+        generator.add_program(0x150, &[0x00, 0x01, 0x02]).unwrap();
         
-        let rom_data = generator.generate_rom();
+        let rom_data = generator.build().unwrap();
         assert_eq!(rom_data.len(), 32 * 1024); // 32KB
         assert_eq!(rom_data[0x150], 0x00);
         assert_eq!(rom_data[0x151], 0x01);
         assert_eq!(rom_data[0x152], 0x02);
     }
+
+    #[test]
+    fn test_rom_generator_rejects_bad_placements() {
+        let mut generator = RomGenerator::new("TEST ROM");
+        generator.add_program(0x150, &[0x00; 16]).unwrap();
+
+        assert!(matches!(generator.add_program(0x140, &[0x00; 4]), Err(RomError::HeaderCollision { .. })));
+        assert!(matches!(generator.add_program(0x15F, &[0x00; 2]), Err(RomError::Overlap { other_start: 0x150, .. })));
+        assert!(matches!(generator.add_program(0x7FFF, &[0x00; 2]), Err(RomError::OutOfBounds { .. })));
+        assert!(matches!(generator.add_program(0x200, &[]), Err(RomError::EmptyProgram(0x200))));
+
+        let builder = RomGenerator::new("A TITLE LONGER THAN 16").program(0x150, &[0x00]);
+        assert!(matches!(builder.build(), Err(RomError::TitleTooLong { len: 22, max: 16 })));
+        assert!(matches!(RomGenerator::new("X").rom_size(9).build(), Err(RomError::InvalidRomSize(9))));
+
+        let cgb = RomGenerator::new("FIFTEEN CHARS!!!").cgb_flag(0x80).program(0x150, &[0x00]);
+        let error = cgb.build().unwrap_err();
+        assert!(matches!(error, RomError::TitleTooLong { len: 16, max: 15 }));
+        assert_eq!(error.to_string(), "标题过长: 16 字节 (最多15字节)");
+
+        let rom = RomGenerator::new("BIG").rom_size(1).program(0x8000, &[0xAA]).build().unwrap();
+        assert_eq!(rom.len(), 64 * 1024);
+        assert_eq!(rom[0x8000], 0xAA);
+    }
+
+    #[test]
+    fn test_generated_rom_passes_validation() {
+        let rom = RomGenerator::new("VALID ROM")
+            .cartridge_type(0x00)
+            .rom_version(0x01)
+            .program(PROGRAM_START, &[0x00, 0x18, 0xFD])
+            .build()
+            .unwrap();
+
+        let header = validate_rom(&rom).unwrap();
+        assert_eq!(header.title_text(), "VALID ROM");
+        assert_eq!(header.rom_version, 1);
+        assert_eq!(&rom[layout::ENTRY_POINT], &[0x00, 0xC3, 0x50, 0x01]);
+        assert_eq!(&rom[0x150..0x153], &[0x00, 0x18, 0xFD]);
+
+        let mut gameboy = crate::GameBoy::new();
+        crate::frontend::Emulator::load_rom(&mut gameboy, &rom).unwrap();
+
+        let mut corrupted = rom.clone();
+        corrupted[0x200] ^= 0xFF;
+        assert!(matches!(validate_rom(&corrupted), Err(RomError::GlobalChecksum { .. })));
+        corrupted[layout::ROM_VERSION] ^= 0xFF;
+        assert!(matches!(validate_rom(&corrupted), Err(RomError::HeaderChecksum { .. })));
+        corrupted[layout::NINTENDO_LOGO.start] ^= 0xFF;
+        assert!(matches!(validate_rom(&corrupted), Err(RomError::BadLogo)));
+    }
 }
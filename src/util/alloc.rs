@@ -0,0 +1,304 @@
+//! 按子系统统计内存分配
+//!
+//! `CountingAllocator` 包装系统分配器，把每次分配计入当前线程所在的子系统。
+//! 库本身不注册全局分配器：开启 `alloc-stats` feature 时 `gamelife` 会注册它，
+//! 其他程序需要统计时自行用 `#[global_allocator]` 注册。
+//! 注册后 `gamelife run` 和 `gamelife selftest` 会在结果后附上 `AllocReport`。
+//! 子系统由 `PerformanceMonitor::scope` 或 `enter` 返回的守卫标记，守卫离开作用域时恢复外层子系统。
+//! 未开启feature时作用域照常工作，但不会有分配被统计。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::frontend::tui::{pad, Align};
+use crate::i18n::{tr, trf, Msg};
+use crate::util::{Json, ToJson};
+
+/// 分配归属的子系统
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    /// 不在任何作用域内
+    Other,
+    Cpu,
+    Gpu,
+    Games,
+    Entropy,
+}
+
+impl Subsystem {
+    /// 全部子系统
+    pub const ALL: [Subsystem; 5] = [Subsystem::Other, Subsystem::Cpu, Subsystem::Gpu, Subsystem::Games, Subsystem::Entropy];
+
+    /// 子系统名称
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Subsystem::Other => Msg::AllocOther,
+            Subsystem::Cpu => Msg::AllocCpu,
+            Subsystem::Gpu => Msg::AllocGpu,
+            Subsystem::Games => Msg::AllocGames,
+            Subsystem::Entropy => Msg::AllocEntropy,
+        })
+    }
+
+    /// JSON输出中使用的标识，不随语言变化
+    pub fn id(&self) -> &'static str {
+        match self {
+            Subsystem::Other => "other",
+            Subsystem::Cpu => "cpu",
+            Subsystem::Gpu => "gpu",
+            Subsystem::Games => "games",
+            Subsystem::Entropy => "entropy",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+const COUNT: usize = Subsystem::ALL.len();
+
+thread_local! {
+    // 常量初始化，访问时不会分配内存
+    static ACTIVE: Cell<Subsystem> = const { Cell::new(Subsystem::Other) };
+}
+
+static ALLOCATIONS: [AtomicU64; COUNT] = [const { AtomicU64::new(0) }; COUNT];
+static BYTES: [AtomicU64; COUNT] = [const { AtomicU64::new(0) }; COUNT];
+
+/// 当前线程所在的子系统
+pub fn active() -> Subsystem {
+    ACTIVE.try_with(Cell::get).unwrap_or(Subsystem::Other)
+}
+
+/// 进入子系统作用域，守卫释放时恢复外层子系统
+pub fn enter(subsystem: Subsystem) -> SubsystemScope {
+    SubsystemScope { previous: ACTIVE.replace(subsystem) }
+}
+
+/// 子系统作用域守卫
+#[derive(Debug)]
+#[must_use = "作用域在守卫释放时结束"]
+pub struct SubsystemScope {
+    previous: Subsystem,
+}
+
+impl Drop for SubsystemScope {
+    fn drop(&mut self) {
+        ACTIVE.set(self.previous);
+    }
+}
+
+/// 统计分配次数的全局分配器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count(size: usize) {
+        let index = active().index();
+        ALLOCATIONS[index].fetch_add(1, Ordering::Relaxed);
+        BYTES[index].fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count(layout.size());
+        // SAFETY: 调用方保证layout满足 `GlobalAlloc::alloc` 的要求
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::count(layout.size());
+        // SAFETY: 同上
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::count(new_size);
+        // SAFETY: ptr由本分配器以layout分配，调用方保证new_size有效
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: ptr由本分配器以layout分配
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+/// 某一时刻的累计分配统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    pub allocations: [u64; COUNT],
+    pub bytes: [u64; COUNT],
+}
+
+impl AllocStats {
+    /// 读取当前累计值
+    pub fn snapshot() -> Self {
+        Self {
+            allocations: std::array::from_fn(|i| ALLOCATIONS[i].load(Ordering::Relaxed)),
+            bytes: std::array::from_fn(|i| BYTES[i].load(Ordering::Relaxed)),
+        }
+    }
+
+    /// 注册了计数分配器时返回自 `start` 以来的增量，否则返回 `None`
+    pub fn measured_since(start: &AllocStats) -> Option<Self> {
+        cfg!(feature = "alloc-stats").then(|| Self::snapshot().since(start))
+    }
+
+    /// 自 `earlier` 以来的增量
+    pub fn since(&self, earlier: &AllocStats) -> Self {
+        Self {
+            allocations: std::array::from_fn(|i| self.allocations[i].saturating_sub(earlier.allocations[i])),
+            bytes: std::array::from_fn(|i| self.bytes[i].saturating_sub(earlier.bytes[i])),
+        }
+    }
+
+    /// 子系统的分配次数
+    pub fn allocations(&self, subsystem: Subsystem) -> u64 {
+        self.allocations[subsystem.index()]
+    }
+
+    /// 子系统的分配字节数
+    pub fn bytes(&self, subsystem: Subsystem) -> u64 {
+        self.bytes[subsystem.index()]
+    }
+
+    /// 按帧平均的报告
+    pub fn per_frame(&self, frames: u64) -> AllocReport {
+        AllocReport { stats: *self, frames: frames.max(1), unit: AllocUnit::Frame }
+    }
+
+    /// 按自检项目平均的报告
+    pub fn per_check(&self, checks: u64) -> AllocReport {
+        AllocReport { stats: *self, frames: checks.max(1), unit: AllocUnit::Check }
+    }
+}
+
+/// 报告求平均的单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocUnit {
+    Frame,
+    Check,
+}
+
+/// 每帧（或每项自检）分配报告
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocReport {
+    pub stats: AllocStats,
+    /// 求平均的帧数或自检项目数
+    pub frames: u64,
+    pub unit: AllocUnit,
+}
+
+impl AllocReport {
+    /// 子系统平均每单位的分配次数
+    pub fn allocations(&self, subsystem: Subsystem) -> f64 {
+        self.stats.allocations(subsystem) as f64 / self.frames as f64
+    }
+
+    /// 子系统平均每单位的分配字节数
+    pub fn bytes(&self, subsystem: Subsystem) -> f64 {
+        self.stats.bytes(subsystem) as f64 / self.frames as f64
+    }
+}
+
+impl fmt::Display for AllocReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = match self.unit {
+            AllocUnit::Frame => Msg::AllocReportTitle,
+            AllocUnit::Check => Msg::AllocReportChecksTitle,
+        };
+        writeln!(f, "{}", trf(title, &[&self.frames]))?;
+        for subsystem in Subsystem::ALL {
+            let allocations = format!("{:>10.1}", self.allocations(subsystem));
+            let bytes = format!("{:>12.1}", self.bytes(subsystem));
+            writeln!(f, "{}", trf(Msg::AllocReportLine, &[&pad(subsystem.name(), 8, Align::Left), &allocations, &bytes]))?;
+        }
+        if !cfg!(feature = "alloc-stats") {
+            writeln!(f, "{}", tr(Msg::AllocReportDisabled))?;
+        }
+        Ok(())
+    }
+}
+
+impl ToJson for AllocReport {
+    fn to_json(&self) -> Json {
+        let unit = match self.unit {
+            AllocUnit::Frame => "frames",
+            AllocUnit::Check => "checks",
+        };
+        let subsystems = Subsystem::ALL
+            .iter()
+            .map(|&subsystem| {
+                (
+                    subsystem.id(),
+                    Json::object(vec![
+                        ("allocations", Json::from(self.allocations(subsystem))),
+                        ("bytes", Json::from(self.bytes(subsystem))),
+                    ]),
+                )
+            })
+            .collect();
+        Json::object(vec![(unit, Json::from(self.frames)), ("per_unit", Json::object(subsystems))])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes_attribute_allocations() {
+        assert_eq!(active(), Subsystem::Other);
+        let before = AllocStats::snapshot();
+        {
+            let _games = enter(Subsystem::Games);
+            {
+                let _entropy = enter(Subsystem::Entropy);
+                assert_eq!(active(), Subsystem::Entropy);
+            }
+            assert_eq!(active(), Subsystem::Games);
+
+            // 直接调用分配器，不依赖是否注册为全局分配器
+            let layout = Layout::from_size_align(64, 8).unwrap();
+            unsafe {
+                let ptr = CountingAllocator.alloc(layout);
+                CountingAllocator.dealloc(ptr, layout);
+            }
+        }
+        assert_eq!(active(), Subsystem::Other);
+
+        let delta = AllocStats::snapshot().since(&before);
+        // 其他测试线程可能同时在游戏作用域内分配
+        assert!(delta.allocations(Subsystem::Games) >= 1);
+        assert!(delta.bytes(Subsystem::Games) >= 64);
+        let report = delta.per_frame(2).to_string();
+        assert!(report.starts_with("内存分配 (2 帧平均):"));
+    }
+
+    #[test]
+    fn test_report_averages_and_json() {
+        let mut stats = AllocStats::default();
+        stats.allocations[Subsystem::Cpu.index()] = 30;
+        stats.bytes[Subsystem::Cpu.index()] = 960;
+
+        let report = stats.per_check(3);
+        assert_eq!((report.allocations(Subsystem::Cpu), report.bytes(Subsystem::Cpu)), (10.0, 320.0));
+        assert!(report.to_string().starts_with("内存分配 (3 项自检平均):"));
+
+        let json = report.to_json();
+        assert_eq!(json.get("checks"), Some(&Json::Int(3)));
+        let per_unit = json.get("per_unit").unwrap();
+        for subsystem in Subsystem::ALL {
+            assert!(per_unit.get(subsystem.id()).is_some(), "{}", subsystem.id());
+        }
+        assert_eq!(per_unit.get("cpu").and_then(|cpu| cpu.get("bytes")), Some(&Json::from(320.0)));
+
+        // 库的单元测试没有注册计数分配器
+        assert_eq!(AllocStats::measured_since(&AllocStats::snapshot()).is_some(), cfg!(feature = "alloc-stats"));
+    }
+}
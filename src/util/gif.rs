@@ -0,0 +1,237 @@
+//! GIF动画编码
+//!
+//! 最小的GIF89a编码器：全局调色板、无限循环、每帧完整重绘并带延时，
+//! 像素数据用变长LZW压缩。输入为调色板下标，每个像素一个字节。
+
+use std::collections::HashMap;
+
+/// LZW码表上限
+const MAX_CODES: u16 = 4096;
+
+/// GIF动画编码器
+#[derive(Debug, Clone)]
+pub struct GifEncoder {
+    width: u16,
+    height: u16,
+    colors: usize,
+    /// 调色板下标位数，至少为2（LZW最小码长）
+    min_code_size: u8,
+    bytes: Vec<u8>,
+}
+
+impl GifEncoder {
+    /// 创建编码器并写入文件头，`palette` 为1-256个RGB颜色
+    pub fn new(width: u16, height: u16, palette: &[[u8; 3]]) -> Result<Self, String> {
+        if palette.is_empty() || palette.len() > 256 {
+            return Err(format!("调色板颜色数必须在1-256之间: {}", palette.len()));
+        }
+        if width == 0 || height == 0 {
+            return Err("图像尺寸不能为0".to_string());
+        }
+
+        // 全局调色板的大小必须是2的幂
+        let table_bits = (usize::BITS - (palette.len() - 1).leading_zeros()).max(1) as u8;
+        let mut bytes = b"GIF89a".to_vec();
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(&[0x80 | ((table_bits - 1) << 4) | (table_bits - 1), 0, 0]);
+        for index in 0..1usize << table_bits {
+            bytes.extend_from_slice(&palette.get(index).copied().unwrap_or([0, 0, 0]));
+        }
+        // NETSCAPE2.0 扩展：无限循环
+        bytes.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+        bytes.extend_from_slice(b"NETSCAPE2.0");
+        bytes.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        Ok(Self { width, height, colors: palette.len(), min_code_size: table_bits.max(2), bytes })
+    }
+
+    /// 添加一帧，`delay` 单位为1/100秒
+    pub fn add_frame(&mut self, pixels: &[u8], delay: u16) -> Result<(), String> {
+        let expected = self.width as usize * self.height as usize;
+        if pixels.len() != expected {
+            return Err(format!("帧大小不匹配: {} != {}", pixels.len(), expected));
+        }
+        if let Some(&index) = pixels.iter().find(|&&index| index as usize >= self.colors) {
+            return Err(format!("调色板下标越界: {}", index));
+        }
+
+        // 图形控制扩展：延时
+        self.bytes.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00]);
+        self.bytes.extend_from_slice(&delay.to_le_bytes());
+        self.bytes.extend_from_slice(&[0x00, 0x00]);
+        // 图像描述符：覆盖整个画面，使用全局调色板
+        self.bytes.extend_from_slice(&[0x2C, 0, 0, 0, 0]);
+        self.bytes.extend_from_slice(&self.width.to_le_bytes());
+        self.bytes.extend_from_slice(&self.height.to_le_bytes());
+        self.bytes.push(0x00);
+
+        self.bytes.push(self.min_code_size);
+        let data = lzw_encode(pixels, self.min_code_size);
+        for block in data.chunks(255) {
+            self.bytes.push(block.len() as u8);
+            self.bytes.extend_from_slice(block);
+        }
+        self.bytes.push(0x00);
+        Ok(())
+    }
+
+    /// 写入文件尾并返回完整的GIF数据
+    pub fn finish(mut self) -> Vec<u8> {
+        self.bytes.push(0x3B);
+        self.bytes
+    }
+}
+
+/// 按LSB优先顺序写入变长码
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// GIF变体的LZW压缩
+fn lzw_encode(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut writer = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut size = min_code_size + 1;
+    writer.write(clear, size);
+
+    let mut current: Option<u16> = None;
+    for &pixel in pixels {
+        let Some(prefix) = current else {
+            current = Some(pixel as u16);
+            continue;
+        };
+        if let Some(&code) = table.get(&(prefix, pixel)) {
+            current = Some(code);
+            continue;
+        }
+
+        writer.write(prefix, size);
+        if next < MAX_CODES {
+            table.insert((prefix, pixel), next);
+            next += 1;
+            // 解码器比编码器晚一个码加入表项，所以在超过而不是达到2^size时增加码长
+            if next > 1 << size && size < 12 {
+                size += 1;
+            }
+        } else {
+            writer.write(clear, size);
+            table.clear();
+            next = end + 1;
+            size = min_code_size + 1;
+        }
+        current = Some(pixel as u16);
+    }
+
+    if let Some(code) = current {
+        writer.write(code, size);
+    }
+    writer.write(end, size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用的LZW解码器，按标准解码器的码长规则读取
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut size = min_code_size + 1;
+        let mut previous: Option<u16> = None;
+        let mut output = Vec::new();
+        let (mut buffer, mut bits, mut bytes) = (0u32, 0u8, data.iter());
+        loop {
+            while bits < size {
+                buffer |= (*bytes.next().unwrap() as u32) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << size) - 1)) as u16;
+            buffer >>= size;
+            bits -= size;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([Vec::new(), Vec::new()]);
+                size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return output;
+            }
+            let entry = match (table.get(code as usize), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => {
+                    let mut entry = table[prev as usize].clone();
+                    entry.push(entry[0]);
+                    entry
+                }
+                (None, None) => panic!("无效的码 {}", code),
+            };
+            if let Some(prev) = previous {
+                if table.len() < MAX_CODES as usize {
+                    let mut added = table[prev as usize].clone();
+                    added.push(entry[0]);
+                    table.push(added);
+                    if table.len() == 1 << size && size < 12 {
+                        size += 1;
+                    }
+                }
+            }
+            output.extend_from_slice(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip_and_gif_layout() {
+        // 足够长的伪随机数据，触发码长增长和码表重置
+        let mut state = 1u32;
+        let pixels: Vec<u8> = (0..128 * 128)
+            .map(|i| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                if i % 7 == 0 { 0 } else { (state >> 16) as u8 % 5 }
+            })
+            .collect();
+        assert_eq!(lzw_decode(&lzw_encode(&pixels, 3), 3), pixels);
+        assert_eq!(lzw_decode(&lzw_encode(&[1, 1, 1, 1, 0], 2), 2), vec![1, 1, 1, 1, 0]);
+
+        let palette = [[0, 0, 0], [255, 255, 255], [255, 0, 0]];
+        let mut gif = GifEncoder::new(2, 2, &palette).unwrap();
+        gif.add_frame(&[0, 1, 2, 1], 10).unwrap();
+        assert!(gif.add_frame(&[0, 1, 3, 1], 10).is_err());
+        assert!(gif.add_frame(&[0, 1], 10).is_err());
+        let bytes = gif.finish();
+        assert!(bytes.starts_with(b"GIF89a\x02\x00\x02\x00\x91"));
+        assert_eq!(&bytes[13..25], &[0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0]);
+        assert_eq!(bytes.last(), Some(&0x3B));
+        assert!(GifEncoder::new(1, 1, &[]).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+//! 轻量JSON输出
+//!
+//! 主要负责序列化，用于命令行的机器可读输出；对象保持插入顺序。
+//! `Json::parse` 用于在测试中读回命令行的输出。
+
+use std::fmt;
+
+/// JSON值
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// 可转换为JSON的类型
+pub trait ToJson {
+    fn to_json(&self) -> Json;
+}
+
+impl Json {
+    /// 由键值对构造对象
+    pub fn object<K: Into<String>>(fields: Vec<(K, Json)>) -> Json {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// 由可转换元素构造数组
+    pub fn array<T: ToJson>(items: &[T]) -> Json {
+        Json::Array(items.iter().map(ToJson::to_json).collect())
+    }
+
+    /// 解析JSON文本，整个输入必须是一个值（前后允许空白）
+    pub fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = Parser { chars: text.char_indices().peekable(), text };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(value),
+            Some((offset, c)) => Err(format!("位置 {} 处多余的字符 '{}'", offset, c)),
+        }
+    }
+
+    /// 对象中的字段，不是对象或没有该字段时为 `None`
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// 带缩进的多行输出
+    pub fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, Some(0));
+        out
+    }
+
+    fn write(&self, out: &mut String, indent: Option<usize>) {
+        let newline = |out: &mut String, level: usize| {
+            if indent.is_some() {
+                out.push('\n');
+                out.push_str(&"  ".repeat(level));
+            }
+        };
+        let level = indent.unwrap_or(0);
+        let child = indent.map(|i| i + 1);
+
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Json::Int(i) => out.push_str(&i.to_string()),
+            // JSON不支持NaN和无穷大
+            Json::Float(f) if !f.is_finite() => out.push_str("null"),
+            // 保留小数点，便于读取方区分整数和浮点数
+            Json::Float(f) if f.fract() == 0.0 && f.abs() < 1e15 => out.push_str(&format!("{:.1}", f)),
+            Json::Float(f) => out.push_str(&f.to_string()),
+            Json::Str(s) => write_string(out, s),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, level + 1);
+                    item.write(out, child);
+                }
+                newline(out, level);
+                out.push(']');
+            }
+            Json::Object(fields) if fields.is_empty() => out.push_str("{}"),
+            Json::Object(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, level + 1);
+                    write_string(out, key);
+                    out.push(':');
+                    if indent.is_some() {
+                        out.push(' ');
+                    }
+                    value.write(out, child);
+                }
+                newline(out, level);
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// 紧凑的单行输出
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out = String::new();
+        self.write(&mut out, None);
+        f.write_str(&out)
+    }
+}
+
+/// 递归下降解析器
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_ascii_whitespace()).is_some() {}
+    }
+
+    fn error(&mut self, expected: &str) -> String {
+        match self.chars.peek() {
+            Some((offset, c)) => format!("位置 {} 处应为{}，实际为 '{}'", offset, expected, c),
+            None => format!("应为{}，输入已结束", expected),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next_if(|(_, c)| *c == expected) {
+            Some(_) => Ok(()),
+            None => Err(self.error(&format!(" '{}'", expected))),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.chars.peek().map(|(_, c)| *c) {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Json::Str),
+            Some('t') => self.literal("true", Json::Bool(true)),
+            Some('f') => self.literal("false", Json::Bool(false)),
+            Some('n') => self.literal("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("JSON值")),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        for expected in word.chars() {
+            if self.chars.next_if(|(_, c)| *c == expected).is_none() {
+                return Err(self.error(word));
+            }
+        }
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.chars.peek().map_or(self.text.len(), |(offset, _)| *offset);
+        let mut end = start;
+        while let Some((offset, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+            end = offset + c.len_utf8();
+        }
+        let literal = &self.text[start..end];
+        if let Ok(value) = literal.parse::<i64>() {
+            return Ok(Json::Int(value));
+        }
+        literal.parse::<f64>().map(Json::Float).map_err(|_| format!("位置 {} 处无效的数字 {}", start, literal))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(out),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, '"')) => out.push('"'),
+                    Some((_, '\\')) => out.push('\\'),
+                    Some((_, '/')) => out.push('/'),
+                    Some((_, 'b')) => out.push('\u{08}'),
+                    Some((_, 'f')) => out.push('\u{0C}'),
+                    Some((_, 'n')) => out.push('\n'),
+                    Some((_, 'r')) => out.push('\r'),
+                    Some((_, 't')) => out.push('\t'),
+                    Some((offset, 'u')) => {
+                        let digits: String = (0..4).filter_map(|_| self.chars.next().map(|(_, c)| c)).collect();
+                        let code = u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32);
+                        out.push(code.ok_or_else(|| format!("位置 {} 处无效的转义 \\u{}", offset, digits))?);
+                    }
+                    Some((offset, c)) => return Err(format!("位置 {} 处无效的转义 \\{}", offset, c)),
+                    None => return Err("字符串没有结束".to_string()),
+                },
+                Some((_, c)) => out.push(c),
+                None => return Err("字符串没有结束".to_string()),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.next_if(|(_, c)| *c == ',' || *c == ']') {
+                Some((_, ',')) => continue,
+                Some(_) => return Ok(Json::Array(items)),
+                None => return Err(self.error(" ',' 或 ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.chars.next_if(|(_, c)| *c == ',' || *c == '}') {
+                Some((_, ',')) => continue,
+                Some(_) => return Ok(Json::Object(fields)),
+                None => return Err(self.error(" ',' 或 '}'")),
+            }
+        }
+    }
+}
+
+/// 写入带转义的字符串
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Float(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::Str(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::Str(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Json {
+            fn from(value: $t) -> Self {
+                Json::Int(value as i64)
+            }
+        })*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        let value = Json::object(vec![
+            ("name", Json::from("a\"b\n")),
+            ("count", Json::from(3u32)),
+            ("ratio", Json::from(0.5)),
+            ("missing", Json::from(None::<u32>)),
+            ("items", Json::Array(vec![Json::from(true), Json::Float(f64::NAN)])),
+        ]);
+
+        assert_eq!(
+            value.to_string(),
+            r#"{"name":"a\"b\n","count":3,"ratio":0.5,"missing":null,"items":[true,null]}"#
+        );
+        assert!(value.to_pretty().contains("\n  \"count\": 3,"));
+    }
+
+    #[test]
+    fn test_parse_round_trip_and_errors() {
+        let value = Json::object(vec![
+            ("name", Json::from("a\"b\n\u{1}中")),
+            ("count", Json::from(-3i32)),
+            ("ratio", Json::from(0.5)),
+            ("missing", Json::Null),
+            ("items", Json::Array(vec![Json::from(true), Json::Array(Vec::new()), Json::object(Vec::<(&str, Json)>::new())])),
+        ]);
+        assert_eq!(Json::parse(&value.to_pretty()), Ok(value.clone()));
+        assert_eq!(Json::parse(&value.to_string()), Ok(value.clone()));
+        assert_eq!(value.get("count"), Some(&Json::Int(-3)));
+        assert_eq!(Json::parse(r#" "\u00e9\/" "#), Ok(Json::from("é/")));
+
+        assert!(Json::parse("[DEBUG] INFO: 启动\n{}").is_err());
+        assert!(Json::parse("{} {}").is_err());
+        assert!(Json::parse(r#"{"a": 1,}"#).is_err());
+        assert!(Json::parse(r#""abc"#).is_err());
+    }
+}
@@ -4,6 +4,17 @@
 
 use std::time::{Duration, Instant};
 
+pub mod alloc;
+pub mod gif;
+pub mod hash;
+pub mod json;
+pub mod progress;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_array;
+
+pub use json::{Json, ToJson};
+pub use progress::Progress;
+
 /// 通用常量
 pub mod constants {
     /// CPU频率 (Hz)
@@ -59,6 +70,11 @@ impl PerformanceMonitor {
         self.start_time.elapsed()
     }
     
+    /// 进入子系统作用域，期间的内存分配计入该子系统（需要 `alloc-stats` feature）
+    pub fn scope(&self, subsystem: alloc::Subsystem) -> alloc::SubsystemScope {
+        alloc::enter(subsystem)
+    }
+    
     /// 打印性能报告
     pub fn print_report(&self) {
         println!("Performance Report:");
@@ -248,7 +264,7 @@ pub type Address = u16;
 pub type Register = u8;
 
 /// 通用结果类型
-pub type GameResult<T> = Result<T, crate::lib::error::Error>;
+pub type GameResult<T> = Result<T, crate::error::Error>;
 
 /// 日志级别
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -0,0 +1,224 @@
+//! 进度报告工具
+//!
+//! 长耗时操作的进度条（已知总量）或旋转指示器（未知总量），带剩余时间估计。
+//! 输出写入标准错误，标准输出保持可被脚本解析；标准错误不是终端时只输出最终结果行，
+//! 安静模式下完全不输出。
+
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use crate::i18n::{tr, trf, Msg};
+
+/// 两次重绘之间的最小间隔
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 进度条宽度（字符）
+const BAR_WIDTH: usize = 24;
+
+/// 旋转指示器帧
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+/// 进度报告器
+#[derive(Debug)]
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    current: u64,
+    start: Instant,
+    last_draw: Option<Instant>,
+    spinner_frame: usize,
+    quiet: bool,
+    interactive: bool,
+    finished: bool,
+}
+
+impl Progress {
+    /// 已知总量的进度条
+    pub fn bar(label: &str, total: u64) -> Self {
+        Self::new(label, Some(total))
+    }
+
+    /// 未知总量的旋转指示器
+    pub fn spinner(label: &str) -> Self {
+        Self::new(label, None)
+    }
+
+    fn new(label: &str, total: Option<u64>) -> Self {
+        Self {
+            label: label.to_string(),
+            total,
+            current: 0,
+            start: Instant::now(),
+            last_draw: None,
+            spinner_frame: 0,
+            quiet: false,
+            interactive: io::stderr().is_terminal(),
+            finished: false,
+        }
+    }
+
+    /// 设置安静模式（用于CI）
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// 增加进度
+    pub fn inc(&mut self, delta: u64) {
+        self.set(self.current + delta);
+    }
+
+    /// 设置当前进度
+    pub fn set(&mut self, current: u64) {
+        self.current = current;
+        let due = self.last_draw.is_none_or(|last| last.elapsed() >= REDRAW_INTERVAL);
+        if due {
+            self.draw();
+        }
+    }
+
+    /// 当前进度
+    pub fn position(&self) -> u64 {
+        self.current
+    }
+
+    /// 按当前速率估计剩余时间，总量未知或尚无进度时返回 `None`
+    pub fn eta(&self) -> Option<Duration> {
+        let total = self.total?;
+        if self.current == 0 {
+            return None;
+        }
+        let remaining = total.saturating_sub(self.current) as f64;
+        let per_item = self.start.elapsed().as_secs_f64() / self.current as f64;
+        Some(Duration::from_secs_f64(remaining * per_item))
+    }
+
+    /// 结束并输出最终结果行
+    pub fn finish(&mut self) {
+        self.finish_with_message(tr(Msg::ProgressDone));
+    }
+
+    /// 结束并附带说明
+    pub fn finish_with_message(&mut self, message: &str) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+
+        if self.quiet {
+            return;
+        }
+
+        let line = trf(Msg::ProgressFinished, &[
+            &self.label,
+            &message,
+            &self.count_text(),
+            &format!("{:.1}", self.start.elapsed().as_secs_f64()),
+        ]);
+        let mut stderr = io::stderr();
+        if self.interactive {
+            let _ = write!(stderr, "\r\x1b[2K");
+        }
+        let _ = writeln!(stderr, "{}", line);
+    }
+
+    /// 重绘当前状态行
+    fn draw(&mut self) {
+        self.last_draw = Some(Instant::now());
+        if self.quiet || !self.interactive || self.finished {
+            return;
+        }
+
+        let line = match self.total {
+            Some(total) => {
+                let ratio = if total == 0 { 1.0 } else { (self.current as f64 / total as f64).min(1.0) };
+                let filled = (ratio * BAR_WIDTH as f64) as usize;
+                let eta = self.eta().map_or_else(|| "--".to_string(), format_duration);
+                format!(
+                    "{} [{}{}] {} {:>3}% {} {}",
+                    self.label,
+                    "#".repeat(filled),
+                    "-".repeat(BAR_WIDTH - filled),
+                    self.count_text(),
+                    (ratio * 100.0) as u32,
+                    tr(Msg::ProgressRemaining),
+                    eta
+                )
+            }
+            None => {
+                self.spinner_frame = (self.spinner_frame + 1) % SPINNER.len();
+                format!("{} {} {}", self.label, SPINNER[self.spinner_frame], self.count_text())
+            }
+        };
+
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "\r\x1b[2K{}", line);
+        let _ = stderr.flush();
+    }
+
+    fn count_text(&self) -> String {
+        match self.total {
+            Some(total) => format!("{}/{}", self.current, total),
+            None => self.current.to_string(),
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        // 未显式结束时清理状态行，避免残留在终端上
+        if !self.finished && !self.quiet && self.interactive && self.last_draw.is_some() {
+            let _ = write!(io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+/// 格式化剩余时间
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        trf(Msg::DurationHours, &[&(secs / 3600), &format!("{:02}", secs % 3600 / 60)])
+    } else if secs >= 60 {
+        trf(Msg::DurationMinutes, &[&(secs / 60), &format!("{:02}", secs % 60)])
+    } else {
+        trf(Msg::DurationSeconds, &[&secs])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_and_eta() {
+        let mut bar = Progress::bar("测试", 10).quiet(true);
+        assert_eq!(bar.eta(), None);
+        bar.inc(3);
+        bar.inc(2);
+        assert_eq!(bar.position(), 5);
+        assert_eq!(bar.count_text(), "5/10");
+        assert!(bar.eta().is_some());
+        bar.set(20);
+        assert_eq!(bar.eta(), Some(Duration::ZERO));
+
+        let mut spinner = Progress::spinner("测试").quiet(true);
+        spinner.inc(7);
+        assert_eq!((spinner.count_text(), spinner.eta()), ("7".to_string(), None));
+    }
+
+    #[test]
+    fn test_finish_only_once() {
+        let mut bar = Progress::bar("测试", 1).quiet(true);
+        bar.finish();
+        assert!(bar.finished);
+        bar.finish_with_message("再次");
+        assert!(bar.finished);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(42)), "42秒");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2分05秒");
+        assert_eq!(format_duration(Duration::from_secs(3 * 3600 + 7 * 60 + 9)), "3时07分");
+    }
+}
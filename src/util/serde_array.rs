@@ -0,0 +1,19 @@
+//! 定长字节数组的serde支持
+//!
+//! serde只为不超过32个元素的数组实现了派生，更长的数组用
+//! `#[serde(with = "crate::util::serde_array")]` 按字节串处理
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(bytes)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error> {
+    let bytes = Vec::<u8>::deserialize(deserializer)?;
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| D::Error::invalid_length(len, &format!("{} bytes", N).as_str()))
+}
@@ -0,0 +1,158 @@
+//! 公共API稳定性检查
+//!
+//! 锁定被广泛重新导出的核心类型（`GameBoy`、`Config`、`Error`、`EntropyManager`）的公共接口：
+//! 方法签名用函数指针强制转换固定，trait实现用泛型约束固定，枚举变体用不带通配符的 `match` 固定。
+//! 这些断言在编译期生效，改名、改签名、删除trait实现或增加错误变体都会让本文件无法编译。
+//! 确实需要破坏性修改时，在提升主版本号的同时更新这里的断言。
+
+use std::error::Error as StdError;
+use std::fmt::{Debug, Display};
+use std::path::Path;
+
+use gameboy_emulator::config::{ConfigError, ConfigSource};
+use gameboy_emulator::cpu::OpcodeStats;
+use gameboy_emulator::emulator::gameboy::CPUState;
+use gameboy_emulator::emulator::CyclesReport;
+use gameboy_emulator::error::{self, CpuError, GameError, GpuError, InstructionError, MemoryError};
+use gameboy_emulator::frontend::Emulator;
+use gameboy_emulator::{Config, Error, GameBoy, Result, SaveState};
+
+fn implements_debug_default<T: Debug + Default>() {}
+fn implements_clone<T: Clone>() {}
+fn implements_error<T: StdError + Display + Debug + Send + Sync + 'static>() {}
+fn implements_send<T: Send>() {}
+
+#[test]
+fn test_gameboy_surface() {
+    implements_debug_default::<GameBoy>();
+    implements_send::<GameBoy>();
+
+    let _: fn() -> GameBoy = GameBoy::new;
+    let _: fn(&mut GameBoy, u16, &[u8]) = GameBoy::load_program;
+    let _: fn(&mut GameBoy) -> std::result::Result<(), String> = GameBoy::step;
+    let _: fn(&mut GameBoy, usize) -> std::result::Result<(), String> = GameBoy::run_steps;
+    let _: fn(&mut GameBoy, u64) -> std::result::Result<CyclesReport, String> = GameBoy::run_cycles;
+    let _: fn(&GameBoy) -> CPUState = GameBoy::get_cpu_state;
+    let _: fn(&GameBoy) -> &[u8] = GameBoy::memory;
+    let _: fn(&GameBoy) -> u64 = GameBoy::cycles;
+    let _: fn(&mut GameBoy) = GameBoy::enable_opcode_stats;
+    let _: fn(&GameBoy) -> Option<&OpcodeStats> = GameBoy::opcode_stats;
+    let _: fn(&GameBoy) -> SaveState = GameBoy::save_state;
+    let _: fn(&mut GameBoy, &SaveState) -> std::result::Result<(), String> = GameBoy::load_state;
+
+    // 前端只通过 `Emulator` 使用核心
+    let mut gameboy = GameBoy::new();
+    let emulator: &mut dyn Emulator = &mut gameboy;
+    emulator.step().unwrap();
+
+    let CPUState { registers: _, pc, sp, flags: _ } = GameBoy::new().get_cpu_state();
+    assert_eq!((pc, sp), (0x100, 0xFFFE));
+}
+
+#[test]
+fn test_config_surface() {
+    implements_debug_default::<Config>();
+    implements_clone::<Config>();
+    implements_send::<Config>();
+    implements_error::<ConfigError>();
+
+    let _: fn() -> Config = Config::new;
+    let _: fn() -> Config = Config::with_defaults;
+    let _: fn(Option<&Path>, &[(String, String)]) -> std::result::Result<Config, ConfigError> = Config::load;
+    let _: fn(&'static str) -> std::result::Result<Config, ConfigError> = Config::from_file::<&'static str>;
+    let _: fn() -> Config = Config::from_env;
+    let _: fn(&mut Config, &str, &str) = Config::set;
+    let _: fn(&mut Config, &str, &str, ConfigSource) -> bool = Config::set_from;
+    let _: for<'a> fn(&'a Config, &str) -> Option<&'a String> = Config::get;
+    let _: for<'a> fn(&'a Config, &str) -> Option<&'a ConfigSource> = Config::provenance;
+    let _: fn(&Config, &str) -> std::result::Result<u32, ConfigError> = Config::parse::<u32>;
+    let _: fn(&Config) -> std::result::Result<(), ConfigError> = Config::validate;
+    let _: fn(&Config, &str, &str) -> String = Config::get_or_default;
+    let _: fn(&Config, &str) -> Option<bool> = Config::get_bool;
+    let _: fn(&Config, &str) -> Option<i32> = Config::get_int;
+    let _: fn(&Config, &str) -> Option<f64> = Config::get_float;
+    let _: fn(&mut Config, &Config) = Config::merge;
+    let _: fn(&Config, &str) -> bool = Config::contains_key;
+
+    let mut config = Config::from_vars(vec![("GAMEBOY_SCALE".to_string(), "3".to_string())]);
+    config.set("custom", "1");
+    assert_eq!(config.keys().filter(|key| key.as_str() == "custom").count(), 1);
+
+    match ConfigError::MissingKey(String::new()) {
+        ConfigError::FileRead(_)
+        | ConfigError::ParseError(_)
+        | ConfigError::MissingKey(_)
+        | ConfigError::InvalidValue { key: _, value: _, source: _, message: _ } => {}
+    }
+    match ConfigSource::Cli {
+        ConfigSource::Default | ConfigSource::File { path: _, line: _ } | ConfigSource::Env(_) | ConfigSource::Cli => {}
+    }
+}
+
+#[test]
+fn test_error_surface() {
+    implements_error::<Error>();
+    implements_error::<CpuError>();
+    implements_error::<MemoryError>();
+    implements_error::<GpuError>();
+    implements_error::<InstructionError>();
+    implements_error::<error::EntropyError>();
+    implements_error::<GameError>();
+    implements_error::<error::ConfigError>();
+
+    let _: fn(std::io::Error) -> Error = Error::from;
+    let _: fn(CpuError) -> Error = Error::from;
+    let _: fn(MemoryError) -> Error = Error::from;
+    let _: fn(GpuError) -> Error = Error::from;
+    let _: fn(InstructionError) -> Error = Error::from;
+    let _: fn(error::EntropyError) -> Error = Error::from;
+    let _: fn(GameError) -> Error = Error::from;
+    let _: fn(error::ConfigError) -> Error = Error::from;
+
+    let result: Result<()> = Err(Error::Generic("x".to_string()));
+    match result.unwrap_err() {
+        Error::Cpu(_)
+        | Error::Memory(_)
+        | Error::Gpu(_)
+        | Error::Instruction(_)
+        | Error::Entropy(_)
+        | Error::Game(_)
+        | Error::Config(_)
+        | Error::Io(_)
+        | Error::Generic(_) => {}
+    }
+    // 只有包装I/O错误的变体提供 `source`
+    let io = || std::io::Error::other("x");
+    assert!(Error::from(io()).source().is_some());
+    assert!(Error::from(error::ConfigError::FileRead(io())).source().is_some());
+    assert!(Error::from(CpuError::InvalidAddress(0x1234)).source().is_none());
+}
+
+#[cfg(feature = "entropy")]
+#[test]
+fn test_entropy_manager_surface() {
+    use gameboy_emulator::entropy::{ConditioningMode, StageConfig};
+    use gameboy_emulator::{EntropyError, EntropyManager, EntropyStats};
+
+    implements_error::<EntropyError>();
+    implements_clone::<EntropyStats>();
+
+    let _: fn() -> EntropyManager = EntropyManager::new;
+    let _: fn(&mut EntropyManager) -> std::result::Result<Vec<u8>, EntropyError> = EntropyManager::collect_and_optimize;
+    let _: fn(&mut EntropyManager, usize) -> std::result::Result<Vec<u8>, EntropyError> = EntropyManager::generate_random;
+    let _: fn(&mut EntropyManager, StageConfig) = EntropyManager::set_quantum_stages;
+    let _: fn(&EntropyManager) -> StageConfig = EntropyManager::quantum_stages;
+    let _: fn(&mut EntropyManager, ConditioningMode) = EntropyManager::set_conditioning;
+    let _: fn(&EntropyManager) -> ConditioningMode = EntropyManager::conditioning;
+    let _: fn(&EntropyManager) -> EntropyStats = EntropyManager::get_entropy_stats;
+
+    let EntropyStats { source_count, pool_size: _, optimizer_stats: _, quantum_stats: _ } = EntropyManager::new().get_entropy_stats();
+    assert!(source_count > 0);
+
+    match EntropyError::InsufficientEntropy {
+        EntropyError::SourceUnavailable(_)
+        | EntropyError::InsufficientEntropy
+        | EntropyError::DistributionError(_)
+        | EntropyError::QuantumProcessingError(_) => {}
+    }
+}
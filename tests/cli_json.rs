@@ -0,0 +1,35 @@
+//! 命令行 `--json` 输出检查
+//!
+//! 运行 `gamelife` 二进制，确认标准输出只有一个可解析的JSON值，没有混入日志。
+
+#![cfg(feature = "frontends")]
+
+use std::process::Command;
+
+use gameboy_emulator::util::Json;
+
+fn gamelife_json(args: &[&str]) -> Json {
+    let output = Command::new(env!("CARGO_BIN_EXE_gamelife")).arg("--json").args(args).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    Json::parse(&stdout).unwrap_or_else(|e| panic!("{}: {}\n{}", args.join(" "), e, stdout))
+}
+
+#[test]
+fn test_selftest_json_parses() {
+    let report = gamelife_json(&["selftest", "--group", "rom"]);
+    assert_eq!(report.get("failed"), Some(&Json::Int(0)));
+    match report.get("checks") {
+        Some(Json::Array(checks)) => assert!(checks.iter().any(|check| check.get("name") == Some(&Json::from("gb-program")))),
+        other => panic!("checks: {:?}", other),
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+#[test]
+fn test_selftest_json_reports_allocations() {
+    let report = gamelife_json(&["selftest", "--group", "rom"]);
+    let allocations = report.get("allocations").expect("allocations");
+    assert_eq!(allocations.get("checks"), Some(&Json::Int(3)));
+    let cpu = allocations.get("per_unit").and_then(|per_unit| per_unit.get("cpu")).expect("cpu");
+    assert!(cpu.get("allocations").is_some() && cpu.get("bytes").is_some());
+}
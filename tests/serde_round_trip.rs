@@ -0,0 +1,95 @@
+//! serde往返检查
+//!
+//! 开启 `serde` 功能时，公开的状态结构序列化为JSON再读回后应与原值一致。
+//! 没有实现 `PartialEq` 的结构比较两次序列化的结果。
+
+#![cfg(feature = "serde")]
+
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use gameboy_emulator::cpu::{FlagsRegister, Registers};
+use gameboy_emulator::emulator::{SaveMetadata, Thumbnail};
+use gameboy_emulator::rom::{RomGenerator, RomHeader};
+use gameboy_emulator::GameBoy;
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("无法读回 {}: {}", json, e))
+}
+
+/// 读回后再序列化，与第一次的结果相同
+fn assert_stable<T: Serialize + DeserializeOwned>(value: &T) {
+    let json = serde_json::to_value(value).unwrap();
+    assert_eq!(serde_json::to_value(round_trip(value)).unwrap(), json);
+}
+
+#[test]
+fn test_savestate_round_trip() {
+    let mut gameboy = GameBoy::new();
+    gameboy.load_program(0x100, &[0x3E, 0x42, 0x0C, 0x80]);
+    gameboy.run_steps(3).unwrap();
+
+    let mut state = gameboy.save_state();
+    state.metadata = SaveMetadata {
+        rom_title: "SERDE".to_string(),
+        rom_checksum: 0xBEEF,
+        saved_at: 1_700_000_000,
+        play_time: Duration::from_millis(90_500),
+        thumbnail: Some(Thumbnail { width: 2, height: 1, pixels: vec![0, 1, 2, 253, 254, 255] }),
+    };
+    assert_eq!(round_trip(&state), state);
+
+    let cpu = gameboy.get_cpu_state();
+    assert_stable(&cpu);
+    let mut flags = FlagsRegister::new();
+    flags.half_carry = true;
+    assert_eq!(round_trip(&flags), flags);
+    assert_eq!(round_trip(&Registers::new()), Registers::new());
+}
+
+#[test]
+fn test_rom_header_round_trip() {
+    let rom = RomGenerator::new("SERDE").program(0x150, &[0x00]).build().unwrap();
+    let header = RomHeader::parse(&rom).unwrap();
+    assert_eq!(round_trip(&header), header);
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn test_debugger_cpu_state_round_trip() {
+    use gameboy_emulator::cpu::CPU;
+    use gameboy_emulator::emulator::AdvancedGameBoy;
+    use gameboy_emulator::memory::MemoryBus;
+
+    let mut gameboy = AdvancedGameBoy::new();
+    gameboy.load_program(0x100, &[0x0C]).unwrap();
+    gameboy.run_steps(1).unwrap();
+    assert_stable(&gameboy.get_cpu_state());
+    assert_stable(&gameboy.debugger.get_cpu_state(&CPU::new(MemoryBus::new())));
+}
+
+#[cfg(feature = "games")]
+#[test]
+fn test_tetris_stats_round_trip() {
+    use gameboy_emulator::games::tetris::tetris_game::TetrisGame;
+
+    let mut game = TetrisGame::new();
+    game.stats.score = 1200;
+    game.stats.lines_cleared = 4;
+    game.stats.play_time = Duration::from_secs(75);
+
+    // 计时起点不参与序列化
+    let stats = round_trip(game.get_stats());
+    assert_eq!((stats.score, stats.lines_cleared, stats.play_time), (1200, 4, Duration::from_secs(75)));
+}
+
+#[cfg(feature = "entropy")]
+#[test]
+fn test_entropy_stats_round_trip() {
+    use gameboy_emulator::EntropyManager;
+
+    assert_stable(&EntropyManager::new().get_entropy_stats());
+}